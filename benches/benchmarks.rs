@@ -0,0 +1,98 @@
+//! Benchmarks for each stage of deserializing arguments.
+//!
+//! See the "Performance" section of `ARCHITECTURE.md` for the recorded baseline.
+
+#[path = "../tests/git/mod.rs"]
+mod git;
+
+use criterion::{
+    criterion_group,
+    criterion_main,
+    Criterion,
+};
+use git::Git;
+use serde_derive::Deserialize;
+use std::hint::black_box;
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Small {
+    path: String,
+    count: u64,
+    force: bool,
+}
+
+/// Defines an enum with a struct variant for each given name, each containing the same fields.
+macro_rules! large {
+    ($($variant:ident),*) => {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        enum Large {
+            $($variant {
+                input: String,
+                output: Option<String>,
+                jobs: Option<u32>,
+                verbose: bool,
+                force: bool,
+            },)*
+        }
+    };
+}
+
+large!(
+    Alpha, Bravo, Charlie, Delta, Echo, Foxtrot, Golf, Hotel, India, Juliett, Kilo, Lima, Mike,
+    November, Oscar, Papa, Quebec, Romeo, Sierra, Tango, Uniform, Victor, Whiskey, Xray, Yankee,
+    Zulu
+);
+
+fn trace(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("trace");
+    group.bench_function("small", |bencher| {
+        bencher.iter(|| serde_args::compile::<Small>().unwrap())
+    });
+    group.bench_function("medium", |bencher| {
+        bencher.iter(|| serde_args::compile::<Git>().unwrap())
+    });
+    group.bench_function("large", |bencher| {
+        bencher.iter(|| serde_args::compile::<Large>().unwrap())
+    });
+    group.finish();
+}
+
+fn parse(criterion: &mut Criterion) {
+    let compiled = serde_args::compile::<Git>().unwrap();
+
+    let mut group = criterion.benchmark_group("parse");
+    group.bench_function("short", |bencher| {
+        bencher.iter(|| compiled.parse(black_box(git::SHORT)).unwrap())
+    });
+    group.bench_function("long", |bencher| {
+        bencher.iter(|| compiled.parse(black_box(git::LONG)).unwrap())
+    });
+    group.finish();
+}
+
+fn help(criterion: &mut Criterion) {
+    let error = serde_args::from_slice::<Git>(git::HELP).unwrap_err();
+
+    criterion.bench_function("help", |bencher| {
+        bencher.iter(|| black_box(&error).to_string())
+    });
+}
+
+fn error(criterion: &mut Criterion) {
+    let compiled = serde_args::compile::<Git>().unwrap();
+
+    criterion.bench_function("error", |bencher| {
+        bencher.iter(|| compiled.parse(black_box(git::INVALID)).unwrap_err())
+    });
+}
+
+fn end_to_end(criterion: &mut Criterion) {
+    criterion.bench_function("end_to_end", |bencher| {
+        bencher.iter(|| serde_args::from_slice::<Git>(black_box(git::LONG)).unwrap())
+    });
+}
+
+criterion_group!(benches, trace, parse, help, error, end_to_end);
+criterion_main!(benches);
@@ -6,6 +6,7 @@
 use proc_macro2::{
     Delimiter,
     Group,
+    Literal,
     Spacing,
     Span,
     TokenStream,
@@ -14,6 +15,7 @@ use proc_macro2::{
 use quote::ToTokens;
 use std::iter;
 use syn::{
+    parse2 as parse,
     token::{
         Bracket,
         Paren,
@@ -21,6 +23,7 @@ use syn::{
     AttrStyle,
     Attribute,
     Ident,
+    LitStr,
     MacroDelimiter,
     Meta,
     MetaList,
@@ -137,9 +140,60 @@ pub(crate) fn remove_serde_attribute(attrs: &mut Vec<Attribute>, name: &str) {
     }
 }
 
+/// Replace a leading `Self` in the paths provided to the named `serde` attributes.
+///
+/// Paths such as `skip_serializing_if = "Self::is_empty"` are resolved relative to the type the
+/// attribute is defined on. When the attributes are copied onto a generated type, `Self` must be
+/// replaced with the original type for the path to still resolve.
+pub(crate) fn replace_self_in_serde_attributes(
+    attrs: &mut [Attribute],
+    names: &[&str],
+    replacement: &str,
+) {
+    for attribute in attrs {
+        if let Meta::List(list) = &mut attribute.meta {
+            if !list.path.is_ident("serde") {
+                continue;
+            }
+            let mut tokens: Vec<_> = list.tokens.clone().into_iter().collect();
+            for index in 2..tokens.len() {
+                let replaced = match (&tokens[index - 2], &tokens[index - 1], &tokens[index]) {
+                    (
+                        TokenTree::Ident(ident),
+                        TokenTree::Punct(punctuation),
+                        TokenTree::Literal(literal),
+                    ) if names.iter().any(|name| ident == name)
+                        && punctuation.as_char() == '='
+                        && punctuation.spacing() == Spacing::Alone =>
+                    {
+                        parse::<LitStr>(TokenTree::Literal(literal.clone()).into())
+                            .ok()
+                            .and_then(|path| {
+                                path.value().strip_prefix("Self::").map(|rest| {
+                                    let mut replaced =
+                                        Literal::string(&format!("{}::{}", replacement, rest));
+                                    replaced.set_span(literal.span());
+                                    replaced
+                                })
+                            })
+                    }
+                    _ => None,
+                };
+                if let Some(replaced) = replaced {
+                    tokens[index] = TokenTree::Literal(replaced);
+                }
+            }
+            list.tokens = tokens.into_iter().collect();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::push_serde_attribute;
+    use super::{
+        push_serde_attribute,
+        replace_self_in_serde_attributes,
+    };
     use crate::test::OuterAttributes;
     use claims::assert_ok;
     use proc_macro2::{
@@ -186,4 +240,58 @@ mod tests {
             assert_ok!(parse_str::<OuterAttributes>("#[foo] #[bar] #[serde(foo)]")).0
         );
     }
+
+    #[test]
+    fn replace_self_in_serde_attributes_leading_self() {
+        let mut attributes = assert_ok!(parse_str::<OuterAttributes>(
+            "#[serde(default, skip_serializing_if = \"Self::is_empty\")]"
+        ))
+        .0;
+
+        replace_self_in_serde_attributes(&mut attributes, &["skip_serializing_if"], "<Foo>");
+
+        assert_eq!(
+            attributes,
+            assert_ok!(parse_str::<OuterAttributes>(
+                "#[serde(default, skip_serializing_if = \"<Foo>::is_empty\")]"
+            ))
+            .0
+        );
+    }
+
+    #[test]
+    fn replace_self_in_serde_attributes_no_self() {
+        let mut attributes = assert_ok!(parse_str::<OuterAttributes>(
+            "#[serde(skip_serializing_if = \"Option::is_none\")] #[foo(skip_serializing_if = \"Self::is_empty\")]"
+        ))
+        .0;
+
+        replace_self_in_serde_attributes(&mut attributes, &["skip_serializing_if"], "<Foo>");
+
+        assert_eq!(
+            attributes,
+            assert_ok!(parse_str::<OuterAttributes>(
+                "#[serde(skip_serializing_if = \"Option::is_none\")] #[foo(skip_serializing_if = \"Self::is_empty\")]"
+            ))
+            .0
+        );
+    }
+
+    #[test]
+    fn replace_self_in_serde_attributes_other_attribute() {
+        let mut attributes = assert_ok!(parse_str::<OuterAttributes>(
+            "#[serde(deserialize_with = \"Self::deserialize_foo\")]"
+        ))
+        .0;
+
+        replace_self_in_serde_attributes(&mut attributes, &["skip_serializing_if"], "<Foo>");
+
+        assert_eq!(
+            attributes,
+            assert_ok!(parse_str::<OuterAttributes>(
+                "#[serde(deserialize_with = \"Self::deserialize_foo\")]"
+            ))
+            .0
+        );
+    }
 }
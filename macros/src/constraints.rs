@@ -0,0 +1,260 @@
+//! Constraints on which options of a struct may be given together.
+//!
+//! Constraints are declared using `generate` parameters such as `one_of(stdin, file)`, naming the
+//! struct's fields. They are communicated to `serde_args` through the settings record, with each
+//! field identified by its index.
+
+use crate::{
+    record::Entry,
+    Container,
+};
+use syn::Ident;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ConstraintKind {
+    OneOf,
+    AtMostOne,
+    AllOrNone,
+}
+
+impl ConstraintKind {
+    /// Returns the kind named by `ident`, if any.
+    pub(crate) fn from_ident(ident: &Ident) -> Option<Self> {
+        if ident == "one_of" {
+            Some(Self::OneOf)
+        } else if ident == "at_most_one" {
+            Some(Self::AtMostOne)
+        } else if ident == "all_or_none" {
+            Some(Self::AllOrNone)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::OneOf => "one_of",
+            Self::AtMostOne => "at_most_one",
+            Self::AllOrNone => "all_or_none",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct Constraint {
+    pub(crate) kind: ConstraintKind,
+    /// The parameter's identifier, used to report errors.
+    pub(crate) ident: Ident,
+    pub(crate) fields: Vec<Ident>,
+}
+
+/// Returns the record entries providing the container's constraints, in the order they were given.
+///
+/// Constraints are only supported on structs, and must name at least two distinct fields of the
+/// struct.
+pub(crate) fn entries(
+    container: &Container,
+    constraints: &[Constraint],
+) -> Result<Vec<Entry>, syn::Error> {
+    let first = match constraints.first() {
+        Some(first) => first,
+        None => return Ok(Vec::new()),
+    };
+    let item = match container {
+        Container::Struct(item) => item,
+        Container::Enum(_) => {
+            return Err(syn::Error::new_spanned(
+                &first.ident,
+                format!("`{}` is only supported on structs", first.kind.name()),
+            ));
+        }
+    };
+
+    let mut entries = Vec::with_capacity(constraints.len());
+    for constraint in constraints {
+        if constraint.fields.len() < 2 {
+            return Err(syn::Error::new_spanned(
+                &constraint.ident,
+                format!("`{}` must name at least two fields", constraint.kind.name()),
+            ));
+        }
+        let mut indices = Vec::with_capacity(constraint.fields.len());
+        for (position, field) in constraint.fields.iter().enumerate() {
+            if constraint.fields[..position].contains(field) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    format!("field `{}` is named more than once", field),
+                ));
+            }
+            match item
+                .fields
+                .iter()
+                .position(|item_field| item_field.ident.as_ref() == Some(field))
+            {
+                Some(index) => indices.push(index.to_string()),
+                None => {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        format!("no field named `{}`", field),
+                    ));
+                }
+            }
+        }
+        entries.push(Entry::new(constraint.kind.name(), None, &indices.join(",")));
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        entries,
+        Constraint,
+        ConstraintKind,
+    };
+    use crate::{
+        record::Entry,
+        Container,
+    };
+    use claims::{
+        assert_err,
+        assert_none,
+        assert_ok,
+        assert_some_eq,
+    };
+    use proc_macro2::Span;
+    use syn::{
+        parse_str,
+        Ident,
+    };
+
+    fn constraint(kind: ConstraintKind, fields: &[&str]) -> Constraint {
+        Constraint {
+            kind,
+            ident: Ident::new("constraint", Span::call_site()),
+            fields: fields
+                .iter()
+                .map(|field| Ident::new(field, Span::call_site()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn kind_from_ident() {
+        assert_some_eq!(
+            ConstraintKind::from_ident(&Ident::new("one_of", Span::call_site())),
+            ConstraintKind::OneOf
+        );
+        assert_some_eq!(
+            ConstraintKind::from_ident(&Ident::new("at_most_one", Span::call_site())),
+            ConstraintKind::AtMostOne
+        );
+        assert_some_eq!(
+            ConstraintKind::from_ident(&Ident::new("all_or_none", Span::call_site())),
+            ConstraintKind::AllOrNone
+        );
+        assert_none!(ConstraintKind::from_ident(&Ident::new(
+            "unknown",
+            Span::call_site()
+        )));
+    }
+
+    #[test]
+    fn entries_none() {
+        let container: Container = assert_ok!(parse_str("struct Foo { bar: bool, baz: bool }"));
+
+        assert_eq!(assert_ok!(entries(&container, &[])), vec![]);
+    }
+
+    #[test]
+    fn entries_constraints() {
+        let container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                input: String,
+                stdin: bool,
+                file: Option<String>,
+                user: Option<String>,
+                password: Option<String>,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(entries(
+                &container,
+                &[
+                    constraint(ConstraintKind::OneOf, &["stdin", "file"]),
+                    constraint(ConstraintKind::AllOrNone, &["user", "password"]),
+                ]
+            )),
+            vec![
+                Entry::new("one_of", None, "1,2"),
+                Entry::new("all_or_none", None, "3,4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_enum() {
+        let container: Container = assert_ok!(parse_str("enum Foo { Bar, Baz }"));
+
+        assert_eq!(
+            format!(
+                "{}",
+                assert_err!(entries(
+                    &container,
+                    &[constraint(ConstraintKind::AtMostOne, &["bar", "baz"])]
+                ))
+            ),
+            "`at_most_one` is only supported on structs"
+        );
+    }
+
+    #[test]
+    fn entries_single_field() {
+        let container: Container = assert_ok!(parse_str("struct Foo { bar: bool, baz: bool }"));
+
+        assert_eq!(
+            format!(
+                "{}",
+                assert_err!(entries(
+                    &container,
+                    &[constraint(ConstraintKind::OneOf, &["bar"])]
+                ))
+            ),
+            "`one_of` must name at least two fields"
+        );
+    }
+
+    #[test]
+    fn entries_duplicate_field() {
+        let container: Container = assert_ok!(parse_str("struct Foo { bar: bool, baz: bool }"));
+
+        assert_eq!(
+            format!(
+                "{}",
+                assert_err!(entries(
+                    &container,
+                    &[constraint(ConstraintKind::OneOf, &["bar", "bar"])]
+                ))
+            ),
+            "field `bar` is named more than once"
+        );
+    }
+
+    #[test]
+    fn entries_unknown_field() {
+        let container: Container = assert_ok!(parse_str("struct Foo { bar: bool, baz: bool }"));
+
+        assert_eq!(
+            format!(
+                "{}",
+                assert_err!(entries(
+                    &container,
+                    &[constraint(ConstraintKind::AllOrNone, &["bar", "qux"])]
+                ))
+            ),
+            "no field named `qux`"
+        );
+    }
+}
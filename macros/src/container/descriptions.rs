@@ -1,3 +1,4 @@
+use super::markdown;
 use syn::{
     Attribute,
     Expr,
@@ -19,7 +20,15 @@ impl From<&Vec<Attribute>> for Documentation {
                     if *ident == "doc" {
                         if let Expr::Lit(literal) = &name_value.value {
                             if let Lit::Str(string) = &literal.lit {
-                                lines.push(string.value().trim().to_owned());
+                                // Only the space following `///` is removed, so that the
+                                // indentation of examples is preserved.
+                                let line = string.value();
+                                lines.push(
+                                    line.strip_prefix(' ')
+                                        .unwrap_or(&line)
+                                        .trim_end()
+                                        .to_owned(),
+                                );
                             }
                         }
                     }
@@ -30,10 +39,67 @@ impl From<&Vec<Attribute>> for Documentation {
     }
 }
 
+impl Documentation {
+    /// Renders the documentation as plain text for display in help messages.
+    pub(crate) fn render(&self) -> String {
+        markdown::render(&self.lines)
+    }
+
+    /// Remove a leading `[Group]` tag line, returning the group name if present.
+    pub(crate) fn take_group(&mut self) -> Option<String> {
+        let group = self
+            .lines
+            .first()?
+            .trim()
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .trim()
+            .to_owned();
+        if group.is_empty() {
+            return None;
+        }
+        self.lines.remove(0);
+        Some(group)
+    }
+
+    /// Remove an `# Examples` section and everything after it, returning its lines if present.
+    ///
+    /// The examples are kept as written, with only code fences removed, so that they can be
+    /// displayed verbatim.
+    pub(crate) fn take_examples(&mut self) -> Option<String> {
+        let mut in_code_block = false;
+        let index = self.lines.iter().position(|line| {
+            let line = line.trim();
+            if markdown::is_fence(line) {
+                in_code_block = !in_code_block;
+            }
+            !in_code_block && markdown::heading(line) == Some("Examples")
+        })?;
+        let section = self.lines.split_off(index);
+        let examples = section[1..]
+            .iter()
+            .filter(|line| !markdown::is_fence(line.trim()))
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("\n")
+            .trim_matches('\n')
+            .to_owned();
+        if examples.is_empty() {
+            None
+        } else {
+            Some(examples)
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) struct Descriptions {
     pub(crate) container: Documentation,
     pub(crate) keys: Vec<Documentation>,
+    /// Help groups for each key. Only populated for enum variants.
+    pub(crate) groups: Vec<Option<String>>,
+    /// Examples taken from the container's documentation.
+    pub(crate) examples: Option<String>,
 }
 
 #[cfg(test)]
@@ -108,4 +174,137 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn documentation_from_attributes_indented() {
+        assert_eq!(
+            Documentation::from(
+                &assert_ok!(parse_str::<OuterAttributes>(
+                    "#[doc = \" foo\"] #[doc = \"   bar  \"]"
+                ))
+                .0
+            ),
+            Documentation {
+                lines: vec!["foo".into(), "  bar".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn documentation_take_group() {
+        let mut documentation = Documentation {
+            lines: vec!["[Repository]".into(), "foo bar baz".into()],
+        };
+
+        assert_eq!(documentation.take_group(), Some("Repository".into()));
+        assert_eq!(
+            documentation,
+            Documentation {
+                lines: vec!["foo bar baz".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn documentation_take_group_none() {
+        let mut documentation = Documentation {
+            lines: vec!["foo [bar] baz".into()],
+        };
+
+        assert_eq!(documentation.take_group(), None);
+        assert_eq!(
+            documentation,
+            Documentation {
+                lines: vec!["foo [bar] baz".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn documentation_take_group_empty() {
+        let mut documentation = Documentation {
+            lines: vec!["[]".into()],
+        };
+
+        assert_eq!(documentation.take_group(), None);
+        assert_eq!(
+            documentation,
+            Documentation {
+                lines: vec!["[]".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn documentation_take_examples() {
+        let mut documentation = Documentation {
+            lines: vec![
+                "foo bar baz".into(),
+                "".into(),
+                "# Examples".into(),
+                "".into(),
+                "```text".into(),
+                "$ foo --bar".into(),
+                "".into(),
+                "$ foo --baz".into(),
+                "```".into(),
+            ],
+        };
+
+        assert_eq!(
+            documentation.take_examples(),
+            Some("$ foo --bar\n\n$ foo --baz".into())
+        );
+        assert_eq!(
+            documentation,
+            Documentation {
+                lines: vec!["foo bar baz".into(), "".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn documentation_take_examples_none() {
+        let mut documentation = Documentation {
+            lines: vec!["foo bar baz".into(), "# Usage".into(), "qux".into()],
+        };
+
+        assert_eq!(documentation.take_examples(), None);
+        assert_eq!(
+            documentation,
+            Documentation {
+                lines: vec!["foo bar baz".into(), "# Usage".into(), "qux".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn documentation_take_examples_in_code_block() {
+        let mut documentation = Documentation {
+            lines: vec!["```sh".into(), "# Examples".into(), "```".into()],
+        };
+
+        assert_eq!(documentation.take_examples(), None);
+        assert_eq!(
+            documentation,
+            Documentation {
+                lines: vec!["```sh".into(), "# Examples".into(), "```".into()]
+            }
+        );
+    }
+
+    #[test]
+    fn documentation_take_examples_empty() {
+        let mut documentation = Documentation {
+            lines: vec!["foo bar baz".into(), "## Examples".into(), "".into()],
+        };
+
+        assert_eq!(documentation.take_examples(), None);
+        assert_eq!(
+            documentation,
+            Documentation {
+                lines: vec!["foo bar baz".into()]
+            }
+        );
+    }
 }
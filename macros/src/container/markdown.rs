@@ -0,0 +1,330 @@
+//! Rendering of Markdown doc comments as plain help text.
+
+/// Renders the lines of a doc comment as plain text.
+///
+/// Hard-wrapped lines are joined into paragraphs, which remain separated by blank lines. Inline
+/// code and emphasis markers are stripped, list items are placed on their own indented lines, and
+/// code blocks are dropped entirely.
+pub(super) fn render(lines: &[String]) -> String {
+    let mut rendered = String::new();
+    // The block currently being joined, along with the separator to place before it.
+    let mut current: Option<(&str, String)> = None;
+    let mut previous = Previous::None;
+    let mut in_code_block = false;
+
+    for line in lines {
+        let line = line.trim();
+        if is_fence(line) {
+            flush(&mut rendered, &mut current);
+            if !in_code_block && previous != Previous::None {
+                previous = Previous::Break;
+            }
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if line.is_empty() {
+            flush(&mut rendered, &mut current);
+            if previous != Previous::None {
+                previous = Previous::Break;
+            }
+        } else if let Some(item) = list_item(line) {
+            flush(&mut rendered, &mut current);
+            let separator = match previous {
+                Previous::None => "",
+                Previous::Item | Previous::Paragraph => "\n",
+                Previous::Break => "\n\n",
+            };
+            current = Some((separator, format!("  {item}")));
+            previous = Previous::Item;
+        } else if let Some(heading) = heading(line) {
+            flush(&mut rendered, &mut current);
+            let separator = match previous {
+                Previous::None => "",
+                _ => "\n\n",
+            };
+            current = Some((separator, heading.to_owned()));
+            flush(&mut rendered, &mut current);
+            previous = Previous::Break;
+        } else if let Some((_, text)) = &mut current {
+            text.push(' ');
+            text.push_str(line);
+        } else {
+            let separator = match previous {
+                Previous::None => "",
+                Previous::Item => "\n",
+                Previous::Paragraph | Previous::Break => "\n\n",
+            };
+            current = Some((separator, line.to_owned()));
+            previous = Previous::Paragraph;
+        }
+    }
+    flush(&mut rendered, &mut current);
+
+    rendered
+}
+
+/// What preceded the line being rendered.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Previous {
+    None,
+    Paragraph,
+    Item,
+    /// A paragraph break, such as a blank line or a code block.
+    Break,
+}
+
+fn flush(rendered: &mut String, current: &mut Option<(&str, String)>) {
+    if let Some((separator, text)) = current.take() {
+        rendered.push_str(separator);
+        rendered.push_str(&strip_inline(&text));
+    }
+}
+
+/// Returns the list item on this line, with any bullet replaced by `-`.
+///
+/// Numbered items keep their numbers.
+fn list_item(line: &str) -> Option<String> {
+    for bullet in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return Some(format!("- {}", item.trim_start()));
+        }
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(item) = line[digits..].strip_prefix(". ") {
+            return Some(format!("{}. {}", &line[..digits], item.trim_start()));
+        }
+    }
+    None
+}
+
+/// Returns whether this line opens or closes a code block.
+pub(super) fn is_fence(line: &str) -> bool {
+    line.starts_with("```") || line.starts_with("~~~")
+}
+
+/// Returns the text of the heading on this line, without its `#` markers.
+pub(super) fn heading(line: &str) -> Option<&str> {
+    let markers = line.chars().take_while(|c| *c == '#').count();
+    if markers == 0 {
+        return None;
+    }
+    line[markers..]
+        .strip_prefix(' ')
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+/// Strips inline code and emphasis markers from `text`.
+///
+/// The contents of inline code are kept verbatim, while emphasis markers are only stripped from
+/// the edges of words so that identifiers such as `snake_case` are left intact.
+fn strip_inline(text: &str) -> String {
+    let mut stripped = String::new();
+    for (index, span) in text.split('`').enumerate() {
+        if index % 2 == 1 {
+            stripped.push_str(span);
+        } else {
+            let mut words = span.split(' ');
+            if let Some(word) = words.next() {
+                stripped.push_str(&strip_emphasis(word));
+            }
+            for word in words {
+                stripped.push(' ');
+                stripped.push_str(&strip_emphasis(word));
+            }
+        }
+    }
+    stripped
+}
+
+fn is_marker(c: char) -> bool {
+    c == '*' || c == '_'
+}
+
+/// Strips emphasis markers from the start and end of a single word, keeping any punctuation
+/// around them.
+fn strip_emphasis(word: &str) -> String {
+    // Words made up entirely of markers, such as a lone `*`, are not emphasis.
+    if word.chars().all(is_marker) {
+        return word.to_owned();
+    }
+    let opening = word
+        .find(|c: char| !"([\"'".contains(c))
+        .unwrap_or(word.len());
+    let (prefix, rest) = word.split_at(opening);
+    let rest = rest.trim_start_matches(is_marker);
+    let closing = rest
+        .rfind(|c: char| !".,;:!?)]\"'".contains(c))
+        .map_or(0, |index| {
+            index + rest[index..].chars().next().map_or(0, char::len_utf8)
+        });
+    let (body, suffix) = rest.split_at(closing);
+    format!("{prefix}{}{suffix}", body.trim_end_matches(is_marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| (*line).to_owned()).collect()
+    }
+
+    #[test]
+    fn render_empty() {
+        assert_eq!(render(&[]), "");
+    }
+
+    #[test]
+    fn render_single_line() {
+        assert_eq!(render(&lines(&["Foo bar baz."])), "Foo bar baz.");
+    }
+
+    #[test]
+    fn render_joins_hard_wrapped_lines() {
+        assert_eq!(
+            render(&lines(&["The file to read the", "configuration from."])),
+            "The file to read the configuration from."
+        );
+    }
+
+    #[test]
+    fn render_paragraphs() {
+        assert_eq!(
+            render(&lines(&[
+                "First paragraph,",
+                "continued.",
+                "",
+                "Second paragraph.",
+            ])),
+            "First paragraph, continued.\n\nSecond paragraph."
+        );
+    }
+
+    #[test]
+    fn render_multiple_blank_lines() {
+        assert_eq!(
+            render(&lines(&["", "Foo.", "", "", "Bar.", ""])),
+            "Foo.\n\nBar."
+        );
+    }
+
+    #[test]
+    fn render_inline_code() {
+        assert_eq!(
+            render(&lines(&["Reads from `stdin` if `--input` is `-`."])),
+            "Reads from stdin if --input is -."
+        );
+    }
+
+    #[test]
+    fn render_inline_code_kept_verbatim() {
+        assert_eq!(
+            render(&lines(&["Calls `__init__` and `*ptr`."])),
+            "Calls __init__ and *ptr."
+        );
+    }
+
+    #[test]
+    fn render_emphasis() {
+        assert_eq!(
+            render(&lines(&[
+                "This is *very* important, **really** _quite_ __important__.",
+            ])),
+            "This is very important, really quite important."
+        );
+    }
+
+    #[test]
+    fn render_emphasis_across_words() {
+        assert_eq!(
+            render(&lines(&["*Do not* use (*ever*)."])),
+            "Do not use (ever)."
+        );
+    }
+
+    #[test]
+    fn render_keeps_identifiers() {
+        assert_eq!(
+            render(&lines(&["Sets snake_case names, and 2 * 3 is 6."])),
+            "Sets snake_case names, and 2 * 3 is 6."
+        );
+    }
+
+    #[test]
+    fn render_list() {
+        assert_eq!(
+            render(&lines(&[
+                "Supported formats:",
+                "- `json`",
+                "* yaml, which is",
+                "  hard-wrapped",
+                "+ toml",
+            ])),
+            "Supported formats:\n  - json\n  - yaml, which is hard-wrapped\n  - toml"
+        );
+    }
+
+    #[test]
+    fn render_list_after_blank_line() {
+        assert_eq!(
+            render(&lines(&[
+                "Supported formats:",
+                "",
+                "- json",
+                "- yaml",
+                "",
+                "Done."
+            ])),
+            "Supported formats:\n\n  - json\n  - yaml\n\nDone."
+        );
+    }
+
+    #[test]
+    fn render_numbered_list() {
+        assert_eq!(
+            render(&lines(&["Steps:", "1. Fetch.", "2. Build.", "10. Test."])),
+            "Steps:\n  1. Fetch.\n  2. Build.\n  10. Test."
+        );
+    }
+
+    #[test]
+    fn render_code_block_dropped() {
+        assert_eq!(
+            render(&lines(&[
+                "Example usage:",
+                "```",
+                "let x = 1;",
+                "```",
+                "More text.",
+            ])),
+            "Example usage:\n\nMore text."
+        );
+    }
+
+    #[test]
+    fn render_code_block_with_language_dropped() {
+        assert_eq!(
+            render(&lines(&["```rust", "# fn main() {}", "```", "Text."])),
+            "Text."
+        );
+    }
+
+    #[test]
+    fn render_heading() {
+        assert_eq!(
+            render(&lines(&["Foo.", "# Examples", "Bar."])),
+            "Foo.\n\nExamples\n\nBar."
+        );
+    }
+
+    #[test]
+    fn render_not_heading() {
+        assert_eq!(render(&lines(&["#hashtag"])), "#hashtag");
+    }
+}
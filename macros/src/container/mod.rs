@@ -1,4 +1,5 @@
 mod descriptions;
+mod markdown;
 
 pub(crate) use descriptions::{
     Descriptions,
@@ -20,6 +21,7 @@ use syn::{
     punctuated::Punctuated,
     AngleBracketedGenericArguments,
     Attribute,
+    Field,
     GenericArgument,
     GenericParam,
     Generics,
@@ -45,19 +47,29 @@ impl Container {
     pub(crate) fn descriptions(&self) -> Descriptions {
         match self {
             Container::Enum(item) => {
-                let container = Documentation::from(&item.attrs);
+                let mut container = Documentation::from(&item.attrs);
+                let examples = container.take_examples();
 
                 // Extract variant information.
                 let mut keys = vec![];
+                let mut groups = vec![];
                 for variant in &item.variants {
-                    keys.push(Documentation::from(&variant.attrs));
+                    let mut documentation = Documentation::from(&variant.attrs);
+                    groups.push(documentation.take_group());
+                    keys.push(documentation);
                 }
 
-                Descriptions { container, keys }
+                Descriptions {
+                    container,
+                    keys,
+                    groups,
+                    examples,
+                }
             }
             Container::Struct(item) => {
                 // Extract the container description from the struct's documentation.
-                let container = Documentation::from(&item.attrs);
+                let mut container = Documentation::from(&item.attrs);
+                let examples = container.take_examples();
 
                 // Extract field information.
                 let mut keys = vec![];
@@ -65,7 +77,12 @@ impl Container {
                     keys.push(Documentation::from(&field.attrs));
                 }
 
-                Descriptions { container, keys }
+                Descriptions {
+                    container,
+                    keys,
+                    groups: vec![],
+                    examples,
+                }
             }
         }
     }
@@ -84,6 +101,17 @@ impl Container {
         }
     }
 
+    pub(crate) fn fields_mut(&mut self) -> Box<dyn Iterator<Item = &mut Field> + '_> {
+        match self {
+            Container::Enum(item) => Box::new(
+                item.variants
+                    .iter_mut()
+                    .flat_map(|variant| variant.fields.iter_mut()),
+            ),
+            Container::Struct(item) => Box::new(item.fields.iter_mut()),
+        }
+    }
+
     pub(crate) fn generics(&self) -> &Generics {
         match self {
             Container::Enum(item) => &item.generics,
@@ -236,7 +264,9 @@ mod tests {
                 keys: vec![
                     Documentation { lines: vec![] },
                     Documentation { lines: vec![] },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -260,7 +290,69 @@ mod tests {
                 keys: vec![
                     Documentation { lines: vec![] },
                     Documentation { lines: vec![] },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
+            }
+        );
+    }
+
+    #[test]
+    fn struct_descriptions_examples() {
+        assert_eq!(
+            Container::Struct(assert_ok!(parse_str(
+                "
+                /// Hello, world!
+                ///
+                /// # Examples
+                /// $ foo 42
+                struct Foo {
+                    /// Bar documentation.
+                    bar: usize,
+                    baz: String,
+                }"
+            )))
+            .descriptions(),
+            Descriptions {
+                container: Documentation {
+                    lines: vec!["Hello, world!".into(), "".into()],
+                },
+                keys: vec![
+                    Documentation {
+                        lines: vec!["Bar documentation.".into()]
+                    },
+                    Documentation { lines: vec![] },
+                ],
+                groups: vec![],
+                examples: Some("$ foo 42".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn enum_descriptions_examples() {
+        assert_eq!(
+            Container::Enum(assert_ok!(parse_str(
+                "
+                /// Hello, world!
+                ///
+                /// # Examples
+                /// $ foo bar
+                enum Foo {
+                    /// Bar documentation.
+                    Bar,
+                }"
+            )))
+            .descriptions(),
+            Descriptions {
+                container: Documentation {
+                    lines: vec!["Hello, world!".into(), "".into()],
+                },
+                keys: vec![Documentation {
+                    lines: vec!["Bar documentation.".into()]
+                }],
+                groups: vec![None],
+                examples: Some("$ foo bar".into()),
             }
         );
     }
@@ -287,7 +379,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into()]
                     },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -317,7 +411,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into()]
                     },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -350,7 +446,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into(), "Second line baz.".into(),]
                     },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -371,7 +469,9 @@ mod tests {
                 keys: vec![
                     Documentation { lines: vec![] },
                     Documentation { lines: vec![] },
-                ]
+                ],
+                groups: vec![None, None],
+                examples: None,
             }
         );
     }
@@ -395,7 +495,9 @@ mod tests {
                 keys: vec![
                     Documentation { lines: vec![] },
                     Documentation { lines: vec![] },
-                ]
+                ],
+                groups: vec![None, None],
+                examples: None,
             }
         );
     }
@@ -422,7 +524,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into()]
                     },
-                ]
+                ],
+                groups: vec![None, None],
+                examples: None,
             }
         );
     }
@@ -452,7 +556,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into()]
                     },
-                ]
+                ],
+                groups: vec![None, None],
+                examples: None,
             }
         );
     }
@@ -485,7 +591,39 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into(), "Second line baz.".into(),]
                     },
-                ]
+                ],
+                groups: vec![None, None],
+                examples: None,
+            }
+        );
+    }
+
+    #[test]
+    fn enum_descriptions_groups() {
+        assert_eq!(
+            Container::Enum(assert_ok!(parse_str(
+                "
+                enum Foo {
+                    /// [Group]
+                    /// Bar documentation.
+                    Bar,
+                    /// Baz documentation.
+                    Baz,
+                }"
+            )))
+            .descriptions(),
+            Descriptions {
+                container: Documentation { lines: vec![] },
+                keys: vec![
+                    Documentation {
+                        lines: vec!["Bar documentation.".into()]
+                    },
+                    Documentation {
+                        lines: vec!["Baz documentation.".into()]
+                    },
+                ],
+                groups: vec![Some("Group".into()), None],
+                examples: None,
             }
         );
     }
@@ -503,7 +641,9 @@ mod tests {
                 keys: vec![
                     Documentation { lines: vec![] },
                     Documentation { lines: vec![] },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -524,7 +664,9 @@ mod tests {
                 keys: vec![
                     Documentation { lines: vec![] },
                     Documentation { lines: vec![] },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -551,7 +693,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into()]
                     },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -581,7 +725,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into()]
                     },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
@@ -614,7 +760,9 @@ mod tests {
                     Documentation {
                         lines: vec!["Baz documentation.".into(), "Second line baz.".into(),]
                     },
-                ]
+                ],
+                groups: vec![],
+                examples: None,
             }
         );
     }
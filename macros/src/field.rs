@@ -0,0 +1,933 @@
+//! Processing `#[serde_args(...)]` attributes on fields.
+//!
+//! These attributes are consumed by the `generate` macro and replaced with the equivalent `serde`
+//! attributes, meaning they are never seen by `serde`'s derive macros. Attributes that have no
+//! `serde` equivalent are instead communicated through the settings record.
+
+use crate::{
+    attributes::push_serde_attribute,
+    lint::serde_values,
+    record::Entry,
+    Container,
+};
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    punctuated::Punctuated,
+    Attribute,
+    Expr,
+    ExprLit,
+    Field,
+    Ident,
+    Lit,
+    Meta,
+    Token,
+    Type,
+    TypePath,
+};
+
+#[derive(Debug, Eq, PartialEq)]
+enum FieldParameter {
+    AllowHyphenValues,
+    Cidr,
+    Exists,
+    Flags,
+    IsDir,
+    IsFile,
+    Multiple,
+    Positional,
+    Required,
+    Trailing,
+    ValueDelimiter(char),
+    ValueName(String),
+}
+
+fn take_field_parameters(attrs: &mut Vec<Attribute>) -> Result<Vec<FieldParameter>, syn::Error> {
+    let mut parameters = Vec::new();
+    let mut error = None;
+    attrs.retain(|attribute| {
+        if !attribute.path().is_ident("serde_args") {
+            return true;
+        }
+        match attribute.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+            Ok(metas) => {
+                for meta in metas {
+                    match meta {
+                        Meta::Path(path) => match path.require_ident() {
+                            Ok(ident)
+                                if *ident
+                                    == Ident::new("allow_hyphen_values", Span::call_site()) =>
+                            {
+                                parameters.push(FieldParameter::AllowHyphenValues);
+                            }
+                            Ok(ident) if *ident == Ident::new("cidr", Span::call_site()) => {
+                                parameters.push(FieldParameter::Cidr);
+                            }
+                            Ok(ident) if *ident == Ident::new("exists", Span::call_site()) => {
+                                parameters.push(FieldParameter::Exists);
+                            }
+                            Ok(ident) if *ident == Ident::new("flags", Span::call_site()) => {
+                                parameters.push(FieldParameter::Flags);
+                            }
+                            Ok(ident) if *ident == Ident::new("is_dir", Span::call_site()) => {
+                                parameters.push(FieldParameter::IsDir);
+                            }
+                            Ok(ident) if *ident == Ident::new("is_file", Span::call_site()) => {
+                                parameters.push(FieldParameter::IsFile);
+                            }
+                            Ok(ident) if *ident == Ident::new("multiple", Span::call_site()) => {
+                                parameters.push(FieldParameter::Multiple);
+                            }
+                            Ok(ident) if *ident == Ident::new("positional", Span::call_site()) => {
+                                parameters.push(FieldParameter::Positional);
+                            }
+                            Ok(ident) if *ident == Ident::new("required", Span::call_site()) => {
+                                parameters.push(FieldParameter::Required);
+                            }
+                            Ok(ident) if *ident == Ident::new("trailing", Span::call_site()) => {
+                                parameters.push(FieldParameter::Trailing);
+                            }
+                            Ok(ident) => {
+                                error.get_or_insert(syn::Error::new_spanned(
+                                    ident,
+                                    "invalid field parameter; expected one of `allow_hyphen_values`, `cidr`, `exists`, `flags`, `is_dir`, `is_file`, `multiple`, `positional`, `required`, `trailing`, `value_delimiter`, or `value_name`",
+                                ));
+                            }
+                            Err(path_error) => {
+                                error.get_or_insert(path_error);
+                            }
+                        },
+                        Meta::NameValue(name_value) if name_value.path.is_ident("value_name") => {
+                            match name_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Str(value_name),
+                                    ..
+                                }) => {
+                                    parameters.push(FieldParameter::ValueName(value_name.value()));
+                                }
+                                value => {
+                                    error.get_or_insert(syn::Error::new_spanned(
+                                        value,
+                                        "expected `value_name` to be a string literal",
+                                    ));
+                                }
+                            }
+                        }
+                        Meta::NameValue(name_value)
+                            if name_value.path.is_ident("value_delimiter") =>
+                        {
+                            match name_value.value {
+                                Expr::Lit(ExprLit {
+                                    lit: Lit::Char(delimiter),
+                                    ..
+                                }) if delimiter.value().is_ascii() => {
+                                    parameters
+                                        .push(FieldParameter::ValueDelimiter(delimiter.value()));
+                                }
+                                value => {
+                                    error.get_or_insert(syn::Error::new_spanned(
+                                        value,
+                                        "expected `value_delimiter` to be an ASCII character literal",
+                                    ));
+                                }
+                            }
+                        }
+                        meta => {
+                            error.get_or_insert(syn::Error::new_spanned(
+                                meta,
+                                "invalid field parameter; expected one of `allow_hyphen_values`, `cidr`, `exists`, `flags`, `is_dir`, `is_file`, `multiple`, `positional`, `required`, `trailing`, `value_delimiter`, or `value_name`",
+                            ));
+                        }
+                    }
+                }
+            }
+            Err(parse_error) => {
+                error.get_or_insert(parse_error);
+            }
+        }
+        false
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(parameters),
+    }
+}
+
+/// Replace all `#[serde_args(...)]` field attributes within the container with their `serde`
+/// equivalents.
+///
+/// The record entries communicating any attributes to `serde_args` directly are returned.
+pub(crate) fn process(container: &mut Container) -> Result<Vec<Entry>, syn::Error> {
+    let is_struct = matches!(container, Container::Struct(_));
+    let rename_all = serde_values(container.attrs(), "rename_all")
+        .pop()
+        .map(|rename_all| rename_all.value());
+    let mut entries = Vec::new();
+    for (index, field) in container.fields_mut().enumerate() {
+        // `serde` lists a field's names in sorted order, so the canonical name must be provided
+        // whenever one of its aliases would be listed first.
+        if is_struct {
+            if let Some(name) = canonical_name(field, rename_all.as_deref()) {
+                if serde_values(&field.attrs, "alias")
+                    .iter()
+                    .any(|alias| alias.value() < name)
+                {
+                    entries.push(Entry::new("name", Some(index), &name));
+                }
+            }
+        }
+        for parameter in take_field_parameters(&mut field.attrs)? {
+            match parameter {
+                FieldParameter::AllowHyphenValues => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`allow_hyphen_values` is only supported on struct fields",
+                        ));
+                    }
+                    entries.push(Entry::new("allow_hyphen_values", Some(index), ""));
+                }
+                FieldParameter::Cidr => push_serde_attribute(
+                    &mut field.attrs,
+                    quote!(deserialize_with = "::serde_args::helpers::cidr::deserialize"),
+                ),
+                FieldParameter::Exists => push_path_attribute(field, "exists"),
+                FieldParameter::IsDir => push_path_attribute(field, "is_dir"),
+                FieldParameter::IsFile => push_path_attribute(field, "is_file"),
+                FieldParameter::Flags => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`flags` is only supported on struct fields",
+                        ));
+                    }
+                    entries.push(Entry::new("flags", Some(index), ""));
+                }
+                FieldParameter::Multiple => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`multiple` is only supported on struct fields",
+                        ));
+                    }
+                    entries.push(Entry::new("multiple", Some(index), ""));
+                }
+                FieldParameter::Positional => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`positional` is only supported on struct fields",
+                        ));
+                    }
+                    entries.push(Entry::new("positional", Some(index), ""));
+                }
+                FieldParameter::Required => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`required` is only supported on struct fields",
+                        ));
+                    }
+                    entries.push(Entry::new("required", Some(index), ""));
+                }
+                FieldParameter::Trailing => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`trailing` is only supported on struct fields",
+                        ));
+                    }
+                    push_serde_attribute(
+                        &mut field.attrs,
+                        quote!(deserialize_with = "::serde_args::helpers::os_strings::deserialize"),
+                    );
+                    entries.push(Entry::new("trailing", Some(index), ""));
+                }
+                FieldParameter::ValueDelimiter(delimiter) => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`value_delimiter` is only supported on struct fields",
+                        ));
+                    }
+                    entries.push(Entry::new(
+                        "value_delimiter",
+                        Some(index),
+                        &delimiter.to_string(),
+                    ));
+                }
+                FieldParameter::ValueName(value_name) => {
+                    if !is_struct {
+                        return Err(syn::Error::new_spanned(
+                            field,
+                            "`value_name` is only supported on struct fields",
+                        ));
+                    }
+                    entries.push(Entry::new("value_name", Some(index), &value_name));
+                }
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// Returns the name `serde` deserializes `field` by, not including its aliases.
+///
+/// `None` is returned if the name cannot be determined, such as when the container is renamed using
+/// a rule `serde` does not define.
+fn canonical_name(field: &Field, rename_all: Option<&str>) -> Option<String> {
+    if let Some(rename) = serde_values(&field.attrs, "rename").pop() {
+        return Some(rename.value());
+    }
+    let ident = field.ident.as_ref()?.to_string();
+    let ident = ident.strip_prefix("r#").unwrap_or(&ident);
+    let pascal_case = || {
+        ident
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                    .unwrap_or_default()
+            })
+            .collect::<String>()
+    };
+    Some(match rename_all {
+        None | Some("lowercase") | Some("snake_case") => ident.to_owned(),
+        Some("UPPERCASE") | Some("SCREAMING_SNAKE_CASE") => ident.to_ascii_uppercase(),
+        Some("PascalCase") => pascal_case(),
+        Some("camelCase") => {
+            let pascal_case = pascal_case();
+            let mut chars = pascal_case.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+        Some("kebab-case") => ident.replace('_', "-"),
+        Some("SCREAMING-KEBAB-CASE") => ident.to_ascii_uppercase().replace('_', "-"),
+        Some(_) => return None,
+    })
+}
+
+/// Deserializes `field` using the path helper `helper`, checking the path on the filesystem.
+///
+/// Optional fields are detected by their type being written as `Option<...>`, and use the helper's
+/// `deserialize_option()` function instead.
+fn push_path_attribute(field: &mut Field, helper: &str) {
+    let function = match &field.ty {
+        Type::Path(TypePath { qself: None, path })
+            if path
+                .segments
+                .last()
+                .is_some_and(|segment| segment.ident == "Option") =>
+        {
+            "deserialize_option"
+        }
+        _ => "deserialize",
+    };
+    let path = format!("::serde_args::helpers::{helper}::{function}");
+    push_serde_attribute(&mut field.attrs, quote!(deserialize_with = #path));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::process;
+    use crate::{
+        record::Entry,
+        Container,
+    };
+    use claims::{
+        assert_err,
+        assert_ok,
+    };
+    use syn::parse_str;
+
+    #[test]
+    fn process_no_field_attributes() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde(alias = \"baz\")]
+                bar: usize,
+            }"
+        ));
+
+        assert_eq!(assert_ok!(process(&mut container)), vec![]);
+
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    #[serde(alias = \"baz\")]
+                    bar: usize,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_struct_cidr() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(cidr)]
+                bar: Network,
+                baz: usize,
+            }"
+        ));
+
+        assert_ok!(process(&mut container));
+
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    #[serde(deserialize_with = \"::serde_args::helpers::cidr::deserialize\")]
+                    bar: Network,
+                    baz: usize,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_struct_paths() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(exists)]
+                bar: PathBuf,
+                #[serde_args(is_file)]
+                baz: Option<PathBuf>,
+                #[serde_args(is_dir)]
+                qux: std::option::Option<PathBuf>,
+            }"
+        ));
+
+        assert_ok!(process(&mut container));
+
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    #[serde(deserialize_with = \"::serde_args::helpers::exists::deserialize\")]
+                    bar: PathBuf,
+                    #[serde(deserialize_with = \"::serde_args::helpers::is_file::deserialize_option\")]
+                    baz: Option<PathBuf>,
+                    #[serde(deserialize_with = \"::serde_args::helpers::is_dir::deserialize_option\")]
+                    qux: std::option::Option<PathBuf>,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_enum_cidr() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    /// Documentation.
+                    #[serde_args(cidr)]
+                    baz: Network,
+                },
+                Qux,
+            }"
+        ));
+
+        assert_ok!(process(&mut container));
+
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                enum Foo {
+                    Bar {
+                        /// Documentation.
+                        #[serde(deserialize_with = \"::serde_args::helpers::cidr::deserialize\")]
+                        baz: Network,
+                    },
+                    Qux,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_unknown_parameter() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(unknown)]
+                bar: usize,
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "invalid field parameter; expected one of `allow_hyphen_values`, `cidr`, `exists`, `flags`, `is_dir`, `is_file`, `multiple`, `positional`, `required`, `trailing`, `value_delimiter`, or `value_name`"
+        );
+    }
+
+    #[test]
+    fn process_struct_allow_hyphen_values() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                bar: usize,
+                #[serde_args(allow_hyphen_values)]
+                baz: String,
+                #[serde_args(allow_hyphen_values)]
+                qux: String,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![
+                Entry::new("allow_hyphen_values", Some(1), ""),
+                Entry::new("allow_hyphen_values", Some(2), ""),
+            ]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: usize,
+                    baz: String,
+                    qux: String,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_enum_allow_hyphen_values() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(allow_hyphen_values)]
+                    baz: String,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`allow_hyphen_values` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_positional() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                bar: String,
+                #[serde_args(positional)]
+                baz: Option<String>,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![Entry::new("positional", Some(1), ""),]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: String,
+                    baz: Option<String>,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_enum_positional() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(positional)]
+                    baz: Option<String>,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`positional` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_required() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                bar: String,
+                #[serde_args(required)]
+                baz: Option<String>,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![Entry::new("required", Some(1), ""),]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: String,
+                    baz: Option<String>,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_enum_required() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(required)]
+                    baz: Option<String>,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`required` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_flags() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                bar: String,
+                #[serde_args(flags)]
+                baz: Option<Format>,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![Entry::new("flags", Some(1), ""),]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: String,
+                    baz: Option<Format>,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_enum_flags() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(flags)]
+                    baz: Option<Format>,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`flags` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_multiple() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                bar: String,
+                #[serde_args(multiple)]
+                baz: Option<Vec<u32>>,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![Entry::new("multiple", Some(1), ""),]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: String,
+                    baz: Option<Vec<u32>>,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_enum_multiple() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(multiple)]
+                    baz: Option<Vec<u32>>,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`multiple` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_trailing() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                bar: String,
+                #[serde_args(trailing)]
+                baz: Vec<OsString>,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![Entry::new("trailing", Some(1), ""),]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: String,
+                    #[serde(deserialize_with = \"::serde_args::helpers::os_strings::deserialize\")]
+                    baz: Vec<OsString>,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_enum_trailing() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(trailing)]
+                    baz: Vec<OsString>,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`trailing` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_value_delimiter() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(value_delimiter = ',')]
+                bar: Option<Vec<String>>,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![Entry::new("value_delimiter", Some(0), ","),]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: Option<Vec<String>>,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_struct_value_delimiter_not_ascii() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(value_delimiter = '·')]
+                bar: Option<Vec<String>>,
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "expected `value_delimiter` to be an ASCII character literal"
+        );
+    }
+
+    #[test]
+    fn process_struct_value_delimiter_string() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(value_delimiter = \",\")]
+                bar: Option<Vec<String>>,
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "expected `value_delimiter` to be an ASCII character literal"
+        );
+    }
+
+    #[test]
+    fn process_enum_value_delimiter() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(value_delimiter = ',')]
+                    baz: Option<Vec<String>>,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`value_delimiter` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_value_name() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(value_name = \"FILE\")]
+                bar: String,
+                #[serde_args(allow_hyphen_values, value_name = \"PATTERN\")]
+                baz: String,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![
+                Entry::new("value_name", Some(0), "FILE"),
+                Entry::new("allow_hyphen_values", Some(1), ""),
+                Entry::new("value_name", Some(1), "PATTERN"),
+            ]
+        );
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                struct Foo {
+                    bar: String,
+                    baz: String,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_value_name_not_string() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            struct Foo {
+                #[serde_args(value_name = 42)]
+                bar: String,
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "expected `value_name` to be a string literal"
+        );
+    }
+
+    #[test]
+    fn process_enum_value_name() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde_args(value_name = \"FILE\")]
+                    baz: String,
+                },
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "`value_name` is only supported on struct fields"
+        );
+    }
+
+    #[test]
+    fn process_struct_aliases() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            #[serde(rename_all = \"kebab-case\")]
+            struct Foo {
+                #[serde(rename = \"color\", alias = \"colour\", alias = \"c\")]
+                bar: String,
+                #[serde(alias = \"d\")]
+                dry_run: bool,
+                #[serde(alias = \"verbosity\")]
+                verbose: bool,
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![
+                Entry::new("name", Some(0), "color"),
+                Entry::new("name", Some(1), "dry-run"),
+            ]
+        );
+    }
+
+    #[test]
+    fn process_enum_aliases() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar {
+                    #[serde(rename = \"color\", alias = \"c\")]
+                    baz: String,
+                },
+            }"
+        ));
+
+        assert_eq!(assert_ok!(process(&mut container)), vec![]);
+    }
+}
@@ -15,8 +15,15 @@ use crate::{
         get_serde_attribute,
         push_serde_attribute,
         remove_serde_attribute,
+        replace_self_in_serde_attributes,
     },
+    constraints,
+    field,
     help,
+    lint,
+    record,
+    settings,
+    variant,
     version,
     Container,
 };
@@ -89,6 +96,18 @@ pub(crate) fn phase_1(mut container: Container, ident: &Ident) -> TokenStream {
             item.ident = Ident::new("Phase1", Span::call_site());
         }
     };
+    // Serialize-only paths relative to `Self` must still refer to the original type.
+    let self_replacement = {
+        let args = container.args();
+        format!("<{}>", quote!(#ident #args))
+    };
+    for field in container.fields_mut() {
+        replace_self_in_serde_attributes(
+            &mut field.attrs,
+            &["skip_serializing_if", "serialize_with"],
+            &self_replacement,
+        );
+    }
 
     let from = if let Some(other_type) = get_serde_attribute(container.attrs(), "from") {
         match parse_str(&other_type) {
@@ -682,7 +701,7 @@ pub(crate) fn phase_3(mut container: Container, module: &Ident) -> TokenStream {
 
 pub(super) fn process(attr: TokenStream, item: TokenStream) -> TokenStream {
     // Parse input.
-    let container: Container = match parse(item) {
+    let mut container: Container = match parse(item) {
         Ok(container) => container,
         Err(error) => return error.into_compile_error(),
     };
@@ -691,11 +710,56 @@ pub(super) fn process(attr: TokenStream, item: TokenStream) -> TokenStream {
         Err(error) => return error.into_compile_error(),
     };
 
+    // Check the definition before any attributes are replaced.
+    if parameters.lint() {
+        if let Err(error) = lint::lint(&container, parameters.doc_help()) {
+            return error.into_compile_error();
+        }
+    }
+
+    // Constraints name fields by their declaration index.
+    let constraint_entries = match constraints::entries(&container, parameters.constraints()) {
+        Ok(constraint_entries) => constraint_entries,
+        Err(error) => return error.into_compile_error(),
+    };
+
+    // Replace field attributes with their `serde` equivalents.
+    let field_entries = match field::process(&mut container) {
+        Ok(field_entries) => field_entries,
+        Err(error) => return error.into_compile_error(),
+    };
+
+    // Remove variant attributes, which have no `serde` equivalents.
+    let variant_entries = match variant::process(&mut container) {
+        Ok(variant_entries) => variant_entries,
+        Err(error) => return error.into_compile_error(),
+    };
+
+    // Everything communicated to `serde_args` is written as a single settings record.
+    let mut entries = field_entries;
+    entries.extend(variant_entries);
+    entries.extend(constraint_entries);
+    let mut help_expecting = None;
+    for parameter in parameters {
+        match parameter {
+            Parameter::DocHelp => {
+                entries.extend(help::entries(&container));
+                help_expecting = Some(help::expecting(&container));
+            }
+            Parameter::Version => entries.push(version::entry()),
+            Parameter::NoAutoHelp => entries.push(settings::no_auto_help()),
+            Parameter::NoEmptyHelp => entries.push(settings::no_empty_help()),
+        }
+    }
+
     // Generating custom expecting functions.
-    let expecting = parameters.into_iter().map(|parameter| match parameter {
-        Parameter::DocHelp => help::expecting(&container),
-        Parameter::Version => version::expecting(),
-    });
+    //
+    // The record must come before any doc help, since doc help responds to every request.
+    let expecting = record::expecting(&entries)
+        .into_iter()
+        .chain(help_expecting)
+        .collect::<Vec<_>>()
+        .into_iter();
     if expecting.len() == 0 {
         // Return early if no extra code should be generated.
         return quote!(#container);
@@ -2432,25 +2496,30 @@ mod tests {
 
                             fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                                 fn __0(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    match formatter.width() {
-                                        ::std::option::Option::Some(0) => {
-                                            formatter.write_str(\"bar documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        ::std::option::Option::Some(1) => {
-                                            formatter.write_str(\"baz documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        _ => {
-                                            formatter.write_str(\"container documentation.\")?;
-                                            ::std::result::Result::Ok(true)
+                                    if formatter.fill() == 's' {
+                                        for entry in [
+                                            \"description\\u{1f}0\\u{1f}bar documentation.\",
+                                            \"description\\u{1f}1\\u{1f}baz documentation.\"
+                                        ] {
+                                            ::std::write!(formatter, \"{}:{}\", entry.len(), entry)?;
                                         }
+                                        ::std::result::Result::Ok(true)
+                                    } else {
+                                        ::std::result::Result::Ok(false)
                                     }
                                 }
 
+                                fn __1(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
+                                    formatter.write_str(\"container documentation.\")?;
+                                    ::std::result::Result::Ok(true)
+                                }
+
                                 if __0(formatter)? {
                                     return ::std::result::Result::Ok(());
                                 }
+                                if __1(formatter)? {
+                                    return ::std::result::Result::Ok(());
+                                }
                                 ::std::result::Result::Ok(())
                             }
 
@@ -2637,8 +2706,12 @@ mod tests {
 
                             fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                                 fn __0(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    if formatter.fill() == 'v' {
-                                        formatter.write_str(::std::env!(\"CARGO_PKG_VERSION\"))?;
+                                    if formatter.fill() == 's' {
+                                        for entry in [
+                                            ::std::concat!(\"version\\u{1f}\\u{1f}\", ::std::env!(\"CARGO_PKG_VERSION\"))
+                                        ] {
+                                            ::std::write!(formatter, \"{}:{}\", entry.len(), entry)?;
+                                        }
                                         ::std::result::Result::Ok(true)
                                     } else {
                                         ::std::result::Result::Ok(false)
@@ -2834,8 +2907,14 @@ mod tests {
 
                             fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                                 fn __0(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    if formatter.fill() == 'v' {
-                                        formatter.write_str(::std::env!(\"CARGO_PKG_VERSION\"))?;
+                                    if formatter.fill() == 's' {
+                                        for entry in [
+                                            ::std::concat!(\"version\\u{1f}\\u{1f}\", ::std::env!(\"CARGO_PKG_VERSION\")),
+                                            \"description\\u{1f}0\\u{1f}bar documentation.\",
+                                            \"description\\u{1f}1\\u{1f}baz documentation.\"
+                                        ] {
+                                            ::std::write!(formatter, \"{}:{}\", entry.len(), entry)?;
+                                        }
                                         ::std::result::Result::Ok(true)
                                     } else {
                                         ::std::result::Result::Ok(false)
@@ -2843,20 +2922,8 @@ mod tests {
                                 }
 
                                 fn __1(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    match formatter.width() {
-                                        ::std::option::Option::Some(0) => {
-                                            formatter.write_str(\"bar documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        ::std::option::Option::Some(1) => {
-                                            formatter.write_str(\"baz documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        _ => {
-                                            formatter.write_str(\"container documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                    }
+                                    formatter.write_str(\"container documentation.\")?;
+                                    ::std::result::Result::Ok(true)
                                 }
 
                                 if __0(formatter)? {
@@ -3051,25 +3118,30 @@ mod tests {
 
                             fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                                 fn __0(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    match formatter.width() {
-                                        ::std::option::Option::Some(0) => {
-                                            formatter.write_str(\"bar documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        ::std::option::Option::Some(1) => {
-                                            formatter.write_str(\"baz documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        _ => {
-                                            formatter.write_str(\"container documentation.\")?;
-                                            ::std::result::Result::Ok(true)
+                                    if formatter.fill() == 's' {
+                                        for entry in [
+                                            \"description\\u{1f}0\\u{1f}bar documentation.\",
+                                            \"description\\u{1f}1\\u{1f}baz documentation.\"
+                                        ] {
+                                            ::std::write!(formatter, \"{}:{}\", entry.len(), entry)?;
                                         }
+                                        ::std::result::Result::Ok(true)
+                                    } else {
+                                        ::std::result::Result::Ok(false)
                                     }
                                 }
 
+                                fn __1(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
+                                    formatter.write_str(\"container documentation.\")?;
+                                    ::std::result::Result::Ok(true)
+                                }
+
                                 if __0(formatter)? {
                                     return ::std::result::Result::Ok(());
                                 }
+                                if __1(formatter)? {
+                                    return ::std::result::Result::Ok(());
+                                }
                                 ::std::result::Result::Ok(())
                             }
 
@@ -3256,8 +3328,12 @@ mod tests {
 
                             fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                                 fn __0(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    if formatter.fill() == 'v' {
-                                        formatter.write_str(::std::env!(\"CARGO_PKG_VERSION\"))?;
+                                    if formatter.fill() == 's' {
+                                        for entry in [
+                                            ::std::concat!(\"version\\u{1f}\\u{1f}\", ::std::env!(\"CARGO_PKG_VERSION\"))
+                                        ] {
+                                            ::std::write!(formatter, \"{}:{}\", entry.len(), entry)?;
+                                        }
                                         ::std::result::Result::Ok(true)
                                     } else {
                                         ::std::result::Result::Ok(false)
@@ -3453,8 +3529,14 @@ mod tests {
 
                             fn expecting(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
                                 fn __0(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    if formatter.fill() == 'v' {
-                                        formatter.write_str(::std::env!(\"CARGO_PKG_VERSION\"))?;
+                                    if formatter.fill() == 's' {
+                                        for entry in [
+                                            ::std::concat!(\"version\\u{1f}\\u{1f}\", ::std::env!(\"CARGO_PKG_VERSION\")),
+                                            \"description\\u{1f}0\\u{1f}bar documentation.\",
+                                            \"description\\u{1f}1\\u{1f}baz documentation.\"
+                                        ] {
+                                            ::std::write!(formatter, \"{}:{}\", entry.len(), entry)?;
+                                        }
                                         ::std::result::Result::Ok(true)
                                     } else {
                                         ::std::result::Result::Ok(false)
@@ -3462,20 +3544,8 @@ mod tests {
                                 }
 
                                 fn __1(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                                    match formatter.width() {
-                                        ::std::option::Option::Some(0) => {
-                                            formatter.write_str(\"bar documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        ::std::option::Option::Some(1) => {
-                                            formatter.write_str(\"baz documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                        _ => {
-                                            formatter.write_str(\"container documentation.\")?;
-                                            ::std::result::Result::Ok(true)
-                                        }
-                                    }
+                                    formatter.write_str(\"container documentation.\")?;
+                                    ::std::result::Result::Ok(true)
                                 }
 
                                 if __0(formatter)? {
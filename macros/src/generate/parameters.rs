@@ -1,3 +1,7 @@
+use crate::constraints::{
+    Constraint,
+    ConstraintKind,
+};
 use proc_macro2::Span;
 use syn::{
     parse,
@@ -7,7 +11,7 @@ use syn::{
     },
     punctuated::Punctuated,
     Ident,
-    Path,
+    Meta,
     Token,
 };
 
@@ -15,10 +19,16 @@ use syn::{
 pub(super) enum Parameter {
     DocHelp,
     Version,
+    NoAutoHelp,
+    NoEmptyHelp,
 }
 
+/// The parameters given to `generate`.
+///
+/// Flags are stored as bits, while constraints on the container's fields are stored in the order
+/// they were given.
 #[derive(Debug, Eq, PartialEq)]
-pub(super) struct Parameters(u8);
+pub(super) struct Parameters(u8, Vec<Constraint>);
 
 impl Parameters {
     #[cfg(test)]
@@ -26,25 +36,72 @@ impl Parameters {
     const VERSION: u8 = 1;
     // DocHelp must be the last one returned in iteration.
     const DOC_HELP: u8 = 2;
+    const NO_AUTO_HELP: u8 = 4;
+    const NO_EMPTY_HELP: u8 = 8;
+    // Lint does not generate any code, and is therefore never returned in iteration.
+    const LINT: u8 = 16;
+
+    /// Returns whether `lint` was provided.
+    pub(super) fn lint(&self) -> bool {
+        self.0 & Parameters::LINT != 0
+    }
+
+    /// Returns whether `doc_help` was provided.
+    pub(super) fn doc_help(&self) -> bool {
+        self.0 & Parameters::DOC_HELP != 0
+    }
+
+    /// Returns the constraints provided, such as `one_of(...)`.
+    pub(super) fn constraints(&self) -> &[Constraint] {
+        &self.1
+    }
 }
 
+const INVALID_PARAMETER: &str = "invalid parameter; expected one of `doc_help`, `version`, `no_auto_help`, `no_empty_help`, `lint`, `one_of(...)`, `at_most_one(...)`, or `all_or_none(...)`";
+
 impl Parse for Parameters {
     fn parse(input: ParseStream) -> parse::Result<Self> {
         let mut parameters = 0;
-        for path in Punctuated::<Path, Token![,]>::parse_terminated(input)? {
+        let mut constraints = Vec::new();
+        for meta in Punctuated::<Meta, Token![,]>::parse_terminated(input)? {
+            let path = match meta {
+                Meta::Path(path) => path,
+                Meta::List(list) => {
+                    let ident = list.path.require_ident()?;
+                    let kind = match ConstraintKind::from_ident(ident) {
+                        Some(kind) => kind,
+                        None => return Err(syn::Error::new_spanned(ident, INVALID_PARAMETER)),
+                    };
+                    constraints.push(Constraint {
+                        kind,
+                        ident: ident.clone(),
+                        fields: list
+                            .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)?
+                            .into_iter()
+                            .collect(),
+                    });
+                    continue;
+                }
+                Meta::NameValue(name_value) => {
+                    return Err(syn::Error::new_spanned(name_value, INVALID_PARAMETER));
+                }
+            };
             let ident = path.require_ident()?;
             if *ident == Ident::new("doc_help", Span::call_site()) {
                 parameters |= Parameters::DOC_HELP;
             } else if *ident == Ident::new("version", Span::call_site()) {
                 parameters |= Parameters::VERSION;
+            } else if *ident == Ident::new("no_auto_help", Span::call_site()) {
+                parameters |= Parameters::NO_AUTO_HELP;
+            } else if *ident == Ident::new("no_empty_help", Span::call_site()) {
+                parameters |= Parameters::NO_EMPTY_HELP;
+            } else if *ident == Ident::new("lint", Span::call_site()) {
+                parameters |= Parameters::LINT;
             } else {
-                return Err(syn::Error::new_spanned(
-                    ident,
-                    "invalid parameter; expected one of `doc_help` or `version`",
-                ));
+                return Err(syn::Error::new_spanned(ident, INVALID_PARAMETER));
             }
         }
-        Ok(Self(parameters))
+        Ok(Self(parameters, constraints))
     }
 }
 
@@ -53,7 +110,9 @@ impl IntoIterator for Parameters {
     type IntoIter = Iter;
 
     fn into_iter(self) -> Self::IntoIter {
-        Iter { parameters: self.0 }
+        Iter {
+            parameters: self.0 & !Parameters::LINT,
+        }
     }
 }
 
@@ -68,6 +127,12 @@ impl Iterator for Iter {
         if self.parameters & Parameters::VERSION != 0 {
             self.parameters ^= Parameters::VERSION;
             Some(Parameter::Version)
+        } else if self.parameters & Parameters::NO_AUTO_HELP != 0 {
+            self.parameters ^= Parameters::NO_AUTO_HELP;
+            Some(Parameter::NoAutoHelp)
+        } else if self.parameters & Parameters::NO_EMPTY_HELP != 0 {
+            self.parameters ^= Parameters::NO_EMPTY_HELP;
+            Some(Parameter::NoEmptyHelp)
         } else if self.parameters & Parameters::DOC_HELP != 0 {
             self.parameters ^= Parameters::DOC_HELP;
             Some(Parameter::DocHelp)
@@ -90,22 +155,33 @@ mod tests {
         Parameter,
         Parameters,
     };
+    use crate::constraints::{
+        Constraint,
+        ConstraintKind,
+    };
     use claims::{
         assert_err,
         assert_ok_eq,
     };
-    use syn::parse_str;
+    use proc_macro2::Span;
+    use syn::{
+        parse_str,
+        Ident,
+    };
 
     #[test]
     fn parse_empty() {
-        assert_ok_eq!(parse_str::<Parameters>(""), Parameters(Parameters::EMPTY));
+        assert_ok_eq!(
+            parse_str::<Parameters>(""),
+            Parameters(Parameters::EMPTY, vec![])
+        );
     }
 
     #[test]
     fn parse_doc_help() {
         assert_ok_eq!(
             parse_str::<Parameters>("doc_help"),
-            Parameters(Parameters::DOC_HELP)
+            Parameters(Parameters::DOC_HELP, vec![])
         );
     }
 
@@ -113,30 +189,132 @@ mod tests {
     fn parse_version() {
         assert_ok_eq!(
             parse_str::<Parameters>("version"),
-            Parameters(Parameters::VERSION)
+            Parameters(Parameters::VERSION, vec![])
+        );
+    }
+
+    #[test]
+    fn parse_no_auto_help() {
+        assert_ok_eq!(
+            parse_str::<Parameters>("no_auto_help"),
+            Parameters(Parameters::NO_AUTO_HELP, vec![])
+        );
+    }
+
+    #[test]
+    fn parse_no_empty_help() {
+        assert_ok_eq!(
+            parse_str::<Parameters>("no_empty_help"),
+            Parameters(Parameters::NO_EMPTY_HELP, vec![])
+        );
+    }
+
+    #[test]
+    fn parse_lint() {
+        assert_ok_eq!(
+            parse_str::<Parameters>("lint"),
+            Parameters(Parameters::LINT, vec![])
         );
     }
 
     #[test]
     fn parse_all() {
         assert_ok_eq!(
-            parse_str::<Parameters>("doc_help, version"),
-            Parameters(Parameters::DOC_HELP | Parameters::VERSION)
+            parse_str::<Parameters>("doc_help, version, no_auto_help, no_empty_help, lint"),
+            Parameters(
+                Parameters::DOC_HELP
+                    | Parameters::VERSION
+                    | Parameters::NO_AUTO_HELP
+                    | Parameters::NO_EMPTY_HELP
+                    | Parameters::LINT,
+                vec![]
+            )
         );
     }
 
+    #[test]
+    fn parse_constraints() {
+        assert_ok_eq!(
+            parse_str::<Parameters>("doc_help, one_of(stdin, file), all_or_none(user, password)"),
+            Parameters(
+                Parameters::DOC_HELP,
+                vec![
+                    Constraint {
+                        kind: ConstraintKind::OneOf,
+                        ident: Ident::new("one_of", Span::call_site()),
+                        fields: vec![
+                            Ident::new("stdin", Span::call_site()),
+                            Ident::new("file", Span::call_site()),
+                        ],
+                    },
+                    Constraint {
+                        kind: ConstraintKind::AllOrNone,
+                        ident: Ident::new("all_or_none", Span::call_site()),
+                        fields: vec![
+                            Ident::new("user", Span::call_site()),
+                            Ident::new("password", Span::call_site()),
+                        ],
+                    },
+                ]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_at_most_one() {
+        assert_ok_eq!(
+            parse_str::<Parameters>("at_most_one(quiet, verbose)"),
+            Parameters(
+                Parameters::EMPTY,
+                vec![Constraint {
+                    kind: ConstraintKind::AtMostOne,
+                    ident: Ident::new("at_most_one", Span::call_site()),
+                    fields: vec![
+                        Ident::new("quiet", Span::call_site()),
+                        Ident::new("verbose", Span::call_site()),
+                    ],
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_unknown_constraint() {
+        assert_eq!(
+            format!("{}", assert_err!(parse_str::<Parameters>("none_of(foo, bar)"))),
+            "invalid parameter; expected one of `doc_help`, `version`, `no_auto_help`, `no_empty_help`, `lint`, `one_of(...)`, `at_most_one(...)`, or `all_or_none(...)`"
+        );
+    }
+
+    #[test]
+    fn parse_constraint_not_identifiers() {
+        assert_err!(parse_str::<Parameters>("one_of(\"foo\", \"bar\")"));
+    }
+
+    #[test]
+    fn lint() {
+        assert!(Parameters(Parameters::LINT, vec![]).lint());
+        assert!(!Parameters(Parameters::DOC_HELP, vec![]).lint());
+    }
+
+    #[test]
+    fn doc_help() {
+        assert!(Parameters(Parameters::DOC_HELP | Parameters::LINT, vec![]).doc_help());
+        assert!(!Parameters(Parameters::LINT, vec![]).doc_help());
+    }
+
     #[test]
     fn parse_unknown() {
         assert_eq!(
             format!("{}", assert_err!(parse_str::<Parameters>("unknown"))),
-            "invalid parameter; expected one of `doc_help` or `version`"
+            "invalid parameter; expected one of `doc_help`, `version`, `no_auto_help`, `no_empty_help`, `lint`, `one_of(...)`, `at_most_one(...)`, or `all_or_none(...)`"
         );
     }
 
     #[test]
     fn iter_none() {
         assert_eq!(
-            Parameters(Parameters::EMPTY)
+            Parameters(Parameters::EMPTY, vec![])
                 .into_iter()
                 .collect::<Vec<_>>(),
             &[]
@@ -146,7 +324,7 @@ mod tests {
     #[test]
     fn iter_doc_help() {
         assert_eq!(
-            Parameters(Parameters::DOC_HELP)
+            Parameters(Parameters::DOC_HELP, vec![])
                 .into_iter()
                 .collect::<Vec<_>>(),
             &[Parameter::DocHelp]
@@ -156,22 +334,53 @@ mod tests {
     #[test]
     fn iter_version() {
         assert_eq!(
-            Parameters(Parameters::VERSION)
+            Parameters(Parameters::VERSION, vec![])
                 .into_iter()
                 .collect::<Vec<_>>(),
             &[Parameter::Version]
         );
     }
 
+    #[test]
+    fn iter_lint() {
+        // `Lint` does not generate an `expecting()` function.
+        let iter = Parameters(Parameters::LINT, vec![]).into_iter();
+
+        assert_eq!(iter.len(), 0);
+        assert_eq!(iter.collect::<Vec<_>>(), &[]);
+    }
+
     #[test]
     fn iter_version_doc_help() {
         // `DocHelp` should always come last.
-        // This is because the `DocHelp` `expecting()` function will never return `false`.
+        // This is because the `DocHelp` `expecting()` function must come after the settings record.
         assert_eq!(
-            Parameters(Parameters::DOC_HELP | Parameters::VERSION)
+            Parameters(Parameters::DOC_HELP | Parameters::VERSION, vec![])
                 .into_iter()
                 .collect::<Vec<_>>(),
             &[Parameter::Version, Parameter::DocHelp]
         );
     }
+
+    #[test]
+    fn iter_all() {
+        // `DocHelp` should still come last.
+        assert_eq!(
+            Parameters(
+                Parameters::DOC_HELP
+                    | Parameters::VERSION
+                    | Parameters::NO_AUTO_HELP
+                    | Parameters::NO_EMPTY_HELP,
+                vec![]
+            )
+            .into_iter()
+            .collect::<Vec<_>>(),
+            &[
+                Parameter::Version,
+                Parameter::NoAutoHelp,
+                Parameter::NoEmptyHelp,
+                Parameter::DocHelp
+            ]
+        );
+    }
 }
@@ -1,59 +1,69 @@
-use crate::Container;
+use crate::{
+    container::{
+        Container,
+        Documentation,
+    },
+    record::Entry,
+};
 use syn::{
     parse_str,
     ItemFn,
 };
 
-pub(super) fn expecting(container: &Container) -> ItemFn {
+/// Returns a statement writing the rendered documentation, if there is any.
+fn write_expr(documentation: &Documentation) -> String {
+    let text = documentation.render();
+    if text.is_empty() {
+        String::new()
+    } else {
+        format!("formatter.write_str({text:?})?;")
+    }
+}
+
+/// Returns the record entries providing the rendered documentation of every key, along with the
+/// groups of variants and the container's examples.
+///
+/// Keys without documentation have no entry.
+pub(super) fn entries(container: &Container) -> Vec<Entry> {
     let descriptions = container.descriptions();
-    let mut container_exprs = descriptions
-        .container
-        .lines
-        .into_iter()
-        .map(|line| format!("formatter.write_str(\"{line}\")?;"))
-        .fold("_ => {".to_owned(), |mut s, line| {
-            s.push_str(&line);
-            s
-        });
-    container_exprs.push_str("::std::result::Result::Ok(true)}");
-    let key_exprs = descriptions
-        .keys
-        .into_iter()
-        .enumerate()
-        .map(|(index, documentation)| {
-            let mut documentation_exprs = documentation
-                .lines
-                .into_iter()
-                .map(|line| format!("formatter.write_str(\"{line}\")?;"))
-                .fold(
-                    format!("::std::option::Option::Some({index}) => {{"),
-                    |mut s, line| {
-                        s.push_str(&line);
-                        s
-                    },
-                );
-            documentation_exprs.push_str("::std::result::Result::Ok(true)}");
-            documentation_exprs
-        })
-        .fold(String::new(), |mut s, expr| {
-            s.push_str(&expr);
-            s.push('\n');
-            s
-        });
+    let mut entries = Vec::new();
+    for (index, documentation) in descriptions.keys.iter().enumerate() {
+        let text = documentation.render();
+        if !text.is_empty() {
+            entries.push(Entry::new("description", Some(index), &text));
+        }
+    }
+    for (index, group) in descriptions.groups.iter().enumerate() {
+        if let Some(group) = group {
+            entries.push(Entry::new("group", Some(index), group));
+        }
+    }
+    if let Some(examples) = &descriptions.examples {
+        entries.push(Entry::new("examples", None, examples));
+    }
+    entries
+}
 
+/// Returns an `expecting()` function writing the rendered documentation of the container.
+///
+/// This responds to every request, so it must come after every other `expecting()` function.
+pub(super) fn expecting(container: &Container) -> ItemFn {
+    let container_expr = write_expr(&container.descriptions().container);
     parse_str(&format!("
         fn expecting(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {{
-            match formatter.width() {{
-                {key_exprs}
-                {container_exprs}
-            }}
+            {container_expr}
+            ::std::result::Result::Ok(true)
         }}
     ")).expect("could not generate help `expecting()` function")
 }
 
 #[cfg(test)]
 mod tests {
-    use super::expecting;
+    use super::{
+        entries,
+        expecting,
+    };
+    use crate::record::Entry;
     use claims::assert_ok;
     use syn::{
         parse_str,
@@ -73,52 +83,177 @@ mod tests {
             );"
         ))), assert_ok!(parse_str::<ItemFn>("
             fn expecting(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                match formatter.width() {
-                    ::std::option::Option::Some(0) => {
-                        formatter.write_str(\"Bar documentation.\")?;
-                        ::std::result::Result::Ok(true)
-                    }
-                    ::std::option::Option::Some(1) => {
-                        formatter.write_str(\"Baz documentation.\")?;
-                        ::std::result::Result::Ok(true)
-                    }
-                    _ => {
-                        formatter.write_str(\"Container documentation.\")?;
-                        ::std::result::Result::Ok(true)
-                    }
-                }
+                formatter.write_str(\"Container documentation.\")?;
+                ::std::result::Result::Ok(true)
             }
         ")));
     }
 
     #[test]
-    fn enum_expecting() {
+    fn struct_expecting_undocumented() {
         assert_eq!(expecting(&assert_ok!(parse_str(
             "
-            /// Container documentation.
-            enum Foo {
+            struct Foo(
                 /// Bar documentation.
-                Bar,
-                /// Baz documentation.
-                Baz,
-            }"
+                usize
+            );"
+        ))), assert_ok!(parse_str::<ItemFn>("
+            fn expecting(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
+                ::std::result::Result::Ok(true)
+            }
+        ")));
+    }
+
+    #[test]
+    fn struct_expecting_markdown() {
+        assert_eq!(expecting(&assert_ok!(parse_str(
+            "
+            /// Container *documentation*, which is
+            /// hard-wrapped.
+            ///
+            /// ```
+            /// example();
+            /// ```
+            struct Foo(usize);"
         ))), assert_ok!(parse_str::<ItemFn>("
             fn expecting(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
-                match formatter.width() {
-                    ::std::option::Option::Some(0) => {
-                        formatter.write_str(\"Bar documentation.\")?;
-                        ::std::result::Result::Ok(true)
-                    }
-                    ::std::option::Option::Some(1) => {
-                        formatter.write_str(\"Baz documentation.\")?;
-                        ::std::result::Result::Ok(true)
-                    }
-                    _ => {
-                        formatter.write_str(\"Container documentation.\")?;
-                        ::std::result::Result::Ok(true)
-                    }
-                }
+                formatter.write_str(\"Container documentation, which is hard-wrapped.\")?;
+                ::std::result::Result::Ok(true)
             }
         ")));
     }
+
+    #[test]
+    fn struct_entries() {
+        assert_eq!(
+            entries(&assert_ok!(parse_str(
+                "
+                /// Container documentation.
+                struct Foo(
+                    /// Bar documentation.
+                    usize,
+                    /// Baz documentation.
+                    String
+                );"
+            ))),
+            vec![
+                Entry::new("description", Some(0), "Bar documentation."),
+                Entry::new("description", Some(1), "Baz documentation."),
+            ]
+        );
+    }
+
+    #[test]
+    fn enum_entries() {
+        assert_eq!(
+            entries(&assert_ok!(parse_str(
+                "
+                /// Container documentation.
+                enum Foo {
+                    /// Bar documentation.
+                    Bar,
+                    /// Baz documentation.
+                    Baz,
+                }"
+            ))),
+            vec![
+                Entry::new("description", Some(0), "Bar documentation."),
+                Entry::new("description", Some(1), "Baz documentation."),
+            ]
+        );
+    }
+
+    #[test]
+    fn enum_entries_groups() {
+        assert_eq!(
+            entries(&assert_ok!(parse_str(
+                "
+                /// Container documentation.
+                enum Foo {
+                    /// [Repository]
+                    /// Bar documentation.
+                    Bar,
+                    /// Baz documentation.
+                    Baz,
+                }"
+            ))),
+            vec![
+                Entry::new("description", Some(0), "Bar documentation."),
+                Entry::new("description", Some(1), "Baz documentation."),
+                Entry::new("group", Some(0), "Repository"),
+            ]
+        );
+    }
+
+    #[test]
+    fn enum_entries_groups_escaped() {
+        assert_eq!(
+            entries(&assert_ok!(parse_str(
+                "
+                /// Container documentation.
+                enum Foo {
+                    /// [\"Quoted\" \\ Group]
+                    /// Bar documentation.
+                    Bar,
+                }"
+            ))),
+            vec![
+                Entry::new("description", Some(0), "Bar documentation."),
+                Entry::new("group", Some(0), "\"Quoted\" \\ Group"),
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_entries_examples() {
+        assert_eq!(
+            entries(&assert_ok!(parse_str(
+                "
+                /// Container documentation.
+                ///
+                /// # Examples
+                ///
+                /// ```text
+                /// $ foo 42 bar
+                /// ```
+                struct Foo(
+                    /// Bar documentation.
+                    usize,
+                    /// Baz documentation.
+                    String
+                );"
+            ))),
+            vec![
+                Entry::new("description", Some(0), "Bar documentation."),
+                Entry::new("description", Some(1), "Baz documentation."),
+                Entry::new("examples", None, "$ foo 42 bar"),
+            ]
+        );
+    }
+
+    #[test]
+    fn struct_entries_markdown() {
+        assert_eq!(
+            entries(&assert_ok!(parse_str(
+                "
+                struct Foo(
+                    /// Bar `documentation`:
+                    /// - first
+                    /// - second
+                    usize,
+                    /// Baz \"documentation\".
+                    String,
+                    usize
+                );"
+            ))),
+            vec![
+                Entry::new(
+                    "description",
+                    Some(0),
+                    "Bar documentation:\n  - first\n  - second"
+                ),
+                Entry::new("description", Some(1), "Baz \"documentation\"."),
+            ]
+        );
+    }
 }
@@ -9,11 +9,17 @@
 //! [`Deserialize`]: https://docs.rs/serde/latest/serde/derive.Deserialize.html
 
 mod attributes;
+mod constraints;
 mod container;
+mod field;
 mod generate;
 mod help;
+mod lint;
+mod record;
+mod settings;
 #[cfg(test)]
 mod test;
+mod variant;
 mod version;
 
 use container::Container;
@@ -33,6 +39,12 @@ use proc_macro::TokenStream;
 ///
 /// - `doc_help`
 /// - `version`
+/// - `no_auto_help`
+/// - `no_empty_help`
+/// - `lint`
+/// - `one_of(...)`
+/// - `at_most_one(...)`
+/// - `all_or_none(...)`
 ///
 /// `doc_help` will generate help messages for the container, along with its fields/variants, using
 /// the item's doc comments. For example, using doc help on the following struct:
@@ -62,6 +74,20 @@ use proc_macro::TokenStream;
 /// each of the fields with the messages "An example program.", "The file to be operated on.", and
 /// "Whether the program's behavior should be forced."
 ///
+/// Doc comments are rendered from Markdown into plain text. Hard-wrapped lines are joined into
+/// paragraphs, inline code and emphasis markers are removed, list items are placed on their own
+/// indented lines, and code blocks are omitted.
+///
+/// A variant's doc comment may begin with a line containing only a group tag, such as
+/// `/// [Repository]`. The tag is stripped from the variant's message, and the variant is listed
+/// under a "Repository" heading in the help output. Untagged variants are listed under the default
+/// heading.
+///
+/// Everything following an `# Examples` heading in the container's doc comment is displayed at the
+/// end of the help output under an "EXAMPLES" heading instead of being included in the container's
+/// message. Examples are displayed as written, without being rendered or wrapped, though code
+/// fences surrounding them are removed.
+///
 /// `version` will activate the `--version` optional flag and include your crate's version,
 /// extracted from your `Cargo.toml`'s `version` field. For example, it can be enabled by:
 ///
@@ -80,12 +106,122 @@ use proc_macro::TokenStream;
 /// # fn main() {}
 /// ```
 ///
+/// `no_auto_help` and `no_empty_help` change the default parser behavior for the type. These are
+/// intended for types parsed by a library on behalf of an application, where the automatic
+/// behavior would be unexpected. `no_auto_help` disables the automatic `-h` and `--help` options,
+/// and `no_empty_help` reports missing arguments as an error instead of displaying help when no
+/// arguments are provided. Only the parameters of the top-level type are respected, and either
+/// can still be overridden through `serde_args::Parser`.
+///
+/// `lint` checks the container's definition for mistakes that would otherwise only be noticed when
+/// using the command line interface, reporting each as a compile error on the offending field or
+/// variant:
+///
+/// - Two fields or variants sharing a name or alias.
+/// - A field or variant with a single-character name and a longer alias, since the longer name
+///   should be the primary name.
+/// - A public field without a doc comment, when `doc_help` is also provided.
+/// - A field or variant name that is not kebab-case, unless it is renamed using `rename` or the
+///   container's `rename_all` (or `rename_all_fields`, for fields of enum variants).
+///
+/// `one_of`, `at_most_one`, and `all_or_none` constrain which options of a struct may be given
+/// together, each naming two or more of the struct's fields. `one_of` requires exactly one of the
+/// fields to be given, `at_most_one` allows no more than one, and `all_or_none` requires either
+/// every field or none of them. The fields must be optional fields or boolean flags, and any number
+/// of constraints may be declared:
+///
+/// ``` rust
+/// use serde::Deserialize;
+/// use std::path::PathBuf;
+///
+/// #[serde_args_macros::generate(one_of(stdin, file), all_or_none(user, password))]
+/// #[derive(Deserialize)]
+/// struct Args {
+///     stdin: bool,
+///     file: Option<PathBuf>,
+///     user: Option<String>,
+///     password: Option<String>,
+/// }
+/// #
+/// # fn main() {}
+/// ```
+///
+/// Unsatisfied constraints are reported as errors after parsing, listing the options involved.
+///
 /// These parameters can also be combined. `#[serde_args::generate(version, doc_help)]` will
 /// generate both results on the same container.
 ///
 /// Note that this attribute will wrap the serialized/deserialized type in a newtype. This has no
 /// effect on `serde_args`, but it could affect other formats if the same type is used across
 /// multiple formats.
+///
+/// # Field Attributes
+///
+/// Fields (including fields of enum variants) can also be annotated with
+/// `#[serde_args(...)]` attributes, which are replaced with the equivalent `serde` attributes
+/// where possible. The following field parameters are available:
+///
+/// - `allow_hyphen_values` - Allows a required struct field to accept values beginning with a
+///   hyphen, such as `-v` or `-1`, rather than parsing them as options. This parameter is not
+///   supported on fields of enum variants.
+/// - `cidr` - Deserializes the field as an IP network in CIDR notation using
+///   `serde_args::helpers::cidr::deserialize()`. The field's type must implement `From<(IpAddr,
+///   u8)>`.
+/// - `flags` - Gives each variant of an `Option<T>` struct field containing an enum of unit
+///   variants as its own flag, such as `--json` or `--yaml`, rather than as an option taking the
+///   variant as a value. At most one of the flags may be given, and the field is `None` if none
+///   are. Combined with `required`, exactly one of the flags must be given. This parameter is not
+///   supported on fields of enum variants.
+/// - `multiple` - Allows an `Option<Vec<T>>` struct field to take several values per occurrence,
+///   such as `--nums 1 2 3`. Values are taken until the next option, `--`, or the end of the
+///   arguments, so a positional argument following the values must be preceded by `--` or another
+///   option. The option may be repeated to provide more values. This parameter is not supported on
+///   fields of enum variants.
+/// - `positional` - Parses an `Option<T>` struct field containing a primitive value as an optional
+///   positional argument, rather than as an option. The field must come after every other
+///   positional argument, and is `None` if no argument remains for it. This parameter is not
+///   supported on fields of enum variants.
+/// - `required` - Requires an `Option<T>` struct field parsed as an option to be given, reporting
+///   an error if it is missing. The option is displayed alongside the positional arguments in usage
+///   messages. This parameter is not supported on fields of enum variants.
+/// - `trailing` - Captures all remaining arguments verbatim in a `Vec<OsString>` struct field,
+///   including options and `--`, using `serde_args::helpers::os_strings::deserialize()`. Options
+///   are still recognized until the field's first value, or until an unrecognized option is found.
+///   The field must be the last positional argument. This parameter is not supported on fields of
+///   enum variants.
+/// - `value_delimiter = ','` - Allows an `Option<Vec<T>>` struct field to be given as a list
+///   separated by the given ASCII character, such as `--tags a,b,c`. Each occurrence of the option
+///   then takes a single value, and the option may be repeated to provide more values. This
+///   parameter is not supported on fields of enum variants.
+/// - `value_name = "..."` - Displays a required struct field as the given name (such as `<FILE>`)
+///   in usage and error messages, instead of the field's name. This parameter is not supported on
+///   fields of enum variants.
+///
+/// See the `serde_args::helpers::cidr` module for an example.
+///
+/// # Variant Attributes
+///
+/// Enum variants can also be annotated with `#[serde_args(...)]` attributes. The following variant
+/// parameters are available:
+///
+/// - `raw` - Parses every argument following the variant's name as a positional argument, as if
+///   `--` were given immediately after it. Arguments such as `--foo` are then values of the
+///   variant's fields, which is useful for commands such as `exec` that pass their arguments on to
+///   another program. The variant cannot contain optional or boolean fields.
+///
+/// ``` rust
+/// use serde::Deserialize;
+///
+/// #[serde_args_macros::generate]
+/// #[derive(Deserialize)]
+/// enum Command {
+///     #[serde_args(raw)]
+///     Exec(Vec<String>),
+///     Status,
+/// }
+/// #
+/// # fn main() {}
+/// ```
 #[proc_macro_attribute]
 pub fn generate(attr: TokenStream, item: TokenStream) -> TokenStream {
     generate::process(attr.into(), item.into()).into()
@@ -0,0 +1,489 @@
+//! Compile-time lints for command line definitions.
+//!
+//! These are enabled by the `lint` parameter, and catch mistakes in a container's definition that
+//! would otherwise only be noticed when using the resulting command line interface.
+
+use crate::Container;
+use proc_macro2::Span;
+use std::collections::HashSet;
+use syn::{
+    punctuated::Punctuated,
+    Attribute,
+    Expr,
+    ExprLit,
+    Fields,
+    Ident,
+    Lit,
+    LitStr,
+    Meta,
+    Token,
+    Visibility,
+};
+
+/// Returns the string values given to `key` within any `#[serde(...)]` attributes.
+pub(crate) fn serde_values(attrs: &[Attribute], key: &str) -> Vec<LitStr> {
+    let mut values = Vec::new();
+    for attribute in attrs {
+        if !attribute.path().is_ident("serde") {
+            continue;
+        }
+        // Attributes that cannot be parsed are left for `serde` to report.
+        if let Ok(metas) =
+            attribute.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+        {
+            for meta in metas {
+                if let Meta::NameValue(name_value) = meta {
+                    if let Expr::Lit(ExprLit {
+                        lit: Lit::Str(value),
+                        ..
+                    }) = name_value.value
+                    {
+                        if name_value.path.is_ident(key) {
+                            values.push(value);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    values
+}
+
+fn is_kebab_case(name: &str) -> bool {
+    name.chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// A field or variant, as seen by the lints.
+struct Key<'a> {
+    /// Either `"field"` or `"variant"`, for use in messages.
+    kind: &'static str,
+    ident: &'a Ident,
+    attrs: &'a [Attribute],
+    /// Whether the key is public, and therefore must be documented when using `doc_help`.
+    public: bool,
+}
+
+/// Lints a set of keys that share a namespace on the command line.
+///
+/// `renamed` indicates whether the keys are renamed by a container attribute, and `rename_all` is
+/// the name of the attribute to suggest when a key is not kebab-case.
+fn lint_keys(
+    keys: &[Key],
+    renamed: bool,
+    rename_all: &str,
+    doc_help: bool,
+    errors: &mut Vec<syn::Error>,
+) {
+    let mut names = HashSet::new();
+    for key in keys {
+        let rename = serde_values(key.attrs, "rename").pop();
+        let name = match &rename {
+            Some(rename) => rename.value(),
+            None => key.ident.to_string().trim_start_matches("r#").to_owned(),
+        };
+        let name_span = rename
+            .as_ref()
+            .map(LitStr::span)
+            .unwrap_or_else(|| key.ident.span());
+
+        if !renamed && rename.is_none() && !is_kebab_case(&name) {
+            errors.push(syn::Error::new(
+                name_span,
+                format!(
+                    "{} `{}` is not kebab-case; add `#[serde({} = \"kebab-case\")]` to the container or rename the {}",
+                    key.kind, name, rename_all, key.kind
+                ),
+            ));
+        }
+        if doc_help
+            && key.public
+            && !key
+                .attrs
+                .iter()
+                .any(|attribute| attribute.path().is_ident("doc"))
+        {
+            errors.push(syn::Error::new(
+                key.ident.span(),
+                format!(
+                    "missing doc comment on public {} `{}`, which is needed by `doc_help`",
+                    key.kind, name
+                ),
+            ));
+        }
+
+        if !names.insert(name.clone()) {
+            errors.push(duplicate(name_span, &name));
+        }
+        for alias in serde_values(key.attrs, "alias") {
+            let alias_name = alias.value();
+            if name.chars().count() == 1 && alias_name.chars().count() > 1 {
+                errors.push(syn::Error::new(
+                    alias.span(),
+                    format!(
+                        "alias `{}` is longer than the name of {} `{}`; name the {} `{}` and alias it as `{}` instead",
+                        alias_name, key.kind, name, key.kind, alias_name, name
+                    ),
+                ));
+            }
+            if !names.insert(alias_name.clone()) {
+                errors.push(duplicate(alias.span(), &alias_name));
+            }
+        }
+    }
+}
+
+fn duplicate(span: Span, name: &str) -> syn::Error {
+    syn::Error::new(
+        span,
+        format!(
+            "`{}` is already used as a name or alias in this container",
+            name
+        ),
+    )
+}
+
+/// Lints the named fields, which share a namespace on the command line.
+fn lint_fields(
+    fields: &Fields,
+    renamed: bool,
+    rename_all: &str,
+    doc_help: bool,
+    errors: &mut Vec<syn::Error>,
+) {
+    if let Fields::Named(fields) = fields {
+        let keys = fields
+            .named
+            .iter()
+            .filter_map(|field| {
+                field.ident.as_ref().map(|ident| Key {
+                    kind: "field",
+                    ident,
+                    attrs: &field.attrs,
+                    public: !matches!(field.vis, Visibility::Inherited),
+                })
+            })
+            .collect::<Vec<_>>();
+        lint_keys(&keys, renamed, rename_all, doc_help, errors);
+    }
+}
+
+/// Checks the container's definition for command line mistakes.
+///
+/// Every mistake found is reported, each spanned to the offending field or variant.
+pub(crate) fn lint(container: &Container, doc_help: bool) -> Result<(), syn::Error> {
+    let mut errors = Vec::new();
+    match container {
+        Container::Struct(item) => {
+            let renamed = !serde_values(&item.attrs, "rename_all").is_empty();
+            lint_fields(&item.fields, renamed, "rename_all", doc_help, &mut errors);
+        }
+        Container::Enum(item) => {
+            let keys = item
+                .variants
+                .iter()
+                .map(|variant| Key {
+                    kind: "variant",
+                    ident: &variant.ident,
+                    attrs: &variant.attrs,
+                    // Variants have no visibility of their own, and are documented as commands
+                    // through the container's `doc_help` rendering.
+                    public: false,
+                })
+                .collect::<Vec<_>>();
+            lint_keys(
+                &keys,
+                !serde_values(&item.attrs, "rename_all").is_empty(),
+                "rename_all",
+                doc_help,
+                &mut errors,
+            );
+            let fields_renamed = !serde_values(&item.attrs, "rename_all_fields").is_empty();
+            for variant in &item.variants {
+                lint_fields(
+                    &variant.fields,
+                    fields_renamed || !serde_values(&variant.attrs, "rename_all").is_empty(),
+                    "rename_all_fields",
+                    doc_help,
+                    &mut errors,
+                );
+            }
+        }
+    }
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut error) => {
+            error.extend(errors);
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::lint;
+    use claims::{
+        assert_err,
+        assert_ok,
+    };
+    use syn::parse_str;
+
+    fn messages(error: syn::Error) -> Vec<String> {
+        error.into_iter().map(|error| error.to_string()).collect()
+    }
+
+    #[test]
+    fn lint_struct_valid() {
+        assert_ok!(lint(
+            &assert_ok!(parse_str(
+                "
+                #[derive(Deserialize)]
+                #[serde(rename_all = \"kebab-case\")]
+                struct Foo {
+                    #[serde(alias = \"b\")]
+                    bar_baz: usize,
+                    qux: String,
+                }"
+            )),
+            false
+        ));
+    }
+
+    #[test]
+    fn lint_struct_not_kebab_case() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    struct Foo {
+                        bar_baz: usize,
+                    }"
+                )),
+                false
+            ))),
+            ["field `bar_baz` is not kebab-case; add `#[serde(rename_all = \"kebab-case\")]` to the container or rename the field"]
+        );
+    }
+
+    #[test]
+    fn lint_struct_renamed_field_not_kebab_case() {
+        assert_ok!(lint(
+            &assert_ok!(parse_str(
+                "
+                #[derive(Deserialize)]
+                struct Foo {
+                    #[serde(rename = \"BAR\")]
+                    bar: usize,
+                }"
+            )),
+            false
+        ));
+    }
+
+    #[test]
+    fn lint_struct_raw_identifier() {
+        assert_ok!(lint(
+            &assert_ok!(parse_str(
+                "
+                #[derive(Deserialize)]
+                struct Foo {
+                    r#type: usize,
+                }"
+            )),
+            false
+        ));
+    }
+
+    #[test]
+    fn lint_struct_duplicate_alias() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    struct Foo {
+                        #[serde(alias = \"b\")]
+                        bar: usize,
+                        #[serde(alias = \"b\")]
+                        baz: usize,
+                    }"
+                )),
+                false
+            ))),
+            ["`b` is already used as a name or alias in this container"]
+        );
+    }
+
+    #[test]
+    fn lint_struct_alias_matches_field_name() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    struct Foo {
+                        bar: usize,
+                        #[serde(alias = \"bar\")]
+                        baz: usize,
+                    }"
+                )),
+                false
+            ))),
+            ["`bar` is already used as a name or alias in this container"]
+        );
+    }
+
+    #[test]
+    fn lint_struct_long_alias_on_short_name() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    struct Foo {
+                        #[serde(alias = \"verbose\")]
+                        v: bool,
+                    }"
+                )),
+                false
+            ))),
+            ["alias `verbose` is longer than the name of field `v`; name the field `verbose` and alias it as `v` instead"]
+        );
+    }
+
+    #[test]
+    fn lint_struct_missing_doc_on_public_field() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    pub struct Foo {
+                        /// Documented.
+                        pub bar: usize,
+                        pub baz: usize,
+                        qux: usize,
+                    }"
+                )),
+                true
+            ))),
+            ["missing doc comment on public field `baz`, which is needed by `doc_help`"]
+        );
+    }
+
+    #[test]
+    fn lint_struct_missing_doc_without_doc_help() {
+        assert_ok!(lint(
+            &assert_ok!(parse_str(
+                "
+                #[derive(Deserialize)]
+                pub struct Foo {
+                    pub bar: usize,
+                }"
+            )),
+            false
+        ));
+    }
+
+    #[test]
+    fn lint_struct_multiple() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    struct Foo {
+                        #[serde(alias = \"b\")]
+                        bar_baz: usize,
+                        #[serde(alias = \"b\")]
+                        qux: usize,
+                    }"
+                )),
+                false
+            ))),
+            [
+                "field `bar_baz` is not kebab-case; add `#[serde(rename_all = \"kebab-case\")]` to the container or rename the field",
+                "`b` is already used as a name or alias in this container",
+            ]
+        );
+    }
+
+    #[test]
+    fn lint_enum_valid() {
+        assert_ok!(lint(
+            &assert_ok!(parse_str(
+                "
+                #[derive(Deserialize)]
+                #[serde(rename_all = \"kebab-case\", rename_all_fields = \"kebab-case\")]
+                enum Foo {
+                    #[serde(alias = \"b\")]
+                    Bar,
+                    BazQux {
+                        dry_run: bool,
+                    },
+                }"
+            )),
+            false
+        ));
+    }
+
+    #[test]
+    fn lint_enum_not_kebab_case() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    enum Foo {
+                        Bar,
+                    }"
+                )),
+                false
+            ))),
+            ["variant `Bar` is not kebab-case; add `#[serde(rename_all = \"kebab-case\")]` to the container or rename the variant"]
+        );
+    }
+
+    #[test]
+    fn lint_enum_variant_fields_not_kebab_case() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    #[serde(rename_all = \"kebab-case\")]
+                    enum Foo {
+                        Bar {
+                            dry_run: bool,
+                        },
+                    }"
+                )),
+                false
+            ))),
+            ["field `dry_run` is not kebab-case; add `#[serde(rename_all_fields = \"kebab-case\")]` to the container or rename the field"]
+        );
+    }
+
+    #[test]
+    fn lint_enum_duplicate_alias() {
+        assert_eq!(
+            messages(assert_err!(lint(
+                &assert_ok!(parse_str(
+                    "
+                    #[derive(Deserialize)]
+                    #[serde(rename_all = \"kebab-case\")]
+                    enum Foo {
+                        #[serde(alias = \"b\")]
+                        Bar,
+                        #[serde(alias = \"b\")]
+                        Baz,
+                    }"
+                )),
+                false
+            ))),
+            ["`b` is already used as a name or alias in this container"]
+        );
+    }
+}
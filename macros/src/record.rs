@@ -0,0 +1,153 @@
+//! The settings record communicated through the generated `expecting()` function.
+//!
+//! Everything `serde_args` is told beyond what `serde` provides, such as field attributes without
+//! `serde` equivalents or documentation of the container's keys, is written as a single record
+//! when `formatter.fill()` is `'s'`.
+
+use syn::{
+    parse_str,
+    ItemFn,
+};
+
+/// A single entry of the settings record.
+///
+/// Each entry is a setting, the index of the key it applies to, and its value, separated by
+/// `\x1F`. The index is empty for settings of the container itself, and the value is empty for
+/// settings that are simply enabled. The entry is stored as an expression evaluating to a
+/// `&'static str`.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct Entry(String);
+
+impl Entry {
+    /// Returns an entry providing `value` for `setting`.
+    pub(crate) fn new(setting: &str, index: Option<usize>, value: &str) -> Self {
+        Self(format!(
+            "{:?}",
+            format!("{}{value}", prefix(setting, index))
+        ))
+    }
+
+    /// Returns an entry providing the value of `expr` for `setting`, where `expr` is an expression
+    /// accepted by `concat!`.
+    pub(crate) fn concat(setting: &str, index: Option<usize>, expr: &str) -> Self {
+        Self(format!(
+            "::std::concat!({:?}, {expr})",
+            prefix(setting, index)
+        ))
+    }
+}
+
+/// Returns the setting and index of an entry, each followed by the separator.
+fn prefix(setting: &str, index: Option<usize>) -> String {
+    let index = index.map(|index| index.to_string()).unwrap_or_default();
+    format!("{setting}\x1F{index}\x1F")
+}
+
+/// Returns an `expecting()` function writing the record made of `entries`, if there are any.
+///
+/// Each entry is prefixed with its length in bytes followed by a `:`, so that values can contain
+/// any text.
+pub(crate) fn expecting(entries: &[Entry]) -> Option<ItemFn> {
+    if entries.is_empty() {
+        return None;
+    }
+    let entries = entries
+        .iter()
+        .map(|entry| entry.0.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(parse_str(&format!("
+        fn expecting(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {{
+            if formatter.fill() == 's' {{
+                for entry in [{entries}] {{
+                    ::std::write!(formatter, \"{{}}:{{}}\", entry.len(), entry)?;
+                }}
+                ::std::result::Result::Ok(true)
+            }} else {{
+                ::std::result::Result::Ok(false)
+            }}
+        }}
+    ")).expect("could not generate record `expecting()` function"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        expecting,
+        Entry,
+    };
+    use claims::{
+        assert_none,
+        assert_ok,
+        assert_some_eq,
+    };
+    use syn::{
+        parse_str,
+        ItemFn,
+    };
+
+    #[test]
+    fn entry_new() {
+        assert_eq!(
+            Entry::new("value_name", Some(2), "FILE"),
+            Entry("\"value_name\\u{1f}2\\u{1f}FILE\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn entry_new_container() {
+        assert_eq!(
+            Entry::new("no_auto_help", None, ""),
+            Entry("\"no_auto_help\\u{1f}\\u{1f}\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn entry_new_escaped() {
+        assert_eq!(
+            Entry::new("group", Some(0), "\"Quoted\" \\ Group"),
+            Entry("\"group\\u{1f}0\\u{1f}\\\"Quoted\\\" \\\\ Group\"".to_owned())
+        );
+    }
+
+    #[test]
+    fn entry_concat() {
+        assert_eq!(
+            Entry::concat("version", None, "::std::env!(\"CARGO_PKG_VERSION\")"),
+            Entry(
+                "::std::concat!(\"version\\u{1f}\\u{1f}\", ::std::env!(\"CARGO_PKG_VERSION\"))"
+                    .to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn expecting_empty() {
+        assert_none!(expecting(&[]));
+    }
+
+    #[test]
+    fn expecting_entries() {
+        assert_some_eq!(
+            expecting(&[
+                Entry::new("flags", Some(1), ""),
+                Entry::concat("version", None, "::std::env!(\"CARGO_PKG_VERSION\")"),
+            ]),
+            assert_ok!(parse_str::<ItemFn>("
+                fn expecting(formatter: &mut ::std::fmt::Formatter) -> ::std::result::Result<bool, ::std::fmt::Error> {
+                    if formatter.fill() == 's' {
+                        for entry in [
+                            \"flags\\u{1f}1\\u{1f}\",
+                            ::std::concat!(\"version\\u{1f}\\u{1f}\", ::std::env!(\"CARGO_PKG_VERSION\"))
+                        ] {
+                            ::std::write!(formatter, \"{}:{}\", entry.len(), entry)?;
+                        }
+                        ::std::result::Result::Ok(true)
+                    } else {
+                        ::std::result::Result::Ok(false)
+                    }
+                }
+            "))
+        );
+    }
+}
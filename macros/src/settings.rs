@@ -0,0 +1,13 @@
+//! Parser settings declared on the container itself.
+
+use crate::record::Entry;
+
+/// Returns the record entry disabling the automatic `--help` option.
+pub(super) fn no_auto_help() -> Entry {
+    Entry::new("no_auto_help", None, "")
+}
+
+/// Returns the record entry disabling help when no arguments are provided.
+pub(super) fn no_empty_help() -> Entry {
+    Entry::new("no_empty_help", None, "")
+}
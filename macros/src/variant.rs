@@ -0,0 +1,178 @@
+//! Processing `#[serde_args(...)]` attributes on enum variants.
+//!
+//! These attributes have no `serde` equivalent, so they are consumed by the `generate` macro and
+//! communicated through the settings record instead.
+
+use crate::{
+    record::Entry,
+    Container,
+};
+use proc_macro2::Span;
+use syn::{
+    punctuated::Punctuated,
+    Attribute,
+    Ident,
+    Meta,
+    Token,
+};
+
+#[derive(Debug, Eq, PartialEq)]
+enum VariantParameter {
+    Raw,
+}
+
+fn take_variant_parameters(
+    attrs: &mut Vec<Attribute>,
+) -> Result<Vec<VariantParameter>, syn::Error> {
+    let mut parameters = Vec::new();
+    let mut error = None;
+    attrs.retain(|attribute| {
+        if !attribute.path().is_ident("serde_args") {
+            return true;
+        }
+        match attribute.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated) {
+            Ok(metas) => {
+                for meta in metas {
+                    match meta {
+                        Meta::Path(path) => match path.require_ident() {
+                            Ok(ident) if *ident == Ident::new("raw", Span::call_site()) => {
+                                parameters.push(VariantParameter::Raw);
+                            }
+                            Ok(ident) => {
+                                error.get_or_insert(syn::Error::new_spanned(
+                                    ident,
+                                    "invalid variant parameter; expected `raw`",
+                                ));
+                            }
+                            Err(path_error) => {
+                                error.get_or_insert(path_error);
+                            }
+                        },
+                        meta => {
+                            error.get_or_insert(syn::Error::new_spanned(
+                                meta,
+                                "invalid variant parameter; expected `raw`",
+                            ));
+                        }
+                    }
+                }
+            }
+            Err(parse_error) => {
+                error.get_or_insert(parse_error);
+            }
+        }
+        false
+    });
+    match error {
+        Some(error) => Err(error),
+        None => Ok(parameters),
+    }
+}
+
+/// Remove all `#[serde_args(...)]` variant attributes within the container.
+///
+/// The record entries providing the attributes that were given are returned.
+pub(crate) fn process(container: &mut Container) -> Result<Vec<Entry>, syn::Error> {
+    let item = match container {
+        Container::Enum(item) => item,
+        Container::Struct(_) => return Ok(Vec::new()),
+    };
+    let mut entries = Vec::new();
+    for (index, variant) in item.variants.iter_mut().enumerate() {
+        for parameter in take_variant_parameters(&mut variant.attrs)? {
+            match parameter {
+                VariantParameter::Raw => entries.push(Entry::new("raw", Some(index), "")),
+            }
+        }
+    }
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::process;
+    use crate::{
+        record::Entry,
+        Container,
+    };
+    use claims::{
+        assert_err,
+        assert_ok,
+    };
+    use syn::parse_str;
+
+    #[test]
+    fn process_no_variant_attributes() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                #[serde(alias = \"b\")]
+                Bar,
+            }"
+        ));
+
+        assert_eq!(assert_ok!(process(&mut container)), vec![]);
+
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                enum Foo {
+                    #[serde(alias = \"b\")]
+                    Bar,
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_struct() {
+        let mut container: Container = assert_ok!(parse_str("struct Foo { bar: usize }"));
+
+        assert_eq!(assert_ok!(process(&mut container)), vec![]);
+    }
+
+    #[test]
+    fn process_raw() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                Bar,
+                #[serde_args(raw)]
+                Baz(Vec<String>),
+            }"
+        ));
+
+        assert_eq!(
+            assert_ok!(process(&mut container)),
+            vec![Entry::new("raw", Some(1), ""),]
+        );
+
+        assert_eq!(
+            container,
+            assert_ok!(parse_str(
+                "
+                enum Foo {
+                    Bar,
+                    Baz(Vec<String>),
+                }"
+            ))
+        );
+    }
+
+    #[test]
+    fn process_unknown() {
+        let mut container: Container = assert_ok!(parse_str(
+            "
+            enum Foo {
+                #[serde_args(unknown)]
+                Bar,
+            }"
+        ));
+
+        assert_eq!(
+            format!("{}", assert_err!(process(&mut container))),
+            "invalid variant parameter; expected `raw`"
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Tests ensuring `skip_serializing` and `skip_deserializing` fields behave the same in other
+//! formats as they would without the macro.
+
+use claims::{
+    assert_err,
+    assert_ok,
+    assert_ok_eq,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_args_macros::generate;
+
+#[generate(doc_help)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Foo {
+    bar: u32,
+    /// Never serialized, but still deserialized.
+    #[serde(default, skip_serializing)]
+    baz: String,
+    /// Never deserialized, but still serialized.
+    #[serde(skip_deserializing)]
+    qux: u32,
+    /// Neither serialized nor deserialized.
+    #[serde(skip)]
+    quux: Vec<u8>,
+}
+
+/// The same type as `Foo`, without the macro applied.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Unannotated {
+    bar: u32,
+    #[serde(default, skip_serializing)]
+    baz: String,
+    #[serde(skip_deserializing)]
+    qux: u32,
+    #[serde(skip)]
+    quux: Vec<u8>,
+}
+
+#[generate(doc_help)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+enum Command {
+    Foo {
+        bar: u32,
+        #[serde(default, skip_serializing)]
+        baz: String,
+        #[serde(skip_deserializing)]
+        qux: u32,
+    },
+    #[serde(skip_serializing)]
+    Bar(u32),
+    #[serde(skip_deserializing)]
+    Baz(u32),
+}
+
+fn foo() -> Foo {
+    Foo {
+        bar: 1,
+        baz: "baz".into(),
+        qux: 2,
+        quux: vec![3],
+    }
+}
+
+fn unannotated() -> Unannotated {
+    Unannotated {
+        bar: 1,
+        baz: "baz".into(),
+        qux: 2,
+        quux: vec![3],
+    }
+}
+
+#[test]
+fn serialize() {
+    assert_ok_eq!(serde_json::to_string(&foo()), r#"{"bar":1,"qux":2}"#);
+}
+
+#[test]
+fn serialize_matches_unannotated() {
+    assert_ok_eq!(
+        serde_json::to_string(&foo()),
+        assert_ok!(serde_json::to_string(&unannotated()))
+    );
+}
+
+#[test]
+fn deserialize() {
+    assert_ok_eq!(
+        serde_json::from_str::<Foo>(r#"{"bar":1,"baz":"baz","qux":2,"quux":[3]}"#),
+        Foo {
+            bar: 1,
+            baz: "baz".into(),
+            qux: 0,
+            quux: vec![],
+        }
+    );
+}
+
+#[test]
+fn deserialize_matches_unannotated() {
+    let json = r#"{"bar":1,"baz":"baz","qux":2,"quux":[3]}"#;
+    let Foo {
+        bar,
+        baz,
+        qux,
+        quux,
+    } = assert_ok!(serde_json::from_str(json));
+
+    assert_ok_eq!(
+        serde_json::from_str::<Unannotated>(json),
+        Unannotated {
+            bar,
+            baz,
+            qux,
+            quux,
+        }
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let json = assert_ok!(serde_json::to_string(&foo()));
+
+    assert_ok_eq!(
+        serde_json::from_str::<Foo>(&json),
+        Foo {
+            bar: 1,
+            baz: String::new(),
+            qux: 0,
+            quux: vec![],
+        }
+    );
+}
+
+#[test]
+fn serialize_enum() {
+    assert_ok_eq!(
+        serde_json::to_string(&Command::Foo {
+            bar: 1,
+            baz: "baz".into(),
+            qux: 2,
+        }),
+        r#"{"Foo":{"bar":1,"qux":2}}"#
+    );
+    assert_ok_eq!(serde_json::to_string(&Command::Baz(3)), r#"{"Baz":3}"#);
+}
+
+#[test]
+fn serialize_enum_skipped_variant() {
+    assert_err!(serde_json::to_string(&Command::Bar(3)));
+}
+
+#[test]
+fn deserialize_enum() {
+    assert_ok_eq!(
+        serde_json::from_str::<Command>(r#"{"Foo":{"bar":1,"baz":"baz","qux":2}}"#),
+        Command::Foo {
+            bar: 1,
+            baz: "baz".into(),
+            qux: 0,
+        }
+    );
+    assert_ok_eq!(
+        serde_json::from_str::<Command>(r#"{"Bar":3}"#),
+        Command::Bar(3)
+    );
+    assert_err!(serde_json::from_str::<Command>(r#"{"Baz":3}"#));
+}
+
+#[test]
+fn roundtrip_enum() {
+    let json = assert_ok!(serde_json::to_string(&Command::Foo {
+        bar: 1,
+        baz: "baz".into(),
+        qux: 2,
+    }));
+
+    assert_ok_eq!(
+        serde_json::from_str::<Command>(&json),
+        Command::Foo {
+            bar: 1,
+            baz: String::new(),
+            qux: 0,
+        }
+    );
+}
@@ -0,0 +1,132 @@
+//! Tests ensuring serialize-only field attributes, such as `skip_serializing_if`, still work.
+
+use claims::{
+    assert_ok,
+    assert_ok_eq,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_args_macros::generate;
+use serde_assert::{
+    Deserializer,
+    Serializer,
+    Token,
+};
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+#[generate(doc_help)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct Foo {
+    #[serde(skip_serializing_if = "is_zero")]
+    bar: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    baz: Option<String>,
+    #[serde(skip_serializing_if = "Self::is_empty")]
+    qux: String,
+}
+
+impl Foo {
+    fn is_empty(value: &str) -> bool {
+        value.is_empty()
+    }
+}
+
+#[test]
+fn serialize() {
+    let value = Foo {
+        bar: 42,
+        baz: Some("baz".into()),
+        qux: "qux".into(),
+    };
+
+    let serializer = Serializer::builder().build();
+
+    assert_ok_eq!(
+        value.serialize(&serializer),
+        [
+            Token::NewtypeStruct { name: "Foo" },
+            Token::Struct {
+                name: "Foo",
+                len: 3,
+            },
+            Token::Field("bar"),
+            Token::U32(42),
+            Token::Field("baz"),
+            Token::Some,
+            Token::Str("baz".into()),
+            Token::Field("qux"),
+            Token::Str("qux".into()),
+            Token::StructEnd,
+        ]
+    );
+}
+
+#[test]
+fn serialize_skipped() {
+    let value = Foo {
+        bar: 0,
+        baz: None,
+        qux: String::new(),
+    };
+
+    let serializer = Serializer::builder().build();
+
+    assert_ok_eq!(
+        value.serialize(&serializer),
+        [
+            Token::NewtypeStruct { name: "Foo" },
+            Token::Struct {
+                name: "Foo",
+                len: 0,
+            },
+            Token::SkippedField("bar"),
+            Token::SkippedField("baz"),
+            Token::SkippedField("qux"),
+            Token::StructEnd,
+        ]
+    );
+}
+
+#[test]
+fn deserialize() {
+    let tokens = [
+        Token::NewtypeStruct { name: "Foo" },
+        Token::Struct {
+            name: "Foo",
+            len: 2,
+        },
+        Token::Field("bar"),
+        Token::U32(0),
+        Token::Field("qux"),
+        Token::Str(String::new()),
+        Token::StructEnd,
+    ];
+    let mut deserializer = Deserializer::builder(tokens).build();
+    assert_ok_eq!(
+        Foo::deserialize(&mut deserializer),
+        Foo {
+            bar: 0,
+            baz: None,
+            qux: String::new(),
+        }
+    );
+}
+
+#[test]
+fn roundtrip() {
+    let value = Foo {
+        bar: 42,
+        baz: None,
+        qux: "qux".into(),
+    };
+
+    let serializer = Serializer::builder().build();
+    let mut deserializer = Deserializer::builder(assert_ok!(value.serialize(&serializer))).build();
+
+    assert_ok_eq!(Foo::deserialize(&mut deserializer), value);
+}
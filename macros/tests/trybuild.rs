@@ -1,6 +1,9 @@
 // These tests are currently only run on nightly.
 //
 // Ideally they'd be run on stable, but the foreign_mod.rs test uses a nightly feature.
+//
+// The expected `.stderr` output is generated on nightly by running
+// `TRYBUILD=overwrite cargo +nightly test -p serde_args_macros --test trybuild`.
 #[rustversion::attr(not(nightly), ignore = "trybuild tests are only run on nightly")]
 #[test]
 fn trybuild() {
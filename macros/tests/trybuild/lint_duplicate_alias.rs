@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use serde_args_macros::generate;
+
+#[generate(lint)]
+#[derive(Deserialize)]
+struct Struct {
+    #[serde(alias = "f")]
+    force: bool,
+    #[serde(alias = "f")]
+    file: Option<String>,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use serde::Deserialize;
+use serde_args_macros::generate;
+
+#[generate(lint)]
+#[derive(Deserialize)]
+enum Command {
+    #[serde(rename = "clone")]
+    Clone {
+        dry_run: bool,
+    },
+    Push,
+}
+
+fn main() {}
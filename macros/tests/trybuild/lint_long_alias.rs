@@ -0,0 +1,11 @@
+use serde::Deserialize;
+use serde_args_macros::generate;
+
+#[generate(lint)]
+#[derive(Deserialize)]
+struct Struct {
+    #[serde(alias = "verbose")]
+    v: bool,
+}
+
+fn main() {}
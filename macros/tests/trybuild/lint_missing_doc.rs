@@ -0,0 +1,13 @@
+use serde::Deserialize;
+use serde_args_macros::generate;
+
+/// A program.
+#[generate(doc_help, lint)]
+#[derive(Deserialize)]
+pub struct Struct {
+    /// The file to operate on.
+    pub file: String,
+    pub force: bool,
+}
+
+fn main() {}
@@ -0,0 +1,553 @@
+//! Check a parsed context against a traced shape without deserializing.
+
+use crate::{
+    de::{
+        Deserializer,
+        Error,
+    },
+    parse::{
+        Context,
+        Segment,
+    },
+    trace::{
+        Field,
+        PrimitiveKind,
+        Shape,
+    },
+};
+use serde::de::{
+    Deserializer as _,
+    Error as _,
+    Visitor,
+};
+use std::{
+    fmt,
+    fmt::Formatter,
+    iter,
+};
+
+/// Checks that every segment of `context` is consumable by `shape`.
+///
+/// Values are checked only as far as the shape knows: primitive values are parsed according to
+/// their recorded kind, but the type being deserialized into is never invoked.
+pub(crate) fn check(context: &Context, shape: &Shape) -> Result<(), Error> {
+    let mut segments = context.segments.iter();
+    check_segments(&mut segments, shape)?;
+    finish(segments)
+}
+
+fn finish<'a, Segments>(mut segments: Segments) -> Result<(), Error>
+where
+    Segments: Iterator<Item = &'a Segment>,
+{
+    match segments.next() {
+        Some(_) => Err(mismatch()),
+        None => Ok(()),
+    }
+}
+
+fn mismatch() -> Error {
+    Error::custom("arguments do not match the expected shape")
+}
+
+fn check_segments<'a, Segments>(segments: &mut Segments, shape: &Shape) -> Result<(), Error>
+where
+    Segments: Iterator<Item = &'a Segment>,
+{
+    match shape {
+        Shape::Empty { .. } => Ok(()),
+        Shape::Primitive { name, kind, .. } => match segments.next() {
+            Some(Segment::Value(raw)) => check_primitive(raw, *kind, name),
+            _ => Err(mismatch()),
+        },
+        Shape::Boolean { name, .. } => match segments.next() {
+            Some(Segment::Value(raw)) => {
+                Deserializer::new(value_context(raw)).deserialize_bool(Accept { name })
+            }
+            _ => Err(mismatch()),
+        },
+        Shape::Optional(shape) => match segments.next() {
+            Some(Segment::Context(context)) => check(context, shape),
+            Some(_) => Err(mismatch()),
+            None => Ok(()),
+        },
+        Shape::Sequence(shape) => {
+            for segment in segments {
+                check_segments(&mut iter::once(segment), shape)?;
+            }
+            Ok(())
+        }
+        Shape::Struct {
+            required,
+            optional,
+            booleans,
+            ..
+        } => {
+            for segment in segments {
+                let mut field_segments = match segment {
+                    Segment::Context(field_context) => field_context.segments.iter(),
+                    _ => return Err(mismatch()),
+                };
+                let name = match field_segments.next() {
+                    Some(Segment::Identifier(name)) => name,
+                    _ => return Err(mismatch()),
+                };
+                if let Some(field) = find_field(required, name) {
+                    check_segments(&mut field_segments, &field.shape)?;
+                } else if let Some(field) = find_field(optional, name) {
+                    match field_segments.next() {
+                        Some(Segment::Context(context)) => check(context, &field.shape)?,
+                        Some(_) => return Err(mismatch()),
+                        None => {}
+                    }
+                } else if find_field(booleans, name).is_some() {
+                    match field_segments.next() {
+                        Some(Segment::Context(context)) if context.segments.is_empty() => {}
+                        Some(_) => return Err(mismatch()),
+                        None => {}
+                    }
+                } else {
+                    return Err(mismatch());
+                }
+                finish(field_segments)?;
+            }
+            Ok(())
+        }
+        Shape::Enum { variants, .. } => {
+            let name = match segments.next() {
+                Some(Segment::Identifier(name)) => name,
+                _ => return Err(mismatch()),
+            };
+            let variant = variants
+                .iter()
+                .find(|variant| {
+                    iter::once(&variant.name)
+                        .chain(&variant.aliases)
+                        .any(|variant_name| variant_name == name)
+                })
+                .ok_or_else(mismatch)?;
+            check_segments(segments, &variant.shape)
+        }
+        Shape::Variant {
+            name: variant_name,
+            shape,
+            ..
+        } => match segments.next() {
+            // The selected variant's shape is the one that was parsed into.
+            Some(Segment::Identifier(name)) if name == variant_name => {
+                check_segments(segments, shape)
+            }
+            _ => Err(mismatch()),
+        },
+    }
+}
+
+fn find_field<'a>(fields: &'a [Field], name: &str) -> Option<&'a Field> {
+    fields.iter().find(|field| {
+        iter::once(&field.name)
+            .chain(&field.aliases)
+            .any(|field_name| *field_name == name)
+    })
+}
+
+fn value_context(raw: &[u8]) -> Context {
+    Context {
+        segments: vec![Segment::Value(raw.to_vec())],
+    }
+}
+
+/// Parses `raw` the same way it would be during deserialization.
+fn check_primitive(raw: &[u8], kind: PrimitiveKind, name: &str) -> Result<(), Error> {
+    let deserializer = Deserializer::new(value_context(raw));
+    let visitor = Accept { name };
+    match kind {
+        PrimitiveKind::I8 => deserializer.deserialize_i8(visitor),
+        PrimitiveKind::I16 => deserializer.deserialize_i16(visitor),
+        PrimitiveKind::I32 => deserializer.deserialize_i32(visitor),
+        PrimitiveKind::I64 => deserializer.deserialize_i64(visitor),
+        PrimitiveKind::I128 => deserializer.deserialize_i128(visitor),
+        PrimitiveKind::U8 => deserializer.deserialize_u8(visitor),
+        PrimitiveKind::U16 => deserializer.deserialize_u16(visitor),
+        PrimitiveKind::U32 => deserializer.deserialize_u32(visitor),
+        PrimitiveKind::U64 => deserializer.deserialize_u64(visitor),
+        PrimitiveKind::U128 => deserializer.deserialize_u128(visitor),
+        PrimitiveKind::F32 => deserializer.deserialize_f32(visitor),
+        PrimitiveKind::F64 => deserializer.deserialize_f64(visitor),
+        PrimitiveKind::Char => deserializer.deserialize_char(visitor),
+        PrimitiveKind::Str => deserializer.deserialize_str(visitor),
+        PrimitiveKind::Bytes => deserializer.deserialize_bytes(visitor),
+        PrimitiveKind::Any(_) => deserializer.deserialize_any(visitor),
+    }
+}
+
+/// A visitor accepting any value, expecting the name recorded in the shape.
+///
+/// This allows errors to be reported with the same message the deserialized type would have
+/// given.
+struct Accept<'a> {
+    name: &'a str,
+}
+
+impl Visitor<'_> for Accept<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(self.name)
+    }
+
+    fn visit_bool<E>(self, _value: bool) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _value: i64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_i128<E>(self, _value: i128) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _value: u64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_u128<E>(self, _value: u128) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _value: f64) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_str<E>(self, _value: &str) -> Result<Self::Value, E> {
+        Ok(())
+    }
+
+    fn visit_bytes<E>(self, _value: &[u8]) -> Result<Self::Value, E> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check;
+    use crate::{
+        de::Error,
+        parse::{
+            Context,
+            Segment,
+        },
+        trace::{
+            Field,
+            PrimitiveKind,
+            Shape,
+            Variant,
+        },
+    };
+    use claims::{
+        assert_err_eq,
+        assert_ok,
+    };
+    use serde::de::Error as _;
+
+    fn primitive(name: &str, kind: PrimitiveKind) -> Shape {
+        Shape::Primitive {
+            name: name.to_owned(),
+            description: String::new(),
+            version: None,
+            kind,
+        }
+    }
+
+    fn field(name: &'static str, shape: Shape) -> Field {
+        Field::new(name, shape, 0)
+    }
+
+    fn values(values: &[&str]) -> Context {
+        Context {
+            segments: values
+                .iter()
+                .map(|value| Segment::Value(value.as_bytes().to_vec()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn check_empty() {
+        assert_ok!(check(
+            &Context { segments: vec![] },
+            &Shape::Empty {
+                description: String::new(),
+                version: None,
+            }
+        ));
+    }
+
+    #[test]
+    fn check_primitive() {
+        assert_ok!(check(
+            &values(&["42"]),
+            &primitive("u64", PrimitiveKind::U64)
+        ));
+    }
+
+    #[test]
+    fn check_primitive_invalid_type() {
+        assert_err_eq!(
+            check(&values(&["foo"]), &primitive("u64", PrimitiveKind::U64)),
+            Error::Unparsable {
+                field: None,
+                expected: "u64".to_owned(),
+                value: "foo".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn check_primitive_invalid_value() {
+        assert_err_eq!(
+            check(&values(&["256"]), &primitive("u8", PrimitiveKind::U8)),
+            Error::OutOfRange {
+                field: None,
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn check_primitive_char() {
+        assert_err_eq!(
+            check(
+                &values(&["ab"]),
+                &primitive("a character", PrimitiveKind::Char)
+            ),
+            Error::InvalidChar("ab".to_owned())
+        );
+    }
+
+    #[test]
+    fn check_primitive_str_not_numeric() {
+        assert_ok!(check(
+            &values(&["foo"]),
+            &primitive("a string", PrimitiveKind::Str)
+        ));
+    }
+
+    #[test]
+    fn check_primitive_any_numeric() {
+        assert_ok!(check(
+            &values(&["3"]),
+            &primitive("a name or code", PrimitiveKind::Any(&["foo", "bar"]))
+        ));
+    }
+
+    #[test]
+    fn check_primitive_any_name() {
+        assert_ok!(check(
+            &values(&["foo"]),
+            &primitive("a name or code", PrimitiveKind::Any(&["foo", "bar"]))
+        ));
+    }
+
+    #[test]
+    fn check_boolean_invalid_value() {
+        assert_err_eq!(
+            check(
+                &values(&["foo"]),
+                &Shape::Boolean {
+                    name: "a boolean".to_owned(),
+                    description: String::new(),
+                    version: None,
+                }
+            ),
+            Error::Unparsable {
+                field: None,
+                expected: "a boolean (true/false, yes/no, on/off, or 1/0)".to_owned(),
+                value: "foo".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn check_optional_present() {
+        assert_ok!(check(
+            &Context {
+                segments: vec![Segment::Context(values(&["42"]))],
+            },
+            &Shape::Optional(Box::new(primitive("u64", PrimitiveKind::U64)))
+        ));
+    }
+
+    #[test]
+    fn check_optional_not_present() {
+        assert_ok!(check(
+            &Context { segments: vec![] },
+            &Shape::Optional(Box::new(primitive("u64", PrimitiveKind::U64)))
+        ));
+    }
+
+    #[test]
+    fn check_sequence() {
+        assert_ok!(check(
+            &values(&["1", "2", "3"]),
+            &Shape::Sequence(Box::new(primitive("u64", PrimitiveKind::U64)))
+        ));
+    }
+
+    #[test]
+    fn check_sequence_invalid_element() {
+        assert_err_eq!(
+            check(
+                &values(&["1", "foo"]),
+                &Shape::Sequence(Box::new(primitive("u64", PrimitiveKind::U64)))
+            ),
+            Error::Unparsable {
+                field: None,
+                expected: "u64".to_owned(),
+                value: "foo".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn check_struct() {
+        assert_ok!(check(
+            &Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("foo"), Segment::Value("42".into())],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("bar"),
+                            Segment::Context(values(&["-1"])),
+                        ],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("baz"),
+                            Segment::Context(Context { segments: vec![] }),
+                        ],
+                    }),
+                ],
+            },
+            &Shape::Struct {
+                name: "",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![field("foo", primitive("u64", PrimitiveKind::U64))],
+                optional: vec![field("bar", primitive("i8", PrimitiveKind::I8))],
+                booleans: vec![field(
+                    "baz",
+                    Shape::Empty {
+                        description: String::new(),
+                        version: None,
+                    }
+                )],
+                constraints: vec![],
+            }
+        ));
+    }
+
+    #[test]
+    fn check_struct_invalid_optional_value() {
+        assert_err_eq!(
+            check(
+                &Context {
+                    segments: vec![Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("bar"),
+                            Segment::Context(values(&["foo"])),
+                        ],
+                    })],
+                },
+                &Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![field("bar", primitive("i8", PrimitiveKind::I8))],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Error::Unparsable {
+                field: None,
+                expected: "i8".to_owned(),
+                value: "foo".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn check_struct_unknown_field() {
+        assert_err_eq!(
+            check(
+                &Context {
+                    segments: vec![Segment::Context(Context {
+                        segments: vec![Segment::Identifier("qux")],
+                    })],
+                },
+                &Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Error::custom("arguments do not match the expected shape")
+        );
+    }
+
+    #[test]
+    fn check_variant() {
+        let variants = vec![Variant {
+            name: "foo",
+            description: String::new(),
+            version: None,
+            group: None,
+            raw: false,
+            aliases: vec![],
+            deprecated_aliases: Vec::new(),
+            shape: primitive("u64", PrimitiveKind::U64),
+        }];
+
+        assert_err_eq!(
+            check(
+                &Context {
+                    segments: vec![Segment::Identifier("foo"), Segment::Value("bar".into())],
+                },
+                &Shape::Variant {
+                    name: "foo",
+                    description: String::new(),
+                    version: None,
+                    shape: Box::new(primitive("u64", PrimitiveKind::U64)),
+                    enum_name: "",
+                    variants,
+                }
+            ),
+            Error::Unparsable {
+                field: None,
+                expected: "u64".to_owned(),
+                value: "bar".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn check_remaining_segments() {
+        assert_err_eq!(
+            check(&values(&["1", "2"]), &primitive("u64", PrimitiveKind::U64)),
+            Error::custom("arguments do not match the expected shape")
+        );
+    }
+}
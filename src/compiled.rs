@@ -0,0 +1,245 @@
+use crate::{
+    deserialize_args_with_shape,
+    parse::Config,
+    trace::{
+        trace_with_settings,
+        Shape,
+    },
+    Error,
+    HelpLabels,
+};
+use serde::de::Deserialize;
+use std::{
+    ffi::OsString,
+    fmt,
+    fmt::{
+        Debug,
+        Formatter,
+    },
+    marker::PhantomData,
+};
+
+/// Traces the shape of `D` ahead of time, returning a handle that can parse arguments repeatedly.
+///
+/// Every entry point such as [`from_env()`](crate::from_env()) traces the shape of the type being
+/// deserialized before parsing. Programs that parse many sets of arguments, such as test harnesses
+/// or long-lived processes dispatching commands, can instead trace once and reuse the result.
+///
+/// # Example
+///
+/// ``` rust
+/// let compiled = serde_args::compile::<u64>().unwrap();
+///
+/// assert_eq!(compiled.parse(["executable", "1"]).unwrap(), 1);
+/// assert_eq!(compiled.parse(["executable", "2"]).unwrap(), 2);
+/// ```
+pub fn compile<'de, D>() -> Result<Compiled<D>, Error>
+where
+    D: Deserialize<'de>,
+{
+    let (shape, settings) = trace_with_settings(PhantomData::<D>)?;
+    Ok(Compiled {
+        shape,
+        config: Config::from_settings(settings),
+        labels: HelpLabels::default(),
+        marker: PhantomData,
+    })
+}
+
+/// The traced shape of a type, returned by [`compile()`].
+pub struct Compiled<D> {
+    shape: Shape,
+    config: Config,
+    labels: HelpLabels,
+    marker: PhantomData<fn() -> D>,
+}
+
+impl<D> Compiled<D> {
+    /// Displays any help or error messages using the provided [`HelpLabels`] in place of the
+    /// default English labels.
+    pub fn with_labels(mut self, labels: HelpLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Deserialize from `args` without tracing the type again.
+    ///
+    /// This behaves the same as [`from_slice()`](crate::from_slice()). The first argument is the
+    /// path of the executable, which is used when displaying help and error messages.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `args` is empty.
+    pub fn parse<'de, Arg, Args>(&self, args: Args) -> Result<D, Error>
+    where
+        D: Deserialize<'de>,
+        Args: IntoIterator<Item = Arg>,
+        Arg: Into<OsString>,
+    {
+        deserialize_args_with_shape(
+            PhantomData::<D>,
+            self.shape.clone(),
+            self.config,
+            args.into_iter().map(Into::<OsString>::into),
+            self.labels.clone(),
+            None,
+        )
+    }
+}
+
+impl<D> Clone for Compiled<D> {
+    fn clone(&self) -> Self {
+        Self {
+            shape: self.shape.clone(),
+            config: self.config,
+            labels: self.labels.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<D> Debug for Compiled<D> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Compiled")
+            .field("shape", &self.shape)
+            .field("config", &self.config)
+            .field("labels", &self.labels)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compile;
+    use claims::{
+        assert_err,
+        assert_ok,
+        assert_ok_eq,
+    };
+    use serde::de::{
+        Deserialize,
+        Deserializer,
+    };
+    use serde_derive::Deserialize;
+    use std::sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    };
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Args {
+        name: String,
+        count: Option<u64>,
+    }
+
+    #[test]
+    fn parse() {
+        let compiled = assert_ok!(compile::<Args>());
+
+        assert_ok_eq!(
+            compiled.parse(["executable", "foo", "--count", "2"]),
+            Args {
+                name: "foo".into(),
+                count: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_repeatedly() {
+        let compiled = assert_ok!(compile::<Args>());
+
+        for (name, count) in [("foo", None), ("bar", Some(1)), ("baz", Some(42))] {
+            let count_string = count.map(|count: u64| count.to_string());
+            let args = ["executable", name].into_iter().chain(
+                count_string
+                    .as_deref()
+                    .map(|count| ["--count", count])
+                    .into_iter()
+                    .flatten(),
+            );
+            assert_ok_eq!(
+                compiled.parse(args),
+                Args {
+                    name: name.into(),
+                    count,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let compiled = assert_ok!(compile::<Args>());
+
+        assert_eq!(
+            format!(
+                "{}",
+                assert_err!(compiled.parse(["executable", "foo", "--count", "bar"]))
+            ),
+            "ERROR: invalid value for --count: expected u64, found 'bar'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+        );
+        // A failed parse does not affect later parses.
+        assert_ok!(compiled.parse(["executable", "foo"]));
+    }
+
+    #[test]
+    fn parse_different_commands() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        #[serde(rename_all = "lowercase")]
+        enum Command {
+            Build,
+            Test { filter: String },
+        }
+
+        let compiled = assert_ok!(compile::<Command>());
+
+        // Parsing selects a command within the shape, which must not carry over to later parses.
+        assert_ok_eq!(compiled.parse(["executable", "build"]), Command::Build);
+        assert_ok_eq!(
+            compiled.parse(["executable", "test", "foo"]),
+            Command::Test {
+                filter: "foo".into()
+            }
+        );
+    }
+
+    #[test]
+    fn traced_once() {
+        static DESERIALIZED: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug)]
+        struct Counted;
+
+        impl<'de> Deserialize<'de> for Counted {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                DESERIALIZED.fetch_add(1, Ordering::SeqCst);
+                Args::deserialize(deserializer).map(|_| Counted)
+            }
+        }
+
+        let compiled = assert_ok!(compile::<Counted>());
+        let traced = DESERIALIZED.load(Ordering::SeqCst);
+        assert!(traced > 0);
+
+        for _ in 0..100 {
+            assert_ok!(compiled.parse(["executable", "foo"]));
+        }
+
+        // Each parse deserializes exactly once, without tracing again.
+        assert_eq!(DESERIALIZED.load(Ordering::SeqCst), traced + 100);
+    }
+
+    #[test]
+    fn clone() {
+        let compiled = assert_ok!(compile::<Args>());
+        let cloned = compiled.clone();
+
+        assert_ok!(compiled.parse(["executable", "foo"]));
+        assert_ok!(cloned.parse(["executable", "bar"]));
+    }
+}
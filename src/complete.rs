@@ -0,0 +1,523 @@
+use crate::trace::{
+    trace,
+    Field,
+    Shape,
+    Variant,
+};
+use serde::de::{
+    Deserialize,
+    DeserializeSeed,
+};
+use std::{
+    ffi::OsString,
+    iter,
+    marker::PhantomData,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A possible next argument of a partially typed command line.
+///
+/// Returned by [`complete()`] and [`complete_seed()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Candidate {
+    text: String,
+    description: Option<String>,
+}
+
+impl Candidate {
+    fn new(text: String, description: &str) -> Self {
+        Self {
+            text,
+            description: if description.is_empty() {
+                None
+            } else {
+                Some(description.to_owned())
+            },
+        }
+    }
+
+    /// The argument, exactly as it would be typed.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The description of the field or variant the argument gives, if it has one.
+    ///
+    /// Descriptions are the same as those displayed in help messages, such as those provided by
+    /// `#[generate(doc_help)]`.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+/// Returns the arguments that may follow `words` on a command line for `D`, beginning with
+/// `current`.
+///
+/// `words` are the complete arguments typed so far, beginning with the path of the executable as
+/// in [`from_os_slice()`](crate::from_os_slice()), and `current` is the partially typed argument
+/// at the cursor. This is intended for shells that complete arguments dynamically by invoking the
+/// program itself.
+///
+/// The arguments are walked the same way they are parsed, stopping at the cursor rather than
+/// reporting errors. Candidates depend on what the position reached accepts:
+///
+/// - After an option taking a value, the accepted values are listed. These are the names of the
+///   variants of an enum, or `true` and `false` for a boolean. No candidates are returned for other
+///   values, such as paths, which the shell should complete itself.
+/// - Otherwise, the values accepted by the next positional argument are listed, followed by every
+///   option in scope that has not yet been given. Options are not listed after `--` or within a raw
+///   variant.
+///
+/// If `D` cannot be traced, no candidates are returned.
+///
+/// # Example
+///
+/// ``` rust
+/// # mod hidden {
+/// use serde::Deserialize;
+/// # }
+/// # use serde_derive::Deserialize;
+/// use std::ffi::OsString;
+///
+/// #[derive(Deserialize)]
+/// #[serde(rename_all = "kebab-case")]
+/// enum Command {
+///     Add { path: String },
+///     Commit { message: Option<String> },
+///     Push { force: bool },
+/// }
+///
+/// let candidates = serde_args::complete::<Command>(&[OsString::from("prog")], "c");
+///
+/// assert_eq!(candidates.len(), 1);
+/// assert_eq!(candidates[0].text(), "commit");
+/// ```
+pub fn complete<'de, D>(words: &[OsString], current: &str) -> Vec<Candidate>
+where
+    D: Deserialize<'de>,
+{
+    complete_seed(PhantomData::<D>, words, current)
+}
+
+/// Returns the arguments that may follow `words` on a command line for `seed`, beginning with
+/// `current`.
+///
+/// This function behaves the same as [`complete()`], except that the type is traced using the
+/// provided seed.
+pub fn complete_seed<'de, D>(seed: D, words: &[OsString], current: &str) -> Vec<Candidate>
+where
+    D: Copy + DeserializeSeed<'de>,
+{
+    let shape = match trace(seed) {
+        Ok(shape) => shape,
+        Err(_) => return Vec::new(),
+    };
+    let mut position = Position::default();
+    position.enter(&shape);
+    for word in words.iter().skip(1) {
+        position.advance(&word.to_string_lossy());
+    }
+    position
+        .candidates()
+        .into_iter()
+        .filter(|candidate| candidate.text.starts_with(current))
+        .collect()
+}
+
+/// The position reached while walking the arguments typed so far.
+#[derive(Default)]
+struct Position<'a> {
+    /// The shapes still expecting positional arguments, with the next one last.
+    positionals: Vec<&'a Shape>,
+    /// The options in scope, in the order they were declared.
+    options: Vec<&'a Field>,
+    /// The names of the fields of the options that have been given.
+    given: Vec<&'static str>,
+    /// The option whose value is expected next, if any.
+    option_value: Option<&'a Field>,
+    end_of_options: bool,
+}
+
+impl<'a> Position<'a> {
+    /// Brings the options of `shape` into scope and queues its positional arguments.
+    fn enter(&mut self, shape: &'a Shape) {
+        match shape {
+            Shape::Empty { .. } => {}
+            Shape::Optional(shape) => self.enter(shape),
+            Shape::Struct {
+                required,
+                optional,
+                booleans,
+                ..
+            } => {
+                self.options.extend(optional.iter().chain(booleans.iter()));
+                for field in required.iter().rev() {
+                    self.enter(&field.shape);
+                }
+            }
+            Shape::Primitive { .. }
+            | Shape::Boolean { .. }
+            | Shape::Sequence(_)
+            | Shape::Enum { .. }
+            | Shape::Variant { .. } => self.positionals.push(shape),
+        }
+    }
+
+    /// Moves past a complete argument.
+    ///
+    /// Arguments that cannot be parsed are skipped, so that completion can continue past them.
+    fn advance(&mut self, word: &str) {
+        if self.option_value.take().is_some() {
+            return;
+        }
+        if !self.end_of_options {
+            if word == "--" {
+                self.end_of_options = true;
+                return;
+            }
+            if let Some(option) = word.strip_prefix('-').filter(|option| !option.is_empty()) {
+                let option = option.strip_prefix('-').unwrap_or(option);
+                let (name, has_value) = match option.split_once('=') {
+                    Some((name, _)) => (name, true),
+                    None => (option, false),
+                };
+                if let Some(field) = self
+                    .options
+                    .iter()
+                    .rev()
+                    .find(|field| field.option_names().contains(&name))
+                {
+                    self.given.push(field.name);
+                    if !has_value && !field.flags && !matches!(field.shape, Shape::Empty { .. }) {
+                        self.option_value = Some(field);
+                    }
+                }
+                return;
+            }
+        }
+        match self.positionals.pop() {
+            Some(Shape::Enum { variants, .. }) => {
+                if let Some(variant) = variants
+                    .iter()
+                    .find(|variant| variant.find_name(word, false).is_some())
+                {
+                    self.end_of_options |= variant.raw;
+                    self.enter(&variant.shape);
+                }
+            }
+            Some(Shape::Variant {
+                name,
+                shape,
+                variants,
+                ..
+            }) => {
+                if word == *name {
+                    self.enter(shape);
+                } else if let Some(variant) = variants
+                    .iter()
+                    .find(|variant| variant.find_name(word, false).is_some())
+                {
+                    self.end_of_options |= variant.raw;
+                    self.enter(&variant.shape);
+                }
+            }
+            // Sequences consume every remaining positional argument.
+            Some(shape @ Shape::Sequence(_)) => self.positionals.push(shape),
+            _ => {}
+        }
+    }
+
+    /// The arguments that may be given at this position.
+    fn candidates(&self) -> Vec<Candidate> {
+        let mut candidates = Vec::new();
+        if let Some(field) = self.option_value {
+            value_candidates(&field.shape, &mut candidates);
+            return candidates;
+        }
+        if let Some(shape) = self.positionals.last() {
+            value_candidates(shape, &mut candidates);
+        }
+        if !self.end_of_options {
+            for field in &self.options {
+                if self.given.contains(&field.name) {
+                    continue;
+                }
+                for (names, _, description) in field.help_rows() {
+                    for name in names {
+                        let prefix = if name.graphemes(true).count() == 1 {
+                            "-"
+                        } else {
+                            "--"
+                        };
+                        candidates.push(Candidate::new(format!("{}{}", prefix, name), description));
+                    }
+                }
+            }
+        }
+        candidates
+    }
+}
+
+/// Adds the values accepted by `shape` that can be listed.
+fn value_candidates(shape: &Shape, candidates: &mut Vec<Candidate>) {
+    match shape {
+        Shape::Variant {
+            name,
+            description,
+            variants,
+            ..
+        } => {
+            candidates.push(Candidate::new((*name).to_owned(), description));
+            variant_candidates(variants, candidates);
+        }
+        Shape::Enum { variants, .. } => variant_candidates(variants, candidates),
+        Shape::Boolean { .. } => {
+            candidates.push(Candidate::new("true".to_owned(), ""));
+            candidates.push(Candidate::new("false".to_owned(), ""));
+        }
+        Shape::Optional(shape) | Shape::Sequence(shape) => value_candidates(shape, candidates),
+        Shape::Empty { .. } | Shape::Primitive { .. } | Shape::Struct { .. } => {}
+    }
+}
+
+/// Adds the names and aliases of each variant.
+fn variant_candidates(variants: &[Variant], candidates: &mut Vec<Candidate>) {
+    for variant in variants {
+        for name in iter::once(variant.name).chain(variant.aliases.iter().copied()) {
+            candidates.push(Candidate::new(name.to_owned(), &variant.description));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        complete,
+        complete_seed,
+        Candidate,
+    };
+    use serde::de::{
+        Deserialize,
+        DeserializeSeed,
+        Deserializer,
+    };
+    use serde_derive::Deserialize;
+    use std::{
+        ffi::OsString,
+        path::PathBuf,
+    };
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    #[allow(dead_code)]
+    enum Command {
+        Add {
+            path: PathBuf,
+        },
+        Commit {
+            #[serde(alias = "m")]
+            message: Option<String>,
+        },
+        Push {
+            #[serde(alias = "f")]
+            force: bool,
+        },
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    #[allow(dead_code)]
+    enum Format {
+        Json,
+        Yaml,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Export {
+        path: String,
+        format: Option<Format>,
+        compress: Option<bool>,
+        verbose: bool,
+    }
+
+    fn words(words: &[&str]) -> Vec<OsString> {
+        words.iter().map(OsString::from).collect()
+    }
+
+    fn texts(candidates: Vec<Candidate>) -> Vec<String> {
+        candidates
+            .into_iter()
+            .map(|candidate| candidate.text)
+            .collect()
+    }
+
+    #[test]
+    fn candidate_description() {
+        assert_eq!(Candidate::new("--foo".to_owned(), "").description(), None);
+        assert_eq!(
+            Candidate::new("--foo".to_owned(), "bar").description(),
+            Some("bar")
+        );
+    }
+
+    #[test]
+    fn complete_after_program_name() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog"]), "")),
+            vec!["add", "commit", "push"]
+        );
+    }
+
+    #[test]
+    fn complete_after_program_name_partial() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog"]), "pu")),
+            vec!["push"]
+        );
+    }
+
+    #[test]
+    fn complete_after_command() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog", "push"]), "")),
+            vec!["-f", "--force"]
+        );
+    }
+
+    #[test]
+    fn complete_after_command_partial() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog", "commit"]), "--")),
+            vec!["--message"]
+        );
+    }
+
+    #[test]
+    fn complete_after_given_option() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog", "push", "-f"]), "")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn complete_after_end_of_options() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog", "push", "--"]), "")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn complete_command_after_end_of_options() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog", "--"]), "")),
+            vec!["add", "commit", "push"]
+        );
+    }
+
+    #[test]
+    fn complete_after_option_expecting_value() {
+        assert_eq!(
+            texts(complete::<Command>(
+                &words(&["prog", "commit", "--message"]),
+                ""
+            )),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn complete_after_option_value() {
+        assert_eq!(
+            texts(complete::<Command>(
+                &words(&["prog", "commit", "-m", "foo"]),
+                ""
+            )),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn complete_after_unknown_command() {
+        assert_eq!(
+            texts(complete::<Command>(&words(&["prog", "foo"]), "")),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn complete_enum_option_value() {
+        assert_eq!(
+            texts(complete::<Export>(&words(&["prog", "--format"]), "")),
+            vec!["json", "yaml"]
+        );
+    }
+
+    #[test]
+    fn complete_boolean_option_value() {
+        assert_eq!(
+            texts(complete::<Export>(&words(&["prog", "--compress"]), "")),
+            vec!["true", "false"]
+        );
+    }
+
+    #[test]
+    fn complete_primitive_positional() {
+        assert_eq!(
+            texts(complete::<Export>(&words(&["prog", "--format=json"]), "")),
+            vec!["--compress", "--verbose"]
+        );
+    }
+
+    #[test]
+    fn complete_after_positional() {
+        assert_eq!(
+            texts(complete::<Export>(
+                &words(&["prog", "foo", "--verbose"]),
+                "--"
+            )),
+            vec!["--format", "--compress"]
+        );
+    }
+
+    #[test]
+    fn complete_untraceable() {
+        struct Untraceable;
+
+        impl<'de> Deserialize<'de> for Untraceable {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                deserializer.deserialize_any(serde::de::IgnoredAny)?;
+                Ok(Untraceable)
+            }
+        }
+
+        assert_eq!(complete::<Untraceable>(&words(&["prog"]), ""), Vec::new());
+    }
+
+    #[test]
+    fn complete_seed_after_command() {
+        #[derive(Clone, Copy)]
+        struct Seed;
+
+        impl<'de> DeserializeSeed<'de> for Seed {
+            type Value = Command;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                Command::deserialize(deserializer)
+            }
+        }
+
+        assert_eq!(
+            texts(complete_seed(Seed, &words(&["prog", "add"]), "")),
+            Vec::<String>::new()
+        );
+    }
+}
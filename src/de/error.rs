@@ -1,3 +1,7 @@
+use crate::quote::{
+    Escaped,
+    Quoted,
+};
 use serde::{
     de,
     de::{
@@ -12,6 +16,7 @@ use std::{
         Formatter,
     },
 };
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum Error {
@@ -19,10 +24,59 @@ pub(crate) enum Error {
     InvalidType(String, String),
     InvalidValue(String, String),
     InvalidLength(usize, String),
+    InvalidChar(String),
+    /// A value could not be parsed as the type expected.
+    Unparsable {
+        /// The field the value was given for, if any.
+        field: Option<String>,
+        expected: String,
+        value: String,
+    },
+    /// A float was written with a comma as its decimal separator.
+    DecimalComma {
+        /// The field the value was given for, if any.
+        field: Option<String>,
+        expected: String,
+        value: String,
+    },
+    InvalidKnownValue {
+        name: &'static str,
+        value: String,
+        reason: String,
+    },
+    OutOfRange {
+        /// The field the value was given for, if any.
+        field: Option<String>,
+        expected: String,
+        min: String,
+        max: String,
+        value: String,
+    },
+    /// An empty value was given for a type that cannot be empty, such as a number.
+    Empty {
+        /// The field the value was given for, if any.
+        field: Option<String>,
+        expected: String,
+    },
+    /// A zero value was given for a `NonZero*` integer type.
+    Zero {
+        /// The field the value was given for, if any.
+        field: Option<String>,
+    },
+    /// A path did not satisfy a constraint on the filesystem.
+    Path {
+        /// The field the path was given for, if any.
+        field: Option<String>,
+        path: String,
+        /// Why the path does not satisfy the constraint, such as "does not exist".
+        reason: &'static str,
+    },
     UnknownVariant(String, &'static [&'static str]),
     UnknownField(String, &'static [&'static str]),
     MissingField(&'static str),
     DuplicateField(&'static str),
+    /// Arguments were parsed into the context but never consumed while deserializing.
+    Unused(Vec<String>),
 }
 
 impl Display for Error {
@@ -32,27 +86,136 @@ impl Display for Error {
             Self::InvalidType(unexpected, expected) => write!(
                 formatter,
                 "invalid type: expected {}, found {}",
-                expected, unexpected
+                expected,
+                Escaped(unexpected.as_bytes())
             ),
             Self::InvalidValue(unexpected, expected) => write!(
                 formatter,
                 "invalid value: expected {}, found {}",
-                expected, unexpected
+                expected,
+                Escaped(unexpected.as_bytes())
             ),
             Self::InvalidLength(length, expected) => write!(
                 formatter,
                 "invalid length {}, expected {}",
                 length, expected
             ),
+            Self::InvalidChar(value) => {
+                write!(
+                    formatter,
+                    "expected a single character, got {}",
+                    Quoted(value.as_bytes())
+                )?;
+                // A single grapheme can still be composed of multiple characters, such as a flag
+                // emoji. This is easy to miss, so it is called out explicitly.
+                if value.graphemes(true).count() == 1 {
+                    write!(
+                        formatter,
+                        ", which is composed of {} characters",
+                        value.chars().count()
+                    )?;
+                }
+                Ok(())
+            }
+            Self::Unparsable {
+                field,
+                expected,
+                value,
+            } => {
+                formatter.write_str("invalid value")?;
+                if let Some(field) = field {
+                    write!(formatter, " for {}", field)?;
+                }
+                write!(
+                    formatter,
+                    ": expected {}, found {}",
+                    expected,
+                    Quoted(value.as_bytes())
+                )
+            }
+            Self::DecimalComma {
+                field,
+                expected,
+                value,
+            } => {
+                formatter.write_str("invalid value")?;
+                if let Some(field) = field {
+                    write!(formatter, " for {}", field)?;
+                }
+                write!(
+                    formatter,
+                    ": expected {}, found {}; use '.' as the decimal separator",
+                    expected,
+                    Quoted(value.as_bytes())
+                )
+            }
+            Self::InvalidKnownValue {
+                name,
+                value,
+                reason,
+            } => write!(
+                formatter,
+                "invalid {} {}: {}",
+                name,
+                Quoted(value.as_bytes()),
+                reason
+            ),
+            Self::OutOfRange {
+                field,
+                expected,
+                min,
+                max,
+                value,
+            } => {
+                formatter.write_str("invalid value")?;
+                if let Some(field) = field {
+                    write!(formatter, " for {}", field)?;
+                }
+                write!(
+                    formatter,
+                    ": expected {} between {} and {}, found {}",
+                    expected,
+                    min,
+                    max,
+                    Quoted(value.as_bytes())
+                )
+            }
+            Self::Empty { field, expected } => {
+                formatter.write_str("invalid value")?;
+                if let Some(field) = field {
+                    write!(formatter, " for {}", field)?;
+                }
+                write!(formatter, ": expected {}, found an empty value", expected)
+            }
+            Self::Zero { field } => {
+                formatter.write_str("value must be nonzero")?;
+                if let Some(field) = field {
+                    write!(formatter, " for {}", field)?;
+                }
+                Ok(())
+            }
+            Self::Path {
+                field,
+                path,
+                reason,
+            } => {
+                formatter.write_str("invalid path")?;
+                if let Some(field) = field {
+                    write!(formatter, " for {}", field)?;
+                }
+                write!(formatter, ": {} {}", Quoted(path.as_bytes()), reason)
+            }
             Self::UnknownVariant(variant, expected) => write!(
                 formatter,
                 "unknown command {}, expected one of {:?}",
-                variant, expected,
+                Escaped(variant.as_bytes()),
+                expected,
             ),
             Self::UnknownField(field, expected) => write!(
                 formatter,
                 "unexpected argument --{}, expected one of {:?}",
-                field, expected,
+                Escaped(field.as_bytes()),
+                expected,
             ),
             Self::MissingField(field) => write!(formatter, "missing argument <{}>", field,),
             Self::DuplicateField(field) => write!(
@@ -60,6 +223,104 @@ impl Display for Error {
                 "the argument --{} cannot be used multiple times",
                 field
             ),
+            Self::Unused(arguments) => {
+                formatter.write_str("unused arguments:")?;
+                for argument in arguments {
+                    write!(formatter, " {}", Escaped(argument.as_bytes()))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Error {
+    /// Creates an error for an integer value outside of the range of the expected type.
+    pub(super) fn out_of_range<T>(value: &str, expected: &dyn Expected, min: T, max: T) -> Self
+    where
+        T: Display,
+    {
+        Self::OutOfRange {
+            field: None,
+            expected: expected.to_string(),
+            min: min.to_string(),
+            max: max.to_string(),
+            value: value.to_owned(),
+        }
+    }
+
+    /// Creates an error for a value that could not be parsed as the expected type.
+    pub(super) fn unparsable(value: &str, expected: &dyn Expected) -> Self {
+        Self::Unparsable {
+            field: None,
+            expected: expected.to_string(),
+            value: value.to_owned(),
+        }
+    }
+
+    /// Creates an error for an empty value given for a type that cannot be empty.
+    pub(super) fn empty(expected: &dyn Expected) -> Self {
+        Self::Empty {
+            field: None,
+            expected: expected.to_string(),
+        }
+    }
+
+    /// Names the field an out of range, unparsable, empty, zero, comma decimal, or path
+    /// value was given for, if it is not already named.
+    pub(super) fn with_field(self, field: &str) -> Self {
+        match self {
+            Self::OutOfRange {
+                field: None,
+                expected,
+                min,
+                max,
+                value,
+            } => Self::OutOfRange {
+                field: Some(field.to_owned()),
+                expected,
+                min,
+                max,
+                value,
+            },
+            Self::Unparsable {
+                field: None,
+                expected,
+                value,
+            } => Self::Unparsable {
+                field: Some(field.to_owned()),
+                expected,
+                value,
+            },
+            Self::Empty {
+                field: None,
+                expected,
+            } => Self::Empty {
+                field: Some(field.to_owned()),
+                expected,
+            },
+            Self::Zero { field: None } => Self::Zero {
+                field: Some(field.to_owned()),
+            },
+            Self::Path {
+                field: None,
+                path,
+                reason,
+            } => Self::Path {
+                field: Some(field.to_owned()),
+                path,
+                reason,
+            },
+            Self::DecimalComma {
+                field: None,
+                expected,
+                value,
+            } => Self::DecimalComma {
+                field: Some(field.to_owned()),
+                expected,
+                value,
+            },
+            error => error,
         }
     }
 }
@@ -79,7 +340,14 @@ impl de::Error for Error {
     }
 
     fn invalid_value(unexpected: Unexpected, expected: &dyn Expected) -> Self {
-        Self::InvalidValue(unexpected.to_string(), expected.to_string())
+        let expected = expected.to_string();
+        // `serde` rejects zero for the `NonZero*` types only after visiting it as an integer.
+        if matches!(unexpected, Unexpected::Unsigned(0) | Unexpected::Signed(0))
+            && expected.starts_with("a nonzero ")
+        {
+            return Self::Zero { field: None };
+        }
+        Self::InvalidValue(unexpected.to_string(), expected)
     }
 
     fn invalid_length(len: usize, expected: &dyn Expected) -> Self {
@@ -135,6 +403,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_invalid_value_zero() {
+        assert_eq!(
+            Error::invalid_value(Unexpected::Unsigned(0), &"a nonzero u8"),
+            Error::Zero { field: None }
+        );
+    }
+
+    #[test]
+    fn error_invalid_value_zero_not_nonzero() {
+        assert_eq!(
+            Error::invalid_value(Unexpected::Unsigned(0), &"a positive u8"),
+            Error::InvalidValue("integer `0`".to_owned(), "a positive u8".to_owned())
+        );
+    }
+
     #[test]
     fn error_invalid_length() {
         assert_eq!(
@@ -143,6 +427,286 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_invalid_char_display() {
+        assert_eq!(
+            Error::InvalidChar("ab".to_owned()).to_string(),
+            "expected a single character, got 'ab'"
+        );
+    }
+
+    #[test]
+    fn error_invalid_char_empty_display() {
+        assert_eq!(
+            Error::InvalidChar(String::new()).to_string(),
+            "expected a single character, got ''"
+        );
+    }
+
+    #[test]
+    fn error_invalid_char_grapheme_cluster_display() {
+        assert_eq!(
+            Error::InvalidChar("🇺🇸".to_owned()).to_string(),
+            "expected a single character, got '🇺🇸', which is composed of 2 characters"
+        );
+    }
+
+    #[test]
+    fn error_decimal_comma_display() {
+        assert_eq!(
+            Error::DecimalComma {
+                field: None,
+                expected: "f64".to_owned(),
+                value: "3,14".to_owned(),
+            }
+            .to_string(),
+            "invalid value: expected f64, found '3,14'; use '.' as the decimal separator"
+        );
+    }
+
+    #[test]
+    fn error_decimal_comma_field_display() {
+        assert_eq!(
+            Error::DecimalComma {
+                field: Some("--ratio".to_owned()),
+                expected: "f32".to_owned(),
+                value: "0,5".to_owned(),
+            }
+            .to_string(),
+            "invalid value for --ratio: expected f32, found '0,5'; use '.' as the decimal separator"
+        );
+    }
+
+    #[test]
+    fn error_unparsable_display() {
+        assert_eq!(
+            Error::Unparsable {
+                field: None,
+                expected: "u8".to_owned(),
+                value: "abc".to_owned(),
+            }
+            .to_string(),
+            "invalid value: expected u8, found 'abc'"
+        );
+    }
+
+    #[test]
+    fn error_unparsable_display_empty() {
+        assert_eq!(
+            Error::Unparsable {
+                field: None,
+                expected: "u8".to_owned(),
+                value: String::new(),
+            }
+            .to_string(),
+            "invalid value: expected u8, found ''"
+        );
+    }
+
+    #[test]
+    fn error_unparsable_display_whitespace() {
+        assert_eq!(
+            Error::Unparsable {
+                field: None,
+                expected: "u8".to_owned(),
+                value: " 1 ".to_owned(),
+            }
+            .to_string(),
+            "invalid value: expected u8, found ' 1 '"
+        );
+    }
+
+    #[test]
+    fn error_unparsable_field_display() {
+        assert_eq!(
+            Error::Unparsable {
+                field: Some("--count".to_owned()),
+                expected: "u8".to_owned(),
+                value: "abc".to_owned(),
+            }
+            .to_string(),
+            "invalid value for --count: expected u8, found 'abc'"
+        );
+    }
+
+    #[test]
+    fn error_invalid_known_value_display() {
+        assert_eq!(
+            Error::InvalidKnownValue {
+                name: "url",
+                value: "foo".to_owned(),
+                reason: "relative URL without a base".to_owned(),
+            }
+            .to_string(),
+            "invalid url 'foo': relative URL without a base"
+        );
+    }
+
+    #[test]
+    fn error_out_of_range_display() {
+        assert_eq!(
+            Error::OutOfRange {
+                field: None,
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+            .to_string(),
+            "invalid value: expected u8 between 0 and 255, found '256'"
+        );
+    }
+
+    #[test]
+    fn error_out_of_range_with_field_display() {
+        assert_eq!(
+            Error::OutOfRange {
+                field: Some("<count>".to_owned()),
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+            .to_string(),
+            "invalid value for <count>: expected u8 between 0 and 255, found '256'"
+        );
+    }
+
+    #[test]
+    fn error_zero_display() {
+        assert_eq!(
+            Error::Zero { field: None }.to_string(),
+            "value must be nonzero"
+        );
+    }
+
+    #[test]
+    fn error_zero_with_field_display() {
+        assert_eq!(
+            Error::Zero {
+                field: Some("<count>".to_owned())
+            }
+            .to_string(),
+            "value must be nonzero for <count>"
+        );
+    }
+
+    #[test]
+    fn error_empty_display() {
+        assert_eq!(
+            Error::Empty {
+                field: None,
+                expected: "i64".to_owned(),
+            }
+            .to_string(),
+            "invalid value: expected i64, found an empty value"
+        );
+    }
+
+    #[test]
+    fn error_empty_with_field_display() {
+        assert_eq!(
+            Error::Empty {
+                field: Some("--count".to_owned()),
+                expected: "i64".to_owned(),
+            }
+            .to_string(),
+            "invalid value for --count: expected i64, found an empty value"
+        );
+    }
+
+    #[test]
+    fn error_with_field_empty() {
+        assert_eq!(
+            Error::Empty {
+                field: None,
+                expected: "i64".to_owned(),
+            }
+            .with_field("<count>"),
+            Error::Empty {
+                field: Some("<count>".to_owned()),
+                expected: "i64".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn error_with_field_unparsable() {
+        assert_eq!(
+            Error::Unparsable {
+                field: None,
+                expected: "u8".to_owned(),
+                value: "abc".to_owned(),
+            }
+            .with_field("--count"),
+            Error::Unparsable {
+                field: Some("--count".to_owned()),
+                expected: "u8".to_owned(),
+                value: "abc".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn error_with_field_zero() {
+        assert_eq!(
+            Error::Zero { field: None }.with_field("--count"),
+            Error::Zero {
+                field: Some("--count".to_owned())
+            }
+        );
+    }
+
+    #[test]
+    fn error_with_field() {
+        assert_eq!(
+            Error::OutOfRange {
+                field: None,
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+            .with_field("<count>"),
+            Error::OutOfRange {
+                field: Some("<count>".to_owned()),
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn error_with_field_already_named() {
+        assert_eq!(
+            Error::OutOfRange {
+                field: Some("<inner>".to_owned()),
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+            .with_field("<outer>"),
+            Error::OutOfRange {
+                field: Some("<inner>".to_owned()),
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn error_with_field_other_error() {
+        assert_eq!(
+            Error::MissingField("foo").with_field("<count>"),
+            Error::MissingField("foo")
+        );
+    }
+
     #[test]
     fn error_unknown_variant() {
         assert_eq!(
@@ -196,6 +760,17 @@ mod tests {
         )
     }
 
+    #[test]
+    fn error_display_invalid_value_escaped() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::InvalidValue("\x1b[31mfoo\n".to_owned(), "bar".to_owned())
+            ),
+            "invalid value: expected bar, found \\x1b[31mfoo\\n"
+        )
+    }
+
     #[test]
     fn error_display_invalid_length() {
         assert_eq!(
@@ -238,4 +813,58 @@ mod tests {
             "the argument --foo cannot be used multiple times"
         )
     }
+
+    #[test]
+    fn error_display_unused() {
+        assert_eq!(
+            format!("{}", Error::Unused(vec!["--foo".into(), "bar".into()])),
+            "unused arguments: --foo bar"
+        )
+    }
+
+    #[test]
+    fn error_path_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::Path {
+                    field: None,
+                    path: "foo".into(),
+                    reason: "does not exist",
+                }
+            ),
+            "invalid path: 'foo' does not exist"
+        )
+    }
+
+    #[test]
+    fn error_path_truncated_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::Path {
+                    field: None,
+                    path: "a".repeat(101),
+                    reason: "does not exist",
+                }
+            ),
+            format!("invalid path: '{}…' does not exist", "a".repeat(100))
+        )
+    }
+
+    #[test]
+    fn error_path_with_field_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::Path {
+                    field: None,
+                    path: "foo".into(),
+                    reason: "is not a directory",
+                }
+                .with_field("--output")
+            ),
+            "invalid path for --output: 'foo' is not a directory"
+        )
+    }
 }
@@ -1,14 +1,18 @@
 pub(crate) mod error;
+pub(crate) mod value;
 
 pub(crate) use error::Error;
+pub(crate) use value::Value;
 
 use crate::{
+    helpers::path,
     key,
     parse::{
         Context,
         ContextIter,
         Segment,
     },
+    trace,
 };
 use serde::{
     de,
@@ -16,41 +20,138 @@ use serde::{
         DeserializeSeed,
         Deserializer as _,
         Error as _,
+        Expected,
+        IntoDeserializer,
         MapAccess,
         Unexpected,
         Visitor,
     },
 };
 use std::{
+    ffi::OsString,
     num::IntErrorKind,
+    path::PathBuf,
     str,
     str::FromStr,
 };
+use unicode_segmentation::UnicodeSegmentation;
 
-pub(crate) struct Deserializer {
+pub(crate) struct Deserializer<'de> {
     context: ContextIter,
+    /// The caller-owned arguments that the context was parsed from.
+    ///
+    /// Values found within these arguments are visited as borrowed data. This is empty when the
+    /// arguments are not owned by the caller.
+    source: &'de [OsString],
+    /// The value used in place of anything missing from the context.
+    ///
+    /// Only struct fields that were not given on the command line are taken from the defaults.
+    defaults: Option<Value>,
 }
 
-impl Deserializer {
+impl Deserializer<'_> {
     pub(crate) fn new(context: Context) -> Self {
         Self {
             context: context.into_iter(),
+            source: &[],
+            defaults: None,
         }
     }
+
+    pub(crate) fn with_defaults(context: Context, defaults: Option<Value>) -> Self {
+        Self {
+            context: context.into_iter(),
+            source: &[],
+            defaults,
+        }
+    }
+}
+
+impl<'de> Deserializer<'de> {
+    pub(crate) fn with_source(context: Context, source: &'de [OsString]) -> Self {
+        Self {
+            context: context.into_iter(),
+            source,
+            defaults: None,
+        }
+    }
+
+    /// Takes the next segment of the context, ensuring that no segments follow it.
+    fn next_segment(&mut self) -> Result<Option<Segment>, Error> {
+        let segment = self.context.next();
+        ensure_consumed(&mut self.context)?;
+        Ok(segment)
+    }
+
+    /// Finds `value` within the caller-owned source arguments.
+    ///
+    /// Any argument ending with the bytes of `value` contains an identical copy of them, so the
+    /// borrowed data is the same regardless of which argument it is found in. Suffixes are checked
+    /// rather than whole arguments because option-like values may have had their prefix
+    /// normalized during parsing.
+    fn borrow(&self, value: &[u8]) -> Option<&'de [u8]> {
+        self.source
+            .iter()
+            .map(|arg| arg.as_encoded_bytes())
+            .find(|arg| arg.ends_with(value))
+            .map(|arg| &arg[(arg.len() - value.len())..])
+    }
 }
 
-impl<'de> de::Deserializer<'de> for Deserializer {
+impl<'de> de::Deserializer<'de> for Deserializer<'de> {
     type Error = Error;
 
+    /// Command line arguments are always text, so types are asked for their human-readable form.
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
     // ---------------
     // Self-describing
     // ---------------
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    /// Values of types accepting several forms are given as numbers or booleans when they can be
+    /// parsed as one, and as strings otherwise.
+    fn deserialize_any<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        unreachable!()
+        match self.next_segment()? {
+            Some(Segment::Value(raw)) => {
+                let value_string = String::from_utf8_lossy(&raw);
+                if let Ok(value) = u64::from_str(&value_string) {
+                    visitor.visit_u64(value)
+                } else if let Ok(value) = i64::from_str(&value_string) {
+                    visitor.visit_i64(value)
+                } else if let Some(value) = value_string
+                    .contains(|c: char| c.is_ascii_digit())
+                    .then(|| f64::from_str(&value_string).ok())
+                    .flatten()
+                {
+                    // Names such as "inf" and "nan" are parsed as floats, so only values
+                    // containing digits are given as floats.
+                    visitor.visit_f64(value)
+                } else if let Ok(value) = bool::from_str(&value_string) {
+                    visitor.visit_bool(value)
+                } else {
+                    match self
+                        .borrow(&raw)
+                        .and_then(|borrowed| str::from_utf8(borrowed).ok())
+                    {
+                        Some(borrowed) => visitor.visit_borrowed_str(borrowed),
+                        None => match str::from_utf8(&raw) {
+                            Ok(value) => visitor.visit_str(value),
+                            Err(_) => visitor.visit_bytes(&raw),
+                        },
+                    }
+                }
+            }
+            Some(Segment::Identifier(name)) => {
+                Err(Error::invalid_type(Unexpected::Other(name), &visitor))
+            }
+            Some(Segment::Context(_)) => Err(Error::invalid_type(Unexpected::Map, &visitor)),
+            None => Err(Error::invalid_type(Unexpected::Unit, &visitor)),
+        }
     }
 
     fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -68,12 +169,21 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
-                bool::from_str(&value_string)
-                    .map_err(|_| Error::invalid_type(Unexpected::Other(&value_string), &visitor))
-                    .and_then(|b| visitor.visit_bool(b))
+                match parse_bool(&value_string) {
+                    Some(b) => visitor.visit_bool(b),
+                    None if value_string.is_empty() => Err(Error::empty(&visitor)),
+                    None => Err(Error::Unparsable {
+                        field: None,
+                        expected: format!(
+                            "{} (true/false, yes/no, on/off, or 1/0)",
+                            &visitor as &dyn Expected
+                        ),
+                        value: value_string.into_owned(),
+                    }),
+                }
             }
             _ => unreachable!(),
         }
@@ -83,19 +193,16 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 i8::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
+                        IntErrorKind::Empty => Error::empty(&visitor),
                         IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
-                            if let Ok(value) = i64::from_str(&value_string) {
-                                Error::invalid_value(Unexpected::Signed(value), &visitor)
-                            } else {
-                                Error::invalid_value(Unexpected::Other(&value_string), &visitor)
-                            }
+                            Error::out_of_range(&value_string, &visitor, i8::MIN, i8::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_i8(int))
             }
@@ -109,19 +216,16 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 i16::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
+                        IntErrorKind::Empty => Error::empty(&visitor),
                         IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
-                            if let Ok(value) = i64::from_str(&value_string) {
-                                Error::invalid_value(Unexpected::Signed(value), &visitor)
-                            } else {
-                                Error::invalid_value(Unexpected::Other(&value_string), &visitor)
-                            }
+                            Error::out_of_range(&value_string, &visitor, i16::MIN, i16::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_i16(int))
             }
@@ -135,19 +239,16 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 i32::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
+                        IntErrorKind::Empty => Error::empty(&visitor),
                         IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
-                            if let Ok(value) = i64::from_str(&value_string) {
-                                Error::invalid_value(Unexpected::Signed(value), &visitor)
-                            } else {
-                                Error::invalid_value(Unexpected::Other(&value_string), &visitor)
-                            }
+                            Error::out_of_range(&value_string, &visitor, i32::MIN, i32::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_i32(int))
             }
@@ -161,15 +262,16 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 i64::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
+                        IntErrorKind::Empty => Error::empty(&visitor),
                         IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
-                            Error::invalid_value(Unexpected::Other(&value_string), &visitor)
+                            Error::out_of_range(&value_string, &visitor, i64::MIN, i64::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_i64(int))
             }
@@ -183,15 +285,16 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 i128::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
+                        IntErrorKind::Empty => Error::empty(&visitor),
                         IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
-                            Error::invalid_value(Unexpected::Other(&value_string), &visitor)
+                            Error::out_of_range(&value_string, &visitor, i128::MIN, i128::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_i128(int))
             }
@@ -205,19 +308,19 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 u8::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
-                        IntErrorKind::PosOverflow => {
-                            if let Ok(value) = u64::from_str(&value_string) {
-                                Error::invalid_value(Unexpected::Unsigned(value), &visitor)
-                            } else {
-                                Error::invalid_value(Unexpected::Other(&value_string), &visitor)
-                            }
+                        IntErrorKind::Empty => Error::empty(&visitor),
+                        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                            Error::out_of_range(&value_string, &visitor, u8::MIN, u8::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        IntErrorKind::InvalidDigit if is_negative_integer(&value_string) => {
+                            Error::out_of_range(&value_string, &visitor, u8::MIN, u8::MAX)
+                        }
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_u8(int))
             }
@@ -231,19 +334,19 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 u16::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
-                        IntErrorKind::PosOverflow => {
-                            if let Ok(value) = u64::from_str(&value_string) {
-                                Error::invalid_value(Unexpected::Unsigned(value), &visitor)
-                            } else {
-                                Error::invalid_value(Unexpected::Other(&value_string), &visitor)
-                            }
+                        IntErrorKind::Empty => Error::empty(&visitor),
+                        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                            Error::out_of_range(&value_string, &visitor, u16::MIN, u16::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        IntErrorKind::InvalidDigit if is_negative_integer(&value_string) => {
+                            Error::out_of_range(&value_string, &visitor, u16::MIN, u16::MAX)
+                        }
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_u16(int))
             }
@@ -257,19 +360,19 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 u32::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
-                        IntErrorKind::PosOverflow => {
-                            if let Ok(value) = u64::from_str(&value_string) {
-                                Error::invalid_value(Unexpected::Unsigned(value), &visitor)
-                            } else {
-                                Error::invalid_value(Unexpected::Other(&value_string), &visitor)
-                            }
+                        IntErrorKind::Empty => Error::empty(&visitor),
+                        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                            Error::out_of_range(&value_string, &visitor, u32::MIN, u32::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        IntErrorKind::InvalidDigit if is_negative_integer(&value_string) => {
+                            Error::out_of_range(&value_string, &visitor, u32::MIN, u32::MAX)
+                        }
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_u32(int))
             }
@@ -283,15 +386,19 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 u64::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
-                        IntErrorKind::PosOverflow => {
-                            Error::invalid_value(Unexpected::Other(&value_string), &visitor)
+                        IntErrorKind::Empty => Error::empty(&visitor),
+                        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                            Error::out_of_range(&value_string, &visitor, u64::MIN, u64::MAX)
+                        }
+                        IntErrorKind::InvalidDigit if is_negative_integer(&value_string) => {
+                            Error::out_of_range(&value_string, &visitor, u64::MIN, u64::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_u64(int))
             }
@@ -305,15 +412,19 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
                 u128::from_str(&value_string)
                     .map_err(|parse_int_error| match parse_int_error.kind() {
-                        IntErrorKind::PosOverflow => {
-                            Error::invalid_value(Unexpected::Other(&value_string), &visitor)
+                        IntErrorKind::Empty => Error::empty(&visitor),
+                        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                            Error::out_of_range(&value_string, &visitor, u128::MIN, u128::MAX)
                         }
-                        _ => Error::invalid_type(Unexpected::Other(&value_string), &visitor),
+                        IntErrorKind::InvalidDigit if is_negative_integer(&value_string) => {
+                            Error::out_of_range(&value_string, &visitor, u128::MIN, u128::MAX)
+                        }
+                        _ => Error::unparsable(&value_string, &visitor),
                     })
                     .and_then(|int| visitor.visit_u128(int))
             }
@@ -327,12 +438,32 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
-                f32::from_str(&value_string)
-                    .map_err(|_| Error::invalid_type(Unexpected::Other(&value_string), &visitor))
-                    .and_then(|float| visitor.visit_f32(float))
+                let float = f32::from_str(&value_string).map_err(|_| {
+                    if value_string.is_empty() {
+                        Error::empty(&visitor)
+                    } else if is_decimal_comma(&value_string) {
+                        Error::DecimalComma {
+                            field: None,
+                            expected: (&visitor as &dyn Expected).to_string(),
+                            value: value_string.to_string(),
+                        }
+                    } else {
+                        Error::unparsable(&value_string, &visitor)
+                    }
+                })?;
+                // Finite values too large for the type would otherwise silently become infinite.
+                if float.is_infinite() && !is_infinity(&value_string) {
+                    return Err(Error::out_of_range(
+                        &value_string,
+                        &visitor,
+                        format!("{:e}", f32::MIN),
+                        format!("{:e}", f32::MAX),
+                    ));
+                }
+                visitor.visit_f32(float)
             }
             _ => {
                 unreachable!()
@@ -344,12 +475,32 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
                 let value_string = String::from_utf8_lossy(&raw);
-                f64::from_str(&value_string)
-                    .map_err(|_| Error::invalid_type(Unexpected::Other(&value_string), &visitor))
-                    .and_then(|float| visitor.visit_f64(float))
+                let float = f64::from_str(&value_string).map_err(|_| {
+                    if value_string.is_empty() {
+                        Error::empty(&visitor)
+                    } else if is_decimal_comma(&value_string) {
+                        Error::DecimalComma {
+                            field: None,
+                            expected: (&visitor as &dyn Expected).to_string(),
+                            value: value_string.to_string(),
+                        }
+                    } else {
+                        Error::unparsable(&value_string, &visitor)
+                    }
+                })?;
+                // Finite values too large for the type would otherwise silently become infinite.
+                if float.is_infinite() && !is_infinity(&value_string) {
+                    return Err(Error::out_of_range(
+                        &value_string,
+                        &visitor,
+                        format!("{:e}", f64::MIN),
+                        format!("{:e}", f64::MAX),
+                    ));
+                }
+                visitor.visit_f64(float)
             }
             _ => {
                 unreachable!()
@@ -361,16 +512,16 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
-                let value_string = str::from_utf8(&raw).map_err(|_| {
-                    Error::invalid_type(Unexpected::Other(&String::from_utf8_lossy(&raw)), &visitor)
-                })?;
-                let chars = value_string.chars().collect::<Vec<char>>();
-                if chars.len() == 1 {
-                    visitor.visit_char(chars[0])
-                } else {
-                    Err(Error::invalid_type(Unexpected::Str(value_string), &visitor))
+                let value_string = str::from_utf8(&raw)
+                    .map_err(|_| Error::unparsable(&String::from_utf8_lossy(&raw), &visitor))?;
+                // Exactly one Unicode scalar value is required. Anything else, including a single
+                // grapheme composed of multiple scalar values, is rejected.
+                let mut chars = value_string.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(char), None) => visitor.visit_char(char),
+                    _ => Err(Error::InvalidChar(value_string.to_owned())),
                 }
             }
             _ => {
@@ -383,12 +534,29 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
+        match self.next_segment()? {
             Some(Segment::Value(raw)) => {
-                let value_string = str::from_utf8(&raw).map_err(|_| {
-                    Error::invalid_type(Unexpected::Other(&String::from_utf8_lossy(&raw)), &visitor)
-                })?;
-                visitor.visit_str(value_string)
+                let value_string = str::from_utf8(&raw)
+                    .map_err(|_| Error::unparsable(&String::from_utf8_lossy(&raw), &visitor))?;
+                let known = trace::known::find(&format!("{}", &visitor as &dyn Expected));
+                match self
+                    .borrow(&raw)
+                    .and_then(|borrowed| str::from_utf8(borrowed).ok())
+                {
+                    Some(borrowed) => visitor.visit_borrowed_str(borrowed),
+                    None => visitor.visit_str(value_string),
+                }
+                .map_err(|error| match (known, error) {
+                    // Name the type and give its own reason the value could not be parsed.
+                    (Some(known), Error::InvalidValue(_, reason) | Error::Custom(reason)) => {
+                        Error::InvalidKnownValue {
+                            name: known.name,
+                            value: value_string.to_owned(),
+                            reason: known.reason(&reason).to_owned(),
+                        }
+                    }
+                    (_, error) => error,
+                })
             }
             _ => {
                 unreachable!()
@@ -407,8 +575,11 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
-            Some(Segment::Value(bytes)) => visitor.visit_bytes(&bytes),
+        match self.next_segment()? {
+            Some(Segment::Value(bytes)) => match self.borrow(&bytes) {
+                Some(borrowed) => visitor.visit_borrowed_bytes(borrowed),
+                None => visitor.visit_bytes(&bytes),
+            },
             _ => unreachable!(),
         }
     }
@@ -427,21 +598,23 @@ impl<'de> de::Deserializer<'de> for Deserializer {
         self.deserialize_bytes(visitor)
     }
 
-    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_unit<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        ensure_consumed(&mut self.context)?;
         visitor.visit_unit()
     }
 
     fn deserialize_unit_struct<V>(
-        self,
+        mut self,
         _name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        ensure_consumed(&mut self.context)?;
         visitor.visit_unit()
     }
 
@@ -453,36 +626,73 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     where
         V: Visitor<'de>,
     {
-        match self.context.next() {
-            Some(Segment::Context(context)) => visitor.visit_some(Deserializer::new(context)),
+        match self.next_segment()? {
+            Some(Segment::Context(context)) => visitor.visit_some(Deserializer {
+                context: context.into_iter(),
+                source: self.source,
+                defaults: match self.defaults {
+                    Some(Value::Some(defaults)) => Some(*defaults),
+                    _ => None,
+                },
+            }),
             Some(_) => unreachable!(),
             None => visitor.visit_none(),
         }
     }
 
+    /// Paths requested by the path helpers are checked against their constraint before being
+    /// provided.
     fn deserialize_newtype_struct<V>(
-        self,
-        _name: &'static str,
+        mut self,
+        name: &'static str,
         visitor: V,
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_newtype_struct(self)
+        match path::Constraint::from_name(name) {
+            Some(constraint) => {
+                let segment = self.next_segment()?;
+                if let Some(Segment::Value(raw)) = &segment {
+                    constraint
+                        .check(&path_from_raw(raw))
+                        .map_err(|reason| Error::Path {
+                            field: None,
+                            path: String::from_utf8_lossy(raw).into_owned(),
+                            reason,
+                        })?;
+                }
+                visitor.visit_newtype_struct(Deserializer {
+                    context: Context {
+                        segments: segment.into_iter().collect(),
+                    }
+                    .into_iter(),
+                    source: self.source,
+                    defaults: self.defaults,
+                })
+            }
+            None => visitor.visit_newtype_struct(self),
+        }
     }
 
-    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        visitor.visit_seq(SeqAccess {
+            context: self.context,
+            source: self.source,
+        })
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        visitor.visit_seq(ElementAccess {
+            context: self.context,
+            source: self.source,
+        })
     }
 
     fn deserialize_tuple_struct<V>(
@@ -516,6 +726,10 @@ impl<'de> de::Deserializer<'de> for Deserializer {
         visitor.visit_map(StructAccess {
             struct_context: self.context,
             field_context: None,
+            field: None,
+            source: self.source,
+            defaults: self.defaults,
+            field_defaults: None,
         })
     }
 
@@ -530,11 +744,12 @@ impl<'de> de::Deserializer<'de> for Deserializer {
     {
         visitor.visit_enum(EnumAccess {
             context: self.context,
+            source: self.source,
         })
     }
 }
 
-impl key::DeserializerError for Deserializer {
+impl key::DeserializerError for Deserializer<'_> {
     type Error = Error;
 
     fn unsupported() -> Self::Error {
@@ -543,25 +758,128 @@ impl key::DeserializerError for Deserializer {
     }
 }
 
+/// Ensures that every segment of `context` has been consumed.
+///
+/// Parsing should only produce segments that the deserializer uses. Any left over were attached to
+/// a part of the context that the type being deserialized does not read, so they are reported
+/// rather than silently ignored.
+fn ensure_consumed(context: &mut ContextIter) -> Result<(), Error> {
+    let mut unused = Vec::new();
+    for segment in context {
+        unused_arguments(segment, &mut unused);
+    }
+    if unused.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Unused(unused))
+    }
+}
+
+/// Collects the arguments `segment` was parsed from into `arguments`, as they would be given on
+/// the command line.
+fn unused_arguments(segment: Segment, arguments: &mut Vec<String>) {
+    match segment {
+        Segment::Identifier(name) => arguments.push(name.to_owned()),
+        Segment::Value(raw) => arguments.push(String::from_utf8_lossy(&raw).into_owned()),
+        Segment::Context(context) => {
+            let mut segments = context.segments.into_iter().peekable();
+            // Options nest their identifier within their own context, while positional fields
+            // are immediately followed by their value.
+            if let Some(&Segment::Identifier(name)) = segments.peek() {
+                segments.next();
+                if !matches!(segments.peek(), Some(Segment::Value(_))) {
+                    arguments.push(if name.graphemes(true).count() == 1 {
+                        format!("-{}", name)
+                    } else {
+                        format!("--{}", name)
+                    });
+                }
+            }
+            for segment in segments {
+                unused_arguments(segment, arguments);
+            }
+        }
+    }
+}
+
+/// Interprets a raw value as a path.
+///
+/// Values that are not valid UTF-8 are kept intact on Unix platforms.
+fn path_from_raw(raw: &[u8]) -> PathBuf {
+    #[cfg(unix)]
+    {
+        use std::{
+            ffi::OsStr,
+            os::unix::ffi::OsStrExt,
+        };
+
+        OsStr::from_bytes(raw).into()
+    }
+    #[cfg(not(unix))]
+    {
+        String::from_utf8_lossy(raw).into_owned().into()
+    }
+}
+
+/// Returns whether `value` is a negative integer, which no unsigned integer type can hold.
+fn is_negative_integer(value: &str) -> bool {
+    match value.strip_prefix('-') {
+        Some(digits) => !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit()),
+        None => false,
+    }
+}
+
+/// Returns whether `value` spells out an infinite float, such as `inf` or `-Infinity`.
+fn is_infinity(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+    unsigned.eq_ignore_ascii_case("inf") || unsigned.eq_ignore_ascii_case("infinity")
+}
+
+/// Whether `value` is a decimal number written with a comma as its decimal separator.
+///
+/// Floats are always parsed using `.` as the decimal separator, regardless of locale.
+fn is_decimal_comma(value: &str) -> bool {
+    value.matches(',').count() == 1 && f64::from_str(&value.replace(',', ".")).is_ok()
+}
+
+/// Parses a boolean value given on the command line.
+///
+/// The spellings `true`/`false`, `yes`/`no`, `on`/`off`, and `1`/`0` are accepted, ignoring case.
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Some(true),
+        "false" | "no" | "off" | "0" => Some(false),
+        _ => None,
+    }
+}
+
 macro_rules! forward_to_deserializer {
     ($($method:ident($($arg:ident: $t:ty),*))*) => {
         $(
             fn $method<V>(self, $($arg: $t,)* visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
                 Deserializer {
                     context: self.context,
+                    source: self.source,
+                    defaults: self.defaults,
                 }.$method($($arg,)* visitor)
             }
         )*
     }
 }
 
-struct FieldDeserializer {
+struct FieldDeserializer<'de> {
     context: ContextIter,
+    source: &'de [OsString],
+    defaults: Option<Value>,
 }
 
-impl<'de> de::Deserializer<'de> for FieldDeserializer {
+impl<'de> de::Deserializer<'de> for FieldDeserializer<'de> {
     type Error = Error;
 
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
     forward_to_deserializer! {
         deserialize_any()
         deserialize_ignored_any()
@@ -599,21 +917,33 @@ impl<'de> de::Deserializer<'de> for FieldDeserializer {
     where
         V: Visitor<'de>,
     {
-        visitor.visit_bool(match self.context.next() {
+        let present = match self.context.next() {
             Some(Segment::Context(_)) => true,
             Some(_) => unreachable!(),
             None => false,
-        })
+        };
+        ensure_consumed(&mut self.context)?;
+        visitor.visit_bool(present)
     }
 }
 
 #[derive(Debug)]
-struct StructAccess {
+struct StructAccess<'de> {
     struct_context: ContextIter,
     field_context: Option<ContextIter>,
+    /// The name of the current field, as it would be given on the command line.
+    field: Option<String>,
+    source: &'de [OsString],
+    /// The defaults of the fields that have not yet been visited.
+    defaults: Option<Value>,
+    /// The default of the current field.
+    ///
+    /// This replaces the field entirely if it was not given on the command line, and is otherwise
+    /// passed on to provide defaults for anything nested within it.
+    field_defaults: Option<Value>,
 }
 
-impl<'de> MapAccess<'de> for StructAccess {
+impl<'de> MapAccess<'de> for StructAccess<'de> {
     type Error = Error;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
@@ -622,15 +952,37 @@ impl<'de> MapAccess<'de> for StructAccess {
     {
         match self.struct_context.next() {
             Some(Segment::Context(context)) => {
+                // Required fields are given their values directly, while optional fields nest them
+                // within their own context.
+                let positional = matches!(context.segments.get(1), Some(Segment::Value(_)));
+                // Options that were not given only contain their identifier.
+                let missing = context.segments.len() == 1;
                 let mut field_context = context.into_iter();
                 // Extract the identifier, which should always be the first element for this type of
                 // context.
                 match field_context.next() {
                     Some(Segment::Identifier(field)) => {
-                        self.field_context = Some(field_context);
-                        Ok(Some(seed.deserialize(
-                            key::Deserializer::<Deserializer>::new(field),
-                        )?))
+                        self.field_defaults = self
+                            .defaults
+                            .as_mut()
+                            .and_then(|defaults| defaults.take_field(field));
+                        self.field_context = if missing && self.field_defaults.is_some() {
+                            None
+                        } else {
+                            Some(field_context)
+                        };
+                        self.field = Some(if positional {
+                            format!("<{}>", field)
+                        } else if field.graphemes(true).count() == 1 {
+                            format!("-{}", field)
+                        } else {
+                            format!("--{}", field)
+                        });
+                        Ok(Some(seed.deserialize(key::Deserializer::<
+                            Deserializer<'de>,
+                        >::new(
+                            field
+                        ))?))
                     }
                     _ => unreachable!(),
                 }
@@ -646,24 +998,97 @@ impl<'de> MapAccess<'de> for StructAccess {
     where
         V: DeserializeSeed<'de>,
     {
-        if let Some(field_context) = self.field_context.take() {
-            seed.deserialize(FieldDeserializer {
+        match (self.field_context.take(), self.field_defaults.take()) {
+            (Some(field_context), defaults) => seed.deserialize(FieldDeserializer {
                 context: field_context,
-            })
-        } else {
-            unreachable!()
+                source: self.source,
+                defaults,
+            }),
+            (None, Some(default)) => seed.deserialize(default.into_deserializer()),
+            (None, None) => unreachable!(),
+        }
+        .map_err(|error| match self.field.take() {
+            Some(field) => error.with_field(&field),
+            None => error,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct SeqAccess<'de> {
+    context: ContextIter,
+    source: &'de [OsString],
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        // Each element is a single value, deserialized on its own.
+        match self.context.next() {
+            Some(segment @ Segment::Value(_)) => {
+                Ok(Some(seed.deserialize(Deserializer::with_source(
+                    Context {
+                        segments: vec![segment],
+                    },
+                    self.source,
+                ))?))
+            }
+            Some(_) => unreachable!(),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Accesses the elements of a tuple or array, each parsed as a required positional argument.
+#[derive(Debug)]
+struct ElementAccess<'de> {
+    context: ContextIter,
+    source: &'de [OsString],
+}
+
+impl<'de> de::SeqAccess<'de> for ElementAccess<'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        match self.context.next() {
+            Some(Segment::Context(context)) => {
+                let mut element_context = context.into_iter();
+                let element = match element_context.next() {
+                    Some(Segment::Identifier(element)) => element,
+                    _ => unreachable!(),
+                };
+                // Elements are always values, so booleans are parsed from their value rather than
+                // from the presence of a flag.
+                seed.deserialize(Deserializer {
+                    context: element_context,
+                    source: self.source,
+                    defaults: None,
+                })
+                .map(Some)
+                .map_err(|error| error.with_field(&format!("<{}>", element)))
+            }
+            Some(_) => unreachable!(),
+            None => Ok(None),
         }
     }
 }
 
 #[derive(Debug)]
-struct EnumAccess {
+struct EnumAccess<'de> {
     context: ContextIter,
+    source: &'de [OsString],
 }
 
-impl<'de> de::EnumAccess<'de> for EnumAccess {
+impl<'de> de::EnumAccess<'de> for EnumAccess<'de> {
     type Error = Error;
-    type Variant = VariantAccess;
+    type Variant = VariantAccess<'de>;
 
     fn variant_seed<V>(mut self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
     where
@@ -671,9 +1096,10 @@ impl<'de> de::EnumAccess<'de> for EnumAccess {
     {
         match self.context.next() {
             Some(Segment::Identifier(variant)) => Ok((
-                seed.deserialize(key::Deserializer::<Deserializer>::new(variant))?,
+                seed.deserialize(key::Deserializer::<Deserializer<'de>>::new(variant))?,
                 VariantAccess {
                     context: self.context,
+                    source: self.source,
                 },
             )),
             _ => unreachable!(),
@@ -682,15 +1108,16 @@ impl<'de> de::EnumAccess<'de> for EnumAccess {
 }
 
 #[derive(Debug)]
-struct VariantAccess {
+struct VariantAccess<'de> {
     context: ContextIter,
+    source: &'de [OsString],
 }
 
-impl<'de> de::VariantAccess<'de> for VariantAccess {
+impl<'de> de::VariantAccess<'de> for VariantAccess<'de> {
     type Error = Error;
 
-    fn unit_variant(self) -> Result<(), Self::Error> {
-        Ok(())
+    fn unit_variant(mut self) -> Result<(), Self::Error> {
+        ensure_consumed(&mut self.context)
     }
 
     fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
@@ -699,6 +1126,8 @@ impl<'de> de::VariantAccess<'de> for VariantAccess {
     {
         seed.deserialize(Deserializer {
             context: self.context,
+            source: self.source,
+            defaults: None,
         })
     }
 
@@ -719,6 +1148,8 @@ impl<'de> de::VariantAccess<'de> for VariantAccess {
     {
         Deserializer {
             context: self.context,
+            source: self.source,
+            defaults: None,
         }
         .deserialize_struct("", fields, visitor)
     }
@@ -732,6 +1163,7 @@ mod tests {
         Error,
         FieldDeserializer,
         StructAccess,
+        Value,
         VariantAccess,
     };
     use crate::{
@@ -743,9 +1175,11 @@ mod tests {
     };
     use claims::{
         assert_err_eq,
+        assert_matches,
         assert_none,
         assert_ok,
         assert_ok_eq,
+        assert_some,
         assert_some_eq,
     };
     use serde::{
@@ -761,194 +1195,432 @@ mod tests {
             Visitor,
         },
     };
-    use serde_derive::Deserialize;
+    use serde_derive::{
+        Deserialize,
+        Serialize,
+    };
     use std::{
+        borrow::Cow,
+        collections::HashSet,
+        ffi::OsString,
         fmt,
         fmt::Formatter,
+        num::{
+            NonZeroU8,
+            NonZeroUsize,
+        },
     };
 
     #[test]
-    #[should_panic(expected = "entered unreachable code")]
-    fn any() {
-        #[derive(Debug)]
-        struct Any;
+    fn is_human_readable() {
+        assert!(de::Deserializer::is_human_readable(&Deserializer::new(
+            Context { segments: vec![] }
+        )));
+    }
 
-        impl<'de> Deserialize<'de> for Any {
-            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-            where
-                D: de::Deserializer<'de>,
-            {
-                struct AnyVisitor;
+    /// An enum accepting either a variant name or a numeric code.
+    #[derive(Debug, Eq, PartialEq)]
+    enum Mode {
+        Fast,
+        Slow,
+    }
+
+    impl<'de> Deserialize<'de> for Mode {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            struct ModeVisitor;
 
-                impl Visitor<'_> for AnyVisitor {
-                    type Value = Any;
+            impl Visitor<'_> for ModeVisitor {
+                type Value = Mode;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("a mode name or code")
+                }
 
-                    fn expecting(&self, _formatter: &mut Formatter) -> fmt::Result {
-                        unimplemented!()
+                fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        0 => Ok(Mode::Fast),
+                        1 => Ok(Mode::Slow),
+                        _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
                     }
                 }
 
-                deserializer.deserialize_any(AnyVisitor)
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        "fast" => Ok(Mode::Fast),
+                        "slow" => Ok(Mode::Slow),
+                        _ => Err(E::unknown_variant(value, &["fast", "slow"])),
+                    }
+                }
             }
-        }
-
-        let deserializer = Deserializer::new(Context { segments: vec![] });
-
-        let _ = Any::deserialize(deserializer);
-    }
-
-    #[test]
-    #[should_panic(expected = "entered unreachable code")]
-    fn ignored_any() {
-        let deserializer = Deserializer::new(Context { segments: vec![] });
 
-        let _ = IgnoredAny::deserialize(deserializer);
+            deserializer.deserialize_any(ModeVisitor)
+        }
     }
 
     #[test]
-    fn bool_false() {
+    fn any_name() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("false".into())],
+            segments: vec![Segment::Value("slow".into())],
         });
 
-        assert_ok_eq!(bool::deserialize(deserializer), false);
+        assert_ok_eq!(Mode::deserialize(deserializer), Mode::Slow);
     }
 
     #[test]
-    fn bool_true() {
+    fn any_numeric() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("true".into())],
+            segments: vec![Segment::Value("1".into())],
         });
 
-        assert_ok_eq!(bool::deserialize(deserializer), true);
+        assert_ok_eq!(Mode::deserialize(deserializer), Mode::Slow);
     }
 
     #[test]
-    fn bool_invalid_type() {
+    fn any_invalid_name() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("a".into())],
+            segments: vec![Segment::Value("medium".into())],
         });
 
         assert_err_eq!(
-            bool::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "a boolean".to_owned())
+            Mode::deserialize(deserializer),
+            Error::UnknownVariant("medium".to_owned(), &["fast", "slow"])
         );
     }
 
     #[test]
-    fn i8() {
+    fn any_invalid_numeric() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("42".into())],
+            segments: vec![Segment::Value("3".into())],
         });
 
-        assert_ok_eq!(i8::deserialize(deserializer), 42);
+        assert_err_eq!(
+            Mode::deserialize(deserializer),
+            Error::InvalidValue(
+                Unexpected::Unsigned(3).to_string(),
+                "a mode name or code".to_owned()
+            )
+        );
     }
 
     #[test]
-    fn i8_invalid_type() {
+    fn any_negative() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("a".into())],
+            segments: vec![Segment::Value("-1".into())],
         });
 
         assert_err_eq!(
-            i8::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "i8".to_owned())
+            Mode::deserialize(deserializer),
+            Error::InvalidType(
+                Unexpected::Signed(-1).to_string(),
+                "a mode name or code".to_owned()
+            )
         );
     }
 
     #[test]
-    fn i8_invalid_type_not_utf8() {
+    fn any_float() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value(vec![255])],
+            segments: vec![Segment::Value("1.5".into())],
         });
 
         assert_err_eq!(
-            i8::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "i8".to_owned())
+            Mode::deserialize(deserializer),
+            Error::InvalidType(
+                Unexpected::Float(1.5).to_string(),
+                "a mode name or code".to_owned()
+            )
         );
     }
 
     #[test]
-    fn i8_invalid_value_positive() {
+    fn any_float_name() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("128".into())],
+            segments: vec![Segment::Value("inf".into())],
         });
 
         assert_err_eq!(
-            i8::deserialize(deserializer),
-            Error::InvalidValue(Unexpected::Signed(128).to_string(), "i8".to_owned())
+            Mode::deserialize(deserializer),
+            Error::UnknownVariant("inf".to_owned(), &["fast", "slow"])
         );
     }
 
     #[test]
-    fn i8_invalid_value_negative() {
+    fn any_bool() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("-129".into())],
+            segments: vec![Segment::Value("true".into())],
         });
 
         assert_err_eq!(
-            i8::deserialize(deserializer),
-            Error::InvalidValue(Unexpected::Signed(-129).to_string(), "i8".to_owned())
+            Mode::deserialize(deserializer),
+            Error::InvalidType(
+                Unexpected::Bool(true).to_string(),
+                "a mode name or code".to_owned()
+            )
         );
     }
 
     #[test]
-    fn i8_invalid_value_out_of_i64_range() {
-        let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("9223372036854775808".into())],
-        });
+    fn any_no_value() {
+        let deserializer = Deserializer::new(Context { segments: vec![] });
 
         assert_err_eq!(
-            i8::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("9223372036854775808").to_string(),
-                "i8".to_owned()
+            Mode::deserialize(deserializer),
+            Error::InvalidType(
+                Unexpected::Unit.to_string(),
+                "a mode name or code".to_owned()
             )
         );
     }
 
     #[test]
-    fn i16() {
-        let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("42".into())],
-        });
+    #[should_panic(expected = "entered unreachable code")]
+    fn ignored_any() {
+        let deserializer = Deserializer::new(Context { segments: vec![] });
 
-        assert_ok_eq!(i16::deserialize(deserializer), 42);
+        let _ = IgnoredAny::deserialize(deserializer);
     }
 
     #[test]
-    fn i16_invalid_type() {
+    fn bool_false() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("a".into())],
+            segments: vec![Segment::Value("false".into())],
         });
 
-        assert_err_eq!(
-            i16::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "i16".to_owned())
-        );
+        assert_ok_eq!(bool::deserialize(deserializer), false);
     }
 
     #[test]
-    fn i16_invalid_type_not_utf8() {
+    fn bool_true() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value(vec![255])],
+            segments: vec![Segment::Value("true".into())],
         });
 
-        assert_err_eq!(
-            i16::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "i16".to_owned())
-        );
+        assert_ok_eq!(bool::deserialize(deserializer), true);
     }
 
     #[test]
-    fn i16_invalid_value_positive() {
-        let deserializer = Deserializer::new(Context {
+    fn bool_true_spellings() {
+        for spelling in ["true", "TRUE", "True", "yes", "YES", "on", "On", "1"] {
+            let deserializer = Deserializer::new(Context {
+                segments: vec![Segment::Value(spelling.into())],
+            });
+
+            assert_ok_eq!(bool::deserialize(deserializer), true, "{}", spelling);
+        }
+    }
+
+    #[test]
+    fn bool_false_spellings() {
+        for spelling in ["false", "FALSE", "False", "no", "NO", "off", "Off", "0"] {
+            let deserializer = Deserializer::new(Context {
+                segments: vec![Segment::Value(spelling.into())],
+            });
+
+            assert_ok_eq!(bool::deserialize(deserializer), false, "{}", spelling);
+        }
+    }
+
+    #[test]
+    fn bool_invalid_value() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("maybe".into())],
+        });
+
+        assert_err_eq!(
+            bool::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "a boolean (true/false, yes/no, on/off, or 1/0)".to_owned(),
+                value: "maybe".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn bool_invalid_value_numeric() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("2".into())],
+        });
+
+        assert_err_eq!(
+            bool::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "a boolean (true/false, yes/no, on/off, or 1/0)".to_owned(),
+                value: "2".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i8() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("42".into())],
+        });
+
+        assert_ok_eq!(i8::deserialize(deserializer), 42);
+    }
+
+    #[test]
+    fn i8_boundary() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("-128".into())],
+        });
+
+        assert_ok_eq!(i8::deserialize(deserializer), -128);
+    }
+
+    #[test]
+    fn i8_unparsable() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("a".into())],
+        });
+
+        assert_err_eq!(
+            i8::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "i8".to_owned(),
+                value: "a".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i8_unparsable_not_utf8() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value(vec![255])],
+        });
+
+        assert_err_eq!(
+            i8::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "i8".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i8_invalid_value_positive() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("128".into())],
+        });
+
+        assert_err_eq!(
+            i8::deserialize(deserializer),
+            Error::OutOfRange {
+                field: None,
+                expected: "i8".to_owned(),
+                min: i8::MIN.to_string(),
+                max: i8::MAX.to_string(),
+                value: "128".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i8_invalid_value_negative() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("-129".into())],
+        });
+
+        assert_err_eq!(
+            i8::deserialize(deserializer),
+            Error::OutOfRange {
+                field: None,
+                expected: "i8".to_owned(),
+                min: i8::MIN.to_string(),
+                max: i8::MAX.to_string(),
+                value: "-129".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i8_invalid_value_out_of_i64_range() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("9223372036854775808".into())],
+        });
+
+        assert_err_eq!(
+            i8::deserialize(deserializer),
+            Error::OutOfRange {
+                field: None,
+                expected: "i8".to_owned(),
+                min: i8::MIN.to_string(),
+                max: i8::MAX.to_string(),
+                value: "9223372036854775808".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i16() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("42".into())],
+        });
+
+        assert_ok_eq!(i16::deserialize(deserializer), 42);
+    }
+
+    #[test]
+    fn i16_unparsable() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("a".into())],
+        });
+
+        assert_err_eq!(
+            i16::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "i16".to_owned(),
+                value: "a".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i16_unparsable_not_utf8() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value(vec![255])],
+        });
+
+        assert_err_eq!(
+            i16::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "i16".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn i16_invalid_value_positive() {
+        let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("32768".into())],
         });
 
         assert_err_eq!(
             i16::deserialize(deserializer),
-            Error::InvalidValue(Unexpected::Signed(32768).to_string(), "i16".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "i16".to_owned(),
+                min: i16::MIN.to_string(),
+                max: i16::MAX.to_string(),
+                value: "32768".to_owned(),
+            }
         );
     }
 
@@ -960,7 +1632,13 @@ mod tests {
 
         assert_err_eq!(
             i16::deserialize(deserializer),
-            Error::InvalidValue(Unexpected::Signed(-32769).to_string(), "i16".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "i16".to_owned(),
+                min: i16::MIN.to_string(),
+                max: i16::MAX.to_string(),
+                value: "-32769".to_owned(),
+            }
         );
     }
 
@@ -972,10 +1650,13 @@ mod tests {
 
         assert_err_eq!(
             i16::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("9223372036854775808").to_string(),
-                "i16".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "i16".to_owned(),
+                min: i16::MIN.to_string(),
+                max: i16::MAX.to_string(),
+                value: "9223372036854775808".to_owned(),
+            }
         );
     }
 
@@ -989,26 +1670,34 @@ mod tests {
     }
 
     #[test]
-    fn i32_invalid_type() {
+    fn i32_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             i32::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "i32".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "i32".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn i32_invalid_type_not_utf8() {
+    fn i32_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             i32::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "i32".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "i32".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1020,7 +1709,13 @@ mod tests {
 
         assert_err_eq!(
             i32::deserialize(deserializer),
-            Error::InvalidValue(Unexpected::Signed(2147483648).to_string(), "i32".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "i32".to_owned(),
+                min: i32::MIN.to_string(),
+                max: i32::MAX.to_string(),
+                value: "2147483648".to_owned(),
+            }
         );
     }
 
@@ -1032,10 +1727,13 @@ mod tests {
 
         assert_err_eq!(
             i32::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Signed(-2147483649).to_string(),
-                "i32".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "i32".to_owned(),
+                min: i32::MIN.to_string(),
+                max: i32::MAX.to_string(),
+                value: "-2147483649".to_owned(),
+            }
         );
     }
 
@@ -1047,10 +1745,13 @@ mod tests {
 
         assert_err_eq!(
             i32::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("9223372036854775808").to_string(),
-                "i32".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "i32".to_owned(),
+                min: i32::MIN.to_string(),
+                max: i32::MAX.to_string(),
+                value: "9223372036854775808".to_owned(),
+            }
         );
     }
 
@@ -1064,26 +1765,34 @@ mod tests {
     }
 
     #[test]
-    fn i64_invalid_type() {
+    fn i64_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             i64::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "i64".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "i64".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn i64_invalid_type_not_utf8() {
+    fn i64_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             i64::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "i64".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "i64".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1095,10 +1804,13 @@ mod tests {
 
         assert_err_eq!(
             i64::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("9223372036854775808").to_string(),
-                "i64".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "i64".to_owned(),
+                min: i64::MIN.to_string(),
+                max: i64::MAX.to_string(),
+                value: "9223372036854775808".to_owned(),
+            }
         );
     }
 
@@ -1110,10 +1822,13 @@ mod tests {
 
         assert_err_eq!(
             i64::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("-9223372036854775809").to_string(),
-                "i64".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "i64".to_owned(),
+                min: i64::MIN.to_string(),
+                max: i64::MAX.to_string(),
+                value: "-9223372036854775809".to_owned(),
+            }
         );
     }
 
@@ -1127,26 +1842,45 @@ mod tests {
     }
 
     #[test]
-    fn i128_invalid_type() {
+    fn i128_min() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value(
+                "-170141183460469231731687303715884105728".into(),
+            )],
+        });
+
+        assert_ok_eq!(i128::deserialize(deserializer), i128::MIN);
+    }
+
+    #[test]
+    fn i128_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             i128::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "i128".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "i128".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn i128_invalid_type_not_utf8() {
+    fn i128_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             i128::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "i128".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "i128".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1160,10 +1894,13 @@ mod tests {
 
         assert_err_eq!(
             i128::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("170141183460469231731687303715884105728").to_string(),
-                "i128".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "i128".to_owned(),
+                min: i128::MIN.to_string(),
+                max: i128::MAX.to_string(),
+                value: "170141183460469231731687303715884105728".to_owned(),
+            }
         );
     }
 
@@ -1177,10 +1914,13 @@ mod tests {
 
         assert_err_eq!(
             i128::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("-170141183460469231731687303715884105729").to_string(),
-                "i128".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "i128".to_owned(),
+                min: i128::MIN.to_string(),
+                max: i128::MAX.to_string(),
+                value: "-170141183460469231731687303715884105729".to_owned(),
+            }
         );
     }
 
@@ -1194,26 +1934,43 @@ mod tests {
     }
 
     #[test]
-    fn u8_invalid_type() {
+    fn u8_boundary() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("255".into())],
+        });
+
+        assert_ok_eq!(u8::deserialize(deserializer), 255);
+    }
+
+    #[test]
+    fn u8_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             u8::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "u8".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u8".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn u8_invalid_type_not_utf8() {
+    fn u8_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             u8::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "u8".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u8".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1225,7 +1982,13 @@ mod tests {
 
         assert_err_eq!(
             u8::deserialize(deserializer),
-            Error::InvalidValue(Unexpected::Unsigned(256).to_string(), "u8".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "u8".to_owned(),
+                min: u8::MIN.to_string(),
+                max: u8::MAX.to_string(),
+                value: "256".to_owned(),
+            }
         );
     }
 
@@ -1237,7 +2000,13 @@ mod tests {
 
         assert_err_eq!(
             u8::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("-1").to_string(), "u8".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "u8".to_owned(),
+                min: u8::MIN.to_string(),
+                max: u8::MAX.to_string(),
+                value: "-1".to_owned(),
+            }
         );
     }
 
@@ -1249,10 +2018,13 @@ mod tests {
 
         assert_err_eq!(
             u8::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("18446744073709551616").to_string(),
-                "u8".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "u8".to_owned(),
+                min: u8::MIN.to_string(),
+                max: u8::MAX.to_string(),
+                value: "18446744073709551616".to_owned(),
+            }
         );
     }
 
@@ -1266,26 +2038,34 @@ mod tests {
     }
 
     #[test]
-    fn u16_invalid_type() {
+    fn u16_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             u16::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "u16".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u16".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn u16_invalid_type_not_utf8() {
+    fn u16_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             u16::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "u16".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u16".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1297,7 +2077,13 @@ mod tests {
 
         assert_err_eq!(
             u16::deserialize(deserializer),
-            Error::InvalidValue(Unexpected::Unsigned(65536).to_string(), "u16".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "u16".to_owned(),
+                min: u16::MIN.to_string(),
+                max: u16::MAX.to_string(),
+                value: "65536".to_owned(),
+            }
         );
     }
 
@@ -1309,7 +2095,13 @@ mod tests {
 
         assert_err_eq!(
             u16::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("-1").to_string(), "u16".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "u16".to_owned(),
+                min: u16::MIN.to_string(),
+                max: u16::MAX.to_string(),
+                value: "-1".to_owned(),
+            }
         );
     }
 
@@ -1321,10 +2113,13 @@ mod tests {
 
         assert_err_eq!(
             u16::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("18446744073709551616").to_string(),
-                "u16".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "u16".to_owned(),
+                min: u16::MIN.to_string(),
+                max: u16::MAX.to_string(),
+                value: "18446744073709551616".to_owned(),
+            }
         );
     }
 
@@ -1338,26 +2133,34 @@ mod tests {
     }
 
     #[test]
-    fn u32_invalid_type() {
+    fn u32_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             u32::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "u32".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u32".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn u32_invalid_type_not_utf8() {
+    fn u32_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             u32::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "u32".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u32".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1369,10 +2172,13 @@ mod tests {
 
         assert_err_eq!(
             u32::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Unsigned(4294967296).to_string(),
-                "u32".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "u32".to_owned(),
+                min: u32::MIN.to_string(),
+                max: u32::MAX.to_string(),
+                value: "4294967296".to_owned(),
+            }
         );
     }
 
@@ -1384,7 +2190,13 @@ mod tests {
 
         assert_err_eq!(
             u32::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("-1").to_string(), "u32".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "u32".to_owned(),
+                min: u32::MIN.to_string(),
+                max: u32::MAX.to_string(),
+                value: "-1".to_owned(),
+            }
         );
     }
 
@@ -1396,10 +2208,13 @@ mod tests {
 
         assert_err_eq!(
             u32::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("18446744073709551616").to_string(),
-                "u32".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "u32".to_owned(),
+                min: u32::MIN.to_string(),
+                max: u32::MAX.to_string(),
+                value: "18446744073709551616".to_owned(),
+            }
         );
     }
 
@@ -1413,26 +2228,34 @@ mod tests {
     }
 
     #[test]
-    fn u64_invalid_type() {
+    fn u64_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             u64::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "u64".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u64".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn u64_invalid_type_not_utf8() {
+    fn u64_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             u64::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "u64".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u64".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1444,10 +2267,13 @@ mod tests {
 
         assert_err_eq!(
             u64::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("18446744073709551616").to_string(),
-                "u64".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "u64".to_owned(),
+                min: u64::MIN.to_string(),
+                max: u64::MAX.to_string(),
+                value: "18446744073709551616".to_owned(),
+            }
         );
     }
 
@@ -1459,7 +2285,13 @@ mod tests {
 
         assert_err_eq!(
             u64::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("-1").to_string(), "u64".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "u64".to_owned(),
+                min: u64::MIN.to_string(),
+                max: u64::MAX.to_string(),
+                value: "-1".to_owned(),
+            }
         );
     }
 
@@ -1473,26 +2305,45 @@ mod tests {
     }
 
     #[test]
-    fn u128_invalid_type() {
+    fn u128_max() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value(
+                "340282366920938463463374607431768211455".into(),
+            )],
+        });
+
+        assert_ok_eq!(u128::deserialize(deserializer), u128::MAX);
+    }
+
+    #[test]
+    fn u128_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             u128::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "u128".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u128".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn u128_invalid_type_not_utf8() {
+    fn u128_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             u128::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "u128".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "u128".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
@@ -1506,10 +2357,13 @@ mod tests {
 
         assert_err_eq!(
             u128::deserialize(deserializer),
-            Error::InvalidValue(
-                Unexpected::Other("340282366920938463463374607431768211456").to_string(),
-                "u128".to_owned()
-            )
+            Error::OutOfRange {
+                field: None,
+                expected: "u128".to_owned(),
+                min: u128::MIN.to_string(),
+                max: u128::MAX.to_string(),
+                value: "340282366920938463463374607431768211456".to_owned(),
+            }
         );
     }
 
@@ -1521,7 +2375,13 @@ mod tests {
 
         assert_err_eq!(
             u128::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("-1").to_string(), "u128".to_owned())
+            Error::OutOfRange {
+                field: None,
+                expected: "u128".to_owned(),
+                min: u128::MIN.to_string(),
+                max: u128::MAX.to_string(),
+                value: "-1".to_owned(),
+            }
         );
     }
 
@@ -1535,107 +2395,379 @@ mod tests {
     }
 
     #[test]
-    fn f32_invalid_type() {
+    fn f32_unparsable() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value("a".into())],
         });
 
         assert_err_eq!(
             f32::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "f32".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "f32".to_owned(),
+                value: "a".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn f32_invalid_type_not_utf8() {
+    fn f32_unparsable_not_utf8() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Value(vec![255])],
         });
 
         assert_err_eq!(
             f32::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "f32".to_owned())
+            Error::Unparsable {
+                field: None,
+                expected: "f32".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn f64() {
+    fn f32_infinity() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("42.9".into())],
+            segments: vec![Segment::Value("inf".into())],
         });
 
-        assert_ok_eq!(f64::deserialize(deserializer), 42.9);
+        assert_ok_eq!(f32::deserialize(deserializer), f32::INFINITY);
     }
 
     #[test]
-    fn f64_invalid_type() {
+    fn f32_nan() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("a".into())],
+            segments: vec![Segment::Value("NaN".into())],
         });
 
-        assert_err_eq!(
-            f64::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("a").to_string(), "f64".to_owned())
-        );
+        assert!(assert_ok!(f32::deserialize(deserializer)).is_nan());
     }
 
     #[test]
-    fn f64_invalid_type_not_utf8() {
+    fn f32_decimal_comma() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value(vec![255])],
+            segments: vec![Segment::Value("3,14".into())],
         });
 
         assert_err_eq!(
-            f64::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Other("\u{fffd}").to_string(), "f64".to_owned())
+            f32::deserialize(deserializer),
+            Error::DecimalComma {
+                field: None,
+                expected: "f32".to_owned(),
+                value: "3,14".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn char() {
+    fn f32_negative_zero() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("a".into())],
+            segments: vec![Segment::Value("-0".into())],
         });
 
-        assert_ok_eq!(char::deserialize(deserializer), 'a');
+        let float = assert_ok!(f32::deserialize(deserializer));
+        assert_eq!(float, 0.0);
+        assert!(float.is_sign_negative());
     }
 
     #[test]
-    fn char_not_utf8() {
+    fn f32_invalid_value_positive() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value(vec![255])],
+            segments: vec![Segment::Value("1e39".into())],
         });
 
         assert_err_eq!(
-            char::deserialize(deserializer),
-            Error::InvalidType(
-                Unexpected::Other("\u{fffd}").to_string(),
-                "a character".to_owned()
-            )
+            f32::deserialize(deserializer),
+            Error::OutOfRange {
+                field: None,
+                expected: "f32".to_owned(),
+                min: format!("{:e}", f32::MIN),
+                max: format!("{:e}", f32::MAX),
+                value: "1e39".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn char_from_string() {
+    fn f32_invalid_value_negative() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("foo".into())],
+            segments: vec![Segment::Value("-1e39".into())],
         });
 
         assert_err_eq!(
-            char::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Str("foo").to_string(), "a character".to_owned())
+            f32::deserialize(deserializer),
+            Error::OutOfRange {
+                field: None,
+                expected: "f32".to_owned(),
+                min: format!("{:e}", f32::MIN),
+                max: format!("{:e}", f32::MAX),
+                value: "-1e39".to_owned(),
+            }
         );
     }
 
     #[test]
-    fn char_from_empty() {
+    fn f64() {
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("".into())],
+            segments: vec![Segment::Value("42.9".into())],
         });
 
-        assert_err_eq!(
+        assert_ok_eq!(f64::deserialize(deserializer), 42.9);
+    }
+
+    #[test]
+    fn f64_unparsable() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("a".into())],
+        });
+
+        assert_err_eq!(
+            f64::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "f64".to_owned(),
+                value: "a".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn f64_unparsable_not_utf8() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value(vec![255])],
+        });
+
+        assert_err_eq!(
+            f64::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "f64".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn f64_infinity() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("-Infinity".into())],
+        });
+
+        assert_ok_eq!(f64::deserialize(deserializer), -f64::INFINITY);
+    }
+
+    #[test]
+    fn f64_nan() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("nan".into())],
+        });
+
+        assert!(assert_ok!(f64::deserialize(deserializer)).is_nan());
+    }
+
+    #[test]
+    #[allow(clippy::approx_constant)]
+    fn f64_decimal() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("3.14".into())],
+        });
+
+        assert_ok_eq!(f64::deserialize(deserializer), 3.14);
+    }
+
+    #[test]
+    fn f64_negative_infinity_abbreviated() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("-inf".into())],
+        });
+
+        assert_ok_eq!(f64::deserialize(deserializer), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn f64_decimal_comma() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("3,14".into())],
+        });
+
+        assert_err_eq!(
+            f64::deserialize(deserializer),
+            Error::DecimalComma {
+                field: None,
+                expected: "f64".to_owned(),
+                value: "3,14".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn f64_multiple_commas() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("1,000,000".into())],
+        });
+
+        assert_err_eq!(
+            f64::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "f64".to_owned(),
+                value: "1,000,000".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn f64_negative_zero() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("-0".into())],
+        });
+
+        let float = assert_ok!(f64::deserialize(deserializer));
+        assert_eq!(float, 0.0);
+        assert!(float.is_sign_negative());
+    }
+
+    #[test]
+    fn f64_invalid_value_positive() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("1e999".into())],
+        });
+
+        assert_err_eq!(
+            f64::deserialize(deserializer),
+            Error::OutOfRange {
+                field: None,
+                expected: "f64".to_owned(),
+                min: format!("{:e}", f64::MIN),
+                max: format!("{:e}", f64::MAX),
+                value: "1e999".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn f64_invalid_value_negative() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("-1e999".into())],
+        });
+
+        assert_err_eq!(
+            f64::deserialize(deserializer),
+            Error::OutOfRange {
+                field: None,
+                expected: "f64".to_owned(),
+                min: format!("{:e}", f64::MIN),
+                max: format!("{:e}", f64::MAX),
+                value: "-1e999".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn char() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("a".into())],
+        });
+
+        assert_ok_eq!(char::deserialize(deserializer), 'a');
+    }
+
+    #[test]
+    fn char_not_utf8() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value(vec![255])],
+        });
+
+        assert_err_eq!(
+            char::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "a character".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn char_from_string() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("foo".into())],
+        });
+
+        assert_err_eq!(
+            char::deserialize(deserializer),
+            Error::InvalidChar("foo".to_owned())
+        );
+    }
+
+    #[test]
+    fn char_from_two_characters() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("ab".into())],
+        });
+
+        assert_err_eq!(
+            char::deserialize(deserializer),
+            Error::InvalidChar("ab".to_owned())
+        );
+    }
+
+    #[test]
+    fn char_multibyte() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("🦀".into())],
+        });
+
+        assert_ok_eq!(char::deserialize(deserializer), '🦀');
+    }
+
+    #[test]
+    fn char_from_grapheme_cluster() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("🇺🇸".into())],
+        });
+
+        assert_err_eq!(
+            char::deserialize(deserializer),
+            Error::InvalidChar("🇺🇸".to_owned())
+        );
+    }
+
+    #[test]
+    fn char_from_empty() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("".into())],
+        });
+
+        assert_err_eq!(
             char::deserialize(deserializer),
-            Error::InvalidType(Unexpected::Str("").to_string(), "a character".to_owned())
+            Error::InvalidChar(String::new())
+        );
+    }
+
+    #[test]
+    fn option_char_multibyte() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![Segment::Value("é".into())],
+            })],
+        });
+
+        assert_ok_eq!(Option::<char>::deserialize(deserializer), Some('é'));
+    }
+
+    #[test]
+    fn option_char_from_two_characters() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![Segment::Value("ab".into())],
+            })],
+        });
+
+        assert_err_eq!(
+            Option::<char>::deserialize(deserializer),
+            Error::InvalidChar("ab".to_owned())
         );
     }
 
@@ -1648,6 +2780,18 @@ mod tests {
         assert_ok_eq!(String::deserialize(deserializer), "foo");
     }
 
+    #[test]
+    fn str_unused_value() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("foo".into()), Segment::Value("bar".into())],
+        });
+
+        assert_err_eq!(
+            String::deserialize(deserializer),
+            Error::Unused(vec!["bar".into()])
+        );
+    }
+
     #[test]
     fn str_not_utf8() {
         let deserializer = Deserializer::new(Context {
@@ -1656,9 +2800,51 @@ mod tests {
 
         assert_err_eq!(
             String::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "a string".to_owned(),
+                value: "\u{fffd}".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn str_borrowed() {
+        let source = vec![OsString::from("foo")];
+        let deserializer = Deserializer::with_source(
+            Context {
+                segments: vec![Segment::Value("foo".into())],
+            },
+            &source,
+        );
+
+        assert_ok_eq!(<&str>::deserialize(deserializer), "foo");
+    }
+
+    #[test]
+    fn str_borrowed_suffix() {
+        let source = vec![OsString::from("--x")];
+        let deserializer = Deserializer::with_source(
+            Context {
+                segments: vec![Segment::Value("-x".into())],
+            },
+            &source,
+        );
+
+        assert_ok_eq!(<&str>::deserialize(deserializer), "-x");
+    }
+
+    #[test]
+    fn str_borrowed_without_source() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("foo".into())],
+        });
+
+        assert_err_eq!(
+            <&str>::deserialize(deserializer),
             Error::InvalidType(
-                Unexpected::Other("\u{fffd}").to_string(),
-                "a string".to_owned()
+                Unexpected::Str("foo").to_string(),
+                "a borrowed string".to_owned()
             )
         );
     }
@@ -1733,6 +2919,19 @@ mod tests {
         assert_ok_eq!(Bytes::deserialize(deserializer), Bytes(vec![255]));
     }
 
+    #[test]
+    fn bytes_borrowed() {
+        let source = vec![OsString::from("foo")];
+        let deserializer = Deserializer::with_source(
+            Context {
+                segments: vec![Segment::Value("foo".into())],
+            },
+            &source,
+        );
+
+        assert_ok_eq!(<&[u8]>::deserialize(deserializer), b"foo");
+    }
+
     #[test]
     fn identifier() {
         #[derive(Debug, Eq, PartialEq)]
@@ -1775,70 +2974,404 @@ mod tests {
     fn unit() {
         let deserializer = Deserializer::new(Context { segments: vec![] });
 
-        assert_ok_eq!(<()>::deserialize(deserializer), ());
+        assert_ok_eq!(<()>::deserialize(deserializer), ());
+    }
+
+    #[test]
+    fn unit_unused_value() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("foo".into())],
+        });
+
+        assert_err_eq!(
+            <()>::deserialize(deserializer),
+            Error::Unused(vec!["foo".into()])
+        );
+    }
+
+    #[test]
+    fn unit_struct() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Unit;
+
+        let deserializer = Deserializer::new(Context { segments: vec![] });
+
+        assert_ok_eq!(Unit::deserialize(deserializer), Unit);
+    }
+
+    #[test]
+    fn newtype_struct() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Newtype(u64);
+
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("42".into())],
+        });
+
+        assert_ok_eq!(Newtype::deserialize(deserializer), Newtype(42));
+    }
+
+    #[test]
+    fn option_unit_some() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context { segments: vec![] })],
+        });
+
+        assert_ok_eq!(Option::<()>::deserialize(deserializer), Some(()));
+    }
+
+    #[test]
+    fn option_unit_none() {
+        let deserializer = Deserializer::new(Context { segments: vec![] });
+
+        assert_ok_eq!(Option::<()>::deserialize(deserializer), None);
+    }
+
+    #[test]
+    fn option_primitive_some() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![Segment::Value("42".into())],
+            })],
+        });
+
+        assert_ok_eq!(Option::<u64>::deserialize(deserializer), Some(42));
+    }
+
+    #[test]
+    fn option_primitive_none() {
+        let deserializer = Deserializer::new(Context { segments: vec![] });
+
+        assert_ok_eq!(Option::<u64>::deserialize(deserializer), None);
+    }
+
+    #[test]
+    fn seq() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("1".into()), Segment::Value("2".into())],
+        });
+
+        assert_ok_eq!(Vec::<u64>::deserialize(deserializer), vec![1, 2]);
+    }
+
+    #[test]
+    fn seq_empty() {
+        let deserializer = Deserializer::new(Context { segments: vec![] });
+
+        assert_ok_eq!(Vec::<u64>::deserialize(deserializer), vec![]);
+    }
+
+    #[test]
+    fn seq_invalid_element() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Value("1".into()), Segment::Value("foo".into())],
+        });
+
+        assert_err_eq!(
+            Vec::<u64>::deserialize(deserializer),
+            Error::Unparsable {
+                field: None,
+                expected: "u64".to_owned(),
+                value: "foo".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn tuple() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("arg1"), Segment::Value("foo".into())],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("arg2"), Segment::Value("42".into())],
+                }),
+            ],
+        });
+
+        assert_ok_eq!(
+            <(String, u32)>::deserialize(deserializer),
+            ("foo".to_owned(), 42)
+        );
+    }
+
+    #[test]
+    fn tuple_boolean_element() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("arg1"), Segment::Value("no".into())],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("arg2"), Segment::Value("yes".into())],
+                }),
+            ],
+        });
+
+        assert_ok_eq!(<[bool; 2]>::deserialize(deserializer), [false, true]);
+    }
+
+    #[test]
+    fn tuple_element_error_names_element() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("arg1"), Segment::Value("1".into())],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("arg2"), Segment::Value("0".into())],
+                }),
+            ],
+        });
+
+        assert_err_eq!(
+            <(NonZeroU8, NonZeroU8)>::deserialize(deserializer),
+            Error::Zero {
+                field: Some("<arg2>".to_owned())
+            }
+        );
+    }
+
+    #[test]
+    fn seq_set_duplicates() {
+        let deserializer = Deserializer::new(Context {
+            segments: vec![
+                Segment::Value("foo".into()),
+                Segment::Value("bar".into()),
+                Segment::Value("foo".into()),
+            ],
+        });
+
+        let set = assert_ok!(HashSet::<String>::deserialize(deserializer));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set, HashSet::from(["foo".to_owned(), "bar".to_owned()]));
+    }
+
+    #[test]
+    fn seq_borrowed() {
+        let source = vec![OsString::from("foo"), OsString::from("bar")];
+        let deserializer = Deserializer::with_source(
+            Context {
+                segments: vec![Segment::Value("foo".into()), Segment::Value("bar".into())],
+            },
+            &source,
+        );
+
+        assert_ok_eq!(Vec::<&str>::deserialize(deserializer), vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn struct_with_required_field() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct {
+            foo: usize,
+        }
+
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![Segment::Identifier("foo"), Segment::Value("42".into())],
+            })],
+        });
+
+        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: 42 });
+    }
+
+    #[test]
+    fn struct_with_borrowed_field() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct<'a> {
+            foo: &'a str,
+        }
+
+        let source = vec![OsString::from("bar")];
+        let deserializer = Deserializer::with_source(
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![Segment::Identifier("foo"), Segment::Value("bar".into())],
+                })],
+            },
+            &source,
+        );
+
+        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: "bar" });
+    }
+
+    #[test]
+    fn struct_with_borrowed_cow_field() {
+        #[derive(Debug, Deserialize)]
+        struct Struct<'a> {
+            #[serde(borrow)]
+            foo: Cow<'a, str>,
+        }
+
+        let source = vec![OsString::from("bar")];
+        let deserializer = Deserializer::with_source(
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![Segment::Identifier("foo"), Segment::Value("bar".into())],
+                })],
+            },
+            &source,
+        );
+
+        assert_matches!(
+            assert_ok!(Struct::deserialize(deserializer)).foo,
+            Cow::Borrowed("bar")
+        );
+    }
+
+    #[test]
+    fn struct_with_cow_field_without_source() {
+        #[derive(Debug, Deserialize)]
+        struct Struct<'a> {
+            #[serde(borrow)]
+            foo: Cow<'a, str>,
+        }
+
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![Segment::Identifier("foo"), Segment::Value("bar".into())],
+            })],
+        });
+
+        assert_matches!(
+            assert_ok!(Struct::deserialize(deserializer)).foo,
+            Cow::Owned(value) if value == "bar"
+        );
     }
 
     #[test]
-    fn unit_struct() {
-        #[derive(Debug, Deserialize, Eq, PartialEq)]
-        struct Unit;
+    fn struct_with_optional_field_present() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct {
+            foo: Option<usize>,
+        }
 
-        let deserializer = Deserializer::new(Context { segments: vec![] });
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![
+                    Segment::Identifier("foo"),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Value("42".into())],
+                    }),
+                ],
+            })],
+        });
 
-        assert_ok_eq!(Unit::deserialize(deserializer), Unit);
+        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: Some(42) });
     }
 
     #[test]
-    fn newtype_struct() {
-        #[derive(Debug, Deserialize, Eq, PartialEq)]
-        struct Newtype(u64);
+    fn struct_with_optional_field_absent() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct {
+            foo: Option<usize>,
+        }
 
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Value("42".into())],
+            segments: vec![Segment::Context(Context {
+                segments: vec![Segment::Identifier("foo")],
+            })],
         });
 
-        assert_ok_eq!(Newtype::deserialize(deserializer), Newtype(42));
+        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: None });
     }
 
     #[test]
-    fn option_unit_some() {
+    fn struct_with_required_field_out_of_range() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct {
+            foo: u8,
+        }
+
         let deserializer = Deserializer::new(Context {
-            segments: vec![Segment::Context(Context { segments: vec![] })],
+            segments: vec![Segment::Context(Context {
+                segments: vec![
+                    Segment::Identifier("foo"),
+                    Segment::Value("99999999999".into()),
+                ],
+            })],
         });
 
-        assert_ok_eq!(Option::<()>::deserialize(deserializer), Some(()));
+        assert_err_eq!(
+            Struct::deserialize(deserializer),
+            Error::OutOfRange {
+                field: Some("<foo>".to_owned()),
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "99999999999".to_owned(),
+            }
+        );
     }
 
     #[test]
-    fn option_unit_none() {
-        let deserializer = Deserializer::new(Context { segments: vec![] });
-
-        assert_ok_eq!(Option::<()>::deserialize(deserializer), None);
-    }
+    fn struct_with_optional_field_out_of_range() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct {
+            foo: Option<i8>,
+        }
 
-    #[test]
-    fn option_primitive_some() {
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Context(Context {
-                segments: vec![Segment::Value("42".into())],
+                segments: vec![
+                    Segment::Identifier("foo"),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Value("-129".into())],
+                    }),
+                ],
             })],
         });
 
-        assert_ok_eq!(Option::<u64>::deserialize(deserializer), Some(42));
+        assert_err_eq!(
+            Struct::deserialize(deserializer),
+            Error::OutOfRange {
+                field: Some("--foo".to_owned()),
+                expected: "i8".to_owned(),
+                min: "-128".to_owned(),
+                max: "127".to_owned(),
+                value: "-129".to_owned(),
+            }
+        );
     }
 
     #[test]
-    fn option_primitive_none() {
-        let deserializer = Deserializer::new(Context { segments: vec![] });
+    fn struct_with_optional_short_field_out_of_range() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct {
+            #[serde(alias = "f")]
+            foo: Option<u8>,
+        }
 
-        assert_ok_eq!(Option::<u64>::deserialize(deserializer), None);
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![
+                    Segment::Identifier("f"),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Value("256".into())],
+                    }),
+                ],
+            })],
+        });
+
+        assert_err_eq!(
+            Struct::deserialize(deserializer),
+            Error::OutOfRange {
+                field: Some("-f".to_owned()),
+                expected: "u8".to_owned(),
+                min: "0".to_owned(),
+                max: "255".to_owned(),
+                value: "256".to_owned(),
+            }
+        );
     }
 
     #[test]
-    fn struct_with_required_field() {
+    fn struct_with_required_field_nonzero() {
         #[derive(Debug, Deserialize, PartialEq, Eq)]
         struct Struct {
-            foo: usize,
+            foo: NonZeroU8,
         }
 
         let deserializer = Deserializer::new(Context {
@@ -1847,44 +3380,59 @@ mod tests {
             })],
         });
 
-        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: 42 });
+        assert_ok_eq!(
+            Struct::deserialize(deserializer),
+            Struct {
+                foo: assert_some!(NonZeroU8::new(42)),
+            }
+        );
     }
 
     #[test]
-    fn struct_with_optional_field_present() {
+    fn struct_with_required_field_zero() {
         #[derive(Debug, Deserialize, PartialEq, Eq)]
         struct Struct {
-            foo: Option<usize>,
+            foo: NonZeroU8,
         }
 
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Context(Context {
-                segments: vec![
-                    Segment::Identifier("foo"),
-                    Segment::Context(Context {
-                        segments: vec![Segment::Value("42".into())],
-                    }),
-                ],
+                segments: vec![Segment::Identifier("foo"), Segment::Value("0".into())],
             })],
         });
 
-        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: Some(42) });
+        assert_err_eq!(
+            Struct::deserialize(deserializer),
+            Error::Zero {
+                field: Some("<foo>".to_owned()),
+            }
+        );
     }
 
     #[test]
-    fn struct_with_optional_field_absent() {
+    fn struct_with_optional_field_zero() {
         #[derive(Debug, Deserialize, PartialEq, Eq)]
         struct Struct {
-            foo: Option<usize>,
+            foo: Option<NonZeroUsize>,
         }
 
         let deserializer = Deserializer::new(Context {
             segments: vec![Segment::Context(Context {
-                segments: vec![Segment::Identifier("foo")],
+                segments: vec![
+                    Segment::Identifier("foo"),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Value("0".into())],
+                    }),
+                ],
             })],
         });
 
-        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: None });
+        assert_err_eq!(
+            Struct::deserialize(deserializer),
+            Error::Zero {
+                field: Some("--foo".to_owned()),
+            }
+        );
     }
 
     #[test]
@@ -1990,6 +3538,156 @@ mod tests {
         );
     }
 
+    #[test]
+    fn struct_with_defaults() {
+        #[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
+        struct Struct {
+            foo: usize,
+            bar: Option<usize>,
+            baz: Option<usize>,
+            qux: bool,
+        }
+
+        let deserializer = Deserializer::with_defaults(
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("foo"), Segment::Value("1".into())],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("bar"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("2".into())],
+                            }),
+                        ],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("baz")],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("qux")],
+                    }),
+                ],
+            },
+            Some(assert_ok!(Value::from_serialize(&Struct {
+                foo: 3,
+                bar: Some(4),
+                baz: Some(5),
+                qux: true,
+            }))),
+        );
+
+        assert_ok_eq!(
+            Struct::deserialize(deserializer),
+            Struct {
+                foo: 1,
+                bar: Some(2),
+                baz: Some(5),
+                qux: true,
+            }
+        );
+    }
+
+    #[test]
+    fn struct_with_default_none() {
+        #[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
+        struct Struct {
+            foo: Option<usize>,
+        }
+
+        let deserializer = Deserializer::with_defaults(
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![Segment::Identifier("foo")],
+                })],
+            },
+            Some(assert_ok!(Value::from_serialize(&Struct { foo: None }))),
+        );
+
+        assert_ok_eq!(Struct::deserialize(deserializer), Struct { foo: None });
+    }
+
+    #[test]
+    fn struct_with_nested_defaults() {
+        #[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
+        struct Inner {
+            bar: Option<usize>,
+            baz: Option<usize>,
+        }
+
+        #[derive(Debug, Deserialize, PartialEq, Eq, Serialize)]
+        struct Struct {
+            foo: Option<Inner>,
+        }
+
+        let deserializer = Deserializer::with_defaults(
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("foo"),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Context(Context {
+                                    segments: vec![
+                                        Segment::Identifier("bar"),
+                                        Segment::Context(Context {
+                                            segments: vec![Segment::Value("1".into())],
+                                        }),
+                                    ],
+                                }),
+                                Segment::Context(Context {
+                                    segments: vec![Segment::Identifier("baz")],
+                                }),
+                            ],
+                        }),
+                    ],
+                })],
+            },
+            Some(assert_ok!(Value::from_serialize(&Struct {
+                foo: Some(Inner {
+                    bar: Some(2),
+                    baz: Some(3),
+                }),
+            }))),
+        );
+
+        assert_ok_eq!(
+            Struct::deserialize(deserializer),
+            Struct {
+                foo: Some(Inner {
+                    bar: Some(1),
+                    baz: Some(3),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn struct_with_default_error() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        struct Struct {
+            foo: Option<u8>,
+        }
+
+        let deserializer = Deserializer::with_defaults(
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![Segment::Identifier("foo")],
+                })],
+            },
+            Some(Value::Struct(vec![(
+                "foo",
+                Value::Some(Box::new(Value::U64(256))),
+            )])),
+        );
+
+        assert_err_eq!(
+            Struct::deserialize(deserializer),
+            Error::invalid_value(Unexpected::Unsigned(256), &"u8").with_field("--foo")
+        );
+    }
+
     #[test]
     fn enum_unit() {
         #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -2018,6 +3716,42 @@ mod tests {
         assert_ok_eq!(Enum::deserialize(deserializer), Enum::Newtype(42));
     }
 
+    #[test]
+    fn enum_newtype_seq() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        enum Enum {
+            Files(Vec<String>),
+        }
+
+        let deserializer = Deserializer::new(Context {
+            segments: vec![
+                Segment::Identifier("Files"),
+                Segment::Value("a".into()),
+                Segment::Value("b".into()),
+                Segment::Value("c".into()),
+            ],
+        });
+
+        assert_ok_eq!(
+            Enum::deserialize(deserializer),
+            Enum::Files(vec!["a".into(), "b".into(), "c".into()])
+        );
+    }
+
+    #[test]
+    fn enum_newtype_seq_empty() {
+        #[derive(Debug, Deserialize, PartialEq, Eq)]
+        enum Enum {
+            Files(Vec<String>),
+        }
+
+        let deserializer = Deserializer::new(Context {
+            segments: vec![Segment::Identifier("Files")],
+        });
+
+        assert_ok_eq!(Enum::deserialize(deserializer), Enum::Files(vec![]));
+    }
+
     #[test]
     fn enum_struct() {
         #[derive(Debug, Deserialize, PartialEq, Eq)]
@@ -2091,6 +3825,8 @@ mod tests {
                 })],
             }
             .into_iter(),
+            source: &[],
+            defaults: None,
         };
 
         assert_ok_eq!(Option::<u64>::deserialize(deserializer), Some(42));
@@ -2101,6 +3837,10 @@ mod tests {
         let mut struct_access = StructAccess {
             struct_context: Context { segments: vec![] }.into_iter(),
             field_context: None,
+            field: None,
+            source: &[],
+            defaults: None,
+            field_defaults: None,
         };
 
         assert_none!(assert_ok!(struct_access.next_key::<()>()));
@@ -2123,6 +3863,10 @@ mod tests {
             }
             .into_iter(),
             field_context: None,
+            field: None,
+            source: &[],
+            defaults: None,
+            field_defaults: None,
         };
 
         assert_some_eq!(assert_ok!(struct_access.next_key::<Key>()), Key::Foo);
@@ -2145,6 +3889,10 @@ mod tests {
             }
             .into_iter(),
             field_context: None,
+            field: None,
+            source: &[],
+            defaults: None,
+            field_defaults: None,
         };
 
         assert_some_eq!(assert_ok!(struct_access.next_key::<Key>()), Key::Foo);
@@ -2165,6 +3913,7 @@ mod tests {
                 segments: vec![Segment::Identifier("foo"), Segment::Value("42".into())],
             }
             .into_iter(),
+            source: &[],
         };
 
         let (key, variant) = assert_ok!(enum_access.variant::<Key>());
@@ -2179,11 +3928,52 @@ mod tests {
     fn variant_access_unit_variant() {
         let variant_access = VariantAccess {
             context: Context { segments: vec![] }.into_iter(),
+            source: &[],
         };
 
         assert_ok!(variant_access.unit_variant());
     }
 
+    #[test]
+    fn variant_access_unit_variant_unused_options() {
+        // Options attached to a unit variant, which has no fields to consume them.
+        let variant_access = VariantAccess {
+            context: Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("verbose"),
+                            Segment::Context(Context { segments: vec![] }),
+                        ],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("o"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("out".into())],
+                            }),
+                        ],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("path"), Segment::Value("foo".into())],
+                    }),
+                ],
+            }
+            .into_iter(),
+            source: &[],
+        };
+
+        assert_err_eq!(
+            variant_access.unit_variant(),
+            Error::Unused(vec![
+                "--verbose".into(),
+                "-o".into(),
+                "out".into(),
+                "foo".into()
+            ])
+        );
+    }
+
     #[test]
     fn variant_access_newtype_variant() {
         let variant_access = VariantAccess {
@@ -2191,6 +3981,7 @@ mod tests {
                 segments: vec![Segment::Value("42".into())],
             }
             .into_iter(),
+            source: &[],
         };
 
         assert_ok_eq!(variant_access.newtype_variant::<u64>(), 42);
@@ -2264,6 +4055,7 @@ mod tests {
                 ],
             }
             .into_iter(),
+            source: &[],
         };
 
         assert_ok_eq!(
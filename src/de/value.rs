@@ -0,0 +1,747 @@
+//! Values held in memory, used as defaults for fields not given on the command line.
+//!
+//! Defaults are provided as an instance of the type being deserialized. Since command line
+//! arguments are not self-describing, the defaults are serialized into a [`Value`] up front, and
+//! each field's default is deserialized from it only when the field is missing from the parsed
+//! context.
+
+use serde::{
+    de,
+    de::{
+        value::{
+            MapDeserializer,
+            SeqDeserializer,
+        },
+        DeserializeSeed,
+        IntoDeserializer,
+        Unexpected,
+        Visitor,
+    },
+    forward_to_deserialize_any,
+    ser,
+    ser::Serialize,
+};
+use std::marker::PhantomData;
+
+pub(crate) use serde::de::value::Error;
+
+/// A serialized value.
+///
+/// Integers and floats are widened to the largest type of their kind, since they are narrowed
+/// again when deserialized. Tuples are stored as sequences, and unit structs as units.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Value {
+    Bool(bool),
+    I64(i64),
+    I128(i128),
+    U64(u64),
+    U128(u128),
+    F64(f64),
+    Char(char),
+    String(String),
+    Bytes(Vec<u8>),
+    None,
+    Some(Box<Value>),
+    Unit,
+    NewtypeStruct(Box<Value>),
+    Seq(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Struct(Vec<(&'static str, Value)>),
+    /// An enum variant, along with its contents.
+    ///
+    /// The contents are a unit for unit variants, a sequence for tuple variants, and a struct for
+    /// struct variants.
+    Variant(&'static str, Box<Value>),
+}
+
+impl Value {
+    /// Serializes `value` into a `Value`.
+    pub(crate) fn from_serialize<T>(value: &T) -> Result<Self, Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        value.serialize(Serializer)
+    }
+
+    /// Removes and returns the value of the field `name`, if this is a struct containing it.
+    pub(crate) fn take_field(&mut self, name: &str) -> Option<Value> {
+        match self {
+            Self::Struct(fields) => fields
+                .iter()
+                .position(|(field, _)| *field == name)
+                .map(|index| fields.swap_remove(index).1),
+            _ => None,
+        }
+    }
+
+    fn unexpected(&self) -> Unexpected<'_> {
+        match self {
+            Self::Bool(value) => Unexpected::Bool(*value),
+            Self::I64(value) => Unexpected::Signed(*value),
+            Self::I128(_) => Unexpected::Other("i128"),
+            Self::U64(value) => Unexpected::Unsigned(*value),
+            Self::U128(_) => Unexpected::Other("u128"),
+            Self::F64(value) => Unexpected::Float(*value),
+            Self::Char(value) => Unexpected::Char(*value),
+            Self::String(value) => Unexpected::Str(value),
+            Self::Bytes(value) => Unexpected::Bytes(value),
+            Self::None | Self::Some(_) => Unexpected::Option,
+            Self::Unit => Unexpected::Unit,
+            Self::NewtypeStruct(_) => Unexpected::NewtypeStruct,
+            Self::Seq(_) => Unexpected::Seq,
+            Self::Map(_) | Self::Struct(_) => Unexpected::Map,
+            Self::Variant(..) => Unexpected::Enum,
+        }
+    }
+}
+
+struct Serializer;
+
+impl ser::Serializer for Serializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeSeq;
+    type SerializeTuple = SerializeSeq;
+    type SerializeTupleStruct = SerializeSeq;
+    type SerializeTupleVariant = SerializeVariant<SerializeSeq>;
+    type SerializeMap = SerializeMap;
+    type SerializeStruct = SerializeStruct;
+    type SerializeStructVariant = SerializeVariant<SerializeStruct>;
+
+    fn serialize_bool(self, value: bool) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Bool(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::I64(value.into()))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::I64(value.into()))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::I64(value.into()))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::I64(value))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::I128(value))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::U64(value.into()))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::U64(value.into()))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::U64(value.into()))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::U64(value))
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::U128(value))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::F64(value.into()))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::F64(value))
+    }
+
+    fn serialize_char(self, value: char) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Char(value))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Bytes(value.to_owned()))
+    }
+
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::None)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(Value::Some(Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Unit)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Variant(variant, Box::new(Value::Unit)))
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(Value::NewtypeStruct(Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        Ok(Value::Variant(variant, Box::new(value.serialize(self)?)))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(SerializeSeq {
+            elements: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(SerializeVariant {
+            variant,
+            contents: self.serialize_seq(Some(len))?,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(SerializeMap {
+            entries: Vec::with_capacity(len.unwrap_or(0)),
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(SerializeStruct {
+            fields: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(SerializeVariant {
+            variant,
+            contents: self.serialize_struct("", len)?,
+        })
+    }
+}
+
+struct SerializeSeq {
+    elements: Vec<Value>,
+}
+
+impl ser::SerializeSeq for SerializeSeq {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.elements.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Seq(self.elements))
+    }
+}
+
+impl ser::SerializeTuple for SerializeSeq {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeSeq {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeMap {
+    entries: Vec<(Value, Value)>,
+    /// The most recently serialized key, waiting for its value.
+    key: Option<Value>,
+}
+
+impl ser::SerializeMap for SerializeMap {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        let key = self
+            .key
+            .take()
+            .expect("serialize_value called before serialize_key");
+        self.entries.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Map(self.entries))
+    }
+}
+
+struct SerializeStruct {
+    fields: Vec<(&'static str, Value)>,
+}
+
+impl ser::SerializeStruct for SerializeStruct {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        self.fields.push((key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Struct(self.fields))
+    }
+}
+
+/// Serializes the contents of a tuple or struct variant.
+struct SerializeVariant<Contents> {
+    variant: &'static str,
+    contents: Contents,
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeSeq> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(&mut self.contents, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Variant(
+            self.variant,
+            Box::new(ser::SerializeSeq::end(self.contents)?),
+        ))
+    }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeStruct> {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: Serialize + ?Sized,
+    {
+        ser::SerializeStruct::serialize_field(&mut self.contents, key, value)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(Value::Variant(
+            self.variant,
+            Box::new(ser::SerializeStruct::end(self.contents)?),
+        ))
+    }
+}
+
+/// Deserializes a [`Value`], reporting errors as `E`.
+pub(crate) struct Deserializer<E> {
+    value: Value,
+    error: PhantomData<E>,
+}
+
+impl<'de, E> IntoDeserializer<'de, E> for Value
+where
+    E: de::Error,
+{
+    type Deserializer = Deserializer<E>;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        Deserializer {
+            value: self,
+            error: PhantomData,
+        }
+    }
+}
+
+impl<'de, E> de::Deserializer<'de> for Deserializer<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Bool(value) => visitor.visit_bool(value),
+            Value::I64(value) => visitor.visit_i64(value),
+            Value::I128(value) => visitor.visit_i128(value),
+            Value::U64(value) => visitor.visit_u64(value),
+            Value::U128(value) => visitor.visit_u128(value),
+            Value::F64(value) => visitor.visit_f64(value),
+            Value::Char(value) => visitor.visit_char(value),
+            Value::String(value) => visitor.visit_string(value),
+            Value::Bytes(value) => visitor.visit_byte_buf(value),
+            Value::None => visitor.visit_none(),
+            Value::Some(value) => visitor.visit_some(value.into_deserializer()),
+            Value::Unit => visitor.visit_unit(),
+            Value::NewtypeStruct(value) => visitor.visit_newtype_struct(value.into_deserializer()),
+            Value::Seq(elements) => {
+                let mut access = SeqDeserializer::new(elements.into_iter());
+                let value = visitor.visit_seq(&mut access)?;
+                access.end()?;
+                Ok(value)
+            }
+            Value::Map(entries) => {
+                let mut access = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut access)?;
+                access.end()?;
+                Ok(value)
+            }
+            Value::Struct(fields) => {
+                let mut access = MapDeserializer::new(fields.into_iter());
+                let value = visitor.visit_map(&mut access)?;
+                access.end()?;
+                Ok(value)
+            }
+            Value::Variant(variant, contents) => visitor.visit_enum(EnumAccess {
+                variant,
+                contents: *contents,
+                error: PhantomData,
+            }),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::None | Value::Unit => visitor.visit_none(),
+            Value::Some(value) => visitor.visit_some(value.into_deserializer()),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::NewtypeStruct(value) => visitor.visit_newtype_struct(value.into_deserializer()),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.value {
+            Value::Variant(variant, contents) => visitor.visit_enum(EnumAccess {
+                variant,
+                contents: *contents,
+                error: PhantomData,
+            }),
+            value => Err(E::invalid_type(value.unexpected(), &visitor)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit
+        unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct EnumAccess<E> {
+    variant: &'static str,
+    contents: Value,
+    error: PhantomData<E>,
+}
+
+impl<'de, E> de::EnumAccess<'de> for EnumAccess<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+    type Variant = VariantAccess<E>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        Ok((
+            seed.deserialize(self.variant.into_deserializer())?,
+            VariantAccess {
+                contents: self.contents,
+                error: PhantomData,
+            },
+        ))
+    }
+}
+
+struct VariantAccess<E> {
+    contents: Value,
+    error: PhantomData<E>,
+}
+
+impl<'de, E> de::VariantAccess<'de> for VariantAccess<E>
+where
+    E: de::Error,
+{
+    type Error = E;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        match self.contents {
+            Value::Unit => Ok(()),
+            contents => Err(E::invalid_type(contents.unexpected(), &"unit variant")),
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.contents.into_deserializer())
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.contents {
+            contents @ Value::Seq(_) => {
+                de::Deserializer::deserialize_any(contents.into_deserializer(), visitor)
+            }
+            contents => Err(E::invalid_type(contents.unexpected(), &"tuple variant")),
+        }
+    }
+
+    fn struct_variant<V>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.contents {
+            contents @ Value::Struct(_) => {
+                de::Deserializer::deserialize_any(contents.into_deserializer(), visitor)
+            }
+            contents => Err(E::invalid_type(contents.unexpected(), &"struct variant")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Error,
+        Value,
+    };
+    use claims::{
+        assert_err,
+        assert_none,
+        assert_ok,
+        assert_ok_eq,
+        assert_some_eq,
+    };
+    use serde::{
+        de::{
+            Deserialize,
+            IntoDeserializer,
+        },
+        ser::Serialize,
+    };
+    use serde_derive::{
+        Deserialize,
+        Serialize,
+    };
+    use std::collections::BTreeMap;
+
+    /// Serializes and deserializes `value` through a [`Value`].
+    fn round_trip<'de, T>(value: &T) -> Result<T, Error>
+    where
+        T: Deserialize<'de> + Serialize,
+    {
+        T::deserialize(Value::from_serialize(value)?.into_deserializer())
+    }
+
+    #[test]
+    fn round_trip_primitives() {
+        assert_ok_eq!(round_trip(&true), true);
+        assert_ok_eq!(round_trip(&-42i8), -42);
+        assert_ok_eq!(round_trip(&42u16), 42);
+        assert_ok_eq!(round_trip(&u128::MAX), u128::MAX);
+        assert_ok_eq!(round_trip(&1.5f32), 1.5);
+        assert_ok_eq!(round_trip(&'a'), 'a');
+        assert_ok_eq!(round_trip(&"foo".to_owned()), "foo");
+    }
+
+    #[test]
+    fn round_trip_option() {
+        assert_ok_eq!(round_trip(&Some(42u8)), Some(42));
+        assert_ok_eq!(round_trip(&None::<u8>), None);
+    }
+
+    #[test]
+    fn round_trip_sequence() {
+        assert_ok_eq!(round_trip(&vec![1u32, 2, 3]), vec![1, 2, 3]);
+        assert_ok_eq!(round_trip(&(1u8, "foo".to_owned())), (1, "foo".to_owned()));
+    }
+
+    #[test]
+    fn round_trip_map() {
+        let map = BTreeMap::from([("foo".to_owned(), 1u8), ("bar".to_owned(), 2)]);
+
+        assert_ok_eq!(round_trip(&map), map);
+    }
+
+    #[derive(Debug, Deserialize, PartialEq, Serialize)]
+    enum Enum {
+        Unit,
+        Newtype(u8),
+        Tuple(u8, u8),
+        Struct { foo: u8 },
+    }
+
+    #[test]
+    fn round_trip_enum() {
+        assert_ok_eq!(round_trip(&Enum::Unit), Enum::Unit);
+        assert_ok_eq!(round_trip(&Enum::Newtype(42)), Enum::Newtype(42));
+        assert_ok_eq!(round_trip(&Enum::Tuple(1, 2)), Enum::Tuple(1, 2));
+        assert_ok_eq!(
+            round_trip(&Enum::Struct { foo: 42 }),
+            Enum::Struct { foo: 42 }
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_type() {
+        assert_err!(u8::deserialize(
+            IntoDeserializer::<Error>::into_deserializer(Value::String("foo".to_owned()))
+        ));
+    }
+
+    #[test]
+    fn take_field() {
+        let mut value = Value::Struct(vec![("foo", Value::U64(1)), ("bar", Value::Unit)]);
+
+        assert_some_eq!(value.take_field("foo"), Value::U64(1));
+        assert_none!(value.take_field("foo"));
+        assert_eq!(value, Value::Struct(vec![("bar", Value::Unit)]));
+    }
+
+    #[test]
+    fn take_field_not_struct() {
+        assert_none!(assert_ok!(Value::from_serialize(&42u8)).take_field("foo"));
+    }
+}
@@ -1,5 +1,6 @@
 use super::width::Width;
 use std::{
+    ffi::OsStr,
     fmt,
     fmt::{
         Display,
@@ -14,6 +15,7 @@ pub(super) enum Color {
     BrightRed,
     BrightCyan,
     BrightWhite,
+    Dim,
 }
 
 impl Color {
@@ -31,6 +33,7 @@ impl Color {
             Self::BrightRed => "\x1B[91m",
             Self::BrightCyan => "\x1B[96m",
             Self::BrightWhite => "\x1B[97m",
+            Self::Dim => "\x1B[2m",
         }
     }
 
@@ -57,6 +60,18 @@ impl Ansi {
         }
     }
 
+    /// Returns whether to color messages written to a stream, given whether it is a terminal and
+    /// the value of the `NO_COLOR` environment variable.
+    ///
+    /// As described at <https://no-color.org>, any non-empty value of `NO_COLOR` disables color.
+    pub(super) fn for_stream(is_terminal: bool, no_color: Option<&OsStr>) -> Self {
+        if is_terminal && no_color.is_none_or(OsStr::is_empty) {
+            Ansi::Enabled
+        } else {
+            Ansi::Disabled
+        }
+    }
+
     pub(super) fn cyan(self) -> Color {
         match self {
             Self::Disabled => Color::None,
@@ -84,6 +99,13 @@ impl Ansi {
             Self::Enabled => Color::BrightWhite,
         }
     }
+
+    pub(super) fn dim(self) -> Color {
+        match self {
+            Self::Disabled => Color::None,
+            Self::Enabled => Color::Dim,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -183,7 +205,10 @@ mod tests {
         StyledList,
         Width,
     };
-    use std::iter;
+    use std::{
+        ffi::OsStr,
+        iter,
+    };
 
     #[test]
     fn color_none_prefix() {
@@ -245,6 +270,29 @@ mod tests {
         assert_eq!(Ansi::from_alternate(false), Ansi::Disabled);
     }
 
+    #[test]
+    fn ansi_for_stream_terminal() {
+        assert_eq!(Ansi::for_stream(true, None), Ansi::Enabled);
+    }
+
+    #[test]
+    fn ansi_for_stream_not_terminal() {
+        assert_eq!(Ansi::for_stream(false, None), Ansi::Disabled);
+    }
+
+    #[test]
+    fn ansi_for_stream_no_color() {
+        assert_eq!(
+            Ansi::for_stream(true, Some(OsStr::new("1"))),
+            Ansi::Disabled
+        );
+    }
+
+    #[test]
+    fn ansi_for_stream_no_color_empty() {
+        assert_eq!(Ansi::for_stream(true, Some(OsStr::new(""))), Ansi::Enabled);
+    }
+
     #[test]
     fn ansi_enabled_cyan() {
         assert_eq!(Ansi::Enabled.cyan(), Color::Cyan);
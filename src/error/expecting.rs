@@ -0,0 +1,150 @@
+use crate::parse::similarity;
+use std::{
+    fmt,
+    fmt::{
+        Display,
+        Formatter,
+    },
+};
+
+/// The default maximum number of expected names listed in an error message.
+pub(super) const DEFAULT_LIMIT: usize = 20;
+
+/// A list of expected command or option names, truncated to a maximum length.
+///
+/// Lists that fit within the limit are displayed in their original order. Longer lists are sorted
+/// by similarity to the user's input (using the same distance as the suggestion tips), then
+/// alphabetically, and end with a count of the omitted names, along with a hint to use `--help` if
+/// it is available.
+#[derive(Debug)]
+pub(super) struct Expecting<'a> {
+    pub(super) input: &'a str,
+    pub(super) names: &'a [&'static str],
+    pub(super) limit: usize,
+    pub(super) executable_path: &'a str,
+    pub(super) noun: &'a str,
+    /// Whether the `--help` option is available.
+    pub(super) auto_help: bool,
+}
+
+impl Display for Expecting<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if self.names.len() <= self.limit {
+            return write!(formatter, "{:?}", self.names);
+        }
+
+        let mut names = self.names.to_vec();
+        names.sort_by_cached_key(|name| (similarity(self.input, name), *name));
+        if !self.auto_help {
+            return write!(
+                formatter,
+                "{:?} ... and {} more",
+                &names[..self.limit],
+                self.names.len() - self.limit,
+            );
+        }
+        write!(
+            formatter,
+            "{:?} ... and {} more (run '{} --help' to list all {})",
+            &names[..self.limit],
+            self.names.len() - self.limit,
+            self.executable_path,
+            self.noun,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Expecting;
+
+    #[test]
+    fn display_within_limit() {
+        assert_eq!(
+            format!(
+                "{}",
+                Expecting {
+                    input: "baz",
+                    names: &["foo", "bar"],
+                    limit: 2,
+                    executable_path: "executable_name",
+                    noun: "commands",
+                    auto_help: true,
+                }
+            ),
+            "[\"foo\", \"bar\"]"
+        );
+    }
+
+    #[test]
+    fn display_truncated() {
+        assert_eq!(
+            format!(
+                "{}",
+                Expecting {
+                    input: "baz",
+                    names: &["foo", "qux", "bar", "quux", "baa"],
+                    limit: 3,
+                    executable_path: "executable_name",
+                    noun: "commands",
+                    auto_help: true,
+                }
+            ),
+            "[\"baa\", \"bar\", \"foo\"] ... and 2 more (run 'executable_name --help' to list all commands)"
+        );
+    }
+
+    #[test]
+    fn display_truncated_without_auto_help() {
+        assert_eq!(
+            format!(
+                "{}",
+                Expecting {
+                    input: "baz",
+                    names: &["foo", "qux", "bar", "quux", "baa"],
+                    limit: 3,
+                    executable_path: "executable_name",
+                    noun: "commands",
+                    auto_help: false,
+                }
+            ),
+            "[\"baa\", \"bar\", \"foo\"] ... and 2 more"
+        );
+    }
+
+    #[test]
+    fn display_truncated_alphabetical_ties() {
+        assert_eq!(
+            format!(
+                "{}",
+                Expecting {
+                    input: "zzz",
+                    names: &["c", "b", "a"],
+                    limit: 2,
+                    executable_path: "executable_name",
+                    noun: "options",
+                    auto_help: true,
+                }
+            ),
+            "[\"a\", \"b\"] ... and 1 more (run 'executable_name --help' to list all options)"
+        );
+    }
+
+    #[test]
+    fn display_truncated_zero_limit() {
+        assert_eq!(
+            format!(
+                "{}",
+                Expecting {
+                    input: "foo",
+                    names: &["foo"],
+                    limit: 0,
+                    executable_path: "executable_name",
+                    noun: "commands",
+                    auto_help: true,
+                }
+            ),
+            "[] ... and 1 more (run 'executable_name --help' to list all commands)"
+        );
+    }
+}
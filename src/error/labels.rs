@@ -0,0 +1,271 @@
+use std::fmt::{
+    self,
+    Display,
+    Write,
+};
+
+/// Text labels used when displaying help and error messages.
+///
+/// By default, all labels are in English. Any label can be overridden to localize the generated
+/// messages, leaving the remaining labels as their defaults:
+///
+/// ``` rust
+/// use serde_args::HelpLabels;
+///
+/// let labels = HelpLabels {
+///     usage: "UTILISATION".into(),
+///     help_description: "Afficher ce message.".into(),
+///     ..Default::default()
+/// };
+/// ```
+///
+/// Labels for error messages are templates. Each `{placeholder}` documented for a label is replaced
+/// with its value when the message is displayed, while any other text, including braces, is
+/// displayed as it is.
+///
+/// ``` rust
+/// use serde_args::HelpLabels;
+///
+/// let labels = HelpLabels {
+///     missing_argument: "argument requis manquant : {name}".into(),
+///     ..Default::default()
+/// };
+/// ```
+///
+/// Labels are provided to the parser using [`from_env_with_labels()`](crate::from_env_with_labels).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HelpLabels {
+    /// Prefix for the usage string.
+    ///
+    /// Defaults to `"USAGE"`.
+    pub usage: String,
+    /// Prefix for error messages.
+    ///
+    /// Defaults to `"ERROR"`.
+    pub error: String,
+    /// Heading for the list of required arguments.
+    ///
+    /// Defaults to `"Required Arguments"`.
+    pub required_arguments: String,
+    /// Heading for the options of the top-level struct.
+    ///
+    /// Defaults to `"Global Options"`.
+    pub global_options: String,
+    /// Heading for the options of nested structs, displayed after the struct's name.
+    ///
+    /// Defaults to `"Options"`.
+    pub options: String,
+    /// Heading for the override options, such as `--help` and `--version`.
+    ///
+    /// Defaults to `"Override Options"`.
+    pub override_options: String,
+    /// Heading for the commands of the top-level enum, or of the enum in the last required field
+    /// of a top-level struct.
+    ///
+    /// Defaults to `"Commands"`.
+    pub global_commands: String,
+    /// Heading for lists of commands of nested enums, displayed after the enum's name.
+    ///
+    /// Defaults to `"Variants"`.
+    pub commands: String,
+    /// Heading for the examples declared by the container.
+    ///
+    /// Defaults to `"EXAMPLES"`.
+    pub examples: String,
+    /// Description of the `--help` option.
+    ///
+    /// Defaults to `"Display this message."`.
+    pub help_description: String,
+    /// Description of the `--version` option.
+    ///
+    /// Defaults to `"Display version information."`.
+    pub version_description: String,
+    /// Error message for a single missing required positional argument.
+    ///
+    /// `{name}` is replaced by the argument, such as `<path>`.
+    ///
+    /// Defaults to `"missing required positional argument: {name}"`.
+    pub missing_argument: String,
+    /// Error message for multiple missing required positional arguments.
+    ///
+    /// `{names}` is replaced by the arguments separated by spaces, such as `<source> <target>`.
+    ///
+    /// Defaults to `"missing required positional arguments: {names}"`.
+    pub missing_arguments: String,
+    /// Error message for an option that was not recognized.
+    ///
+    /// `{option}` is replaced by the option as it was given, within single quotes.
+    ///
+    /// Defaults to `"unrecognized optional flag: {option}"`.
+    pub unrecognized_option: String,
+    /// Note displayed in parentheses after [`unrecognized_option`](Self::unrecognized_option) when
+    /// the option is an abbreviation of an existing option.
+    ///
+    /// `{option}` is replaced by the existing option, within single quotes.
+    ///
+    /// Defaults to `"unabbreviated option {option} exists; abbreviations are not enabled"`.
+    pub unabbreviated_option: String,
+    /// Tip displayed after an unrecognized option that is similar to an existing option.
+    ///
+    /// `{option}` is replaced by the existing option.
+    ///
+    /// Defaults to `"tip: a similar option exists: {option}"`.
+    pub similar_option: String,
+    /// Error message for an abbreviated option that could refer to multiple options.
+    ///
+    /// `{option}` is replaced by the option as it was given, within single quotes, and
+    /// `{candidates}` by the options it could refer to, each within single quotes and separated by
+    /// commas.
+    ///
+    /// Defaults to `"{option} is ambiguous: could match {candidates}"`.
+    pub ambiguous_option: String,
+    /// Error message for a command that was not recognized.
+    ///
+    /// `{command}` is replaced by the command as it was given, within single quotes.
+    ///
+    /// Defaults to `"unrecognized command: {command}"`.
+    pub unrecognized_command: String,
+    /// Tip displayed after an unrecognized command that is similar to an existing command.
+    ///
+    /// `{command}` is replaced by the existing command.
+    ///
+    /// Defaults to `"tip: a similar command exists: {command}"`.
+    pub similar_command: String,
+    /// Heading for an error message listing multiple problems.
+    ///
+    /// `{count}` is replaced by the number of problems.
+    ///
+    /// Defaults to `"{count} problems found:"`.
+    pub problems: String,
+    /// Hint displayed at the end of error messages when the `--help` option is available.
+    ///
+    /// `{help}` is replaced by the `--help` option.
+    ///
+    /// Defaults to `"For more information, use {help}."`.
+    pub more_information: String,
+}
+
+impl Default for HelpLabels {
+    fn default() -> Self {
+        Self {
+            usage: "USAGE".into(),
+            error: "ERROR".into(),
+            required_arguments: "Required Arguments".into(),
+            global_options: "Global Options".into(),
+            options: "Options".into(),
+            override_options: "Override Options".into(),
+            global_commands: "Commands".into(),
+            commands: "Variants".into(),
+            examples: "EXAMPLES".into(),
+            help_description: "Display this message.".into(),
+            version_description: "Display version information.".into(),
+            missing_argument: "missing required positional argument: {name}".into(),
+            missing_arguments: "missing required positional arguments: {names}".into(),
+            unrecognized_option: "unrecognized optional flag: {option}".into(),
+            unabbreviated_option:
+                "unabbreviated option {option} exists; abbreviations are not enabled".into(),
+            similar_option: "tip: a similar option exists: {option}".into(),
+            ambiguous_option: "{option} is ambiguous: could match {candidates}".into(),
+            unrecognized_command: "unrecognized command: {command}".into(),
+            similar_command: "tip: a similar command exists: {command}".into(),
+            problems: "{count} problems found:".into(),
+            more_information: "For more information, use {help}.".into(),
+        }
+    }
+}
+
+/// Writes `template`, replacing each `{placeholder}` with its value.
+///
+/// Braces that do not enclose one of the given placeholders are written as they are. Values are
+/// not searched for placeholders themselves.
+pub(crate) fn write_template<W>(
+    output: &mut W,
+    template: &str,
+    values: &[(&str, &dyn Display)],
+) -> fmt::Result
+where
+    W: Write,
+{
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.write_str(&rest[..start])?;
+        rest = &rest[start..];
+        match values.iter().find(|(placeholder, _)| {
+            rest[1..]
+                .strip_prefix(placeholder)
+                .is_some_and(|after| after.starts_with('}'))
+        }) {
+            Some((placeholder, value)) => {
+                write!(output, "{}", value)?;
+                rest = &rest[placeholder.len() + 2..];
+            }
+            None => {
+                output.write_char('{')?;
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.write_str(rest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write_template;
+    use claims::assert_ok;
+
+    #[test]
+    fn write_template_no_placeholders() {
+        let mut output = String::new();
+
+        assert_ok!(write_template(&mut output, "foo bar", &[("name", &"baz")]));
+        assert_eq!(output, "foo bar");
+    }
+
+    #[test]
+    fn write_template_placeholder() {
+        let mut output = String::new();
+
+        assert_ok!(write_template(
+            &mut output,
+            "missing {name}!",
+            &[("name", &"<foo>")]
+        ));
+        assert_eq!(output, "missing <foo>!");
+    }
+
+    #[test]
+    fn write_template_repeated_placeholders() {
+        let mut output = String::new();
+
+        assert_ok!(write_template(
+            &mut output,
+            "{a}{b}{a}",
+            &[("a", &1), ("b", &"two")]
+        ));
+        assert_eq!(output, "1two1");
+    }
+
+    #[test]
+    fn write_template_unknown_placeholder() {
+        let mut output = String::new();
+
+        assert_ok!(write_template(
+            &mut output,
+            "{unknown} {name",
+            &[("name", &"foo")]
+        ));
+        assert_eq!(output, "{unknown} {name");
+    }
+
+    #[test]
+    fn write_template_value_not_substituted() {
+        let mut output = String::new();
+
+        assert_ok!(write_template(
+            &mut output,
+            "{name} {other}",
+            &[("name", &"{other}"), ("other", &"bar")]
+        ));
+        assert_eq!(output, "{other} bar");
+    }
+}
@@ -1,10 +1,24 @@
 mod ansi;
+mod expecting;
 mod intersperse;
+mod labels;
+mod stream;
+mod warning;
 mod width;
 
+pub use labels::HelpLabels;
+pub use stream::OutputStream;
+pub use warning::Warning;
+
+pub(crate) use labels::write_template;
+
 use super::{
     de,
+    hook,
+    outcome::Report,
     parse,
+    parser::SortOrder,
+    split,
     trace,
     trace::Shape,
 };
@@ -12,27 +26,35 @@ use ansi::{
     Ansi,
     StyledList,
 };
+use expecting::Expecting;
 use intersperse::Intersperse;
 use std::{
+    env,
     ffi::OsString,
     fmt,
     fmt::{
         Display,
         Formatter,
     },
+    io,
     iter,
 };
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 use width::{
+    Hanging,
     Width,
     WidthFormatted,
+    MAX_LONG_OPTIONS_WIDTH,
 };
 
 #[derive(Debug)]
 enum UsageError {
     Parsing(parse::Error),
     Deserializing(de::Error),
+    Splitting(split::Error),
+    /// The parsed arguments were rejected by a post-parse hook.
+    Rejected(String),
 }
 
 impl Display for UsageError {
@@ -40,19 +62,44 @@ impl Display for UsageError {
         match self {
             Self::Parsing(error) => Display::fmt(error, formatter),
             Self::Deserializing(error) => Display::fmt(error, formatter),
+            Self::Splitting(error) => Display::fmt(error, formatter),
+            Self::Rejected(message) => formatter.write_str(message),
         }
     }
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 enum Kind {
     Development {
         error: trace::Error,
     },
+    /// A post-parse hook inserted an invalid value.
+    Injection {
+        error: hook::InjectionError,
+    },
+    /// The arguments were checked successfully without being deserialized.
+    Checked,
+    /// Version information was requested.
+    ///
+    /// This is rendered on its own, without any of the scaffolding of usage and help messages.
+    Version {
+        executable_path: OsString,
+        version: String,
+    },
     Usage {
         error: UsageError,
         executable_path: OsString,
         shape: Shape,
+        labels: HelpLabels,
+        /// Whether help requested using `-h` is displayed as a summary.
+        short_help: bool,
+        /// The order options are listed in within help messages.
+        sort_options: SortOrder,
+        /// Whether the `--help` option is available.
+        auto_help: bool,
+        /// Whether the top-level command was selected by the executable's name.
+        multicall: bool,
     },
 }
 
@@ -73,16 +120,43 @@ impl Display for Kind {
 
         match self {
             Self::Development { error } => Display::fmt(error, formatter),
+            Self::Injection { error } => Display::fmt(error, formatter),
+            Self::Checked => formatter.write_str("arguments are valid"),
+            Self::Version {
+                executable_path,
+                version,
+            } => write!(
+                formatter,
+                "{}{}{} {}",
+                ansi.dim().prefix(),
+                executable_path.to_string_lossy(),
+                ansi.dim().suffix(),
+                version
+            ),
             Self::Usage {
                 error,
                 executable_path,
                 shape,
+                labels,
+                short_help,
+                sort_options,
+                auto_help,
+                multicall,
             } => {
+                // A command selected by the executable's name is not repeated after it.
+                let usage = match (multicall, shape) {
+                    (true, Shape::Variant { shape, .. }) => format!("{:#}", shape),
+                    _ => format!("{}", shape),
+                };
                 match error {
-                    UsageError::Parsing(parse::Error::Help) => {
+                    UsageError::Parsing(parse::Error::Help { short }) => {
+                        // A summary lists the usage and the names of arguments, options, and
+                        // commands, without any descriptions or examples.
+                        let short = *short && *short_help;
+
                         // Write program description.
                         let program_description = shape.description();
-                        if !program_description.is_empty() {
+                        if !short && !program_description.is_empty() {
                             formatter.write_str(shape.description())?;
                             formatter.write_str("\n\n")?;
                         }
@@ -90,9 +164,10 @@ impl Display for Kind {
                         // Write usage string.
                         write!(
                             formatter,
-                            "{bright_white_start}USAGE{bright_white_end}: {bright_cyan_start}{}{bright_cyan_end} {cyan_start}{}{cyan_end}",
+                            "{bright_white_start}{}{bright_white_end}: {bright_cyan_start}{}{bright_cyan_end} {cyan_start}{}{cyan_end}",
+                            labels.usage,
                             executable_path.to_string_lossy(),
-                            shape
+                            usage
                         )?;
 
                         // Write required arguments.
@@ -100,7 +175,8 @@ impl Display for Kind {
                         if !required_arguments.is_empty() {
                             write!(
                                 formatter,
-                                "\n\n{bright_white_start}Required Arguments:{bright_white_end}"
+                                "\n\n{bright_white_start}{}:{bright_white_end}",
+                                labels.required_arguments,
                             )?;
                         }
                         // Get longest argument name.
@@ -110,50 +186,76 @@ impl Display for Kind {
                             .max()
                             .unwrap_or(0);
                         for (name, description) in required_arguments {
+                            if short {
+                                write!(
+                                    formatter,
+                                    "\n  {bright_cyan_start}<{}>{bright_cyan_end}",
+                                    name
+                                )?;
+                                continue;
+                            }
                             write!(
                                 formatter,
-                                "\n  {bright_cyan_start}{:longest_argument$}{bright_cyan_end}  {description}",
+                                "\n  {bright_cyan_start}{:longest_argument$}{bright_cyan_end}  {:indent$}",
                                 WidthFormatted(format!("<{}>", name)),
+                                Hanging(description),
                                 longest_argument = longest_argument + 2,
+                                indent = longest_argument + 6,
                             )?;
                         }
 
-                        // Write options.
-                        let optional_groups = shape.optional_groups();
+                        // Write options, with options taking values before boolean flags unless
+                        // sorted otherwise.
+                        let mut optional_groups = shape.optional_groups();
+                        for (_, group) in &mut optional_groups {
+                            match sort_options {
+                                SortOrder::Grouped => {}
+                                SortOrder::Declaration => group.sort_by_key(|field| field.index),
+                                SortOrder::Alphabetical => group.sort_by_cached_key(|field| {
+                                    field.option_names().first().map(|name| name.to_lowercase())
+                                }),
+                            }
+                        }
                         for (index, (name, group)) in optional_groups.iter().enumerate() {
                             if !group.is_empty() {
                                 if index == 0 && matches!(shape, Shape::Struct { .. }) {
                                     write!(
                                         formatter,
-                                        "\n\n{bright_white_start}Global Options:{bright_white_end}"
+                                        "\n\n{bright_white_start}{}:{bright_white_end}",
+                                        labels.global_options,
                                     )?;
                                 } else {
                                     write!(
                                         formatter,
-                                        "\n\n{bright_white_start}{} Options:{bright_white_end}",
-                                        name
+                                        "\n\n{bright_white_start}{} {}:{bright_white_end}",
+                                        name, labels.options,
                                     )?;
                                 }
 
-                                let long_options = group.iter().map(|field| {
+                                let rows: Vec<_> =
+                                    group.iter().flat_map(|field| field.help_rows()).collect();
+                                let long_options = rows.iter().map(|(names, value, _)| {
                                     Intersperse::new(
-                                        iter::once(field.name)
-                                            .chain(field.aliases.iter().copied())
+                                        names
+                                            .iter()
                                             .filter(|name| name.graphemes(true).count() != 1)
                                             .map(|name| {
                                                 bright_cyan.apply(format!("--{}", name)).into()
                                             })
-                                            .chain(iter::once(
-                                                cyan.apply(format!("{}", field.shape)).into(),
-                                            )),
+                                            .chain(
+                                                // A summary does not leave room for the values
+                                                // of boolean flags.
+                                                (!short || !value.is_empty())
+                                                    .then(|| cyan.apply(value.clone()).into()),
+                                            ),
                                         " ".to_owned().into(),
                                     )
                                     .collect::<StyledList>()
                                 });
-                                let short_options = group.iter().map(|field| {
+                                let short_options = rows.iter().map(|(names, ..)| {
                                     Intersperse::new(
-                                        iter::once(field.name)
-                                            .chain(field.aliases.iter().copied())
+                                        names
+                                            .iter()
                                             .filter(|name| name.graphemes(true).count() == 1)
                                             .map(|name| {
                                                 bright_cyan.apply(format!("-{}", name)).into()
@@ -163,9 +265,14 @@ impl Display for Kind {
                                     .collect::<StyledList>()
                                 });
 
+                                // Options wider than the limit do not push the descriptions of
+                                // every other option to the right.
+                                let has_long_options =
+                                    long_options.clone().any(|styled| styled.width() > 0);
                                 let longest_long_options = long_options
                                     .clone()
                                     .map(|styled| styled.width())
+                                    .filter(|width| *width <= MAX_LONG_OPTIONS_WIDTH)
                                     .max()
                                     .unwrap_or(0);
                                 let longest_short_options = short_options
@@ -174,33 +281,98 @@ impl Display for Kind {
                                     .max()
                                     .unwrap_or(0);
 
-                                for ((field, long_options), short_options) in
-                                    group.iter().zip(long_options).zip(short_options)
+                                for (((_, _, description), long_options), short_options) in
+                                    rows.iter().zip(long_options).zip(short_options)
                                 {
-                                    write!(
-                                        formatter,
-                                        "\n  {:longest_short_options$}{}{:longest_long_options$}{}{}",
-                                        WidthFormatted(short_options),
-                                        if longest_short_options == 0 {""} else {" "},
-                                        WidthFormatted(long_options),
-                                        if longest_long_options == 0 {" "} else {"  "},
-                                        field.description,
-                                    )?;
+                                    let short_separator =
+                                        if longest_short_options == 0 { "" } else { " " };
+                                    let long_separator = if has_long_options { "  " } else { " " };
+                                    let indent = 2
+                                        + longest_short_options
+                                        + short_separator.len()
+                                        + longest_long_options
+                                        + long_separator.len();
+                                    if short || long_options.width() > longest_long_options {
+                                        // Too wide to align with, so the description begins on
+                                        // the next line in the usual column.
+                                        write!(
+                                            formatter,
+                                            "\n  {:longest_short_options$}{}{}",
+                                            WidthFormatted(short_options),
+                                            short_separator,
+                                            long_options,
+                                        )?;
+                                        if !short && !description.is_empty() {
+                                            write!(
+                                                formatter,
+                                                "\n{:indent$}{:indent$}",
+                                                "",
+                                                Hanging(description),
+                                            )?;
+                                        }
+                                    } else {
+                                        write!(
+                                            formatter,
+                                            "\n  {:longest_short_options$}{}{:longest_long_options$}{}{:indent$}",
+                                            WidthFormatted(short_options),
+                                            short_separator,
+                                            WidthFormatted(long_options),
+                                            long_separator,
+                                            Hanging(description),
+                                        )?;
+                                    }
                                 }
                             }
                         }
 
                         // Write override options.
-                        if shape.version().is_some() {
-                            write!(formatter, "\n\n{bright_white_start}Override Options:{bright_white_end}\n  {bright_cyan_start}-h --help{bright_cyan_end}     Display this message.\n  {bright_cyan_start}   --version{bright_cyan_end}  Display version information.")?;
+                        if short {
+                            if *auto_help || shape.version().is_some() {
+                                write!(
+                                    formatter,
+                                    "\n\n{bright_white_start}{}:{bright_white_end}",
+                                    labels.override_options
+                                )?;
+                            }
+                            if *auto_help {
+                                write!(
+                                    formatter,
+                                    "\n  {bright_cyan_start}-h --help{bright_cyan_end}"
+                                )?;
+                            }
+                            if shape.version().is_some() {
+                                let indent = if *auto_help { "   " } else { "" };
+                                write!(
+                                    formatter,
+                                    "\n  {indent}{bright_cyan_start}--version{bright_cyan_end}"
+                                )?;
+                            }
+                        } else if !auto_help {
+                            if shape.version().is_some() {
+                                write!(formatter, "\n\n{bright_white_start}{}:{bright_white_end}\n  {bright_cyan_start}--version{bright_cyan_end}  {}", labels.override_options, labels.version_description)?;
+                            }
+                        } else if shape.version().is_some() {
+                            write!(formatter, "\n\n{bright_white_start}{}:{bright_white_end}\n  {bright_cyan_start}-h --help{bright_cyan_end}     {}\n  {bright_cyan_start}   --version{bright_cyan_end}  {}", labels.override_options, labels.help_description, labels.version_description)?;
                         } else {
-                            write!(formatter, "\n\n{bright_white_start}Override Options:{bright_white_end}\n  {bright_cyan_start}-h --help{bright_cyan_end}  Display this message.")?;
+                            write!(formatter, "\n\n{bright_white_start}{}:{bright_white_end}\n  {bright_cyan_start}-h --help{bright_cyan_end}  {}", labels.override_options, labels.help_description)?;
                         }
 
                         // Write commands.
+                        //
+                        // The commands of the last required field of a struct are listed as the
+                        // commands of the program, the same as those of a top-level enum.
+                        let mut command_name = shape.command_field().and_then(|field| {
+                            if let Shape::Enum { name, .. } = field.shape {
+                                Some(name)
+                            } else {
+                                None
+                            }
+                        });
                         let variant_groups = shape.variant_groups();
-                        for (name, group) in variant_groups {
-                            let variant_names = group.iter().map(|variant| {
+                        for (index, (name, variants)) in variant_groups.into_iter().enumerate() {
+                            let global = (index == 0 && matches!(shape, Shape::Enum { .. }))
+                                || command_name.take_if(|command| *command == name).is_some();
+                            let variant_names = variants.iter().map(|variant| {
                                 let mut combined = iter::once(variant.name)
                                     .chain(variant.aliases.iter().copied())
                                     .fold(bright_cyan_start.to_owned(), |combined, variant| {
@@ -217,33 +389,178 @@ impl Display for Kind {
                                 .map(|name| name.width())
                                 .max()
                                 .unwrap_or(0);
+                            // The names above contain ANSI escape codes when colored, so the
+                            // column descriptions begin in is measured without them.
+                            let longest_plain_variant_names = variants
+                                .iter()
+                                .map(|variant| {
+                                    iter::once(variant.name)
+                                        .chain(variant.aliases.iter().copied())
+                                        .map(|name| name.width() + 1)
+                                        .sum::<usize>()
+                                        + format!("{}", variant.shape).width()
+                                })
+                                .max()
+                                .unwrap_or(0);
+
+                            // Cluster variants by their help group, in order of first appearance.
+                            let mut clusters: Vec<(Option<&str>, Vec<_>)> = Vec::new();
+                            for (variant, variant_name) in variants.iter().zip(variant_names) {
+                                let group = variant.group.as_deref();
+                                match clusters
+                                    .iter_mut()
+                                    .find(|(cluster_group, _)| *cluster_group == group)
+                                {
+                                    Some((_, cluster)) => cluster.push((variant, variant_name)),
+                                    None => clusters.push((group, vec![(variant, variant_name)])),
+                                }
+                            }
+
+                            for (group, cluster) in clusters {
+                                if let Some(group) = group {
+                                    write!(
+                                        formatter,
+                                        "\n\n{bright_white_start}{group}:{bright_white_end}"
+                                    )?;
+                                } else if global {
+                                    write!(
+                                        formatter,
+                                        "\n\n{bright_white_start}{}:{bright_white_end}",
+                                        labels.global_commands,
+                                    )?;
+                                } else {
+                                    write!(
+                                        formatter,
+                                        "\n\n{bright_white_start}{name} {}:{bright_white_end}",
+                                        labels.commands,
+                                    )?;
+                                }
+                                for (variant, variant_name) in cluster {
+                                    if short {
+                                        let names = iter::once(variant.name)
+                                            .chain(variant.aliases.iter().copied())
+                                            .collect::<Vec<_>>()
+                                            .join(" ");
+                                        write!(
+                                            formatter,
+                                            "\n  {bright_cyan_start}{names}{bright_cyan_end}"
+                                        )?;
+                                        let usage = format!("{}", variant.shape);
+                                        if !usage.is_empty() {
+                                            write!(formatter, " {cyan_start}{usage}{cyan_end}")?;
+                                        }
+                                        continue;
+                                    }
+                                    write!(
+                                        formatter,
+                                        "\n  {:longest_variant_names$}  {:indent$}",
+                                        WidthFormatted(variant_name),
+                                        Hanging(&variant.description),
+                                        indent = longest_plain_variant_names + 4,
+                                    )?;
+                                }
+                            }
+                        }
 
+                        // Write examples verbatim, without wrapping.
+                        if let Some(examples) = shape.examples().filter(|_| !short) {
                             write!(
                                 formatter,
-                                "\n\n{bright_white_start}{name} Variants:{bright_white_end}"
+                                "\n\n{bright_white_start}{}:{bright_white_end}",
+                                labels.examples
                             )?;
-                            for (variant, name) in group.iter().zip(variant_names) {
-                                write!(
-                                    formatter,
-                                    "\n  {:longest_variant_names$}  {}",
-                                    WidthFormatted(name),
-                                    variant.description
-                                )?;
+                            for line in examples.lines() {
+                                if line.is_empty() {
+                                    formatter.write_str("\n")?;
+                                } else {
+                                    write!(formatter, "\n  {line}")?;
+                                }
                             }
                         }
 
                         Ok(())
                     }
-                    UsageError::Parsing(parse::Error::Version) => formatter
-                        .write_str(shape.version().expect("no version information available")),
                     _ => {
                         write!(
                             formatter,
-                            "{bright_red_start}ERROR{bright_red_end}: {}\n\n{bright_white_start}USAGE:{bright_white_end} {bright_cyan_start}{}{bright_cyan_end} {cyan_start}{}{cyan_end}\n\nFor more information, use {bright_cyan_start}--help{bright_cyan_end}.",
-                            error,
-                            executable_path.to_string_lossy(),
-                            shape
-                        )
+                            "{bright_red_start}{}{bright_red_end}: ",
+                            labels.error
+                        )?;
+                        // Long lists of expected names are truncated, limited by the precision.
+                        let limit = formatter.precision().unwrap_or(expecting::DEFAULT_LIMIT);
+                        let executable_name = executable_path.to_string_lossy();
+                        match error {
+                            UsageError::Deserializing(de::Error::UnknownVariant(
+                                variant,
+                                expected,
+                            )) => write!(
+                                formatter,
+                                "unknown command {}, expected one of {}",
+                                variant,
+                                Expecting {
+                                    input: variant,
+                                    names: expected,
+                                    limit,
+                                    executable_path: &executable_name,
+                                    noun: "commands",
+                                    auto_help: *auto_help,
+                                },
+                            )?,
+                            UsageError::Parsing(parse::Error::MissingCommand {
+                                name,
+                                expecting,
+                            }) => write!(
+                                formatter,
+                                "missing required command <{}>, expected one of {}",
+                                name,
+                                Expecting {
+                                    input: "",
+                                    names: expecting,
+                                    limit,
+                                    executable_path: &executable_name,
+                                    noun: "commands",
+                                    auto_help: *auto_help,
+                                },
+                            )?,
+                            UsageError::Deserializing(de::Error::UnknownField(field, expected)) => {
+                                write!(
+                                    formatter,
+                                    "unexpected argument --{}, expected one of {}",
+                                    field,
+                                    Expecting {
+                                        input: field,
+                                        names: expected,
+                                        limit,
+                                        executable_path: &executable_name,
+                                        noun: "options",
+                                        auto_help: *auto_help,
+                                    },
+                                )?
+                            }
+                            UsageError::Parsing(error) => {
+                                write!(formatter, "{}", parse::Labeled { error, labels })?
+                            }
+                            _ => write!(formatter, "{}", error)?,
+                        }
+                        write!(
+                            formatter,
+                            "\n\n{bright_white_start}{}:{bright_white_end} {bright_cyan_start}{}{bright_cyan_end} {cyan_start}{}{cyan_end}",
+                            labels.usage,
+                            executable_name,
+                            usage
+                        )?;
+                        if *auto_help {
+                            formatter.write_str("\n\n")?;
+                            write_template(
+                                formatter,
+                                &labels.more_information,
+                                &[(
+                                    "help",
+                                    &format!("{bright_cyan_start}--help{bright_cyan_end}"),
+                                )],
+                            )?;
+                        }
+                        Ok(())
                     }
                 }
             }
@@ -272,6 +589,26 @@ impl Display for Kind {
 /// }
 /// ```
 ///
+/// Help and version information were requested by the user, and are the program's output rather
+/// than a diagnostic. [`write_to()`](Error::write_to()) writes them to a separate stream from other
+/// errors, so that, for example, `prog --help | less` works while errors still go to standard
+/// error.
+///
+/// ``` rust
+/// # mod hidden {
+/// use std::process::exit;
+/// # }
+/// # fn exit(_: usize) -> () {}
+/// use std::io;
+///
+/// if let Err(error) = serde_args::from_env::<usize>() {
+///     error
+///         .write_to(&mut io::stdout(), &mut io::stderr())
+///         .expect("failed to write error");
+///     exit(1);
+/// }
+/// ```
+///
 /// # Formatting
 ///
 /// `Error` allows formatting using ANSI color sequences. This will print help messages and error
@@ -289,9 +626,34 @@ impl Display for Kind {
 ///     exit(1);
 /// }
 /// ```
+///
+/// Error messages listing the expected commands or options show at most 20 names, preferring the
+/// names most similar to the user's input. This limit can be changed using the precision
+/// formatting parameter.
+///
+/// ``` rust
+/// # mod hidden {
+/// use std::process::exit;
+/// # }
+/// # fn exit(_: usize) -> () {}
+///
+/// if let Err(error) = serde_args::from_env::<usize>() {
+///     println!("{error:.5}");
+///     exit(1);
+/// }
+/// ```
+///
+/// # Error Sources
+///
+/// With the `source` feature enabled, an error raised while deserializing the parsed arguments is
+/// available through [`source()`](std::error::Error::source()), allowing error reporting crates
+/// such as `anyhow` to display the underlying cause. Errors found while parsing the arguments have
+/// no underlying cause.
 #[derive(Debug)]
 pub struct Error {
-    kind: Kind,
+    // The message is boxed, as it carries everything displayed with it, such as the traced shape
+    // and the labels.
+    kind: Box<Kind>,
 }
 
 impl Error {
@@ -299,13 +661,31 @@ impl Error {
         error: parse::Error,
         executable_path: OsString,
         shape: Shape,
+        labels: HelpLabels,
     ) -> Self {
+        if let parse::Error::Version = error {
+            return Self {
+                kind: Box::new(Kind::Version {
+                    version: shape
+                        .version()
+                        .expect("no version information available")
+                        .to_owned(),
+                    executable_path,
+                }),
+            };
+        }
+        let error = error.with_executable_name(&executable_path.to_string_lossy());
         Self {
-            kind: Kind::Usage {
+            kind: Box::new(Kind::Usage {
                 error: UsageError::Parsing(error),
                 executable_path,
                 shape,
-            },
+                labels,
+                short_help: false,
+                sort_options: SortOrder::Grouped,
+                auto_help: true,
+                multicall: false,
+            }),
         }
     }
 
@@ -313,13 +693,176 @@ impl Error {
         error: de::Error,
         executable_path: OsString,
         shape: Shape,
+        labels: HelpLabels,
     ) -> Self {
         Self {
-            kind: Kind::Usage {
+            kind: Box::new(Kind::Usage {
                 error: UsageError::Deserializing(error),
                 executable_path,
                 shape,
-            },
+                labels,
+                short_help: false,
+                sort_options: SortOrder::Grouped,
+                auto_help: true,
+                multicall: false,
+            }),
+        }
+    }
+
+    #[cfg_attr(all(target_family = "wasm", target_os = "unknown"), allow(dead_code))]
+    pub(crate) fn from_splitting_error(
+        error: split::Error,
+        executable_path: OsString,
+        shape: Shape,
+        labels: HelpLabels,
+    ) -> Self {
+        Self {
+            kind: Box::new(Kind::Usage {
+                error: UsageError::Splitting(error),
+                executable_path,
+                shape,
+                labels,
+                short_help: false,
+                sort_options: SortOrder::Grouped,
+                auto_help: true,
+                multicall: false,
+            }),
+        }
+    }
+
+    pub(crate) fn from_rejection(
+        message: String,
+        executable_path: OsString,
+        shape: Shape,
+        labels: HelpLabels,
+    ) -> Self {
+        Self {
+            kind: Box::new(Kind::Usage {
+                error: UsageError::Rejected(message),
+                executable_path,
+                shape,
+                labels,
+                short_help: false,
+                sort_options: SortOrder::Grouped,
+                auto_help: true,
+                multicall: false,
+            }),
+        }
+    }
+
+    /// Sets whether help requested using `-h` is displayed as a summary.
+    pub(crate) fn with_short_help(mut self, short: bool) -> Self {
+        if let Kind::Usage {
+            ref mut short_help, ..
+        } = *self.kind
+        {
+            *short_help = short;
+        }
+        self
+    }
+
+    /// Sets the order options are listed in within help messages.
+    pub(crate) fn with_sort_options(mut self, order: SortOrder) -> Self {
+        if let Kind::Usage {
+            ref mut sort_options,
+            ..
+        } = *self.kind
+        {
+            *sort_options = order;
+        }
+        self
+    }
+
+    /// Sets whether the `--help` option is available, omitting any mention of it if not.
+    pub(crate) fn with_auto_help(mut self, enabled: bool) -> Self {
+        if let Kind::Usage {
+            ref mut auto_help, ..
+        } = *self.kind
+        {
+            *auto_help = enabled;
+        }
+        self
+    }
+
+    /// Sets whether the top-level command was selected by the executable's name, displaying the
+    /// executable's name in place of the command's.
+    pub(crate) fn with_multicall(mut self, enabled: bool) -> Self {
+        if let Kind::Usage {
+            ref mut multicall, ..
+        } = *self.kind
+        {
+            *multicall = enabled;
+        }
+        self
+    }
+
+    pub(crate) fn checked() -> Self {
+        Self {
+            kind: Box::new(Kind::Checked),
+        }
+    }
+
+    /// Returns whether the arguments passed a check requested with the hidden `--__check` flag.
+    ///
+    /// Programs should treat this as success: the arguments are valid, but no value was
+    /// deserialized. See [`Parser::with_check_flag()`](crate::Parser::with_check_flag()).
+    pub fn is_check_success(&self) -> bool {
+        matches!(*self.kind, Kind::Checked)
+    }
+
+    /// Writes this error's message, followed by a newline, to the stream it belongs on.
+    ///
+    /// Help and version information, along with the message of a successful check, were requested
+    /// by the user and are written to `out`. Every other message is a diagnostic, and is written to
+    /// `err`. Nothing is written to the other stream.
+    ///
+    /// Whether the message is written in color is decided for each stream: it is colored only if
+    /// the stream it is written to is a terminal, so that, for example, help piped to another
+    /// program is written exactly as it is displayed while errors are still colored. Setting the
+    /// `NO_COLOR` environment variable to any non-empty value disables color entirely.
+    pub fn write_to(
+        &self,
+        out: &mut dyn OutputStream,
+        err: &mut dyn OutputStream,
+    ) -> io::Result<()> {
+        if Report::of(self).is_requested() {
+            self.write_line(out)
+        } else {
+            self.write_line(err)
+        }
+    }
+
+    /// Writes this error's message, followed by a newline, to `stream`, in color if it is a
+    /// terminal.
+    fn write_line(&self, stream: &mut dyn OutputStream) -> io::Result<()> {
+        match Ansi::for_stream(stream.is_terminal(), env::var_os("NO_COLOR").as_deref()) {
+            Ansi::Enabled => writeln!(stream, "{:#}", self),
+            Ansi::Disabled => writeln!(stream, "{}", self),
+        }
+    }
+
+    /// Returns whether this error is a help message requested by the user.
+    pub(crate) fn is_help(&self) -> bool {
+        matches!(
+            *self.kind,
+            Kind::Usage {
+                error: UsageError::Parsing(parse::Error::Help { .. }),
+                ..
+            }
+        )
+    }
+
+    /// Returns whether this error is version information requested by the user.
+    pub(crate) fn is_version(&self) -> bool {
+        matches!(*self.kind, Kind::Version { .. })
+    }
+}
+
+impl From<hook::InjectionError> for Error {
+    fn from(error: hook::InjectionError) -> Self {
+        // Invalid injections are mistakes in the program, not in the user's input.
+        Self {
+            kind: Box::new(Kind::Injection { error }),
         }
     }
 }
@@ -328,7 +871,7 @@ impl From<trace::Error> for Error {
     fn from(error: trace::Error) -> Self {
         // Tracing errors are always development errors.
         Self {
-            kind: Kind::Development { error },
+            kind: Box::new(Kind::Development { error }),
         }
     }
 }
@@ -339,7 +882,21 @@ impl Display for Error {
     }
 }
 
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+    /// Returns the error raised while deserializing the parsed arguments, if any.
+    ///
+    /// Parsing errors, development errors, and successful checks have no underlying cause.
+    #[cfg(feature = "source")]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &*self.kind {
+            Kind::Usage {
+                error: UsageError::Deserializing(error),
+                ..
+            } => Some(error),
+            _ => None,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -347,28 +904,161 @@ mod tests {
         super::{
             de,
             parse,
+            parser::SortOrder,
+            split,
             trace,
             trace::{
                 Field,
+                PrimitiveKind,
                 Shape,
                 Variant,
             },
         },
         Error,
+        HelpLabels,
         Kind,
+        OutputStream,
         UsageError,
     };
+    use claims::assert_ok;
+    #[cfg(feature = "source")]
+    use claims::{
+        assert_none,
+        assert_some,
+    };
+    use std::{
+        env,
+        io,
+    };
+
+    fn french_labels() -> HelpLabels {
+        HelpLabels {
+            usage: "UTILISATION".into(),
+            error: "ERREUR".into(),
+            required_arguments: "Arguments requis".into(),
+            global_options: "Options globales".into(),
+            options: "Options".into(),
+            override_options: "Options prioritaires".into(),
+            global_commands: "Commandes".into(),
+            commands: "Commandes".into(),
+            examples: "EXEMPLES".into(),
+            help_description: "Afficher ce message.".into(),
+            version_description: "Afficher la version.".into(),
+            missing_argument: "argument positionnel requis manquant : {name}".into(),
+            missing_arguments: "arguments positionnels requis manquants : {names}".into(),
+            unrecognized_option: "option non reconnue : {option}".into(),
+            unabbreviated_option:
+                "l'option {option} existe ; les abréviations ne sont pas activées".into(),
+            similar_option: "astuce : une option similaire existe : {option}".into(),
+            ambiguous_option: "{option} est ambiguë : correspond à {candidates}".into(),
+            unrecognized_command: "commande non reconnue : {command}".into(),
+            similar_command: "astuce : une commande similaire existe : {command}".into(),
+            problems: "{count} problèmes trouvés :".into(),
+            more_information: "Pour plus d'informations, utilisez {help}.".into(),
+        }
+    }
+
+    fn german_labels() -> HelpLabels {
+        HelpLabels {
+            usage: "VERWENDUNG".into(),
+            error: "FEHLER".into(),
+            required_arguments: "Erforderliche Argumente".into(),
+            global_options: "Globale Optionen".into(),
+            options: "Optionen".into(),
+            override_options: "Vorrangige Optionen".into(),
+            global_commands: "Befehle".into(),
+            commands: "Varianten".into(),
+            examples: "BEISPIELE".into(),
+            help_description: "Diese Nachricht anzeigen.".into(),
+            version_description: "Versionsinformationen anzeigen.".into(),
+            missing_argument: "fehlendes Argument {name}".into(),
+            missing_arguments: "fehlende Argumente {names}".into(),
+            unrecognized_option: "unbekannte Option: {option}".into(),
+            unabbreviated_option: "die Option {option} existiert; Abkürzungen sind nicht aktiviert"
+                .into(),
+            similar_option: "Tipp: eine ähnliche Option existiert: {option}".into(),
+            ambiguous_option: "{option} ist mehrdeutig: passt zu {candidates}".into(),
+            unrecognized_command: "unbekannter Befehl: {command}".into(),
+            similar_command: "Tipp: ein ähnlicher Befehl existiert: {command}".into(),
+            problems: "{count} Probleme gefunden:".into(),
+            more_information: "Weitere Informationen mit {help}.".into(),
+        }
+    }
 
     #[test]
     fn display_development_error() {
         assert_eq!(
             format!("{}", Error {
-                kind: Kind::Development {
+                kind: Box::new(Kind::Development {
                     error: trace::Error::NotSelfDescribing,
-                }
+                })
             }),
-            "cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` or `Deserializer::deserialize_ignored_any()` is not allowed",
+            "cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` is only allowed for enums accepting their variants either by name or by numeric code, and use of `Deserializer::deserialize_ignored_any()` is not allowed",
+        );
+    }
+
+    #[test]
+    fn display_checked() {
+        assert_eq!(format!("{}", Error::checked()), "arguments are valid");
+    }
+
+    #[test]
+    fn is_check_success_checked() {
+        assert!(Error::checked().is_check_success());
+    }
+
+    #[test]
+    fn is_check_success_development_error() {
+        assert!(!Error::from(trace::Error::NotSelfDescribing).is_check_success());
+    }
+
+    #[cfg(feature = "source")]
+    #[test]
+    fn source_deserializing() {
+        let error = Error::from_deserializing_error(
+            de::Error::MissingField("foo"),
+            "executable_name".into(),
+            Shape::Empty {
+                description: String::new(),
+                version: None,
+            },
+            HelpLabels::default(),
+        );
+
+        assert_eq!(
+            assert_some!(std::error::Error::source(&error)).downcast_ref::<de::Error>(),
+            Some(&de::Error::MissingField("foo"))
+        );
+    }
+
+    #[cfg(feature = "source")]
+    #[test]
+    fn source_parsing() {
+        let error = Error::from_parsing_error(
+            parse::Error::MissingArguments(vec!["foo".into()]),
+            "executable_name".into(),
+            Shape::Empty {
+                description: String::new(),
+                version: None,
+            },
+            HelpLabels::default(),
         );
+
+        assert_none!(std::error::Error::source(&error));
+    }
+
+    #[cfg(feature = "source")]
+    #[test]
+    fn source_development() {
+        assert_none!(std::error::Error::source(&Error::from(
+            trace::Error::NotSelfDescribing
+        )));
+    }
+
+    #[cfg(feature = "source")]
+    #[test]
+    fn source_checked() {
+        assert_none!(std::error::Error::source(&Error::checked()));
     }
 
     #[test]
@@ -377,15 +1067,21 @@ mod tests {
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
+                    kind: Box::new(Kind::Usage {
                         error: UsageError::Parsing(parse::Error::MissingArguments(vec!["foo".into()])),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Primitive {
                             name: "bar".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                    }
+                    })
                 }
             ),
             "ERROR: missing required positional argument: <foo>\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
@@ -393,283 +1089,1254 @@ mod tests {
     }
 
     #[test]
-    fn display_usage_error_deserializing() {
+    fn display_usage_error_parsing_with_german_labels() {
         assert_eq!(
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Deserializing(de::Error::Custom("foo".into())),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::MissingArguments(vec!["pfad".into()])),
                         executable_path: "executable_name".into(),
+                        labels: german_labels(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Primitive {
-                            name: "bar".to_owned(),
+                            name: "pfad".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                    }
+                    })
                 }
             ),
-            "ERROR: foo\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
+            "FEHLER: fehlendes Argument <pfad>\n\nVERWENDUNG: executable_name <pfad>\n\nWeitere Informationen mit --help."
         )
     }
 
     #[test]
-    fn display_usage_error_help_empty() {
+    fn display_usage_error_parsing_multiple_with_german_labels() {
         assert_eq!(
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Multiple(vec![
+                            parse::Error::UnrecognizedOption {
+                                name: b"verbsoe".to_vec(),
+                                expecting: vec!["verbose"],
+                            },
+                            parse::Error::MissingArguments(vec!["quelle".into(), "ziel".into()]),
+                        ])),
                         executable_path: "executable_name".into(),
-                        shape: Shape::Empty {
-                            description: "description".into(),
+                        labels: german_labels(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "pfad".to_owned(),
+                            description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                    }
+                    })
                 }
             ),
-            "description\n\nUSAGE: executable_name \n\nOverride Options:\n  -h --help  Display this message."
+            "FEHLER: 2 Probleme gefunden:\n  - unbekannte Option: '--verbsoe'\n    Tipp: eine ähnliche Option existiert: --verbose\n  - fehlende Argumente <quelle> <ziel>\n\nVERWENDUNG: executable_name <pfad>\n\nWeitere Informationen mit --help."
         )
     }
 
     #[test]
-    fn display_usage_error_help_primitive() {
+    fn display_usage_error_parsing_value_name() {
         assert_eq!(
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::MissingArguments(vec!["FILE".into()])),
                         executable_path: "executable_name".into(),
-                        shape: Shape::Primitive {
-                            name: "name".into(),
-                            description: "description".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Struct {
+                            name: "Struct",
+                            description: String::new(),
                             version: None,
+                            examples: None,
+                            required: vec![Field { value_name: Some("FILE".into()), ..Field::new("input", Shape::Primitive {
+                                    name: "a string".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                }, 0) }],
+                            optional: vec![],
+                            booleans: vec![],
+                            constraints: vec![],
                         },
-                    }
+                    })
                 }
             ),
-            "description\n\nUSAGE: executable_name <name>\n\nRequired Arguments:\n  <name>  description\n\nOverride Options:\n  -h --help  Display this message."
+            "ERROR: missing required positional argument: <FILE>\n\nUSAGE: executable_name <FILE>\n\nFor more information, use --help."
         )
     }
 
     #[test]
-    fn display_usage_error_help_optional() {
+    fn display_usage_error_deserializing() {
         assert_eq!(
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Deserializing(de::Error::Custom("foo".into())),
                         executable_path: "executable_name".into(),
-                        shape: Shape::Optional(Box::new(Shape::Primitive {
-                            name: "name".into(),
-                            description: "description".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
                             version: None,
-                        })),
-                    }
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
                 }
             ),
-            "description\n\nUSAGE: executable_name [--<name>]\n\nOverride Options:\n  -h --help  Display this message."
+            "ERROR: foo\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
         )
     }
 
     #[test]
-    fn display_usage_error_help_struct() {
+    fn display_usage_error_rejected() {
         assert_eq!(
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Rejected("--foo cannot be used with --bar".into()),
                         executable_path: "executable_name".into(),
-                        shape: Shape::Struct {
-                            name: "name",
-                            description: "description".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
                             version: None,
-                            required: vec![Field {
-                                name: "foo",
-                                description: "foo bar".into(),
-                                aliases: vec![],
-                                shape: Shape::Primitive {
-                                    name: "not shown".into(),
-                                    description: "not shown".into(),
-                                    version: None,
-                                },
-                                index: 0,
-                            }],
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "ERROR: --foo cannot be used with --bar\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_splitting() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Splitting(split::Error::UnterminatedQuote {
+                            quote: '"',
+                            offset: 10,
+                        }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "ERROR: unterminated double quote starting at byte 10\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
+        )
+    }
+
+    fn synthetic_commands() -> &'static [&'static str] {
+        Box::leak(
+            (0..300)
+                .map(|index| &*Box::leak(format!("command{index:03}").into_boxed_str()))
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+        )
+    }
+
+    #[test]
+    fn display_usage_error_deserializing_unknown_variant_truncated() {
+        let expected = ["command299", "command099", "command199"]
+            .into_iter()
+            .map(String::from)
+            .chain((0..9).map(|index| format!("command2{index}9")))
+            .chain((0..8).map(|index| format!("command29{index}")))
+            .collect::<Vec<_>>();
+        assert_eq!(expected.len(), 20);
+
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Deserializing(de::Error::UnknownVariant(
+                            "command299".into(),
+                            synthetic_commands()
+                        )),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            format!("ERROR: unknown command command299, expected one of {expected:?} ... and 280 more (run 'executable_name --help' to list all commands)\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help.")
+        )
+    }
+
+    #[test]
+    fn display_usage_error_deserializing_unknown_variant_truncated_precision() {
+        assert_eq!(
+            format!(
+                "{:.3}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Deserializing(de::Error::UnknownVariant(
+                            "zzz".into(),
+                            synthetic_commands()
+                        )),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "ERROR: unknown command zzz, expected one of [\"command000\", \"command001\", \"command002\"] ... and 297 more (run 'executable_name --help' to list all commands)\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_missing_command_truncated() {
+        assert_eq!(
+            format!(
+                "{:.3}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::MissingCommand {
+                            name: "bar".into(),
+                            expecting: synthetic_commands().to_vec(),
+                        }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "ERROR: missing required command <bar>, expected one of [\"command000\", \"command001\", \"command002\"] ... and 297 more (run 'executable_name --help' to list all commands)\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_deserializing_unknown_field_truncated() {
+        assert_eq!(
+            format!(
+                "{:.2}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Deserializing(de::Error::UnknownField(
+                            "baz".into(),
+                            &["foo", "bar", "qux", "baa"]
+                        )),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "ERROR: unexpected argument --baz, expected one of [\"baa\", \"bar\"] ... and 2 more (run 'executable_name --help' to list all options)\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_deserializing_unknown_field_within_limit() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Deserializing(de::Error::UnknownField(
+                            "baz".into(),
+                            &["foo", "bar"]
+                        )),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "ERROR: unexpected argument --baz, expected one of [\"foo\", \"bar\"]\n\nUSAGE: executable_name <bar>\n\nFor more information, use --help."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_empty() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Empty {
+                            description: "description".into(),
+                            version: None,
+                        },
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name \n\nOverride Options:\n  -h --help  Display this message."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_primitive() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "name".into(),
+                            description: "description".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name <name>\n\nRequired Arguments:\n  <name>  description\n\nOverride Options:\n  -h --help  Display this message."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_optional() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Optional(Box::new(Shape::Primitive {
+                            name: "name".into(),
+                            description: "description".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        })),
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name [--<name>]\n\nOverride Options:\n  -h --help  Display this message."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_struct() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Struct {
+                            name: "name",
+                            description: "description".into(),
+                            version: None,
+                            examples: None,
+                            required: vec![Field { description: "foo bar".into(), ..Field::new("foo", Shape::Primitive {
+                                    name: "not shown".into(),
+                                    description: "not shown".into(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                }, 0) }],
+                            optional: vec![
+                                Field { description: "bar baz".into(), aliases: vec!["b"], ..Field::new("bar", Shape::Primitive {
+                                        name: "u64".into(),
+                                        description: "not shown".into(),
+                                        version: None,
+                                        kind: PrimitiveKind::U64,
+                                    }, 0) }
+                            ],
+                            booleans: vec![],
+                            constraints: vec![],
+                        },
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name [options] <foo>\n\nRequired Arguments:\n  <foo>  foo bar\n\nGlobal Options:\n  -b --bar <u64>  bar baz\n\nOverride Options:\n  -h --help  Display this message."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_struct_long_option() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Struct {
+                            name: "name",
+                            description: "description".into(),
+                            version: None,
+                            examples: None,
+                            required: vec![],
+                            optional: vec![
+                                Field { description: "output file".into(), aliases: vec!["o", "out"], ..Field::new("output", Shape::Primitive {
+                                        name: "file".into(),
+                                        description: "not shown".into(),
+                                        version: None,
+                                        kind: PrimitiveKind::Str,
+                                    }, 0) },
+                                Field { description: "first line\nsecond line".into(), ..Field::new("a-very-long-option-name-for-testing", Shape::Primitive {
+                                        name: "u64".into(),
+                                        description: "not shown".into(),
+                                        version: None,
+                                        kind: PrimitiveKind::U64,
+                                    }, 1) },
+                            ],
+                            booleans: vec![],
+                            constraints: vec![],
+                        },
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name [options]\n\nGlobal Options:\n  -o --output --out <file>  output file\n     --a-very-long-option-name-for-testing <u64>\n                            first line\n                            second line\n\nOverride Options:\n  -h --help  Display this message."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_enum() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Enum {
+                            name: "name",
+                            description: "description".into(),
+                            version: None,
+                            examples: None,
+                            variants: vec![
+                                Variant {
+                                    name: "foo",
+                                    description: "bar".into(),
+                                    version: None,
+                                    group: None,
+                                    raw: false,
+                                    aliases: vec!["f"],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Empty {
+                                        description: "not shown".into(),
+                                        version: None,
+                                    },
+                                },
+                                Variant {
+                                    name: "baz",
+                                    description: "qux".into(),
+                                    version: None,
+                                    group: None,
+                                    raw: false,
+                                    aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Primitive {
+                                        name: "i32".into(),
+                                        description: "not shown".into(), 
+                                        version: None,
+                                        kind: PrimitiveKind::I32,
+                                    },
+                                }
+                            ],
+                        },
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name <name>\n\nRequired Arguments:\n  <name>  description\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo f      bar\n  baz <i32>  qux"
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_enum_groups() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Enum {
+                            name: "name",
+                            description: "description".into(),
+                            version: None,
+                            examples: None,
+                            variants: vec![
+                                Variant {
+                                    name: "foo",
+                                    description: "bar".into(),
+                                    version: None,
+                                    group: Some("First".into()),
+                                    raw: false,
+                                    aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Empty {
+                                        description: "not shown".into(),
+                                        version: None,
+                                    },
+                                },
+                                Variant {
+                                    name: "baz",
+                                    description: "qux".into(),
+                                    version: None,
+                                    group: None,
+                                    raw: false,
+                                    aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Empty {
+                                        description: "not shown".into(),
+                                        version: None,
+                                    },
+                                },
+                                Variant {
+                                    name: "quux",
+                                    description: "corge".into(),
+                                    version: None,
+                                    group: Some("Second".into()),
+                                    raw: false,
+                                    aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Primitive {
+                                        name: "i32".into(),
+                                        description: "not shown".into(),
+                                        version: None,
+                                        kind: PrimitiveKind::I32,
+                                    },
+                                },
+                                Variant {
+                                    name: "grault",
+                                    description: "garply".into(),
+                                    version: None,
+                                    group: Some("First".into()),
+                                    raw: false,
+                                    aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Empty {
+                                        description: "not shown".into(),
+                                        version: None,
+                                    },
+                                },
+                            ],
+                        },
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name <name>\n\nRequired Arguments:\n  <name>  description\n\nOverride Options:\n  -h --help  Display this message.\n\nFirst:\n  foo         bar\n  grault      garply\n\nCommands:\n  baz         qux\n\nSecond:\n  quux <i32>  corge"
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_variant() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Variant {
+                            name: "f",
+                            description: "bar".into(),
+                            version: None,
+                            shape: Box::new(Shape::Primitive {
+                                name: "i32".into(),
+                                description: "i32 description".into(), 
+                                version: None,
+                                kind: PrimitiveKind::I32,
+                            }),
+                            variants: vec![
+                                Variant {
+                                    name: "foo",
+                                    description: "bar".into(),
+                                    version: None,
+                                    group: None,
+                                    raw: false,
+                                    aliases: vec!["f"],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Empty {
+                                        description: "not shown".into(),
+                                        version: None,
+                                    },
+                                },
+                                Variant {
+                                    name: "baz",
+                                    description: "qux".into(),
+                                    version: None,
+                                    group: None,
+                                    raw: false,
+                                    aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
+                                    shape: Shape::Primitive {
+                                        name: "i32".into(),
+                                        description: "not shown".into(),
+                                        version: None,
+                                        kind: PrimitiveKind::I32,
+                                    },
+                                }
+                            ],
+                            enum_name: "name",
+                        },
+                    })
+                }
+            ),
+            "bar\n\nUSAGE: executable_name f <i32>\n\nRequired Arguments:\n  <i32>  i32 description\n\nOverride Options:\n  -h --help  Display this message."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_with_version() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Empty {
+                            description: "description".into(),
+                            version: Some("version".into()),
+                        },
+                    })
+                }
+            ),
+            "description\n\nUSAGE: executable_name \n\nOverride Options:\n  -h --help     Display this message.\n     --version  Display version information."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_parsing_with_labels() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::MissingArguments(vec![
+                            "foo".into()
+                        ])),
+                        executable_path: "executable_name".into(),
+                        labels: french_labels(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Primitive {
+                            name: "foo".into(),
+                            description: "bar".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    })
+                }
+            ),
+            "ERREUR: argument positionnel requis manquant : <foo>\n\nUTILISATION: executable_name <foo>\n\nPour plus d'informations, utilisez --help."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_struct_with_labels() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: french_labels(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Struct {
+                            name: "name",
+                            description: "description".into(),
+                            version: Some("version".into()),
+                            examples: None,
+                            required: vec![Field { description: "foo bar".into(), ..Field::new("foo", Shape::Primitive {
+                                    name: "not shown".into(),
+                                    description: "not shown".into(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                }, 0) }],
                             optional: vec![
-                                Field {
-                                    name: "bar",
-                                    description: "bar baz".into(),
-                                    aliases: vec!["b"],
-                                    shape: Shape::Primitive {
+                                Field { description: "bar baz".into(), aliases: vec!["b"], ..Field::new("bar", Shape::Primitive {
                                         name: "u64".into(),
                                         description: "not shown".into(),
                                         version: None,
-                                    },
-                                    index: 0,
-                                }
+                                        kind: PrimitiveKind::U64,
+                                    }, 0) }
                             ],
                             booleans: vec![],
+                            constraints: vec![],
                         },
-                    }
+                    })
                 }
             ),
-            "description\n\nUSAGE: executable_name [options] <foo>\n\nRequired Arguments:\n  <foo>  foo bar\n\nGlobal Options:\n  -b --bar <u64>  bar baz\n\nOverride Options:\n  -h --help  Display this message."
+            "description\n\nUTILISATION: executable_name [options] <foo>\n\nArguments requis:\n  <foo>  foo bar\n\nOptions globales:\n  -b --bar <u64>  bar baz\n\nOptions prioritaires:\n  -h --help     Afficher ce message.\n     --version  Afficher la version."
         )
     }
 
     #[test]
-    fn display_usage_error_help_enum() {
+    fn display_usage_error_help_struct_with_german_labels() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: german_labels(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Struct {
+                            name: "name",
+                            description: "description".into(),
+                            version: Some("version".into()),
+                            examples: None,
+                            required: vec![Field { description: "foo bar".into(), ..Field::new("foo", Shape::Primitive {
+                                    name: "not shown".into(),
+                                    description: "not shown".into(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                }, 0) }],
+                            optional: vec![
+                                Field { description: "bar baz".into(), aliases: vec!["b"], ..Field::new("bar", Shape::Primitive {
+                                        name: "u64".into(),
+                                        description: "not shown".into(),
+                                        version: None,
+                                        kind: PrimitiveKind::U64,
+                                    }, 0) }
+                            ],
+                            booleans: vec![],
+                            constraints: vec![],
+                        },
+                    })
+                }
+            ),
+            "description\n\nVERWENDUNG: executable_name [options] <foo>\n\nErforderliche Argumente:\n  <foo>  foo bar\n\nGlobale Optionen:\n  -b --bar <u64>  bar baz\n\nVorrangige Optionen:\n  -h --help     Diese Nachricht anzeigen.\n     --version  Versionsinformationen anzeigen."
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_enum_with_labels() {
         assert_eq!(
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: french_labels(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Enum {
                             name: "name",
                             description: "description".into(),
                             version: None,
+                            examples: None,
                             variants: vec![
                                 Variant {
                                     name: "foo",
                                     description: "bar".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec!["f"],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: "not shown".into(),
                                         version: None,
                                     },
                                 },
-                                Variant {
-                                    name: "baz",
-                                    description: "qux".into(),
+                            ],
+                        },
+                    })
+                }
+            ),
+            "description\n\nUTILISATION: executable_name <name>\n\nArguments requis:\n  <name>  description\n\nOptions prioritaires:\n  -h --help  Afficher ce message.\n\nCommandes:\n  foo f   bar"
+        )
+    }
+
+    #[test]
+    fn display_usage_error_help_struct_with_examples() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Struct {
+                            name: "name",
+                            description: "description".into(),
+                            version: None,
+                            examples: Some("$ executable_name foo\n\n$ executable_name --bar 42 foo".into()),
+                            required: vec![Field { description: "foo bar".into(), ..Field::new("foo", Shape::Primitive {
+                                    name: "not shown".into(),
+                                    description: "not shown".into(),
                                     version: None,
-                                    aliases: vec![],
-                                    shape: Shape::Primitive {
-                                        name: "i32".into(),
-                                        description: "not shown".into(), 
+                                    kind: PrimitiveKind::Str,
+                                }, 0) }],
+                            optional: vec![
+                                Field { description: "bar baz".into(), ..Field::new("bar", Shape::Primitive {
+                                        name: "u64".into(),
+                                        description: "not shown".into(),
                                         version: None,
-                                    },
-                                }
+                                        kind: PrimitiveKind::U64,
+                                    }, 0) }
                             ],
+                            booleans: vec![],
+                            constraints: vec![],
                         },
-                    }
+                    })
                 }
             ),
-            "description\n\nUSAGE: executable_name <name>\n\nRequired Arguments:\n  <name>  description\n\nOverride Options:\n  -h --help  Display this message.\n\nname Variants:\n  foo f      bar\n  baz <i32>  qux"
+            "description\n\nUSAGE: executable_name [options] <foo>\n\nRequired Arguments:\n  <foo>  foo bar\n\nGlobal Options:\n  --bar <u64>  bar baz\n\nOverride Options:\n  -h --help  Display this message.\n\nEXAMPLES:\n  $ executable_name foo\n\n  $ executable_name --bar 42 foo"
         )
     }
 
     #[test]
-    fn display_usage_error_help_variant() {
+    fn display_usage_error_help_enum_with_examples() {
         assert_eq!(
             format!(
                 "{}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
-                        shape: Shape::Variant {
-                            name: "f",
-                            description: "bar".into(),
+                        labels: french_labels(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Enum {
+                            name: "name",
+                            description: "description".into(),
                             version: None,
-                            shape: Box::new(Shape::Primitive {
-                                name: "i32".into(),
-                                description: "i32 description".into(), 
-                                version: None,
-                            }),
+                            examples: Some("$ executable_name foo".into()),
                             variants: vec![
                                 Variant {
                                     name: "foo",
                                     description: "bar".into(),
                                     version: None,
-                                    aliases: vec!["f"],
+                                    group: None,
+                                    raw: false,
+                                    aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: "not shown".into(),
                                         version: None,
                                     },
                                 },
-                                Variant {
-                                    name: "baz",
-                                    description: "qux".into(),
-                                    version: None,
-                                    aliases: vec![],
-                                    shape: Shape::Primitive {
-                                        name: "i32".into(),
-                                        description: "not shown".into(),
-                                        version: None,
-                                    },
-                                }
                             ],
-                            enum_name: "name",
                         },
-                    }
+                    })
                 }
             ),
-            "bar\n\nUSAGE: executable_name f <i32>\n\nRequired Arguments:\n  <i32>  i32 description\n\nOverride Options:\n  -h --help  Display this message."
+            "description\n\nUTILISATION: executable_name <name>\n\nArguments requis:\n  <name>  description\n\nOptions prioritaires:\n  -h --help  Afficher ce message.\n\nCommandes:\n  foo   bar\n\nEXEMPLES:\n  $ executable_name foo"
+        )
+    }
+
+    fn version_struct() -> Shape {
+        Shape::Struct {
+            name: "Args",
+            description: String::new(),
+            version: Some("1.2.3".into()),
+            examples: None,
+            required: vec![],
+            optional: vec![],
+            booleans: vec![],
+            constraints: vec![],
+        }
+    }
+
+    fn version_enum() -> Shape {
+        Shape::Enum {
+            name: "Command",
+            description: String::new(),
+            version: Some("1.2.3".into()),
+            examples: None,
+            variants: vec![Variant {
+                name: "foo",
+                description: String::new(),
+                version: None,
+                group: None,
+                raw: false,
+                aliases: vec![],
+                deprecated_aliases: Vec::new(),
+                shape: Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn write_to_help() {
+        let error = Error::from_parsing_error(
+            parse::Error::Help { short: false },
+            "executable_name".into(),
+            Shape::Empty {
+                description: "description".into(),
+                version: None,
+            },
+            HelpLabels::default(),
+        );
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+
+        assert_ok!(error.write_to(&mut out, &mut err));
+        assert_eq!(
+            String::from_utf8_lossy(&out),
+            "description\n\nUSAGE: executable_name \n\nOverride Options:\n  -h --help  Display this message.\n"
+        );
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    fn write_to_version() {
+        let error = Error::from_parsing_error(
+            parse::Error::Version,
+            "executable_name".into(),
+            Shape::Empty {
+                description: String::new(),
+                version: Some("foo".into()),
+            },
+            HelpLabels::default(),
+        );
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+
+        assert_ok!(error.write_to(&mut out, &mut err));
+        assert_eq!(String::from_utf8_lossy(&out), "executable_name foo\n");
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    fn write_to_missing_argument() {
+        let error = Error::from_parsing_error(
+            parse::Error::MissingArguments(vec!["foo".into()]),
+            "executable_name".into(),
+            Shape::Primitive {
+                name: "foo".into(),
+                description: String::new(),
+                version: None,
+                kind: PrimitiveKind::Str,
+            },
+            HelpLabels::default(),
+        );
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+
+        assert_ok!(error.write_to(&mut out, &mut err));
+        assert!(out.is_empty());
+        assert_eq!(
+            String::from_utf8_lossy(&err),
+            "ERROR: missing required positional argument: <foo>\n\nUSAGE: executable_name <foo>\n\nFor more information, use --help.\n"
+        );
+    }
+
+    /// A buffer that claims to be a terminal.
+    struct Terminal(Vec<u8>);
+
+    impl io::Write for Terminal {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            self.0.flush()
+        }
+    }
+
+    impl OutputStream for Terminal {
+        fn is_terminal(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn write_to_terminal() {
+        let error = Error::from_parsing_error(
+            parse::Error::MissingArguments(vec!["foo".into()]),
+            "executable_name".into(),
+            Shape::Primitive {
+                name: "foo".into(),
+                description: String::new(),
+                version: None,
+                kind: PrimitiveKind::Str,
+            },
+            HelpLabels::default(),
+        );
+        let mut out = Vec::new();
+        let mut err = Terminal(Vec::new());
+
+        assert_ok!(error.write_to(&mut out, &mut err));
+        assert!(out.is_empty());
+        // Color is only disabled if requested by the environment running the tests.
+        if env::var_os("NO_COLOR").is_some_and(|no_color| !no_color.is_empty()) {
+            assert_eq!(String::from_utf8_lossy(&err.0), format!("{}\n", error));
+        } else {
+            assert_eq!(String::from_utf8_lossy(&err.0), format!("{:#}\n", error));
+        }
+    }
+
+    #[test]
+    fn write_to_checked() {
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+
+        assert_ok!(Error::checked().write_to(&mut out, &mut err));
+        assert_eq!(String::from_utf8_lossy(&out), "arguments are valid\n");
+        assert!(err.is_empty());
+    }
+
+    #[test]
+    fn display_version_empty() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::from_parsing_error(
+                    parse::Error::Version,
+                    "executable_name".into(),
+                    Shape::Empty {
+                        description: String::new(),
+                        version: Some("foo".into()),
+                    },
+                    HelpLabels::default(),
+                )
+            ),
+            "executable_name foo"
         )
     }
 
     #[test]
-    fn display_usage_error_help_with_version() {
+    fn display_version_struct() {
         assert_eq!(
             format!(
                 "{}",
-                Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
-                        executable_path: "executable_name".into(),
-                        shape: Shape::Empty {
-                            description: "description".into(),
-                            version: Some("version".into()),
-                        },
-                    }
-                }
+                Error::from_parsing_error(
+                    parse::Error::Version,
+                    "executable_name".into(),
+                    version_struct(),
+                    HelpLabels::default(),
+                )
             ),
-            "description\n\nUSAGE: executable_name \n\nOverride Options:\n  -h --help     Display this message.\n     --version  Display version information."
+            "executable_name 1.2.3"
         )
     }
 
     #[test]
-    fn display_usage_error_version() {
+    fn display_version_enum() {
         assert_eq!(
             format!(
                 "{}",
-                Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Version),
-                        executable_path: "executable_name".into(),
-                        shape: Shape::Empty {
-                            description: String::new(),
-                            version: Some("foo".into()),
-                        },
-                    }
-                }
+                Error::from_parsing_error(
+                    parse::Error::Version,
+                    "executable_name".into(),
+                    version_enum(),
+                    HelpLabels::default(),
+                )
+            ),
+            "executable_name 1.2.3"
+        )
+    }
+
+    #[test]
+    fn display_alternate_version_struct() {
+        assert_eq!(
+            format!(
+                "{:#}",
+                Error::from_parsing_error(
+                    parse::Error::Version,
+                    "executable_name".into(),
+                    version_struct(),
+                    HelpLabels::default(),
+                )
+            ),
+            "\x1b[2mexecutable_name\x1b[0m 1.2.3"
+        )
+    }
+
+    #[test]
+    fn display_alternate_version_enum() {
+        assert_eq!(
+            format!(
+                "{:#}",
+                Error::from_parsing_error(
+                    parse::Error::Version,
+                    "executable_name".into(),
+                    version_enum(),
+                    HelpLabels::default(),
+                )
+            ),
+            "\x1b[2mexecutable_name\x1b[0m 1.2.3"
+        )
+    }
+
+    #[test]
+    fn display_version_ignores_sort_options() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::from_parsing_error(
+                    parse::Error::Version,
+                    "executable_name".into(),
+                    version_struct(),
+                    HelpLabels::default(),
+                )
+                .with_sort_options(SortOrder::Declaration)
             ),
-            "foo"
+            "executable_name 1.2.3"
         )
     }
 
     #[test]
     #[should_panic(expected = "no version information available")]
-    fn display_usage_error_version_not_present() {
-        let _ = format!(
-            "{}",
-            Error {
-                kind: Kind::Usage {
-                    error: UsageError::Parsing(parse::Error::Version),
-                    executable_path: "executable_name".into(),
-                    shape: Shape::Empty {
-                        description: String::new(),
-                        version: None,
-                    },
-                }
-            }
+    fn version_not_present() {
+        let _ = Error::from_parsing_error(
+            parse::Error::Version,
+            "executable_name".into(),
+            Shape::Empty {
+                description: String::new(),
+                version: None,
+            },
+            HelpLabels::default(),
         );
     }
 
@@ -679,15 +2346,21 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
+                    kind: Box::new(Kind::Usage {
                         error: UsageError::Parsing(parse::Error::MissingArguments(vec!["foo".into()])),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Primitive {
                             name: "bar".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                    }
+                    })
                 }
             ),
             "\x1b[91mERROR\x1b[0m: missing required positional argument: <foo>\n\n\x1b[97mUSAGE:\x1b[0m \x1b[96mexecutable_name\x1b[0m \x1b[36m<bar>\x1b[0m\n\nFor more information, use \x1b[96m--help\x1b[0m."
@@ -700,15 +2373,21 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
+                    kind: Box::new(Kind::Usage {
                         error: UsageError::Deserializing(de::Error::Custom("foo".into())),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Primitive {
                             name: "bar".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                    }
+                    })
                 }
             ),
             "\x1b[91mERROR\x1b[0m: foo\n\n\x1b[97mUSAGE:\x1b[0m \x1b[96mexecutable_name\x1b[0m \x1b[36m<bar>\x1b[0m\n\nFor more information, use \x1b[96m--help\x1b[0m."
@@ -721,14 +2400,19 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Empty {
                             description: "description".into(),
                             version: None,
                         },
-                    }
+                    })
                 }
             ),
             "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m\x1b[0m\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message."
@@ -741,15 +2425,21 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Primitive {
                             name: "name".into(),
                             description: "description".into(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                    }
+                    })
                 }
             ),
             "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m<name>\x1b[0m\n\n\x1b[97mRequired Arguments:\x1b[0m\n  \x1b[96m<name>\x1b[0m  description\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message."
@@ -762,15 +2452,21 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Optional(Box::new(Shape::Primitive {
                             name: "name".into(),
                             description: "description".into(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         })),
-                    }
+                    })
                 }
             ),
             "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m[--<name>]\x1b[0m\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message."
@@ -783,40 +2479,37 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Struct {
                             name: "name",
                             description: "description".into(),
                             version: None,
-                            required: vec![Field {
-                                name: "foo",
-                                description: "foo bar".into(),
-                                aliases: vec![],
-                                shape: Shape::Primitive {
+                            examples: None,
+                            required: vec![Field { description: "foo bar".into(), ..Field::new("foo", Shape::Primitive {
                                     name: "not shown".into(),
                                     description: "not shown".into(),
                                     version: None,
-                                },
-                                index: 0,
-                            }],
+                                    kind: PrimitiveKind::Str,
+                                }, 0) }],
                             optional: vec![
-                                Field {
-                                    name: "bar",
-                                    description: "bar baz".into(),
-                                    aliases: vec!["b"],
-                                    shape: Shape::Primitive {
+                                Field { description: "bar baz".into(), aliases: vec!["b"], ..Field::new("bar", Shape::Primitive {
                                         name: "u64".into(),
                                         description: "not shown".into(),
                                         version: None,
-                                    },
-                                    index: 0,
-                                }
+                                        kind: PrimitiveKind::U64,
+                                    }, 0) }
                             ],
                             booleans: vec![],
+                            constraints: vec![],
                         },
-                    }
+                    })
                 }
             ),
             "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m[options] <foo>\x1b[0m\n\n\x1b[97mRequired Arguments:\x1b[0m\n  \x1b[96m<foo>\x1b[0m  foo bar\n\n\x1b[97mGlobal Options:\x1b[0m\n  \x1b[96m-b\x1b[0m \x1b[96m--bar\x1b[0m \x1b[36m<u64>\x1b[0m  bar baz\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message."
@@ -829,19 +2522,28 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Enum {
                             name: "name",
                             description: "description".into(),
                             version: None,
+                            examples: None,
                             variants: vec![
                                 Variant {
                                     name: "foo",
                                     description: "bar".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec!["f"],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: "not shown".into(),
                                         version: None,
@@ -851,19 +2553,54 @@ mod tests {
                                     name: "baz",
                                     description: "qux".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Primitive {
                                         name: "i32".into(),
                                         description: "not shown".into(),
                                         version: None,
+                                        kind: PrimitiveKind::I32,
                                     },
                                 }
                             ],
                         },
-                    }
+                    })
+                }
+            ),
+            "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m<name>\x1b[0m\n\n\x1b[97mRequired Arguments:\x1b[0m\n  \x1b[96m<name>\x1b[0m  description\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message.\n\n\x1b[97mCommands:\x1b[0m\n  \x1b[96mfoo f \x1b[0m\x1b[36m\x1b[0m     bar\n  \x1b[96mbaz \x1b[0m\x1b[36m<i32>\x1b[0m  qux"
+        )
+    }
+
+    #[test]
+    fn display_alternate_usage_error_help_with_examples() {
+        assert_eq!(
+            format!(
+                "{:#}",
+                Error {
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
+                        executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
+                        shape: Shape::Struct {
+                            name: "name",
+                            description: "description".into(),
+                            version: None,
+                            examples: Some("$ executable_name".into()),
+                            required: vec![],
+                            optional: vec![],
+                            booleans: vec![],
+                            constraints: vec![],
+                        },
+                    })
                 }
             ),
-            "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m<name>\x1b[0m\n\n\x1b[97mRequired Arguments:\x1b[0m\n  \x1b[96m<name>\x1b[0m  description\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message.\n\n\x1b[97mname Variants:\x1b[0m\n  \x1b[96mfoo f \x1b[0m\x1b[36m\x1b[0m     bar\n  \x1b[96mbaz \x1b[0m\x1b[36m<i32>\x1b[0m  qux"
+            "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m\x1b[0m\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message.\n\n\x1b[97mEXAMPLES:\x1b[0m\n  $ executable_name"
         )
     }
 
@@ -873,9 +2610,14 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Variant {
                             name: "f",
                             description: "bar".into(),
@@ -884,13 +2626,17 @@ mod tests {
                                 name: "i32".into(),
                                 description: "i32 description".into(),
                                 version: None,
+                                kind: PrimitiveKind::I32,
                             }),
                             variants: vec![
                                 Variant {
                                     name: "foo",
                                     description: "bar".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec!["f"],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: "not shown".into(),
                                         version: None,
@@ -900,17 +2646,21 @@ mod tests {
                                     name: "baz",
                                     description: "qux".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Primitive {
                                         name: "i32".into(),
                                         description: "not shown".into(),
                                         version: None,
+                                        kind: PrimitiveKind::I32,
                                     },
                                 }
                             ],
                             enum_name: "name",
                         },
-                    }
+                    })
                 }
             ),
             "bar\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36mf <i32>\x1b[0m\n\n\x1b[97mRequired Arguments:\x1b[0m\n  \x1b[96m<i32>\x1b[0m  i32 description\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message."
@@ -923,14 +2673,19 @@ mod tests {
             format!(
                 "{:#}",
                 Error {
-                    kind: Kind::Usage {
-                        error: UsageError::Parsing(parse::Error::Help),
+                    kind: Box::new(Kind::Usage {
+                        error: UsageError::Parsing(parse::Error::Help { short: false }),
                         executable_path: "executable_name".into(),
+                        labels: HelpLabels::default(),
+                        short_help: false,
+                        sort_options: SortOrder::Grouped,
+                        auto_help: true,
+                        multicall: false,
                         shape: Shape::Empty {
                             description: "description".into(),
                             version: Some("version".into()),
                         },
-                    }
+                    })
                 }
             ),
             "description\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96mexecutable_name\x1b[0m \x1b[36m\x1b[0m\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m     Display this message.\n  \x1b[96m   --version\x1b[0m  Display version information."
@@ -0,0 +1,72 @@
+use std::{
+    fs::File,
+    io,
+    io::{
+        IsTerminal,
+        Stderr,
+        StderrLock,
+        Stdout,
+        StdoutLock,
+    },
+};
+
+/// A stream that [`Error::write_to()`](crate::Error::write_to()) can write messages to.
+///
+/// Messages are written in color to streams that are terminals. Every other stream, such as a
+/// file, a pipe, or a buffer, receives the messages exactly as they are displayed.
+///
+/// ``` rust
+/// use serde_args::OutputStream;
+///
+/// // A buffer is never a terminal.
+/// assert!(!Vec::new().is_terminal());
+/// ```
+pub trait OutputStream: io::Write {
+    /// Returns whether the stream is a terminal.
+    fn is_terminal(&self) -> bool;
+}
+
+impl OutputStream for Stdout {
+    fn is_terminal(&self) -> bool {
+        IsTerminal::is_terminal(self)
+    }
+}
+
+impl OutputStream for StdoutLock<'_> {
+    fn is_terminal(&self) -> bool {
+        IsTerminal::is_terminal(self)
+    }
+}
+
+impl OutputStream for Stderr {
+    fn is_terminal(&self) -> bool {
+        IsTerminal::is_terminal(self)
+    }
+}
+
+impl OutputStream for StderrLock<'_> {
+    fn is_terminal(&self) -> bool {
+        IsTerminal::is_terminal(self)
+    }
+}
+
+impl OutputStream for File {
+    fn is_terminal(&self) -> bool {
+        IsTerminal::is_terminal(self)
+    }
+}
+
+impl OutputStream for Vec<u8> {
+    fn is_terminal(&self) -> bool {
+        false
+    }
+}
+
+impl<W> OutputStream for &mut W
+where
+    W: OutputStream + ?Sized,
+{
+    fn is_terminal(&self) -> bool {
+        (**self).is_terminal()
+    }
+}
@@ -0,0 +1,58 @@
+use std::{
+    fmt,
+    fmt::{
+        Display,
+        Formatter,
+    },
+};
+
+/// A problem with the command line that did not prevent it from being parsed.
+///
+/// Warnings are returned along with the parsed value by
+/// [`Parser::parse_with_warnings()`](crate::Parser::parse_with_warnings()) and
+/// [`Parser::parse_prefix_with_warnings()`](crate::Parser::parse_prefix_with_warnings()), where
+/// they can be written anywhere using
+/// [`ParseOutcome::write_warnings()`](crate::ParseOutcome::write_warnings()) or ignored. Other
+/// entry points print them to standard error instead.
+///
+/// Currently, a warning is recorded whenever an option or command is given using an alias that
+/// was deprecated using [`Metadata::deprecate_alias()`](crate::Metadata::deprecate_alias()).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Warning {
+    /// The deprecated name, as it is displayed.
+    name: String,
+    message: String,
+}
+
+impl Warning {
+    /// Creates a warning that the deprecated alias `name` was used.
+    pub(crate) fn deprecated_alias(name: String, message: String) -> Self {
+        Self { name, message }
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "WARNING: {} is deprecated: {}",
+            self.name, self.message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Warning;
+
+    #[test]
+    fn display_deprecated_alias() {
+        assert_eq!(
+            format!(
+                "{}",
+                Warning::deprecated_alias("--colour".into(), "use --color instead".into())
+            ),
+            "WARNING: --colour is deprecated: use --color instead"
+        );
+    }
+}
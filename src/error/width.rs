@@ -8,6 +8,12 @@ use std::{
 };
 use unicode_width::UnicodeWidthStr;
 
+/// The widest the long options column of a help message may be.
+///
+/// Options wider than this are not used to align the descriptions of the other options. Their own
+/// descriptions are instead written on the following line.
+pub(super) const MAX_LONG_OPTIONS_WIDTH: usize = 30;
+
 pub(super) trait Width {
     fn width(&self) -> usize;
 }
@@ -45,9 +51,37 @@ where
     }
 }
 
+/// A multi-line string whose continuation lines are indented.
+///
+/// Every line after the first is indented by the `width` parameter, aligning it with the column the
+/// first line began in. Empty lines are left empty.
+#[derive(Debug)]
+pub(super) struct Hanging<'a>(pub(super) &'a str);
+
+impl Display for Hanging<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let indent = formatter.width().unwrap_or(0);
+        for (index, line) in self.0.split('\n').enumerate() {
+            if index > 0 {
+                formatter.write_char('\n')?;
+                if !line.is_empty() {
+                    for _ in 0..indent {
+                        formatter.write_char(' ')?;
+                    }
+                }
+            }
+            formatter.write_str(line)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::WidthFormatted;
+    use super::{
+        Hanging,
+        WidthFormatted,
+    };
 
     #[test]
     fn no_width() {
@@ -70,4 +104,22 @@ mod tests {
     fn width_non_ascii() {
         assert_eq!(format!("{:6}", WidthFormatted("🔥".to_owned())), "🔥    ");
     }
+
+    #[test]
+    fn hanging_single_line() {
+        assert_eq!(format!("{:4}", Hanging("foo")), "foo");
+    }
+
+    #[test]
+    fn hanging_multiple_lines() {
+        assert_eq!(
+            format!("{:4}", Hanging("foo\n  - bar\n\nbaz")),
+            "foo\n      - bar\n\n    baz"
+        );
+    }
+
+    #[test]
+    fn hanging_no_width() {
+        assert_eq!(format!("{}", Hanging("foo\nbar")), "foo\nbar");
+    }
 }
@@ -0,0 +1,271 @@
+//! Deserialization of IP networks written in CIDR notation.
+//!
+//! The standard library provides no type representing an IP network, so there is no `Deserialize`
+//! implementation to rely on for arguments like `10.0.0.0/24`. This module provides the parsing
+//! as a shim that can be used with any type implementing `From<(IpAddr, u8)>`, allowing users to
+//! bring their own network type.
+//!
+//! The shim is most easily applied to a field using the `#[serde_args(cidr)]` field attribute
+//! with the [`#[generate]`](crate::generate) macro:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! use std::net::IpAddr;
+//!
+//! struct Network {
+//!     address: IpAddr,
+//!     prefix_length: u8,
+//! }
+//!
+//! impl From<(IpAddr, u8)> for Network {
+//!     fn from((address, prefix_length): (IpAddr, u8)) -> Self {
+//!         Self {
+//!             address,
+//!             prefix_length,
+//!         }
+//!     }
+//! }
+//!
+//! #[serde_args::generate]
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde_args(cidr)]
+//!     network: Network,
+//! }
+//! ```
+//!
+//! Without the macro, the shim can be used directly through `serde`'s
+//! [`deserialize_with`](https://serde.rs/field-attrs.html#deserialize_with) attribute:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! # use std::net::IpAddr;
+//! #
+//! # struct Network;
+//! #
+//! # impl From<(IpAddr, u8)> for Network {
+//! #     fn from(_: (IpAddr, u8)) -> Self {
+//! #         Self
+//! #     }
+//! # }
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde(deserialize_with = "serde_args::helpers::cidr::deserialize")]
+//!     network: Network,
+//! }
+//! ```
+//!
+//! The network is parsed as a single positional value. The address must be a valid IPv4 or IPv6
+//! address, and the prefix length must not exceed the number of bits in the address.
+
+use serde::de::{
+    Deserializer,
+    Error,
+    Unexpected,
+    Visitor,
+};
+use std::{
+    fmt,
+    fmt::Formatter,
+    marker::PhantomData,
+    net::IpAddr,
+    str::FromStr,
+};
+
+/// Deserialize an IP network in CIDR notation into any type implementing `From<(IpAddr, u8)>`.
+///
+/// The value is requested from the deserializer as a string, meaning it is parsed as a single
+/// primitive argument. See the [module documentation](self) for usage examples.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<(IpAddr, u8)>,
+{
+    deserializer.deserialize_str(CidrVisitor(PhantomData))
+}
+
+struct CidrVisitor<T>(PhantomData<T>);
+
+impl<T> Visitor<'_> for CidrVisitor<T>
+where
+    T: From<(IpAddr, u8)>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("an IP network in CIDR notation")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let (address, prefix_length) = value
+            .split_once('/')
+            .ok_or_else(|| E::invalid_value(Unexpected::Other(value), &self))?;
+        let address = IpAddr::from_str(address)
+            .map_err(|_| E::invalid_value(Unexpected::Other(value), &self))?;
+        let prefix_length = u8::from_str(prefix_length)
+            .map_err(|_| E::invalid_value(Unexpected::Other(value), &self))?;
+        let maximum_prefix_length = match address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if prefix_length > maximum_prefix_length {
+            return Err(E::invalid_value(Unexpected::Other(value), &self));
+        }
+
+        Ok(T::from((address, prefix_length)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        de,
+        de::Deserializer,
+        parse::parse,
+        trace::{
+            trace,
+            Field,
+            PrimitiveKind,
+            Shape,
+        },
+    };
+    use claims::{
+        assert_err_eq,
+        assert_ok,
+        assert_ok_eq,
+    };
+    use serde::de::Deserialize;
+    use serde_derive::Deserialize;
+    use std::{
+        marker::PhantomData,
+        net::{
+            IpAddr,
+            Ipv4Addr,
+            Ipv6Addr,
+        },
+    };
+
+    #[derive(Debug, Eq, PartialEq)]
+    struct Network(IpAddr, u8);
+
+    impl From<(IpAddr, u8)> for Network {
+        fn from((address, prefix_length): (IpAddr, u8)) -> Self {
+            Self(address, prefix_length)
+        }
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Args {
+        #[serde(deserialize_with = "super::deserialize")]
+        network: Network,
+    }
+
+    fn deserialize_args(args: &[&str]) -> Result<Args, de::Error> {
+        let mut shape = assert_ok!(trace(PhantomData::<Args>));
+        let context = assert_ok!(parse(args.iter().copied(), &mut shape));
+        Args::deserialize(Deserializer::new(context))
+    }
+
+    #[test]
+    fn trace_cidr() {
+        assert_ok_eq!(
+            trace(PhantomData::<Args>),
+            Shape::Struct {
+                name: "Args",
+                description: "struct Args".into(),
+                version: None,
+                examples: None,
+                required: vec![Field::new(
+                    "network",
+                    Shape::Primitive {
+                        name: "an IP network in CIDR notation".into(),
+                        description: "an IP network in CIDR notation".into(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    0
+                )],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_ipv4() {
+        assert_ok_eq!(
+            deserialize_args(&["10.0.0.0/24"]),
+            Args {
+                network: Network(IpAddr::V4(Ipv4Addr::new(10, 0, 0, 0)), 24),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_ipv6() {
+        assert_ok_eq!(
+            deserialize_args(&["fe80::/10"]),
+            Args {
+                network: Network(IpAddr::V6(Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0)), 10),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_missing_prefix_length() {
+        assert_err_eq!(
+            deserialize_args(&["10.0.0.0"]),
+            de::Error::InvalidValue("10.0.0.0".into(), "an IP network in CIDR notation".into())
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_address() {
+        assert_err_eq!(
+            deserialize_args(&["10.0.0/24"]),
+            de::Error::InvalidValue("10.0.0/24".into(), "an IP network in CIDR notation".into())
+        );
+    }
+
+    #[test]
+    fn deserialize_invalid_prefix_length() {
+        assert_err_eq!(
+            deserialize_args(&["10.0.0.0/foo"]),
+            de::Error::InvalidValue(
+                "10.0.0.0/foo".into(),
+                "an IP network in CIDR notation".into()
+            )
+        );
+    }
+
+    #[test]
+    fn deserialize_ipv4_prefix_length_too_large() {
+        assert_err_eq!(
+            deserialize_args(&["10.0.0.0/33"]),
+            de::Error::InvalidValue(
+                "10.0.0.0/33".into(),
+                "an IP network in CIDR notation".into()
+            )
+        );
+    }
+
+    #[test]
+    fn deserialize_ipv6_prefix_length_maximum() {
+        assert_ok_eq!(
+            deserialize_args(&["::1/128"]),
+            Args {
+                network: Network(IpAddr::V6(Ipv6Addr::LOCALHOST), 128),
+            }
+        );
+    }
+}
@@ -0,0 +1,80 @@
+//! Deserialization of paths that must exist.
+//!
+//! When a path is given, it is checked to refer to an existing file or directory. If it does not,
+//! deserialization fails with an error naming both the argument and the path.
+//!
+//! The check is most easily applied to a field using the `#[serde_args(exists)]` field attribute
+//! with the [`#[generate]`](crate::generate) macro, which supports both required and optional
+//! fields:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! use std::path::PathBuf;
+//!
+//! #[serde_args::generate]
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde_args(exists)]
+//!     input: PathBuf,
+//!     #[serde_args(exists)]
+//!     config: Option<PathBuf>,
+//! }
+//! ```
+//!
+//! Without the macro, the check can be requested directly through `serde`'s
+//! [`deserialize_with`](https://serde.rs/field-attrs.html#deserialize_with) attribute, using
+//! [`deserialize_option()`] for optional fields:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! # use std::path::PathBuf;
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde(deserialize_with = "serde_args::helpers::exists::deserialize")]
+//!     input: PathBuf,
+//!     #[serde(deserialize_with = "serde_args::helpers::exists::deserialize_option")]
+//!     config: Option<PathBuf>,
+//! }
+//! ```
+//!
+//! The path is only checked when deserializing command line arguments with `serde_args`. Other
+//! deserializers provide the path unchecked.
+//!
+//! Note that the filesystem may change between the path being checked and it being used, so this
+//! check is a convenience for reporting mistakes early rather than a guarantee. Programs must still
+//! handle the path no longer existing when using it.
+
+use super::path::{
+    self,
+    Constraint,
+};
+use serde::de::Deserializer;
+use std::path::PathBuf;
+
+/// Deserialize a path that must exist into any type implementing `From<PathBuf>`.
+///
+/// See the [module documentation](self) for usage examples.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<PathBuf>,
+{
+    path::deserialize(deserializer, Constraint::Exists).map(T::from)
+}
+
+/// Deserialize an optional path that must exist if it is given.
+///
+/// See the [module documentation](self) for usage examples.
+pub fn deserialize_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<PathBuf>,
+{
+    path::deserialize_option(deserializer, Constraint::Exists).map(|path| path.map(T::from))
+}
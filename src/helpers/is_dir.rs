@@ -0,0 +1,80 @@
+//! Deserialization of paths that must refer to a directory.
+//!
+//! When a path is given, it is checked to refer to an existing directory, following symbolic links.
+//! If it does not, deserialization fails with an error naming both the argument and the path.
+//!
+//! The check is most easily applied to a field using the `#[serde_args(is_dir)]` field attribute
+//! with the [`#[generate]`](crate::generate) macro, which supports both required and optional
+//! fields:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! use std::path::PathBuf;
+//!
+//! #[serde_args::generate]
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde_args(is_dir)]
+//!     output: PathBuf,
+//!     #[serde_args(is_dir)]
+//!     cache: Option<PathBuf>,
+//! }
+//! ```
+//!
+//! Without the macro, the check can be requested directly through `serde`'s
+//! [`deserialize_with`](https://serde.rs/field-attrs.html#deserialize_with) attribute, using
+//! [`deserialize_option()`] for optional fields:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! # use std::path::PathBuf;
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde(deserialize_with = "serde_args::helpers::is_dir::deserialize")]
+//!     output: PathBuf,
+//!     #[serde(deserialize_with = "serde_args::helpers::is_dir::deserialize_option")]
+//!     cache: Option<PathBuf>,
+//! }
+//! ```
+//!
+//! The path is only checked when deserializing command line arguments with `serde_args`. Other
+//! deserializers provide the path unchecked.
+//!
+//! Note that the filesystem may change between the path being checked and it being used, so this
+//! check is a convenience for reporting mistakes early rather than a guarantee. Programs must still
+//! handle the path no longer being a directory when using it.
+
+use super::path::{
+    self,
+    Constraint,
+};
+use serde::de::Deserializer;
+use std::path::PathBuf;
+
+/// Deserialize a path that must refer to a directory into any type implementing `From<PathBuf>`.
+///
+/// See the [module documentation](self) for usage examples.
+pub fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<PathBuf>,
+{
+    path::deserialize(deserializer, Constraint::IsDir).map(T::from)
+}
+
+/// Deserialize an optional path that must refer to a directory if it is given.
+///
+/// See the [module documentation](self) for usage examples.
+pub fn deserialize_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: From<PathBuf>,
+{
+    path::deserialize_option(deserializer, Constraint::IsDir).map(|path| path.map(T::from))
+}
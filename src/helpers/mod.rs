@@ -0,0 +1,14 @@
+//! Helpers for deserializing common command line values.
+//!
+//! Each helper is a module providing a `deserialize()` function, which can be used with `serde`'s
+//! [`deserialize_with`](https://serde.rs/field-attrs.html#deserialize_with) attribute. Most
+//! helpers can also be applied using a `#[serde_args(...)]` field attribute with the
+//! [`#[generate]`](crate::generate) macro.
+
+pub mod cidr;
+pub mod exists;
+pub mod is_dir;
+pub mod is_file;
+pub mod os_strings;
+
+pub(crate) mod path;
@@ -0,0 +1,354 @@
+//! Deserialization of arguments as [`OsString`]s.
+//!
+//! `OsString`'s own `Deserialize` implementation expects a platform-specific enum, which cannot be
+//! provided by a single argument. This module instead deserializes a sequence of arguments into a
+//! `Vec<OsString>`, keeping arguments that are not valid UTF-8 (or, on Windows, not valid UTF-16)
+//! intact on Unix and Windows platforms.
+//!
+//! This is most often used to capture the remaining arguments verbatim, as done by the
+//! `#[serde_args(trailing)]` field attribute with the [`#[generate]`](crate::generate) macro:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! use std::ffi::OsString;
+//!
+//! #[serde_args::generate]
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     program: String,
+//!     #[serde_args(trailing)]
+//!     args: Vec<OsString>,
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! Without the macro, the shim can be used directly through `serde`'s
+//! [`deserialize_with`](https://serde.rs/field-attrs.html#deserialize_with) attribute. In that
+//! case, the values are collected as a regular sequence, meaning options are still parsed among
+//! them. See the [specification](crate::specification) for how to capture them verbatim instead.
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! # use std::ffi::OsString;
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde(deserialize_with = "serde_args::helpers::os_strings::deserialize")]
+//!     paths: Vec<OsString>,
+//! }
+//! #
+//! # fn main() {}
+//! ```
+
+use serde::de::{
+    DeserializeSeed,
+    Deserializer,
+    Error,
+    SeqAccess,
+    Visitor,
+};
+use std::{
+    ffi::OsString,
+    fmt,
+    fmt::Formatter,
+};
+
+/// Deserialize a sequence of arguments into a `Vec<OsString>`.
+///
+/// Each argument is requested from the deserializer as bytes. See the
+/// [module documentation](self) for usage examples.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<OsString>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_seq(OsStringsVisitor)
+}
+
+struct OsStringsVisitor;
+
+impl<'de> Visitor<'de> for OsStringsVisitor {
+    type Value = Vec<OsString>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("a sequence of arguments")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element_seed(OsStringSeed)? {
+            values.push(value);
+        }
+        Ok(values)
+    }
+}
+
+struct OsStringSeed;
+
+impl<'de> DeserializeSeed<'de> for OsStringSeed {
+    type Value = OsString;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(OsStringVisitor)
+    }
+}
+
+struct OsStringVisitor;
+
+impl Visitor<'_> for OsStringVisitor {
+    type Value = OsString;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("an argument")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(value.into())
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        self.visit_byte_buf(value.to_vec())
+    }
+
+    #[cfg(unix)]
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        use std::os::unix::ffi::OsStringExt;
+
+        Ok(OsString::from_vec(value))
+    }
+
+    #[cfg(windows)]
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        use std::os::windows::ffi::OsStringExt;
+
+        Ok(match decode_wtf8(&value) {
+            Some(wide) => OsString::from_wide(&wide),
+            None => String::from_utf8_lossy(&value).into_owned().into(),
+        })
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    fn visit_byte_buf<E>(self, value: Vec<u8>) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        // Arguments that are not valid UTF-8 cannot be reconstructed portably.
+        Ok(match String::from_utf8(value) {
+            Ok(value) => value.into(),
+            Err(error) => String::from_utf8_lossy(error.as_bytes())
+                .into_owned()
+                .into(),
+        })
+    }
+}
+
+/// Decodes the bytes of an argument into UTF-16 code units.
+///
+/// On Windows, arguments are provided as WTF-8, which encodes unpaired surrogates in the same way
+/// as any other code point. Returns `None` if `bytes` is not well-formed.
+#[cfg(any(windows, test))]
+fn decode_wtf8(bytes: &[u8]) -> Option<Vec<u16>> {
+    let mut wide = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let first = bytes[index];
+        let (len, min, initial) = match first {
+            0x00..=0x7f => (1, 0, u32::from(first)),
+            0xc0..=0xdf => (2, 0x80, u32::from(first & 0x1f)),
+            0xe0..=0xef => (3, 0x800, u32::from(first & 0x0f)),
+            0xf0..=0xf7 => (4, 0x10000, u32::from(first & 0x07)),
+            _ => return None,
+        };
+        let code_point = bytes.get((index + 1)..(index + len))?.iter().try_fold(
+            initial,
+            |code_point, &byte| {
+                (byte & 0xc0 == 0x80).then(|| (code_point << 6) | u32::from(byte & 0x3f))
+            },
+        )?;
+        if code_point < min || code_point > 0x10ffff {
+            // Overlong encodings and values outside of Unicode's range are never produced.
+            return None;
+        }
+        match char::from_u32(code_point) {
+            Some(character) => wide.extend_from_slice(character.encode_utf16(&mut [0; 2])),
+            // Surrogates are the only code points that are not `char`s.
+            None => wide.push(code_point as u16),
+        }
+        index += len;
+    }
+    Some(wide)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_wtf8;
+    use crate::{
+        de,
+        de::Deserializer,
+        parse::parse,
+        trace::{
+            trace,
+            Field,
+            PrimitiveKind,
+            Shape,
+        },
+    };
+    use claims::{
+        assert_none,
+        assert_ok,
+        assert_ok_eq,
+        assert_some_eq,
+    };
+    use serde::de::Deserialize;
+    use serde_derive::Deserialize;
+    use std::{
+        ffi::OsString,
+        marker::PhantomData,
+    };
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Args {
+        #[serde(deserialize_with = "super::deserialize")]
+        values: Vec<OsString>,
+    }
+
+    fn deserialize_args<Arg>(args: Vec<Arg>) -> Result<Args, de::Error>
+    where
+        Arg: Into<OsString>,
+    {
+        let mut shape = assert_ok!(trace(PhantomData::<Args>));
+        let context = assert_ok!(parse(
+            args.into_iter().map(Into::<OsString>::into),
+            &mut shape
+        ));
+        Args::deserialize(Deserializer::new(context))
+    }
+
+    #[test]
+    fn trace_os_strings() {
+        assert_ok_eq!(
+            trace(PhantomData::<Args>),
+            Shape::Struct {
+                name: "Args",
+                description: "struct Args".into(),
+                version: None,
+                examples: None,
+                required: vec![Field::new(
+                    "values",
+                    Shape::Sequence(Box::new(Shape::Primitive {
+                        name: "an argument".into(),
+                        description: "an argument".into(),
+                        version: None,
+                        kind: PrimitiveKind::Bytes,
+                    })),
+                    0
+                )],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_empty() {
+        assert_ok_eq!(
+            deserialize_args(Vec::<&str>::new()),
+            Args { values: vec![] }
+        );
+    }
+
+    #[test]
+    fn deserialize_values() {
+        assert_ok_eq!(
+            deserialize_args(vec!["foo", "bar"]),
+            Args {
+                values: vec!["foo".into(), "bar".into()]
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn deserialize_unpaired_surrogate() {
+        use std::os::windows::ffi::OsStringExt;
+
+        assert_ok_eq!(
+            deserialize_args(vec![OsString::from_wide(&[0x66, 0x6f, 0x6f, 0xd800])]),
+            Args {
+                values: vec![OsString::from_wide(&[0x66, 0x6f, 0x6f, 0xd800])]
+            }
+        );
+    }
+
+    #[test]
+    fn decode_wtf8_ascii() {
+        assert_some_eq!(decode_wtf8(b"foo"), vec![0x66, 0x6f, 0x6f]);
+    }
+
+    #[test]
+    fn decode_wtf8_multibyte() {
+        assert_some_eq!(decode_wtf8("ã€".as_bytes()), vec![0xe3, 0x20ac]);
+    }
+
+    #[test]
+    fn decode_wtf8_supplementary() {
+        assert_some_eq!(decode_wtf8("🦀".as_bytes()), vec![0xd83e, 0xdd80]);
+    }
+
+    #[test]
+    fn decode_wtf8_unpaired_surrogate() {
+        assert_some_eq!(decode_wtf8(b"-\xed\xa0\x80"), vec![0x2d, 0xd800]);
+    }
+
+    #[test]
+    fn decode_wtf8_invalid_byte() {
+        assert_none!(decode_wtf8(b"foo\xff"));
+    }
+
+    #[test]
+    fn decode_wtf8_truncated() {
+        assert_none!(decode_wtf8(b"foo\xe2\x82"));
+    }
+
+    #[test]
+    fn decode_wtf8_overlong() {
+        assert_none!(decode_wtf8(b"\xc0\xaf"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn deserialize_non_utf8() {
+        use std::os::unix::ffi::OsStringExt;
+
+        assert_ok_eq!(
+            deserialize_args(vec![OsString::from_vec(b"foo\xff".to_vec())]),
+            Args {
+                values: vec![OsString::from_vec(b"foo\xff".to_vec())]
+            }
+        );
+    }
+}
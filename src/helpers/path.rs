@@ -0,0 +1,294 @@
+//! Shared deserialization of paths that must satisfy a constraint on the filesystem.
+//!
+//! Paths are requested from the deserializer as a newtype struct whose name identifies the
+//! constraint. The `serde_args` deserializer recognizes these names and checks the path on the
+//! filesystem before providing it, allowing the error to name the argument the path was given
+//! for. Other deserializers simply provide the path without checking it.
+
+use serde::de::{
+    Deserialize,
+    Deserializer,
+    Error,
+    Visitor,
+};
+use std::{
+    fmt,
+    fmt::Formatter,
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+};
+
+/// A constraint on what a path must refer to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum Constraint {
+    Exists,
+    IsFile,
+    IsDir,
+}
+
+impl Constraint {
+    /// The name of the newtype struct the path is requested as.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Exists => "serde_args::helpers::exists",
+            Self::IsFile => "serde_args::helpers::is_file",
+            Self::IsDir => "serde_args::helpers::is_dir",
+        }
+    }
+
+    /// Returns the constraint requested by a newtype struct named `name`, if any.
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        [Self::Exists, Self::IsFile, Self::IsDir]
+            .into_iter()
+            .find(|constraint| constraint.name() == name)
+    }
+
+    /// Checks that `path` satisfies this constraint, returning the reason it does not otherwise.
+    pub(crate) fn check(self, path: &Path) -> Result<(), &'static str> {
+        let metadata = fs::metadata(path).map_err(|_| "does not exist")?;
+        match self {
+            Self::Exists => Ok(()),
+            Self::IsFile if metadata.is_file() => Ok(()),
+            Self::IsFile => Err("is not a file"),
+            Self::IsDir if metadata.is_dir() => Ok(()),
+            Self::IsDir => Err("is not a directory"),
+        }
+    }
+}
+
+/// Deserialize a path, requesting that it be checked against `constraint`.
+pub(super) fn deserialize<'de, D>(
+    deserializer: D,
+    constraint: Constraint,
+) -> Result<PathBuf, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_newtype_struct(constraint.name(), PathVisitor(constraint))
+}
+
+/// Deserialize an optional path, requesting that it be checked against `constraint` if given.
+pub(super) fn deserialize_option<'de, D>(
+    deserializer: D,
+    constraint: Constraint,
+) -> Result<Option<PathBuf>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_option(OptionVisitor(constraint))
+}
+
+struct PathVisitor(Constraint);
+
+impl<'de> Visitor<'de> for PathVisitor {
+    type Value = PathBuf;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str(match self.0 {
+            Constraint::Exists => "an existing path",
+            Constraint::IsFile => "a file",
+            Constraint::IsDir => "a directory",
+        })
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        PathBuf::deserialize(deserializer)
+    }
+}
+
+struct OptionVisitor(Constraint);
+
+impl<'de> Visitor<'de> for OptionVisitor {
+    type Value = Option<PathBuf>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+        PathVisitor(self.0).expecting(formatter)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer, self.0).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        de,
+        de::Deserializer,
+        parse::parse,
+        trace::trace,
+    };
+    use claims::{
+        assert_err_eq,
+        assert_ok,
+        assert_ok_eq,
+    };
+    use serde::de::Deserialize;
+    use serde_derive::Deserialize;
+    use std::{
+        env,
+        fs,
+        marker::PhantomData,
+        path::PathBuf,
+        process,
+    };
+
+    /// A directory containing a file named `file` and a directory named `dir`, removed when
+    /// dropped.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = env::temp_dir().join(format!("serde_args-{}-{}", name, process::id()));
+            assert_ok!(fs::create_dir_all(path.join("dir")));
+            assert_ok!(fs::write(path.join("file"), ""));
+            Self(path)
+        }
+
+        fn join(&self, name: &str) -> String {
+            self.0.join(name).to_string_lossy().into_owned()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Args {
+        #[serde(deserialize_with = "crate::helpers::exists::deserialize")]
+        path: PathBuf,
+        #[serde(deserialize_with = "crate::helpers::is_file::deserialize_option")]
+        file: Option<PathBuf>,
+        #[serde(deserialize_with = "crate::helpers::is_dir::deserialize_option")]
+        dir: Option<PathBuf>,
+    }
+
+    fn deserialize_args(args: &[&str]) -> Result<Args, de::Error> {
+        let mut shape = assert_ok!(trace(PhantomData::<Args>));
+        let context = assert_ok!(parse(args.iter().copied(), &mut shape));
+        Args::deserialize(Deserializer::new(context))
+    }
+
+    #[test]
+    fn exists_file() {
+        let temp = TempDir::new("exists_file");
+
+        assert_ok_eq!(
+            deserialize_args(&[&temp.join("file")]),
+            Args {
+                path: temp.join("file").into(),
+                file: None,
+                dir: None,
+            }
+        );
+    }
+
+    #[test]
+    fn exists_dir() {
+        let temp = TempDir::new("exists_dir");
+
+        assert_ok_eq!(
+            deserialize_args(&[&temp.join("dir")]),
+            Args {
+                path: temp.join("dir").into(),
+                file: None,
+                dir: None,
+            }
+        );
+    }
+
+    #[test]
+    fn exists_missing() {
+        let temp = TempDir::new("exists_missing");
+
+        assert_err_eq!(
+            deserialize_args(&[&temp.join("missing")]),
+            de::Error::Path {
+                field: Some("<path>".into()),
+                path: temp.join("missing"),
+                reason: "does not exist",
+            }
+        );
+    }
+
+    #[test]
+    fn is_file_and_is_dir() {
+        let temp = TempDir::new("is_file_and_is_dir");
+
+        assert_ok_eq!(
+            deserialize_args(&[
+                &temp.join("dir"),
+                "--file",
+                &temp.join("file"),
+                "--dir",
+                &temp.join("dir"),
+            ]),
+            Args {
+                path: temp.join("dir").into(),
+                file: Some(temp.join("file").into()),
+                dir: Some(temp.join("dir").into()),
+            }
+        );
+    }
+
+    #[test]
+    fn is_file_missing() {
+        let temp = TempDir::new("is_file_missing");
+
+        assert_err_eq!(
+            deserialize_args(&[&temp.join("dir"), "--file", &temp.join("missing")]),
+            de::Error::Path {
+                field: Some("--file".into()),
+                path: temp.join("missing"),
+                reason: "does not exist",
+            }
+        );
+    }
+
+    #[test]
+    fn is_file_dir() {
+        let temp = TempDir::new("is_file_dir");
+
+        assert_err_eq!(
+            deserialize_args(&[&temp.join("dir"), "--file", &temp.join("dir")]),
+            de::Error::Path {
+                field: Some("--file".into()),
+                path: temp.join("dir"),
+                reason: "is not a file",
+            }
+        );
+    }
+
+    #[test]
+    fn is_dir_file() {
+        let temp = TempDir::new("is_dir_file");
+
+        assert_err_eq!(
+            deserialize_args(&[&temp.join("dir"), "--dir", &temp.join("file")]),
+            de::Error::Path {
+                field: Some("--dir".into()),
+                path: temp.join("file"),
+                reason: "is not a directory",
+            }
+        );
+    }
+}
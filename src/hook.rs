@@ -0,0 +1,261 @@
+//! Hooks run on the parsed arguments before they are deserialized.
+
+use crate::{
+    check::check,
+    de,
+    parse::{
+        Context,
+        Segment,
+    },
+    trace::{
+        Field,
+        Shape,
+    },
+};
+use std::{
+    borrow::Cow,
+    fmt,
+    fmt::{
+        Debug,
+        Display,
+        Formatter,
+    },
+    iter,
+    sync::Arc,
+};
+
+/// The signature of a hook run on the parsed arguments.
+type Hook = dyn Fn(&mut ContextView) -> Result<(), String> + Send + Sync;
+
+/// A callback registered with [`Parser::post_parse()`](crate::Parser::post_parse()).
+#[derive(Clone)]
+pub(crate) struct PostParse(pub(crate) Arc<Hook>);
+
+impl Debug for PostParse {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.write_str("PostParse(..)")
+    }
+}
+
+/// An invalid injection made through [`ContextView::insert()`].
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum InjectionError {
+    /// No option with the given name exists.
+    UnknownOption(String),
+    /// The option does not take a single value.
+    NotSingleValue(String),
+    /// The value is not valid for the option.
+    InvalidValue { name: String, error: de::Error },
+}
+
+impl Display for InjectionError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownOption(name) => write!(
+                formatter,
+                "cannot inject a value for --{}: no such option exists",
+                name
+            ),
+            Self::NotSingleValue(name) => write!(
+                formatter,
+                "cannot inject a value for --{}: the option does not take a single value",
+                name
+            ),
+            Self::InvalidValue { name, error } => {
+                write!(formatter, "cannot inject a value for --{}: {}", name, error)
+            }
+        }
+    }
+}
+
+/// The kinds of fields a struct can contain.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum FieldKind {
+    Required,
+    Optional,
+    Boolean,
+}
+
+/// A limited view of the parsed arguments, passed to a hook registered with
+/// [`Parser::post_parse()`](crate::Parser::post_parse()).
+///
+/// The view covers the fields of the top-level struct, or of the selected command's struct if the
+/// top-level type is an enum. Fields are named as they are declared, after any renaming; aliases
+/// given on the command line are resolved to the same field.
+pub struct ContextView<'a> {
+    segments: &'a mut [Segment],
+    shape: Option<&'a Shape>,
+    invalid: Option<InjectionError>,
+}
+
+impl<'a> ContextView<'a> {
+    pub(crate) fn new(context: &'a mut Context, shape: &'a Shape) -> Self {
+        let (segments, shape) = match shape {
+            Shape::Variant { shape, .. } => (
+                match context.segments.split_first_mut() {
+                    Some((Segment::Identifier(_), segments)) => segments,
+                    _ => &mut [],
+                },
+                shape.as_ref(),
+            ),
+            shape => (context.segments.as_mut_slice(), shape),
+        };
+        Self {
+            segments,
+            shape: matches!(shape, Shape::Struct { .. }).then_some(shape),
+            invalid: None,
+        }
+    }
+
+    /// Returns the first invalid injection, if any were made.
+    pub(crate) fn into_invalid(self) -> Option<InjectionError> {
+        self.invalid
+    }
+
+    /// Finds the field with the given name, along with which kind of field it is.
+    fn find_field(&self, name: &str) -> Option<(&'a Field, FieldKind)> {
+        match self.shape {
+            Some(Shape::Struct {
+                required,
+                optional,
+                booleans,
+                ..
+            }) => required
+                .iter()
+                .map(|field| (field, FieldKind::Required))
+                .chain(optional.iter().map(|field| (field, FieldKind::Optional)))
+                .chain(booleans.iter().map(|field| (field, FieldKind::Boolean)))
+                .find(|(field, _)| field.name == name),
+            _ => None,
+        }
+    }
+
+    /// Finds the segments of the field's context, which begin with the field's identifier.
+    fn field_index(&self, field: &Field) -> Option<usize> {
+        self.segments.iter().position(|segment| match segment {
+            Segment::Context(context) => match context.segments.first() {
+                Some(Segment::Identifier(identifier)) => iter::once(field.name)
+                    .chain(field.aliases.iter().copied())
+                    .any(|name| name == *identifier),
+                _ => false,
+            },
+            _ => false,
+        })
+    }
+
+    fn field_segments(&self, name: &str) -> Option<&Vec<Segment>> {
+        let (field, _) = self.find_field(name)?;
+        match &self.segments[self.field_index(field)?] {
+            Segment::Context(context) => Some(&context.segments),
+            _ => None,
+        }
+    }
+
+    fn field_segments_mut(&mut self, field: &Field) -> Option<&mut Vec<Segment>> {
+        let index = self.field_index(field)?;
+        match &mut self.segments[index] {
+            Segment::Context(context) => Some(&mut context.segments),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the field with the given name was provided.
+    ///
+    /// Required fields are always provided. Optional fields and boolean flags are provided only if
+    /// they were given on the command line, or have been inserted.
+    pub fn contains(&self, name: &str) -> bool {
+        self.field_segments(name)
+            .is_some_and(|segments| segments.len() > 1)
+    }
+
+    /// Returns the value provided for the field with the given name.
+    ///
+    /// Only fields holding a single value have one, such as `String` or `Option<u64>` fields.
+    /// Invalid UTF-8 is replaced with the replacement character.
+    pub fn value(&self, name: &str) -> Option<Cow<'_, str>> {
+        let raw = match self.field_segments(name)?.get(1)? {
+            Segment::Value(raw) => raw,
+            Segment::Context(context) => match context.segments.as_slice() {
+                [Segment::Value(raw)] => raw,
+                _ => return None,
+            },
+            Segment::Identifier(_) => return None,
+        };
+        Some(String::from_utf8_lossy(raw))
+    }
+
+    /// Removes the optional field or boolean flag with the given name, as though it was not
+    /// provided.
+    ///
+    /// Returns whether the field was provided before being removed. Required fields cannot be
+    /// removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let field = match self.find_field(name) {
+            Some((field, FieldKind::Optional | FieldKind::Boolean)) => field,
+            _ => return false,
+        };
+        match self.field_segments_mut(field) {
+            Some(segments) if segments.len() > 1 => {
+                segments.truncate(1);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Provides `value` for the optional field with the given name, replacing any value that was
+    /// given on the command line.
+    ///
+    /// The value is validated against the field's type as though it were given on the command
+    /// line. Inserting into a field that is not an optional field holding a single value, or
+    /// inserting an invalid value, is a mistake in the program rather than in the user's input.
+    /// Such an insertion is ignored, and is reported as an [`Error`](crate::Error) once the hook
+    /// returns.
+    pub fn insert(&mut self, name: &str, value: &str) {
+        let field = match self.find_field(name) {
+            Some((field, FieldKind::Optional)) => field,
+            Some((_, FieldKind::Boolean)) => {
+                self.invalidate(InjectionError::NotSingleValue(name.to_owned()));
+                return;
+            }
+            _ => {
+                self.invalidate(InjectionError::UnknownOption(name.to_owned()));
+                return;
+            }
+        };
+        if !matches!(field.shape, Shape::Primitive { .. } | Shape::Boolean { .. }) {
+            self.invalidate(InjectionError::NotSingleValue(name.to_owned()));
+            return;
+        }
+
+        let value_context = Context {
+            segments: vec![Segment::Value(value.as_bytes().to_vec())],
+        };
+        if let Err(error) = check(&value_context, &field.shape) {
+            self.invalidate(InjectionError::InvalidValue {
+                name: name.to_owned(),
+                error,
+            });
+            return;
+        }
+        // Every field of a parsed struct has its own context, even if it was not provided.
+        if let Some(segments) = self.field_segments_mut(field) {
+            segments.truncate(1);
+            segments.push(Segment::Context(value_context));
+        }
+    }
+
+    fn invalidate(&mut self, error: InjectionError) {
+        if self.invalid.is_none() {
+            self.invalid = Some(error);
+        }
+    }
+}
+
+impl Debug for ContextView<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("ContextView")
+            .finish_non_exhaustive()
+    }
+}
@@ -13,10 +13,11 @@
 //!
 //! `serde_args` defines an unambiguous deserialization format through internal [`Deserializer`]s;
 //! therefore it should be noted that not every command line interface can be represented using it.
-//! Notably, optional positional parameters are not supported at all, nor are default command
-//! values. The format defined here requires that all positional arguments (those *without* a `-`
-//! or `--` preceeding them) be required arguments, and that all other arguments be preceeded with
-//! either a `-` or `--` (including compound types). See the [format specification](specification)
+//! Notably, optional positional parameters are only supported as an explicit opt-in for the last
+//! positional argument, and default command values are not supported at all. The format defined
+//! here requires that all other positional arguments (those *without* a `-` or `--` preceeding
+//! them) be required arguments, and that all other arguments be preceeded with either a `-` or
+//! `--` (including compound types). See the [format specification](specification)
 //! for more details.
 //!
 //! # Parsing Arguments
@@ -90,6 +91,11 @@
 //! }
 //! ```
 //!
+//! Options declared on a struct containing a command, such as a global `--verbose` flag alongside
+//! a `command` field, may be given before the command, between its arguments, or after it.
+//! `prog --verbose add foo`, `prog add --verbose foo`, and `prog add foo --verbose` are all
+//! accepted. See [Global Options](specification#global-options).
+//!
 //! For simple use cases you can also use existing types that
 //! already implement `Deserialize`:
 //!
@@ -106,8 +112,33 @@
 //! }
 //! ```
 //!
-//! Note that the only way to deserialize using this crate is through [`from_env()`] and
-//! [`from_env_seed()`]. No public [`Deserializer`] is provided.
+//! Note that the only way to deserialize using this crate is through the following entry points.
+//! No public [`Deserializer`] is provided.
+//!
+//! - [`from_env()`] and [`from_env_seed()`] read the arguments from the environment, and are what
+//!   most programs should use. [`from_env_with_labels()`] and [`from_env_seed_with_labels()`]
+//!   additionally localize help and error messages.
+//! - [`from_slice()`] and [`from_os_slice()`] read arguments that have already been collected,
+//!   beginning with the path of the executable as [`env::args()`] does. These are useful for
+//!   testing a command line interface against fixed sets of arguments, or for arguments obtained
+//!   from somewhere other than the environment.
+//! - [`try_from_args()`] reads arguments the same way, returning an [`Outcome`] that separates
+//!   requested help and version information from errors.
+//! - [`from_command_line()`] splits a single command line string into arguments.
+//! - [`compile()`] traces a type once, for parsing any number of argument lists.
+//! - [`Parser`] holds the arguments, obtained either from the environment using
+//!   [`Parser::from_env()`] or given without the path of the executable using
+//!   [`Parser::from_args_without_exe()`], and provides further configuration.
+//!
+//! Shell completion of partially typed arguments is provided by [`complete()`] and
+//! [`complete_seed()`], which list the arguments that may follow without deserializing anything.
+//! Completion engines that instead consume a static description of the command line interface can
+//! be given one generated by [`generate_spec()`] or [`generate_spec_seed()`], which can also
+//! describe the traced type as versioned JSON for documentation generators.
+//!
+//! To deserialize into types that borrow from the arguments, such as `&str` or `Cow<str>`, collect
+//! the arguments into a [`Parser`] first and use [`Parser::parse_borrowed()`]. The returned value
+//! may then borrow from the `Parser` instead of allocating.
 //!
 //! # Error Formatting
 //!
@@ -179,10 +210,78 @@
 //! }
 //! ```
 //!
+//! Commands of an enum can be grouped under separate headings in the help message by starting a
+//! variant's doc comment with a group tag, such as `[Repository]`. The tag is removed from the
+//! variant's description. Variants without a tag are listed under the default heading:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//!
+//! /// An example program.
+//! #[serde_args::generate(doc_help)]
+//! #[derive(Deserialize)]
+//! #[serde(rename_all = "kebab-case")]
+//! enum Command {
+//!     /// [Repository]
+//!     /// Create a new repository.
+//!     Init,
+//!     /// [Changes]
+//!     /// Record changes to the repository.
+//!     Commit,
+//!     /// Display the status of the working tree.
+//!     Status,
+//! }
+//!
+//! fn main() {
+//!     let command: Command = match serde_args::from_env() {
+//!         Ok(command) => command,
+//!         Err(error) => {
+//!             println!("{error}");
+//!             return;
+//!         }
+//!     };
+//!     // Execute your program with `command`...
+//! }
+//! ```
+//!
+//! Invocation examples can be shown at the end of the help message by placing them after an
+//! `# Examples` heading in the container's doc comment. They are displayed verbatim under an
+//! `EXAMPLES` heading, and are not included in the program's description:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//! use std::path::PathBuf;
+//!
+//! /// An example program.
+//! ///
+//! /// # Examples
+//! ///
+//! /// ```text
+//! /// $ example --force file.txt
+//! /// ```
+//! #[serde_args::generate(doc_help)]
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     /// The path to operate on.
+//!     path: PathBuf,
+//!     /// Whether the program's behavior should be forced.
+//!     force: bool,
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
 //! ## Version Information
 //!
 //! To automatically make the version of your crate available through a `--version` flag, use
-//! `#[generate]` with `version` as a parameter:
+//! `#[generate]` with `version` as a parameter. The version is displayed after the program's
+//! name, such as `prog 1.2.3`:
 //!
 //! ```rust
 //! # mod hidden {
@@ -209,11 +308,142 @@
 //! }
 //! ```
 //!
+//! ## Field Shims
+//!
+//! Some common command line values have no `Deserialize` implementation in the standard library.
+//! Individual fields can opt into a parsing shim using the `#[serde_args(...)]` field attribute
+//! when combined with `#[generate]`. Currently, the following shims are available:
+//!
+//! - `cidr` - Parses an IP network in CIDR notation (such as `10.0.0.0/24`) into any type
+//!   implementing `From<(IpAddr, u8)>`. See the [`helpers::cidr`] module for more details.
+//! - `exists`, `is_file`, and `is_dir` - Checks that a path refers to an existing path, file, or
+//!   directory while parsing, reporting the argument it was given for otherwise. These apply to
+//!   `PathBuf` and `Option<PathBuf>` fields. See the [`helpers::exists`] module for more details.
+//! - `trailing` - Captures every remaining argument verbatim into a `Vec<OsString>`, for programs
+//!   that pass arguments through to another program. Options are recognized until the field's first
+//!   value, so `wrapper --verbose cargo build --release` passes `build --release` through while
+//!   still enabling `--verbose`. An unrecognized option also begins the captured arguments. See the
+//!   [`helpers::os_strings`] module for more details.
+//!
+//! Fields of structs can also be annotated with `#[serde_args(allow_hyphen_values)]`, allowing a
+//! required field to accept values beginning with a hyphen, such as search patterns:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//!
+//! #[serde_args::generate]
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     #[serde_args(allow_hyphen_values)]
+//!     pattern: String,
+//! }
+//!
+//! fn main() {
+//!     let args: Args = match serde_args::from_env() {
+//!         Ok(args) => args,
+//!         Err(error) => {
+//!             println!("{error}");
+//!             return;
+//!         }
+//!     };
+//!     // Execute your program with `args`...
+//! }
+//! ```
+//!
+//! With this, `grep -v` is parsed with `-v` as the pattern. Values matching one of the struct's
+//! own options are still parsed as options. This is not needed for negative numbers, which are
+//! always accepted as the values of numeric fields.
+//!
+//! Required fields are displayed using their field names by default. A different name can be
+//! displayed in usage and error messages using `#[serde_args(value_name = "...")]`, so that a
+//! field named `input` can be shown as `<FILE>`.
+//!
+//! An `Option<T>` field is parsed as an option by default. If it is the last positional argument,
+//! it can instead be parsed as an optional positional argument using `#[serde_args(positional)]`:
+//!
+//! ``` rust
+//! # mod hidden {
+//! use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//!
+//! #[serde_args::generate]
+//! #[derive(Deserialize)]
+//! struct Args {
+//!     repository: String,
+//!     #[serde_args(positional)]
+//!     directory: Option<String>,
+//! }
+//! #
+//! # fn main() {}
+//! ```
+//!
+//! With this, both `prog foo` and `prog foo bar` are accepted, setting `directory` to `None` and
+//! `Some("bar")` respectively.
+//!
+//! Conversely, an `Option<T>` field that is parsed as an option can be made mandatory using
+//! `#[serde_args(required)]`. Parsing fails with `missing required option: '--output'` if the
+//! option is not given, and the option is displayed alongside the positional arguments in usage
+//! messages, such as `prog [options] --output <file> <input>`. This is useful when the field must
+//! remain an `Option` for other reasons.
+//!
+//! An `Option<Vec<T>>` field takes a single value per occurrence of its option, and the option
+//! can be repeated to give more values, so `--tags a --tags b` collects both `a` and `b`. Any
+//! following value is left for the positional arguments. Using
+//! `#[serde_args(value_delimiter = ',')]`, each value is also split on the given character, so
+//! `--tags a,b --tags c` gives the same values as `--tags a,b,c`. Empty values, such as in
+//! `--tags a,,b` or `--tags a,b,`, are rejected.
+//!
+//! To take several values per occurrence instead, annotate the field with
+//! `#[serde_args(multiple)]`. The option then takes every value up to the next option, `--`, or the
+//! end of the arguments, so `--tags a b --tags c` collects `a`, `b`, and `c`. A positional
+//! argument following the values must be preceded by `--` or another option. This is only
+//! supported on `Option<Vec<T>>` struct fields of primitive values, and cannot be combined with
+//! `value_delimiter`.
+//!
+//! ## Types From Other Crates
+//!
+//! Types such as `url::Url`, `uuid::Uuid`, and `chrono::DateTime` are displayed as strings by
+//! default. Enabling the `url`, `uuid`, or `chrono` features displays them as `<url>`, `<uuid>`,
+//! and `<datetime>` instead, and reports invalid values along with the reason they could not be
+//! parsed. These features do not depend on the crates themselves.
+//!
+//! ## Values Accepting Several Forms
+//!
+//! Some enums accept their variants either by name or by numeric code. A `Deserialize`
+//! implementation for such an enum can use
+//! [`deserialize_any()`](serde::Deserializer::deserialize_any), in which case the value is
+//! accepted as-is while parsing and is only validated during deserialization. Values that can be
+//! parsed as numbers or as `true` or `false` are visited as such, and all other values are
+//! visited as strings.
+//!
+//! The names of the variants are found by visiting a string that is not one of them, so the
+//! visitor must reject unknown names using
+//! [`unknown_variant()`](serde::de::Error::unknown_variant). Help messages list these names as the
+//! accepted values, although the numeric codes are not known and should be described in the field's
+//! documentation instead. Any other use of `deserialize_any()` is not supported.
+//!
+//! ## Localized Help Messages
+//!
+//! The headings and descriptions that `serde_args` writes into help and error messages are in
+//! English by default. These can be replaced by providing [`HelpLabels`] to
+//! [`from_env_with_labels()`] or [`from_env_seed_with_labels()`].
+//!
+//! The most common error messages, such as for missing arguments and unrecognized options, are
+//! included as templates, with placeholders like `{name}` replaced by the values they describe.
+//!
 //! ## Customization Without Deriving
 //!
 //! To provide these customization options without deriving, see
 //! [`expecting()` Option Specification](specification/index.html#expecting-option-specification).
 //!
+//! Types defined in other crates cannot be annotated at all. Descriptions and version information
+//! can instead be attached to them at runtime by giving [`Metadata`] to
+//! [`Parser::with_metadata()`].
+//!
 //! # Supported `serde` Attributes
 //!
 //! Nearly all `serde` attributes are supported. Those that are not supported are those that
@@ -233,6 +463,10 @@
 //!
 //! - [`#[serde(alias)]`](https://serde.rs/field-attrs.html#alias) - Useful for defining multiple
 //!   names for optional fields or command variants.
+//!   - Single-character names are given as short options, such as `-f`. Short flags can be grouped
+//!     together, such as `-ab`, and a short option taking a value can have it joined directly, such
+//!     as `-ofile` or `-o=file`. Long options can likewise be joined with their values using `=`,
+//!     such as `--output=file`.
 //! - [`#[serde(expecting)]`](https://serde.rs/container-attrs.html#expecting) - Can be used to
 //!   define a description for your program. Whatever is provided here will be output at the top of
 //!   the generated help message.
@@ -242,38 +476,103 @@
 //!   renaming all field names or enum variants to kebab-case, which is common for command-line
 //!   tools.
 //!
+//! [`env::args()`]: std::env::args()
 //! [`Deserializer`]: serde::Deserializer
 //! [`Display`]: std::fmt::Display
 
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![allow(clippy::needless_doctest_main)]
 
+pub mod helpers;
+pub mod prelude;
 pub mod specification;
 
+mod check;
+mod compiled;
+mod complete;
 mod de;
 mod error;
+mod hook;
 mod key;
+mod metadata;
+mod outcome;
 mod parse;
+mod parser;
+mod quote;
+mod spec;
+mod split;
 mod trace;
+mod validate;
 
-pub use error::Error;
+pub use compiled::{
+    compile,
+    Compiled,
+};
+pub use complete::{
+    complete,
+    complete_seed,
+    Candidate,
+};
+pub use error::{
+    Error,
+    HelpLabels,
+    OutputStream,
+    Warning,
+};
+pub use hook::ContextView;
+pub use metadata::Metadata;
+pub use outcome::Outcome;
+pub use parser::{
+    ParseOutcome,
+    Parser,
+    SortOrder,
+};
 #[cfg(feature = "macros")]
 #[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+#[doc(inline)]
 pub use serde_args_macros::generate;
+pub use spec::{
+    generate_spec,
+    generate_spec_seed,
+    SpecFormat,
+};
+pub use validate::{
+    assert_valid,
+    assert_valid_seed,
+    validate,
+    validate_seed,
+    ValidationError,
+};
 
-use de::Deserializer;
-use parse::parse;
+use de::{
+    Deserializer,
+    Value,
+};
+use parse::{
+    parse_with_config,
+    Config,
+    RawArg,
+};
+use parser::executable_name;
 use serde::de::{
     Deserialize,
     DeserializeSeed,
 };
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use serde::ser::Serialize;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use split::split;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use std::env;
 use std::{
-    env,
     ffi::OsString,
     marker::PhantomData,
-    path::PathBuf,
 };
-use trace::trace;
+use trace::{
+    trace,
+    trace_with_settings,
+    Shape,
+};
 
 /// Deserialize from [`env::args()`] using a seed.
 ///
@@ -321,30 +620,141 @@ use trace::trace;
 ///     // Execute your program with `value`...
 /// }
 /// ```
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(not(all(target_family = "wasm", target_os = "unknown"))))
+)]
 pub fn from_env_seed<'de, D>(seed: D) -> Result<D::Value, Error>
 where
     D: Copy + DeserializeSeed<'de>,
 {
-    let mut shape = trace(seed)?;
+    from_env_seed_with_labels(seed, HelpLabels::default())
+}
 
-    let mut args = env::args_os();
-    let executable_path: OsString = {
-        let path_str = args.next().expect("could not obtain binary name");
-        let path_buf = PathBuf::from(&path_str);
-        if let Some(file_name) = path_buf.file_name() {
-            file_name.to_owned()
-        } else {
-            path_str
-        }
-    };
+/// Deserialize from [`env::args()`] using a seed, displaying messages with the given labels.
+///
+/// This function behaves the same as [`from_env_seed()`], except that any help or error messages
+/// are displayed using the provided [`HelpLabels`] in place of the default English labels.
+///
+/// # Example
+///
+/// This example reads an integer from the command line and adds it to the seed value, displaying
+/// messages with French headings.
+///
+/// ``` rust
+/// use serde::de::{
+///     Deserialize,
+///     DeserializeSeed,
+///     Deserializer,
+/// };
+/// use serde_args::HelpLabels;
+///
+/// #[derive(Clone, Copy)]
+/// struct Seed(u32);
+///
+/// impl<'de> DeserializeSeed<'de> for Seed {
+///     type Value = u32;
+///
+///     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+///     where
+///         D: Deserializer<'de>,
+///     {
+///         u32::deserialize(deserializer).map(|value| value + self.0)
+///     }
+/// }
+///
+/// fn main() {
+///     let labels = HelpLabels {
+///         usage: "UTILISATION".into(),
+///         ..Default::default()
+///     };
+///     let value = match serde_args::from_env_seed_with_labels(Seed(42), labels) {
+///         Ok(value) => value,
+///         Err(error) => {
+///             println!("{error}");
+///             return;
+///         }
+///     };
+///     // Execute your program with `value`...
+/// }
+/// ```
+///
+/// [`env::args()`]: std::env::args()
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(not(all(target_family = "wasm", target_os = "unknown"))))
+)]
+pub fn from_env_seed_with_labels<'de, D>(seed: D, labels: HelpLabels) -> Result<D::Value, Error>
+where
+    D: Copy + DeserializeSeed<'de>,
+{
+    deserialize_args(seed, env::args_os(), labels)
+}
 
-    let context = match parse(args, &mut shape) {
+/// Deserialize from the given arguments using a seed.
+///
+/// The first argument is the path of the executable, as with [`env::args_os()`].
+fn deserialize_args<'a, 'de, D, Arg, Args>(
+    seed: D,
+    args: Args,
+    labels: HelpLabels,
+) -> Result<D::Value, Error>
+where
+    D: Copy + DeserializeSeed<'de>,
+    Args: IntoIterator<Item = Arg>,
+    Arg: RawArg<'a>,
+{
+    let (shape, settings) = trace_with_settings(seed)?;
+    deserialize_args_with_shape(
+        seed,
+        shape,
+        Config::from_settings(settings),
+        args,
+        labels,
+        None,
+    )
+}
+
+/// Deserialize from `args` using a shape that has already been traced from `seed`.
+///
+/// Any fields not given in `args` are taken from `defaults`, if provided.
+fn deserialize_args_with_shape<'a, 'de, D, Arg, Args>(
+    seed: D,
+    mut shape: Shape,
+    config: Config,
+    args: Args,
+    labels: HelpLabels,
+    defaults: Option<Value>,
+) -> Result<D::Value, Error>
+where
+    D: DeserializeSeed<'de>,
+    Args: IntoIterator<Item = Arg>,
+    Arg: RawArg<'a>,
+{
+    let mut args = args.into_iter();
+    let executable_path = executable_name(
+        args.next()
+            .expect("could not obtain binary name")
+            .into_os_string(),
+    );
+
+    let context = match parse_with_config(args, &mut shape, config) {
         Ok(context) => context,
-        Err(error) => return Err(Error::from_parsing_error(error, executable_path, shape)),
+        Err(error) => {
+            return Err(
+                Error::from_parsing_error(error, executable_path, shape, labels)
+                    .with_auto_help(config.auto_help),
+            )
+        }
     };
 
-    seed.deserialize(Deserializer::new(context))
-        .map_err(|error| Error::from_deserializing_error(error, executable_path, shape))
+    seed.deserialize(Deserializer::with_defaults(context, defaults))
+        .map_err(|error| {
+            Error::from_deserializing_error(error, executable_path, shape, labels)
+                .with_auto_help(config.auto_help)
+        })
 }
 
 /// Deserialize from [`env::args()`].
@@ -370,9 +780,315 @@ where
 /// ```
 ///
 /// [`env::args()`]: std::env::args()
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(not(all(target_family = "wasm", target_os = "unknown"))))
+)]
 pub fn from_env<'de, D>() -> Result<D, Error>
 where
     D: Deserialize<'de>,
 {
     from_env_seed(PhantomData::<D>)
 }
+
+/// Deserialize from [`env::args()`], displaying messages with the given labels.
+///
+/// This function behaves the same as [`from_env()`], except that any help or error messages are
+/// displayed using the provided [`HelpLabels`] in place of the default English labels.
+///
+/// # Example
+///
+/// This example reads a string from the command line, displaying messages with French headings.
+///
+/// ``` rust
+/// use serde_args::HelpLabels;
+///
+/// fn main() {
+///     let labels = HelpLabels {
+///         usage: "UTILISATION".into(),
+///         global_options: "Options globales".into(),
+///         override_options: "Options prioritaires".into(),
+///         help_description: "Afficher ce message.".into(),
+///         ..Default::default()
+///     };
+///     let value: String = match serde_args::from_env_with_labels(labels) {
+///         Ok(value) => value,
+///         Err(error) => {
+///             println!("{error}");
+///             return;
+///         }
+///     };
+///     // Execute your program with `value`...
+/// }
+/// ```
+///
+/// [`env::args()`]: std::env::args()
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(not(all(target_family = "wasm", target_os = "unknown"))))
+)]
+pub fn from_env_with_labels<'de, D>(labels: HelpLabels) -> Result<D, Error>
+where
+    D: Deserialize<'de>,
+{
+    from_env_seed_with_labels(PhantomData::<D>, labels)
+}
+
+/// Deserialize from [`env::args()`], falling back to `defaults` for fields that are not given.
+///
+/// This function behaves the same as [`from_env()`], except that any field of a struct that is
+/// not given on the command line takes its value from the corresponding field of `defaults`
+/// instead. This allows defaults to be loaded from another source, such as a configuration file,
+/// while still allowing them to be overridden on the command line.
+///
+/// Fields are merged one at a time. A field that is given on the command line replaces the
+/// default entirely, except that defaults are still used for any fields of a struct nested within
+/// it. Only options can be missing from the command line, so required arguments are never taken
+/// from `defaults`. Note that a boolean flag can only be set by giving it, so a flag defaulting to
+/// `true` cannot be unset on the command line.
+///
+/// The type must implement [`Serialize`] as well as `Deserialize`, since `defaults` is serialized
+/// to read the value of each of its fields. Deriving both is usually enough. If `defaults` cannot
+/// be serialized, such as when its `Serialize` implementation returns an error, the error is
+/// returned as an [`Error`] without parsing any arguments.
+///
+/// # Example
+///
+/// This example reads a port number from the command line, falling back to a default port.
+///
+/// ``` rust
+/// use serde_derive::{
+///     Deserialize,
+///     Serialize,
+/// };
+///
+/// #[derive(Deserialize, Serialize)]
+/// struct Args {
+///     host: String,
+///     port: Option<u16>,
+/// }
+///
+/// fn main() {
+///     // These would usually be read from a configuration file.
+///     let defaults = Args {
+///         host: "localhost".into(),
+///         port: Some(8080),
+///     };
+///     let args = match serde_args::from_env_with_defaults(defaults) {
+///         Ok(args) => args,
+///         Err(error) => {
+///             println!("{error}");
+///             return;
+///         }
+///     };
+///     // Execute your program with `args`...
+/// }
+/// ```
+///
+/// [`env::args()`]: std::env::args()
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(not(all(target_family = "wasm", target_os = "unknown"))))
+)]
+pub fn from_env_with_defaults<'de, D>(defaults: D) -> Result<D, Error>
+where
+    D: Deserialize<'de> + Serialize,
+{
+    deserialize_args_with_defaults(defaults, env::args_os(), HelpLabels::default())
+}
+
+/// Deserialize from the given arguments, falling back to `defaults` for fields that are not given.
+///
+/// The first argument is the path of the executable, as with [`env::args_os()`].
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+fn deserialize_args_with_defaults<'de, D, Arg, Args>(
+    defaults: D,
+    args: Args,
+    labels: HelpLabels,
+) -> Result<D, Error>
+where
+    D: Deserialize<'de> + Serialize,
+    Args: IntoIterator<Item = Arg>,
+    Arg: Into<OsString>,
+{
+    let (shape, settings) = trace_with_settings(PhantomData::<D>)?;
+    let mut args = args.into_iter().map(Into::into).peekable();
+    let defaults = match Value::from_serialize(&defaults) {
+        Ok(defaults) => defaults,
+        Err(error) => {
+            return Err(Error::from_deserializing_error(
+                de::Error::Custom(error.to_string()),
+                executable_name(args.peek().cloned().expect("could not obtain binary name")),
+                shape,
+                labels,
+            ))
+        }
+    };
+    deserialize_args_with_shape(
+        PhantomData::<D>,
+        shape,
+        Config::from_settings(settings),
+        args,
+        labels,
+        Some(defaults),
+    )
+}
+
+/// Deserialize from a slice of arguments.
+///
+/// This function behaves the same as [`from_env()`], except that the arguments are read from
+/// `args` instead of the environment. As with [`env::args()`], the first argument is the path of
+/// the executable, which is used when displaying help and error messages. This is useful for
+/// testing a command line interface against fixed sets of arguments.
+///
+/// The arguments are borrowed rather than copied, so only the parts of them that are kept as
+/// values are ever allocated.
+///
+/// # Panics
+///
+/// Panics if `args` is empty.
+///
+/// # Example
+///
+/// This example reads a string from a fixed set of arguments.
+///
+/// ``` rust
+/// let value: String = serde_args::from_slice(&["executable", "foo"]).unwrap();
+///
+/// assert_eq!(value, "foo");
+/// ```
+///
+/// [`env::args()`]: std::env::args()
+pub fn from_slice<'de, D>(args: &[&str]) -> Result<D, Error>
+where
+    D: Deserialize<'de>,
+{
+    deserialize_args(
+        PhantomData::<D>,
+        args.iter().copied(),
+        HelpLabels::default(),
+    )
+}
+
+/// Deserialize from a slice of arguments that may not be valid UTF-8.
+///
+/// This function behaves the same as [`from_slice()`], except that the arguments are given as
+/// [`OsString`]s, such as those already collected from [`env::args_os()`]. As with
+/// [`from_slice()`], the first argument is the path of the executable.
+///
+/// # Panics
+///
+/// Panics if `args` is empty.
+///
+/// # Example
+///
+/// This example reads a string from a fixed set of arguments.
+///
+/// ``` rust
+/// use std::ffi::OsString;
+///
+/// let args = vec![OsString::from("executable"), OsString::from("foo")];
+/// let value: String = serde_args::from_os_slice(&args).unwrap();
+///
+/// assert_eq!(value, "foo");
+/// ```
+///
+/// [`env::args_os()`]: std::env::args_os()
+pub fn from_os_slice<'de, D>(args: &[OsString]) -> Result<D, Error>
+where
+    D: Deserialize<'de>,
+{
+    deserialize_args(PhantomData::<D>, args, HelpLabels::default())
+}
+
+/// Deserialize from the given arguments, classifying the outcome.
+///
+/// The arguments are parsed the same as with [`from_slice()`], with the first argument being the
+/// path of the executable rather than an argument to parse. To parse arguments that do not include
+/// the path of the executable, use [`Parser::from_args_without_exe()`] instead. Rather than
+/// reporting requested help and version information as an [`Error`], they are returned as
+/// [`Outcome::Help`] and [`Outcome::Version`], containing the rendered messages. Only problems with
+/// the arguments or the type are returned as [`Outcome::Error`].
+///
+/// # Panics
+///
+/// Panics if `args` is empty.
+///
+/// # Example
+///
+/// ``` rust
+/// use serde_args::Outcome;
+///
+/// let outcome = serde_args::try_from_args::<String, _, _>(["executable", "foo"]);
+///
+/// assert!(matches!(outcome, Outcome::Parsed(value) if value == "foo"));
+/// ```
+pub fn try_from_args<'de, D, Args, Arg>(args: Args) -> Outcome<D>
+where
+    D: Deserialize<'de>,
+    Args: IntoIterator<Item = Arg>,
+    Arg: Into<OsString>,
+{
+    deserialize_args(
+        PhantomData::<D>,
+        args.into_iter().map(Into::<OsString>::into),
+        HelpLabels::default(),
+    )
+    .into()
+}
+
+/// Deserialize from a command line string.
+///
+/// This function behaves the same as [`from_env()`], except that the arguments are obtained by
+/// splitting `line` in the manner of a shell. Arguments are separated by whitespace, and may be
+/// quoted with either double or single quotes to include whitespace or to form an empty argument.
+/// A backslash escapes the following character, except within single quotes, where every character
+/// is taken literally. No variable expansion or globbing is performed.
+///
+/// The line should contain only the arguments; the path of the executable is still obtained from
+/// [`env::args()`] for use in help and error messages. An unterminated quote is reported as an
+/// [`Error`] indicating the byte offset of the opening quote.
+///
+/// # Example
+///
+/// This example reads a list of strings from a command line string.
+///
+/// ``` rust
+/// let value: Vec<String> =
+///     serde_args::from_command_line(r#"foo "bar baz" '' qux\ quux"#).unwrap();
+///
+/// assert_eq!(value, ["foo", "bar baz", "", "qux quux"]);
+/// ```
+///
+/// [`env::args()`]: std::env::args()
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(not(all(target_family = "wasm", target_os = "unknown"))))
+)]
+pub fn from_command_line<'de, D>(line: &str) -> Result<D, Error>
+where
+    D: Deserialize<'de>,
+{
+    let mut executable_path = env::args_os().take(1);
+    match split(line) {
+        Ok(args) => deserialize_args(
+            PhantomData::<D>,
+            executable_path.chain(args.into_iter().map(OsString::from)),
+            HelpLabels::default(),
+        ),
+        Err(error) => Err(Error::from_splitting_error(
+            error,
+            executable_name(
+                executable_path
+                    .next()
+                    .expect("could not obtain binary name"),
+            ),
+            trace(PhantomData::<D>)?,
+            HelpLabels::default(),
+        )),
+    }
+}
@@ -0,0 +1,552 @@
+use crate::trace::{
+    Error,
+    Field,
+    Shape,
+    Variant,
+};
+use std::iter;
+
+/// Descriptions and version information attached to a type at runtime.
+///
+/// [`generate`](crate::generate) can only annotate types defined in the current crate. `Metadata`
+/// provides the same information for any type, including types defined in other crates. It is
+/// given to [`Parser::with_metadata()`](crate::Parser::with_metadata()), and is merged into the
+/// traced shape of the type before parsing, replacing anything the type declares itself.
+///
+/// Fields and variants are identified by paths of their names separated by `.`, such as `path` for
+/// a field of the type itself or `push.force` for a field of the `push` command. Names are the
+/// names given on the command line, after any renaming by `serde`, and any of a field's or
+/// variant's aliases may be used in place of its name. A path that does not identify
+/// a field or variant is reported as a development error when parsing, so that typos do not go
+/// unnoticed.
+///
+/// # Example
+///
+/// ``` rust
+/// # mod hidden {
+/// use serde::Deserialize;
+/// # }
+/// # use serde_derive::Deserialize;
+/// use serde_args::{
+///     Metadata,
+///     Parser,
+/// };
+///
+/// // A type from another crate, which cannot be annotated.
+/// #[derive(Deserialize)]
+/// struct Args {
+///     path: String,
+///     verbose: bool,
+/// }
+///
+/// fn main() {
+///     let metadata = Metadata::new()
+///         .description("Operates on a file.")
+///         .field_help("path", "The file to operate on.")
+///         .field_help("verbose", "Be more verbose.")
+///         .version(env!("CARGO_PKG_VERSION"));
+///     let parser = Parser::from_env().with_metadata(metadata);
+///     let args: Args = match parser.parse_borrowed() {
+///         Ok(args) => args,
+///         Err(error) => {
+///             println!("{error}");
+///             return;
+///         }
+///     };
+///     // Execute your program with `args`...
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Metadata {
+    description: Option<String>,
+    version: Option<String>,
+    field_help: Vec<(String, String)>,
+    deprecated_aliases: Vec<(String, String, String)>,
+}
+
+impl Metadata {
+    /// Creates metadata that does not change anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Displays `description` at the beginning of help messages.
+    pub fn description<Description>(mut self, description: Description) -> Self
+    where
+        Description: Into<String>,
+    {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Enables the `--version` option, displaying `version`.
+    pub fn version<Version>(mut self, version: Version) -> Self
+    where
+        Version: Into<String>,
+    {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Displays `description` for the field or variant identified by `path` in help messages.
+    ///
+    /// If the same path is given more than once, the last description is used.
+    pub fn field_help<Path, Description>(mut self, path: Path, description: Description) -> Self
+    where
+        Path: Into<String>,
+        Description: Into<String>,
+    {
+        self.field_help.push((path.into(), description.into()));
+        self
+    }
+
+    /// Deprecates `alias` of the field or variant identified by `path`.
+    ///
+    /// The alias is still accepted, but using it records a warning displaying `message`, which
+    /// should describe what to use instead. Warnings are returned by
+    /// [`Parser::parse_with_warnings()`](crate::Parser::parse_with_warnings()) and
+    /// [`Parser::parse_prefix_with_warnings()`](crate::Parser::parse_prefix_with_warnings()), and
+    /// are printed to standard error by the other methods of [`Parser`](crate::Parser).
+    ///
+    /// The alias must already be an alias of the field or variant, such as one given using
+    /// `#[serde(alias = "...")]`. An alias it does not have is reported as a development error
+    /// when parsing.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// # mod hidden {
+    /// use serde::Deserialize;
+    /// # }
+    /// # use serde_derive::Deserialize;
+    /// use serde_args::{
+    ///     Metadata,
+    ///     Parser,
+    /// };
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Args {
+    ///     #[serde(alias = "colour")]
+    ///     color: bool,
+    /// }
+    ///
+    /// let parser = Parser::from_args_without_exe(["--colour"])
+    ///     .name("executable")
+    ///     .with_metadata(Metadata::new().deprecate_alias("color", "colour", "use --color instead"));
+    /// let outcome = parser.parse_with_warnings::<Args>().unwrap();
+    ///
+    /// assert!(outcome.value.color);
+    /// assert_eq!(
+    ///     outcome.warnings[0].to_string(),
+    ///     "WARNING: --colour is deprecated: use --color instead"
+    /// );
+    /// ```
+    pub fn deprecate_alias<Path, Alias, Message>(
+        mut self,
+        path: Path,
+        alias: Alias,
+        message: Message,
+    ) -> Self
+    where
+        Path: Into<String>,
+        Alias: Into<String>,
+        Message: Into<String>,
+    {
+        self.deprecated_aliases
+            .push((path.into(), alias.into(), message.into()));
+        self
+    }
+
+    /// Merges the metadata into `shape`.
+    pub(crate) fn apply(&self, shape: &mut Shape) -> Result<(), Error> {
+        if let Some(description) = &self.description {
+            *shape.description_mut() = description.clone();
+        }
+        if let Some(version) = &self.version {
+            *shape.version_mut() = Some(version.clone());
+        }
+        for (path, description) in &self.field_help {
+            *find(shape, path)
+                .ok_or_else(|| Error::UnknownMetadataPath(path.clone()))?
+                .description() = description.clone();
+        }
+        for (path, alias, message) in &self.deprecated_aliases {
+            let (names, deprecated_aliases) = find(shape, path)
+                .ok_or_else(|| Error::UnknownMetadataPath(path.clone()))?
+                .names();
+            // The name the path refers to the item by cannot be deprecated.
+            let referenced = path.rsplit('.').next();
+            let alias = names
+                .into_iter()
+                .find(|name| name == alias && Some(*name) != referenced)
+                .ok_or_else(|| Error::UnknownMetadataAlias(path.clone(), alias.clone()))?;
+            deprecated_aliases.retain(|(deprecated, _)| *deprecated != alias);
+            deprecated_aliases.push((alias, message.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// A field or variant identified by a path.
+enum Item<'a> {
+    Field(&'a mut Field),
+    Variant(&'a mut Variant),
+}
+
+impl<'a> Item<'a> {
+    fn description(self) -> &'a mut String {
+        match self {
+            Self::Field(field) => &mut field.description,
+            Self::Variant(variant) => &mut variant.description,
+        }
+    }
+
+    /// Returns the names and aliases of the item, along with the aliases that are deprecated.
+    fn names(self) -> (Vec<&'static str>, &'a mut Vec<(&'static str, String)>) {
+        let (name, aliases, deprecated_aliases) = match self {
+            Self::Field(field) => (field.name, &field.aliases, &mut field.deprecated_aliases),
+            Self::Variant(variant) => (
+                variant.name,
+                &variant.aliases,
+                &mut variant.deprecated_aliases,
+            ),
+        };
+        (
+            iter::once(name).chain(aliases.iter().copied()).collect(),
+            deprecated_aliases,
+        )
+    }
+
+    fn shape(self) -> &'a mut Shape {
+        match self {
+            Self::Field(field) => &mut field.shape,
+            Self::Variant(variant) => &mut variant.shape,
+        }
+    }
+}
+
+/// Returns the shape contained within any optionals or sequences.
+fn innermost(shape: &mut Shape) -> &mut Shape {
+    match shape {
+        Shape::Optional(shape) | Shape::Sequence(shape) => innermost(shape),
+        shape => shape,
+    }
+}
+
+/// Finds the field or variant identified by `path`.
+fn find<'a>(shape: &'a mut Shape, path: &str) -> Option<Item<'a>> {
+    let (name, rest) = match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    };
+    let item = match innermost(shape) {
+        Shape::Struct {
+            required,
+            optional,
+            booleans,
+            ..
+        } => Item::Field(
+            required
+                .iter_mut()
+                .chain(optional.iter_mut())
+                .chain(booleans.iter_mut())
+                .find(|field| field.name == name || field.aliases.contains(&name))?,
+        ),
+        Shape::Enum { variants, .. } => Item::Variant(
+            variants
+                .iter_mut()
+                .find(|variant| variant.find_name(name, false).is_some())?,
+        ),
+        _ => return None,
+    };
+    match rest {
+        Some(rest) => find(item.shape(), rest),
+        None => Some(item),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Metadata;
+    use crate::trace::{
+        Error,
+        Field,
+        PrimitiveKind,
+        Shape,
+        Variant,
+    };
+    use claims::{
+        assert_err_eq,
+        assert_ok,
+    };
+
+    fn primitive(name: &str) -> Shape {
+        Shape::Primitive {
+            name: name.to_owned(),
+            description: String::new(),
+            version: None,
+            kind: PrimitiveKind::Str,
+        }
+    }
+
+    fn args() -> Shape {
+        Shape::Struct {
+            name: "Args",
+            description: "struct Args".into(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("path", primitive("path"), 0)],
+            optional: vec![Field {
+                aliases: vec!["lvl"],
+                ..Field::new("level", Shape::Optional(Box::new(primitive("level"))), 1)
+            }],
+            booleans: vec![],
+            constraints: vec![],
+        }
+    }
+
+    fn command() -> Shape {
+        Shape::Enum {
+            name: "Command",
+            description: "enum Command".into(),
+            version: None,
+            examples: None,
+            variants: vec![Variant {
+                name: "run",
+                description: String::new(),
+                version: None,
+                group: None,
+                raw: false,
+                aliases: vec!["r"],
+                deprecated_aliases: Vec::new(),
+                shape: args(),
+            }],
+        }
+    }
+
+    #[test]
+    fn apply_empty() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new().apply(&mut shape));
+
+        assert_eq!(shape, args());
+    }
+
+    #[test]
+    fn apply_description() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new().description("foo").apply(&mut shape));
+
+        assert_eq!(shape.description(), "foo");
+    }
+
+    #[test]
+    fn apply_version() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new().version("1.2.3").apply(&mut shape));
+
+        assert_eq!(shape.version(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn apply_description_optional() {
+        let mut shape = Shape::Optional(Box::new(primitive("foo")));
+
+        assert_ok!(Metadata::new()
+            .description("bar")
+            .version("1.2.3")
+            .apply(&mut shape));
+
+        assert_eq!(shape.description(), "bar");
+        assert_eq!(shape.version(), Some("1.2.3"));
+    }
+
+    #[test]
+    fn apply_field_help() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new()
+            .field_help("path", "foo")
+            .field_help("level", "bar")
+            .apply(&mut shape));
+
+        if let Shape::Struct {
+            required, optional, ..
+        } = shape
+        {
+            assert_eq!(required[0].description, "foo");
+            assert_eq!(optional[0].description, "bar");
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn apply_field_help_repeated() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new()
+            .field_help("path", "foo")
+            .field_help("path", "bar")
+            .apply(&mut shape));
+
+        if let Shape::Struct { required, .. } = shape {
+            assert_eq!(required[0].description, "bar");
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn apply_field_help_variant() {
+        let mut shape = command();
+
+        assert_ok!(Metadata::new()
+            .field_help("run", "foo")
+            .field_help("run.path", "bar")
+            .apply(&mut shape));
+
+        if let Shape::Enum { variants, .. } = shape {
+            assert_eq!(variants[0].description, "foo");
+            if let Shape::Struct { required, .. } = &variants[0].shape {
+                assert_eq!(required[0].description, "bar");
+            } else {
+                unreachable!()
+            }
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn apply_field_help_unknown() {
+        assert_err_eq!(
+            Metadata::new().field_help("pth", "foo").apply(&mut args()),
+            Error::UnknownMetadataPath("pth".into())
+        );
+    }
+
+    #[test]
+    fn apply_field_help_unknown_nested() {
+        assert_err_eq!(
+            Metadata::new()
+                .field_help("run.path.foo", "bar")
+                .apply(&mut command()),
+            Error::UnknownMetadataPath("run.path.foo".into())
+        );
+    }
+
+    #[test]
+    fn apply_field_help_empty() {
+        assert_err_eq!(
+            Metadata::new().field_help("", "foo").apply(&mut args()),
+            Error::UnknownMetadataPath(String::new())
+        );
+    }
+
+    #[test]
+    fn apply_deprecate_alias() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new()
+            .deprecate_alias("level", "lvl", "use --level instead")
+            .apply(&mut shape));
+
+        if let Shape::Struct { optional, .. } = shape {
+            assert_eq!(
+                optional[0].deprecated_aliases,
+                vec![("lvl", "use --level instead".to_owned())]
+            );
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn apply_deprecate_alias_repeated() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new()
+            .deprecate_alias("level", "lvl", "foo")
+            .deprecate_alias("level", "lvl", "bar")
+            .apply(&mut shape));
+
+        if let Shape::Struct { optional, .. } = shape {
+            assert_eq!(
+                optional[0].deprecated_aliases,
+                vec![("lvl", "bar".to_owned())]
+            );
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn apply_deprecate_alias_variant() {
+        let mut shape = command();
+
+        assert_ok!(Metadata::new()
+            .deprecate_alias("run", "r", "use run instead")
+            .apply(&mut shape));
+
+        if let Shape::Enum { variants, .. } = shape {
+            assert_eq!(
+                variants[0].deprecated_aliases,
+                vec![("r", "use run instead".to_owned())]
+            );
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn apply_deprecate_alias_unknown_alias() {
+        assert_err_eq!(
+            Metadata::new()
+                .deprecate_alias("level", "levl", "foo")
+                .apply(&mut args()),
+            Error::UnknownMetadataAlias("level".into(), "levl".into())
+        );
+    }
+
+    #[test]
+    fn apply_deprecate_alias_name() {
+        assert_err_eq!(
+            Metadata::new()
+                .deprecate_alias("level", "level", "foo")
+                .apply(&mut args()),
+            Error::UnknownMetadataAlias("level".into(), "level".into())
+        );
+    }
+
+    #[test]
+    fn apply_deprecate_alias_by_alias() {
+        let mut shape = args();
+
+        assert_ok!(Metadata::new()
+            .deprecate_alias("lvl", "level", "use --lvl instead")
+            .apply(&mut shape));
+
+        if let Shape::Struct { optional, .. } = shape {
+            assert_eq!(
+                optional[0].deprecated_aliases,
+                vec![("level", "use --lvl instead".to_owned())]
+            );
+        } else {
+            unreachable!()
+        }
+    }
+
+    #[test]
+    fn apply_deprecate_alias_unknown_path() {
+        assert_err_eq!(
+            Metadata::new()
+                .deprecate_alias("levl", "lvl", "foo")
+                .apply(&mut args()),
+            Error::UnknownMetadataPath("levl".into())
+        );
+    }
+}
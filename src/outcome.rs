@@ -0,0 +1,88 @@
+//! Classification of the result of parsing arguments.
+
+use crate::Error;
+
+/// The outcome of parsing command line arguments, as returned by [`try_from_args()`].
+///
+/// Functions such as [`from_env()`] report requested help and version information as an
+/// [`Error`], since it is displayed in place of running the program. An `Outcome` instead
+/// separates them from the errors reporting a problem, so that each can be handled explicitly.
+///
+/// The help and version messages are rendered without color formatting, exactly as the
+/// corresponding [`Error`] would be displayed.
+///
+/// # Example
+///
+/// ``` rust
+/// use serde_args::Outcome;
+///
+/// match serde_args::try_from_args::<String, _, _>(["executable", "--help"]) {
+///     Outcome::Parsed(value) => {
+///         // Execute your program with `value`...
+///     }
+///     Outcome::Help(message) | Outcome::Version(message) => println!("{message}"),
+///     Outcome::Error(error) => eprintln!("{error}"),
+/// }
+/// ```
+///
+/// [`try_from_args()`]: crate::try_from_args()
+/// [`from_env()`]: crate::from_env()
+#[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
+pub enum Outcome<D> {
+    /// The arguments were deserialized into a value.
+    Parsed(D),
+    /// Help was requested, containing the help message.
+    Help(String),
+    /// Version information was requested, containing the version message.
+    Version(String),
+    /// The arguments could not be parsed or deserialized, or the type itself is invalid.
+    Error(Error),
+}
+
+impl<D> From<Result<D, Error>> for Outcome<D> {
+    fn from(result: Result<D, Error>) -> Self {
+        match result {
+            Ok(value) => Self::Parsed(value),
+            Err(error) => match Report::of(&error) {
+                Report::Help => Self::Help(error.to_string()),
+                Report::Version => Self::Version(error.to_string()),
+                Report::Checked | Report::Problem => Self::Error(error),
+            },
+        }
+    }
+}
+
+/// What the message of an [`Error`] reports.
+///
+/// This is the single classification of errors, used both to build an [`Outcome`] and to choose
+/// the stream [`Error::write_to()`] writes to.
+pub(crate) enum Report {
+    /// Help was requested.
+    Help,
+    /// Version information was requested.
+    Version,
+    /// The arguments passed a requested check.
+    Checked,
+    /// A problem with the arguments or the type.
+    Problem,
+}
+
+impl Report {
+    pub(crate) fn of(error: &Error) -> Self {
+        if error.is_help() {
+            Self::Help
+        } else if error.is_version() {
+            Self::Version
+        } else if error.is_check_success() {
+            Self::Checked
+        } else {
+            Self::Problem
+        }
+    }
+
+    /// Returns whether the message was requested by the user, rather than reporting a problem.
+    pub(crate) fn is_requested(&self) -> bool {
+        !matches!(self, Self::Problem)
+    }
+}
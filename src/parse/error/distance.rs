@@ -27,9 +27,24 @@ pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
     )
 }
 
+/// The distance used to rank names by similarity to the user's input.
+///
+/// Differences in case are ignored, so that names using a different case than the one given (such
+/// as names renamed to `SCREAMING_SNAKE_CASE`) are still found. Ties are broken by the
+/// case-sensitive distance.
+pub(crate) fn similarity(input: &str, name: &str) -> (usize, usize) {
+    (
+        levenshtein(&input.to_lowercase(), &name.to_lowercase()),
+        levenshtein(input, name),
+    )
+}
+
 #[cfg(test)]
 mod tests {
-    use super::levenshtein;
+    use super::{
+        levenshtein,
+        similarity,
+    };
 
     #[test]
     fn levenshtein_empty() {
@@ -70,4 +85,19 @@ mod tests {
     fn levenshtein_graphemes() {
         assert_eq!(levenshtein("foo", "bãr"), 3);
     }
+
+    #[test]
+    fn similarity_same_case() {
+        assert_eq!(similarity("foo", "bar"), (3, 3));
+    }
+
+    #[test]
+    fn similarity_ignores_case() {
+        assert_eq!(similarity("output_file", "OUTPUT_FILE"), (0, 10));
+    }
+
+    #[test]
+    fn similarity_mixed_case() {
+        assert_eq!(similarity("dryrun", "dryRun"), (0, 1));
+    }
 }
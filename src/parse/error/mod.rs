@@ -1,5 +1,12 @@
 mod distance;
 
+pub(crate) use distance::similarity;
+
+use crate::{
+    error::write_template,
+    quote::Quoted,
+    HelpLabels,
+};
 use std::{
     fmt,
     fmt::{
@@ -11,47 +18,347 @@ use std::{
 #[derive(Debug, Eq, PartialEq)]
 pub(crate) enum Error {
     MissingArguments(Vec<String>),
-    UnexpectedArgument(Vec<u8>),
-    UnrecognizedOption {
+    /// An option taking multiple values was not given all of them.
+    MissingOptionArguments {
+        /// The name of the option, as it was given.
         name: String,
+        /// The names of the missing values.
+        arguments: Vec<String>,
+    },
+    /// Options marked as required were not given.
+    MissingOptions(Vec<String>),
+    /// None of the options of a `one_of` constraint were given.
+    MissingOneOfOptions(Vec<String>),
+    /// More than one of the options of a `one_of` or `at_most_one` constraint were given.
+    ConflictingOptions(Vec<String>),
+    /// Flags for more than one variant of an option given as flags were given.
+    ConflictingFlags {
+        /// The name of the first flag that was given.
+        first: String,
+        /// The name of the second flag that was given.
+        second: String,
+    },
+    /// The flag for one variant of an option given as flags was given more than once.
+    ///
+    /// This contains the name of the flag.
+    RepeatedFlag(String),
+    /// Only some of the options of an `all_or_none` constraint were given.
+    IncompleteOptions {
+        /// The names of the options that were given.
+        given: Vec<String>,
+        /// The names of the options that were not given.
+        missing: Vec<String>,
+    },
+    /// Positional arguments remained after the innermost command received all of its arguments.
+    TooManyArguments {
+        /// The names of the commands leading to the innermost command.
+        command_path: Vec<String>,
+        /// The positional arguments accepted by the innermost command.
+        arguments: Vec<String>,
+        /// The optional positional argument accepted after `arguments`, if any.
+        optional: Option<String>,
+        first_unexpected: Vec<u8>,
+        count: usize,
+    },
+    UnrecognizedOption {
+        /// The name of the option, as it was given.
+        name: Vec<u8>,
         expecting: Vec<&'static str>,
     },
+    /// A long option was abbreviated to a prefix shared by the names of multiple options.
+    AmbiguousOption {
+        /// The abbreviated name, as it was given.
+        name: String,
+        /// The names of the options the abbreviation could refer to.
+        candidates: Vec<&'static str>,
+    },
     UnrecognizedVariant {
+        /// The name of the command, as it was given.
+        name: Vec<u8>,
+        expecting: Vec<&'static str>,
+    },
+    /// The command selected by the last required field of a struct was not given.
+    MissingCommand {
+        /// The name of the field.
         name: String,
+        /// The names of the commands that could have been given.
         expecting: Vec<&'static str>,
     },
-    Help,
+    /// A command was required from an enum with no variants, which no argument can select.
+    ///
+    /// This contains the name of the enum.
+    NoVariants(String),
+    /// A value split on an option's delimiter contained an empty element.
+    EmptyDelimitedValue {
+        /// The name of the option, as it was given.
+        name: String,
+    },
+    /// A value split on an option's delimiter ended with the delimiter.
+    TrailingDelimiter {
+        /// The name of the option, as it was given.
+        name: String,
+        delimiter: char,
+    },
+    /// Multiple errors, in the order they were encountered.
+    Multiple(Vec<Error>),
+    Help {
+        /// Whether help was requested using the short `-h` alias, displaying a summary rather
+        /// than the full help message.
+        short: bool,
+    },
     Version,
 }
 
+impl Error {
+    /// Prepends the executable name to the command path of any `TooManyArguments` errors.
+    pub(crate) fn with_executable_name(self, executable_name: &str) -> Self {
+        match self {
+            Self::TooManyArguments {
+                mut command_path,
+                arguments,
+                optional,
+                first_unexpected,
+                count,
+            } => {
+                command_path.insert(0, executable_name.to_owned());
+                Self::TooManyArguments {
+                    command_path,
+                    arguments,
+                    optional,
+                    first_unexpected,
+                    count,
+                }
+            }
+            Self::Multiple(errors) => Self::Multiple(
+                errors
+                    .into_iter()
+                    .map(|error| error.with_executable_name(executable_name))
+                    .collect(),
+            ),
+            error => error,
+        }
+    }
+
+    /// Removes the top-level command from the command path of leftover arguments.
+    ///
+    /// This is used when the command was selected by the name of the executable, which is
+    /// displayed in its place.
+    pub(crate) fn without_command(self) -> Self {
+        match self {
+            Self::TooManyArguments {
+                mut command_path,
+                arguments,
+                optional,
+                first_unexpected,
+                count,
+            } => {
+                if !command_path.is_empty() {
+                    command_path.remove(0);
+                }
+                Self::TooManyArguments {
+                    command_path,
+                    arguments,
+                    optional,
+                    first_unexpected,
+                    count,
+                }
+            }
+            Self::Multiple(errors) => {
+                Self::Multiple(errors.into_iter().map(Self::without_command).collect())
+            }
+            error => error,
+        }
+    }
+
+    /// Combines the errors encountered while parsing into a single error.
+    pub(crate) fn from_errors(mut errors: Vec<Error>) -> Self {
+        if errors.len() == 1 {
+            errors.pop().expect("no errors")
+        } else {
+            Self::Multiple(errors)
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        match self {
-            Self::MissingArguments(arguments) => {
+        Display::fmt(
+            &Labeled {
+                error: self,
+                labels: &HelpLabels::default(),
+            },
+            formatter,
+        )
+    }
+}
+
+/// A parsing error displayed using the messages of the given labels.
+pub(crate) struct Labeled<'a> {
+    pub(crate) error: &'a Error,
+    pub(crate) labels: &'a HelpLabels,
+}
+
+impl Display for Labeled<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let labels = self.labels;
+        match self.error {
+            Error::MissingArguments(arguments) => {
                 if arguments.len() == 1 {
-                    write!(
+                    write_template(
                         formatter,
-                        "missing required positional argument: <{}>",
-                        arguments.last().expect("argument not present")
+                        &labels.missing_argument,
+                        &[(
+                            "name",
+                            &format!("<{}>", arguments.last().expect("argument not present")),
+                        )],
                     )
                 } else {
-                    formatter.write_str("missing required positional arguments:")?;
-                    for argument in arguments {
-                        write!(formatter, " <{}>", argument)?;
-                    }
-                    Ok(())
+                    let names = arguments
+                        .iter()
+                        .map(|argument| format!("<{}>", argument))
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    let mut message = String::new();
+                    write_template(
+                        &mut message,
+                        &labels.missing_arguments,
+                        &[("names", &names)],
+                    )?;
+                    formatter.write_str(message.trim_end())
                 }
             }
-            Self::UnexpectedArgument(argument) => {
+            Error::MissingOptionArguments { name, arguments } => {
                 write!(
                     formatter,
-                    "unexpected positional argument: {}",
-                    String::from_utf8_lossy(argument)
-                )
+                    "missing argument{}",
+                    if arguments.len() == 1 { "" } else { "s" }
+                )?;
+                for argument in arguments {
+                    write!(formatter, " <{}>", argument)?;
+                }
+                write!(formatter, " for option '{}'", option_display(name))
             }
-            Self::UnrecognizedOption { name, expecting } => {
-                // Find the most similar option.
+            Error::MissingOptions(names) => {
+                write!(
+                    formatter,
+                    "missing required option{}:",
+                    if names.len() == 1 { "" } else { "s" }
+                )?;
+                for name in names {
+                    write!(formatter, " '{}'", option_display(name))?;
+                }
+                Ok(())
+            }
+            Error::MissingOneOfOptions(names) => {
+                formatter.write_str("missing one of the options:")?;
+                for name in names {
+                    write!(formatter, " '{}'", option_display(name))?;
+                }
+                Ok(())
+            }
+            Error::ConflictingOptions(names) => {
+                formatter.write_str("options cannot be used together:")?;
+                for name in names {
+                    write!(formatter, " '{}'", option_display(name))?;
+                }
+                Ok(())
+            }
+            Error::ConflictingFlags { first, second } => write!(
+                formatter,
+                "'{}' cannot be used with '{}'",
+                option_display(first),
+                option_display(second)
+            ),
+            Error::RepeatedFlag(name) => write!(
+                formatter,
+                "'{}' cannot be used multiple times",
+                option_display(name)
+            ),
+            Error::IncompleteOptions { given, missing } => {
+                write!(
+                    formatter,
+                    "missing option{}",
+                    if missing.len() == 1 { "" } else { "s" }
+                )?;
+                for name in missing {
+                    write!(formatter, " '{}'", option_display(name))?;
+                }
+                formatter.write_str(", which must be given together with")?;
+                for name in given {
+                    write!(formatter, " '{}'", option_display(name))?;
+                }
+                Ok(())
+            }
+            Error::TooManyArguments {
+                command_path,
+                arguments,
+                optional,
+                first_unexpected,
+                count,
+            } => {
+                write!(
+                    formatter,
+                    "unexpected argument {}",
+                    Quoted(first_unexpected)
+                )?;
+                if *count > 1 {
+                    write!(formatter, " (and {} more)", count - 1)?;
+                }
+                write!(formatter, ": '{}' takes ", command_path.join(" "))?;
+                let length = arguments.len() + usize::from(optional.is_some());
+                if length == 0 {
+                    return formatter.write_str("no arguments");
+                }
+                write!(
+                    formatter,
+                    "{} {} argument{} (",
+                    if optional.is_some() {
+                        "at most"
+                    } else {
+                        "exactly"
+                    },
+                    length,
+                    if length == 1 { "" } else { "s" }
+                )?;
+                for (index, argument) in arguments.iter().enumerate() {
+                    if index > 0 {
+                        formatter.write_str(" ")?;
+                    }
+                    write!(formatter, "<{}>", argument)?;
+                }
+                if let Some(optional) = optional {
+                    if !arguments.is_empty() {
+                        formatter.write_str(" ")?;
+                    }
+                    write!(formatter, "[<{}>]", optional)?;
+                }
+                formatter.write_str(")")
+            }
+            Error::UnrecognizedOption { name, expecting } => {
+                let given = option_given(name);
+                let name = String::from_utf8_lossy(name);
                 let name_count = name.chars().count();
+                // An option abbreviated to a prefix of its name would have been recognized if
+                // abbreviations were enabled.
+                if name_count > 1 {
+                    if let Some(unabbreviated) = expecting.iter().find(|field| {
+                        field.chars().count() > name_count && field.starts_with(name.as_ref())
+                    }) {
+                        write_template(
+                            formatter,
+                            &labels.unrecognized_option,
+                            &[("option", &Quoted(&given))],
+                        )?;
+                        formatter.write_str(" (")?;
+                        write_template(
+                            formatter,
+                            &labels.unabbreviated_option,
+                            &[("option", &format!("'--{}'", unabbreviated))],
+                        )?;
+                        return formatter.write_str(")");
+                    }
+                }
+                // Find the most similar option.
                 let hint = expecting
                     .iter()
                     .filter(|field| {
@@ -63,58 +370,135 @@ impl Display for Error {
                             field.chars().count() != 1
                         }
                     })
-                    .map(|field| (field, distance::levenshtein(name, field)))
-                    .filter(|(_, distance)| *distance < 5)
+                    .map(|field| (field, similarity(&name, field)))
+                    .filter(|(_, (distance, _))| *distance < 5)
                     .min_by_key(|(_, distance)| *distance)
                     .map(|(name, _)| name);
                 // Write message.
-                write!(
+                write_template(
                     formatter,
-                    "unrecognized optional flag: {}",
-                    if name.chars().count() <= 1 {
-                        format!("-{}", name)
-                    } else {
-                        format!("--{}", name)
-                    }
+                    &labels.unrecognized_option,
+                    &[("option", &Quoted(&given))],
                 )?;
                 if let Some(field) = hint {
-                    write!(
+                    formatter.write_str("\n\n  ")?;
+                    write_template(
                         formatter,
-                        "\n\n  tip: a similar option exists: {}",
-                        if field.chars().count() == 1 {
-                            format!("-{}", field)
-                        } else {
-                            format!("--{}", field)
-                        },
+                        &labels.similar_option,
+                        &[("option", &option_display(field))],
                     )?;
                 }
                 Ok(())
             }
-            Self::UnrecognizedVariant { name, expecting } => {
+            Error::AmbiguousOption { name, candidates } => {
+                let candidates = candidates
+                    .iter()
+                    .map(|candidate| format!("'{}'", option_display(candidate)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                // Only long options are abbreviated, even to a single character.
+                write_template(
+                    formatter,
+                    &labels.ambiguous_option,
+                    &[
+                        ("option", &format!("'--{}'", name)),
+                        ("candidates", &candidates),
+                    ],
+                )
+            }
+            Error::EmptyDelimitedValue { name } => {
+                write!(
+                    formatter,
+                    "empty value in list given to {}",
+                    option_display(name)
+                )
+            }
+            Error::TrailingDelimiter { name, delimiter } => {
+                write!(
+                    formatter,
+                    "trailing '{}' in list given to {}",
+                    delimiter,
+                    option_display(name)
+                )
+            }
+            Error::UnrecognizedVariant { name, expecting } => {
+                write_template(
+                    formatter,
+                    &labels.unrecognized_command,
+                    &[("command", &Quoted(name))],
+                )?;
+                // An empty name is not a misspelling of any command, so no command is suggested.
+                if name.is_empty() {
+                    return Ok(());
+                }
                 // Find the most similar command.
+                let name = String::from_utf8_lossy(name);
                 let hint = expecting
                     .iter()
-                    .map(|variant| (variant, distance::levenshtein(name, variant)))
-                    .filter(|(_, distance)| *distance < 5)
+                    .map(|variant| (variant, similarity(&name, variant)))
+                    .filter(|(_, (distance, _))| *distance < 5)
                     .min_by_key(|(_, distance)| *distance)
                     .map(|(name, _)| name);
-                // Write message.
-                write!(formatter, "unrecognized command: {}", name)?;
                 if let Some(variant) = hint {
-                    write!(
-                        formatter,
-                        "\n\n  tip: a similar command exists: {}",
-                        variant
-                    )?;
+                    formatter.write_str("\n\n  ")?;
+                    write_template(formatter, &labels.similar_command, &[("command", variant)])?;
+                }
+                Ok(())
+            }
+            Error::MissingCommand { name, expecting } => write!(
+                formatter,
+                "missing required command <{}>, expected one of {:?}",
+                name, expecting
+            ),
+            Error::NoVariants(name) => write!(
+                formatter,
+                "no commands are available for <{}>, so it can never be given",
+                name
+            ),
+            Error::Multiple(errors) => {
+                write_template(formatter, &labels.problems, &[("count", &errors.len())])?;
+                for error in errors {
+                    // Each error is listed as a bullet point, with any tips indented beneath it.
+                    let message = Labeled { error, labels }.to_string();
+                    let mut paragraphs = message.split("\n\n");
+                    if let Some(first) = paragraphs.next() {
+                        write!(formatter, "\n  - {}", first)?;
+                    }
+                    for paragraph in paragraphs {
+                        write!(formatter, "\n    {}", paragraph.trim_start())?;
+                    }
                 }
                 Ok(())
             }
-            Self::Help => formatter.write_str("help requested"),
-            Self::Version => formatter.write_str("version requested"),
+            Error::Help { .. } => formatter.write_str("help requested"),
+            Error::Version => formatter.write_str("version requested"),
         }
     }
 }
 
+/// Returns an option's name as it was given, with its leading hyphens.
+///
+/// The hyphens are determined in the same way as by [`option_display()`], counting characters
+/// after any bytes that are not valid UTF-8 are replaced.
+fn option_given(name: &[u8]) -> Vec<u8> {
+    let mut given = if String::from_utf8_lossy(name).chars().count() <= 1 {
+        b"-".to_vec()
+    } else {
+        b"--".to_vec()
+    };
+    given.extend_from_slice(name);
+    given
+}
+
+/// Displays an option's name with its leading hyphens.
+pub(super) fn option_display(name: &str) -> String {
+    if name.chars().count() <= 1 {
+        format!("-{}", name)
+    } else {
+        format!("--{}", name)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Error;
@@ -147,18 +531,225 @@ mod tests {
     }
 
     #[test]
-    fn unexpected_argument_display() {
+    fn too_many_arguments_no_arguments_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into(), "stop".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                }
+            ),
+            "unexpected argument 'foo': 'prog stop' takes no arguments"
+        );
+    }
+
+    #[test]
+    fn too_many_arguments_single_argument_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into()],
+                    arguments: vec!["path".into()],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                }
+            ),
+            "unexpected argument 'foo': 'prog' takes exactly 1 argument (<path>)"
+        );
+    }
+
+    #[test]
+    fn too_many_arguments_optional_argument_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into()],
+                    arguments: vec!["repository".into()],
+                    optional: Some("directory".into()),
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                }
+            ),
+            "unexpected argument 'foo': 'prog' takes at most 2 arguments (<repository> [<directory>])"
+        );
+    }
+
+    #[test]
+    fn too_many_arguments_only_optional_argument_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into()],
+                    arguments: vec![],
+                    optional: Some("directory".into()),
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                }
+            ),
+            "unexpected argument 'foo': 'prog' takes at most 1 argument ([<directory>])"
+        );
+    }
+
+    #[test]
+    fn too_many_arguments_multiple_arguments_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into(), "push".into()],
+                    arguments: vec!["remote".into(), "refspec".into()],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                }
+            ),
+            "unexpected argument 'foo': 'prog push' takes exactly 2 arguments (<remote> <refspec>)"
+        );
+    }
+
+    #[test]
+    fn too_many_arguments_count_display() {
         assert_eq!(
-            format!("{}", Error::UnexpectedArgument("foo".into())),
-            "unexpected positional argument: foo"
+            format!(
+                "{}",
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into(), "stop".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 3,
+                }
+            ),
+            "unexpected argument 'foo' (and 2 more): 'prog stop' takes no arguments"
         );
     }
 
     #[test]
-    fn unexpected_argument_non_utf8_display() {
+    fn too_many_arguments_non_utf8_display() {
         assert_eq!(
-            format!("{}", Error::UnexpectedArgument(b"foo\xff".into())),
-            "unexpected positional argument: foo\u{fffd}"
+            format!(
+                "{}",
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into(), "stop".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: b"foo\xff".into(),
+                    count: 1,
+                }
+            ),
+            "unexpected argument 'foo\\xff': 'prog stop' takes no arguments"
+        );
+    }
+
+    #[test]
+    fn with_executable_name_too_many_arguments() {
+        assert_eq!(
+            Error::TooManyArguments {
+                command_path: vec!["push".into()],
+                arguments: vec!["remote".into()],
+                optional: None,
+                first_unexpected: "foo".into(),
+                count: 1,
+            }
+            .with_executable_name("prog"),
+            Error::TooManyArguments {
+                command_path: vec!["prog".into(), "push".into()],
+                arguments: vec!["remote".into()],
+                optional: None,
+                first_unexpected: "foo".into(),
+                count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn with_executable_name_multiple() {
+        assert_eq!(
+            Error::Multiple(vec![
+                Error::TooManyArguments {
+                    command_path: vec!["stop".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                },
+                Error::MissingArguments(vec!["bar".into()])
+            ])
+            .with_executable_name("prog"),
+            Error::Multiple(vec![
+                Error::TooManyArguments {
+                    command_path: vec!["prog".into(), "stop".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                },
+                Error::MissingArguments(vec!["bar".into()])
+            ])
+        );
+    }
+
+    #[test]
+    fn with_executable_name_other() {
+        assert_eq!(
+            Error::MissingArguments(vec!["foo".into()]).with_executable_name("prog"),
+            Error::MissingArguments(vec!["foo".into()])
+        );
+    }
+
+    #[test]
+    fn without_command_too_many_arguments() {
+        assert_eq!(
+            Error::TooManyArguments {
+                command_path: vec!["push".into()],
+                arguments: vec!["remote".into()],
+                optional: None,
+                first_unexpected: "foo".into(),
+                count: 1,
+            }
+            .without_command(),
+            Error::TooManyArguments {
+                command_path: vec![],
+                arguments: vec!["remote".into()],
+                optional: None,
+                first_unexpected: "foo".into(),
+                count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn without_command_multiple() {
+        assert_eq!(
+            Error::Multiple(vec![
+                Error::TooManyArguments {
+                    command_path: vec!["remote".into(), "add".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                },
+                Error::MissingArguments(vec!["bar".into()])
+            ])
+            .without_command(),
+            Error::Multiple(vec![
+                Error::TooManyArguments {
+                    command_path: vec!["add".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: "foo".into(),
+                    count: 1,
+                },
+                Error::MissingArguments(vec!["bar".into()])
+            ])
         );
     }
 
@@ -172,7 +763,7 @@ mod tests {
                     expecting: vec![],
                 }
             ),
-            "unrecognized optional flag: -f"
+            "unrecognized optional flag: '-f'"
         );
     }
 
@@ -186,7 +777,77 @@ mod tests {
                     expecting: vec![],
                 }
             ),
-            "unrecognized optional flag: --foo"
+            "unrecognized optional flag: '--foo'"
+        );
+    }
+
+    #[test]
+    fn unrecognized_option_escape_sequence_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnrecognizedOption {
+                    name: b"\x1b[2Jfoo".to_vec(),
+                    expecting: vec![],
+                }
+            ),
+            "unrecognized optional flag: '--\\x1b[2Jfoo'"
+        );
+    }
+
+    #[test]
+    fn unrecognized_option_non_utf8_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnrecognizedOption {
+                    name: b"f\xffo".to_vec(),
+                    expecting: vec!["foo"],
+                }
+            ),
+            "unrecognized optional flag: '--f\\xffo'\n\n  tip: a similar option exists: --foo"
+        );
+    }
+
+    #[test]
+    fn unrecognized_option_abbreviated_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnrecognizedOption {
+                    name: "forc".into(),
+                    expecting: vec!["file", "force", "f"],
+                }
+            ),
+            "unrecognized optional flag: '--forc' (unabbreviated option '--force' exists; abbreviations are not enabled)"
+        );
+    }
+
+    #[test]
+    fn ambiguous_option_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::AmbiguousOption {
+                    name: "fi".into(),
+                    candidates: vec!["file", "filter"],
+                }
+            ),
+            "'--fi' is ambiguous: could match '--file', '--filter'"
+        );
+    }
+
+    #[test]
+    fn ambiguous_option_single_character_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::AmbiguousOption {
+                    name: "f".into(),
+                    candidates: vec!["force", "file"],
+                }
+            ),
+            "'--f' is ambiguous: could match '--force', '--file'"
         );
     }
 
@@ -200,7 +861,7 @@ mod tests {
                     expecting: vec!["g"],
                 }
             ),
-            "unrecognized optional flag: -f\n\n  tip: a similar option exists: -g"
+            "unrecognized optional flag: '-f'\n\n  tip: a similar option exists: -g"
         );
     }
 
@@ -214,7 +875,7 @@ mod tests {
                     expecting: vec!["goo"],
                 }
             ),
-            "unrecognized optional flag: --foo\n\n  tip: a similar option exists: --goo"
+            "unrecognized optional flag: '--foo'\n\n  tip: a similar option exists: --goo"
         );
     }
 
@@ -228,7 +889,7 @@ mod tests {
                     expecting: vec!["bar", "goo", "baz"],
                 }
             ),
-            "unrecognized optional flag: --foo\n\n  tip: a similar option exists: --goo"
+            "unrecognized optional flag: '--foo'\n\n  tip: a similar option exists: --goo"
         );
     }
 
@@ -242,7 +903,161 @@ mod tests {
                     expecting: vec!["abcdefghijkl"],
                 }
             ),
-            "unrecognized optional flag: --foo"
+            "unrecognized optional flag: '--foo'"
+        );
+    }
+
+    #[test]
+    fn missing_option_arguments_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::MissingOptionArguments {
+                    name: "rect".into(),
+                    arguments: vec!["height".into()],
+                }
+            ),
+            "missing argument <height> for option '--rect'"
+        );
+    }
+
+    #[test]
+    fn missing_option_arguments_multiple_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::MissingOptionArguments {
+                    name: "r".into(),
+                    arguments: vec!["width".into(), "height".into()],
+                }
+            ),
+            "missing arguments <width> <height> for option '-r'"
+        );
+    }
+
+    #[test]
+    fn missing_options_single_display() {
+        assert_eq!(
+            format!("{}", Error::MissingOptions(vec!["output".into()])),
+            "missing required option: '--output'"
+        );
+    }
+
+    #[test]
+    fn missing_one_of_options_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::MissingOneOfOptions(vec!["stdin".into(), "f".into()])
+            ),
+            "missing one of the options: '--stdin' '-f'"
+        );
+    }
+
+    #[test]
+    fn conflicting_options_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::ConflictingOptions(vec!["stdin".into(), "file".into()])
+            ),
+            "options cannot be used together: '--stdin' '--file'"
+        );
+    }
+
+    #[test]
+    fn conflicting_flags_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::ConflictingFlags {
+                    first: "json".into(),
+                    second: "y".into(),
+                }
+            ),
+            "'--json' cannot be used with '-y'"
+        );
+    }
+
+    #[test]
+    fn repeated_flag_display() {
+        assert_eq!(
+            format!("{}", Error::RepeatedFlag("yaml".into())),
+            "'--yaml' cannot be used multiple times"
+        );
+    }
+
+    #[test]
+    fn incomplete_options_single_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::IncompleteOptions {
+                    given: vec!["user".into()],
+                    missing: vec!["password".into()],
+                }
+            ),
+            "missing option '--password', which must be given together with '--user'"
+        );
+    }
+
+    #[test]
+    fn incomplete_options_multiple_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::IncompleteOptions {
+                    given: vec!["user".into(), "host".into()],
+                    missing: vec!["password".into(), "port".into()],
+                }
+            ),
+            "missing options '--password' '--port', which must be given together with '--user' '--host'"
+        );
+    }
+
+    #[test]
+    fn missing_options_multiple_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::MissingOptions(vec!["output".into(), "o".into()])
+            ),
+            "missing required options: '--output' '-o'"
+        );
+    }
+
+    #[test]
+    fn empty_delimited_value_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::EmptyDelimitedValue {
+                    name: "tags".into()
+                }
+            ),
+            "empty value in list given to --tags"
+        );
+    }
+
+    #[test]
+    fn empty_delimited_value_short_display() {
+        assert_eq!(
+            format!("{}", Error::EmptyDelimitedValue { name: "t".into() }),
+            "empty value in list given to -t"
+        );
+    }
+
+    #[test]
+    fn trailing_delimiter_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::TrailingDelimiter {
+                    name: "tags".into(),
+                    delimiter: ',',
+                }
+            ),
+            "trailing ',' in list given to --tags"
         );
     }
 
@@ -256,7 +1071,29 @@ mod tests {
                     expecting: vec![],
                 }
             ),
-            "unrecognized command: foo"
+            "unrecognized command: 'foo'"
+        );
+    }
+
+    #[test]
+    fn missing_command_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::MissingCommand {
+                    name: "command".into(),
+                    expecting: vec!["add", "commit", "push"],
+                }
+            ),
+            "missing required command <command>, expected one of [\"add\", \"commit\", \"push\"]"
+        );
+    }
+
+    #[test]
+    fn no_variants_display() {
+        assert_eq!(
+            format!("{}", Error::NoVariants("Never".into())),
+            "no commands are available for <Never>, so it can never be given"
         );
     }
 
@@ -270,7 +1107,7 @@ mod tests {
                     expecting: vec!["goo"],
                 }
             ),
-            "unrecognized command: foo\n\n  tip: a similar command exists: goo"
+            "unrecognized command: 'foo'\n\n  tip: a similar command exists: goo"
         );
     }
 
@@ -284,7 +1121,7 @@ mod tests {
                     expecting: vec!["bar", "goo", "baz"],
                 }
             ),
-            "unrecognized command: foo\n\n  tip: a similar command exists: goo"
+            "unrecognized command: 'foo'\n\n  tip: a similar command exists: goo"
         );
     }
 
@@ -298,17 +1135,124 @@ mod tests {
                     expecting: vec!["abcdefghijkl"],
                 }
             ),
-            "unrecognized command: foo"
+            "unrecognized command: 'foo'"
+        );
+    }
+
+    #[test]
+    fn unrecognized_variant_empty_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnrecognizedVariant {
+                    name: "".into(),
+                    expecting: vec!["foo", "bar"],
+                }
+            ),
+            "unrecognized command: ''"
+        );
+    }
+
+    #[test]
+    fn unrecognized_variant_newline_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnrecognizedVariant {
+                    name: "foo\nbar".into(),
+                    expecting: vec![],
+                }
+            ),
+            "unrecognized command: 'foo\\nbar'"
         );
     }
 
     #[test]
     fn help_display() {
-        assert_eq!(format!("{}", Error::Help), "help requested")
+        assert_eq!(
+            format!("{}", Error::Help { short: false }),
+            "help requested"
+        )
     }
 
     #[test]
     fn version_display() {
         assert_eq!(format!("{}", Error::Version), "version requested")
     }
+
+    #[test]
+    fn from_errors_single() {
+        assert_eq!(
+            Error::from_errors(vec![Error::MissingArguments(vec!["foo".into()])]),
+            Error::MissingArguments(vec!["foo".into()])
+        );
+    }
+
+    #[test]
+    fn from_errors_multiple() {
+        assert_eq!(
+            Error::from_errors(vec![
+                Error::MissingArguments(vec!["foo".into()]),
+                Error::Help { short: false }
+            ]),
+            Error::Multiple(vec![
+                Error::MissingArguments(vec!["foo".into()]),
+                Error::Help { short: false }
+            ])
+        );
+    }
+
+    #[test]
+    fn multiple_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::Multiple(vec![
+                    Error::TooManyArguments {
+                        command_path: vec!["prog".into()],
+                        arguments: vec![],
+                        optional: None,
+                        first_unexpected: "foo".into(),
+                        count: 1,
+                    },
+                    Error::MissingArguments(vec!["bar".into(), "baz".into()])
+                ])
+            ),
+            "2 problems found:\n  - unexpected argument 'foo': 'prog' takes no arguments\n  - missing required positional arguments: <bar> <baz>"
+        );
+    }
+
+    #[test]
+    fn multiple_with_tip_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::Multiple(vec![
+                    Error::UnrecognizedOption {
+                        name: "fo".into(),
+                        expecting: vec!["foo"],
+                    },
+                    Error::MissingArguments(vec!["bar".into()])
+                ])
+            ),
+            "2 problems found:\n  - unrecognized optional flag: '--fo' (unabbreviated option '--foo' exists; abbreviations are not enabled)\n  - missing required positional argument: <bar>"
+        );
+    }
+
+    #[test]
+    fn multiple_with_similar_option_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::Multiple(vec![
+                    Error::UnrecognizedOption {
+                        name: "fop".into(),
+                        expecting: vec!["foo"],
+                    },
+                    Error::MissingArguments(vec!["bar".into()])
+                ])
+            ),
+            "2 problems found:\n  - unrecognized optional flag: '--fop'\n    tip: a similar option exists: --foo\n  - missing required positional argument: <bar>"
+        );
+    }
 }
@@ -1,5 +1,8 @@
 mod context;
 mod error;
+mod normalize;
+mod options;
+mod required;
 mod token;
 
 pub(crate) use context::{
@@ -7,57 +10,182 @@ pub(crate) use context::{
     ContextIter,
     Segment,
 };
-pub(crate) use error::Error;
+pub(crate) use error::{
+    similarity,
+    Error,
+    Labeled,
+};
 
-use crate::trace::{
-    Field,
-    Shape,
+use crate::{
+    trace::{
+        Field,
+        Settings,
+        Shape,
+        Variant,
+    },
+    Warning,
+};
+use error::option_display;
+use normalize::normalize;
+use options::Options;
+use required::{
+    exclusive_flags,
+    missing_options,
+    violated_constraints,
 };
 use std::{
+    borrow::Cow,
+    collections::{
+        HashMap,
+        HashSet,
+    },
     ffi::OsString,
     iter,
+    mem,
     str,
     vec,
 };
+pub(crate) use token::RawArg;
 use token::{
     ParsedArgs,
     Token,
 };
 use unicode_segmentation::UnicodeSegmentation;
 
-pub(crate) fn parse<Arg, Args>(args: Args, shape: &mut Shape) -> Result<Context, Error>
+/// Parses the arguments using the default configuration.
+#[cfg(test)]
+pub(crate) fn parse<'a, Arg, Args>(args: Args, shape: &mut Shape) -> Result<Context, Error>
 where
     Args: IntoIterator<Item = Arg>,
-    Arg: Into<OsString>,
+    Arg: RawArg<'a>,
 {
-    let mut parsed_args = ParsedArgs::new(args.into_iter().map(|arg| arg.into()));
-    let mut override_options = vec![Field {
-        name: "help",
-        description: "Display this message.".into(),
-        aliases: vec!["h"],
-        shape: Shape::Empty {
-            description: String::new(),
-            version: None,
-        },
-        index: 0,
-    }];
+    parse_with_config(args, shape, Config::default())
+}
+
+/// Configuration of parsing behavior that is not recorded in the shape.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct Config {
+    /// Whether command names and aliases are matched case-insensitively.
+    pub(crate) case_insensitive_commands: bool,
+    /// Whether commands may be selected by their index when no command name matches.
+    pub(crate) match_command_indices: bool,
+    /// Whether long options may be given using an unambiguous prefix of their names.
+    pub(crate) allow_abbreviations: bool,
+    /// Whether the `--help` option is recognized.
+    pub(crate) auto_help: bool,
+    /// Whether help is requested when no arguments are provided.
+    pub(crate) empty_help: bool,
+    /// Whether parsing stops once the shape is satisfied, leaving the remaining arguments
+    /// unparsed.
+    pub(crate) prefix: bool,
+}
+
+impl Config {
+    /// Returns the configuration requested by settings declared on the type.
+    pub(crate) fn from_settings(settings: Settings) -> Self {
+        Self {
+            auto_help: !settings.no_auto_help,
+            empty_help: !settings.no_empty_help,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            case_insensitive_commands: false,
+            match_command_indices: false,
+            allow_abbreviations: false,
+            auto_help: true,
+            empty_help: true,
+            prefix: false,
+        }
+    }
+}
+
+/// Parses the arguments according to `config`.
+///
+/// When command names are matched case-insensitively, the declared name of a matched command is
+/// always what is recorded in the context.
+pub(crate) fn parse_with_config<'a, Arg, Args>(
+    args: Args,
+    shape: &mut Shape,
+    config: Config,
+) -> Result<Context, Error>
+where
+    Args: IntoIterator<Item = Arg>,
+    Arg: RawArg<'a>,
+{
+    parse_args(args, shape, config).map(|parsed| parsed.context)
+}
+
+/// The result of successfully parsing the arguments.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct Parsed {
+    pub(crate) context: Context,
+    /// Arguments left unparsed, in their original order.
+    ///
+    /// Arguments are only left unparsed when `Config::prefix` is set. Parsing then stops as soon
+    /// as every required argument of the shape has been given, and nothing after that point is
+    /// read, including options that would otherwise be recognized. Otherwise, any remaining
+    /// arguments are reported as errors instead.
+    pub(crate) remainder: Vec<OsString>,
+    /// Warnings about the arguments, such as the use of deprecated aliases.
+    pub(crate) warnings: Vec<Warning>,
+}
+
+/// Parses the arguments according to `config`, returning everything learned while parsing.
+pub(crate) fn parse_args<'a, Arg, Args>(
+    args: Args,
+    shape: &mut Shape,
+    config: Config,
+) -> Result<Parsed, Error>
+where
+    Args: IntoIterator<Item = Arg>,
+    Arg: RawArg<'a>,
+{
+    let mut parsed_args = ParsedArgs::new(args.into_iter());
+    parsed_args.case_insensitive_commands = config.case_insensitive_commands;
+    parsed_args.match_command_indices = config.match_command_indices;
+    parsed_args.allow_abbreviations = config.allow_abbreviations;
+    parsed_args.prefix = config.prefix;
+    let mut override_options = Vec::new();
+    if config.auto_help {
+        override_options.push(Field {
+            description: "Display this message.".into(),
+            aliases: vec!["h"],
+            ..Field::new(
+                "help",
+                Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+                0,
+            )
+        });
+    }
     if shape.version().is_some() {
         override_options.push(Field {
-            name: "version",
             description: "Display version information.".into(),
-            aliases: vec![],
-            shape: Shape::Empty {
-                description: String::new(),
-                version: None,
-            },
-            index: 1,
+            ..Field::new(
+                "version",
+                Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+                1,
+            )
         });
     }
+    let mut options = Options::from(override_options.clone());
     let parsed_context = parse_context(
         &mut parsed_args,
+        &override_options,
         shape,
-        &mut override_options,
+        &mut options,
         Context { segments: vec![] },
+        false,
     );
 
     // Parse any remaining options that are at the end of the tokens.
@@ -67,14 +195,19 @@ where
     } else {
         let closing_parsed_context = parse_context(
             &mut parsed_args,
+            &override_options,
             &mut Shape::Empty {
                 description: String::new(),
                 version: None,
             },
-            &mut override_options,
+            &mut options,
             Context { segments: vec![] },
+            false,
         );
-        let _ = closing_parsed_context.context?;
+        if let Err(error) = closing_parsed_context.context {
+            parsed_args.errors.push(error);
+            return Err(Error::from_errors(parsed_args.errors));
+        }
         let mut options = parsed_context.options;
         options.extend(closing_parsed_context.options);
         options
@@ -82,84 +215,168 @@ where
 
     // Handle overriding options.
     if let Some((option_name, _option_context)) = options.into_iter().next() {
-        let mut expecting = vec!["help", "h"];
-        if shape.version().is_some() {
-            expecting.push("version");
-        }
         match option_name {
-            "help" | "h" => return Err(Error::Help),
+            "help" => return Err(Error::Help { short: false }),
+            "h" => return Err(Error::Help { short: true }),
             "version" if shape.version().is_some() => return Err(Error::Version),
-            _ => {
-                return Err(Error::UnrecognizedOption {
-                    name: option_name.to_owned(),
-                    expecting,
-                })
-            }
+            _ => parsed_args.errors.push(Error::UnrecognizedOption {
+                name: option_name.into(),
+                expecting: expecting_options(&override_options),
+            }),
         }
     }
 
-    let context = parsed_context.context.map_err(|error| {
-        if matches!(error, Error::MissingArguments(_)) && !parsed_args.consumed_token {
-            Error::Help
-        } else {
-            error
+    let mut context = match parsed_context.context {
+        Ok(context) => context,
+        Err(error) => {
+            // Override options requested from within an isolated context take precedence over
+            // any other errors.
+            if matches!(error, Error::Help { .. } | Error::Version) {
+                return Err(error);
+            }
+            if config.empty_help
+                && matches!(error, Error::MissingArguments(_))
+                && !parsed_args.consumed_token
+            {
+                return Err(Error::Help { short: false });
+            }
+            // Parsing cannot continue past this error.
+            parsed_args.errors.push(missing_command(error, shape));
+            return Err(Error::from_errors(parsed_args.errors));
         }
-    })?;
+    };
 
     // Ensure there are no remaining arguments.
+    //
+    // Leftover positional arguments are reported together as a single error, positioned where
+    // the first of them was found.
+    let remainder = if config.prefix {
+        parsed_args.remaining()
+    } else {
+        Vec::new()
+    };
+    let mut unexpected: Option<(usize, Vec<u8>, usize)> = None;
     let mut end_of_options = parsed_context.closing_end_of_options;
     loop {
-        if end_of_options {
-            if let Some(value) = parsed_args.next_positional() {
-                return Err(Error::UnexpectedArgument(value));
-            } else {
-                break;
+        let value = if end_of_options {
+            match parsed_args.next_positional() {
+                Some(value) => value,
+                None => break,
             }
         } else if let Some(token) = parsed_args.next_token() {
             match token {
-                Token::Positional(value) => {
-                    return Err(Error::UnexpectedArgument(value));
-                }
-                Token::Optional(value) => {
-                    return Err(Error::UnrecognizedOption {
-                        name: String::from_utf8_lossy(&value).into(),
-                        expecting: vec!["help", "h"]
-                            .into_iter()
-                            .chain(shape.trailing_options().into_iter().flat_map(|field| {
-                                iter::once(field.name).chain(field.aliases.iter().copied())
-                            }))
-                            .collect(),
+                Token::Positional(value) => value,
+                Token::Optional { name: value, .. } => {
+                    parsed_args.errors.push(Error::UnrecognizedOption {
+                        name: value.into_owned(),
+                        // Only the help option is recognized after the trailing arguments.
+                        expecting: expecting_options(
+                            override_options
+                                .iter()
+                                .filter(|option| option.name == "help")
+                                .chain(shape.trailing_options()),
+                        ),
                     });
+                    continue;
                 }
                 Token::EndOfOptions => {
                     end_of_options = true;
+                    continue;
                 }
             }
         } else {
             break;
+        };
+        match unexpected {
+            Some((_, _, ref mut count)) => *count += 1,
+            None => unexpected = Some((parsed_args.errors.len(), value.into_owned(), 1)),
+        }
+    }
+    if let Some((index, first_unexpected, count)) = unexpected {
+        let (command_path, innermost_shape) = shape.trailing_command();
+        let mut arguments: Vec<String> = innermost_shape
+            .required_arguments()
+            .into_iter()
+            .map(|(name, _)| name.to_owned())
+            .collect();
+        // A trailing optional positional argument is always listed last.
+        let optional = if matches!(innermost_shape, Shape::Struct { .. })
+            && innermost_shape.trailing_optional_positional().is_some()
+        {
+            arguments.pop()
+        } else {
+            None
+        };
+        parsed_args.errors.insert(
+            index,
+            Error::TooManyArguments {
+                command_path: command_path.into_iter().map(ToOwned::to_owned).collect(),
+                arguments,
+                optional,
+                first_unexpected,
+                count,
+            },
+        );
+    }
+
+    let missing = missing_options(&context, shape);
+    let mut violated = exclusive_flags(&context, shape);
+    violated.extend(violated_constraints(&context, shape));
+    if !missing.is_empty() || !violated.is_empty() {
+        // As with missing positional arguments, help is displayed when nothing was given.
+        if config.empty_help && !parsed_args.consumed_token && parsed_args.errors.is_empty() {
+            return Err(Error::Help { short: false });
         }
+        if !missing.is_empty() {
+            parsed_args.errors.push(Error::MissingOptions(missing));
+        }
+        parsed_args.errors.extend(violated);
+    }
+
+    if !parsed_args.errors.is_empty() {
+        return Err(Error::from_errors(parsed_args.errors));
     }
 
-    Ok(context)
+    normalize(&mut context, shape);
+    Ok(Parsed {
+        context,
+        remainder,
+        warnings: parsed_args.warnings,
+    })
 }
 
-fn parse_context_no_options<Args>(
-    args: &mut ParsedArgs<Args>,
+/// Parses the shape without recognizing options in the current context.
+///
+/// If `raw` is set, options are not recognized within nested contexts either, meaning every
+/// remaining argument is parsed as a positional argument. This is the case within raw variants.
+fn parse_context_no_options<'a, Args>(
+    args: &mut ParsedArgs<'a, Args>,
+    override_options: &[Field],
     shape: &mut Shape,
     mut context: Context,
+    raw: bool,
 ) -> Result<Context, Error>
 where
-    Args: Iterator<Item = OsString>,
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
 {
     match *shape {
         Shape::Empty { .. } => Ok(context),
         Shape::Primitive { ref name, .. } | Shape::Boolean { ref name, .. } => {
             context.segments.push(Segment::Value(
                 args.next_positional()
-                    .ok_or(Error::MissingArguments(vec![name.clone()]))?,
+                    .ok_or(Error::MissingArguments(vec![name.clone()]))?
+                    .into_owned(),
             ));
             Ok(context)
         }
+        Shape::Sequence(_) => {
+            // Sequences collect all remaining values.
+            while let Some(value) = args.next_positional() {
+                context.segments.push(Segment::Value(value.into_owned()));
+            }
+            Ok(context)
+        }
         Shape::Optional(ref mut optional_shape) => {
             // This is a "positional optional". It starts its own isolated context, which only
             // contains its own optional value if it exists.
@@ -172,8 +389,10 @@ where
                                     context.segments.push(Segment::Context(
                                         parse_context_no_options(
                                             args,
+                                            override_options,
                                             optional_shape,
                                             Context { segments: vec![] },
+                                            raw,
                                         )?,
                                     ));
                                 }
@@ -189,7 +408,11 @@ where
                         }
                     }
                 }
-                Shape::Primitive { .. } | Shape::Boolean { .. } | Shape::Enum { .. } => {
+                Shape::Primitive { .. }
+                | Shape::Boolean { .. }
+                | Shape::Enum { .. }
+                | Shape::Variant { .. }
+                | Shape::Sequence(_) => {
                     if let Some(optional) = args.next_optional() {
                         args.revisit = Some(optional);
                         let optional_context = Context { segments: vec![] };
@@ -197,20 +420,22 @@ where
                             .segments
                             .push(Segment::Context(parse_context_no_options(
                                 args,
+                                override_options,
                                 optional_shape,
                                 optional_context,
+                                raw,
                             )?));
                     }
                 }
                 Shape::Struct { ref required, .. } => {
                     if let Some(optional) = args.next_optional() {
-                        // If the value we extracted is empty, we only revisit it if there is at
-                        // least one required field that is not an empty field.
-                        if !optional.is_empty()
-                            || required
-                                .iter()
-                                .any(|field| !matches!(field.shape, Shape::Empty { .. }))
-                        {
+                        // A lone `-` is an empty value, which is given to the first required field
+                        // taking a value. If no required field takes a value, there is nothing to
+                        // give it to, and it only marks the struct as present.
+                        let takes_value = required
+                            .iter()
+                            .any(|field| !matches!(field.shape, Shape::Empty { .. }));
+                        if !optional.is_empty() || takes_value {
                             args.revisit = Some(optional);
                         }
                         let optional_context = Context { segments: vec![] };
@@ -218,14 +443,13 @@ where
                             .segments
                             .push(Segment::Context(parse_context_no_options(
                                 args,
+                                override_options,
                                 optional_shape,
                                 optional_context,
+                                raw,
                             )?));
                     }
                 }
-                Shape::Variant { .. } => {
-                    unreachable!()
-                }
             }
             Ok(context)
         }
@@ -237,8 +461,19 @@ where
         } => {
             // Parse the struct in its own nested context.
             //
-            // While the current context cannot have options, the nested context can.
-            let mut end_of_options = false;
+            // While the current context cannot have options, the nested context can, unless it is
+            // raw.
+            let mut end_of_options = raw;
+            let mut options = Options::from(
+                override_options
+                    .iter()
+                    .chain(optional.iter())
+                    .chain(booleans.iter())
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            );
+            let struct_options = option_names(optional.iter().chain(booleans.iter()));
+            let mut late_options = LateOptions::default();
             let mut required_iter = required.iter_mut();
             while let Some(required_field) = required_iter.next() {
                 let inner_context = Context {
@@ -246,10 +481,15 @@ where
                 };
                 if end_of_options {
                     context.segments.push(Segment::Context(
-                        match parse_context_no_options(
-                            args,
-                            &mut required_field.shape,
-                            inner_context,
+                        match resolve_positional(
+                            parse_context_no_options(
+                                args,
+                                override_options,
+                                positional_shape(&mut required_field.shape),
+                                inner_context,
+                                raw,
+                            ),
+                            required_field,
                         ) {
                             Ok(context) => context,
                             Err(error) => {
@@ -263,15 +503,20 @@ where
                                             )
                                         {
                                             *arguments.last_mut().expect("no arguments") =
-                                                required_field.name.to_owned();
+                                                required_field.placeholder().to_owned();
                                         }
                                         // Append any more missing arguments.
                                         arguments.extend(
                                             required_iter
                                                 .filter(|field| {
-                                                    !matches!(field.shape, Shape::Empty { .. })
+                                                    !matches!(
+                                                        field.shape,
+                                                        Shape::Empty { .. }
+                                                            | Shape::Optional(_)
+                                                            | Shape::Sequence(_)
+                                                    )
                                                 })
-                                                .map(|field| field.name.to_owned()),
+                                                .map(|field| field.placeholder().to_owned()),
                                         );
                                         Error::MissingArguments(arguments)
                                     } else {
@@ -282,19 +527,40 @@ where
                         },
                     ));
                 } else {
-                    let parsed_context = parse_context(
+                    let parsed_context = parse_required_field(
                         args,
-                        &mut required_field.shape,
-                        &mut optional
-                            .clone()
-                            .into_iter()
-                            .chain(booleans.clone())
-                            .collect(),
+                        override_options,
+                        required_field,
+                        &mut options,
                         inner_context,
                     );
-                    context
-                        .segments
-                        .push(Segment::Context(match parsed_context.context {
+                    end_of_options = parsed_context.closing_end_of_options;
+                    // Options are resolved before any error is returned, so that override options
+                    // take precedence over the error.
+                    let mut found_options = Vec::new();
+                    for (optional_name, optional_context) in parsed_context.options {
+                        // Find whether the optional name is in this struct.
+                        if struct_options.contains(optional_name)
+                            || late_options.contains(optional_name)
+                        {
+                            found_options.push((optional_name, optional_context));
+                        } else {
+                            if let Some(error) =
+                                override_error(override_options, optional_name.as_bytes())
+                            {
+                                return Err(error);
+                            }
+                            args.errors.push(Error::UnrecognizedOption {
+                                name: optional_name.into(),
+                                expecting: expecting_options(
+                                    optional.iter().chain(booleans.iter()),
+                                ),
+                            });
+                        }
+                    }
+                    let position = context.segments.len();
+                    context.segments.push(Segment::Context(
+                        match resolve_positional(parsed_context.context, required_field) {
                             Ok(context) => context,
                             Err(error) => {
                                 return Err({
@@ -307,15 +573,20 @@ where
                                             )
                                         {
                                             *arguments.last_mut().expect("no arguments") =
-                                                required_field.name.to_owned();
+                                                required_field.placeholder().to_owned();
                                         }
                                         // Append any more missing arguments.
                                         arguments.extend(
                                             required_iter
                                                 .filter(|field| {
-                                                    !matches!(field.shape, Shape::Empty { .. })
+                                                    !matches!(
+                                                        field.shape,
+                                                        Shape::Empty { .. }
+                                                            | Shape::Optional(_)
+                                                            | Shape::Sequence(_)
+                                                    )
                                                 })
-                                                .map(|field| field.name.to_owned()),
+                                                .map(|field| field.placeholder().to_owned()),
                                         );
                                         Error::MissingArguments(arguments)
                                     } else {
@@ -323,34 +594,14 @@ where
                                     }
                                 });
                             }
-                        }));
-                    end_of_options = parsed_context.closing_end_of_options;
-                    let parsed_options = parsed_context.options;
-                    for (optional_name, optional_context) in parsed_options {
-                        let mut found = false;
-                        // Find whether the optional name is in this struct.
-                        for optional_field in optional.iter_mut().chain(&mut *booleans) {
-                            if optional_name == optional_field.name
-                                || optional_field.aliases.contains(&optional_name)
-                            {
-                                found = true;
-                                context
-                                    .segments
-                                    .push(Segment::Context(optional_context.clone()));
-                                break;
-                            }
-                        }
-                        if !found {
-                            return Err(Error::UnrecognizedOption {
-                                name: optional_name.into(),
-                                expecting: optional
-                                    .iter()
-                                    .chain(booleans.iter())
-                                    .flat_map(|field| {
-                                        iter::once(field.name).chain(field.aliases.iter().copied())
-                                    })
-                                    .collect(),
-                            });
+                        },
+                    ));
+                    late_options.add(&mut options, position, &required_field.shape);
+                    for (optional_name, optional_context) in found_options {
+                        if struct_options.contains(optional_name) {
+                            context.segments.push(Segment::Context(optional_context));
+                        } else {
+                            late_options.insert(&mut context, optional_name, optional_context);
                         }
                     }
                 }
@@ -359,79 +610,46 @@ where
             if !end_of_options {
                 let parsed_context = parse_context(
                     args,
+                    override_options,
                     &mut Shape::Empty {
                         description: String::new(),
                         version: None,
                     },
-                    &mut optional
-                        .clone()
-                        .into_iter()
-                        .chain(booleans.clone())
-                        .collect(),
+                    &mut options,
                     context,
+                    false,
                 );
-                context = parsed_context.context?;
+                let mut found_options = Vec::new();
                 for (optional_name, optional_context) in parsed_context.options {
-                    let mut found = false;
                     // Find whether the optional name is in this struct.
-                    for optional_field in optional.iter_mut().chain(&mut *booleans) {
-                        if optional_name == optional_field.name
-                            || optional_field.aliases.contains(&optional_name)
+                    if struct_options.contains(optional_name)
+                        || late_options.contains(optional_name)
+                    {
+                        found_options.push((optional_name, optional_context));
+                    } else {
+                        if let Some(error) =
+                            override_error(override_options, optional_name.as_bytes())
                         {
-                            found = true;
-                            context
-                                .segments
-                                .push(Segment::Context(optional_context.clone()));
-                            break;
+                            return Err(error);
                         }
-                    }
-                    if !found {
-                        return Err(Error::UnrecognizedOption {
+                        args.errors.push(Error::UnrecognizedOption {
                             name: optional_name.into(),
-                            expecting: optional
-                                .iter()
-                                .chain(booleans.iter())
-                                .flat_map(|field| {
-                                    iter::once(field.name).chain(field.aliases.iter().copied())
-                                })
-                                .collect(),
+                            expecting: expecting_options(optional.iter().chain(booleans.iter())),
                         });
                     }
                 }
-            }
-            // Fill in any missing optional and boolean fields.
-            let cloned_segments = context.segments.clone();
-            let found_fields: Vec<_> = cloned_segments
-                .iter()
-                .filter_map(|segment| {
-                    if let Segment::Context(field_context) = segment {
-                        if let Some(Segment::Identifier(name)) = field_context.segments.first() {
-                            Some(name)
-                        } else {
-                            None
-                        }
+                context = parsed_context.context?;
+                for (optional_name, optional_context) in found_options {
+                    if struct_options.contains(optional_name) {
+                        context.segments.push(Segment::Context(optional_context));
                     } else {
-                        None
-                    }
-                })
-                .collect();
-            for optional_field in optional.iter_mut().chain(booleans) {
-                // Check whether the field name or any aliases have been found.
-                let mut found = false;
-                for field_name in
-                    iter::once(&optional_field.name).chain(optional_field.aliases.iter())
-                {
-                    if found_fields.contains(&field_name) {
-                        found = true;
-                        break;
+                        late_options.insert(&mut context, optional_name, optional_context);
                     }
                 }
-                if !found {
-                    context.segments.push(Segment::Context(Context {
-                        segments: vec![Segment::Identifier(optional_field.name)],
-                    }));
-                }
             }
+            merge_repeated_options(&mut context, optional);
+            // Fill in any missing optional and boolean fields.
+            fill_missing_options(&mut context, optional.iter().chain(booleans.iter()));
 
             Ok(context)
         }
@@ -440,26 +658,22 @@ where
             ref mut variants,
             ..
         } => {
+            check_variants(name, variants)?;
             let variant_name = args
                 .next_positional()
                 .ok_or(Error::MissingArguments(vec![name.into()]))?;
             let variant_name_str =
                 str::from_utf8(&variant_name).map_err(|_| Error::UnrecognizedVariant {
-                    name: String::from_utf8_lossy(&variant_name).into(),
-                    expecting: variants
-                        .iter()
-                        .flat_map(|variant| {
-                            iter::once(variant.name).chain(variant.aliases.iter().copied())
-                        })
-                        .collect(),
+                    name: variant_name.to_vec(),
+                    expecting: expecting_variants(variants),
                 })?;
+            let variant_name_str = args.resolve_variant_index(variants, variant_name_str);
 
             let mut variants_iter = variants.clone().into_iter();
             loop {
                 if let Some(variant) = variants_iter.next() {
-                    if let Some(static_variant_name) = iter::once(variant.name)
-                        .chain(variant.aliases)
-                        .find(|s| *s == variant_name_str)
+                    if let Some(static_variant_name) =
+                        args.find_variant_name(&variant, variant_name_str)
                     {
                         *shape = Shape::Variant {
                             name: static_variant_name,
@@ -476,7 +690,13 @@ where
                             context
                                 .segments
                                 .push(Segment::Identifier(static_variant_name));
-                            context = parse_context_no_options(args, inner_shape, context)?;
+                            context = parse_context_no_options(
+                                args,
+                                override_options,
+                                inner_shape,
+                                context,
+                                raw || variant.raw,
+                            )?;
                         } else {
                             unreachable!();
                         }
@@ -486,12 +706,7 @@ where
                 } else {
                     return Err(Error::UnrecognizedVariant {
                         name: variant_name_str.into(),
-                        expecting: variants
-                            .iter()
-                            .flat_map(|variant| {
-                                iter::once(variant.name).chain(variant.aliases.iter().copied())
-                            })
-                            .collect(),
+                        expecting: expecting_variants(variants),
                     });
                 }
             }
@@ -506,231 +721,778 @@ where
                 .ok_or(Error::MissingArguments(vec![enum_name.into()]))?;
             let variant_name_str =
                 str::from_utf8(&variant_name).map_err(|_| Error::UnrecognizedVariant {
-                    name: String::from_utf8_lossy(&variant_name).into(),
-                    expecting: variants
-                        .iter()
-                        .flat_map(|variant| {
-                            iter::once(variant.name).chain(variant.aliases.iter().copied())
-                        })
-                        .collect(),
+                    name: variant_name.to_vec(),
+                    expecting: expecting_variants(variants),
                 })?;
+            let variant_name_str = args.resolve_variant_index(variants, variant_name_str);
 
             for variant in variants.iter_mut() {
-                if let Some(static_variant_name) = iter::once(variant.name)
-                    .chain(variant.aliases.clone())
-                    .find(|s| *s == variant_name_str)
+                if let Some(static_variant_name) = args.find_variant_name(variant, variant_name_str)
                 {
                     context
                         .segments
                         .push(Segment::Identifier(static_variant_name));
-                    return parse_context_no_options(args, &mut variant.shape, context);
+                    return parse_context_no_options(
+                        args,
+                        override_options,
+                        &mut variant.shape,
+                        context,
+                        raw || variant.raw,
+                    );
                 }
             }
 
             Err(Error::UnrecognizedVariant {
                 name: variant_name_str.into(),
-                expecting: variants
-                    .iter()
-                    .flat_map(|variant| {
-                        iter::once(variant.name).chain(variant.aliases.iter().copied())
-                    })
-                    .collect(),
+                expecting: expecting_variants(variants),
             })
         }
     }
 }
 
-#[derive(Debug)]
-struct ParsedContext {
-    context: Result<Context, Error>,
-    options: Vec<(&'static str, Context)>,
-    /// If an `EndOfOptions` token appeared at the end of the positional arguments.
-    ///
-    /// This indicates that the outer context's options should also be terminated.
-    closing_end_of_options: bool,
+/// Returns an error if the enum `name` has no variants, since no argument could select one.
+fn check_variants(name: &str, variants: &[Variant]) -> Result<(), Error> {
+    if variants.is_empty() {
+        Err(Error::NoVariants(name.to_owned()))
+    } else {
+        Ok(())
+    }
 }
 
-fn parse_context<Args>(
-    args: &mut ParsedArgs<Args>,
-    shape: &mut Shape,
-    options: &mut Vec<Field>,
-    mut context: Context,
+/// Parses the value of a required field, recognizing any of `options` among its values.
+///
+/// A trailing field only parses options until its first value is found, or until an option is
+/// found that is not recognized. That argument and every argument following it are then captured
+/// verbatim, including options and `--`.
+fn parse_required_field<'a, Args>(
+    args: &mut ParsedArgs<'a, Args>,
+    override_options: &[Field],
+    field: &mut Field,
+    options: &mut Options,
+    context: Context,
 ) -> ParsedContext
 where
-    Args: Iterator<Item = OsString>,
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
 {
-    let mut parsed_options = Vec::new();
-    let mut closing_end_of_options = false;
+    if !field.trailing {
+        return parse_context(
+            args,
+            override_options,
+            positional_shape(&mut field.shape),
+            options,
+            context,
+            field.allow_hyphen_values,
+        );
+    }
+    let mut parsed_context = parse_context(
+        args,
+        override_options,
+        &mut Shape::Empty {
+            description: String::new(),
+            version: None,
+        },
+        options,
+        context,
+        false,
+    );
+    if let Ok(context) = &mut parsed_context.context {
+        context.segments.extend(
+            args.by_ref()
+                .map(|value| Segment::Value(value.into_owned())),
+        );
+    }
+    parsed_context
+}
 
-    // This is wrapped in a function to allow easily returning errors with `?` while also giving
-    // context to the outer scope.
-    let context = (|| {
-        match shape {
-            Shape::Empty { .. } => {
-                while let Some(token) = args.next_token() {
-                    match token {
-                        Token::Positional(value) => {
-                            args.revisit = Some(value);
-                            break;
-                        }
-                        Token::Optional(value) => {
-                            // Find the option and parse it.
-                            let identifier =
-                                str::from_utf8(&value).or(Err(Error::UnrecognizedOption {
-                                    name: String::from_utf8_lossy(&value).into(),
-                                    expecting: options
-                                        .iter()
-                                        .flat_map(|field| {
-                                            iter::once(field.name)
-                                                .chain(field.aliases.iter().copied())
-                                        })
-                                        .collect(),
-                                }))?;
-                            let mut found = false;
-                            let mut index = 0;
-                            while index < options.len() {
-                                let optional_field = &options[index];
-                                if let Some(static_field_name) = iter::once(optional_field.name)
-                                    .chain(optional_field.aliases.clone())
-                                    .find(|s| *s == identifier)
-                                {
-                                    let mut optional_field = options.remove(index);
-                                    found = true;
-                                    let parsed_context = parse_context(
-                                        args,
-                                        &mut optional_field.shape,
-                                        options,
-                                        Context { segments: vec![] },
-                                    );
-                                    parsed_options.extend(parsed_context.options);
-                                    parsed_options.push((
-                                        static_field_name,
-                                        Context {
-                                            segments: vec![
-                                                Segment::Identifier(static_field_name),
-                                                Segment::Context(parsed_context.context?),
-                                            ],
-                                        },
-                                    ));
-                                    if parsed_context.closing_end_of_options {
-                                        closing_end_of_options = true;
-                                    }
-                                    options.insert(index, optional_field);
-                                    break;
-                                } else {
-                                    index += 1;
-                                }
-                            }
-                            if !found {
-                                // The argument could belong to a neighboring context.
-                                if identifier.graphemes(true).count() <= 1 {
-                                    args.revisit = Some({
-                                        let mut bytes = vec![b'-'];
-                                        bytes.extend(value);
-                                        bytes
-                                    });
-                                } else {
-                                    args.revisit = Some({
-                                        let mut bytes = vec![b'-', b'-'];
-                                        bytes.extend(value);
-                                        bytes
-                                    });
-                                }
-                                break;
-                            }
-                        }
-                        Token::EndOfOptions => {
-                            closing_end_of_options = true;
-                        }
-                    }
-                    if closing_end_of_options {
-                        break;
-                    }
-                }
-            }
-            Shape::Primitive { ref name, .. } | Shape::Boolean { ref name, .. } => loop {
-                let token = args
-                    .next_token()
-                    .ok_or(Error::MissingArguments(vec![name.clone()]))?;
-                match token {
-                    Token::Positional(value) => {
-                        context.segments.push(Segment::Value(value));
-                        break;
-                    }
-                    Token::Optional(value) => {
-                        let identifier =
-                            str::from_utf8(&value).or(Err(Error::UnrecognizedOption {
-                                name: String::from_utf8_lossy(&value).into(),
-                                expecting: options
-                                    .iter()
-                                    .flat_map(|field| {
-                                        iter::once(field.name).chain(field.aliases.iter().copied())
-                                    })
-                                    .collect(),
-                            }))?;
-                        let mut found = false;
-                        let mut index = 0;
-                        while index < options.len() {
-                            let optional_field = &options[index];
-                            if let Some(static_field_name) = iter::once(optional_field.name)
-                                .chain(optional_field.aliases.clone())
-                                .find(|s| *s == identifier)
-                            {
-                                let mut optional_field = options.remove(index);
-                                found = true;
-                                let parsed_context = parse_context(
-                                    args,
-                                    &mut optional_field.shape,
-                                    options,
-                                    Context { segments: vec![] },
-                                );
-                                parsed_options.extend(parsed_context.options);
-                                parsed_options.push((
-                                    static_field_name,
-                                    Context {
-                                        segments: vec![
-                                            Segment::Identifier(static_field_name),
-                                            Segment::Context(parsed_context.context?),
-                                        ],
-                                    },
-                                ));
-                                if parsed_context.closing_end_of_options {
-                                    closing_end_of_options = true;
-                                }
-                                options.insert(index, optional_field);
-                                break;
-                            } else {
-                                index += 1;
-                            }
-                        }
-                        if !found {
-                            return Err(Error::UnrecognizedOption {
-                                name: identifier.into(),
-                                expecting: options
-                                    .iter()
-                                    .flat_map(|field| {
-                                        iter::once(field.name).chain(field.aliases.iter().copied())
-                                    })
-                                    .collect(),
-                            });
-                        }
-                    }
-                    Token::EndOfOptions => {
-                        closing_end_of_options = true;
-                    }
-                }
-                if closing_end_of_options {
-                    context = parse_context_no_options(args, shape, context)?;
-                    break;
-                }
-            },
-            Shape::Optional(_) => {
-                // This is a "positional optional". It starts its own isolated context, which only
-                // contains its own optional value if it exists.
-                //
-                // We therefore simply parse in a no-option context, thereby ignoring all parent
-                // context options.
-                context = parse_context_no_options(args, shape, context)?;
+/// Returns the shape to parse for a required field's `shape`.
+///
+/// Optional positional fields are parsed as the value they contain.
+fn positional_shape(shape: &mut Shape) -> &mut Shape {
+    match shape {
+        Shape::Optional(shape) => shape,
+        shape => shape,
+    }
+}
+
+/// Lists the commands that could have been given if the command of a struct is missing.
+///
+/// A struct whose last required field is an enum takes that field as its command, so its missing
+/// value is reported along with the names of its variants rather than as a positional argument.
+fn missing_command(error: Error, shape: &Shape) -> Error {
+    let Error::MissingArguments(arguments) = error else {
+        return error;
+    };
+    if let Some(field) = shape.command_field() {
+        if let (Shape::Enum { variants, .. }, [argument]) = (&field.shape, arguments.as_slice()) {
+            if argument == field.placeholder() {
+                return Error::MissingCommand {
+                    name: argument.clone(),
+                    expecting: expecting_variants(variants),
+                };
+            }
+        }
+    }
+    Error::MissingArguments(arguments)
+}
+
+/// Resolves the result of parsing a required field.
+///
+/// The value of an optional positional field is nested in its own context, the same as the value of
+/// an optional field. If no arguments remain for the field, it is left without a value.
+fn resolve_positional(result: Result<Context, Error>, field: &Field) -> Result<Context, Error> {
+    if !matches!(field.shape, Shape::Optional(_)) {
+        return result;
+    }
+    match result {
+        Ok(mut context) => {
+            let segments = context.segments.split_off(1);
+            context
+                .segments
+                .push(Segment::Context(Context { segments }));
+            Ok(context)
+        }
+        Err(Error::MissingArguments(_)) => Ok(Context {
+            segments: vec![Segment::Identifier(field.name)],
+        }),
+        Err(error) => Err(error),
+    }
+}
+
+/// Returns the error requested by an override option, if the identifier names one.
+///
+/// Override options are recognized within isolated contexts, even though the options of their
+/// parent contexts are not.
+fn override_error(override_options: &[Field], identifier: &[u8]) -> Option<Error> {
+    override_options
+        .iter()
+        .find(|field| {
+            iter::once(field.name)
+                .chain(field.aliases.iter().copied())
+                .any(|name| name.as_bytes() == identifier)
+        })
+        .map(|field| match field.name {
+            "version" => Error::Version,
+            _ => Error::Help {
+                short: identifier == b"h",
+            },
+        })
+}
+
+/// Collects the names and aliases of the given options, for use in an `UnrecognizedOption` error.
+///
+/// Names are listed in the order they are first seen, with duplicates removed. The `help` option
+/// and its alias are always listed last.
+fn expecting_options<'a, Fields>(fields: Fields) -> Vec<&'static str>
+where
+    Fields: IntoIterator<Item = &'a Field>,
+{
+    let mut expecting = Vec::new();
+    let mut help = Vec::new();
+    for name in fields.into_iter().flat_map(Field::option_names) {
+        let names = if matches!(name, "help" | "h") {
+            &mut help
+        } else {
+            &mut expecting
+        };
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    expecting.extend(help);
+    expecting
+}
+
+/// Collects the names and aliases of the given variants, for use in an `UnrecognizedVariant`
+/// error.
+///
+/// Names are listed in the order they are first seen, with duplicates removed.
+fn expecting_variants(variants: &[Variant]) -> Vec<&'static str> {
+    let mut expecting = Vec::new();
+    for name in variants
+        .iter()
+        .flat_map(|variant| iter::once(variant.name).chain(variant.aliases.iter().copied()))
+    {
+        if !expecting.contains(&name) {
+            expecting.push(name);
+        }
+    }
+    expecting
+}
+
+/// Returns whether the option named by an optional token is in scope.
+///
+/// Any value joined to the name by `=` is ignored, so `--verbose=no` names the `verbose` option.
+fn option_in_scope(name: &[u8], options: &Options) -> bool {
+    let name = name.split(|byte| *byte == b'=').next().unwrap_or(name);
+    str::from_utf8(name).is_ok_and(|name| options.find(name).is_some())
+}
+
+/// Whether `argument` is a negative number given where `shape` takes a number.
+///
+/// Such an argument is a value rather than an option, as long as no option in scope has its name.
+fn is_numeric_value(shape: &Shape, argument: &[u8]) -> bool {
+    let kind = match shape {
+        Shape::Primitive { kind, .. } => kind,
+        Shape::Sequence(element) => match &**element {
+            Shape::Primitive { kind, .. } => kind,
+            _ => return false,
+        },
+        _ => return false,
+    };
+    kind.is_numeric()
+        && str::from_utf8(argument).is_ok_and(|argument| argument.parse::<f64>().is_ok())
+}
+
+/// Parses the value of an option.
+///
+/// Options with a value delimiter take a single value, which is split into the elements of the
+/// option's sequence. Splitting is done on the raw bytes, so values do not need to be valid UTF-8.
+///
+/// Options taking multiple values take every value up to the next option, `--`, or the end of the
+/// arguments. Other options taking sequences take a single element per occurrence, and must be
+/// repeated to give more elements.
+fn parse_option_value<'a, Args>(
+    args: &mut ParsedArgs<'a, Args>,
+    override_options: &[Field],
+    optional_field: &mut Field,
+    name: &str,
+    options: &mut Options,
+) -> ParsedContext
+where
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
+{
+    if optional_field.multiple {
+        // Anything that is not a value is left for the enclosing context, including `--`.
+        let mut context = Context { segments: vec![] };
+        while let Some(value) = args.next_value() {
+            context.segments.push(Segment::Value(value.into_owned()));
+        }
+        return ParsedContext {
+            context: Ok(context),
+            options: vec![],
+            closing_end_of_options: false,
+        };
+    }
+    if let (Some(delimiter), Shape::Sequence(element_shape)) =
+        (optional_field.value_delimiter, &mut optional_field.shape)
+    {
+        let mut parsed_context = parse_context(
+            args,
+            override_options,
+            element_shape,
+            options,
+            Context { segments: vec![] },
+            false,
+        );
+        parsed_context.context = parsed_context
+            .context
+            .and_then(|context| split_delimited(context, name, delimiter));
+        return parsed_context;
+    }
+    let shape = match &mut optional_field.shape {
+        Shape::Sequence(element_shape) => element_shape,
+        shape => shape,
+    };
+    let mut parsed_context = parse_context(
+        args,
+        override_options,
+        shape,
+        options,
+        Context { segments: vec![] },
+        false,
+    );
+    // Attribute the missing values to the option, rather than to the positional arguments of the
+    // enclosing command.
+    parsed_context.context = parsed_context.context.map_err(|error| match error {
+        Error::MissingArguments(arguments) => Error::MissingOptionArguments {
+            name: name.to_owned(),
+            arguments,
+        },
+        error => error,
+    });
+    parsed_context
+}
+
+/// Parses the option named `identifier` along with its value, if it is one of `options`.
+///
+/// The option is unavailable while its own value is parsed. On success, the returned context
+/// contains the name of the option's field followed by its value, even if the option was given
+/// using an alias. An option given as flags takes no value, and is followed by the variant its
+/// flag names.
+///
+/// If abbreviations are allowed, an `identifier` given as a `long` option but not naming any option
+/// may instead be a prefix of the name of a single option, even if it is a single character. A
+/// prefix of multiple options is an error.
+fn parse_option<'a, Args>(
+    args: &mut ParsedArgs<'a, Args>,
+    override_options: &[Field],
+    identifier: &str,
+    long: bool,
+    options: &mut Options,
+) -> Option<(&'static str, ParsedContext)>
+where
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
+{
+    let (position, spelling) = match options.find(identifier) {
+        Some(found) => found,
+        None if args.allow_abbreviations && long => {
+            let mut found = options.find_abbreviated(identifier);
+            if found.len() > 1 {
+                // The error is returned before the name is ever used.
+                return Some((
+                    found[0].1,
+                    ParsedContext {
+                        context: Err(Error::AmbiguousOption {
+                            name: identifier.to_owned(),
+                            candidates: found.into_iter().map(|(_, name)| name).collect(),
+                        }),
+                        options: vec![],
+                        closing_end_of_options: false,
+                    },
+                ));
+            }
+            found.pop()?
+        }
+        None => return None,
+    };
+    let mut optional_field = options.take(position);
+    if let Some(message) = optional_field.deprecation(spelling) {
+        args.warnings.push(Warning::deprecated_alias(
+            option_display(spelling),
+            message.to_owned(),
+        ));
+    }
+    // Errors refer to the option as it was given, rather than by the name of its field.
+    //
+    // Override options always come first, and are identified by the name or alias they were
+    // given as, so that `-h` can be told apart from `--help`.
+    let name = if position < override_options.len() {
+        spelling
+    } else {
+        optional_field.name
+    };
+    if optional_field.flags {
+        // Each flag selects one of the field's variants without taking a value.
+        let variant_name = match &optional_field.shape {
+            Shape::Enum { variants, .. } => variants
+                .iter()
+                .find_map(|variant| variant.find_name(spelling, false)),
+            _ => None,
+        }
+        .expect("flag does not name a variant");
+        options.restore(position, optional_field);
+        return Some((
+            name,
+            ParsedContext {
+                context: Ok(Context {
+                    segments: vec![
+                        Segment::Identifier(name),
+                        Segment::Context(Context {
+                            segments: vec![Segment::Identifier(variant_name)],
+                        }),
+                    ],
+                }),
+                options: vec![],
+                closing_end_of_options: false,
+            },
+        ));
+    }
+    let mut parsed_context = parse_option_value(
+        args,
+        override_options,
+        &mut optional_field,
+        spelling,
+        options,
+    );
+    options.restore(position, optional_field);
+    parsed_context.context = parsed_context.context.map(|context| Context {
+        segments: vec![Segment::Identifier(name), Segment::Context(context)],
+    });
+    Some((name, parsed_context))
+}
+
+/// Collects the names and aliases of the given options, along with the names of their fields.
+fn option_names<'a, Fields>(fields: Fields) -> HashSet<&'static str>
+where
+    Fields: IntoIterator<Item = &'a Field>,
+{
+    fields
+        .into_iter()
+        .flat_map(|field| iter::once(field.name).chain(field.option_names()))
+        .collect()
+}
+
+/// Adds an empty context for each of `fields` that was not found within `context`.
+///
+/// A field is found if its name or any of its aliases identifies one of the nested contexts.
+fn fill_missing_options<'a, Fields>(context: &mut Context, fields: Fields)
+where
+    Fields: IntoIterator<Item = &'a Field>,
+{
+    let found_fields: HashSet<&'static str> = context
+        .segments
+        .iter()
+        .filter_map(|segment| match segment {
+            Segment::Context(field_context) => match field_context.segments.first() {
+                Some(Segment::Identifier(name)) => Some(*name),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    for optional_field in fields {
+        if !iter::once(optional_field.name)
+            .chain(optional_field.aliases.iter().copied())
+            .any(|field_name| found_fields.contains(field_name))
+        {
+            context.segments.push(Segment::Context(Context {
+                segments: vec![Segment::Identifier(optional_field.name)],
+            }));
+        }
+    }
+}
+
+/// The options of nested structs that have already been parsed.
+///
+/// A nested struct's options may still be given after its positional arguments, while the rest of
+/// the enclosing struct is parsed. Options of the enclosing struct and of any outer contexts take
+/// precedence over them.
+#[derive(Default)]
+struct LateOptions {
+    /// The position of each nested struct's context within the enclosing context, along with the
+    /// nested struct's optional and boolean fields.
+    structs: Vec<(usize, Vec<Field>)>,
+    /// The nested struct each option name belongs to.
+    names: HashMap<&'static str, usize>,
+}
+
+impl LateOptions {
+    /// Brings the options of the nested struct whose context is at `position` back into scope.
+    ///
+    /// Options whose names are already in scope are not added.
+    fn add(&mut self, options: &mut Options, position: usize, shape: &Shape) {
+        let (optional, booleans) = match shape {
+            Shape::Struct {
+                optional, booleans, ..
+            } => (optional, booleans),
+            _ => return,
+        };
+        let fields: Vec<Field> = optional.iter().chain(booleans.iter()).cloned().collect();
+        let late_fields: Vec<Field> = fields
+            .iter()
+            .filter(|field| {
+                field
+                    .option_names()
+                    .into_iter()
+                    .all(|name| options.find(name).is_none())
+            })
+            .cloned()
+            .collect();
+        for name in late_fields
+            .iter()
+            .flat_map(|field| iter::once(field.name).chain(field.option_names()))
+        {
+            self.names.entry(name).or_insert(self.structs.len());
+        }
+        options.extend(late_fields);
+        self.structs.push((position, fields));
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.names.contains_key(name)
+    }
+
+    /// Adds an occurrence of the option `name` to the context of the nested struct it belongs to.
+    ///
+    /// The nested struct's context records the option as missing, since it was not given while
+    /// the nested struct was parsed. That record is replaced by the occurrence.
+    fn insert(&self, context: &mut Context, name: &str, option_context: Context) {
+        let (position, fields) = &self.structs[self.names[name]];
+        let struct_context = match context.segments.get_mut(*position) {
+            Some(Segment::Context(struct_context)) => struct_context,
+            _ => return,
+        };
+        if let Some(field) = fields.iter().find(|field| {
+            iter::once(field.name)
+                .chain(field.aliases.iter().copied())
+                .any(|field_name| field_name == name)
+        }) {
+            struct_context.segments.retain(|segment| {
+                !matches!(
+                    segment,
+                    Segment::Context(field_context)
+                        if field_context.segments == [Segment::Identifier(field.name)]
+                )
+            });
+        }
+        struct_context
+            .segments
+            .push(Segment::Context(option_context));
+        merge_repeated_options(struct_context, fields);
+    }
+}
+
+/// Merges repeated occurrences of options taking sequences into their first occurrence.
+///
+/// The values of every occurrence are collected, in the order they were given.
+fn merge_repeated_options(context: &mut Context, optional: &[Field]) {
+    /// Returns the name of the repeatable option whose occurrence is contained in `segment`.
+    fn repeated_option<'a>(segment: &Segment, fields: &'a [Field]) -> Option<&'a str> {
+        if let Segment::Context(field_context) = segment {
+            if let Some(Segment::Identifier(identifier)) = field_context.segments.first() {
+                return fields
+                    .iter()
+                    .find(|field| {
+                        matches!(field.shape, Shape::Sequence(_))
+                            && iter::once(field.name)
+                                .chain(field.aliases.iter().copied())
+                                .any(|name| name == *identifier)
+                    })
+                    .map(|field| field.name);
+            }
+        }
+        None
+    }
+
+    let mut segments: Vec<Segment> = Vec::with_capacity(context.segments.len());
+    for segment in mem::take(&mut context.segments) {
+        if let Some(name) = repeated_option(&segment, optional) {
+            if let Some(Segment::Context(first)) = segments
+                .iter_mut()
+                .find(|previous| repeated_option(previous, optional) == Some(name))
+            {
+                // Each occurrence is an identifier followed by a context containing its values.
+                if let (Segment::Context(occurrence), Some(Segment::Context(values))) =
+                    (segment, first.segments.last_mut())
+                {
+                    for segment in occurrence.segments.into_iter().skip(1) {
+                        if let Segment::Context(occurrence_values) = segment {
+                            values.segments.extend(occurrence_values.segments);
+                        }
+                    }
+                }
+                continue;
+            }
+        }
+        segments.push(segment);
+    }
+    context.segments = segments;
+}
+
+/// Splits the values within `context` on `delimiter`.
+fn split_delimited(context: Context, name: &str, delimiter: u8) -> Result<Context, Error> {
+    let mut segments = Vec::new();
+    for segment in context.segments {
+        let value = match segment {
+            Segment::Value(value) => value,
+            segment => {
+                segments.push(segment);
+                continue;
+            }
+        };
+        let elements: Vec<&[u8]> = value.split(|byte| *byte == delimiter).collect();
+        for (index, element) in elements.iter().enumerate() {
+            if element.is_empty() {
+                return Err(if index > 0 && index == elements.len() - 1 {
+                    Error::TrailingDelimiter {
+                        name: name.to_owned(),
+                        delimiter: char::from(delimiter),
+                    }
+                } else {
+                    Error::EmptyDelimitedValue {
+                        name: name.to_owned(),
+                    }
+                });
+            }
+            segments.push(Segment::Value(element.to_vec()));
+        }
+    }
+    Ok(Context { segments })
+}
+
+#[derive(Debug)]
+struct ParsedContext {
+    context: Result<Context, Error>,
+    options: Vec<(&'static str, Context)>,
+    /// If an `EndOfOptions` token appeared at the end of the positional arguments.
+    ///
+    /// This indicates that the outer context's options should also be terminated.
+    closing_end_of_options: bool,
+}
+
+fn parse_context<'a, Args>(
+    args: &mut ParsedArgs<'a, Args>,
+    override_options: &[Field],
+    shape: &mut Shape,
+    options: &mut Options,
+    mut context: Context,
+    allow_hyphen_values: bool,
+) -> ParsedContext
+where
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
+{
+    let mut parsed_options = Vec::new();
+    let mut closing_end_of_options = false;
+    // Options added within this context are only in scope until it ends.
+    let scope = options.len();
+
+    // This is wrapped in a function to allow easily returning errors with `?` while also giving
+    // context to the outer scope.
+    let context = (|| {
+        match shape {
+            // Nothing more is read once a prefix is satisfied.
+            Shape::Empty { .. } if args.prefix => {}
+            Shape::Empty { .. } => {
+                while let Some(token) = args.next_token_with_options(options.iter().rev()) {
+                    match token {
+                        Token::Positional(value) => {
+                            args.revisit = Some(value);
+                            break;
+                        }
+                        Token::Optional {
+                            name: value,
+                            argument,
+                        } => {
+                            // Find the option and parse it.
+                            let identifier = match str::from_utf8(&value) {
+                                Ok(identifier) => identifier,
+                                Err(_) => {
+                                    args.errors.push(Error::UnrecognizedOption {
+                                        name: value.into_owned(),
+                                        expecting: expecting_options(options.iter()),
+                                    });
+                                    continue;
+                                }
+                            };
+                            if let Some((static_field_name, parsed_context)) = parse_option(
+                                args,
+                                override_options,
+                                identifier,
+                                argument.starts_with(b"--"),
+                                options,
+                            ) {
+                                parsed_options.extend(parsed_context.options);
+                                parsed_options.push((static_field_name, parsed_context.context?));
+                                if parsed_context.closing_end_of_options {
+                                    closing_end_of_options = true;
+                                }
+                            } else {
+                                // The argument could belong to a neighboring context.
+                                if identifier.graphemes(true).count() <= 1 {
+                                    args.revisit = Some({
+                                        let mut bytes = vec![b'-'];
+                                        bytes.extend_from_slice(&value);
+                                        Cow::Owned(bytes)
+                                    });
+                                } else {
+                                    args.revisit = Some({
+                                        let mut bytes = vec![b'-', b'-'];
+                                        bytes.extend_from_slice(&value);
+                                        Cow::Owned(bytes)
+                                    });
+                                }
+                                break;
+                            }
+                        }
+                        Token::EndOfOptions => {
+                            closing_end_of_options = true;
+                        }
+                    }
+                    if closing_end_of_options {
+                        break;
+                    }
+                }
+            }
+            Shape::Primitive { .. } | Shape::Boolean { .. } | Shape::Sequence(_) => loop {
+                let token = match args.next_token_with_options(options.iter().rev()) {
+                    Some(token) => token,
+                    None => match shape {
+                        Shape::Primitive { name, .. } | Shape::Boolean { name, .. } => {
+                            return Err(Error::MissingArguments(vec![name.clone()]));
+                        }
+                        // Sequences collect values until the arguments run out.
+                        _ => break,
+                    },
+                };
+                match token {
+                    Token::Positional(value) => {
+                        context.segments.push(Segment::Value(value.into_owned()));
+                        if !matches!(shape, Shape::Sequence(_)) {
+                            break;
+                        }
+                    }
+                    Token::Optional { name, argument } if name.is_empty() => {
+                        // A lone `-` is a value, conventionally meaning standard input or output.
+                        context.segments.push(Segment::Value(argument.into_owned()));
+                        if !matches!(shape, Shape::Sequence(_)) {
+                            break;
+                        }
+                    }
+                    Token::Optional { name, argument }
+                        if (allow_hyphen_values || is_numeric_value(shape, &argument))
+                            && !option_in_scope(&name, options) =>
+                    {
+                        // The value does not match any option in scope, so the argument is used as
+                        // the value exactly as it was given.
+                        context.segments.push(Segment::Value(argument.into_owned()));
+                        if !matches!(shape, Shape::Sequence(_)) {
+                            break;
+                        }
+                    }
+                    Token::Optional {
+                        name: value,
+                        argument,
+                    } => {
+                        let identifier = match str::from_utf8(&value) {
+                            Ok(identifier) => identifier,
+                            Err(_) => {
+                                args.errors.push(Error::UnrecognizedOption {
+                                    name: value.into_owned(),
+                                    expecting: expecting_options(options.iter()),
+                                });
+                                continue;
+                            }
+                        };
+                        if let Some((static_field_name, parsed_context)) = parse_option(
+                            args,
+                            override_options,
+                            identifier,
+                            argument.starts_with(b"--"),
+                            options,
+                        ) {
+                            parsed_options.extend(parsed_context.options);
+                            parsed_options.push((static_field_name, parsed_context.context?));
+                            if parsed_context.closing_end_of_options {
+                                closing_end_of_options = true;
+                            }
+                        } else {
+                            args.errors.push(Error::UnrecognizedOption {
+                                name: identifier.into(),
+                                expecting: expecting_options(options.iter()),
+                            });
+                        }
+                    }
+                    Token::EndOfOptions => {
+                        closing_end_of_options = true;
+                    }
+                }
+                if closing_end_of_options {
+                    context =
+                        parse_context_no_options(args, override_options, shape, context, false)?;
+                    break;
+                }
+            },
+            Shape::Optional(_) => {
+                // This is a "positional optional". It starts its own isolated context, which only
+                // contains its own optional value if it exists.
+                //
+                // We therefore simply parse in a no-option context, thereby ignoring all parent
+                // context options except for the override options.
+                context = parse_context_no_options(args, override_options, shape, context, false)?;
             }
             Shape::Struct {
                 required,
@@ -740,9 +1502,9 @@ where
             } => {
                 // Parse the struct in its own nested context.
                 let mut end_of_options = false;
-                let mut combined_options = options.clone();
-                combined_options.extend(optional.clone());
-                combined_options.extend(booleans.clone());
+                options.extend(optional.iter().chain(booleans.iter()).cloned());
+                let struct_options = option_names(optional.iter().chain(booleans.iter()));
+                let mut late_options = LateOptions::default();
                 let mut required_iter = required.iter_mut();
                 while let Some(required_field) = required_iter.next() {
                     let inner_context = Context {
@@ -750,10 +1512,15 @@ where
                     };
                     if end_of_options {
                         context.segments.push(Segment::Context(
-                            match parse_context_no_options(
-                                args,
-                                &mut required_field.shape,
-                                inner_context,
+                            match resolve_positional(
+                                parse_context_no_options(
+                                    args,
+                                    override_options,
+                                    positional_shape(&mut required_field.shape),
+                                    inner_context,
+                                    false,
+                                ),
+                                required_field,
                             ) {
                                 Ok(context) => context,
                                 Err(error) => {
@@ -767,15 +1534,20 @@ where
                                                 )
                                             {
                                                 *arguments.last_mut().expect("no arguments") =
-                                                    required_field.name.to_owned();
+                                                    required_field.placeholder().to_owned();
                                             }
                                             // Append any more missing arguments.
                                             arguments.extend(
                                                 required_iter
                                                     .filter(|field| {
-                                                        !matches!(field.shape, Shape::Empty { .. })
+                                                        !matches!(
+                                                            field.shape,
+                                                            Shape::Empty { .. }
+                                                                | Shape::Optional(_)
+                                                                | Shape::Sequence(_)
+                                                        )
                                                     })
-                                                    .map(|field| field.name.to_owned()),
+                                                    .map(|field| field.placeholder().to_owned()),
                                             );
                                             Error::MissingArguments(arguments)
                                         } else {
@@ -786,31 +1558,27 @@ where
                             },
                         ));
                     } else {
-                        let parsed_context = parse_context(
+                        let parsed_context = parse_required_field(
                             args,
-                            &mut required_field.shape,
-                            &mut combined_options,
+                            override_options,
+                            required_field,
+                            options,
                             inner_context,
                         );
                         end_of_options = parsed_context.closing_end_of_options;
-                        let found_parsed_options = parsed_context.options;
-                        'outer: for (optional_name, optional_context) in found_parsed_options {
+                        for (optional_name, optional_context) in parsed_context.options {
                             // Find whether the optional name is in this struct.
-                            for optional_field in optional.iter_mut().chain(&mut *booleans) {
-                                if optional_name == optional_field.name
-                                    || optional_field.aliases.contains(&optional_name)
-                                {
-                                    context
-                                        .segments
-                                        .push(Segment::Context(optional_context.clone()));
-                                    continue 'outer;
-                                }
+                            if struct_options.contains(optional_name) {
+                                context.segments.push(Segment::Context(optional_context));
+                            } else if late_options.contains(optional_name) {
+                                late_options.insert(&mut context, optional_name, optional_context);
+                            } else {
+                                parsed_options.push((optional_name, optional_context));
                             }
-                            parsed_options.push((optional_name, optional_context));
                         }
-                        context
-                            .segments
-                            .push(Segment::Context(match parsed_context.context {
+                        let position = context.segments.len();
+                        context.segments.push(Segment::Context(
+                            match resolve_positional(parsed_context.context, required_field) {
                                 Ok(context) => context,
                                 Err(error) => {
                                     return Err({
@@ -823,15 +1591,20 @@ where
                                                 )
                                             {
                                                 *arguments.last_mut().expect("no arguments") =
-                                                    required_field.name.to_owned();
+                                                    required_field.placeholder().to_owned();
                                             }
                                             // Append any more missing arguments.
                                             arguments.extend(
                                                 required_iter
                                                     .filter(|field| {
-                                                        !matches!(field.shape, Shape::Empty { .. })
+                                                        !matches!(
+                                                            field.shape,
+                                                            Shape::Empty { .. }
+                                                                | Shape::Optional(_)
+                                                                | Shape::Sequence(_)
+                                                        )
                                                     })
-                                                    .map(|field| field.name.to_owned()),
+                                                    .map(|field| field.placeholder().to_owned()),
                                             );
                                             Error::MissingArguments(arguments)
                                         } else {
@@ -839,98 +1612,75 @@ where
                                         }
                                     });
                                 }
-                            }));
+                            },
+                        ));
+                        late_options.add(options, position, &required_field.shape);
                     }
                 }
                 // Parse any remaining options.
                 if !end_of_options {
                     let parsed_context = parse_context(
                         args,
+                        override_options,
                         &mut Shape::Empty {
                             description: String::new(),
                             version: None,
                         },
-                        &mut combined_options,
+                        options,
                         context,
+                        false,
                     );
-                    context = parsed_context.context?;
-                    'outer: for (optional_name, optional_context) in parsed_context.options {
+                    // Options from outer contexts (such as `--help`) are passed along before any
+                    // error is returned, so that they take precedence over the error.
+                    let mut found_options = Vec::new();
+                    for (optional_name, optional_context) in parsed_context.options {
                         // Find whether the optional name is in this struct.
-                        for optional_field in optional.iter_mut().chain(&mut *booleans) {
-                            if optional_name == optional_field.name
-                                || optional_field.aliases.contains(&optional_name)
-                            {
-                                context
-                                    .segments
-                                    .push(Segment::Context(optional_context.clone()));
-                                continue 'outer;
-                            }
+                        if struct_options.contains(optional_name)
+                            || late_options.contains(optional_name)
+                        {
+                            found_options.push((optional_name, optional_context));
+                        } else {
+                            parsed_options.push((optional_name, optional_context));
                         }
-                        parsed_options.push((optional_name, optional_context));
-                    }
-                    if parsed_context.closing_end_of_options {
-                        closing_end_of_options = true;
                     }
-                }
-                // Fill in any missing optional and boolean fields.
-                let cloned_segments = context.segments.clone();
-                let found_fields: Vec<_> = cloned_segments
-                    .iter()
-                    .filter_map(|segment| {
-                        if let Segment::Context(field_context) = segment {
-                            if let Some(Segment::Identifier(name)) = field_context.segments.first()
-                            {
-                                Some(name)
-                            } else {
-                                None
-                            }
+                    context = parsed_context.context?;
+                    for (optional_name, optional_context) in found_options {
+                        if struct_options.contains(optional_name) {
+                            context.segments.push(Segment::Context(optional_context));
                         } else {
-                            None
-                        }
-                    })
-                    .collect();
-                for optional_field in optional.iter_mut().chain(booleans) {
-                    // Check whether the field name or any aliases have been found.
-                    let mut found = false;
-                    for field_name in
-                        iter::once(&optional_field.name).chain(optional_field.aliases.iter())
-                    {
-                        if found_fields.contains(&field_name) {
-                            found = true;
-                            break;
+                            late_options.insert(&mut context, optional_name, optional_context);
                         }
                     }
-                    if !found {
-                        context.segments.push(Segment::Context(Context {
-                            segments: vec![Segment::Identifier(optional_field.name)],
-                        }));
+                    if parsed_context.closing_end_of_options {
+                        closing_end_of_options = true;
                     }
                 }
+                merge_repeated_options(&mut context, optional);
+                // Fill in any missing optional and boolean fields.
+                fill_missing_options(&mut context, optional.iter().chain(booleans.iter()));
             }
             Shape::Enum { name, variants, .. } => {
                 // Parse the variant.
                 'outer: loop {
-                    let token = args
-                        .next_token()
-                        .ok_or(Error::MissingArguments(vec![name.to_owned()]))?;
+                    let token = args.next_token_with_options(options.iter().rev());
+                    // Options are still parsed, so that help can be requested.
+                    if !matches!(token, Some(Token::Optional { .. })) {
+                        check_variants(name, variants)?;
+                    }
+                    let token = token.ok_or(Error::MissingArguments(vec![name.to_owned()]))?;
                     match token {
                         Token::Positional(variant_name) => {
                             let variant_name_str = str::from_utf8(&variant_name).map_err(|_| {
                                 Error::UnrecognizedVariant {
-                                    name: String::from_utf8_lossy(&variant_name).into(),
-                                    expecting: variants
-                                        .iter()
-                                        .flat_map(|variant| {
-                                            iter::once(variant.name)
-                                                .chain(variant.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    name: variant_name.to_vec(),
+                                    expecting: expecting_variants(variants),
                                 }
                             })?;
+                            let variant_name_str =
+                                args.resolve_variant_index(variants, variant_name_str);
                             for variant in variants.clone() {
-                                if let Some(static_variant_name) = iter::once(variant.name)
-                                    .chain(variant.aliases)
-                                    .find(|s| *s == variant_name_str)
+                                if let Some(static_variant_name) =
+                                    args.find_variant_name(&variant, variant_name_str)
                                 {
                                     *shape = Shape::Variant {
                                         name: static_variant_name,
@@ -948,9 +1698,26 @@ where
                                         context
                                             .segments
                                             .push(Segment::Identifier(static_variant_name));
+                                        if variant.raw {
+                                            // Every remaining argument is positional.
+                                            context = parse_context_no_options(
+                                                args,
+                                                override_options,
+                                                inner_shape,
+                                                context,
+                                                true,
+                                            )?;
+                                            break 'outer;
+                                        }
                                         // Parse the variant's shape.
-                                        let parsed_context =
-                                            parse_context(args, inner_shape, options, context);
+                                        let parsed_context = parse_context(
+                                            args,
+                                            override_options,
+                                            inner_shape,
+                                            options,
+                                            context,
+                                            false,
+                                        );
                                         // Handle options.
                                         parsed_options.extend(parsed_context.options);
                                         if parsed_context.closing_end_of_options {
@@ -965,72 +1732,39 @@ where
                             }
                             return Err(Error::UnrecognizedVariant {
                                 name: variant_name_str.into(),
-                                expecting: variants
-                                    .iter()
-                                    .flat_map(|variant| {
-                                        iter::once(variant.name)
-                                            .chain(variant.aliases.iter().copied())
-                                    })
-                                    .collect(),
+                                expecting: expecting_variants(variants),
                             });
                         }
-                        Token::Optional(value) => {
-                            let identifier =
-                                str::from_utf8(&value).or(Err(Error::UnrecognizedOption {
-                                    name: String::from_utf8_lossy(&value).into(),
-                                    expecting: options
-                                        .iter()
-                                        .flat_map(|field| {
-                                            iter::once(field.name)
-                                                .chain(field.aliases.iter().copied())
-                                        })
-                                        .collect(),
-                                }))?;
-                            let mut found = false;
-                            let mut index = 0;
-                            while index < options.len() {
-                                let optional_field = &options[index];
-                                if let Some(static_field_name) = iter::once(optional_field.name)
-                                    .chain(optional_field.aliases.clone())
-                                    .find(|s| *s == identifier)
-                                {
-                                    let mut optional_field = options.remove(index);
-                                    found = true;
-                                    let parsed_context = parse_context(
-                                        args,
-                                        &mut optional_field.shape,
-                                        options,
-                                        Context { segments: vec![] },
-                                    );
-                                    parsed_options.extend(parsed_context.options);
-                                    parsed_options.push((
-                                        static_field_name,
-                                        Context {
-                                            segments: vec![
-                                                Segment::Identifier(static_field_name),
-                                                Segment::Context(parsed_context.context?),
-                                            ],
-                                        },
-                                    ));
-                                    if parsed_context.closing_end_of_options {
-                                        closing_end_of_options = true;
-                                    }
-                                    options.insert(index, optional_field);
-                                    break;
-                                } else {
-                                    index += 1;
+                        Token::Optional {
+                            name: value,
+                            argument,
+                        } => {
+                            let identifier = match str::from_utf8(&value) {
+                                Ok(identifier) => identifier,
+                                Err(_) => {
+                                    args.errors.push(Error::UnrecognizedOption {
+                                        name: value.into_owned(),
+                                        expecting: expecting_options(options.iter()),
+                                    });
+                                    continue;
                                 }
-                            }
-                            if !found {
-                                return Err(Error::UnrecognizedOption {
+                            };
+                            if let Some((static_field_name, parsed_context)) = parse_option(
+                                args,
+                                override_options,
+                                identifier,
+                                argument.starts_with(b"--"),
+                                options,
+                            ) {
+                                parsed_options.extend(parsed_context.options);
+                                parsed_options.push((static_field_name, parsed_context.context?));
+                                if parsed_context.closing_end_of_options {
+                                    closing_end_of_options = true;
+                                }
+                            } else {
+                                args.errors.push(Error::UnrecognizedOption {
                                     name: identifier.into(),
-                                    expecting: options
-                                        .iter()
-                                        .flat_map(|field| {
-                                            iter::once(field.name)
-                                                .chain(field.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    expecting: expecting_options(options.iter()),
                                 });
                             }
                         }
@@ -1040,20 +1774,15 @@ where
                                 .ok_or(Error::MissingArguments(vec![name.to_owned()]))?;
                             let variant_name_str = str::from_utf8(&variant_name).map_err(|_| {
                                 Error::UnrecognizedVariant {
-                                    name: String::from_utf8_lossy(&variant_name).into(),
-                                    expecting: variants
-                                        .iter()
-                                        .flat_map(|variant| {
-                                            iter::once(variant.name)
-                                                .chain(variant.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    name: variant_name.to_vec(),
+                                    expecting: expecting_variants(variants),
                                 }
                             })?;
+                            let variant_name_str =
+                                args.resolve_variant_index(variants, variant_name_str);
                             for variant in variants.clone() {
-                                if let Some(static_variant_name) = iter::once(variant.name)
-                                    .chain(variant.aliases)
-                                    .find(|s| *s == variant_name_str)
+                                if let Some(static_variant_name) =
+                                    args.find_variant_name(&variant, variant_name_str)
                                 {
                                     *shape = Shape::Variant {
                                         name: static_variant_name,
@@ -1070,8 +1799,13 @@ where
                                         context
                                             .segments
                                             .push(Segment::Identifier(static_variant_name));
-                                        context =
-                                            parse_context_no_options(args, inner_shape, context)?;
+                                        context = parse_context_no_options(
+                                            args,
+                                            override_options,
+                                            inner_shape,
+                                            context,
+                                            variant.raw,
+                                        )?;
                                     } else {
                                         unreachable!();
                                     }
@@ -1080,13 +1814,7 @@ where
                             }
                             return Err(Error::UnrecognizedVariant {
                                 name: variant_name_str.into(),
-                                expecting: variants
-                                    .iter()
-                                    .flat_map(|variant| {
-                                        iter::once(variant.name)
-                                            .chain(variant.aliases.iter().copied())
-                                    })
-                                    .collect(),
+                                expecting: expecting_variants(variants),
                             });
                         }
                     }
@@ -1100,115 +1828,94 @@ where
                 // Parse the variant.
                 loop {
                     let token = args
-                        .next_token()
+                        .next_token_with_options(options.iter().rev())
                         .ok_or(Error::MissingArguments(vec![enum_name.to_owned()]))?;
                     match token {
                         Token::Positional(variant_name) => {
                             let variant_name_str = str::from_utf8(&variant_name).map_err(|_| {
                                 Error::UnrecognizedVariant {
-                                    name: String::from_utf8_lossy(&variant_name).into(),
-                                    expecting: variants
-                                        .iter()
-                                        .flat_map(|variant| {
-                                            iter::once(variant.name)
-                                                .chain(variant.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    name: variant_name.to_vec(),
+                                    expecting: expecting_variants(variants),
                                 }
                             })?;
+                            let variant_name_str =
+                                args.resolve_variant_index(variants, variant_name_str);
                             let mut found = false;
                             for mut variant in variants.clone() {
-                                if let Some(static_variant_name) = iter::once(variant.name)
-                                    .chain(variant.aliases)
-                                    .find(|s| *s == variant_name_str)
+                                if let Some(static_variant_name) =
+                                    args.find_variant_name(&variant, variant_name_str)
                                 {
                                     context
                                         .segments
                                         .push(Segment::Identifier(static_variant_name));
+                                    found = true;
+                                    if variant.raw {
+                                        // Every remaining argument is positional.
+                                        context = parse_context_no_options(
+                                            args,
+                                            override_options,
+                                            &mut variant.shape,
+                                            context,
+                                            true,
+                                        )?;
+                                        break;
+                                    }
                                     // Parse the variant's shape.
-                                    let parsed_context =
-                                        parse_context(args, &mut variant.shape, options, context);
+                                    let parsed_context = parse_context(
+                                        args,
+                                        override_options,
+                                        &mut variant.shape,
+                                        options,
+                                        context,
+                                        false,
+                                    );
                                     // Handle options.
                                     parsed_options.extend(parsed_context.options);
                                     if parsed_context.closing_end_of_options {
                                         closing_end_of_options = true;
                                     }
                                     context = parsed_context.context?;
-                                    found = true;
                                     break;
                                 }
                             }
                             if !found {
                                 return Err(Error::UnrecognizedVariant {
                                     name: variant_name_str.into(),
-                                    expecting: variants
-                                        .iter()
-                                        .flat_map(|variant| {
-                                            iter::once(variant.name)
-                                                .chain(variant.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    expecting: expecting_variants(variants),
                                 });
                             }
                             break;
                         }
-                        Token::Optional(value) => {
-                            let identifier =
-                                str::from_utf8(&value).or(Err(Error::UnrecognizedOption {
-                                    name: String::from_utf8_lossy(&value).into(),
-                                    expecting: options
-                                        .iter()
-                                        .flat_map(|field| {
-                                            iter::once(field.name)
-                                                .chain(field.aliases.iter().copied())
-                                        })
-                                        .collect(),
-                                }))?;
-                            let mut found = false;
-                            let mut index = 0;
-                            while index < options.len() {
-                                let optional_field = &options[index];
-                                if let Some(static_field_name) = iter::once(optional_field.name)
-                                    .chain(optional_field.aliases.clone())
-                                    .find(|s| *s == identifier)
-                                {
-                                    let mut optional_field = options.remove(index);
-                                    found = true;
-                                    let parsed_context = parse_context(
-                                        args,
-                                        &mut optional_field.shape,
-                                        options,
-                                        Context { segments: vec![] },
-                                    );
-                                    parsed_options.extend(parsed_context.options);
-                                    parsed_options.push((
-                                        static_field_name,
-                                        Context {
-                                            segments: vec![
-                                                Segment::Identifier(static_field_name),
-                                                Segment::Context(parsed_context.context?),
-                                            ],
-                                        },
-                                    ));
-                                    if parsed_context.closing_end_of_options {
-                                        closing_end_of_options = true;
-                                    }
-                                    options.insert(index, optional_field);
-                                    break;
-                                } else {
-                                    index += 1;
+                        Token::Optional {
+                            name: value,
+                            argument,
+                        } => {
+                            let identifier = match str::from_utf8(&value) {
+                                Ok(identifier) => identifier,
+                                Err(_) => {
+                                    args.errors.push(Error::UnrecognizedOption {
+                                        name: value.into_owned(),
+                                        expecting: expecting_options(options.iter()),
+                                    });
+                                    continue;
                                 }
-                            }
-                            if !found {
-                                return Err(Error::UnrecognizedOption {
+                            };
+                            if let Some((static_field_name, parsed_context)) = parse_option(
+                                args,
+                                override_options,
+                                identifier,
+                                argument.starts_with(b"--"),
+                                options,
+                            ) {
+                                parsed_options.extend(parsed_context.options);
+                                parsed_options.push((static_field_name, parsed_context.context?));
+                                if parsed_context.closing_end_of_options {
+                                    closing_end_of_options = true;
+                                }
+                            } else {
+                                args.errors.push(Error::UnrecognizedOption {
                                     name: identifier.into(),
-                                    expecting: options
-                                        .iter()
-                                        .flat_map(|field| {
-                                            iter::once(field.name)
-                                                .chain(field.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    expecting: expecting_options(options.iter()),
                                 });
                             }
                         }
@@ -1218,29 +1925,26 @@ where
                                 .ok_or(Error::MissingArguments(vec![enum_name.to_owned()]))?;
                             let variant_name_str = str::from_utf8(&variant_name).map_err(|_| {
                                 Error::UnrecognizedVariant {
-                                    name: String::from_utf8_lossy(&variant_name).into(),
-                                    expecting: variants
-                                        .iter()
-                                        .flat_map(|variant| {
-                                            iter::once(variant.name)
-                                                .chain(variant.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    name: variant_name.to_vec(),
+                                    expecting: expecting_variants(variants),
                                 }
                             })?;
+                            let variant_name_str =
+                                args.resolve_variant_index(variants, variant_name_str);
                             let mut found = false;
                             for mut variant in variants.clone() {
-                                if let Some(static_variant_name) = iter::once(variant.name)
-                                    .chain(variant.aliases)
-                                    .find(|s| *s == variant_name_str)
+                                if let Some(static_variant_name) =
+                                    args.find_variant_name(&variant, variant_name_str)
                                 {
                                     context
                                         .segments
                                         .push(Segment::Identifier(static_variant_name));
                                     context = parse_context_no_options(
                                         args,
+                                        override_options,
                                         &mut variant.shape,
                                         context,
+                                        variant.raw,
                                     )?;
                                     found = true;
                                     break;
@@ -1249,13 +1953,7 @@ where
                             if !found {
                                 return Err(Error::UnrecognizedVariant {
                                     name: variant_name_str.into(),
-                                    expecting: variants
-                                        .iter()
-                                        .flat_map(|variant| {
-                                            iter::once(variant.name)
-                                                .chain(variant.aliases.iter().copied())
-                                        })
-                                        .collect(),
+                                    expecting: expecting_variants(variants),
                                 });
                             }
                             break;
@@ -1266,6 +1964,7 @@ where
         }
         Ok(context)
     })();
+    options.truncate(scope);
 
     ParsedContext {
         context,
@@ -1277,20 +1976,36 @@ where
 #[cfg(test)]
 mod tests {
     use super::{
+        expecting_options,
+        expecting_variants,
         parse,
+        parse_args,
+        parse_with_config,
+        Config,
         Context,
         Error,
+        Parsed,
         Segment,
     };
     use crate::trace::{
         Field,
+        PrimitiveKind,
         Shape,
         Variant,
     };
     use claims::{
+        assert_err,
         assert_err_eq,
+        assert_ok,
         assert_ok_eq,
     };
+    use std::{
+        ffi::OsString,
+        time::{
+            Duration,
+            Instant,
+        },
+    };
 
     #[test]
     fn parse_empty() {
@@ -1317,6 +2032,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }
             ),
             Context {
@@ -1325,6 +2041,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_primitive_dash() {
+        assert_ok_eq!(
+            parse(
+                ["-"],
+                &mut Shape::Primitive {
+                    name: "bar".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }
+            ),
+            Context {
+                segments: vec![Segment::Value("-".into())],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_sequence_dash() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "-", "bar"],
+                &mut Shape::Sequence(Box::new(Shape::Primitive {
+                    name: "bar".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }))
+            ),
+            Context {
+                segments: vec![
+                    Segment::Value("foo".into()),
+                    Segment::Value("-".into()),
+                    Segment::Value("bar".into()),
+                ],
+            }
+        );
+    }
+
     #[test]
     fn parse_primitive_no_args() {
         assert_err_eq!(
@@ -1334,10 +2090,55 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }
             ),
             // No arguments at all when arguments are expected should trigger help.
-            Error::Help
+            Error::Help { short: false }
+        );
+    }
+
+    #[test]
+    fn parse_primitive_no_args_empty_help_disabled() {
+        assert_err_eq!(
+            parse_with_config(
+                Vec::<&str>::new(),
+                &mut Shape::Primitive {
+                    name: "bar".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                },
+                Config {
+                    empty_help: false,
+                    ..Config::default()
+                }
+            ),
+            Error::MissingArguments(vec!["bar".to_owned()])
+        );
+    }
+
+    #[test]
+    fn parse_struct_no_args_empty_help_disabled() {
+        assert_err_eq!(
+            parse_with_config(
+                Vec::<&str>::new(),
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new("foo", primitive("bar"), 0)],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+                Config {
+                    empty_help: false,
+                    ..Config::default()
+                }
+            ),
+            Error::MissingArguments(vec!["foo".to_owned()])
         );
     }
 
@@ -1350,6 +2151,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }
             ),
             Error::MissingArguments(vec!["bar".to_owned()])
@@ -1365,6 +2167,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }
             ),
             Context {
@@ -1402,7 +2205,7 @@ mod tests {
                 }
             ),
             // No arguments at all when arguments are expected should trigger help.
-            Error::Help
+            Error::Help { short: false }
         );
     }
 
@@ -1482,6 +2285,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_optional_empty_help() {
+        assert_err_eq!(
+            parse(["-", "--help"], &mut Shape::Optional(Box::new(empty()))),
+            Error::Help { short: false }
+        );
+    }
+
     #[test]
     fn parse_optional_primitive() {
         assert_ok_eq!(
@@ -1491,6 +2302,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }))
             ),
             Context {
@@ -1510,6 +2322,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }))
             ),
             Context { segments: vec![] }
@@ -1525,6 +2338,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }))
             ),
             Context {
@@ -1544,6 +2358,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }))
             ),
             Context { segments: vec![] }
@@ -1627,9 +2442,11 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
@@ -1647,9 +2464,11 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context { segments: vec![] }
@@ -1665,9 +2484,11 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context { segments: vec![] }
@@ -1683,19 +2504,20 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
-                    required: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    examples: None,
+                    required: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
@@ -1717,19 +2539,20 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
-                    required: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    examples: None,
+                    required: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context { segments: vec![] }
@@ -1745,19 +2568,20 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
-                    required: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    examples: None,
+                    required: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
@@ -1779,31 +2603,30 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Empty {
+                        Field::new(
+                            "baz",
+                            Shape::Empty {
                                 description: String::new(),
                                 version: None,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        }
+                            1
+                        )
                     ],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
@@ -1830,30 +2653,28 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Empty {
+                        Field::new(
+                            "bar",
+                            Shape::Empty {
                                 description: String::new(),
                                 version: None,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Empty {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Empty {
                                 description: String::new(),
                                 version: None,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
@@ -1880,32 +2701,32 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "quux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        }
+                            1
+                        )
                     ],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
@@ -1938,42 +2759,41 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "quux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        }
+                            1
+                        )
                     ],
-                    optional: vec![Field {
-                        name: "baz",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    optional: vec![Field::new(
+                        "baz",
+                        Shape::Primitive {
                             name: "string".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 2,
-                    }],
+                        2
+                    )],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
@@ -2009,52 +2829,106 @@ mod tests {
     fn parse_optional_struct_optional_fields_from_outer_context_not_allowed() {
         assert_err_eq!(
             parse(
-                ["--foo", "--help", "123", "--baz", "quux"],
+                ["--foo", "--corge", "123", "--baz", "quux"],
                 &mut Shape::Optional(Box::new(Shape::Struct {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "quux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        }
+                            1
+                        )
                     ],
-                    optional: vec![Field {
-                        name: "baz",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    optional: vec![Field::new(
+                        "baz",
+                        Shape::Primitive {
                             name: "string".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 2,
-                    }],
+                        2
+                    )],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Error::UnrecognizedOption {
-                name: "help".into(),
-                expecting: vec!["baz"]
+                name: "corge".into(),
+                expecting: vec!["baz", "help", "h"]
+            }
+        );
+    }
+
+    /// A positional optional struct with two required fields and one optional field.
+    fn optional_struct(version: Option<String>) -> Shape {
+        Shape::Optional(Box::new(Shape::Struct {
+            name: "",
+            description: String::new(),
+            version,
+            examples: None,
+            required: vec![
+                Field::new("bar", primitive("baz"), 0),
+                Field::new("qux", primitive("quux"), 1),
+            ],
+            optional: vec![Field::new("baz", primitive("string"), 2)],
+            booleans: vec![],
+            constraints: vec![],
+        }))
+    }
+
+    #[test]
+    fn parse_optional_struct_help_inside_context() {
+        assert_err_eq!(
+            parse(["--foo", "--help", "123"], &mut optional_struct(None)),
+            Error::Help { short: false }
+        );
+    }
+
+    #[test]
+    fn parse_optional_struct_help_after_optional_field() {
+        assert_err_eq!(
+            parse(["--foo", "--baz", "--help"], &mut optional_struct(None)),
+            Error::Help { short: false }
+        );
+    }
+
+    #[test]
+    fn parse_optional_struct_version_inside_context() {
+        assert_err_eq!(
+            parse(
+                ["--foo", "--version"],
+                &mut optional_struct(Some("1.0.0".into()))
+            ),
+            Error::Version
+        );
+    }
+
+    #[test]
+    fn parse_optional_struct_version_not_enabled_inside_context() {
+        assert_err_eq!(
+            parse(["--foo", "--version", "123"], &mut optional_struct(None)),
+            Error::UnrecognizedOption {
+                name: "version".into(),
+                expecting: vec!["baz", "help", "h"],
             }
         );
     }
@@ -2068,37 +2942,40 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "string".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        }
+                            1
+                        )
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             Context {
                 segments: vec![Segment::Context(Context {
                     segments: vec![
+                        Segment::Context(Context {
+                            segments: vec![Segment::Identifier("bar")]
+                        }),
                         Segment::Context(Context {
                             segments: vec![
                                 Segment::Identifier("baz"),
@@ -2106,11 +2983,8 @@ mod tests {
                                     segments: vec![Segment::Value("quux".into())]
                                 })
                             ]
-                        }),
-                        Segment::Context(Context {
-                            segments: vec![Segment::Identifier("bar")]
                         })
-                    ],
+                    ]
                 })]
             }
         );
@@ -2125,45 +2999,43 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Empty {
+                        Field::new(
+                            "bar",
+                            Shape::Empty {
                                 description: String::new(),
                                 version: None,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Empty {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Empty {
                                 description: String::new(),
                                 version: None,
                             },
-                            index: 1,
-                        }
+                            1
+                        )
                     ],
+                    constraints: vec![],
                 }))
             ),
             Context {
                 segments: vec![Segment::Context(Context {
                     segments: vec![
+                        Segment::Context(Context {
+                            segments: vec![Segment::Identifier("bar")]
+                        }),
                         Segment::Context(Context {
                             segments: vec![
                                 Segment::Identifier("baz"),
-                                Segment::Context(Context { segments: vec![] }),
+                                Segment::Context(Context { segments: vec![] })
                             ]
-                        }),
-                        Segment::Context(Context {
-                            segments: vec![Segment::Identifier("bar"),]
                         })
-                    ],
+                    ]
                 })]
             }
         );
@@ -2178,11 +3050,15 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![Variant {
                         name: "foo",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2207,11 +3083,15 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![Variant {
                         name: "foo",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec!["f"],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2221,7 +3101,7 @@ mod tests {
             ),
             Context {
                 segments: vec![Segment::Context(Context {
-                    segments: vec![Segment::Identifier("f")],
+                    segments: vec![Segment::Identifier("foo")],
                 })]
             }
         );
@@ -2236,11 +3116,15 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![Variant {
                         name: "foo",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2261,15 +3145,20 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![Variant {
                         name: "foo",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Primitive {
                             name: "string".into(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         }
                     }],
                 }))
@@ -2291,11 +3180,15 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![Variant {
                         name: "",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2320,9 +3213,11 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context { segments: vec![] }
@@ -2338,19 +3233,20 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
-                    required: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    examples: None,
+                    required: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
@@ -2370,32 +3266,32 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "string".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "string".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        }
+                            1
+                        )
                     ],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
@@ -2420,19 +3316,20 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
-                    optional: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    optional: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
@@ -2452,19 +3349,20 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
-                    optional: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    optional: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
@@ -2489,25 +3387,29 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![Field {
-                        name: "bar",
-                        description: String::new(),
                         aliases: vec!["qux"],
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     }],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
                 segments: vec![Segment::Context(Context {
                     segments: vec![
-                        Segment::Identifier("qux"),
+                        Segment::Identifier("bar"),
                         Segment::Context(Context {
                             segments: vec![Segment::Value("foo".into())]
                         })
@@ -2526,26 +3428,30 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![Field {
-                        name: "bar",
-                        description: String::new(),
                         aliases: vec!["qux"],
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     }],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
                 segments: vec![
                     Segment::Context(Context {
                         segments: vec![
-                            Segment::Identifier("qux"),
+                            Segment::Identifier("bar"),
                             Segment::Context(Context {
                                 segments: vec![Segment::Value("foo".into())]
                             })
@@ -2573,18 +3479,18 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
-                    booleans: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Empty {
+                    booleans: vec![Field::new(
+                        "bar",
+                        Shape::Empty {
                             description: String::new(),
                             version: None,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
+                    constraints: vec![],
                 }
             ),
             Context {
@@ -2604,18 +3510,18 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
-                    booleans: vec![Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Empty {
+                    booleans: vec![Field::new(
+                        "bar",
+                        Shape::Empty {
                             description: String::new(),
                             version: None,
                         },
-                        index: 0,
-                    }],
+                        0
+                    )],
+                    constraints: vec![],
                 }
             ),
             Context {
@@ -2638,24 +3544,27 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![Field {
-                        name: "bar",
-                        description: String::new(),
                         aliases: vec!["qux"],
-                        shape: Shape::Empty {
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "bar",
+                            Shape::Empty {
+                                description: String::new(),
+                                version: None,
+                            },
+                            0
+                        )
                     }],
+                    constraints: vec![],
                 }
             ),
             Context {
                 segments: vec![Segment::Context(Context {
                     segments: vec![
-                        Segment::Identifier("qux"),
+                        Segment::Identifier("bar"),
                         Segment::Context(Context { segments: vec![] })
                     ]
                 })]
@@ -2672,25 +3581,28 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![Field {
-                        name: "bar",
-                        description: String::new(),
                         aliases: vec!["qux"],
-                        shape: Shape::Empty {
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "bar",
+                            Shape::Empty {
+                                description: String::new(),
+                                version: None,
+                            },
+                            0
+                        )
                     }],
+                    constraints: vec![],
                 }
             ),
             Context {
                 segments: vec![
                     Segment::Context(Context {
                         segments: vec![
-                            Segment::Identifier("qux"),
+                            Segment::Identifier("bar"),
                             Segment::Context(Context { segments: vec![] })
                         ]
                     }),
@@ -2714,72 +3626,72 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "quux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "quux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 2,
-                        },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            2
+                        ),
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 3,
-                        },
-                        Field {
-                            name: "missing",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            3
+                        ),
+                        Field::new(
+                            "missing",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 4,
-                        },
+                            4
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
                 segments: vec![
                     Segment::Context(Context {
-                        segments: vec![Segment::Identifier("foo"), Segment::Value("123".into())],
+                        segments: vec![Segment::Identifier("foo"), Segment::Value("123".into())]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("quux"), Segment::Value("456".into())]
                     }),
                     Segment::Context(Context {
                         segments: vec![
@@ -2787,10 +3699,7 @@ mod tests {
                             Segment::Context(Context {
                                 segments: vec![Segment::Value("foo".into())]
                             })
-                        ],
-                    }),
-                    Segment::Context(Context {
-                        segments: vec![Segment::Identifier("quux"), Segment::Value("456".into())],
+                        ]
                     }),
                     Segment::Context(Context {
                         segments: vec![
@@ -2798,11 +3707,11 @@ mod tests {
                             Segment::Context(Context {
                                 segments: vec![Segment::Value("789".into())]
                             })
-                        ],
+                        ]
                     }),
                     Segment::Context(Context {
-                        segments: vec![Segment::Identifier("missing")],
-                    }),
+                        segments: vec![Segment::Identifier("missing")]
+                    })
                 ]
             }
         );
@@ -2817,15 +3726,15 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "inner_struct",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Struct {
+                        Field::new(
+                            "inner_struct",
+                            Shape::Struct {
                                 name: "",
                                 description: String::new(),
                                 version: None,
+                                examples: None,
                                 required: vec![Field {
                                     name: "foo",
                                     description: String::new(),
@@ -2834,8 +3743,17 @@ mod tests {
                                         name: "baz".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 0,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },],
                                 optional: vec![Field {
                                     name: "bar",
@@ -2845,62 +3763,62 @@ mod tests {
                                         name: "baz".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 1,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },],
                                 booleans: vec![],
+                                constraints: vec![],
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "quux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "quux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 2,
-                        },
-                        Field {
-                            name: "missing",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            2
+                        ),
+                        Field::new(
+                            "missing",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 3,
-                        },
+                            3
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
                 segments: vec![
-                    Segment::Context(Context {
-                        segments: vec![
-                            Segment::Identifier("qux"),
-                            Segment::Context(Context {
-                                segments: vec![Segment::Value("789".into())]
-                            })
-                        ],
-                    }),
                     Segment::Context(Context {
                         segments: vec![
                             Segment::Identifier("inner_struct"),
@@ -2908,24 +3826,32 @@ mod tests {
                                 segments: vec![
                                     Segment::Identifier("foo"),
                                     Segment::Value("123".into())
-                                ],
+                                ]
                             }),
                             Segment::Context(Context {
                                 segments: vec![
                                     Segment::Identifier("bar"),
                                     Segment::Context(Context {
                                         segments: vec![Segment::Value("foo".into())]
-                                    }),
+                                    })
                                 ]
-                            }),
-                        ],
+                            })
+                        ]
                     }),
                     Segment::Context(Context {
-                        segments: vec![Segment::Identifier("quux"), Segment::Value("456".into())],
+                        segments: vec![Segment::Identifier("quux"), Segment::Value("456".into())]
                     }),
                     Segment::Context(Context {
-                        segments: vec![Segment::Identifier("missing")]
+                        segments: vec![
+                            Segment::Identifier("qux"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("789".into())]
+                            })
+                        ]
                     }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("missing")]
+                    })
                 ]
             }
         );
@@ -2940,72 +3866,72 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "quux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "quux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![
-                        Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 2,
-                        },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            2
+                        ),
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 3,
-                        },
-                        Field {
-                            name: "missing",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            3
+                        ),
+                        Field::new(
+                            "missing",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 4,
-                        },
+                            4
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
                 segments: vec![
                     Segment::Context(Context {
-                        segments: vec![Segment::Identifier("foo"), Segment::Value("123".into())],
+                        segments: vec![Segment::Identifier("foo"), Segment::Value("123".into())]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("quux"), Segment::Value("--qux".into())]
                     }),
                     Segment::Context(Context {
                         segments: vec![
@@ -3013,16 +3939,13 @@ mod tests {
                             Segment::Context(Context {
                                 segments: vec![Segment::Value("foo".into())]
                             })
-                        ],
-                    }),
-                    Segment::Context(Context {
-                        segments: vec![Segment::Identifier("quux"), Segment::Value("--qux".into())],
+                        ]
                     }),
                     Segment::Context(Context {
-                        segments: vec![Segment::Identifier("qux")],
+                        segments: vec![Segment::Identifier("qux")]
                     }),
                     Segment::Context(Context {
-                        segments: vec![Segment::Identifier("missing")],
+                        segments: vec![Segment::Identifier("missing")]
                     })
                 ]
             }
@@ -3038,26 +3961,25 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "quux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "quux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "inner_struct",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Struct {
+                            0
+                        ),
+                        Field::new(
+                            "inner_struct",
+                            Shape::Struct {
                                 name: "",
                                 description: String::new(),
                                 version: None,
+                                examples: None,
                                 required: vec![Field {
                                     name: "foo",
                                     description: String::new(),
@@ -3066,8 +3988,17 @@ mod tests {
                                         name: "baz".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 0,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },],
                                 optional: vec![Field {
                                     name: "bar",
@@ -3077,39 +4008,48 @@ mod tests {
                                         name: "baz".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 1,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },],
                                 booleans: vec![],
+                                constraints: vec![],
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 2,
-                        },
-                        Field {
-                            name: "missing",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            2
+                        ),
+                        Field::new(
+                            "missing",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 3,
-                        },
+                            3
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             Context {
@@ -3156,11 +4096,15 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![Variant {
                         name: "foo",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3183,12 +4127,16 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![
                         Variant {
                             name: "foo",
                             description: String::new(),
                             version: None,
+                            group: None,
+                            raw: false,
                             aliases: vec![],
+                            deprecated_aliases: Vec::new(),
                             shape: Shape::Empty {
                                 description: String::new(),
                                 version: None,
@@ -3198,7 +4146,10 @@ mod tests {
                             name: "bar",
                             description: String::new(),
                             version: None,
+                            group: None,
+                            raw: false,
                             aliases: vec![],
+                            deprecated_aliases: Vec::new(),
                             shape: Shape::Empty {
                                 description: String::new(),
                                 version: None,
@@ -3222,11 +4173,15 @@ mod tests {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![Variant {
                         name: "foo",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec!["f"],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3235,122 +4190,4329 @@ mod tests {
                 }
             ),
             Context {
-                segments: vec![Segment::Identifier("f")],
+                segments: vec![Segment::Identifier("foo")],
             }
         );
     }
 
-    #[test]
-    fn parse_enum_with_value() {
-        assert_ok_eq!(
-            parse(
-                ["foo", "bar"],
-                &mut Shape::Enum {
-                    name: "Enum",
+    fn numbered_enum() -> Shape {
+        Shape::Enum {
+            name: "Enum",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants: vec![
+                Variant {
+                    name: "foo",
                     description: String::new(),
                     version: None,
-                    variants: vec![Variant {
-                        name: "foo",
+                    group: None,
+                    raw: false,
+                    aliases: vec![],
+                    deprecated_aliases: Vec::new(),
+                    shape: Shape::Empty {
                         description: String::new(),
                         version: None,
-                        aliases: vec![],
-                        shape: Shape::Primitive {
-                            name: "string".into(),
-                            description: String::new(),
-                            version: None,
-                        }
-                    }],
-                }
-            ),
+                    },
+                },
+                Variant {
+                    name: "bar",
+                    description: String::new(),
+                    version: None,
+                    group: None,
+                    raw: false,
+                    aliases: vec![],
+                    deprecated_aliases: Vec::new(),
+                    shape: Shape::Empty {
+                        description: String::new(),
+                        version: None,
+                    },
+                },
+                Variant {
+                    name: "1",
+                    description: String::new(),
+                    version: None,
+                    group: None,
+                    raw: false,
+                    aliases: vec![],
+                    deprecated_aliases: Vec::new(),
+                    shape: Shape::Empty {
+                        description: String::new(),
+                        version: None,
+                    },
+                },
+            ],
+        }
+    }
+
+    fn match_command_indices() -> Config {
+        Config {
+            match_command_indices: true,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn parse_enum_numeric_name() {
+        assert_ok_eq!(
+            parse(["1"], &mut numbered_enum()),
             Context {
-                segments: vec![Segment::Identifier("foo"), Segment::Value("bar".into())],
+                segments: vec![Segment::Identifier("1")],
             }
         );
     }
 
     #[test]
-    fn parse_enum_after_end_of_options() {
-        assert_ok_eq!(
-            parse(
+    fn parse_enum_index_disabled() {
+        assert_err_eq!(
+            parse(["0"], &mut numbered_enum()),
+            Error::UnrecognizedVariant {
+                name: "0".into(),
+                expecting: vec!["foo", "bar", "1"],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_index() {
+        assert_ok_eq!(
+            parse_with_config(["0"], &mut numbered_enum(), match_command_indices()),
+            Context {
+                segments: vec![Segment::Identifier("foo")],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_index_name_takes_priority() {
+        assert_ok_eq!(
+            parse_with_config(["1"], &mut numbered_enum(), match_command_indices()),
+            Context {
+                segments: vec![Segment::Identifier("1")],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_index_after_end_of_options() {
+        assert_ok_eq!(
+            parse_with_config(["--", "2"], &mut numbered_enum(), match_command_indices()),
+            Context {
+                segments: vec![Segment::Identifier("1")],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_index_out_of_range() {
+        assert_err_eq!(
+            parse_with_config(["3"], &mut numbered_enum(), match_command_indices()),
+            Error::UnrecognizedVariant {
+                name: "3".into(),
+                expecting: vec!["foo", "bar", "1"],
+            }
+        );
+    }
+
+    fn empty_enum() -> Shape {
+        Shape::Enum {
+            name: "Never",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_enum_no_variants() {
+        assert_err_eq!(
+            parse(["foo"], &mut empty_enum()),
+            Error::NoVariants("Never".into())
+        );
+    }
+
+    #[test]
+    fn parse_enum_no_variants_no_args() {
+        assert_err_eq!(
+            parse_with_config(
+                Vec::<&str>::new(),
+                &mut empty_enum(),
+                Config {
+                    empty_help: false,
+                    ..Config::default()
+                }
+            ),
+            Error::NoVariants("Never".into())
+        );
+    }
+
+    #[test]
+    fn parse_enum_no_variants_after_end_of_options() {
+        assert_err_eq!(
+            parse(["--", "foo"], &mut empty_enum()),
+            Error::NoVariants("Never".into())
+        );
+    }
+
+    #[test]
+    fn parse_enum_no_variants_help() {
+        assert_err_eq!(
+            parse(["--help"], &mut empty_enum()),
+            Error::Help { short: false }
+        );
+    }
+
+    #[test]
+    fn parse_enum_with_value() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "bar"],
+                &mut Shape::Enum {
+                    name: "Enum",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    variants: vec![Variant {
+                        name: "foo",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Primitive {
+                            name: "string".into(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        }
+                    }],
+                }
+            ),
+            Context {
+                segments: vec![Segment::Identifier("foo"), Segment::Value("bar".into())],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_after_end_of_options() {
+        assert_ok_eq!(
+            parse(
                 ["--", "foo"],
                 &mut Shape::Enum {
                     name: "Enum",
                     description: String::new(),
                     version: None,
+                    examples: None,
+                    variants: vec![Variant {
+                        name: "foo",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        }
+                    }],
+                }
+            ),
+            Context {
+                segments: vec![Segment::Identifier("foo")],
+            }
+        );
+    }
+
+    /// A struct containing a nested struct with an option, followed by another positional
+    /// argument. If `shared` is set, the outer struct declares an option with the same name.
+    fn nested_option(shared: bool) -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![
+                Field::new(
+                    "inner",
+                    Shape::Struct {
+                        name: "",
+                        description: String::new(),
+                        version: None,
+                        examples: None,
+                        required: vec![Field::new("value", primitive("value"), 0)],
+                        optional: vec![Field::new("opt", primitive("opt"), 1)],
+                        booleans: vec![],
+                        constraints: vec![],
+                    },
+                    0,
+                ),
+                Field::new("second", primitive("second"), 1),
+            ],
+            optional: if shared {
+                vec![Field::new("opt", primitive("opt"), 2)]
+            } else {
+                vec![]
+            },
+            booleans: vec![],
+            constraints: vec![],
+        }
+    }
+
+    fn nested_option_context(inner_opt: Option<&str>, outer_opt: Option<Option<&str>>) -> Context {
+        let option = |value: Option<&str>| {
+            Segment::Context(Context {
+                segments: match value {
+                    Some(value) => vec![
+                        Segment::Identifier("opt"),
+                        Segment::Context(Context {
+                            segments: vec![Segment::Value(value.into())],
+                        }),
+                    ],
+                    None => vec![Segment::Identifier("opt")],
+                },
+            })
+        };
+        let mut segments = vec![
+            Segment::Context(Context {
+                segments: vec![
+                    Segment::Identifier("inner"),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("value"), Segment::Value("val1".into())],
+                    }),
+                    option(inner_opt),
+                ],
+            }),
+            Segment::Context(Context {
+                segments: vec![Segment::Identifier("second"), Segment::Value("val2".into())],
+            }),
+        ];
+        if let Some(outer_opt) = outer_opt {
+            segments.push(option(outer_opt));
+        }
+        Context { segments }
+    }
+
+    #[test]
+    fn parse_struct_nested_option_interleaved() {
+        assert_ok_eq!(
+            parse(
+                vec!["val1", "--opt", "foo", "val2"],
+                &mut nested_option(false)
+            ),
+            nested_option_context(Some("foo"), None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_nested_option_late() {
+        assert_ok_eq!(
+            parse(
+                vec!["val1", "val2", "--opt", "foo"],
+                &mut nested_option(false)
+            ),
+            nested_option_context(Some("foo"), None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_nested_option_shared_within_span() {
+        assert_ok_eq!(
+            parse(
+                vec!["--opt", "foo", "val1", "val2"],
+                &mut nested_option(true)
+            ),
+            nested_option_context(Some("foo"), Some(None))
+        );
+    }
+
+    #[test]
+    fn parse_struct_nested_option_shared_after_span() {
+        assert_ok_eq!(
+            parse(
+                vec!["val1", "--opt", "foo", "val2", "--opt", "bar"],
+                &mut nested_option(true)
+            ),
+            nested_option_context(Some("foo"), Some(Some("bar")))
+        );
+    }
+
+    #[test]
+    fn parse_struct_nested_option_shared_late() {
+        assert_ok_eq!(
+            parse(
+                vec!["val1", "val2", "--opt", "foo"],
+                &mut nested_option(true)
+            ),
+            nested_option_context(None, Some(Some("foo")))
+        );
+    }
+
+    fn required_option() -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("path", primitive("path"), 0)],
+            optional: vec![Field {
+                required: true,
+                ..Field::new("out", primitive("out"), 1)
+            }],
+            booleans: vec![],
+            constraints: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_struct_required_option() {
+        assert_ok_eq!(
+            parse(vec!["foo", "--out", "bar"], &mut required_option()),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("path"), Segment::Value("foo".into())],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("out"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("bar".into())],
+                            }),
+                        ],
+                    }),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_required_option_missing() {
+        assert_err_eq!(
+            parse(vec!["foo"], &mut required_option()),
+            Error::MissingOptions(vec!["out".into()])
+        );
+    }
+
+    #[test]
+    fn parse_struct_required_option_missing_with_other_errors() {
+        assert_err_eq!(
+            parse(vec!["foo", "--unknown"], &mut required_option()),
+            Error::Multiple(vec![
+                Error::UnrecognizedOption {
+                    name: "unknown".into(),
+                    expecting: vec!["out", "help", "h"],
+                },
+                Error::MissingOptions(vec!["out".into()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_struct_only_required_option_no_arguments() {
+        let mut shape = required_option();
+        if let Shape::Struct { required, .. } = &mut shape {
+            required.clear();
+        }
+
+        assert_err_eq!(
+            parse(Vec::<&str>::new(), &mut shape),
+            Error::Help { short: false }
+        );
+    }
+
+    #[test]
+    fn parse_struct_nested_required_option_late() {
+        let mut shape = nested_option(false);
+        if let Shape::Struct { required, .. } = &mut shape {
+            if let Shape::Struct { optional, .. } = &mut required[0].shape {
+                optional[0].required = true;
+            }
+        }
+
+        assert_ok_eq!(
+            parse(vec!["val1", "val2", "--opt", "foo"], &mut shape),
+            nested_option_context(Some("foo"), None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_nested_required_option_missing() {
+        let mut shape = nested_option(false);
+        if let Shape::Struct { required, .. } = &mut shape {
+            if let Shape::Struct { optional, .. } = &mut required[0].shape {
+                optional[0].required = true;
+            }
+        }
+
+        assert_err_eq!(
+            parse(vec!["val1", "val2"], &mut shape),
+            Error::MissingOptions(vec!["opt".into()])
+        );
+    }
+
+    /// A struct with an optional enum of unit variants given as flags.
+    fn flags_option(required: bool) -> Shape {
+        let variant = |name, aliases| Variant {
+            name,
+            description: String::new(),
+            version: None,
+            group: None,
+            raw: false,
+            aliases,
+            deprecated_aliases: Vec::new(),
+            shape: empty(),
+        };
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("path", primitive("path"), 0)],
+            optional: vec![Field {
+                required,
+                flags: true,
+                multiple: false,
+                deprecated_aliases: Vec::new(),
+                ..Field::new(
+                    "format",
+                    Shape::Enum {
+                        name: "Format",
+                        description: String::new(),
+                        version: None,
+                        examples: None,
+                        variants: vec![
+                            variant("json", vec![]),
+                            variant("yaml", vec!["y"]),
+                            variant("toml", vec![]),
+                        ],
+                    },
+                    1,
+                )
+            }],
+            booleans: vec![],
+            constraints: vec![],
+        }
+    }
+
+    fn flags_option_context(variant: Option<&'static str>) -> Context {
+        Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("path"), Segment::Value("foo".into())],
+                }),
+                Segment::Context(Context {
+                    segments: match variant {
+                        Some(variant) => vec![
+                            Segment::Identifier("format"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Identifier(variant)],
+                            }),
+                        ],
+                        None => vec![Segment::Identifier("format")],
+                    },
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_struct_flags_none() {
+        assert_ok_eq!(
+            parse(vec!["foo"], &mut flags_option(false)),
+            flags_option_context(None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_flags() {
+        assert_ok_eq!(
+            parse(vec!["--toml", "foo"], &mut flags_option(false)),
+            flags_option_context(Some("toml"))
+        );
+    }
+
+    #[test]
+    fn parse_struct_flags_alias() {
+        assert_ok_eq!(
+            parse(vec!["foo", "-y"], &mut flags_option(false)),
+            flags_option_context(Some("yaml"))
+        );
+    }
+
+    #[test]
+    fn parse_struct_flags_conflicting() {
+        assert_err_eq!(
+            parse(vec!["--json", "foo", "-y"], &mut flags_option(false)),
+            Error::ConflictingFlags {
+                first: "json".into(),
+                second: "yaml".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_flags_repeated() {
+        assert_err_eq!(
+            parse(vec!["--yaml", "foo", "-y"], &mut flags_option(false)),
+            Error::RepeatedFlag("yaml".into())
+        );
+    }
+
+    #[test]
+    fn parse_struct_flags_unrecognized() {
+        assert_err_eq!(
+            parse(vec!["foo", "--format", "json"], &mut flags_option(false)),
+            Error::Multiple(vec![
+                Error::UnrecognizedOption {
+                    name: "format".into(),
+                    expecting: vec!["json", "yaml", "y", "toml", "help", "h"],
+                },
+                Error::TooManyArguments {
+                    command_path: vec![],
+                    arguments: vec!["path".into()],
+                    optional: None,
+                    first_unexpected: "json".into(),
+                    count: 1,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_struct_flags_required_missing() {
+        assert_err_eq!(
+            parse(vec!["foo"], &mut flags_option(true)),
+            Error::MissingOneOfOptions(vec!["json".into(), "yaml".into(), "toml".into()])
+        );
+    }
+
+    fn empty() -> Shape {
+        Shape::Empty {
+            description: String::new(),
+            version: None,
+        }
+    }
+
+    fn primitive(name: &str) -> Shape {
+        Shape::Primitive {
+            name: name.to_owned(),
+            description: String::new(),
+            version: None,
+            kind: PrimitiveKind::Str,
+        }
+    }
+
+    /// An enum with an all-boolean variant, an all-optional variant, and a variant mixing booleans
+    /// with required fields.
+    fn command() -> Shape {
+        let variant = |name, shape| Variant {
+            name,
+            description: String::new(),
+            version: None,
+            group: None,
+            raw: false,
+            aliases: vec![],
+            deprecated_aliases: Vec::new(),
+            shape,
+        };
+        let fields = |required, optional, booleans| Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required,
+            optional,
+            booleans,
+            constraints: vec![],
+        };
+        Shape::Enum {
+            name: "Command",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants: vec![
+                variant(
+                    "clean",
+                    fields(
+                        vec![],
+                        vec![],
+                        vec![
+                            Field::new("dry_run", empty(), 0),
+                            Field::new("verbose", empty(), 1),
+                        ],
+                    ),
+                ),
+                variant(
+                    "opt",
+                    fields(
+                        vec![],
+                        vec![
+                            Field::new("a", primitive("a"), 0),
+                            Field::new("b", primitive("b"), 1),
+                        ],
+                        vec![],
+                    ),
+                ),
+                variant(
+                    "mixed",
+                    fields(
+                        vec![
+                            Field::new("path", primitive("path"), 1),
+                            Field::new("count", primitive("count"), 2),
+                        ],
+                        vec![],
+                        vec![Field::new("dry_run", empty(), 0)],
+                    ),
+                ),
+            ],
+        }
+    }
+
+    fn clean_context() -> Context {
+        Context {
+            segments: vec![
+                Segment::Identifier("clean"),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("dry_run")],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("verbose")],
+                }),
+            ],
+        }
+    }
+
+    fn opt_context() -> Context {
+        Context {
+            segments: vec![
+                Segment::Identifier("opt"),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("a")],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("b")],
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_enum_only_boolean_fields_variant() {
+        assert_ok_eq!(parse(["clean"], &mut command()), clean_context());
+    }
+
+    #[test]
+    fn parse_enum_only_boolean_fields_variant_after_end_of_options() {
+        assert_ok_eq!(parse(["--", "clean"], &mut command()), clean_context());
+    }
+
+    #[test]
+    fn parse_enum_only_boolean_fields_variant_end_of_options() {
+        assert_ok_eq!(parse(["clean", "--"], &mut command()), clean_context());
+    }
+
+    #[test]
+    fn parse_optional_enum_only_boolean_fields_variant() {
+        assert_ok_eq!(
+            parse(["--clean"], &mut Shape::Optional(Box::new(command()))),
+            Context {
+                segments: vec![Segment::Context(clean_context())],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_only_optional_fields_variant() {
+        assert_ok_eq!(parse(["opt"], &mut command()), opt_context());
+    }
+
+    #[test]
+    fn parse_enum_only_optional_fields_variant_after_end_of_options() {
+        assert_ok_eq!(parse(["--", "opt"], &mut command()), opt_context());
+    }
+
+    #[test]
+    fn parse_enum_only_optional_fields_variant_end_of_options() {
+        assert_ok_eq!(parse(["opt", "--"], &mut command()), opt_context());
+    }
+
+    #[test]
+    fn parse_optional_enum_only_optional_fields_variant() {
+        assert_ok_eq!(
+            parse(["--opt"], &mut Shape::Optional(Box::new(command()))),
+            Context {
+                segments: vec![Segment::Context(opt_context())],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_mixed_fields_variant_missing_required() {
+        assert_err_eq!(
+            parse(["mixed"], &mut command()),
+            Error::MissingArguments(vec!["path".into(), "count".into()])
+        );
+    }
+
+    #[test]
+    fn parse_enum_mixed_fields_variant_missing_required_after_end_of_options() {
+        assert_err_eq!(
+            parse(["--", "mixed"], &mut command()),
+            Error::MissingArguments(vec!["path".into(), "count".into()])
+        );
+    }
+
+    #[test]
+    fn parse_enum_mixed_fields_variant_missing_required_end_of_options() {
+        assert_err_eq!(
+            parse(["mixed", "--"], &mut command()),
+            Error::MissingArguments(vec!["path".into(), "count".into()])
+        );
+    }
+
+    #[test]
+    fn parse_optional_enum_mixed_fields_variant_missing_required() {
+        assert_err_eq!(
+            parse(["--mixed"], &mut Shape::Optional(Box::new(command()))),
+            Error::MissingArguments(vec!["path".into(), "count".into()])
+        );
+    }
+
+    /// A struct with an optional struct field `rect` taking two values, and a boolean field
+    /// `verbose`.
+    fn rect_option() -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![],
+            optional: vec![Field::new(
+                "rect",
+                Shape::Struct {
+                    name: "Rect",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new("width", primitive("u32"), 0),
+                        Field::new("height", primitive("u32"), 1),
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+                0,
+            )],
+            booleans: vec![Field::new("verbose", empty(), 1)],
+            constraints: vec![],
+        }
+    }
+
+    fn rect_option_context() -> Context {
+        Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("rect"),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Context(Context {
+                                    segments: vec![
+                                        Segment::Identifier("width"),
+                                        Segment::Value("10".into()),
+                                    ],
+                                }),
+                                Segment::Context(Context {
+                                    segments: vec![
+                                        Segment::Identifier("height"),
+                                        Segment::Value("20".into()),
+                                    ],
+                                }),
+                            ],
+                        }),
+                    ],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("verbose")],
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_struct_option_values() {
+        assert_ok_eq!(
+            parse(["--rect", "10", "20"], &mut rect_option()),
+            rect_option_context()
+        );
+    }
+
+    #[test]
+    fn parse_struct_option_values_after_end_of_options() {
+        assert_ok_eq!(
+            parse(["--rect", "--", "10", "20"], &mut rect_option()),
+            rect_option_context()
+        );
+    }
+
+    #[test]
+    fn parse_struct_option_values_end_of_options_between() {
+        assert_ok_eq!(
+            parse(["--rect", "10", "--", "20"], &mut rect_option()),
+            rect_option_context()
+        );
+    }
+
+    #[test]
+    fn parse_struct_option_missing_value() {
+        assert_err_eq!(
+            parse(["--rect", "10"], &mut rect_option()),
+            Error::MissingOptionArguments {
+                name: "rect".into(),
+                arguments: vec!["height".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_option_missing_values() {
+        assert_err_eq!(
+            parse(["--rect"], &mut rect_option()),
+            Error::MissingOptionArguments {
+                name: "rect".into(),
+                arguments: vec!["width".into(), "height".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_option_missing_value_before_option() {
+        assert_err_eq!(
+            parse(["--rect", "10", "--verbose"], &mut rect_option()),
+            Error::MissingOptionArguments {
+                name: "rect".into(),
+                arguments: vec!["height".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_option_missing_value_after_end_of_options() {
+        assert_err_eq!(
+            parse(["--rect", "10", "--"], &mut rect_option()),
+            Error::MissingOptionArguments {
+                name: "rect".into(),
+                arguments: vec!["height".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_option_dash_value() {
+        assert_ok_eq!(
+            parse(["--rect", "10", "-"], &mut rect_option()),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("rect"),
+                            Segment::Context(Context {
+                                segments: vec![
+                                    Segment::Context(Context {
+                                        segments: vec![
+                                            Segment::Identifier("width"),
+                                            Segment::Value("10".into()),
+                                        ]
+                                    }),
+                                    Segment::Context(Context {
+                                        segments: vec![
+                                            Segment::Identifier("height"),
+                                            Segment::Value("-".into()),
+                                        ]
+                                    }),
+                                ]
+                            }),
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("verbose")]
+                    }),
+                ]
+            }
+        );
+    }
+
+    /// A struct with a required field, an optional field `o`, and boolean fields `a` and `b`.
+    fn short_options() -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("path", primitive("path"), 0)],
+            optional: vec![Field::new("o", primitive("file"), 1)],
+            booleans: vec![Field::new("a", empty(), 2), Field::new("b", empty(), 3)],
+            constraints: vec![],
+        }
+    }
+
+    fn short_option_value_context() -> Context {
+        Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("path"), Segment::Value("bar".into())],
+                }),
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("o"),
+                        Segment::Context(Context {
+                            segments: vec![Segment::Value("foo".into())],
+                        }),
+                    ],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("a")],
+                }),
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("b")],
+                }),
+            ],
+        }
+    }
+
+    fn grouped_short_options_context() -> Context {
+        Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("path"), Segment::Value("bar".into())],
+                }),
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("o"),
+                        Segment::Context(Context {
+                            segments: vec![Segment::Value("foo".into())],
+                        }),
+                    ],
+                }),
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("a"),
+                        Segment::Context(Context { segments: vec![] }),
+                    ],
+                }),
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("b"),
+                        Segment::Context(Context { segments: vec![] }),
+                    ],
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_short_option_separate_value() {
+        assert_ok_eq!(
+            parse(["-o", "foo", "bar"], &mut short_options()),
+            short_option_value_context()
+        );
+    }
+
+    #[test]
+    fn parse_short_option_joined_value() {
+        assert_ok_eq!(
+            parse(["-ofoo", "bar"], &mut short_options()),
+            short_option_value_context()
+        );
+    }
+
+    #[test]
+    fn parse_short_option_joined_value_equals() {
+        assert_ok_eq!(
+            parse(["-o=foo", "bar"], &mut short_options()),
+            short_option_value_context()
+        );
+    }
+
+    #[test]
+    fn parse_short_options_grouped() {
+        assert_ok_eq!(
+            parse(["bar", "-ba"], &mut short_options()),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("path"), Segment::Value("bar".into())],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("o")],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("a"),
+                            Segment::Context(Context { segments: vec![] }),
+                        ],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("b"),
+                            Segment::Context(Context { segments: vec![] }),
+                        ],
+                    }),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_short_options_grouped_with_value() {
+        assert_ok_eq!(
+            parse(["-abo", "foo", "bar"], &mut short_options()),
+            grouped_short_options_context()
+        );
+    }
+
+    #[test]
+    fn parse_short_options_grouped_with_joined_value() {
+        assert_ok_eq!(
+            parse(["-abofoo", "bar"], &mut short_options()),
+            grouped_short_options_context()
+        );
+    }
+
+    #[test]
+    fn parse_short_options_grouped_unrecognized() {
+        assert_err_eq!(
+            parse(["-ax", "bar"], &mut short_options()),
+            Error::UnrecognizedOption {
+                name: "x".into(),
+                expecting: vec!["o", "a", "b", "help", "h"],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_sequence() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "bar", "baz"],
+                &mut Shape::Sequence(Box::new(Shape::Primitive {
+                    name: "foo".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }))
+            ),
+            Context {
+                segments: vec![
+                    Segment::Value("foo".into()),
+                    Segment::Value("bar".into()),
+                    Segment::Value("baz".into()),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_sequence_empty() {
+        assert_ok_eq!(
+            parse(
+                Vec::<&str>::new(),
+                &mut Shape::Sequence(Box::new(Shape::Primitive {
+                    name: "foo".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }))
+            ),
+            Context { segments: vec![] }
+        );
+    }
+
+    #[test]
+    fn parse_sequence_end_of_options() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "--", "--bar", "-"],
+                &mut Shape::Sequence(Box::new(Shape::Primitive {
+                    name: "foo".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }))
+            ),
+            Context {
+                segments: vec![
+                    Segment::Value("foo".into()),
+                    Segment::Value("--bar".into()),
+                    Segment::Value("-".into()),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_sequence_variant() {
+        assert_ok_eq!(
+            parse(
+                ["files", "a", "b", "c"],
+                &mut Shape::Enum {
+                    name: "Command",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    variants: vec![Variant {
+                        name: "files",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Sequence(Box::new(Shape::Primitive {
+                            name: "file".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        })),
+                    }],
+                }
+            ),
+            Context {
+                segments: vec![
+                    Segment::Identifier("files"),
+                    Segment::Value("a".into()),
+                    Segment::Value("b".into()),
+                    Segment::Value("c".into()),
+                ],
+            }
+        );
+    }
+
+    /// An enum with a single variant named `run`, containing a struct with a sequence and an
+    /// option.
+    fn run_command(raw: bool) -> Shape {
+        Shape::Enum {
+            name: "Command",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants: vec![Variant {
+                name: "run",
+                description: String::new(),
+                version: None,
+                group: None,
+                raw,
+                aliases: vec![],
+                deprecated_aliases: Vec::new(),
+                shape: Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new(
+                        "args",
+                        Shape::Sequence(Box::new(Shape::Primitive {
+                            name: "arg".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        })),
+                        0,
+                    )],
+                    optional: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        1,
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            }],
+        }
+    }
+
+    #[test]
+    fn parse_enum_raw_variant() {
+        assert_ok_eq!(
+            parse(["run", "--bar", "baz", "-h"], &mut run_command(true)),
+            Context {
+                segments: vec![
+                    Segment::Identifier("run"),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("args"),
+                            Segment::Value("--bar".into()),
+                            Segment::Value("baz".into()),
+                            Segment::Value("-h".into()),
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    }),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_raw_variant_end_of_options() {
+        assert_ok_eq!(
+            parse(["run", "--", "--bar"], &mut run_command(true)),
+            Context {
+                segments: vec![
+                    Segment::Identifier("run"),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("args"),
+                            Segment::Value("--".into()),
+                            Segment::Value("--bar".into()),
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    }),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_raw_variant_after_end_of_options() {
+        assert_ok_eq!(
+            parse(["--", "run", "--bar"], &mut run_command(true)),
+            Context {
+                segments: vec![
+                    Segment::Identifier("run"),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("args"), Segment::Value("--bar".into()),]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    }),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_enum_not_raw_variant() {
+        assert_ok_eq!(
+            parse(["run", "--bar", "baz", "qux"], &mut run_command(false)),
+            Context {
+                segments: vec![
+                    Segment::Identifier("run"),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("args"), Segment::Value("qux".into()),]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("bar"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("baz".into())]
+                            })
+                        ]
+                    }),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_sequence_with_options() {
+        assert_ok_eq!(
+            parse(
+                ["a", "--bar", "baz", "b"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new(
+                        "files",
+                        Shape::Sequence(Box::new(Shape::Primitive {
+                            name: "file".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        })),
+                        0
+                    )],
+                    optional: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        1
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("files"),
+                            Segment::Value("a".into()),
+                            Segment::Value("b".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("bar"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("baz".into())]
+                            })
+                        ]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_long() {
+        assert_ok_eq!(
+            parse(
+                vec!["--fix-things"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        allow_hyphen_values: true,
+                        ..Field::new(
+                            "message",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![Field {
+                        aliases: vec!["b"],
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("message"),
+                            Segment::Value("--fix-things".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_short() {
+        assert_ok_eq!(
+            parse(
+                vec!["-x"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        allow_hyphen_values: true,
+                        ..Field::new(
+                            "message",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![Field {
+                        aliases: vec!["b"],
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("message"), Segment::Value("-x".into())]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_single_hyphen() {
+        assert_ok_eq!(
+            parse(
+                vec!["-"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        allow_hyphen_values: true,
+                        ..Field::new(
+                            "message",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![Field {
+                        aliases: vec!["b"],
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("message"), Segment::Value("-".into())]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_matching_option() {
+        assert_ok_eq!(
+            parse(
+                vec!["-b", "foo", "--fix"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        allow_hyphen_values: true,
+                        ..Field::new(
+                            "message",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![Field {
+                        aliases: vec!["b"],
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("message"),
+                            Segment::Value("--fix".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("bar"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("foo".into())]
+                            })
+                        ]
+                    })
+                ]
+            }
+        );
+    }
+
+    /// A struct with a required field allowing hyphen values, an option taking a value, and a
+    /// boolean.
+    fn allow_hyphen_values_struct() -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field {
+                allow_hyphen_values: true,
+                ..Field::new("message", primitive("baz"), 0)
+            }],
+            optional: vec![Field::new("bar", primitive("qux"), 1)],
+            booleans: vec![Field::new("verbose", empty(), 2)],
+            constraints: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_long_single_character() {
+        assert_ok_eq!(
+            parse(vec!["--x"], &mut allow_hyphen_values_struct()),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("message"),
+                            Segment::Value("--x".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("verbose")]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_short_single_character() {
+        assert_ok_eq!(
+            parse(vec!["-x"], &mut allow_hyphen_values_struct()),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("message"), Segment::Value("-x".into())]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("verbose")]
+                    })
+                ]
+            }
+        );
+    }
+
+    /// A struct with a required number, an option taking a number, an option taking a sequence of
+    /// numbers, and the given booleans.
+    fn numeric_struct(booleans: Vec<Field>) -> Shape {
+        let number = |name: &str, kind| Shape::Primitive {
+            name: name.to_owned(),
+            description: String::new(),
+            version: None,
+            kind,
+        };
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("level", number("u8", PrimitiveKind::U8), 0)],
+            optional: vec![
+                Field::new("ratio", number("f64", PrimitiveKind::F64), 1),
+                Field::new(
+                    "offsets",
+                    Shape::Sequence(Box::new(number("i32", PrimitiveKind::I32))),
+                    2,
+                ),
+            ],
+            booleans,
+            constraints: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_struct_negative_numbers() {
+        assert_ok_eq!(
+            parse(
+                vec![
+                    "-2",
+                    "--ratio",
+                    "-0",
+                    "--offsets",
+                    "-1",
+                    "--offsets",
+                    "-1e5",
+                    "--offsets",
+                    "-inf"
+                ],
+                &mut numeric_struct(vec![])
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("level"), Segment::Value("-2".into())]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("ratio"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("-0".into())]
+                            })
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("offsets"),
+                            Segment::Context(Context {
+                                segments: vec![
+                                    Segment::Value("-1".into()),
+                                    Segment::Value("-1e5".into()),
+                                    Segment::Value("-inf".into())
+                                ]
+                            })
+                        ]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_negative_number_matching_option() {
+        // An option in scope takes precedence over a negative number.
+        assert_ok_eq!(
+            parse(
+                vec!["0", "--ratio", "-1", "5"],
+                &mut numeric_struct(vec![Field::new("1", empty(), 3)])
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("level"), Segment::Value("0".into())]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("ratio"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("5".into())]
+                            })
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("offsets")]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("1"),
+                            Segment::Context(Context { segments: vec![] })
+                        ]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_negative_number_not_numeric() {
+        // Only values of numeric fields may begin with a hyphen without allowing hyphen values.
+        assert_err_eq!(
+            parse(
+                vec!["-1"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new("message", primitive("baz"), 0)],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Error::Multiple(vec![
+                Error::UnrecognizedOption {
+                    name: "1".into(),
+                    expecting: vec!["help", "h"],
+                },
+                Error::MissingArguments(vec!["message".into()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_known_option_joined_value() {
+        // The argument names an option in scope, so it is parsed as that option rather than used
+        // as the value.
+        assert_err_eq!(
+            parse(vec!["--verbose=no"], &mut allow_hyphen_values_struct()),
+            Error::Multiple(vec![
+                Error::UnrecognizedOption {
+                    name: "verbose=no".into(),
+                    expecting: vec!["bar", "verbose", "help", "h"],
+                },
+                Error::MissingArguments(vec!["message".into()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_unknown_option_joined_value() {
+        assert_ok_eq!(
+            parse(vec!["--unknown=value"], &mut allow_hyphen_values_struct()),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("message"),
+                            Segment::Value("--unknown=value".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("verbose")]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_after_end_of_options() {
+        assert_ok_eq!(
+            parse(
+                vec!["--", "--bar"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        allow_hyphen_values: true,
+                        ..Field::new(
+                            "message",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![Field {
+                        aliases: vec!["b"],
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("message"),
+                            Segment::Value("--bar".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("bar")]
+                    })
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_allow_hyphen_values_not_allowed() {
+        assert_err_eq!(
+            parse(
+                vec!["--fix"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new(
+                        "message",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Error::Multiple(vec![
+                Error::UnrecognizedOption {
+                    name: "fix".into(),
+                    expecting: vec!["help", "h"],
+                },
+                Error::MissingArguments(vec!["message".into()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_struct_optional_positional_present() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "bar"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "repository",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field::new(
+                            "directory",
+                            Shape::Optional(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            1
+                        ),
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("repository"),
+                            Segment::Value("foo".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("directory"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("bar".into())]
+                            }),
+                        ]
+                    }),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_optional_positional_absent() {
+        assert_ok_eq!(
+            parse(
+                ["foo"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "repository",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field::new(
+                            "directory",
+                            Shape::Optional(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            1
+                        ),
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("repository"),
+                            Segment::Value("foo".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("directory")]
+                    }),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_optional_positional_too_many() {
+        assert_err_eq!(
+            parse(
+                ["foo", "bar", "baz"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "repository",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field::new(
+                            "directory",
+                            Shape::Optional(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            1
+                        ),
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::TooManyArguments {
+                command_path: vec![],
+                arguments: vec!["repository".into()],
+                optional: Some("directory".into()),
+                first_unexpected: "baz".into(),
+                count: 1,
+            }
+        );
+    }
+
+    fn multiple_struct() -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new(
+                "name",
+                Shape::Primitive {
+                    name: "a string".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                },
+                0,
+            )],
+            optional: vec![
+                Field {
+                    multiple: true,
+                    ..Field::new(
+                        "nums",
+                        Shape::Sequence(Box::new(Shape::Primitive {
+                            name: "a number".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::U32,
+                        })),
+                        1,
+                    )
+                },
+                Field::new(
+                    "label",
+                    Shape::Primitive {
+                        name: "a string".to_owned(),
+                        description: String::new(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    2,
+                ),
+            ],
+            booleans: vec![],
+            constraints: vec![],
+        }
+    }
+
+    fn multiple_context(name: &str, nums: &[&str], label: Option<&str>) -> Context {
+        Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![Segment::Identifier("name"), Segment::Value(name.into())],
+                }),
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("nums"),
+                        Segment::Context(Context {
+                            segments: nums
+                                .iter()
+                                .map(|num| Segment::Value((*num).into()))
+                                .collect(),
+                        }),
+                    ],
+                }),
+                Segment::Context(Context {
+                    segments: match label {
+                        Some(label) => vec![
+                            Segment::Identifier("label"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value(label.into())],
+                            }),
+                        ],
+                        None => vec![Segment::Identifier("label")],
+                    },
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_struct_multiple() {
+        assert_ok_eq!(
+            parse(["foo", "--nums", "1", "2", "3"], &mut multiple_struct()),
+            multiple_context("foo", &["1", "2", "3"], None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_multiple_end_of_options() {
+        assert_ok_eq!(
+            parse(["--nums", "1", "2", "--", "foo"], &mut multiple_struct()),
+            multiple_context("foo", &["1", "2"], None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_multiple_end_of_options_hyphen_value() {
+        assert_ok_eq!(
+            parse(["--nums", "1", "--", "--foo"], &mut multiple_struct()),
+            multiple_context("--foo", &["1"], None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_multiple_until_option() {
+        assert_ok_eq!(
+            parse(
+                ["--nums", "1", "2", "--label", "bar", "foo"],
+                &mut multiple_struct()
+            ),
+            multiple_context("foo", &["1", "2"], Some("bar"))
+        );
+    }
+
+    #[test]
+    fn parse_struct_multiple_joined() {
+        assert_ok_eq!(
+            parse(["foo", "--nums=1", "2"], &mut multiple_struct()),
+            multiple_context("foo", &["1", "2"], None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_multiple_repeated() {
+        assert_ok_eq!(
+            parse(
+                ["--nums", "1", "--label", "bar", "--nums", "2", "3", "--", "foo"],
+                &mut multiple_struct()
+            ),
+            multiple_context("foo", &["1", "2", "3"], Some("bar"))
+        );
+    }
+
+    #[test]
+    fn parse_struct_multiple_no_values() {
+        assert_ok_eq!(
+            parse(["foo", "--nums"], &mut multiple_struct()),
+            multiple_context("foo", &[], None)
+        );
+    }
+
+    #[test]
+    fn parse_struct_multiple_consumes_positional() {
+        assert_err_eq!(
+            parse(["--nums", "1", "foo"], &mut multiple_struct()),
+            Error::MissingArguments(vec!["name".into()])
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter() {
+        assert_ok_eq!(
+            parse(
+                ["--tags", "foo,bar,baz"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("tags"),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Value("foo".into()),
+                                Segment::Value("bar".into()),
+                                Segment::Value("baz".into())
+                            ]
+                        }),
+                    ]
+                })]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter_single_value() {
+        assert_ok_eq!(
+            parse(
+                ["--tags", "foo"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("tags"),
+                        Segment::Context(Context {
+                            segments: vec![Segment::Value("foo".into())]
+                        }),
+                    ]
+                })]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter_repeated() {
+        assert_ok_eq!(
+            parse(
+                ["--tags", "foo,bar", "-t", "baz", "--tags", "qux"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("tags"),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Value("foo".into()),
+                                Segment::Value("bar".into()),
+                                Segment::Value("baz".into()),
+                                Segment::Value("qux".into())
+                            ]
+                        }),
+                    ]
+                })]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter_takes_single_value() {
+        assert_err_eq!(
+            parse(
+                ["--tags", "foo", "bar"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::TooManyArguments {
+                command_path: vec![],
+                arguments: vec![],
+                optional: None,
+                first_unexpected: "bar".into(),
+                count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter_empty_element() {
+        assert_err_eq!(
+            parse(
+                ["--tags", "foo,,bar"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::EmptyDelimitedValue {
+                name: "tags".into()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter_leading_delimiter() {
+        assert_err_eq!(
+            parse(
+                ["-t", ",foo"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::EmptyDelimitedValue { name: "t".into() }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter_empty() {
+        assert_err_eq!(
+            parse(
+                ["--tags", ""],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::EmptyDelimitedValue {
+                name: "tags".into()
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_delimiter_trailing_delimiter() {
+        assert_err_eq!(
+            parse(
+                ["--tags", "foo,bar,"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::TrailingDelimiter {
+                name: "tags".into(),
+                delimiter: ',',
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_struct_value_delimiter_non_utf8() {
+        use std::os::unix::ffi::OsStringExt;
+
+        assert_ok_eq!(
+            parse(
+                [
+                    OsString::from("--tags"),
+                    OsString::from_vec(b"foo\xff,bar".to_vec())
+                ],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        aliases: vec!["t"],
+                        value_delimiter: Some(b','),
+                        ..Field::new(
+                            "tags",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("tags"),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Value(b"foo\xff".to_vec()),
+                                Segment::Value("bar".into())
+                            ]
+                        }),
+                    ]
+                })]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_trailing() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "bar", "--verbose", "-x", "--"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "program",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field {
+                            trailing: true,
+                            ..Field::new(
+                                "args",
+                                Shape::Sequence(Box::new(Shape::Primitive {
+                                    name: "an argument".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Bytes,
+                                })),
+                                1
+                            )
+                        },
+                    ],
+                    optional: vec![],
+                    booleans: vec![Field::new(
+                        "verbose",
+                        Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        },
+                        2
+                    )],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("program"),
+                            Segment::Value("foo".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("args"),
+                            Segment::Value("bar".into()),
+                            Segment::Value("--verbose".into()),
+                            Segment::Value("-x".into()),
+                            Segment::Value("--".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("verbose")]
+                    }),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_trailing_options_before() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "--verbose", "bar", "--verbose"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "program",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field {
+                            trailing: true,
+                            ..Field::new(
+                                "args",
+                                Shape::Sequence(Box::new(Shape::Primitive {
+                                    name: "an argument".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Bytes,
+                                })),
+                                1
+                            )
+                        },
+                    ],
+                    optional: vec![],
+                    booleans: vec![Field::new(
+                        "verbose",
+                        Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        },
+                        2
+                    )],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("program"),
+                            Segment::Value("foo".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("args"),
+                            Segment::Value("bar".into()),
+                            Segment::Value("--verbose".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("verbose"),
+                            Segment::Context(Context { segments: vec![] })
+                        ]
+                    }),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_trailing_unrecognized_option() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "--bar", "baz"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "program",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field {
+                            trailing: true,
+                            ..Field::new(
+                                "args",
+                                Shape::Sequence(Box::new(Shape::Primitive {
+                                    name: "an argument".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Bytes,
+                                })),
+                                1
+                            )
+                        },
+                    ],
+                    optional: vec![],
+                    booleans: vec![Field::new(
+                        "verbose",
+                        Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        },
+                        2
+                    )],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("program"),
+                            Segment::Value("foo".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("args"),
+                            Segment::Value("--bar".into()),
+                            Segment::Value("baz".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("verbose")]
+                    }),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_trailing_end_of_options() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "--verbose", "--", "--verbose", "--"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "program",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field {
+                            trailing: true,
+                            ..Field::new(
+                                "args",
+                                Shape::Sequence(Box::new(Shape::Primitive {
+                                    name: "an argument".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Bytes,
+                                })),
+                                1
+                            )
+                        },
+                    ],
+                    optional: vec![],
+                    booleans: vec![Field::new(
+                        "verbose",
+                        Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        },
+                        2
+                    )],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("program"),
+                            Segment::Value("foo".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("args"),
+                            Segment::Value("--verbose".into()),
+                            Segment::Value("--".into())
+                        ]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("verbose"),
+                            Segment::Context(Context { segments: vec![] })
+                        ]
+                    }),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_name_missing() {
+        assert_err_eq!(
+            parse(
+                ["--"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field {
+                            value_name: Some("FILE".into()),
+                            ..Field::new(
+                                "input",
+                                Shape::Primitive {
+                                    name: "a string".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                0
+                            )
+                        },
+                        Field {
+                            value_name: Some("DIRECTORY".into()),
+                            ..Field::new(
+                                "output",
+                                Shape::Primitive {
+                                    name: "a string".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                1
+                            )
+                        },
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::MissingArguments(vec!["FILE".into(), "DIRECTORY".into()]),
+        );
+    }
+
+    #[test]
+    fn parse_struct_value_name_matches_field_name() {
+        assert_ok_eq!(
+            parse(
+                ["foo", "--output", "bar"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        value_name: Some("FILE".into()),
+                        ..Field::new(
+                            "input",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![Field {
+                        value_name: Some("DIRECTORY".into()),
+                        ..Field::new(
+                            "output",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("input"), Segment::Value("foo".into()),]
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("output"),
+                            Segment::Context(Context {
+                                segments: vec![Segment::Value("bar".into())]
+                            }),
+                        ]
+                    }),
+                ]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_multiple_errors_missing_arguments_and_unrecognized_option() {
+        assert_err_eq!(
+            parse(
+                ["--qux"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "foo".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "bar".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        ),
+                    ],
+                    optional: vec![Field::new(
+                        "baz",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        2
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Error::Multiple(vec![
+                Error::UnrecognizedOption {
+                    name: "qux".into(),
+                    expecting: vec!["baz", "help", "h"],
+                },
+                Error::MissingArguments(vec!["foo".into(), "bar".into()]),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_multiple_errors_unexpected_arguments() {
+        assert_err_eq!(
+            parse(
+                ["foo", "bar", "--baz"],
+                &mut Shape::Primitive {
+                    name: "primitive".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                },
+            ),
+            Error::Multiple(vec![
+                Error::TooManyArguments {
+                    command_path: vec![],
+                    arguments: vec!["primitive".into()],
+                    optional: None,
+                    first_unexpected: "bar".into(),
+                    count: 1,
+                },
+                Error::UnrecognizedOption {
+                    name: "baz".into(),
+                    expecting: vec!["help", "h"],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_multiple_errors_help_takes_precedence() {
+        assert_err_eq!(
+            parse(
+                ["--qux", "--help"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "foo".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "bar".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        ),
+                    ],
+                    optional: vec![Field::new(
+                        "baz",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        2
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Error::Help { short: false }
+        );
+    }
+
+    #[test]
+    fn parse_unrecognized_variant_stops_parsing() {
+        assert_err_eq!(
+            parse(
+                ["bar", "--baz", "qux"],
+                &mut Shape::Enum {
+                    name: "Enum",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    variants: vec![Variant {
+                        name: "foo",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        }
+                    }],
+                }
+            ),
+            Error::UnrecognizedVariant {
+                name: "bar".into(),
+                expecting: vec!["foo"],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_help() {
+        assert_err_eq!(
+            parse(
+                ["--help"],
+                &mut Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+            ),
+            Error::Help { short: false },
+        );
+    }
+
+    #[test]
+    fn parse_help_short() {
+        assert_err_eq!(
+            parse(
+                ["-h"],
+                &mut Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+            ),
+            Error::Help { short: true },
+        );
+    }
+
+    #[test]
+    fn parse_help_as_option_value() {
+        assert_err_eq!(
+            parse(
+                ["--output", "--help"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field::new(
+                        "output",
+                        Shape::Primitive {
+                            name: "path".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::Help { short: false },
+        );
+    }
+
+    #[test]
+    fn parse_help_short_as_option_value() {
+        assert_err_eq!(
+            parse(
+                ["--output", "-h"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field::new(
+                        "output",
+                        Shape::Primitive {
+                            name: "path".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::Help { short: true },
+        );
+    }
+
+    #[test]
+    fn parse_help_after_subcommand() {
+        assert_err_eq!(
+            parse(
+                ["foo", "--help"],
+                &mut Shape::Enum {
+                    name: "Enum",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    variants: vec![Variant {
+                        name: "foo",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                    }],
+                },
+            ),
+            Error::Help { short: false },
+        );
+    }
+
+    #[test]
+    fn parse_help_between_positionals() {
+        assert_err_eq!(
+            parse(
+                ["foo", "--help", "bar"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        ),
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        ),
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::Help { short: false },
+        );
+    }
+
+    #[test]
+    fn parse_help_as_allowed_hyphen_value() {
+        assert_err_eq!(
+            parse(
+                ["--help"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        allow_hyphen_values: true,
+                        ..Field::new(
+                            "pattern",
+                            Shape::Primitive {
+                                name: "pattern".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::Help { short: false },
+        );
+    }
+
+    #[test]
+    fn parse_version_as_option_value() {
+        assert_err_eq!(
+            parse(
+                ["--output", "--version"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: Some("1.0.0".into()),
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field::new(
+                        "output",
+                        Shape::Primitive {
+                            name: "path".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::Version,
+        );
+    }
+
+    #[test]
+    fn parse_version() {
+        assert_err_eq!(
+            parse(
+                ["--version"],
+                &mut Shape::Empty {
+                    description: String::new(),
+                    version: Some("foo".into()),
+                },
+            ),
+            Error::Version,
+        );
+    }
+
+    #[test]
+    fn parse_version_not_available() {
+        assert_err_eq!(
+            parse(
+                ["--version"],
+                &mut Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+            ),
+            Error::UnrecognizedOption {
+                name: "version".into(),
+                expecting: vec!["help", "h"]
+            },
+        );
+    }
+
+    /// An enum with a unit variant and a variant taking two required arguments.
+    fn remote() -> Shape {
+        Shape::Enum {
+            name: "Remote",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants: vec![
+                Variant {
+                    name: "stop",
+                    description: String::new(),
+                    version: None,
+                    group: None,
+                    raw: false,
+                    aliases: vec![],
+                    deprecated_aliases: Vec::new(),
+                    shape: empty(),
+                },
+                Variant {
+                    name: "push",
+                    description: String::new(),
+                    version: None,
+                    group: None,
+                    raw: false,
+                    aliases: vec![],
+                    deprecated_aliases: Vec::new(),
+                    shape: Shape::Struct {
+                        name: "",
+                        description: String::new(),
+                        version: None,
+                        examples: None,
+                        required: vec![
+                            Field::new("remote", primitive("remote"), 0),
+                            Field::new("refspec", primitive("refspec"), 1),
+                        ],
+                        optional: vec![],
+                        booleans: vec![],
+                        constraints: vec![],
+                    },
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_too_many_arguments_after_struct() {
+        assert_err_eq!(
+            parse(
+                ["foo", "bar", "baz"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new("path", primitive("path"), 0)],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::TooManyArguments {
+                command_path: vec![],
+                arguments: vec!["path".into()],
+                optional: None,
+                first_unexpected: "bar".into(),
+                count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_too_many_arguments_after_unit_variant() {
+        assert_err_eq!(
+            parse(["stop", "foo"], &mut remote()),
+            Error::TooManyArguments {
+                command_path: vec!["stop".into()],
+                arguments: vec![],
+                optional: None,
+                first_unexpected: "foo".into(),
+                count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_too_many_arguments_after_end_of_options() {
+        assert_err_eq!(
+            parse(
+                ["push", "origin", "main", "--", "foo", "--bar"],
+                &mut remote()
+            ),
+            Error::TooManyArguments {
+                command_path: vec!["push".into()],
+                arguments: vec!["remote".into(), "refspec".into()],
+                optional: None,
+                first_unexpected: "foo".into(),
+                count: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_too_many_arguments_nested_variant() {
+        assert_err_eq!(
+            parse(
+                ["config", "push", "origin", "main", "foo"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new("config", primitive("config"), 0),
+                        Field::new("remote", remote(), 1),
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+            ),
+            Error::TooManyArguments {
+                command_path: vec!["push".into()],
+                arguments: vec!["remote".into(), "refspec".into()],
+                optional: None,
+                first_unexpected: "foo".into(),
+                count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_too_many_arguments_around_unrecognized_option() {
+        assert_err_eq!(
+            parse(["stop", "--foo", "bar", "baz"], &mut remote()),
+            Error::Multiple(vec![
+                Error::UnrecognizedOption {
+                    name: "foo".into(),
+                    expecting: vec!["help", "h"],
+                },
+                Error::TooManyArguments {
+                    command_path: vec!["stop".into()],
+                    arguments: vec![],
+                    optional: None,
+                    first_unexpected: "bar".into(),
+                    count: 2,
+                },
+            ])
+        );
+    }
+
+    /// A struct with a global `--verbose` flag and a `command` field containing `remote()`.
+    fn global_options() -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("command", remote(), 0)],
+            optional: vec![],
+            booleans: vec![Field::new("verbose", empty(), 1)],
+            constraints: vec![],
+        }
+    }
+
+    fn global_options_context() -> Context {
+        Context {
+            segments: vec![
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("command"),
+                        Segment::Identifier("push"),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Identifier("remote"),
+                                Segment::Value("origin".into()),
+                            ],
+                        }),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Identifier("refspec"),
+                                Segment::Value("main".into()),
+                            ],
+                        }),
+                    ],
+                }),
+                Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("verbose"),
+                        Segment::Context(Context { segments: vec![] }),
+                    ],
+                }),
+            ],
+        }
+    }
+
+    #[test]
+    fn parse_global_option_before_command() {
+        assert_ok_eq!(
+            parse(
+                ["--verbose", "push", "origin", "main"],
+                &mut global_options()
+            ),
+            global_options_context()
+        );
+    }
+
+    #[test]
+    fn parse_global_option_within_command() {
+        assert_ok_eq!(
+            parse(
+                ["push", "--verbose", "origin", "main"],
+                &mut global_options()
+            ),
+            global_options_context()
+        );
+    }
+
+    #[test]
+    fn parse_global_option_after_command() {
+        assert_ok_eq!(
+            parse(
+                ["push", "origin", "main", "--verbose"],
+                &mut global_options()
+            ),
+            global_options_context()
+        );
+    }
+
+    #[test]
+    fn parse_global_option_before_unit_command() {
+        assert_ok_eq!(
+            parse(["--verbose", "stop"], &mut global_options()),
+            Context {
+                segments: vec![
+                    Segment::Context(Context {
+                        segments: vec![Segment::Identifier("command"), Segment::Identifier("stop")],
+                    }),
+                    Segment::Context(Context {
+                        segments: vec![
+                            Segment::Identifier("verbose"),
+                            Segment::Context(Context { segments: vec![] }),
+                        ],
+                    }),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_global_option_before_missing_command() {
+        assert_err_eq!(
+            parse(["--verbose"], &mut global_options()),
+            Error::MissingCommand {
+                name: "command".into(),
+                expecting: vec!["stop", "push"],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_missing_command_after_positional() {
+        assert_err_eq!(
+            parse(
+                ["origin"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new("remote", primitive("remote"), 0),
+                        Field::new("command", remote(), 1)
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            Error::MissingCommand {
+                name: "command".into(),
+                expecting: vec!["stop", "push"],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_missing_command_not_last() {
+        assert_err_eq!(
+            parse(
+                ["--verbose"],
+                &mut Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new("command", remote(), 0),
+                        Field::new("remote", primitive("remote"), 1)
+                    ],
+                    optional: vec![],
+                    booleans: vec![Field::new("verbose", empty(), 2)],
+                    constraints: vec![],
+                }
+            ),
+            Error::MissingArguments(vec!["command".into(), "remote".into()])
+        );
+    }
+
+    #[test]
+    fn expecting_options_declaration_order() {
+        assert_eq!(
+            expecting_options(&[
+                Field {
+                    aliases: vec!["f"],
+                    ..Field::new("force", empty(), 0)
+                },
+                Field::new("output", primitive("output"), 1),
+            ]),
+            vec!["force", "f", "output"]
+        );
+    }
+
+    #[test]
+    fn expecting_options_deduplicated() {
+        assert_eq!(
+            expecting_options(&[
+                Field {
+                    aliases: vec!["f"],
+                    ..Field::new("force", empty(), 0)
+                },
+                Field::new("output", primitive("output"), 1),
+                Field {
+                    aliases: vec!["f"],
+                    ..Field::new("force", empty(), 0)
+                },
+            ]),
+            vec!["force", "f", "output"]
+        );
+    }
+
+    #[test]
+    fn expecting_options_help_last() {
+        assert_eq!(
+            expecting_options(&[
+                Field {
+                    aliases: vec!["h"],
+                    ..Field::new("help", empty(), 0)
+                },
+                Field::new("version", empty(), 1),
+                Field::new("force", empty(), 0),
+                Field {
+                    aliases: vec!["h"],
+                    ..Field::new("help", empty(), 0)
+                },
+            ]),
+            vec!["version", "force", "help", "h"]
+        );
+    }
+
+    #[test]
+    fn expecting_options_empty() {
+        assert_eq!(expecting_options(&[]), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn expecting_variants_deduplicated() {
+        let variant = |name, aliases| Variant {
+            name,
+            description: String::new(),
+            version: None,
+            group: None,
+            raw: false,
+            aliases,
+            deprecated_aliases: Vec::new(),
+            shape: empty(),
+        };
+
+        assert_eq!(
+            expecting_variants(&[
+                variant("stop", vec!["halt"]),
+                variant("start", vec!["run", "stop"]),
+                variant("halt", vec![]),
+            ]),
+            vec!["stop", "halt", "start", "run"]
+        );
+    }
+
+    /// A struct with a global `--force` flag and a command whose only variant declares its own
+    /// `--force` flag.
+    fn shadowed_options() -> Shape {
+        Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new(
+                "command",
+                Shape::Enum {
+                    name: "Command",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
                     variants: vec![Variant {
-                        name: "foo",
+                        name: "run",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
-                        shape: Shape::Empty {
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Struct {
+                            name: "",
                             description: String::new(),
                             version: None,
-                        }
+                            examples: None,
+                            required: vec![],
+                            optional: vec![],
+                            booleans: vec![Field {
+                                aliases: vec!["f"],
+                                ..Field::new("force", empty(), 0)
+                            }],
+                            constraints: vec![],
+                        },
                     }],
-                }
-            ),
-            Context {
-                segments: vec![Segment::Identifier("foo")],
+                },
+                0,
+            )],
+            optional: vec![],
+            booleans: vec![Field {
+                aliases: vec!["f"],
+                ..Field::new("force", empty(), 1)
+            }],
+            constraints: vec![],
+        }
+    }
+
+    #[test]
+    fn parse_unrecognized_option_shadowed() {
+        let error = assert_err!(parse(["run", "--forse"], &mut shadowed_options()));
+
+        assert_eq!(
+            error,
+            Error::UnrecognizedOption {
+                name: "forse".into(),
+                expecting: vec!["force", "f", "help", "h"],
             }
         );
+        assert_eq!(
+            format!("{}", error),
+            "unrecognized optional flag: '--forse'\n\n  tip: a similar option exists: --force"
+        );
     }
 
     #[test]
-    fn parse_help() {
+    fn parse_unrecognized_short_option_shadowed() {
+        let error = assert_err!(parse(["run", "-g"], &mut shadowed_options()));
+
+        assert_eq!(
+            error,
+            Error::UnrecognizedOption {
+                name: "g".into(),
+                expecting: vec!["force", "f", "help", "h"],
+            }
+        );
+        assert_eq!(
+            format!("{}", error),
+            "unrecognized optional flag: '-g'\n\n  tip: a similar option exists: -f"
+        );
+    }
+
+    #[test]
+    fn parse_unrecognized_variant_duplicate_alias() {
+        let error = assert_err!(parse(
+            ["stpo"],
+            &mut Shape::Enum {
+                name: "Command",
+                description: String::new(),
+                version: None,
+                examples: None,
+                variants: vec![
+                    Variant {
+                        name: "stop",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec!["halt"],
+                        deprecated_aliases: Vec::new(),
+                        shape: empty(),
+                    },
+                    Variant {
+                        name: "halt",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec!["stop"],
+                        deprecated_aliases: Vec::new(),
+                        shape: empty(),
+                    },
+                ],
+            }
+        ));
+
+        assert_eq!(
+            error,
+            Error::UnrecognizedVariant {
+                name: "stpo".into(),
+                expecting: vec!["stop", "halt"],
+            }
+        );
+        assert_eq!(
+            format!("{}", error),
+            "unrecognized command: 'stpo'\n\n  tip: a similar command exists: stop"
+        );
+    }
+
+    /// The shape of `remote()` after it has been parsed as its `stop` variant.
+    fn remote_variant() -> Shape {
+        let mut shape = remote();
+        assert_ok!(parse(["stop"], &mut shape));
+        assert!(matches!(shape, Shape::Variant { .. }));
+        shape
+    }
+
+    #[test]
+    fn parse_optional_variant_empty_optional() {
+        // The same as for the enum before it was parsed.
+        assert_err_eq!(
+            parse(["-"], &mut Shape::Optional(Box::new(remote()))),
+            Error::UnrecognizedVariant {
+                name: "".into(),
+                expecting: vec!["stop", "push"],
+            }
+        );
         assert_err_eq!(
+            parse(["-"], &mut Shape::Optional(Box::new(remote_variant()))),
+            Error::UnrecognizedVariant {
+                name: "".into(),
+                expecting: vec!["stop", "push"],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_optional_variant_no_args() {
+        assert_ok_eq!(
             parse(
-                ["--help"],
-                &mut Shape::Empty {
+                Vec::<&str>::new(),
+                &mut Shape::Optional(Box::new(remote_variant()))
+            ),
+            Context { segments: vec![] }
+        );
+    }
+
+    #[test]
+    fn parse_optional_variant_present() {
+        assert_ok_eq!(
+            parse(
+                ["--push", "origin", "main"],
+                &mut Shape::Optional(Box::new(remote_variant()))
+            ),
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("push"),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Identifier("remote"),
+                                Segment::Value("origin".into()),
+                            ],
+                        }),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Identifier("refspec"),
+                                Segment::Value("main".into()),
+                            ],
+                        }),
+                    ],
+                })],
+            }
+        );
+    }
+
+    fn prefix() -> Config {
+        Config {
+            prefix: true,
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn parse_prefix_struct() {
+        assert_ok_eq!(
+            parse_args(
+                ["--verbose", "foo", "--bar", "baz"],
+                &mut Shape::Struct {
+                    name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
+                    required: vec![Field::new("command", primitive("command"), 0)],
+                    optional: vec![],
+                    booleans: vec![Field::new("verbose", empty(), 1)],
+                    constraints: vec![],
                 },
+                prefix()
             ),
-            Error::Help,
+            Parsed {
+                context: Context {
+                    segments: vec![
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Identifier("command"),
+                                Segment::Value("foo".into()),
+                            ],
+                        }),
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Identifier("verbose"),
+                                Segment::Context(Context { segments: vec![] }),
+                            ],
+                        }),
+                    ],
+                },
+                remainder: vec![OsString::from("--bar"), OsString::from("baz")],
+                warnings: vec![],
+            }
         );
     }
 
     #[test]
-    fn parse_help_short() {
-        assert_err_eq!(
-            parse(
-                ["-h"],
-                &mut Shape::Empty {
+    fn parse_prefix_struct_options_after_satisfied() {
+        assert_ok_eq!(
+            parse_args(
+                ["foo", "--verbose"],
+                &mut Shape::Struct {
+                    name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
+                    required: vec![Field::new("command", primitive("command"), 0)],
+                    optional: vec![],
+                    booleans: vec![Field::new("verbose", empty(), 1)],
+                    constraints: vec![],
                 },
+                prefix()
             ),
-            Error::Help,
+            Parsed {
+                context: Context {
+                    segments: vec![
+                        Segment::Context(Context {
+                            segments: vec![
+                                Segment::Identifier("command"),
+                                Segment::Value("foo".into()),
+                            ],
+                        }),
+                        Segment::Context(Context {
+                            segments: vec![Segment::Identifier("verbose")],
+                        }),
+                    ],
+                },
+                remainder: vec![OsString::from("--verbose")],
+                warnings: vec![],
+            }
         );
     }
 
     #[test]
-    fn parse_version() {
-        assert_err_eq!(
-            parse(
-                ["--version"],
-                &mut Shape::Empty {
+    fn parse_prefix_enum_unit_variant() {
+        assert_ok_eq!(
+            parse_args(
+                ["foo", "bar", "--baz"],
+                &mut Shape::Enum {
+                    name: "Enum",
                     description: String::new(),
-                    version: Some("foo".into()),
+                    version: None,
+                    examples: None,
+                    variants: vec![Variant {
+                        name: "foo",
+                        description: String::new(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: empty(),
+                    }],
                 },
+                prefix()
             ),
-            Error::Version,
+            Parsed {
+                context: Context {
+                    segments: vec![Segment::Identifier("foo")],
+                },
+                remainder: vec![OsString::from("bar"), OsString::from("--baz")],
+                warnings: vec![],
+            }
         );
     }
 
     #[test]
-    fn parse_version_not_available() {
+    fn parse_prefix_missing_argument() {
         assert_err_eq!(
-            parse(
-                ["--version"],
-                &mut Shape::Empty {
+            parse_args(
+                ["--verbose"],
+                &mut Shape::Struct {
+                    name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
+                    required: vec![Field::new("command", primitive("command"), 0)],
+                    optional: vec![],
+                    booleans: vec![Field::new("verbose", empty(), 1)],
+                    constraints: vec![],
                 },
+                prefix()
             ),
-            Error::UnrecognizedOption {
-                name: "version".into(),
-                expecting: vec!["help", "h"]
-            },
+            Error::MissingArguments(vec!["command".into()])
+        );
+    }
+
+    #[test]
+    fn parse_prefix_help() {
+        assert_err_eq!(
+            parse_args(["--help", "foo"], &mut primitive("foo"), prefix()),
+            Error::Help { short: false }
         );
     }
+
+    #[test]
+    fn parse_without_prefix_remaining_arguments() {
+        assert_err!(parse_args(
+            ["foo", "bar"],
+            &mut primitive("foo"),
+            Config::default()
+        ));
+    }
+
+    /// A deterministic xorshift generator, so that any failure can be reproduced.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, bound: usize) -> usize {
+            (self.next() % bound as u64) as usize
+        }
+    }
+
+    /// Generates a random list of arguments.
+    ///
+    /// Arguments are mostly drawn from tokens that are meaningful to the representative shapes,
+    /// mixed with random bytes that are frequently invalid UTF-8.
+    #[cfg(unix)]
+    fn random_args(rng: &mut Rng) -> Vec<OsString> {
+        use std::os::unix::ffi::OsStringExt;
+
+        const TOKENS: &[&[u8]] = &[
+            b"",
+            b"-",
+            b"--",
+            b"-h",
+            b"--help",
+            b"--version",
+            b"-o",
+            b"-ab",
+            b"-ofile",
+            b"-o=x",
+            b"-=",
+            b"=",
+            b"--path",
+            b"--dry_run",
+            b"--verbose",
+            b"--count",
+            b"--force",
+            b"-f",
+            b"clean",
+            b"opt",
+            b"mixed",
+            b"push",
+            b"stop",
+            b"run",
+            b"origin",
+            b"\xff",
+            b"-\xff",
+            b"--\xff",
+            b"\xc3",
+            b"-\xc3",
+            b"-\xe3\x81",
+            b"-\xc3\xa3",
+        ];
+
+        (0..rng.below(7))
+            .map(|_| {
+                OsString::from_vec(if rng.below(4) == 0 {
+                    (0..rng.below(5)).map(|_| rng.next() as u8).collect()
+                } else {
+                    TOKENS[rng.below(TOKENS.len())].to_vec()
+                })
+            })
+            .collect()
+    }
+
+    /// Parses many random argument lists against `shape`, which must never panic.
+    #[cfg(unix)]
+    fn assert_parse_never_panics(shape: Shape) {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..5000 {
+            let _ = parse(random_args(&mut rng), &mut shape.clone());
+        }
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_primitive() {
+        assert_parse_never_panics(primitive("foo"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_sequence() {
+        assert_parse_never_panics(Shape::Sequence(Box::new(primitive("foo"))));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_struct_with_options() {
+        assert_parse_never_panics(short_options());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_enum() {
+        assert_parse_never_panics(command());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_enum_within_struct() {
+        assert_parse_never_panics(global_options());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_nested_optional() {
+        assert_parse_never_panics(Shape::Optional(Box::new(Shape::Optional(Box::new(
+            primitive("foo"),
+        )))));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_optional_enum() {
+        assert_parse_never_panics(Shape::Optional(Box::new(command())));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_optional_struct() {
+        assert_parse_never_panics(Shape::Optional(Box::new(short_options())));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn parse_random_optional_variant() {
+        assert_parse_never_panics(Shape::Optional(Box::new(remote_variant())));
+    }
+
+    #[test]
+    fn parse_many_repeated_flags() {
+        let mut shape = Shape::Struct {
+            name: "",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("input", primitive("input"), 0)],
+            optional: vec![Field::new("level", primitive("level"), 1)],
+            booleans: vec![
+                Field::new("verbose", empty(), 2),
+                Field::new("quiet", empty(), 3),
+            ],
+            constraints: vec![],
+        };
+        let mut args = vec!["input"];
+        for _ in 0..5_000 {
+            args.extend(["--verbose", "--quiet"]);
+        }
+
+        // Parsing is linear in the number of arguments, so this bound is generous even for
+        // unoptimized builds.
+        let start = Instant::now();
+        let context = assert_ok!(parse(args, &mut shape));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        // The required field, every occurrence of the flags, and the absent option.
+        assert_eq!(context.segments.len(), 10_002);
+    }
 }
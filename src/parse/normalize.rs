@@ -0,0 +1,410 @@
+use super::{
+    Context,
+    Segment,
+};
+use crate::trace::{
+    Field,
+    Shape,
+    Variant,
+};
+use std::iter;
+
+/// Sorts the fields of every struct within the context into declaration order.
+///
+/// Optional and boolean fields are added to a struct's context in the order they are given on the
+/// command line, with any fields that were not given appended afterward. Deserializers therefore
+/// see the fields in declaration order regardless of how the options were provided.
+pub(super) fn normalize(context: &mut Context, shape: &Shape) {
+    normalize_segments(&mut context.segments, shape);
+}
+
+fn normalize_segments(segments: &mut [Segment], shape: &Shape) {
+    match shape {
+        Shape::Empty { .. }
+        | Shape::Primitive { .. }
+        | Shape::Boolean { .. }
+        | Shape::Sequence(_) => {}
+        Shape::Optional(optional_shape) => {
+            if let Some(Segment::Context(optional_context)) = segments.first_mut() {
+                normalize(optional_context, optional_shape);
+            }
+        }
+        Shape::Struct {
+            required,
+            optional,
+            booleans,
+            ..
+        } => {
+            let fields: Vec<&Field> = required
+                .iter()
+                .chain(optional.iter())
+                .chain(booleans.iter())
+                .collect();
+            for segment in segments.iter_mut() {
+                if let Segment::Context(field_context) = segment {
+                    if let Some((Segment::Identifier(name), field_segments)) =
+                        field_context.segments.split_first_mut()
+                    {
+                        if let Some(field) = find_field(&fields, name) {
+                            normalize_segments(field_segments, &field.shape);
+                        }
+                    }
+                }
+            }
+            segments.sort_by_key(|segment| {
+                if let Segment::Context(field_context) = segment {
+                    if let Some(Segment::Identifier(name)) = field_context.segments.first() {
+                        return find_field(&fields, name).map(|field| field.index);
+                    }
+                }
+                None
+            });
+        }
+        Shape::Enum { variants, .. } => {
+            if let Some((Segment::Identifier(name), variant_segments)) = segments.split_first_mut()
+            {
+                if let Some(variant) = find_variant(variants, name) {
+                    normalize_segments(variant_segments, &variant.shape);
+                }
+            }
+        }
+        Shape::Variant {
+            name: variant_name,
+            shape: variant_shape,
+            variants,
+            ..
+        } => {
+            if let Some((Segment::Identifier(name), variant_segments)) = segments.split_first_mut()
+            {
+                if name == variant_name {
+                    normalize_segments(variant_segments, variant_shape);
+                } else if let Some(variant) = find_variant(variants, name) {
+                    normalize_segments(variant_segments, &variant.shape);
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn find_field<'a>(fields: &[&'a Field], name: &str) -> Option<&'a Field> {
+    fields.iter().copied().find(|field| {
+        iter::once(field.name)
+            .chain(field.aliases.iter().copied())
+            .any(|field_name| field_name == name)
+    })
+}
+
+pub(super) fn find_variant<'a>(variants: &'a [Variant], name: &str) -> Option<&'a Variant> {
+    variants.iter().find(|variant| {
+        iter::once(variant.name)
+            .chain(variant.aliases.iter().copied())
+            .any(|variant_name| variant_name == name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+    use crate::{
+        parse::{
+            Context,
+            Segment,
+        },
+        trace::{
+            Field,
+            PrimitiveKind,
+            Shape,
+            Variant,
+        },
+    };
+
+    fn empty() -> Shape {
+        Shape::Empty {
+            description: String::new(),
+            version: None,
+        }
+    }
+
+    fn primitive(name: &str) -> Shape {
+        Shape::Primitive {
+            name: name.to_owned(),
+            description: String::new(),
+            version: None,
+            kind: PrimitiveKind::Str,
+        }
+    }
+
+    /// A struct declaring a required field, an optional field, and two booleans, with the
+    /// declaration order interleaving the kinds of fields.
+    fn args() -> Shape {
+        Shape::Struct {
+            name: "Args",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new("path", primitive("path"), 1)],
+            optional: vec![Field::new("output", primitive("output"), 3)],
+            booleans: vec![
+                Field::new("verbose", empty(), 0),
+                Field::new("force", empty(), 2),
+            ],
+            constraints: vec![],
+        }
+    }
+
+    fn flag(name: &'static str, present: bool) -> Segment {
+        Segment::Context(Context {
+            segments: if present {
+                vec![
+                    Segment::Identifier(name),
+                    Segment::Context(Context { segments: vec![] }),
+                ]
+            } else {
+                vec![Segment::Identifier(name)]
+            },
+        })
+    }
+
+    fn value(name: &'static str, value: &str) -> Segment {
+        Segment::Context(Context {
+            segments: vec![Segment::Identifier(name), Segment::Value(value.into())],
+        })
+    }
+
+    fn option(name: &'static str, value: &str) -> Segment {
+        Segment::Context(Context {
+            segments: vec![
+                Segment::Identifier(name),
+                Segment::Context(Context {
+                    segments: vec![Segment::Value(value.into())],
+                }),
+            ],
+        })
+    }
+
+    #[test]
+    fn normalize_primitive() {
+        let mut context = Context {
+            segments: vec![Segment::Value("foo".into())],
+        };
+
+        normalize(&mut context, &primitive("foo"));
+
+        assert_eq!(
+            context,
+            Context {
+                segments: vec![Segment::Value("foo".into())],
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_struct_backfilled() {
+        let mut context = Context {
+            segments: vec![
+                value("path", "foo"),
+                flag("force", true),
+                option("output", "bar"),
+                flag("verbose", false),
+            ],
+        };
+
+        normalize(&mut context, &args());
+
+        assert_eq!(
+            context,
+            Context {
+                segments: vec![
+                    flag("verbose", false),
+                    value("path", "foo"),
+                    flag("force", true),
+                    option("output", "bar"),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_struct_alias() {
+        let mut context = Context {
+            segments: vec![value("path", "foo"), flag("f", true), flag("verbose", true)],
+        };
+
+        normalize(
+            &mut context,
+            &Shape::Struct {
+                name: "Args",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![Field::new("path", primitive("path"), 2)],
+                optional: vec![],
+                booleans: vec![
+                    Field::new("verbose", empty(), 0),
+                    Field {
+                        aliases: vec!["f"],
+                        ..Field::new("force", empty(), 1)
+                    },
+                ],
+                constraints: vec![],
+            },
+        );
+
+        assert_eq!(
+            context,
+            Context {
+                segments: vec![flag("verbose", true), flag("f", true), value("path", "foo")],
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_optional_struct() {
+        let mut context = Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![
+                    value("path", "foo"),
+                    flag("force", true),
+                    flag("verbose", false),
+                ],
+            })],
+        };
+
+        normalize(&mut context, &Shape::Optional(Box::new(args())));
+
+        assert_eq!(
+            context,
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        flag("verbose", false),
+                        value("path", "foo"),
+                        flag("force", true),
+                    ],
+                })],
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_nested_struct() {
+        let mut context = Context {
+            segments: vec![Segment::Context(Context {
+                segments: vec![
+                    Segment::Identifier("inner"),
+                    value("path", "foo"),
+                    flag("force", false),
+                    flag("verbose", true),
+                ],
+            })],
+        };
+
+        normalize(
+            &mut context,
+            &Shape::Struct {
+                name: "Outer",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![Field::new("inner", args(), 0)],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            },
+        );
+
+        assert_eq!(
+            context,
+            Context {
+                segments: vec![Segment::Context(Context {
+                    segments: vec![
+                        Segment::Identifier("inner"),
+                        flag("verbose", true),
+                        value("path", "foo"),
+                        flag("force", false),
+                    ],
+                })],
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_enum() {
+        let mut context = Context {
+            segments: vec![
+                Segment::Identifier("run"),
+                value("path", "foo"),
+                flag("force", true),
+                flag("verbose", false),
+            ],
+        };
+
+        normalize(
+            &mut context,
+            &Shape::Enum {
+                name: "Command",
+                description: String::new(),
+                version: None,
+                examples: None,
+                variants: vec![Variant {
+                    name: "run",
+                    description: String::new(),
+                    version: None,
+                    group: None,
+                    raw: false,
+                    aliases: vec![],
+                    deprecated_aliases: Vec::new(),
+                    shape: args(),
+                }],
+            },
+        );
+
+        assert_eq!(
+            context,
+            Context {
+                segments: vec![
+                    Segment::Identifier("run"),
+                    flag("verbose", false),
+                    value("path", "foo"),
+                    flag("force", true),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn normalize_variant() {
+        let mut context = Context {
+            segments: vec![
+                Segment::Identifier("run"),
+                value("path", "foo"),
+                flag("force", true),
+                flag("verbose", false),
+            ],
+        };
+
+        normalize(
+            &mut context,
+            &Shape::Variant {
+                name: "run",
+                description: String::new(),
+                version: None,
+                shape: Box::new(args()),
+                enum_name: "Command",
+                variants: vec![],
+            },
+        );
+
+        assert_eq!(
+            context,
+            Context {
+                segments: vec![
+                    Segment::Identifier("run"),
+                    flag("verbose", false),
+                    value("path", "foo"),
+                    flag("force", true),
+                ],
+            }
+        );
+    }
+}
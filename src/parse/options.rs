@@ -0,0 +1,262 @@
+use crate::trace::Field;
+use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The options available within a context, indexed by their names and aliases.
+///
+/// Options given as flags are indexed by the names and aliases of their variants instead.
+///
+/// An option is taken out while its value is parsed, so that it is not available within its own
+/// value.
+#[derive(Debug, Default)]
+pub(super) struct Options {
+    fields: Vec<Option<Field>>,
+    /// The positions of the options declaring each name or alias, in the order they were added.
+    positions: HashMap<&'static str, Vec<usize>>,
+}
+
+impl Options {
+    pub(super) fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Adds options after all existing options.
+    ///
+    /// If an option shares a name with an existing option, the added option is found first.
+    pub(super) fn extend<Fields>(&mut self, fields: Fields)
+    where
+        Fields: IntoIterator<Item = Field>,
+    {
+        for field in fields {
+            let position = self.fields.len();
+            for name in field.option_names() {
+                self.positions.entry(name).or_default().push(position);
+            }
+            self.fields.push(Some(field));
+        }
+    }
+
+    /// Removes every option after the first `len` options.
+    pub(super) fn truncate(&mut self, len: usize) {
+        for field in self.fields.drain(len..).flatten() {
+            for name in field.option_names() {
+                if let Some(positions) = self.positions.get_mut(name) {
+                    while positions.last().is_some_and(|position| *position >= len) {
+                        positions.pop();
+                    }
+                    if positions.is_empty() {
+                        self.positions.remove(name);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Finds the most recently added available option with the name or alias `name`.
+    ///
+    /// Returns the option's position along with the matching name as it was declared.
+    pub(super) fn find(&self, name: &str) -> Option<(usize, &'static str)> {
+        let (declared_name, positions) = self.positions.get_key_value(name)?;
+        positions
+            .iter()
+            .copied()
+            .rev()
+            .find(|position| self.fields[*position].is_some())
+            .map(|position| (position, *declared_name))
+    }
+
+    /// Finds the available options with a long name or alias that `prefix` abbreviates.
+    ///
+    /// Returns each matching option's position along with its first matching name as it was
+    /// declared, in the order the options were added. Names of a single character are never
+    /// abbreviated.
+    pub(super) fn find_abbreviated(&self, prefix: &str) -> Vec<(usize, &'static str)> {
+        let mut found: Vec<(usize, &'static str)> = Vec::new();
+        let mut names = self
+            .positions
+            .keys()
+            .copied()
+            .filter(|name| {
+                name.graphemes(true).count() > 1
+                    && name.len() > prefix.len()
+                    && name.starts_with(prefix)
+            })
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        for name in names {
+            if let Some(found_at) = self.find(name) {
+                if !found.iter().any(|(position, _)| *position == found_at.0) {
+                    found.push(found_at);
+                }
+            }
+        }
+        found.sort_by_key(|(position, _)| *position);
+        found
+    }
+
+    /// Takes the option at `position`, making it unavailable until it is restored.
+    pub(super) fn take(&mut self, position: usize) -> Field {
+        self.fields[position]
+            .take()
+            .expect("option was already taken")
+    }
+
+    /// Returns an option previously taken from `position`.
+    pub(super) fn restore(&mut self, position: usize, field: Field) {
+        self.fields[position] = Some(field);
+    }
+
+    /// Returns the available options, in the order they were added.
+    ///
+    /// Since later options shadow earlier ones, the options should be searched in reverse.
+    pub(super) fn iter(&self) -> impl DoubleEndedIterator<Item = &Field> {
+        self.fields.iter().flatten()
+    }
+}
+
+impl From<Vec<Field>> for Options {
+    fn from(fields: Vec<Field>) -> Self {
+        let mut options = Self::default();
+        options.extend(fields);
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Options;
+    use crate::trace::{
+        Field,
+        Shape,
+    };
+    use claims::{
+        assert_none,
+        assert_some_eq,
+    };
+
+    fn field(name: &'static str, aliases: Vec<&'static str>) -> Field {
+        Field {
+            aliases,
+            ..Field::new(
+                name,
+                Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+                0,
+            )
+        }
+    }
+
+    #[test]
+    fn find_name() {
+        let options = Options::from(vec![field("foo", vec![]), field("bar", vec!["b"])]);
+
+        assert_some_eq!(options.find("bar"), (1, "bar"));
+    }
+
+    #[test]
+    fn find_alias() {
+        let options = Options::from(vec![field("foo", vec![]), field("bar", vec!["b"])]);
+
+        assert_some_eq!(options.find("b"), (1, "b"));
+    }
+
+    #[test]
+    fn find_unknown() {
+        let options = Options::from(vec![field("foo", vec![])]);
+
+        assert_none!(options.find("bar"));
+    }
+
+    #[test]
+    fn find_last_added() {
+        let mut options = Options::from(vec![field("foo", vec![])]);
+        options.extend([field("bar", vec!["foo"])]);
+
+        assert_some_eq!(options.find("foo"), (1, "foo"));
+    }
+
+    #[test]
+    fn find_taken() {
+        let mut options = Options::from(vec![field("foo", vec![])]);
+        options.extend([field("bar", vec!["foo"])]);
+
+        let taken = options.take(1);
+
+        assert_some_eq!(options.find("foo"), (0, "foo"));
+        assert_eq!(
+            options.iter().map(|field| field.name).collect::<Vec<_>>(),
+            vec!["foo"]
+        );
+
+        options.restore(1, taken);
+
+        assert_some_eq!(options.find("foo"), (1, "foo"));
+    }
+
+    #[test]
+    fn find_abbreviated_unique() {
+        let options = Options::from(vec![field("force", vec![]), field("file", vec![])]);
+
+        assert_eq!(options.find_abbreviated("fo"), vec![(0, "force")]);
+    }
+
+    #[test]
+    fn find_abbreviated_multiple() {
+        let options = Options::from(vec![
+            field("force", vec![]),
+            field("filter", vec![]),
+            field("file", vec![]),
+        ]);
+
+        assert_eq!(
+            options.find_abbreviated("f"),
+            vec![(0, "force"), (1, "filter"), (2, "file")]
+        );
+    }
+
+    #[test]
+    fn find_abbreviated_name_and_alias() {
+        let options = Options::from(vec![field("verbose", vec!["verbosity", "v"])]);
+
+        assert_eq!(options.find_abbreviated("verbos"), vec![(0, "verbose")]);
+    }
+
+    #[test]
+    fn find_abbreviated_not_short() {
+        let options = Options::from(vec![field("bar", vec!["b"])]);
+
+        assert_eq!(options.find_abbreviated(""), vec![(0, "bar")]);
+    }
+
+    #[test]
+    fn find_abbreviated_not_exact() {
+        let options = Options::from(vec![field("foo", vec![])]);
+
+        assert_eq!(options.find_abbreviated("foo"), vec![]);
+    }
+
+    #[test]
+    fn find_abbreviated_taken() {
+        let mut options = Options::from(vec![field("force", vec![]), field("format", vec![])]);
+
+        let taken = options.take(1);
+
+        assert_eq!(options.find_abbreviated("fo"), vec![(0, "force")]);
+
+        options.restore(1, taken);
+    }
+
+    #[test]
+    fn truncate() {
+        let mut options = Options::from(vec![field("foo", vec![])]);
+        options.extend([field("bar", vec!["foo"])]);
+
+        options.truncate(1);
+
+        assert_eq!(options.len(), 1);
+        assert_none!(options.find("bar"));
+        assert_some_eq!(options.find("foo"), (0, "foo"));
+    }
+}
@@ -0,0 +1,205 @@
+use super::{
+    normalize::{
+        find_field,
+        find_variant,
+    },
+    Context,
+    Error,
+    Segment,
+};
+use crate::trace::{
+    ConstraintKind,
+    Field,
+    Shape,
+};
+
+/// Returns the names of any required options that were not given within the context.
+///
+/// Options marked as required are optional fields, so they are recorded in the context as missing
+/// rather than causing parsing to fail. This can only be checked once the whole context is parsed,
+/// since the options of a nested struct may be given after the nested struct itself.
+///
+/// Required options given as flags are checked by `exclusive_flags()` instead.
+pub(super) fn missing_options(context: &Context, shape: &Shape) -> Vec<String> {
+    let mut missing = Vec::new();
+    for_each_struct(&context.segments, shape, &mut |fields, _| {
+        for (field, field_segments) in fields {
+            if field.required && !field.flags && field_segments.is_empty() {
+                missing.push(field.name.to_owned());
+            }
+        }
+    });
+    missing
+}
+
+/// Returns an error for each option given as flags where flags for more than one variant were
+/// given, where the flag for a single variant was repeated, or where no flag was given for a
+/// required option.
+///
+/// As with required options, this can only be checked once the whole context is parsed.
+pub(super) fn exclusive_flags(context: &Context, shape: &Shape) -> Vec<Error> {
+    let mut errors = Vec::new();
+    for_each_struct(&context.segments, shape, &mut |fields, shape| {
+        let optional = match shape {
+            Shape::Struct { optional, .. } => optional,
+            _ => return,
+        };
+        for flags_field in optional.iter().filter(|field| field.flags) {
+            // Each occurrence of the option contains the name of the variant its flag selected.
+            let mut given: Vec<&str> = Vec::new();
+            let mut repeated = None;
+            for (field, field_segments) in fields {
+                if field.name != flags_field.name {
+                    continue;
+                }
+                if let Some(Segment::Context(variant_context)) = field_segments.first() {
+                    if let Some(Segment::Identifier(variant_name)) =
+                        variant_context.segments.first()
+                    {
+                        if !given.contains(variant_name) {
+                            given.push(variant_name);
+                        } else if repeated.is_none() {
+                            repeated = Some(*variant_name);
+                        }
+                    }
+                }
+            }
+            match given.as_slice() {
+                [] if flags_field.required => {
+                    if let Shape::Enum { variants, .. } = &flags_field.shape {
+                        errors.push(Error::MissingOneOfOptions(
+                            variants
+                                .iter()
+                                .map(|variant| variant.name.to_owned())
+                                .collect(),
+                        ));
+                    }
+                }
+                [first, second, ..] => errors.push(Error::ConflictingFlags {
+                    first: (*first).to_owned(),
+                    second: (*second).to_owned(),
+                }),
+                [_] => {
+                    if let Some(name) = repeated {
+                        errors.push(Error::RepeatedFlag(name.to_owned()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+    errors
+}
+
+/// Returns an error for each constraint on a struct's options that is not satisfied within the
+/// context.
+///
+/// As with required options, this can only be checked once the whole context is parsed.
+pub(super) fn violated_constraints(context: &Context, shape: &Shape) -> Vec<Error> {
+    let mut errors = Vec::new();
+    for_each_struct(&context.segments, shape, &mut |fields, shape| {
+        let constraints = match shape {
+            Shape::Struct { constraints, .. } => constraints,
+            _ => return,
+        };
+        for constraint in constraints {
+            let (given, missing): (Vec<&str>, Vec<&str>) =
+                constraint.fields.iter().partition(|name| {
+                    fields.iter().any(|(field, field_segments)| {
+                        field.name == **name && !field_segments.is_empty()
+                    })
+                });
+            let to_owned = |names: Vec<&str>| names.into_iter().map(ToOwned::to_owned).collect();
+            match constraint.kind {
+                ConstraintKind::OneOf if given.is_empty() => {
+                    errors.push(Error::MissingOneOfOptions(to_owned(missing)));
+                }
+                ConstraintKind::OneOf | ConstraintKind::AtMostOne if given.len() > 1 => {
+                    errors.push(Error::ConflictingOptions(to_owned(given)));
+                }
+                ConstraintKind::AllOrNone if !given.is_empty() && !missing.is_empty() => {
+                    errors.push(Error::IncompleteOptions {
+                        given: to_owned(given),
+                        missing: to_owned(missing),
+                    });
+                }
+                _ => {}
+            }
+        }
+    });
+    errors
+}
+
+/// Calls `visit` for every struct parsed within the segments, with the struct's fields paired
+/// with the segments given for them, excluding their identifiers.
+///
+/// Structs that were not given, such as those within optional fields that were not provided, are
+/// not visited.
+fn for_each_struct<'a, F>(segments: &'a [Segment], shape: &'a Shape, visit: &mut F)
+where
+    F: FnMut(&[(&'a Field, &'a [Segment])], &'a Shape),
+{
+    match shape {
+        Shape::Empty { .. }
+        | Shape::Primitive { .. }
+        | Shape::Boolean { .. }
+        | Shape::Sequence(_) => {}
+        Shape::Optional(optional_shape) => {
+            if let Some(Segment::Context(optional_context)) = segments.first() {
+                for_each_struct(&optional_context.segments, optional_shape, visit);
+            }
+        }
+        Shape::Struct {
+            required,
+            optional,
+            booleans,
+            ..
+        } => {
+            if segments.is_empty() {
+                return;
+            }
+            let fields: Vec<&Field> = required
+                .iter()
+                .chain(optional.iter())
+                .chain(booleans.iter())
+                .collect();
+            let mut field_contexts = Vec::with_capacity(segments.len());
+            for segment in segments {
+                if let Segment::Context(field_context) = segment {
+                    if let Some((Segment::Identifier(name), field_segments)) =
+                        field_context.segments.split_first()
+                    {
+                        if let Some(field) = find_field(&fields, name) {
+                            field_contexts.push((field, field_segments));
+                        }
+                    }
+                }
+            }
+            visit(&field_contexts, shape);
+            for (field, field_segments) in field_contexts {
+                for_each_struct(field_segments, &field.shape, visit);
+            }
+        }
+        Shape::Enum { variants, .. } => {
+            if let Some((Segment::Identifier(name), variant_segments)) = segments.split_first() {
+                if let Some(variant) = find_variant(variants, name) {
+                    for_each_struct(variant_segments, &variant.shape, visit);
+                }
+            }
+        }
+        Shape::Variant {
+            name: variant_name,
+            shape: variant_shape,
+            variants,
+            ..
+        } => {
+            if let Some((Segment::Identifier(name), variant_segments)) = segments.split_first() {
+                if name == variant_name {
+                    for_each_struct(variant_segments, variant_shape, visit);
+                } else if let Some(variant) = find_variant(variants, name) {
+                    for_each_struct(variant_segments, &variant.shape, visit);
+                }
+            }
+        }
+    }
+}
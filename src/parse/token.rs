@@ -1,47 +1,207 @@
+use super::Error;
+use crate::{
+    trace::{
+        Field,
+        Shape,
+        Variant,
+    },
+    Warning,
+};
 use std::{
-    ffi::OsString,
+    borrow::Cow,
+    ffi::{
+        OsStr,
+        OsString,
+    },
+    slice::SliceIndex,
     str,
 };
 use unicode_segmentation::UnicodeSegmentation;
 
+/// An argument that can be read as its platform encoding.
+///
+/// Borrowed arguments are read without being copied, so that only the parts of them that are kept
+/// once parsed are ever allocated.
+pub(crate) trait RawArg<'a> {
+    /// Returns the argument as its platform encoding, as given by [`OsStr::as_encoded_bytes()`].
+    fn into_encoded_bytes(self) -> Cow<'a, [u8]>;
+
+    /// Returns the argument as an `OsString`.
+    fn into_os_string(self) -> OsString
+    where
+        Self: Sized,
+    {
+        os_string_from_bytes(self.into_encoded_bytes().into_owned())
+    }
+}
+
+impl<'a> RawArg<'a> for OsString {
+    fn into_encoded_bytes(self) -> Cow<'a, [u8]> {
+        Cow::Owned(OsString::into_encoded_bytes(self))
+    }
+
+    fn into_os_string(self) -> OsString {
+        self
+    }
+}
+
+impl<'a> RawArg<'a> for &'a OsString {
+    fn into_encoded_bytes(self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.as_encoded_bytes())
+    }
+}
+
+impl<'a> RawArg<'a> for &'a OsStr {
+    fn into_encoded_bytes(self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.as_encoded_bytes())
+    }
+}
+
+impl<'a> RawArg<'a> for String {
+    fn into_encoded_bytes(self) -> Cow<'a, [u8]> {
+        Cow::Owned(self.into_bytes())
+    }
+}
+
+/// Valid UTF-8 is always a valid platform encoding, so a string is used as its bytes directly.
+impl<'a> RawArg<'a> for &'a str {
+    fn into_encoded_bytes(self) -> Cow<'a, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+/// Returns the `range` of `bytes`, borrowing it if `bytes` is borrowed.
+fn slice<'a, R>(bytes: &Cow<'a, [u8]>, range: R) -> Cow<'a, [u8]>
+where
+    R: SliceIndex<[u8], Output = [u8]>,
+{
+    match bytes {
+        Cow::Borrowed(bytes) => Cow::Borrowed(&bytes[range]),
+        Cow::Owned(bytes) => Cow::Owned(bytes[range].to_vec()),
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
-pub(super) enum Token {
-    Positional(Vec<u8>),
-    Optional(Vec<u8>),
+pub(super) enum Token<'a> {
+    Positional(Cow<'a, [u8]>),
+    Optional {
+        /// The name of the option, without its leading hyphens.
+        name: Cow<'a, [u8]>,
+        /// The argument the option was read from, exactly as it was given.
+        ///
+        /// This is used in place of the option when the argument turns out to be a value, such as
+        /// for a field allowing hyphen values.
+        argument: Cow<'a, [u8]>,
+    },
     EndOfOptions,
 }
 
-pub(super) struct ParsedArgs<Args> {
+pub(super) struct ParsedArgs<'a, Args> {
     args: Args,
-    pub(super) revisit: Option<Vec<u8>>,
+    pub(super) revisit: Option<Cow<'a, [u8]>>,
+    /// Whether `revisit` is a value that was joined to its option, such as `foo` in `--opt=foo`.
+    ///
+    /// A joined value is always a value, even if it is empty or begins with a hyphen.
+    joined_value: bool,
     pub(super) consumed_token: bool,
+    /// Errors that parsing was able to continue past.
+    ///
+    /// These are reported together with any error that stops parsing, so that the user can see
+    /// every problem at once.
+    pub(super) errors: Vec<Error>,
+    /// Whether command names are matched regardless of case.
+    pub(super) case_insensitive_commands: bool,
+    /// Whether commands may be selected by their index when no command name matches.
+    pub(super) match_command_indices: bool,
+    /// Whether long options may be given using an unambiguous prefix of their names.
+    pub(super) allow_abbreviations: bool,
+    /// Whether parsing stops as soon as the shape is satisfied.
+    pub(super) prefix: bool,
+    /// Warnings about arguments that were parsed successfully, such as deprecated aliases.
+    pub(super) warnings: Vec<Warning>,
 }
 
-impl<Args> ParsedArgs<Args> {
+impl<Args> ParsedArgs<'_, Args> {
     pub(super) fn new(args: Args) -> Self {
         Self {
             args,
             revisit: None,
+            joined_value: false,
             consumed_token: false,
+            errors: Vec::new(),
+            case_insensitive_commands: false,
+            match_command_indices: false,
+            allow_abbreviations: false,
+            prefix: false,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Returns the declared name of `variant` if `name` matches its name or any of its aliases.
+    ///
+    /// A warning is recorded if `name` is one of the variant's deprecated aliases.
+    pub(super) fn find_variant_name(
+        &mut self,
+        variant: &Variant,
+        name: &str,
+    ) -> Option<&'static str> {
+        let found = variant.find_name(name, self.case_insensitive_commands)?;
+        if let Some(message) = variant.deprecation(name, self.case_insensitive_commands) {
+            self.warnings.push(Warning::deprecated_alias(
+                name.to_owned(),
+                message.to_owned(),
+            ));
+        }
+        Some(found)
+    }
+
+    /// Returns the declared name of the variant at the index given by `name`, if commands are
+    /// matched by index and `name` does not match any variant by name.
+    ///
+    /// Otherwise, `name` is returned unchanged. Names always take priority, so a variant renamed
+    /// to a number is selected by that name rather than by its index.
+    pub(super) fn resolve_variant_index<'a>(&self, variants: &[Variant], name: &'a str) -> &'a str {
+        if !self.match_command_indices
+            || variants.iter().any(|variant| {
+                variant
+                    .find_name(name, self.case_insensitive_commands)
+                    .is_some()
+            })
+        {
+            return name;
         }
+        name.parse::<usize>()
+            .ok()
+            .and_then(|index| variants.get(index))
+            .map_or(name, |variant| variant.name)
     }
 }
 
-impl<Args> ParsedArgs<Args>
+impl<'a, Args> ParsedArgs<'a, Args>
 where
-    Args: Iterator<Item = OsString>,
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
 {
-    pub(super) fn next_token(&mut self) -> Option<Token> {
+    pub(super) fn next_token(&mut self) -> Option<Token<'a>> {
+        if self.joined_value {
+            return self.next().map(Token::Positional);
+        }
         if let Some(token) = self.next() {
             if let Some(short_token) = token.strip_prefix(b"-") {
                 if short_token.is_empty() {
                     // A single `-` is an empty optional token.
-                    Some(Token::Optional(Vec::new()))
+                    Some(Token::Optional {
+                        name: Cow::Borrowed(&[]),
+                        argument: token,
+                    })
                 } else if let Some(long_token) = short_token.strip_prefix(b"-") {
                     if long_token.is_empty() {
                         Some(Token::EndOfOptions)
                     } else {
-                        Some(Token::Optional(long_token.to_vec()))
+                        Some(Token::Optional {
+                            name: slice(&token, 2..),
+                            argument: token,
+                        })
                     }
                 } else {
                     // This is only an option if there is a single character.
@@ -49,7 +209,10 @@ where
                         Some(Token::Positional(token))
                     } else if let Ok(short_token_str) = str::from_utf8(short_token) {
                         if short_token_str.graphemes(true).count() == 1 {
-                            Some(Token::Optional(short_token.to_vec()))
+                            Some(Token::Optional {
+                                name: slice(&token, 1..),
+                                argument: token,
+                            })
                         } else {
                             Some(Token::Positional(token))
                         }
@@ -65,14 +228,149 @@ where
         }
     }
 
-    pub(super) fn next_positional(&mut self) -> Option<Vec<u8>> {
+    /// Returns the next token, splitting options that are joined with their values.
+    ///
+    /// A single-dash token beginning with a short option from `options` is split after that
+    /// option. If the option is a flag, the remaining characters are revisited as more short
+    /// options, so `-ab` is `-a -b`. Otherwise, the remaining characters are revisited as the
+    /// option's value, so `-ofoo` and `-o=foo` are both `-o foo`. Options given as flags are
+    /// found by the names of their variants, and never take a value.
+    ///
+    /// A long option from `options` taking a value may likewise be joined with it by `=`, so
+    /// `--opt=foo` is `--opt foo`. The joined value is always used as the value, so `--opt=` gives
+    /// the option an empty value, while `--opt` on its own still requires a value to follow it.
+    pub(super) fn next_token_with_options<'b, Options>(
+        &mut self,
+        options: Options,
+    ) -> Option<Token<'a>>
+    where
+        Options: IntoIterator<Item = &'b Field>,
+    {
+        if self.joined_value {
+            return self.next_token();
+        }
+        let argument = match self.next_token()? {
+            Token::Positional(argument) => argument,
+            Token::Optional { name, argument } => {
+                return Some(self.split_joined_value(name, argument, options))
+            }
+            Token::EndOfOptions => return Some(Token::EndOfOptions),
+        };
+        let short_token = match argument.strip_prefix(b"-").map(str::from_utf8) {
+            Some(Ok(short_token)) => short_token,
+            _ => return Some(Token::Positional(argument)),
+        };
+        let short_name = match short_token.graphemes(true).next() {
+            Some(short_name) => short_name,
+            None => return Some(Token::Positional(argument)),
+        };
+        let field = match options
+            .into_iter()
+            .find(|field| field.option_names().contains(&short_name))
+        {
+            Some(field) => field,
+            None => return Some(Token::Positional(argument)),
+        };
+        // The name follows the leading hyphen.
+        let end = 1 + short_name.len();
+        let name = slice(&argument, 1..end);
+        let remainder = &short_token[short_name.len()..];
+        if !remainder.is_empty() {
+            if field.flags || matches!(field.shape, Shape::Empty { .. }) {
+                self.revisit = Some(Cow::Owned(format!("-{}", remainder).into_bytes()));
+            } else {
+                let start = if remainder.starts_with('=') {
+                    end + 1
+                } else {
+                    end
+                };
+                self.revisit = Some(slice(&argument, start..));
+                self.joined_value = true;
+            }
+        }
+        Some(Token::Optional { name, argument })
+    }
+
+    /// Splits a long option token of the form `name=value`, if `name` is one of `options` that
+    /// takes a value.
+    ///
+    /// The value is revisited as a joined value. Otherwise, the token is returned unchanged, so
+    /// that it is reported as it was given.
+    fn split_joined_value<'b, Options>(
+        &mut self,
+        token_name: Cow<'a, [u8]>,
+        argument: Cow<'a, [u8]>,
+        options: Options,
+    ) -> Token<'a>
+    where
+        Options: IntoIterator<Item = &'b Field>,
+    {
+        let index = match token_name.iter().position(|byte| *byte == b'=') {
+            Some(index) => index,
+            None => {
+                return Token::Optional {
+                    name: token_name,
+                    argument,
+                }
+            }
+        };
+        let name = match str::from_utf8(&token_name[..index]) {
+            Ok(name) if !name.is_empty() => name,
+            _ => {
+                return Token::Optional {
+                    name: token_name,
+                    argument,
+                }
+            }
+        };
+        let takes_value = options
+            .into_iter()
+            .find(|field| {
+                field.option_names().into_iter().any(|option_name| {
+                    option_name == name
+                        || (self.allow_abbreviations && option_name.starts_with(name))
+                })
+            })
+            .is_some_and(|field| !field.flags && !matches!(field.shape, Shape::Empty { .. }));
+        if !takes_value {
+            return Token::Optional {
+                name: token_name,
+                argument,
+            };
+        }
+        self.revisit = Some(slice(&token_name, index + 1..));
+        self.joined_value = true;
+        Token::Optional {
+            name: slice(&token_name, ..index),
+            argument,
+        }
+    }
+
+    /// Returns the next argument if it is a value, leaving it to be parsed again otherwise.
+    ///
+    /// Any argument beginning with a hyphen is not a value, including `--` and negative numbers,
+    /// except for a lone `-`. A value joined to its option, such as `foo` in `--opt=foo`, is always
+    /// a value.
+    pub(super) fn next_value(&mut self) -> Option<Cow<'a, [u8]>> {
+        if self.joined_value {
+            return self.next();
+        }
+        let value = self.next()?;
+        if value.len() > 1 && value.starts_with(b"-") {
+            self.revisit = Some(value);
+            return None;
+        }
+        Some(value)
+    }
+
+    pub(super) fn next_positional(&mut self) -> Option<Cow<'a, [u8]>> {
         self.next()
     }
 
-    pub(super) fn next_optional(&mut self) -> Option<Vec<u8>> {
+    pub(super) fn next_optional(&mut self) -> Option<Cow<'a, [u8]>> {
         if let Some(token) = self.next_token() {
             match token {
-                Token::Optional(token) => Some(token),
+                Token::Optional { name, .. } => Some(name),
                 Token::EndOfOptions => None,
                 Token::Positional(token) => {
                     self.revisit = Some(token);
@@ -85,17 +383,61 @@ where
     }
 }
 
-impl<Args> Iterator for ParsedArgs<Args>
+impl<'a, Args> ParsedArgs<'a, Args>
 where
-    Args: Iterator<Item = OsString>,
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
 {
-    type Item = Vec<u8>;
+    /// Takes every argument that has not been parsed, in their original order.
+    ///
+    /// An argument that was only partially parsed, such as the remaining short options of `-ab`
+    /// after `-a`, is included as the part that remains.
+    pub(super) fn remaining(&mut self) -> Vec<OsString> {
+        self.revisit
+            .take()
+            .into_iter()
+            .map(|bytes| os_string_from_bytes(bytes.into_owned()))
+            .chain(self.args.by_ref().map(RawArg::into_os_string))
+            .collect()
+    }
+}
+
+/// Converts bytes obtained from [`ParsedArgs`] back into an `OsString`.
+#[cfg(unix)]
+fn os_string_from_bytes(bytes: Vec<u8>) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
 
+    OsString::from_vec(bytes)
+}
+
+/// Converts bytes obtained from [`ParsedArgs`] back into an `OsString`.
+#[cfg(not(unix))]
+fn os_string_from_bytes(bytes: Vec<u8>) -> OsString {
+    // SAFETY: The bytes were obtained from `RawArg::into_encoded_bytes()`, which gives either the
+    // encoding of an `OsStr` or valid UTF-8, and were only split
+    // immediately before or after ASCII characters or joined with valid UTF-8, as permitted by
+    // `OsString::from_encoded_bytes_unchecked()`.
+    unsafe { OsString::from_encoded_bytes_unchecked(bytes) }
+}
+
+impl<'a, Args> Iterator for ParsedArgs<'a, Args>
+where
+    Args: Iterator,
+    Args::Item: RawArg<'a>,
+{
+    type Item = Cow<'a, [u8]>;
+
+    /// Returns the next argument as its platform encoding.
+    ///
+    /// This is lossless on every platform. Tokens are only split at ASCII characters, which never
+    /// appear within the encoding of another character, or within valid UTF-8, so values that are
+    /// not valid UTF-8 (or UTF-16, on Windows) are preserved.
     fn next(&mut self) -> Option<Self::Item> {
+        self.joined_value = false;
         let value = self
             .revisit
             .take()
-            .or_else(|| self.args.next().map(|os_str| os_str.into_encoded_bytes()));
+            .or_else(|| self.args.next().map(RawArg::into_encoded_bytes));
         if value.is_some() {
             self.consumed_token = true;
         }
@@ -109,6 +451,11 @@ mod tests {
         ParsedArgs,
         Token,
     };
+    use crate::trace::{
+        Field,
+        PrimitiveKind,
+        Shape,
+    };
     use claims::{
         assert_none,
         assert_some,
@@ -118,7 +465,7 @@ mod tests {
 
     #[test]
     fn next_token_none() {
-        let mut args = ParsedArgs::new([].into_iter());
+        let mut args = ParsedArgs::new(Vec::<OsString>::new().into_iter());
 
         assert_none!(args.next_token());
     }
@@ -127,35 +474,123 @@ mod tests {
     fn next_token_short_option() {
         let mut args = ParsedArgs::new([OsString::from("-h")].into_iter());
 
-        assert_some_eq!(args.next_token(), Token::Optional("h".into()));
+        assert_some_eq!(
+            args.next_token(),
+            Token::Optional {
+                name: "h".as_bytes().into(),
+                argument: "-h".as_bytes().into(),
+            }
+        );
     }
 
     #[test]
     fn next_token_short_option_grapheme() {
         let mut args = ParsedArgs::new([OsString::from("-ã")].into_iter());
 
-        assert_some_eq!(args.next_token(), Token::Optional("ã".into()));
+        assert_some_eq!(
+            args.next_token(),
+            Token::Optional {
+                name: "ã".as_bytes().into(),
+                argument: "-ã".as_bytes().into(),
+            }
+        );
     }
 
     #[test]
     fn next_token_long_option() {
         let mut args = ParsedArgs::new([OsString::from("--help")].into_iter());
 
-        assert_some_eq!(args.next_token(), Token::Optional("help".into()));
+        assert_some_eq!(
+            args.next_token(),
+            Token::Optional {
+                name: "help".as_bytes().into(),
+                argument: "--help".as_bytes().into(),
+            }
+        );
     }
 
     #[test]
     fn next_token_positional() {
         let mut args = ParsedArgs::new([OsString::from("foo")].into_iter());
 
-        assert_some_eq!(args.next_token(), Token::Positional("foo".into()));
+        assert_some_eq!(
+            args.next_token(),
+            Token::Positional("foo".as_bytes().into())
+        );
     }
 
     #[test]
     fn next_token_positional_leading_dash() {
         let mut args = ParsedArgs::new([OsString::from("-foo")].into_iter());
 
-        assert_some_eq!(args.next_token(), Token::Positional("-foo".into()));
+        assert_some_eq!(
+            args.next_token(),
+            Token::Positional("-foo".as_bytes().into())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn next_token_short_option_non_utf8() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let mut args = ParsedArgs::new([OsString::from_vec(b"-\xff".to_vec())].into_iter());
+
+        assert_some_eq!(
+            args.next_token(),
+            Token::Positional(b"-\xff".to_vec().into())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn next_token_long_option_non_utf8() {
+        use std::os::unix::ffi::OsStringExt;
+
+        let mut args = ParsedArgs::new([OsString::from_vec(b"--foo\xff".to_vec())].into_iter());
+
+        assert_some_eq!(
+            args.next_token(),
+            Token::Optional {
+                name: b"foo\xff".to_vec().into(),
+                argument: b"--foo\xff".to_vec().into(),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn next_token_unpaired_surrogate() {
+        use std::os::windows::ffi::OsStringExt;
+
+        let mut args = ParsedArgs::new([OsString::from_wide(&[0x2d, 0xd800])].into_iter());
+
+        assert_some_eq!(
+            args.next_token(),
+            Token::Positional(
+                OsString::from_wide(&[0x2d, 0xd800])
+                    .into_encoded_bytes()
+                    .into()
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn next_token_long_option_unpaired_surrogate() {
+        use std::os::windows::ffi::OsStringExt;
+
+        let mut args = ParsedArgs::new([OsString::from_wide(&[0x2d, 0x2d, 0xd800])].into_iter());
+
+        assert_some_eq!(
+            args.next_token(),
+            Token::Optional {
+                name: OsString::from_wide(&[0xd800]).into_encoded_bytes().into(),
+                argument: OsString::from_wide(&[0x2d, 0x2d, 0xd800])
+                    .into_encoded_bytes()
+                    .into(),
+            }
+        );
     }
 
     #[test]
@@ -169,12 +604,12 @@ mod tests {
     fn next_positional() {
         let mut args = ParsedArgs::new([OsString::from("foo")].into_iter());
 
-        assert_some_eq!(args.next_positional(), b"foo");
+        assert_some_eq!(args.next_positional().as_deref(), b"foo");
     }
 
     #[test]
     fn next_positional_none() {
-        let mut args = ParsedArgs::new([].into_iter());
+        let mut args = ParsedArgs::new(Vec::<OsString>::new().into_iter());
 
         assert_none!(args.next_positional());
     }
@@ -183,26 +618,26 @@ mod tests {
     fn next_positional_leading_dash() {
         let mut args = ParsedArgs::new([OsString::from("-h")].into_iter());
 
-        assert_some_eq!(args.next_positional(), b"-h");
+        assert_some_eq!(args.next_positional().as_deref(), b"-h");
     }
 
     #[test]
     fn next_positional_leading_dashes() {
         let mut args = ParsedArgs::new([OsString::from("--help")].into_iter());
 
-        assert_some_eq!(args.next_positional(), b"--help");
+        assert_some_eq!(args.next_positional().as_deref(), b"--help");
     }
 
     #[test]
     fn next_positional_double_dash() {
         let mut args = ParsedArgs::new([OsString::from("--")].into_iter());
 
-        assert_some_eq!(args.next_positional(), b"--");
+        assert_some_eq!(args.next_positional().as_deref(), b"--");
     }
 
     #[test]
     fn next_optional_none() {
-        let mut args = ParsedArgs::new([].into_iter());
+        let mut args = ParsedArgs::new(Vec::<OsString>::new().into_iter());
 
         assert_none!(args.next_optional());
     }
@@ -211,14 +646,14 @@ mod tests {
     fn next_optional_short() {
         let mut args = ParsedArgs::new([OsString::from("-h")].into_iter());
 
-        assert_some_eq!(args.next_optional(), b"h");
+        assert_some_eq!(args.next_optional().as_deref(), b"h");
     }
 
     #[test]
     fn next_optional_long() {
         let mut args = ParsedArgs::new([OsString::from("--help")].into_iter());
 
-        assert_some_eq!(args.next_optional(), b"help");
+        assert_some_eq!(args.next_optional().as_deref(), b"help");
     }
 
     #[test]
@@ -234,36 +669,327 @@ mod tests {
 
         assert_none!(args.next_optional());
         // Ensure we revisit the positional argument.
-        assert_some_eq!(args.next_positional(), b"foo");
+        assert_some_eq!(args.next_positional().as_deref(), b"foo");
+    }
+
+    fn options() -> Vec<Field> {
+        vec![
+            Field::new(
+                "a",
+                Shape::Empty {
+                    description: String::new(),
+                    version: None,
+                },
+                0,
+            ),
+            Field {
+                aliases: vec!["o"],
+                ..Field::new(
+                    "output",
+                    Shape::Primitive {
+                        name: "file".to_owned(),
+                        description: String::new(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    1,
+                )
+            },
+        ]
+    }
+
+    #[test]
+    fn next_token_with_options_short_option() {
+        let mut args = ParsedArgs::new([OsString::from("-o")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "o".as_bytes().into(),
+                argument: "-o".as_bytes().into(),
+            }
+        );
+        assert_none!(args.next_token_with_options(&options()));
+    }
+
+    #[test]
+    fn next_token_with_options_joined_value() {
+        let mut args = ParsedArgs::new([OsString::from("-ofoo")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "o".as_bytes().into(),
+                argument: "-ofoo".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("foo".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_joined_value_equals() {
+        let mut args = ParsedArgs::new([OsString::from("-o=foo")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "o".as_bytes().into(),
+                argument: "-o=foo".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("foo".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_joined_empty_value() {
+        let mut args = ParsedArgs::new([OsString::from("-o=")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "o".as_bytes().into(),
+                argument: "-o=".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_grouped() {
+        let mut args = ParsedArgs::new([OsString::from("-ao")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "a".as_bytes().into(),
+                argument: "-ao".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "o".as_bytes().into(),
+                argument: "-o".as_bytes().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_unknown() {
+        let mut args = ParsedArgs::new([OsString::from("-xo")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("-xo".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_long_option() {
+        let mut args = ParsedArgs::new([OsString::from("--output")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "output".as_bytes().into(),
+                argument: "--output".as_bytes().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_joined_value_hyphen() {
+        let mut args = ParsedArgs::new([OsString::from("-o-a")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "o".as_bytes().into(),
+                argument: "-o-a".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("-a".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_long_joined_value() {
+        let mut args = ParsedArgs::new([OsString::from("--output=foo")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "output".as_bytes().into(),
+                argument: "--output=foo".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("foo".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_long_joined_empty_value() {
+        let mut args = ParsedArgs::new([OsString::from("--output=")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "output".as_bytes().into(),
+                argument: "--output=".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("".as_bytes().into())
+        );
+        assert_none!(args.next_token_with_options(&options()));
+    }
+
+    #[test]
+    fn next_token_with_options_long_joined_option_value() {
+        let mut args = ParsedArgs::new([OsString::from("--output=--output")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "output".as_bytes().into(),
+                argument: "--output=--output".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("--output".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_long_joined_value_abbreviated() {
+        let mut args = ParsedArgs::new([OsString::from("--out=foo")].into_iter());
+        args.allow_abbreviations = true;
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "out".as_bytes().into(),
+                argument: "--out=foo".as_bytes().into(),
+            }
+        );
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Positional("foo".as_bytes().into())
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_long_joined_value_not_abbreviated() {
+        let mut args = ParsedArgs::new([OsString::from("--out=foo")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "out=foo".as_bytes().into(),
+                argument: "--out=foo".as_bytes().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_long_joined_value_no_value_taken() {
+        let mut args = ParsedArgs::new([OsString::from("--a=foo")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "a=foo".as_bytes().into(),
+                argument: "--a=foo".as_bytes().into(),
+            }
+        );
+    }
+
+    #[test]
+    fn next_token_with_options_long_joined_value_unknown() {
+        let mut args = ParsedArgs::new([OsString::from("--foo=bar")].into_iter());
+
+        assert_some_eq!(
+            args.next_token_with_options(&options()),
+            Token::Optional {
+                name: "foo=bar".as_bytes().into(),
+                argument: "--foo=bar".as_bytes().into(),
+            }
+        );
     }
 
     #[test]
     fn next_none() {
-        let mut args = ParsedArgs::new([].into_iter());
+        let mut args = ParsedArgs::new(Vec::<OsString>::new().into_iter());
 
         assert_none!(args.next());
     }
 
     #[test]
     fn next() {
-        let mut args = ParsedArgs::new(["foo".into()].into_iter());
+        let mut args = ParsedArgs::new(["foo"].into_iter());
 
-        assert_some_eq!(args.next(), b"foo");
+        assert_some_eq!(args.next().as_deref(), b"foo");
     }
 
     #[test]
     fn next_revisit() {
-        let mut args = ParsedArgs::new(["foo".into()].into_iter());
-        args.revisit = Some("bar".into());
+        let mut args = ParsedArgs::new(["foo"].into_iter());
+        args.revisit = Some("bar".as_bytes().into());
 
-        assert_some_eq!(args.next(), b"bar");
+        assert_some_eq!(args.next().as_deref(), b"bar");
     }
 
     #[test]
     fn next_consumed_token() {
-        let mut args = ParsedArgs::new(["foo".into()].into_iter());
+        let mut args = ParsedArgs::new(["foo"].into_iter());
 
         assert_some!(args.next());
         assert!(args.consumed_token);
     }
+
+    #[test]
+    fn remaining() {
+        let mut args = ParsedArgs::new(["foo", "--bar"].into_iter());
+        assert_some!(args.next());
+
+        assert_eq!(args.remaining(), vec![OsString::from("--bar")]);
+        assert_none!(args.next());
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn remaining_revisit_unpaired_surrogate() {
+        use std::os::windows::ffi::OsStringExt;
+
+        let mut args = ParsedArgs::new([OsString::from_wide(&[0x2d, 0xd800])].into_iter());
+        args.revisit = args.next();
+
+        assert_eq!(args.remaining(), vec![OsString::from_wide(&[0x2d, 0xd800])]);
+    }
+
+    #[test]
+    fn remaining_revisit() {
+        let mut args = ParsedArgs::new(["foo"].into_iter());
+        args.revisit = Some("-b".as_bytes().into());
+
+        assert_eq!(
+            args.remaining(),
+            vec![OsString::from("-b"), OsString::from("foo")]
+        );
+    }
 }
@@ -0,0 +1,2157 @@
+use crate::{
+    check::check,
+    de::Deserializer,
+    hook::{
+        ContextView,
+        PostParse,
+    },
+    parse::{
+        self,
+        parse_args,
+        Config,
+        Parsed,
+    },
+    trace::{
+        self,
+        trace_with_max_depth,
+    },
+    Error,
+    HelpLabels,
+    Metadata,
+    Warning,
+};
+use serde::de::Deserialize;
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+use std::{
+    env,
+    path::PathBuf,
+};
+use std::{
+    env::consts::EXE_SUFFIX,
+    ffi::OsString,
+    io,
+    io::Write,
+    marker::PhantomData,
+    sync::Arc,
+};
+
+/// Returns the file name of the executable, for display in help and error messages.
+#[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+pub(crate) fn executable_name(path: OsString) -> OsString {
+    let path_buf = PathBuf::from(&path);
+    if let Some(file_name) = path_buf.file_name() {
+        file_name.to_owned()
+    } else {
+        path
+    }
+}
+
+/// Returns the file name of the executable, for display in help and error messages.
+///
+/// Targets without an operating system have no meaningful path conventions, so the path is used
+/// as given.
+#[cfg(all(target_family = "wasm", target_os = "unknown"))]
+pub(crate) fn executable_name(path: OsString) -> OsString {
+    path
+}
+
+/// Returns the name the executable was invoked as, without any platform-specific extension.
+fn invoked_name(executable_path: &OsString) -> Option<String> {
+    let name = executable_name(executable_path.clone())
+        .into_string()
+        .ok()?;
+    Some(
+        name.strip_suffix(EXE_SUFFIX)
+            .map(ToOwned::to_owned)
+            .unwrap_or(name),
+    )
+}
+
+/// A value parsed from the command line, along with any warnings about the arguments.
+///
+/// This is returned by [`Parser::parse_with_warnings()`] and
+/// [`Parser::parse_prefix_with_warnings()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseOutcome<D> {
+    /// The deserialized value.
+    pub value: D,
+    /// Warnings about the arguments, in the order they were given.
+    pub warnings: Vec<Warning>,
+}
+
+impl<D> ParseOutcome<D> {
+    /// Writes each of the warnings to `writer`, one per line.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use serde_args::Parser;
+    ///
+    /// let parser = Parser::from_args_without_exe(["foo"]).name("executable");
+    /// let outcome = parser.parse_with_warnings::<String>().unwrap();
+    ///
+    /// let mut output = Vec::new();
+    /// outcome.write_warnings(&mut output).unwrap();
+    /// assert!(output.is_empty());
+    /// ```
+    pub fn write_warnings<W>(&self, writer: &mut W) -> io::Result<()>
+    where
+        W: Write,
+    {
+        for warning in &self.warnings {
+            writeln!(writer, "{warning}")?;
+        }
+        Ok(())
+    }
+
+    /// Writes the warnings to standard error, returning the value.
+    ///
+    /// Failing to write the warnings does not prevent the value from being returned.
+    fn print_warnings(self) -> D {
+        let _ = self.write_warnings(&mut io::stderr().lock());
+        self.value
+    }
+}
+
+/// The order options are listed in within help messages.
+///
+/// Passed to [`Parser::sort_options()`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum SortOrder {
+    /// Options taking values are listed first, followed by boolean flags, each in the order their
+    /// fields are declared.
+    #[default]
+    Grouped,
+    /// Options are listed in the order their fields are declared, regardless of whether they take
+    /// values.
+    Declaration,
+    /// Options are listed alphabetically by their displayed names, ignoring case.
+    Alphabetical,
+}
+
+/// Command line arguments owned by the caller.
+///
+/// Unlike [`from_env()`](crate::from_env()), which consumes the arguments while deserializing,
+/// a `Parser` holds onto the arguments so that the deserialized value can borrow from them. This
+/// allows deserializing into types like `&str` and `Cow<str>` without allocating.
+///
+/// # Example
+///
+/// This example reads a borrowed string from the command line, returning early if an error is
+/// encountered.
+///
+/// ``` rust
+/// use serde_args::Parser;
+///
+/// fn main() {
+///     let parser = Parser::from_env();
+///     let value: &str = match parser.parse_borrowed() {
+///         Ok(value) => value,
+///         Err(error) => {
+///             println!("{error}");
+///             return;
+///         }
+///     };
+///     // Execute your program with `value`...
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Parser {
+    executable_path: Option<OsString>,
+    args: Vec<OsString>,
+    labels: HelpLabels,
+    check_flag: bool,
+    short_help: bool,
+    sort_options: SortOrder,
+    case_insensitive_commands: bool,
+    match_command_indices: bool,
+    allow_abbreviations: bool,
+    max_type_depth: usize,
+    multicall: bool,
+    auto_help: Option<bool>,
+    empty_help: Option<bool>,
+    metadata: Option<Metadata>,
+    post_parse: Option<PostParse>,
+}
+
+impl Parser {
+    /// Collects the arguments from [`env::args_os()`].
+    ///
+    /// This is not available on `wasm32-unknown-unknown` and similar targets, which have no
+    /// command line to read from. Use [`from_args_without_exe()`](Parser::from_args_without_exe())
+    /// there instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name of the executable cannot be obtained.
+    #[cfg(not(all(target_family = "wasm", target_os = "unknown")))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(not(all(target_family = "wasm", target_os = "unknown"))))
+    )]
+    pub fn from_env() -> Self {
+        let mut args = env::args_os();
+        let executable_path = executable_name(args.next().expect("could not obtain binary name"));
+
+        Self {
+            executable_path: Some(executable_path),
+            args: args.collect(),
+            labels: HelpLabels::default(),
+            check_flag: false,
+            short_help: false,
+            sort_options: SortOrder::Grouped,
+            case_insensitive_commands: false,
+            match_command_indices: false,
+            allow_abbreviations: false,
+            max_type_depth: trace::DEFAULT_MAX_DEPTH,
+            multicall: false,
+            auto_help: None,
+            empty_help: None,
+            metadata: None,
+            post_parse: None,
+        }
+    }
+
+    /// Holds the given arguments, which do not include the name of the executable.
+    ///
+    /// Every argument is parsed, including the first. This differs from
+    /// [`try_from_args()`](crate::try_from_args()) and [`from_slice()`](crate::from_slice()),
+    /// which take the first argument to be the path of the executable.
+    ///
+    /// This is the way to provide arguments on `wasm32-unknown-unknown` and similar targets, which
+    /// have no command line to read from. Since the name of the executable is not included, it
+    /// must be set using [`name()`](Parser::name()) for display in help and error messages.
+    /// Parsing fails with a development error if it is not set.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// use serde_args::Parser;
+    ///
+    /// let parser = Parser::from_args_without_exe(["foo"]).name("executable");
+    ///
+    /// assert_eq!(parser.parse_borrowed::<&str>().unwrap(), "foo");
+    /// ```
+    pub fn from_args_without_exe<I, Arg>(args: I) -> Self
+    where
+        I: IntoIterator<Item = Arg>,
+        Arg: Into<OsString>,
+    {
+        Self {
+            executable_path: None,
+            args: args.into_iter().map(Into::into).collect(),
+            labels: HelpLabels::default(),
+            check_flag: false,
+            short_help: false,
+            sort_options: SortOrder::Grouped,
+            case_insensitive_commands: false,
+            match_command_indices: false,
+            allow_abbreviations: false,
+            max_type_depth: trace::DEFAULT_MAX_DEPTH,
+            multicall: false,
+            auto_help: None,
+            empty_help: None,
+            metadata: None,
+            post_parse: None,
+        }
+    }
+
+    /// Displays `name` as the name of the executable in help and error messages.
+    ///
+    /// The name is displayed exactly as given, replacing any name obtained from the environment.
+    pub fn name<Name>(mut self, name: Name) -> Self
+    where
+        Name: Into<OsString>,
+    {
+        self.executable_path = Some(name.into());
+        self
+    }
+
+    /// Displays any help or error messages using the provided [`HelpLabels`] in place of the
+    /// default English labels.
+    pub fn with_labels(mut self, labels: HelpLabels) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Enables the hidden `--__check` flag.
+    ///
+    /// When the flag is provided, the arguments are parsed fully and then checked against the
+    /// shape of the type instead of being deserialized. The type's [`Deserialize`] implementation
+    /// is never invoked, so any side effects it has (such as opening files) do not occur. Values
+    /// are checked only as far as the shape records them, such as whether a value is numeric.
+    ///
+    /// A successful check is returned as an [`Error`] for which
+    /// [`is_check_success()`](Error::is_check_success()) returns `true`. The flag is not shown in
+    /// help messages, and is treated as a positional value after `--`.
+    pub fn with_check_flag(mut self) -> Self {
+        self.check_flag = true;
+        self
+    }
+
+    /// Displays a summary when help is requested using `-h`, rather than the full help message.
+    ///
+    /// The summary lists the usage along with the names of the required arguments, options, and
+    /// commands, without any descriptions or examples. The full help message is still displayed
+    /// when help is requested using `--help`, or when no arguments are provided.
+    pub fn with_short_help(mut self) -> Self {
+        self.short_help = true;
+        self
+    }
+
+    /// Lists the options of each struct in help messages in the given `order`.
+    ///
+    /// By default, options taking values are listed before boolean flags. When sorted
+    /// alphabetically, options are sorted by the name they are displayed with, which is the name
+    /// after any `#[serde(rename_all)]` is applied, and remain listed alongside their aliases.
+    /// Options given as flags are sorted by the name of their first variant. Either way, required
+    /// arguments are listed before options, and commands are listed last.
+    ///
+    /// Only help messages are affected. Required arguments are always listed in the order they are
+    /// given, and the options suggested in error messages are unchanged.
+    pub fn sort_options(mut self, order: SortOrder) -> Self {
+        self.sort_options = order;
+        self
+    }
+
+    /// Matches command names and aliases regardless of case when `enabled` is `true`.
+    ///
+    /// Names are compared after Unicode case folding, so `clone`, `Clone`, and `CLONE` all select
+    /// the same command. The command's declared name is still what is deserialized and displayed
+    /// in help messages. Option names remain case-sensitive.
+    ///
+    /// If any enum contains two variant names or aliases that differ only by case, parsing fails
+    /// with a development error, since the user would have no way to select between them.
+    pub fn case_insensitive_commands(mut self, enabled: bool) -> Self {
+        self.case_insensitive_commands = enabled;
+        self
+    }
+
+    /// Selects a command by its index when `enabled` is `true`.
+    ///
+    /// Commands are numbered from `0` in the order their variants are declared, so `2` selects the
+    /// third command. The command's declared name is still what is deserialized and displayed in
+    /// help messages. Names always take priority over indices, so a command named or aliased `1`
+    /// is selected by `1` regardless of its position.
+    ///
+    /// This is disabled by default. A variant renamed to a number, such as with
+    /// `#[serde(rename = "2")]`, is always selected by that name.
+    pub fn match_command_indices(mut self, enabled: bool) -> Self {
+        self.match_command_indices = enabled;
+        self
+    }
+
+    /// Matches a long option given as an unambiguous prefix of its name or alias when `enabled` is
+    /// `true`.
+    ///
+    /// For example, `--for` selects `--force` if no other option in scope begins with `for`. A
+    /// prefix shared by multiple options is reported as ambiguous, listing the options it could
+    /// match, so `--f` is ambiguous if both `--force` and `--file` exist. Options named exactly
+    /// are always matched first, and short options such as `-f` are never abbreviated.
+    ///
+    /// This is disabled by default, in which case an abbreviated option is reported as
+    /// unrecognized, noting the option it abbreviates.
+    pub fn allow_abbreviations(mut self, enabled: bool) -> Self {
+        self.allow_abbreviations = enabled;
+        self
+    }
+
+    /// Limits how deeply types may be nested within the type being deserialized.
+    ///
+    /// Each struct field, enum variant, or newtype containing another type nests it one level
+    /// deeper, while `Option` and `Box` do not. A type nested more deeply than `depth` levels is
+    /// reported as a development error listing the types containing it. This catches types that
+    /// contain themselves, such as a tree whose nodes contain optional children, which could never
+    /// be described by a finite command line interface.
+    ///
+    /// Defaults to `128`.
+    pub fn max_type_depth(mut self, depth: usize) -> Self {
+        self.max_type_depth = depth;
+        self
+    }
+
+    /// Selects a command using the name of the executable when `enabled` is `true`.
+    ///
+    /// This supports a single executable installed under multiple names, each running one of its
+    /// commands. If the type is an enum and the file name of the executable matches one of its
+    /// variant names or aliases, the arguments are parsed as if that name had been given as the
+    /// first argument. Help and error messages then describe that command alone, displayed under
+    /// the executable's name. Any platform-specific extension of the executable, such as `.exe`,
+    /// is ignored when matching. If no command matches, the arguments are parsed as usual.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// # mod hidden {
+    /// use serde::Deserialize;
+    /// # }
+    /// # use serde_derive::Deserialize;
+    /// use serde_args::Parser;
+    ///
+    /// #[derive(Debug, Deserialize, PartialEq)]
+    /// #[serde(rename_all = "lowercase")]
+    /// enum Tool {
+    ///     Cat(String),
+    ///     Ls,
+    /// }
+    ///
+    /// let parser = Parser::from_args_without_exe(["notes.txt"])
+    ///     .name("/usr/bin/cat")
+    ///     .multicall(true);
+    ///
+    /// assert_eq!(
+    ///     parser.parse_borrowed::<Tool>().unwrap(),
+    ///     Tool::Cat("notes.txt".into())
+    /// );
+    /// ```
+    pub fn multicall(mut self, enabled: bool) -> Self {
+        self.multicall = enabled;
+        self
+    }
+
+    /// Recognizes the automatic `-h` and `--help` options when `enabled` is `true`.
+    ///
+    /// The options are recognized by default, unless the type declares `no_auto_help` using
+    /// [`generate`](crate::generate). Calling this method overrides the type's declaration either
+    /// way. When disabled, `--help` is parsed like any other argument and is not mentioned in help
+    /// or error messages.
+    pub fn auto_help(mut self, enabled: bool) -> Self {
+        self.auto_help = Some(enabled);
+        self
+    }
+
+    /// Displays help when no arguments are provided and some are required, when `enabled` is
+    /// `true`.
+    ///
+    /// This is enabled by default, unless the type declares `no_empty_help` using
+    /// [`generate`](crate::generate). Calling this method overrides the type's declaration either
+    /// way. When disabled, missing arguments are reported as an error instead.
+    pub fn empty_help(mut self, enabled: bool) -> Self {
+        self.empty_help = Some(enabled);
+        self
+    }
+
+    /// Merges `metadata` into the traced shape of the type, adding descriptions and version
+    /// information to types that cannot be annotated using [`generate`](crate::generate).
+    ///
+    /// See [`Metadata`] for how fields and variants are identified.
+    pub fn with_metadata(mut self, metadata: Metadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Registers a hook that is run on the parsed arguments before they are deserialized.
+    ///
+    /// The hook is given a [`ContextView`] of the parsed arguments, through which it can read
+    /// the provided fields, remove optional fields, and insert values for optional fields. This is
+    /// an escape hatch for policies that cannot be expressed by the type itself, such as
+    /// forbidding certain combinations of options or providing a value from the environment.
+    ///
+    /// Returning an error rejects the arguments. The message is displayed along with the usage
+    /// string, the same as any other problem with the user's input. Any invalid insertions are
+    /// instead reported as mistakes in the program, taking precedence over the hook's result.
+    ///
+    /// The hook is not run when help or version information is requested.
+    ///
+    /// # Example
+    ///
+    /// This example forbids providing both `--quiet` and `--verbose`.
+    ///
+    /// ``` rust
+    /// # mod hidden {
+    /// use serde::Deserialize;
+    /// # }
+    /// # use serde_derive::Deserialize;
+    /// use serde_args::Parser;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Args {
+    ///     quiet: bool,
+    ///     verbose: bool,
+    /// }
+    ///
+    /// fn main() {
+    ///     let parser = Parser::from_env().post_parse(|view| {
+    ///         if view.contains("quiet") && view.contains("verbose") {
+    ///             Err("--quiet cannot be used with --verbose".into())
+    ///         } else {
+    ///             Ok(())
+    ///         }
+    ///     });
+    ///     let args: Args = match parser.parse_borrowed() {
+    ///         Ok(args) => args,
+    ///         Err(error) => {
+    ///             println!("{error}");
+    ///             return;
+    ///         }
+    ///     };
+    ///     // Execute your program with `args`...
+    /// }
+    /// ```
+    pub fn post_parse<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&mut ContextView) -> Result<(), String> + Send + Sync + 'static,
+    {
+        self.post_parse = Some(PostParse(Arc::new(hook)));
+        self
+    }
+
+    /// Returns the name of the executable to display in help and error messages.
+    fn executable_path(&self) -> Result<OsString, Error> {
+        self.executable_path
+            .clone()
+            .ok_or_else(|| trace::Error::MissingExecutableName.into())
+    }
+
+    /// Returns the arguments to parse, along with whether a check was requested.
+    fn args(&self) -> (Vec<OsString>, bool) {
+        let mut args = self.args.clone();
+        if self.check_flag {
+            if let Some(index) = args
+                .iter()
+                .take_while(|arg| *arg != "--")
+                .position(|arg| arg == "--__check")
+            {
+                args.remove(index);
+                return (args, true);
+            }
+        }
+        (args, false)
+    }
+
+    /// Deserialize from the owned arguments, borrowing from them where possible.
+    ///
+    /// String and byte values are visited as borrowed data, allowing the returned value to refer
+    /// to the arguments held by this `Parser`. Types that do not borrow are deserialized the same
+    /// as with [`from_env()`](crate::from_env()).
+    ///
+    /// Any [`Warning`]s about the arguments are printed to standard error. To write them elsewhere
+    /// or ignore them, use [`parse_with_warnings()`](Parser::parse_with_warnings()) instead.
+    pub fn parse_borrowed<'a, D>(&'a self) -> Result<D, Error>
+    where
+        D: Deserialize<'a>,
+    {
+        self.parse(false)
+            .map(|(outcome, _)| outcome.print_warnings())
+    }
+
+    /// Deserialize from the owned arguments, returning any warnings along with the value.
+    ///
+    /// This behaves the same as [`parse_borrowed()`](Parser::parse_borrowed()), except that
+    /// [`Warning`]s about the arguments are returned rather than printed, allowing them to be
+    /// displayed however the program chooses. Warnings never cause parsing to fail.
+    pub fn parse_with_warnings<'a, D>(&'a self) -> Result<ParseOutcome<D>, Error>
+    where
+        D: Deserialize<'a>,
+    {
+        self.parse(false).map(|(outcome, _)| outcome)
+    }
+
+    /// Deserialize from the beginning of the owned arguments, returning the arguments that follow.
+    ///
+    /// Parsing stops as soon as every required argument of the type has been given, and the
+    /// remaining arguments are returned untouched in their original order. This allows handing
+    /// the rest of the command line to another parser, such as one for the selected command.
+    ///
+    /// Nothing is read once the type is satisfied, including options that it would otherwise
+    /// recognize. Options must therefore be given before the last required argument, and a type
+    /// without any required arguments reads no arguments at all.
+    ///
+    /// # Example
+    ///
+    /// ``` rust
+    /// # mod hidden {
+    /// use serde::Deserialize;
+    /// # }
+    /// # use serde_derive::Deserialize;
+    /// use serde_args::Parser;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Global {
+    ///     verbose: bool,
+    ///     command: String,
+    /// }
+    ///
+    /// let parser =
+    ///     Parser::from_args_without_exe(["--verbose", "build", "--release"]).name("executable");
+    /// let (global, remainder) = parser.parse_prefix::<Global>().unwrap();
+    ///
+    /// assert!(global.verbose);
+    /// assert_eq!(global.command, "build");
+    /// assert_eq!(remainder, ["--release"]);
+    /// ```
+    ///
+    /// Any [`Warning`]s about the arguments are printed to standard error. To write them elsewhere
+    /// or ignore them, use [`parse_prefix_with_warnings()`](Parser::parse_prefix_with_warnings())
+    /// instead.
+    pub fn parse_prefix<'a, D>(&'a self) -> Result<(D, Vec<OsString>), Error>
+    where
+        D: Deserialize<'a>,
+    {
+        self.parse(true)
+            .map(|(outcome, remainder)| (outcome.print_warnings(), remainder))
+    }
+
+    /// Deserialize from the beginning of the owned arguments, returning any warnings along with
+    /// the value and the arguments that follow.
+    ///
+    /// This behaves the same as [`parse_prefix()`](Parser::parse_prefix()), except that
+    /// [`Warning`]s about the arguments are returned rather than printed.
+    pub fn parse_prefix_with_warnings<'a, D>(
+        &'a self,
+    ) -> Result<(ParseOutcome<D>, Vec<OsString>), Error>
+    where
+        D: Deserialize<'a>,
+    {
+        self.parse(true)
+    }
+
+    /// Deserialize from the owned arguments, stopping once the type is satisfied if `prefix` is
+    /// set.
+    ///
+    /// Any arguments left unparsed are returned along with the outcome.
+    fn parse<'a, D>(&'a self, prefix: bool) -> Result<(ParseOutcome<D>, Vec<OsString>), Error>
+    where
+        D: Deserialize<'a>,
+    {
+        let executable_path = self.executable_path()?;
+        let (mut shape, settings) = trace_with_max_depth(PhantomData::<D>, self.max_type_depth)?;
+        if let Some(metadata) = &self.metadata {
+            metadata.apply(&mut shape)?;
+        }
+        // Explicit settings take precedence over those declared by the type.
+        let mut config = Config::from_settings(settings);
+        config.case_insensitive_commands = self.case_insensitive_commands;
+        config.match_command_indices = self.match_command_indices;
+        config.allow_abbreviations = self.allow_abbreviations;
+        if let Some(auto_help) = self.auto_help {
+            config.auto_help = auto_help;
+        }
+        if let Some(empty_help) = self.empty_help {
+            config.empty_help = empty_help;
+        }
+        config.prefix = prefix;
+        if self.case_insensitive_commands {
+            if let Some((first, second)) = shape.case_insensitive_conflict() {
+                return Err(trace::Error::CaseInsensitiveVariantConflict(first, second).into());
+            }
+        }
+        let (mut args, checking) = self.args();
+        // A command selected by the executable's name is given implicitly as the first argument.
+        let command = if self.multicall {
+            invoked_name(&executable_path)
+                .and_then(|name| shape.find_command(&name, self.case_insensitive_commands))
+        } else {
+            None
+        };
+        let no_args = args.is_empty();
+        if let Some(command) = command {
+            args.insert(0, command.into());
+        }
+
+        let Parsed {
+            mut context,
+            remainder,
+            warnings,
+        } = match parse_args(args, &mut shape, config) {
+            Ok(parsed) => parsed,
+            Err(error) => {
+                let error = match error {
+                    // Help is displayed when nothing but the implicit command was given.
+                    parse::Error::MissingArguments(_)
+                    | parse::Error::MissingCommand { .. }
+                    | parse::Error::MissingOptions(_)
+                    | parse::Error::MissingOneOfOptions(_)
+                        if command.is_some() && no_args && config.empty_help =>
+                    {
+                        parse::Error::Help { short: false }
+                    }
+                    error if command.is_some() => error.without_command(),
+                    error => error,
+                };
+                return Err(Error::from_parsing_error(
+                    error,
+                    executable_path.clone(),
+                    shape,
+                    self.labels.clone(),
+                )
+                .with_short_help(self.short_help)
+                .with_sort_options(self.sort_options)
+                .with_auto_help(config.auto_help)
+                .with_multicall(command.is_some()));
+            }
+        };
+
+        if let Some(PostParse(hook)) = &self.post_parse {
+            let mut view = ContextView::new(&mut context, &shape);
+            let result = hook(&mut view);
+            if let Some(error) = view.into_invalid() {
+                return Err(error.into());
+            }
+            if let Err(message) = result {
+                return Err(Error::from_rejection(
+                    message,
+                    executable_path.clone(),
+                    shape,
+                    self.labels.clone(),
+                )
+                .with_short_help(self.short_help)
+                .with_sort_options(self.sort_options)
+                .with_auto_help(config.auto_help)
+                .with_multicall(command.is_some()));
+            }
+        }
+
+        if checking {
+            return Err(match check(&context, &shape) {
+                Ok(()) => Error::checked(),
+                Err(error) => Error::from_deserializing_error(
+                    error,
+                    executable_path.clone(),
+                    shape,
+                    self.labels.clone(),
+                )
+                .with_short_help(self.short_help)
+                .with_sort_options(self.sort_options)
+                .with_auto_help(config.auto_help)
+                .with_multicall(command.is_some()),
+            });
+        }
+
+        D::deserialize(Deserializer::with_source(context, &self.args))
+            .map(|value| (ParseOutcome { value, warnings }, remainder))
+            .map_err(|error| {
+                Error::from_deserializing_error(error, executable_path, shape, self.labels.clone())
+                    .with_short_help(self.short_help)
+                    .with_sort_options(self.sort_options)
+                    .with_auto_help(config.auto_help)
+                    .with_multicall(command.is_some())
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        executable_name,
+        invoked_name,
+        ParseOutcome,
+        Parser,
+        SortOrder,
+    };
+    use crate::{
+        trace,
+        HelpLabels,
+        Metadata,
+    };
+    use claims::{
+        assert_err,
+        assert_matches,
+        assert_ok,
+        assert_ok_eq,
+        assert_some_eq,
+    };
+    use serde::de::{
+        Deserialize,
+        Deserializer,
+        MapAccess,
+        Visitor,
+    };
+    use serde_derive::Deserialize;
+    use std::{
+        borrow::Cow,
+        env,
+        ffi::OsString,
+        fmt,
+        fmt::Formatter,
+        sync::atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+    };
+
+    #[derive(Debug, Deserialize)]
+    struct Args {
+        #[allow(dead_code)]
+        count: u64,
+        #[allow(dead_code)]
+        name: String,
+    }
+
+    fn parser(args: &[&str]) -> Parser {
+        Parser {
+            executable_path: Some("executable".into()),
+            args: args.iter().map(OsString::from).collect(),
+            labels: HelpLabels::default(),
+            check_flag: false,
+            short_help: false,
+            sort_options: SortOrder::Grouped,
+            case_insensitive_commands: false,
+            match_command_indices: false,
+            allow_abbreviations: false,
+            max_type_depth: trace::DEFAULT_MAX_DEPTH,
+            multicall: false,
+            auto_help: None,
+            empty_help: None,
+            metadata: None,
+            post_parse: None,
+        }
+    }
+
+    #[test]
+    fn executable_name_file_name() {
+        assert_eq!(
+            executable_name("/usr/bin/foo".into()),
+            OsString::from("foo")
+        );
+    }
+
+    #[test]
+    fn executable_name_no_file_name() {
+        assert_eq!(executable_name("/".into()), OsString::from("/"));
+    }
+
+    #[test]
+    fn invoked_name_file_name() {
+        assert_some_eq!(invoked_name(&"/usr/bin/foo".into()), "foo");
+    }
+
+    #[test]
+    fn invoked_name_extension() {
+        assert_some_eq!(
+            invoked_name(&format!("/usr/bin/foo{}", env::consts::EXE_SUFFIX).into()),
+            "foo"
+        );
+    }
+
+    #[test]
+    fn from_args_without_exe_name() {
+        let parser = Parser::from_args_without_exe(["foo"]).name("/usr/bin/executable");
+
+        assert_ok_eq!(parser.parse_borrowed::<&str>(), "foo");
+        let error = assert_err!(Parser::from_args_without_exe(Vec::<&str>::new())
+            .name("/usr/bin/executable")
+            .parse_borrowed::<&str>());
+        // The name is displayed as given.
+        assert!(format!("{error}").contains("USAGE: /usr/bin/executable <a borrowed string>"));
+    }
+
+    #[test]
+    fn from_args_without_exe_missing_name() {
+        let parser = Parser::from_args_without_exe(["foo"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<&str>())),
+            "the name of the executable could not be obtained; set it using `Parser::name()`"
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_str() {
+        let parser = parser(&["foo"]);
+
+        assert_ok_eq!(parser.parse_borrowed::<&str>(), "foo");
+    }
+
+    #[test]
+    fn parse_borrowed_struct() {
+        #[derive(Debug, Deserialize)]
+        struct Args<'a> {
+            name: &'a str,
+            #[serde(borrow)]
+            greeting: Cow<'a, str>,
+            punctuation: Option<&'a str>,
+        }
+
+        let parser = parser(&["foo", "hello", "--punctuation", "!"]);
+        let args = assert_ok!(parser.parse_borrowed::<Args>());
+
+        assert_eq!(args.name, "foo");
+        assert_matches!(args.greeting, Cow::Borrowed("hello"));
+        assert_eq!(args.punctuation, Some("!"));
+    }
+
+    #[test]
+    fn parse_borrowed_struct_variant() {
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        enum Command<'a> {
+            Push {
+                #[serde(borrow)]
+                remote: Cow<'a, str>,
+                branch: Option<&'a str>,
+            },
+        }
+
+        let parser = parser(&["push", "origin", "--branch", "main"]);
+        let Command::Push { remote, branch } = assert_ok!(parser.parse_borrowed::<Command>());
+
+        assert_matches!(remote, Cow::Borrowed("origin"));
+        assert_eq!(branch, Some("main"));
+    }
+
+    #[test]
+    fn parse_borrowed_owned() {
+        let parser = parser(&["42"]);
+
+        assert_ok_eq!(parser.parse_borrowed::<u64>(), 42);
+    }
+
+    #[test]
+    fn parse_borrowed_missing_argument() {
+        let parser = parser(&[]);
+
+        assert_err!(parser.parse_borrowed::<&str>());
+    }
+
+    #[test]
+    fn parse_borrowed_check_valid() {
+        let parser = parser(&["--__check", "42", "foo"]).with_check_flag();
+
+        assert!(assert_err!(parser.parse_borrowed::<Args>()).is_check_success());
+    }
+
+    #[test]
+    fn parse_borrowed_check_flag_after_arguments() {
+        let parser = parser(&["42", "foo", "--__check"]).with_check_flag();
+
+        assert!(assert_err!(parser.parse_borrowed::<Args>()).is_check_success());
+    }
+
+    #[test]
+    fn parse_borrowed_check_invalid_value() {
+        let parser = parser(&["--__check", "foo", "bar"]).with_check_flag();
+        let error = assert_err!(parser.parse_borrowed::<Args>());
+
+        assert!(!error.is_check_success());
+        assert_eq!(
+            format!("{}", error),
+            "ERROR: invalid value: expected u64, found 'foo'\n\nUSAGE: executable <count> <name>\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_check_invalid_value_out_of_range() {
+        let parser = parser(&["--__check", "256"]).with_check_flag();
+        let error = assert_err!(parser.parse_borrowed::<u8>());
+
+        assert!(!error.is_check_success());
+    }
+
+    #[test]
+    fn parse_borrowed_check_does_not_deserialize() {
+        static OPENED: AtomicUsize = AtomicUsize::new(0);
+
+        // Simulates a side effect that occurs once the value has been deserialized, such as
+        // opening a file.
+        fn open<'de, D>(deserializer: D) -> Result<String, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let path = String::deserialize(deserializer)?;
+            OPENED.fetch_add(1, Ordering::SeqCst);
+            Ok(path)
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Args {
+            #[allow(dead_code)]
+            #[serde(deserialize_with = "open")]
+            path: String,
+        }
+
+        let checking_parser = parser(&["--__check", "foo"]).with_check_flag();
+        assert!(assert_err!(checking_parser.parse_borrowed::<Args>()).is_check_success());
+        assert_eq!(OPENED.load(Ordering::SeqCst), 0);
+
+        let parsing_parser = parser(&["foo"]).with_check_flag();
+        assert_ok!(parsing_parser.parse_borrowed::<Args>());
+        assert_eq!(OPENED.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn parse_borrowed_check_flag_not_enabled() {
+        let parser = parser(&["--__check", "42", "foo"]);
+        let error = assert_err!(parser.parse_borrowed::<Args>());
+
+        assert!(!error.is_check_success());
+    }
+
+    #[test]
+    fn parse_borrowed_check_flag_after_end_of_options() {
+        let parser = parser(&["--", "--__check"]).with_check_flag();
+
+        assert_ok_eq!(parser.parse_borrowed::<&str>(), "--__check");
+    }
+
+    #[test]
+    fn parse_borrowed_transparent_newtypes() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        #[serde(transparent)]
+        struct Port(u16);
+
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        #[serde(transparent)]
+        struct Timeout(Option<u64>);
+
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        #[serde(transparent)]
+        struct Address(Port);
+
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Args {
+            port: Port,
+            address: Address,
+            timeout: Timeout,
+        }
+
+        let parser = parser(&["80", "8080", "--timeout", "30"]);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Args>(),
+            Args {
+                port: Port(80),
+                address: Address(Port(8080)),
+                timeout: Timeout(Some(30)),
+            }
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    #[serde(field_identifier, rename_all = "lowercase")]
+    enum Key {
+        Verbose,
+        Path,
+        Force,
+        Output,
+    }
+
+    /// Records the order in which the deserializer provides the fields.
+    #[derive(Debug, Eq, PartialEq)]
+    struct FieldOrder(Vec<Key>);
+
+    impl<'de> Deserialize<'de> for FieldOrder {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct FieldOrderVisitor;
+
+            impl<'de> Visitor<'de> for FieldOrderVisitor {
+                type Value = FieldOrder;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("struct FieldOrder")
+                }
+
+                fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                where
+                    A: MapAccess<'de>,
+                {
+                    let mut keys = Vec::new();
+                    while let Some(key) = map.next_key()? {
+                        match key {
+                            Key::Verbose | Key::Force => {
+                                map.next_value::<bool>()?;
+                            }
+                            Key::Path => {
+                                map.next_value::<String>()?;
+                            }
+                            Key::Output => {
+                                map.next_value::<Option<String>>()?;
+                            }
+                        }
+                        keys.push(key);
+                    }
+                    Ok(FieldOrder(keys))
+                }
+            }
+
+            deserializer.deserialize_struct(
+                "FieldOrder",
+                &["verbose", "path", "force", "output"],
+                FieldOrderVisitor,
+            )
+        }
+    }
+
+    #[test]
+    fn parse_borrowed_field_order_flags_defaulted() {
+        let parser = parser(&["foo"]);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<FieldOrder>(),
+            FieldOrder(vec![Key::Verbose, Key::Path, Key::Force, Key::Output])
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_field_order_flags_passed() {
+        let parser = parser(&["--output", "bar", "--force", "foo", "--verbose"]);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<FieldOrder>(),
+            FieldOrder(vec![Key::Verbose, Key::Path, Key::Force, Key::Output])
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_field_order_some_flags_passed() {
+        let parser = parser(&["foo", "--force"]);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<FieldOrder>(),
+            FieldOrder(vec![Key::Verbose, Key::Path, Key::Force, Key::Output])
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_field_order_derived() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Args {
+            verbose: bool,
+            path: String,
+            force: bool,
+            output: Option<String>,
+        }
+
+        let parser = parser(&["--output", "bar", "--force", "foo"]);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Args>(),
+            Args {
+                verbose: false,
+                path: "foo".into(),
+                force: true,
+                output: Some("bar".into()),
+            }
+        );
+    }
+
+    /// Declares booleans and options interleaved, with a command in between.
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Interleaved {
+        verbose: bool,
+        output: Option<String>,
+        command: Command,
+        force: bool,
+        path: String,
+        jobs: Option<u64>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "lowercase")]
+    #[allow(dead_code)]
+    enum Command {
+        Build,
+        Test,
+    }
+
+    #[test]
+    fn help_short() {
+        let parser = parser(&["-h"]).with_short_help();
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Interleaved>())),
+            "USAGE: executable [options] <command> <path>\n\nRequired Arguments:\n  <command>\n  <path>\n\nGlobal Options:\n  --output <a string>\n  --jobs <u64>\n  --verbose\n  --force\n\nOverride Options:\n  -h --help\n\nCommand Variants:\n  build\n  test"
+        );
+    }
+
+    #[test]
+    fn help_short_not_requested() {
+        let parser = parser(&["-h"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Interleaved>())),
+            "struct Interleaved\n\nUSAGE: executable [options] <command> <path>\n\nRequired Arguments:\n  <command>  \n  <path>     \n\nGlobal Options:\n  --output <a string>  \n  --jobs <u64>         \n  --verbose            \n  --force              \n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  build   \n  test    "
+        );
+    }
+
+    #[test]
+    fn help_long_with_short_help() {
+        let parser = parser(&["--help"]).with_short_help();
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Interleaved>())),
+            "struct Interleaved\n\nUSAGE: executable [options] <command> <path>\n\nRequired Arguments:\n  <command>  \n  <path>     \n\nGlobal Options:\n  --output <a string>  \n  --jobs <u64>         \n  --verbose            \n  --force              \n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  build   \n  test    "
+        );
+    }
+
+    #[test]
+    fn help_grouped() {
+        let parser = parser(&["--help"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Interleaved>())),
+            "struct Interleaved\n\nUSAGE: executable [options] <command> <path>\n\nRequired Arguments:\n  <command>  \n  <path>     \n\nGlobal Options:\n  --output <a string>  \n  --jobs <u64>         \n  --verbose            \n  --force              \n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  build   \n  test    "
+        );
+    }
+
+    #[test]
+    fn help_grouped_explicit() {
+        let parser = parser(&["--help"]).sort_options(SortOrder::Grouped);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Interleaved>())),
+            "struct Interleaved\n\nUSAGE: executable [options] <command> <path>\n\nRequired Arguments:\n  <command>  \n  <path>     \n\nGlobal Options:\n  --output <a string>  \n  --jobs <u64>         \n  --verbose            \n  --force              \n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  build   \n  test    "
+        );
+    }
+
+    #[test]
+    fn help_alphabetical() {
+        let parser = parser(&["--help"]).sort_options(SortOrder::Alphabetical);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Interleaved>())),
+            "struct Interleaved\n\nUSAGE: executable [options] <command> <path>\n\nRequired Arguments:\n  <command>  \n  <path>     \n\nGlobal Options:\n  --force              \n  --jobs <u64>         \n  --output <a string>  \n  --verbose            \n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  build   \n  test    "
+        );
+    }
+
+    #[test]
+    fn help_declaration_order() {
+        let parser = parser(&["--help"]).sort_options(SortOrder::Declaration);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Interleaved>())),
+            "struct Interleaved\n\nUSAGE: executable [options] <command> <path>\n\nRequired Arguments:\n  <command>  \n  <path>     \n\nGlobal Options:\n  --verbose            \n  --output <a string>  \n  --force              \n  --jobs <u64>         \n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  build   \n  test    "
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    #[serde(rename_all = "kebab-case")]
+    struct Style {
+        zoom: Option<u64>,
+        #[serde(alias = "x")]
+        width: Option<u64>,
+        #[serde(alias = "strong")]
+        bold: bool,
+        all_caps: bool,
+    }
+
+    #[test]
+    fn help_alphabetical_renamed_with_aliases() {
+        let parser = parser(&["--help"]).sort_options(SortOrder::Alphabetical);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Style>())),
+            "struct Style\n\nUSAGE: executable [options]\n\nGlobal Options:\n     --all-caps        \n     --bold --strong   \n  -x --width <u64>     \n     --zoom <u64>      \n\nOverride Options:\n  -h --help  Display this message."
+        );
+    }
+
+    #[test]
+    fn parse_alphabetical_unaffected() {
+        let parser =
+            parser(&["--strong", "-x", "2", "--zoom", "3"]).sort_options(SortOrder::Alphabetical);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Style>(),
+            Style {
+                zoom: Some(3),
+                width: Some(2),
+                bold: true,
+                all_caps: false,
+            }
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Deploy {
+        target: String,
+        dry_run: bool,
+        force: bool,
+        tenant: Option<String>,
+        retries: Option<u64>,
+    }
+
+    #[test]
+    fn post_parse_veto() {
+        let parser = parser(&["prod", "--dry_run", "--force"]).post_parse(|view| {
+            if view.contains("dry_run") && view.contains("force") {
+                Err("--dry_run cannot be used with --force".into())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Deploy>())),
+            "ERROR: --dry_run cannot be used with --force\n\nUSAGE: executable [options] <target>\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn post_parse_veto_not_triggered() {
+        let parser = parser(&["prod", "--force"]).post_parse(|view| {
+            if view.contains("dry_run") && view.contains("force") {
+                Err("--dry_run cannot be used with --force".into())
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Deploy>(),
+            Deploy {
+                target: "prod".into(),
+                dry_run: false,
+                force: true,
+                tenant: None,
+                retries: None,
+            }
+        );
+    }
+
+    #[test]
+    fn post_parse_inject() {
+        let parser = parser(&["prod"]).post_parse(|view| {
+            if !view.contains("tenant") {
+                view.insert("tenant", "default");
+            }
+            view.insert("retries", "3");
+            Ok(())
+        });
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Deploy>(),
+            Deploy {
+                target: "prod".into(),
+                dry_run: false,
+                force: false,
+                tenant: Some("default".into()),
+                retries: Some(3),
+            }
+        );
+    }
+
+    #[test]
+    fn post_parse_inject_replaces_value() {
+        let parser = parser(&["prod", "--tenant", "foo"]).post_parse(|view| {
+            assert_eq!(view.value("tenant").as_deref(), Some("foo"));
+            view.insert("tenant", "bar");
+            Ok(())
+        });
+
+        assert_eq!(
+            assert_ok!(parser.parse_borrowed::<Deploy>()).tenant,
+            Some("bar".into())
+        );
+    }
+
+    #[test]
+    fn post_parse_remove() {
+        let parser = parser(&["prod", "--force", "--tenant", "foo"]).post_parse(|view| {
+            assert!(view.remove("force"));
+            assert!(view.remove("tenant"));
+            assert!(!view.remove("dry_run"));
+            assert!(!view.remove("target"));
+            Ok(())
+        });
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Deploy>(),
+            Deploy {
+                target: "prod".into(),
+                dry_run: false,
+                force: false,
+                tenant: None,
+                retries: None,
+            }
+        );
+    }
+
+    #[test]
+    fn post_parse_read() {
+        let parser = parser(&["prod", "--retries", "2"]).post_parse(|view| {
+            assert!(view.contains("target"));
+            assert!(view.contains("retries"));
+            assert!(!view.contains("tenant"));
+            assert!(!view.contains("unknown"));
+            assert_eq!(view.value("target").as_deref(), Some("prod"));
+            assert_eq!(view.value("retries").as_deref(), Some("2"));
+            assert_eq!(view.value("tenant"), None);
+            assert_eq!(view.value("force"), None);
+            Ok(())
+        });
+
+        assert_ok!(parser.parse_borrowed::<Deploy>());
+    }
+
+    #[test]
+    fn post_parse_inject_unknown_option() {
+        let parser = parser(&["prod"]).post_parse(|view| {
+            view.insert("region", "eu");
+            Ok(())
+        });
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Deploy>())),
+            "cannot inject a value for --region: no such option exists"
+        );
+    }
+
+    #[test]
+    fn post_parse_inject_required_field() {
+        let parser = parser(&["prod"]).post_parse(|view| {
+            view.insert("target", "staging");
+            Ok(())
+        });
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Deploy>())),
+            "cannot inject a value for --target: no such option exists"
+        );
+    }
+
+    #[test]
+    fn post_parse_inject_boolean_flag() {
+        let parser = parser(&["prod"]).post_parse(|view| {
+            view.insert("force", "true");
+            Ok(())
+        });
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Deploy>())),
+            "cannot inject a value for --force: the option does not take a single value"
+        );
+    }
+
+    #[test]
+    fn post_parse_inject_invalid_value() {
+        let parser = parser(&["prod"]).post_parse(|view| {
+            view.insert("retries", "abc");
+            Ok(())
+        });
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Deploy>())),
+            "cannot inject a value for --retries: invalid value: expected u64, found 'abc'"
+        );
+    }
+
+    #[test]
+    fn post_parse_invalid_injection_precedes_veto() {
+        let parser = parser(&["prod"]).post_parse(|view| {
+            view.insert("retries", "abc");
+            Err("rejected".into())
+        });
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Deploy>())),
+            "cannot inject a value for --retries: invalid value: expected u64, found 'abc'"
+        );
+    }
+
+    #[test]
+    fn post_parse_not_run_for_help() {
+        let parser = parser(&["--help"]).post_parse(|_| Err("rejected".into()));
+
+        assert!(
+            !format!("{}", assert_err!(parser.parse_borrowed::<Deploy>())).contains("rejected")
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    #[serde(rename_all = "lowercase")]
+    enum Git {
+        Clone {
+            url: String,
+        },
+        #[serde(alias = "ci")]
+        Commit,
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Global {
+        verbose: bool,
+        command: String,
+    }
+
+    #[test]
+    fn parse_prefix_struct() {
+        let parser = parser(&["--verbose", "run", "--release", "foo"]);
+
+        assert_ok_eq!(
+            parser.parse_prefix::<Global>(),
+            (
+                Global {
+                    verbose: true,
+                    command: "run".into(),
+                },
+                vec![OsString::from("--release"), OsString::from("foo")]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_prefix_struct_options_after_satisfied() {
+        let parser = parser(&["run", "--verbose"]);
+
+        assert_ok_eq!(
+            parser.parse_prefix::<Global>(),
+            (
+                Global {
+                    verbose: false,
+                    command: "run".into(),
+                },
+                vec![OsString::from("--verbose")]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_prefix_enum_unit_variant() {
+        let parser = parser(&["commit", "-m", "message"]);
+
+        assert_ok_eq!(
+            parser.parse_prefix::<Git>(),
+            (
+                Git::Commit,
+                vec![OsString::from("-m"), OsString::from("message")]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_prefix_no_remainder() {
+        let parser = parser(&["run"]);
+
+        assert_ok_eq!(
+            parser.parse_prefix::<Global>(),
+            (
+                Global {
+                    verbose: false,
+                    command: "run".into(),
+                },
+                vec![]
+            )
+        );
+    }
+
+    #[test]
+    fn parse_prefix_missing_argument() {
+        let parser = parser(&["--verbose"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_prefix::<Global>())),
+            "ERROR: missing required positional argument: <command>\n\nUSAGE: executable [options] <command>\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_unaffected_by_prefix() {
+        let parser = parser(&["run", "--release"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Global>())),
+            "ERROR: unrecognized optional flag: '--release'\n\n  tip: a similar option exists: --verbose\n\nUSAGE: executable [options] <command>\n\nFor more information, use --help."
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Paint {
+        #[serde(alias = "colour", alias = "c")]
+        color: Option<String>,
+    }
+
+    fn deprecated_colour() -> Metadata {
+        Metadata::new().deprecate_alias("color", "colour", "use --color instead")
+    }
+
+    #[test]
+    fn parse_with_warnings_deprecated_field_alias() {
+        let parser = parser(&["--colour", "red"]).with_metadata(deprecated_colour());
+
+        let outcome = assert_ok!(parser.parse_with_warnings::<Paint>());
+
+        assert_eq!(
+            outcome.value,
+            Paint {
+                color: Some("red".into())
+            }
+        );
+        assert_eq!(
+            outcome
+                .warnings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["WARNING: --colour is deprecated: use --color instead"]
+        );
+    }
+
+    #[test]
+    fn parse_with_warnings_canonical_name() {
+        let parser = parser(&["--color", "red"]).with_metadata(deprecated_colour());
+
+        assert_ok_eq!(
+            parser.parse_with_warnings::<Paint>(),
+            ParseOutcome {
+                value: Paint {
+                    color: Some("red".into())
+                },
+                warnings: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_with_warnings_other_alias() {
+        let parser = parser(&["-c", "red"]).with_metadata(deprecated_colour());
+
+        assert_ok_eq!(
+            parser.parse_with_warnings::<Paint>(),
+            ParseOutcome {
+                value: Paint {
+                    color: Some("red".into())
+                },
+                warnings: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_with_warnings_deprecated_variant_alias() {
+        let parser = parser(&["ci"]).with_metadata(Metadata::new().deprecate_alias(
+            "commit",
+            "ci",
+            "use commit instead",
+        ));
+
+        let outcome = assert_ok!(parser.parse_with_warnings::<Git>());
+
+        assert_eq!(outcome.value, Git::Commit);
+        assert_eq!(
+            outcome
+                .warnings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["WARNING: ci is deprecated: use commit instead"]
+        );
+    }
+
+    #[test]
+    fn parse_borrowed_deprecated_alias() {
+        let parser = parser(&["--colour", "red"]).with_metadata(deprecated_colour());
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Paint>(),
+            Paint {
+                color: Some("red".into())
+            }
+        );
+    }
+
+    #[test]
+    fn parse_prefix_with_warnings_deprecated_alias() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        struct Brush {
+            #[serde(alias = "colour")]
+            color: Option<String>,
+            stroke: String,
+        }
+
+        let parser =
+            parser(&["--colour", "red", "wide", "--release"]).with_metadata(deprecated_colour());
+
+        let (outcome, remainder) = assert_ok!(parser.parse_prefix_with_warnings::<Brush>());
+
+        assert_eq!(
+            outcome.value,
+            Brush {
+                color: Some("red".into()),
+                stroke: "wide".into(),
+            }
+        );
+        assert_eq!(
+            outcome
+                .warnings
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["WARNING: --colour is deprecated: use --color instead"]
+        );
+        assert_eq!(remainder, vec![OsString::from("--release")]);
+    }
+
+    #[test]
+    fn write_warnings() {
+        let parser = parser(&["--colour", "red"]).with_metadata(deprecated_colour());
+        let outcome = assert_ok!(parser.parse_with_warnings::<Paint>());
+
+        let mut output = Vec::new();
+        assert_ok!(outcome.write_warnings(&mut output));
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "WARNING: --colour is deprecated: use --color instead\n"
+        );
+    }
+
+    #[test]
+    fn write_warnings_none() {
+        let outcome = ParseOutcome {
+            value: (),
+            warnings: Vec::new(),
+        };
+
+        let mut output = Vec::new();
+        assert_ok!(outcome.write_warnings(&mut output));
+
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn parse_with_warnings_unknown_alias() {
+        let parser = parser(&["--color", "red"])
+            .with_metadata(Metadata::new().deprecate_alias("color", "colr", "foo"));
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_with_warnings::<Paint>())),
+            "metadata deprecates alias `colr` of `color`, which is not one of its aliases"
+        );
+    }
+
+    #[test]
+    fn case_insensitive_commands_mixed_case() {
+        let parser = parser(&["ClOnE", "https://example.com"]).case_insensitive_commands(true);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Git>(),
+            Git::Clone {
+                url: "https://example.com".into()
+            }
+        );
+    }
+
+    #[test]
+    fn case_insensitive_commands_mixed_case_alias() {
+        let parser = parser(&["CI"]).case_insensitive_commands(true);
+
+        assert_ok_eq!(parser.parse_borrowed::<Git>(), Git::Commit);
+    }
+
+    #[test]
+    fn case_insensitive_commands_disabled() {
+        let parser = parser(&["CLONE", "https://example.com"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Git>())),
+            "ERROR: unrecognized command: 'CLONE'\n\n  tip: a similar command exists: clone\n\nUSAGE: executable <Git>\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn case_insensitive_commands_options_case_sensitive() {
+        let parser = parser(&["prod", "--FORCE"]).case_insensitive_commands(true);
+
+        assert_err!(parser.parse_borrowed::<Deploy>());
+    }
+
+    #[test]
+    fn case_insensitive_commands_conflict() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        enum Conflicting {
+            #[serde(rename = "build")]
+            Build,
+            #[serde(rename = "BUILD")]
+            BuildAll,
+        }
+
+        let parser = parser(&["build"]).case_insensitive_commands(true);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Conflicting>())),
+            "variants `build` and `BUILD` cannot be distinguished when matching commands case-insensitively"
+        );
+    }
+
+    #[test]
+    fn case_insensitive_commands_conflict_allowed_when_disabled() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        enum Conflicting {
+            #[serde(rename = "build")]
+            Build,
+            #[serde(rename = "BUILD")]
+            BuildAll,
+        }
+
+        let parser = parser(&["BUILD"]);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Conflicting>(),
+            Conflicting::BuildAll
+        );
+    }
+
+    #[test]
+    fn match_command_indices() {
+        let parser = parser(&["1"]).match_command_indices(true);
+
+        assert_ok_eq!(parser.parse_borrowed::<Git>(), Git::Commit);
+    }
+
+    #[test]
+    fn match_command_indices_with_value() {
+        let parser = parser(&["0", "https://example.com"]).match_command_indices(true);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Git>(),
+            Git::Clone {
+                url: "https://example.com".into()
+            }
+        );
+    }
+
+    #[test]
+    fn match_command_indices_disabled() {
+        let parser = parser(&["1"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Git>())),
+            "ERROR: unrecognized command: '1'\n\n  tip: a similar command exists: ci\n\nUSAGE: executable <Git>\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn match_command_indices_name_takes_priority() {
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        enum Level {
+            #[serde(rename = "high")]
+            High,
+            #[serde(rename = "0")]
+            Low,
+        }
+
+        let parser = parser(&["0"]).match_command_indices(true);
+
+        assert_ok_eq!(parser.parse_borrowed::<Level>(), Level::Low);
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Copy {
+        source: String,
+        force: bool,
+        #[serde(alias = "f")]
+        file: Option<String>,
+        filter: Option<String>,
+    }
+
+    #[test]
+    fn allow_abbreviations_unique_prefix() {
+        let parser = parser(&["foo", "--fo"]).allow_abbreviations(true);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Copy>(),
+            Copy {
+                source: "foo".into(),
+                force: true,
+                file: None,
+                filter: None,
+            }
+        );
+    }
+
+    #[test]
+    fn allow_abbreviations_ambiguous_prefix() {
+        let parser = parser(&["foo", "--fi", "bar"]).allow_abbreviations(true);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Copy>())),
+            "ERROR: '--fi' is ambiguous: could match '--file', '--filter'\n\nUSAGE: executable [options] <source>\n\nFor more information, use --help."
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Remove {
+        force: bool,
+        file: Option<String>,
+        output: Option<String>,
+    }
+
+    #[test]
+    fn allow_abbreviations_ambiguous_single_character() {
+        let parser = parser(&["--f"]).allow_abbreviations(true);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Remove>())),
+            "ERROR: '--f' is ambiguous: could match '--file', '--force'\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn allow_abbreviations_unique_single_character() {
+        let parser = parser(&["--o", "foo"]).allow_abbreviations(true);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Remove>(),
+            Remove {
+                force: false,
+                file: None,
+                output: Some("foo".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn allow_abbreviations_disabled() {
+        let parser = parser(&["foo", "--forc"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Copy>())),
+            "ERROR: unrecognized optional flag: '--forc' (unabbreviated option '--force' exists; abbreviations are not enabled)\n\nUSAGE: executable [options] <source>\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn allow_abbreviations_exact_short_alias() {
+        let parser = parser(&["foo", "-f", "bar"]).allow_abbreviations(true);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Copy>(),
+            Copy {
+                source: "foo".into(),
+                force: false,
+                file: Some("bar".into()),
+                filter: None,
+            }
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Tree {
+        name: String,
+        child: Option<Box<Tree>>,
+    }
+
+    #[test]
+    fn max_type_depth_recursive() {
+        let parser = parser(&["foo"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Tree>())),
+            "recursive or overly deep type detected while tracing: Tree -> Option -> Tree"
+        );
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Outer {
+        inner: Inner,
+    }
+
+    #[derive(Debug, Deserialize, Eq, PartialEq)]
+    struct Inner {
+        value: u32,
+    }
+
+    #[test]
+    fn max_type_depth_within_limit() {
+        let parser = parser(&["42"]).max_type_depth(2);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Outer>(),
+            Outer {
+                inner: Inner { value: 42 }
+            }
+        );
+    }
+
+    #[test]
+    fn max_type_depth_exceeded() {
+        let parser = parser(&["42"]).max_type_depth(1);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Outer>())),
+            "recursive or overly deep type detected while tracing: Outer -> Inner"
+        );
+    }
+
+    /// A newtype around a string that declares both `no_auto_help` and `no_empty_help`, as
+    /// `#[serde_args::generate(no_auto_help, no_empty_help)]` would.
+    #[derive(Debug, Eq, PartialEq)]
+    struct Embedded(String);
+
+    impl<'de> Deserialize<'de> for Embedded {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            struct EmbeddedVisitor;
+
+            impl<'de> Visitor<'de> for EmbeddedVisitor {
+                type Value = Embedded;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    // The settings record, as described in the specification.
+                    if formatter.fill() == 's' {
+                        formatter.write_str("14:no_auto_help\x1F\x1F15:no_empty_help\x1F\x1F")
+                    } else {
+                        formatter.write_str("an embedded value")
+                    }
+                }
+
+                fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                where
+                    D: Deserializer<'de>,
+                {
+                    String::deserialize(deserializer).map(Embedded)
+                }
+            }
+
+            deserializer.deserialize_newtype_struct("Embedded", EmbeddedVisitor)
+        }
+    }
+
+    #[test]
+    fn auto_help_default() {
+        let parser = parser(&["--help"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<String>())),
+            "a string\n\nUSAGE: executable <a string>\n\nRequired Arguments:\n  <a string>  a string\n\nOverride Options:\n  -h --help  Display this message."
+        );
+    }
+
+    #[test]
+    fn auto_help_disabled() {
+        let parser = parser(&["--help"]).auto_help(false);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<String>())),
+            "ERROR: 2 problems found:\n  - unrecognized optional flag: '--help'\n  - missing required positional argument: <a string>\n\nUSAGE: executable <a string>"
+        );
+    }
+
+    #[test]
+    fn auto_help_declared() {
+        let parser = parser(&["--help"]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Embedded>())),
+            "ERROR: 2 problems found:\n  - unrecognized optional flag: '--help'\n  - missing required positional argument: <Embedded>\n\nUSAGE: executable <Embedded>"
+        );
+    }
+
+    #[test]
+    fn auto_help_declared_overridden() {
+        let parser = parser(&["--help"]).auto_help(true);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Embedded>())),
+            "an embedded value\n\nUSAGE: executable <Embedded>\n\nRequired Arguments:\n  <Embedded>  an embedded value\n\nOverride Options:\n  -h --help  Display this message."
+        );
+    }
+
+    #[test]
+    fn auto_help_declared_allows_help_as_value() {
+        let parser = parser(&["--", "--help"]);
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<Embedded>(),
+            Embedded("--help".into())
+        );
+    }
+
+    #[test]
+    fn empty_help_default() {
+        let parser = parser(&[]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<String>())),
+            "a string\n\nUSAGE: executable <a string>\n\nRequired Arguments:\n  <a string>  a string\n\nOverride Options:\n  -h --help  Display this message."
+        );
+    }
+
+    #[test]
+    fn empty_help_disabled() {
+        let parser = parser(&[]).empty_help(false);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<String>())),
+            "ERROR: missing required positional argument: <a string>\n\nUSAGE: executable <a string>\n\nFor more information, use --help."
+        );
+    }
+
+    #[test]
+    fn empty_help_declared() {
+        let parser = parser(&[]);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Embedded>())),
+            "ERROR: missing required positional argument: <Embedded>\n\nUSAGE: executable <Embedded>"
+        );
+    }
+
+    #[test]
+    fn empty_help_declared_overridden() {
+        let parser = parser(&[]).empty_help(true).auto_help(true);
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<Embedded>())),
+            "an embedded value\n\nUSAGE: executable <Embedded>\n\nRequired Arguments:\n  <Embedded>  an embedded value\n\nOverride Options:\n  -h --help  Display this message."
+        );
+    }
+
+    mod foreign {
+        use serde_derive::Deserialize;
+
+        #[derive(Debug, Deserialize, Eq, PartialEq)]
+        pub(super) struct Args {
+            pub(super) path: String,
+            pub(super) verbose: bool,
+        }
+    }
+
+    fn metadata() -> Metadata {
+        Metadata::new()
+            .description("Operates on a file.")
+            .field_help("path", "The file to operate on.")
+            .field_help("verbose", "Be more verbose.")
+            .version("1.2.3")
+    }
+
+    #[test]
+    fn with_metadata() {
+        let parser = parser(&["foo", "--verbose"]).with_metadata(metadata());
+
+        assert_ok_eq!(
+            parser.parse_borrowed::<foreign::Args>(),
+            foreign::Args {
+                path: "foo".into(),
+                verbose: true,
+            }
+        );
+    }
+
+    #[test]
+    fn with_metadata_help() {
+        let parser = parser(&["--help"]).with_metadata(metadata());
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<foreign::Args>())),
+            "Operates on a file.\n\nUSAGE: executable [options] <path>\n\nRequired Arguments:\n  <path>  The file to operate on.\n\nGlobal Options:\n  --verbose   Be more verbose.\n\nOverride Options:\n  -h --help     Display this message.\n     --version  Display version information."
+        );
+    }
+
+    #[test]
+    fn with_metadata_version() {
+        let parser = parser(&["--version"]).with_metadata(metadata());
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<foreign::Args>())),
+            "executable 1.2.3"
+        );
+    }
+
+    #[test]
+    fn with_metadata_unknown_path() {
+        let parser = parser(&["foo"]).with_metadata(Metadata::new().field_help("pth", "The file."));
+
+        assert_eq!(
+            format!("{}", assert_err!(parser.parse_borrowed::<foreign::Args>())),
+            "metadata refers to `pth`, which is not a field or variant"
+        );
+    }
+}
@@ -0,0 +1,32 @@
+//! The items most programs need, for convenient importing.
+//!
+//! ``` rust
+//! use serde_args::prelude::*;
+//! # mod hidden {
+//! # use serde::Deserialize;
+//! # }
+//! # use serde_derive::Deserialize;
+//!
+//! #[generate(doc_help)]
+//! #[derive(Deserialize)]
+//! /// An example program.
+//! struct Args {
+//!     /// A file to read.
+//!     input: String,
+//! }
+//!
+//! fn main() {
+//!     let args: Result<Args, Error> = from_env();
+//!     // Execute your program with `args`...
+//! }
+//! ```
+
+#[cfg(feature = "macros")]
+#[cfg_attr(docsrs, doc(cfg(feature = "macros")))]
+pub use crate::generate;
+pub use crate::{
+    from_env,
+    from_env_seed,
+    Error,
+    Parser,
+};
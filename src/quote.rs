@@ -0,0 +1,172 @@
+//! Safe display of user input within error messages.
+//!
+//! Arguments are given by the user and may contain anything, including newlines, terminal escape
+//! sequences, and bytes that are not valid UTF-8. Echoing them verbatim could corrupt the terminal
+//! an error message is written to, so they are displayed with anything unprintable escaped.
+
+use std::{
+    fmt,
+    fmt::{
+        Display,
+        Formatter,
+        Write,
+    },
+};
+
+/// The most characters of a value that are displayed before it is truncated.
+const MAX_LENGTH: usize = 100;
+
+/// User input displayed with control characters and invalid UTF-8 escaped.
+///
+/// Printable characters are displayed as they are. Newlines, carriage returns, tabs, and null
+/// characters are displayed as `\n`, `\r`, `\t`, and `\0`, while all other control characters are
+/// displayed by their code point, such as `\x1b` for an escape character. Bytes that are not valid
+/// UTF-8 are displayed as `\xNN`. Values longer than [`MAX_LENGTH`] characters are truncated,
+/// ending with `…`.
+#[derive(Debug)]
+pub(crate) struct Escaped<'a>(pub(crate) &'a [u8]);
+
+impl Display for Escaped<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        let mut length = 0;
+        for chunk in self.0.utf8_chunks() {
+            for character in chunk.valid().chars() {
+                if length == MAX_LENGTH {
+                    return formatter.write_char('…');
+                }
+                length += 1;
+                match character {
+                    '\n' => formatter.write_str("\\n")?,
+                    '\r' => formatter.write_str("\\r")?,
+                    '\t' => formatter.write_str("\\t")?,
+                    '\0' => formatter.write_str("\\0")?,
+                    character if character.is_ascii_control() => {
+                        write!(formatter, "\\x{:02x}", u32::from(character))?
+                    }
+                    character if character.is_control() => {
+                        write!(formatter, "\\u{{{:x}}}", u32::from(character))?
+                    }
+                    character => formatter.write_char(character)?,
+                }
+            }
+            for byte in chunk.invalid() {
+                if length == MAX_LENGTH {
+                    return formatter.write_char('…');
+                }
+                length += 1;
+                write!(formatter, "\\x{:02x}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// User input displayed within single quotes, escaped as by [`Escaped`].
+#[derive(Debug)]
+pub(crate) struct Quoted<'a>(pub(crate) &'a [u8]);
+
+impl Display for Quoted<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "'{}'", Escaped(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        Escaped,
+        Quoted,
+        MAX_LENGTH,
+    };
+
+    #[test]
+    fn escaped_printable() {
+        assert_eq!(format!("{}", Escaped(b"foo bar")), "foo bar");
+    }
+
+    #[test]
+    fn escaped_empty() {
+        assert_eq!(format!("{}", Escaped(b"")), "");
+    }
+
+    #[test]
+    fn escaped_unicode() {
+        assert_eq!(format!("{}", Escaped("é🦀".as_bytes())), "é🦀");
+    }
+
+    #[test]
+    fn escaped_whitespace_controls() {
+        assert_eq!(format!("{}", Escaped(b"a\nb\rc\td\0")), "a\\nb\\rc\\td\\0");
+    }
+
+    #[test]
+    fn escaped_escape_sequence() {
+        assert_eq!(format!("{}", Escaped(b"\x1b[31mred")), "\\x1b[31mred");
+    }
+
+    #[test]
+    fn escaped_delete() {
+        assert_eq!(format!("{}", Escaped(b"\x7f")), "\\x7f");
+    }
+
+    #[test]
+    fn escaped_c1_control() {
+        assert_eq!(format!("{}", Escaped("\u{9b}".as_bytes())), "\\u{9b}");
+    }
+
+    #[test]
+    fn escaped_invalid_utf8() {
+        assert_eq!(format!("{}", Escaped(b"f\xffo\xc3")), "f\\xffo\\xc3");
+    }
+
+    #[test]
+    fn escaped_backslash() {
+        assert_eq!(format!("{}", Escaped(b"C:\\foo")), "C:\\foo");
+    }
+
+    #[test]
+    fn escaped_at_max_length() {
+        let value = "a".repeat(MAX_LENGTH);
+
+        assert_eq!(format!("{}", Escaped(value.as_bytes())), value);
+    }
+
+    #[test]
+    fn escaped_truncated() {
+        assert_eq!(
+            format!("{}", Escaped("a".repeat(MAX_LENGTH + 1).as_bytes())),
+            format!("{}…", "a".repeat(MAX_LENGTH))
+        );
+    }
+
+    #[test]
+    fn escaped_truncated_invalid_utf8() {
+        let mut value = "a".repeat(MAX_LENGTH);
+        value.push('b');
+        let mut bytes = value.into_bytes();
+        bytes.push(0xff);
+
+        assert_eq!(
+            format!("{}", Escaped(&bytes)),
+            format!("{}…", "a".repeat(MAX_LENGTH))
+        );
+    }
+
+    #[test]
+    fn quoted() {
+        assert_eq!(format!("{}", Quoted(b"foo")), "'foo'");
+    }
+
+    #[test]
+    fn quoted_empty() {
+        assert_eq!(format!("{}", Quoted(b"")), "''");
+    }
+
+    #[test]
+    fn quoted_escaped() {
+        assert_eq!(
+            format!("{}", Quoted(b"\x1b]0;\x07\xfe")),
+            "'\\x1b]0;\\x07\\xfe'"
+        );
+    }
+}
@@ -0,0 +1,917 @@
+use crate::{
+    trace::{
+        trace,
+        Field,
+        PrimitiveKind,
+        Shape,
+        Variant,
+    },
+    Error,
+};
+use serde::de::{
+    Deserialize,
+    DeserializeSeed,
+};
+use std::{
+    fmt::Write,
+    iter,
+    marker::PhantomData,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// The formats a specification of a command line interface can be generated in.
+///
+/// Passed to [`generate_spec()`] and [`generate_spec_seed()`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SpecFormat {
+    /// A JSON tree of commands, in the style consumed by completion engines such as Fig and
+    /// Carapace.
+    ///
+    /// The tree begins with the root command, which has no `name` or `aliases`, since it is
+    /// invoked using the name of the executable. Every command is an object with the following
+    /// keys:
+    ///
+    /// - `name`: The name the command is given by.
+    /// - `aliases`: The other names the command may be given by.
+    /// - `description`: The command's description, which is empty if it has none.
+    /// - `arguments`: The command's positional arguments, in the order they are given.
+    /// - `options`: The options introduced by the command. These are also accepted by each of its
+    ///   subcommands.
+    /// - `commands`: The subcommands, one of which is given after the command's arguments.
+    ///
+    /// Each argument is an object with the following keys:
+    ///
+    /// - `name`: The name displayed for the argument.
+    /// - `description`: The argument's description, which is empty if it has none.
+    /// - `value`: The value taken by the argument.
+    /// - `optional`: Whether the argument may be omitted.
+    /// - `multiple`: Whether the argument takes every remaining value.
+    ///
+    /// Each option is an object with the following keys:
+    ///
+    /// - `names`: Every name the option may be given by, including its leading hyphens.
+    /// - `description`: The option's description, which is empty if it has none.
+    /// - `value`: The value taken by the option, or `null` if it takes no value.
+    /// - `required`: Whether the option must be given.
+    /// - `multiple`: Whether the option takes multiple values.
+    ///
+    /// Each value is an object with the following keys:
+    ///
+    /// - `name`: The name displayed for the value.
+    /// - `type`: One of `"string"`, `"character"`, `"bytes"`, `"integer"`, `"number"`,
+    ///   `"boolean"`, `"enum"`, `"struct"`, or `"any"`.
+    /// - `values`: The names of the variants accepted, for values of type `"enum"`. This is empty
+    ///   for all other types.
+    Json,
+    /// A versioned JSON description of the type the command line interface is derived from, in
+    /// the style consumed by documentation generators.
+    ///
+    /// The output is an object with the following keys:
+    ///
+    /// - `schema_version`: The version of this format, which is currently `1`. It is incremented
+    ///   whenever the structure of the output changes.
+    /// - `shape`: The shape of the type.
+    ///
+    /// Every shape is an object with a `kind` key, along with the keys listed for its kind:
+    ///
+    /// - `"empty"`: A value taking no arguments, with `description` and `version`.
+    /// - `"primitive"`: A single value, with `name`, `description`, `version`, and `type`, which
+    ///   is one of `"string"`, `"character"`, `"bytes"`, `"integer"`, `"number"`, or `"any"`.
+    /// - `"boolean"`: A boolean value, with `name`, `description`, and `version`.
+    /// - `"optional"`: A value that may be omitted, with the `shape` of the value.
+    /// - `"sequence"`: Any number of values, with the `shape` of each value.
+    /// - `"struct"`: A struct, with `name`, `description`, and `version`, along with its fields
+    ///   classified as `required` positional arguments, `optional` options, and `booleans` flags.
+    /// - `"enum"`: An enum, with `name`, `description`, `version`, and `variants`.
+    /// - `"variant"`: An enum with a variant already selected, with the variant's `name`,
+    ///   `description`, `version`, and `shape`, along with the `enum_name` and the enum's
+    ///   `variants`.
+    ///
+    /// A `description` is empty if there is none, and a `version` is `null` unless version
+    /// information is advertised for the shape.
+    ///
+    /// Each field is an object with the following keys:
+    ///
+    /// - `name`: The name the field is given by.
+    /// - `aliases`: The other names the field may be given by.
+    /// - `description`: The field's description, which is empty if it has none.
+    /// - `shape`: The shape of the field.
+    ///
+    /// Each variant is an object with the following keys:
+    ///
+    /// - `name`: The name the variant is given by.
+    /// - `aliases`: The other names the variant may be given by.
+    /// - `description`: The variant's description, which is empty if it has none.
+    /// - `version`: The variant's version, or `null` if it has none.
+    /// - `shape`: The shape of the variant's contents.
+    ///
+    /// Keys are always written in the order listed here, so the output for a type is the same
+    /// across runs.
+    Schema,
+}
+
+/// The version of the [`SpecFormat::Schema`] format.
+const SCHEMA_VERSION: u64 = 1;
+
+/// Returns a specification of the command line interface for `D` in the given `format`.
+///
+/// The specification describes every command, argument, and option that can be given, along with
+/// their aliases, descriptions, and value types. This is intended for completion engines that
+/// consume a static description of a command line interface, rather than invoking the program
+/// itself as with [`complete()`](crate::complete()), and for documentation generators. See
+/// [`SpecFormat`] for the schema of each format.
+///
+/// An error is returned if `D` cannot be traced.
+///
+/// # Example
+///
+/// ``` rust
+/// # mod hidden {
+/// use serde::Deserialize;
+/// # }
+/// # use serde_derive::Deserialize;
+/// use serde_args::SpecFormat;
+///
+/// #[derive(Deserialize)]
+/// #[serde(rename_all = "kebab-case")]
+/// enum Command {
+///     Add { path: String },
+///     Push { force: bool },
+/// }
+///
+/// let spec = serde_args::generate_spec::<Command>(SpecFormat::Json).unwrap();
+///
+/// assert!(spec.contains("\"name\": \"push\""));
+/// assert!(spec.contains("\"--force\""));
+/// ```
+pub fn generate_spec<'de, D>(format: SpecFormat) -> Result<String, Error>
+where
+    D: Deserialize<'de>,
+{
+    generate_spec_seed(PhantomData::<D>, format)
+}
+
+/// Returns a specification of the command line interface for `seed` in the given `format`.
+///
+/// This function behaves the same as [`generate_spec()`], except that the type is traced using the
+/// provided seed.
+pub fn generate_spec_seed<'de, D>(seed: D, format: SpecFormat) -> Result<String, Error>
+where
+    D: Copy + DeserializeSeed<'de>,
+{
+    let shape = trace(seed)?;
+    let mut output = String::new();
+    match format {
+        SpecFormat::Json => {
+            let mut root = Command::new(Vec::new(), shape.description());
+            root.enter(&[Positional {
+                shape: &shape,
+                field: None,
+            }]);
+            root.json().write(&mut output, 0);
+        }
+        SpecFormat::Schema => {
+            Json::Object(vec![
+                ("schema_version", Json::Number(SCHEMA_VERSION)),
+                ("shape", shape_json(&shape)),
+            ])
+            .write(&mut output, 0);
+        }
+    }
+    Ok(output)
+}
+
+/// A positional argument waiting to be added to a command, along with the field it belongs to.
+#[derive(Clone, Copy)]
+struct Positional<'a> {
+    shape: &'a Shape,
+    field: Option<&'a Field>,
+}
+
+/// A command, either the root command or one selected by a variant.
+struct Command<'a> {
+    /// The name followed by the aliases of the command, which is empty for the root command.
+    names: Vec<&'static str>,
+    description: &'a str,
+    arguments: Vec<Argument<'a>>,
+    options: Vec<Opt<'a>>,
+    commands: Vec<Command<'a>>,
+}
+
+impl<'a> Command<'a> {
+    fn new(names: Vec<&'static str>, description: &'a str) -> Self {
+        Self {
+            names,
+            description,
+            arguments: Vec::new(),
+            options: Vec::new(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Adds the arguments and options of the `pending` positionals, in order.
+    ///
+    /// Positionals selecting a variant become subcommands, each of which receives the positionals
+    /// remaining after it.
+    fn enter(&mut self, pending: &[Positional<'a>]) {
+        let Some((positional, rest)) = pending.split_first() else {
+            return;
+        };
+        match positional.shape {
+            Shape::Empty { .. } => self.enter(rest),
+            Shape::Struct {
+                required,
+                optional,
+                booleans,
+                ..
+            } => {
+                self.options.extend(
+                    optional
+                        .iter()
+                        .chain(booleans.iter())
+                        .flat_map(Opt::from_field),
+                );
+                let pending = required
+                    .iter()
+                    .map(|field| Positional {
+                        shape: &field.shape,
+                        field: Some(field),
+                    })
+                    .chain(rest.iter().copied())
+                    .collect::<Vec<_>>();
+                self.enter(&pending);
+            }
+            Shape::Enum { variants, .. } | Shape::Variant { variants, .. } => {
+                for variant in variants {
+                    let mut command = Command::new(
+                        iter::once(variant.name)
+                            .chain(variant.aliases.iter().copied())
+                            .collect(),
+                        &variant.description,
+                    );
+                    let pending = iter::once(Positional {
+                        shape: &variant.shape,
+                        field: None,
+                    })
+                    .chain(rest.iter().copied())
+                    .collect::<Vec<_>>();
+                    command.enter(&pending);
+                    self.commands.push(command);
+                }
+            }
+            Shape::Primitive { .. }
+            | Shape::Boolean { .. }
+            | Shape::Optional(_)
+            | Shape::Sequence(_) => {
+                self.arguments.push(Argument::new(*positional));
+                self.enter(rest);
+            }
+        }
+    }
+
+    fn json(&self) -> Json {
+        let mut entries = Vec::new();
+        if let Some((name, aliases)) = self.names.split_first() {
+            entries.push(("name", Json::String((*name).to_owned())));
+            entries.push((
+                "aliases",
+                Json::Array(
+                    aliases
+                        .iter()
+                        .map(|alias| Json::String((*alias).to_owned()))
+                        .collect(),
+                ),
+            ));
+        }
+        entries.push(("description", Json::String(self.description.to_owned())));
+        entries.push((
+            "arguments",
+            Json::Array(self.arguments.iter().map(Argument::json).collect()),
+        ));
+        entries.push((
+            "options",
+            Json::Array(self.options.iter().map(Opt::json).collect()),
+        ));
+        entries.push((
+            "commands",
+            Json::Array(self.commands.iter().map(Command::json).collect()),
+        ));
+        Json::Object(entries)
+    }
+}
+
+/// A positional argument of a command.
+struct Argument<'a> {
+    name: &'a str,
+    description: &'a str,
+    value: Option<Value<'a>>,
+    optional: bool,
+    multiple: bool,
+}
+
+impl<'a> Argument<'a> {
+    fn new(positional: Positional<'a>) -> Self {
+        let mut optional = false;
+        let mut multiple = false;
+        let mut shape = positional.shape;
+        loop {
+            match shape {
+                Shape::Optional(inner) => {
+                    optional = true;
+                    shape = inner;
+                }
+                Shape::Sequence(inner) => {
+                    multiple = true;
+                    shape = inner;
+                }
+                _ => break,
+            }
+        }
+        let value = Value::new(shape);
+        match positional.field {
+            Some(field) => Self {
+                name: field.placeholder(),
+                description: &field.description,
+                value,
+                optional,
+                multiple,
+            },
+            None => Self {
+                name: value.as_ref().map_or("", |value| value.name),
+                description: shape.description(),
+                value,
+                optional,
+                multiple,
+            },
+        }
+    }
+
+    fn json(&self) -> Json {
+        Json::Object(vec![
+            ("name", Json::String(self.name.to_owned())),
+            ("description", Json::String(self.description.to_owned())),
+            ("value", self.value.as_ref().map_or(Json::Null, Value::json)),
+            ("optional", Json::Bool(self.optional)),
+            ("multiple", Json::Bool(self.multiple)),
+        ])
+    }
+}
+
+/// An option of a command.
+struct Opt<'a> {
+    /// The names of the option, including their leading hyphens.
+    names: Vec<String>,
+    description: &'a str,
+    value: Option<Value<'a>>,
+    required: bool,
+    multiple: bool,
+}
+
+impl<'a> Opt<'a> {
+    /// The options given by `field`.
+    ///
+    /// An option given as flags is given as a separate option for each of its variants.
+    fn from_field(field: &'a Field) -> Vec<Self> {
+        match &field.shape {
+            Shape::Enum { variants, .. } if field.flags => variants
+                .iter()
+                .map(|variant| Self {
+                    names: option_names(
+                        iter::once(variant.name).chain(variant.aliases.iter().copied()),
+                    ),
+                    description: &variant.description,
+                    value: Value::new(&variant.shape),
+                    required: false,
+                    multiple: false,
+                })
+                .collect(),
+            shape => vec![Self {
+                names: option_names(iter::once(field.name).chain(field.aliases.iter().copied())),
+                description: &field.description,
+                value: Value::new(shape),
+                required: field.required,
+                multiple: matches!(shape, Shape::Sequence(_)),
+            }],
+        }
+    }
+
+    fn json(&self) -> Json {
+        Json::Object(vec![
+            (
+                "names",
+                Json::Array(
+                    self.names
+                        .iter()
+                        .map(|name| Json::String(name.clone()))
+                        .collect(),
+                ),
+            ),
+            ("description", Json::String(self.description.to_owned())),
+            ("value", self.value.as_ref().map_or(Json::Null, Value::json)),
+            ("required", Json::Bool(self.required)),
+            ("multiple", Json::Bool(self.multiple)),
+        ])
+    }
+}
+
+/// Prefixes each option name with hyphens, the same way they are given on the command line.
+fn option_names(names: impl Iterator<Item = &'static str>) -> Vec<String> {
+    names
+        .map(|name| {
+            if name.graphemes(true).count() == 1 {
+                format!("-{}", name)
+            } else {
+                format!("--{}", name)
+            }
+        })
+        .collect()
+}
+
+/// A value taken by an argument or option.
+struct Value<'a> {
+    name: &'a str,
+    kind: &'static str,
+    /// The names of the variants accepted, if the value is an enum.
+    values: Vec<&'static str>,
+}
+
+impl<'a> Value<'a> {
+    /// Returns the value described by `shape`, or `None` if `shape` takes no value.
+    fn new(shape: &'a Shape) -> Option<Self> {
+        match shape {
+            Shape::Empty { .. } => None,
+            Shape::Primitive { name, kind, .. } => Some(Self {
+                name,
+                kind: primitive_type(*kind),
+                values: Vec::new(),
+            }),
+            Shape::Boolean { name, .. } => Some(Self {
+                name,
+                kind: "boolean",
+                values: Vec::new(),
+            }),
+            Shape::Optional(shape) | Shape::Sequence(shape) => Self::new(shape),
+            Shape::Struct { name, .. } => Some(Self {
+                name,
+                kind: "struct",
+                values: Vec::new(),
+            }),
+            Shape::Enum { name, variants, .. } => Some(Self {
+                name,
+                kind: "enum",
+                values: variant_names(variants),
+            }),
+            Shape::Variant {
+                enum_name,
+                variants,
+                ..
+            } => Some(Self {
+                name: enum_name,
+                kind: "enum",
+                values: variant_names(variants),
+            }),
+        }
+    }
+
+    fn json(&self) -> Json {
+        Json::Object(vec![
+            ("name", Json::String(self.name.to_owned())),
+            ("type", Json::String(self.kind.to_owned())),
+            (
+                "values",
+                Json::Array(
+                    self.values
+                        .iter()
+                        .map(|value| Json::String((*value).to_owned()))
+                        .collect(),
+                ),
+            ),
+        ])
+    }
+}
+
+/// The type of value taken by a primitive of the given `kind`.
+fn primitive_type(kind: PrimitiveKind) -> &'static str {
+    match kind {
+        PrimitiveKind::I8
+        | PrimitiveKind::I16
+        | PrimitiveKind::I32
+        | PrimitiveKind::I64
+        | PrimitiveKind::I128
+        | PrimitiveKind::U8
+        | PrimitiveKind::U16
+        | PrimitiveKind::U32
+        | PrimitiveKind::U64
+        | PrimitiveKind::U128 => "integer",
+        PrimitiveKind::F32 | PrimitiveKind::F64 => "number",
+        PrimitiveKind::Char => "character",
+        PrimitiveKind::Str => "string",
+        PrimitiveKind::Bytes => "bytes",
+        PrimitiveKind::Any(_) => "any",
+    }
+}
+
+fn variant_names(variants: &[Variant]) -> Vec<&'static str> {
+    variants.iter().map(|variant| variant.name).collect()
+}
+
+/// Describes `shape` as documented for [`SpecFormat::Schema`].
+fn shape_json(shape: &Shape) -> Json {
+    match shape {
+        Shape::Empty {
+            description,
+            version,
+        } => Json::Object(vec![
+            ("kind", Json::String("empty".to_owned())),
+            ("description", Json::String(description.clone())),
+            ("version", optional_string(version)),
+        ]),
+        Shape::Primitive {
+            name,
+            description,
+            version,
+            kind,
+        } => Json::Object(vec![
+            ("kind", Json::String("primitive".to_owned())),
+            ("name", Json::String(name.clone())),
+            ("description", Json::String(description.clone())),
+            ("version", optional_string(version)),
+            ("type", Json::String(primitive_type(*kind).to_owned())),
+        ]),
+        Shape::Boolean {
+            name,
+            description,
+            version,
+        } => Json::Object(vec![
+            ("kind", Json::String("boolean".to_owned())),
+            ("name", Json::String(name.clone())),
+            ("description", Json::String(description.clone())),
+            ("version", optional_string(version)),
+        ]),
+        Shape::Optional(shape) => Json::Object(vec![
+            ("kind", Json::String("optional".to_owned())),
+            ("shape", shape_json(shape)),
+        ]),
+        Shape::Sequence(shape) => Json::Object(vec![
+            ("kind", Json::String("sequence".to_owned())),
+            ("shape", shape_json(shape)),
+        ]),
+        Shape::Struct {
+            name,
+            description,
+            version,
+            required,
+            optional,
+            booleans,
+            ..
+        } => Json::Object(vec![
+            ("kind", Json::String("struct".to_owned())),
+            ("name", Json::String((*name).to_owned())),
+            ("description", Json::String(description.clone())),
+            ("version", optional_string(version)),
+            (
+                "required",
+                Json::Array(required.iter().map(field_json).collect()),
+            ),
+            (
+                "optional",
+                Json::Array(optional.iter().map(field_json).collect()),
+            ),
+            (
+                "booleans",
+                Json::Array(booleans.iter().map(field_json).collect()),
+            ),
+        ]),
+        Shape::Enum {
+            name,
+            description,
+            version,
+            variants,
+            ..
+        } => Json::Object(vec![
+            ("kind", Json::String("enum".to_owned())),
+            ("name", Json::String((*name).to_owned())),
+            ("description", Json::String(description.clone())),
+            ("version", optional_string(version)),
+            (
+                "variants",
+                Json::Array(variants.iter().map(variant_json).collect()),
+            ),
+        ]),
+        Shape::Variant {
+            name,
+            description,
+            version,
+            shape,
+            enum_name,
+            variants,
+        } => Json::Object(vec![
+            ("kind", Json::String("variant".to_owned())),
+            ("name", Json::String((*name).to_owned())),
+            ("description", Json::String(description.clone())),
+            ("version", optional_string(version)),
+            ("shape", shape_json(shape)),
+            ("enum_name", Json::String((*enum_name).to_owned())),
+            (
+                "variants",
+                Json::Array(variants.iter().map(variant_json).collect()),
+            ),
+        ]),
+    }
+}
+
+fn field_json(field: &Field) -> Json {
+    Json::Object(vec![
+        ("name", Json::String(field.name.to_owned())),
+        ("aliases", names_json(&field.aliases)),
+        ("description", Json::String(field.description.clone())),
+        ("shape", shape_json(&field.shape)),
+    ])
+}
+
+fn variant_json(variant: &Variant) -> Json {
+    Json::Object(vec![
+        ("name", Json::String(variant.name.to_owned())),
+        ("aliases", names_json(&variant.aliases)),
+        ("description", Json::String(variant.description.clone())),
+        ("version", optional_string(&variant.version)),
+        ("shape", shape_json(&variant.shape)),
+    ])
+}
+
+fn names_json(names: &[&'static str]) -> Json {
+    Json::Array(
+        names
+            .iter()
+            .map(|name| Json::String((*name).to_owned()))
+            .collect(),
+    )
+}
+
+fn optional_string(value: &Option<String>) -> Json {
+    value
+        .as_ref()
+        .map_or(Json::Null, |value| Json::String(value.clone()))
+}
+
+/// A JSON value, written with two spaces of indentation per level.
+enum Json {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    fn write(&self, output: &mut String, indent: usize) {
+        match self {
+            Self::Null => output.push_str("null"),
+            Self::Bool(value) => output.push_str(if *value { "true" } else { "false" }),
+            Self::Number(value) => {
+                // Writing to a `String` cannot fail.
+                let _ = write!(output, "{}", value);
+            }
+            Self::String(value) => write_string(output, value),
+            Self::Array(values) if values.is_empty() => output.push_str("[]"),
+            Self::Array(values) => {
+                output.push('[');
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        output.push(',');
+                    }
+                    write_indent(output, indent + 1);
+                    value.write(output, indent + 1);
+                }
+                write_indent(output, indent);
+                output.push(']');
+            }
+            Self::Object(entries) => {
+                output.push('{');
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        output.push(',');
+                    }
+                    write_indent(output, indent + 1);
+                    write_string(output, key);
+                    output.push_str(": ");
+                    value.write(output, indent + 1);
+                }
+                write_indent(output, indent);
+                output.push('}');
+            }
+        }
+    }
+}
+
+fn write_indent(output: &mut String, indent: usize) {
+    output.push('\n');
+    for _ in 0..indent {
+        output.push_str("  ");
+    }
+}
+
+fn write_string(output: &mut String, value: &str) {
+    output.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if c.is_control() => {
+                // Writing to a `String` cannot fail.
+                let _ = write!(output, "\\u{:04x}", u32::from(c));
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        generate_spec,
+        Json,
+        SpecFormat,
+    };
+    use claims::assert_ok;
+    use serde_derive::Deserialize;
+    use std::path::PathBuf;
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    #[allow(dead_code)]
+    enum Command {
+        Add {
+            path: PathBuf,
+        },
+        Commit {
+            #[serde(alias = "m")]
+            message: Option<String>,
+        },
+        Push {
+            #[serde(alias = "f")]
+            force: bool,
+        },
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    #[allow(dead_code)]
+    enum Format {
+        Json,
+        Yaml,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    #[allow(dead_code)]
+    struct Args {
+        inputs: Vec<String>,
+        jobs: Option<u8>,
+        format: Option<Format>,
+    }
+
+    #[test]
+    fn enum_shape() {
+        let spec = assert_ok!(generate_spec::<Command>(SpecFormat::Json));
+
+        for name in [
+            "\"name\": \"add\"",
+            "\"name\": \"commit\"",
+            "\"name\": \"push\"",
+            "\"name\": \"path\"",
+            "\"-m\"",
+            "\"--message\"",
+            "\"-f\"",
+            "\"--force\"",
+        ] {
+            assert!(spec.contains(name), "{} not found in {}", name, spec);
+        }
+    }
+
+    #[test]
+    fn struct_shape() {
+        assert_eq!(
+            assert_ok!(generate_spec::<Args>(SpecFormat::Json)),
+            r#"{
+  "description": "struct Args",
+  "arguments": [
+    {
+      "name": "inputs",
+      "description": "",
+      "value": {
+        "name": "a string",
+        "type": "string",
+        "values": []
+      },
+      "optional": false,
+      "multiple": true
+    }
+  ],
+  "options": [
+    {
+      "names": [
+        "--jobs"
+      ],
+      "description": "",
+      "value": {
+        "name": "u8",
+        "type": "integer",
+        "values": []
+      },
+      "required": false,
+      "multiple": false
+    },
+    {
+      "names": [
+        "--format"
+      ],
+      "description": "",
+      "value": {
+        "name": "Format",
+        "type": "enum",
+        "values": [
+          "json",
+          "yaml"
+        ]
+      },
+      "required": false,
+      "multiple": false
+    }
+  ],
+  "commands": []
+}"#
+        );
+    }
+
+    #[test]
+    fn primitive_shape() {
+        assert_eq!(
+            assert_ok!(generate_spec::<String>(SpecFormat::Json)),
+            r#"{
+  "description": "a string",
+  "arguments": [
+    {
+      "name": "a string",
+      "description": "a string",
+      "value": {
+        "name": "a string",
+        "type": "string",
+        "values": []
+      },
+      "optional": false,
+      "multiple": false
+    }
+  ],
+  "options": [],
+  "commands": []
+}"#
+        );
+    }
+
+    #[test]
+    fn schema_primitive_shape() {
+        assert_eq!(
+            assert_ok!(generate_spec::<String>(SpecFormat::Schema)),
+            r#"{
+  "schema_version": 1,
+  "shape": {
+    "kind": "primitive",
+    "name": "a string",
+    "description": "a string",
+    "version": null,
+    "type": "string"
+  }
+}"#
+        );
+    }
+
+    #[test]
+    fn schema_enum_shape() {
+        let spec = assert_ok!(generate_spec::<Command>(SpecFormat::Schema));
+
+        for name in [
+            "\"kind\": \"enum\"",
+            "\"name\": \"add\"",
+            "\"name\": \"commit\"",
+            "\"name\": \"push\"",
+            "\"name\": \"path\"",
+            "\"booleans\": [",
+        ] {
+            assert!(spec.contains(name), "{} not found in {}", name, spec);
+        }
+    }
+
+    #[test]
+    fn json_string_escaped() {
+        let mut output = String::new();
+        Json::String("\"quoted\" \\ line\nbreak \u{7}".to_owned()).write(&mut output, 0);
+
+        assert_eq!(output, r#""\"quoted\" \\ line\nbreak \u0007""#);
+    }
+}
@@ -21,9 +21,11 @@
 //!
 //! ## Booleans
 //!
-//! Booleans, requested with [`Deserializer::deserialize_bool()`], can parse either `true` or
-//! `false`. Note that these values must be lowercase; `TRUE` or `FALSE` are not accepted, nor are
-//! `t`, `f`, `0`, or `1`.
+//! Booleans, requested with [`Deserializer::deserialize_bool()`], can parse `true`, `yes`, `on`,
+//! or `1` as true, and `false`, `no`, `off`, or `0` as false. These values are case-insensitive, so
+//! `TRUE` and `No` are also accepted. Abbreviations such as `t` or `f` are not accepted.
+//!
+//! An empty value is rejected as an invalid value, naming the field it was given for.
 //!
 //! ## Numeric Values
 //!
@@ -32,6 +34,21 @@
 //! [`Deserializer::deserialize_u32()`] will result in the next value being parsed with
 //! `u32::from_str()`.
 //!
+//! Values outside the range of the requested type, including negative values for unsigned types,
+//! are rejected along with the accepted range. This includes floats such as `1e999`, which are too
+//! large to be represented by any finite value of the type, rather than being rounded to infinity.
+//!
+//! Negative numbers, such as `-1` or `-1e5`, are parsed as values rather than as options when a
+//! numeric value is expected, unless they match the name of an option in scope. Values that cannot
+//! be parsed as the requested type are rejected as invalid values, naming the field they were given
+//! for.
+//!
+//! Floats also accept the special values `inf`, `infinity`, and `nan`, in any case and optionally
+//! preceded by a sign, such as `-INF` or `NaN`. Negative zero, given as `-0`, is preserved.
+//!
+//! An empty value is rejected as an invalid value, naming the field it was given for, rather than
+//! being treated as zero.
+//!
 //! ## Characters, Strings, and Bytes
 //!
 //! Characters, strings, and bytes will be parsed by interpreting the next value as the given type.
@@ -39,6 +56,20 @@
 //! bytes, although there may be limitations regarding what bytes can actually be passed on the
 //! command line depending on the operating system being used.
 //!
+//! A character is a single Unicode scalar value, matching Rust's [`char`]. Multi-byte characters
+//! such as `é` or `🦀` are accepted, but a value that appears to be one character while being
+//! composed of multiple scalar values, such as the flag emoji `🇺🇸`, is rejected. Empty values and
+//! values with more than one character are never truncated; they are rejected as well.
+//!
+//! An empty value, such as one given as `""` in a shell, is a legitimate string or byte value. It
+//! is never skipped, and fills the position it was given in like any other value.
+//!
+//! A lone `-` is likewise given verbatim wherever a value is expected, whether as a positional
+//! argument, as the value of an option, or as an element of a sequence. This follows the
+//! convention of using `-` to name standard input or output, so a `PathBuf` given as `-` is the
+//! path `-`. It is only interpreted as an empty optional value where an optional value could
+//! begin, as described below.
+//!
 //! # Optionals
 //!
 //! Optional values, requested with [`Deserializer::deserialize_option()`], will optionally parse
@@ -48,6 +79,10 @@
 //!
 //! For example, parsing an `Option<String>` would interpret the argument `--foo` as `Some("foo")`.
 //!
+//! A lone `-` is an optional value that is empty, so it is interpreted as `Some("")`. When parsing
+//! an optional struct, the empty value is given to the struct's first required field that takes a
+//! value. If none of its required fields take a value, `-` only marks the struct as present.
+//!
 //! # Structs
 //!
 //! Structs act as the main compound data structure to be used when multiple positional data types
@@ -64,6 +99,12 @@
 //! of the struct, immediately before the first field of the struct, or immediately after the last
 //! field of the struct.
 //!
+//! When a required field contains a struct, that struct's boolean and optional fields can also be
+//! provided after its own required fields, for as long as the enclosing struct is being parsed. An
+//! option of a nested struct takes precedence over an option of the same name in an enclosing
+//! struct while the nested struct's required fields are being parsed. Once they have been parsed,
+//! the enclosing struct's option takes precedence instead.
+//!
 //! ### Boolean fields
 //! Boolean fields are set to true using the name of the field as an optional value. If the name is
 //! never provided, a default value of `false` is used.
@@ -78,14 +119,34 @@
 //! For example, an optional field named `foo` containing a `String` value could be set using
 //! `--foo bar`. This would set the field's value to `Some("bar")`.
 //!
+//! The value may also be joined to the name with `=`, as in `--foo=bar`. A joined value is always
+//! used as the value, even if it begins with a hyphen, so `--foo=` sets the field to `Some("")`.
+//! Without a joined value, the value must be given as the next argument; `--foo` at the end of the
+//! arguments is an error. Fields that take no value, such as booleans and `Option<()>`, cannot be
+//! given a joined value.
+//!
+//! An optional field containing a struct takes each of the struct's required fields as a value, in
+//! order. For example, an optional field named `rect` containing a struct with `width` and `height`
+//! fields is set using `--rect 10 20`, and is displayed in help messages as
+//! `--rect <width> <height>`. The values may be preceded by `--` to allow them to begin with a
+//! hyphen.
+//!
 //! ### Required fields
 //!
 //! Required fields (sometimes called "positional fields") are all fields that are not booleans or
 //! optionals. They must be provided in the order they are defined and cannot be omitted.
 //!
+//! An optional field containing a primitive value can opt into being parsed as the last positional
+//! argument instead of as an option. It is `None` if no argument remains for it. See
+//! [Optional Positional Fields](#optional-positional-fields).
+//!
+//! An optional field that is not a boolean can also be required to be given as an option, in which
+//! case parsing fails without it. See [Required Options](#required-options).
+//!
 //! ## Unit Structs
 //!
-//! See [Units](#units).
+//! See [Units](#units). A unit struct used as a required field consumes no arguments, and is not
+//! displayed in usage messages.
 //!
 //! ## Newtype Structs
 //!
@@ -105,6 +166,16 @@
 //! Enums (often called "commands" in terms of command line interfaces) are specified by the
 //! identifier parsed from the next available argument (parsed as a string, not as an integer or
 //! any other value). The matched variant type will determine how the next arguments are parsed.
+//! An empty identifier never matches a variant, and is reported as an unrecognized command.
+//!
+//! An enum with no variants can never be given. Parsing fails with an error as soon as a command
+//! is required from one, although help can still be requested.
+//!
+//! A variant renamed to a number, such as with `#[serde(rename = "2")]`, is therefore matched by
+//! that number like any other name. Variants can also be selected by their index, counting from
+//! `0` in declaration order, using
+//! [`Parser::match_command_indices()`](crate::Parser::match_command_indices()). Names always take
+//! priority over indices.
 //!
 //! ## Unit Variants
 //!
@@ -124,18 +195,62 @@
 //!
 //! Tuple variants are not currently supported.
 //!
+//! ## Global Options
+//!
+//! Options that apply to every command, such as `--verbose` or `--config`, are declared once on a
+//! struct containing the enum as a required field, rather than on each variant. Since the struct
+//! is still being parsed while its command is, its boolean and optional fields are recognized
+//! before the command, between the command's arguments, and after them, including within nested
+//! commands. They are displayed as global options in the help messages of every command.
+//!
+//! If a variant declares an option of the same name, the variant's option takes precedence once
+//! the variant has been parsed, so the global option can then only be given before the command.
+//!
 //! # Tuples
 //!
-//! Tuples are not currently supported.
+//! Tuples and fixed-size arrays are parsed as one required positional argument per element, in
+//! order. They are displayed in help messages as `<arg1> <arg2>`, and so on. Giving fewer arguments
+//! than there are elements is an error listing the missing ones, as is giving more.
+//!
+//! For example, parsing a `(String, u32)` would interpret the arguments `foo 42` as
+//! `("foo", 42)`.
+//!
+//! Each element must be a primitive value. While the shape of the tuple is traced, every element
+//! but the last must accept a placeholder value, such as `1` for integers or one of a few common
+//! strings for values parsed from strings. Elements with stricter validation should be placed
+//! last.
 //!
 //! # Sequences
 //!
-//! Sequences are not currently supported.
+//! Sequences of primitive values, such as `Vec<T>`, consume all remaining positional values. Each
+//! value is parsed as a single element, and options may be interleaved between them. If no values
+//! remain, the sequence is deserialized as empty.
+//!
+//! Sets, such as `HashSet<T>` and `BTreeSet<T>`, are parsed the same way. Duplicate values are
+//! dropped without error, as they are by the set's own `Deserialize` implementation.
+//!
+//! A sequence should be the last positional value, since no values will be left for anything
+//! following it. Sequences whose elements are not primitive values are not supported and will
+//! result in a development error.
+//!
+//! Sequences may also be contained in [Newtype Variants](#newtype-variants), allowing a command to
+//! accept any number of values.
+//!
+//! A sequence that is the value of an option instead takes a single value per occurrence of the
+//! option. The option may be repeated, and the values of every occurrence are collected in the
+//! order they were given.
 //!
 //! # Maps
 //!
 //! Maps are not currently supported.
 //!
+//! # Smart Pointers
+//!
+//! Smart pointers such as `Box<T>`, `Rc<T>`, and `Arc<T>` are treated exactly as their contained
+//! value, wherever they appear. For example, a field of type `Box<bool>` is a boolean field, and a
+//! field of type `Option<Arc<String>>` is an optional field. Deserializing `Rc<T>` and `Arc<T>`
+//! requires `serde`'s `rc` feature.
+//!
 //! # `expecting()` Option Specification
 //!
 //! While most users will likely want to create types using `serde`'s derive macros, some users may
@@ -178,10 +293,9 @@
 //!
 //! ### Field and variant messages
 //!
-//! Custom messages are provided for fields and variants by writing the corresponding message using
-//! the field/variant index provided. The field/variant index is provided through
-//! `formatter.width()`. Write the corresponding message (and nothing else) when a width is
-//! provided corresponding to the field or variant.
+//! Custom messages are provided for fields and variants as `description` entries of the settings
+//! record, described in [Settings](#settings). Fields and variants without an entry have no
+//! message.
 //!
 //! For example, for a struct defined as follows:
 //!
@@ -207,6 +321,53 @@
 //!     type Value = ();
 //!
 //!     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+//!         if formatter.fill() == 's' {
+//!             for (index, message) in ["First field's message", "Second field's message"]
+//!                 .into_iter()
+//!                 .enumerate()
+//!             {
+//!                 let entry = format!("description\x1F{}\x1F{}", index, message);
+//!                 write!(formatter, "{}:{}", entry.len(), entry)?;
+//!             }
+//!             return Ok(());
+//!         }
+//!         formatter.write_str("Container's message")
+//!     }
+//! }
+//! ```
+//!
+//! Since every message is obtained at once, the messages do not depend on how the formatter is
+//! otherwise configured.
+//!
+//! If no record is written, each message is instead requested individually by providing the
+//! field/variant index through `formatter.width()`. Write the corresponding message (and nothing
+//! else) when a width is provided corresponding to the field or variant. This form is deprecated,
+//! and will be removed in a future release.
+//!
+//! For example, for the same struct:
+//!
+//! ```rust
+//! struct Container {
+//!     first_field: String,
+//!     second_field: Option<usize>,
+//! }
+//! ```
+//!
+//! One could specify field messages as follows:
+//!
+//! ```rust
+//! use serde::de::Visitor;
+//! use std::{
+//!     fmt,
+//!     fmt::Formatter,
+//! };
+//!
+//! struct ContainerVisitor;
+//!
+//! impl<'de> Visitor<'de> for ContainerVisitor {
+//!     type Value = ();
+//!
+//!     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
 //!         match formatter.width() {
 //!             Some(0) => formatter.write_str("First field's message"),
 //!             Some(1) => formatter.write_str("Second field's message"),
@@ -216,10 +377,90 @@
 //! }
 //! ```
 //!
+//! ## Settings
+//!
+//! Every other customization is provided by writing a single settings record when `expecting()` is
+//! called with `formatter.fill()` set to `'s'` and no width. The record is made of entries written
+//! one after another, with nothing in between. Each entry is the setting's name, the index of the
+//! field or variant it applies to, and the setting's value, separated by `\x1F`, prefixed with the
+//! length of the entry in bytes followed by a `:`. The index is left empty for settings of the
+//! container itself, and the value is left empty for settings that are simply enabled. Entries for
+//! unknown settings are ignored.
+//!
+//! Visitors that do not write a record usually write their message regardless of the fill. Anything
+//! that is not a record provides no settings. Settings of fields and variants are read from the
+//! visitor of a newtype struct wrapping the struct or enum, which is how the
+//! [`#[generate]`](crate::generate) macro provides them.
+//!
+//! | Setting | Index | Value | Meaning |
+//! | --- | --- | --- | --- |
+//! | `description` | Field or variant | Any message | The message of the field or variant, as described in [Field and variant messages](#field-and-variant-messages). |
+//! | `name` | Field | The field's name | The field's actual name. `serde` lists a field's names in sorted order, so the first one may be an alias. |
+//! | `value_name` | Field | Any name | The name displayed for a required field in usage and error messages. The field name is still used when parsing. |
+//! | `allow_hyphen_values` | Field | Empty | A required field accepts values beginning with a hyphen, such as search patterns. Values matching one of the struct's own options are still parsed as options. |
+//! | `positional` | Field | Empty | An optional primitive field is parsed as the last positional argument, and is `None` if no argument remains for it. It must come after every other positional argument, including those of any struct or command containing it. |
+//! | `trailing` | Field | Empty | A required sequence field captures its first value, or the first option that is not recognized, along with every argument following it, including options and `--`. It must be the last positional argument of its struct. |
+//! | `required` | Field | Empty | An optional field that is not a boolean must be given. Parsing fails listing every missing required option, and the option is displayed alongside the positional arguments in usage messages. |
+//! | `flags` | Field | Empty | An optional enum of unit variants is given as a separate flag for each variant, such as `--json` or `--yaml`, each listed in help messages with its variant's message. Giving flags for more than one variant, or the same flag more than once, is an error. A variant named the same as another option of the struct is a development error. |
+//! | `value_delimiter` | Field | A single ASCII character | An optional sequence of primitives takes a single value per occurrence, split into elements on the delimiter before being interpreted as UTF-8. Values containing empty elements are rejected. |
+//! | `multiple` | Field | Empty | An optional sequence of primitives takes values until the next argument beginning with a hyphen, which is parsed as usual. A lone `-` is a value. It cannot also have a delimiter. |
+//! | `one_of`, `at_most_one`, `all_or_none` | None | Field indices, such as `0,1` | A constraint on which options of a struct may be given together, as described below. |
+//! | `group` | Variant | Any heading | The heading the variant is listed under in help messages. Groups are listed in the order they first appear, with ungrouped variants under the default heading. |
+//! | `raw` | Variant | Empty | Every argument following the variant's name is positional, as if `--` were given. The variant cannot contain options, and a newtype variant wrapping a sequence captures every remaining argument, displayed as `<args>...`. |
+//! | `version` | None or variant | Any version | The version of the container or variant, as described in [Version Information](#version-information). |
+//! | `examples` | None | Any text | Examples of invoking the program, displayed verbatim one line at a time at the end of help messages. |
+//! | `no_auto_help` | None | Empty | The automatic `-h` and `--help` options are disabled. |
+//! | `no_empty_help` | None | Empty | Missing arguments are reported as an error rather than displaying help when no arguments are given. |
+//!
+//! Options given more than once with `value_delimiter` or `multiple` collect the values of every
+//! occurrence in order. Each constraint is written as its own entry, with the comma-separated
+//! indices of the fields it applies to as its value. The kinds are:
+//!
+//! - `one_of` - Exactly one of the options must be given.
+//! - `at_most_one` - No more than one of the options may be given.
+//! - `all_or_none` - Either every option or none of them must be given.
+//!
+//! Only optional and boolean fields may be constrained. Constraints are checked once all arguments
+//! have been parsed, and each unsatisfied constraint is reported as an error listing the options
+//! involved.
+//!
+//! For example, a newtype struct wrapping a struct whose first field is a required `String` and
+//! whose second field is an `Option<Vec<String>>` could accept hyphenated values for the first
+//! field and comma-separated values for the second:
+//!
+//! ```rust
+//! use serde::de::Visitor;
+//! use std::{
+//!     fmt,
+//!     fmt::Formatter,
+//! };
+//!
+//! struct NewtypeVisitor;
+//!
+//! impl<'de> Visitor<'de> for NewtypeVisitor {
+//!     type Value = ();
+//!
+//!     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+//!         if formatter.fill() == 's' {
+//!             for entry in [
+//!                 "allow_hyphen_values\x1F0\x1F",
+//!                 "value_name\x1F0\x1FPATTERN",
+//!                 "value_delimiter\x1F1\x1F,",
+//!                 "examples\x1F\x1F$ program -v --files a.txt,b.txt",
+//!             ] {
+//!                 write!(formatter, "{}:{}", entry.len(), entry)?;
+//!             }
+//!             return Ok(());
+//!         }
+//!         formatter.write_str("Struct's message")
+//!     }
+//! }
+//! ```
+//!
 //! ## Version Information
 //!
-//! To specify that a `--version` flag should be used, `expecting()` should provide a version to be
-//! used as version output when `formatter.fill()` is `'v'`. For example:
+//! To specify that a `--version` flag should be used, the settings record should provide a
+//! `version` entry without an index, whose value is used as version output. For example:
 //!
 //! ```rust
 //! use serde::de::Visitor;
@@ -234,16 +475,20 @@
 //!     type Value = ();
 //!
 //!     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-//!         if formatter.fill() == 'v' {
-//!             formatter.write_str("0.1.0")?;
+//!         if formatter.fill() == 's' {
+//!             let entry = "version\x1F\x1F0.1.0";
+//!             write!(formatter, "{}:{}", entry.len(), entry)?;
 //!         }
 //!         Ok(())
 //!     }
 //! }
 //! ```
 //!
-//! Note that the version provided to the formatter must be different than what is provided when
-//! the version is not requested; otherwise it will be ignored and no `--format` flag will be used.
+//! If no record is written, the version is instead requested by setting `formatter.fill()` to
+//! `'v'`, providing the variant index through `formatter.width()` for the version of a variant.
+//! The version provided must be different than what is provided when the version is not
+//! requested; otherwise it will be ignored and no `--version` flag will be used. This form is
+//! deprecated, and will be removed in a future release.
 //!
 //! # Unsupported Deserialization Behavior
 //!
@@ -255,8 +500,13 @@
 //!   then calling `Deserializer::deserialize_enum()` on the next run (or vice-versa).
 //! - Attempting to deserialize an identifier by calling anything besides
 //!   `Deserializer::deserialize_identifier()`.
-//! - Calling `Deserializer::deserialize_any()` or `Deserializer::deserialize_ignored_any()`.
-//!   `serde_args` is **not** a self-describing format.
+//! - Calling `Deserializer::deserialize_ignored_any()`, or calling
+//!   `Deserializer::deserialize_any()` for anything other than an enum accepting its variants
+//!   either by name or by numeric code. `serde_args` is **not** a self-describing format.
+//! - Types that contain themselves, such as `struct Tree { child: Option<Box<Tree>> }`. The command
+//!   line interface is described by a finite shape, so types nested more than 128 levels deep are
+//!   rejected with a development error naming the types containing them. This limit can be changed
+//!   using [`Parser::max_type_depth()`](crate::Parser::max_type_depth()).
 //!
 //! [`Deserialize`]: serde::Deserialize
 //! [`Deserializer`]: serde::Deserializer
@@ -0,0 +1,240 @@
+//! Shell-like splitting of a command line into arguments.
+
+use std::{
+    fmt,
+    fmt::{
+        Display,
+        Formatter,
+    },
+};
+
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum Error {
+    /// A quote was opened but never closed.
+    UnterminatedQuote {
+        quote: char,
+        /// The byte offset of the opening quote.
+        offset: usize,
+    },
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::UnterminatedQuote { quote, offset } => write!(
+                formatter,
+                "unterminated {} quote starting at byte {}",
+                if *quote == '\'' { "single" } else { "double" },
+                offset
+            ),
+        }
+    }
+}
+
+/// Splits a command line into arguments.
+///
+/// Arguments are separated by unquoted whitespace. Within single quotes, every character is taken
+/// literally. Within double quotes, a backslash escapes a following `"` or `\`, and is otherwise
+/// taken literally. Outside of quotes, a backslash escapes any following character. Adjacent quoted
+/// and unquoted text forms a single argument, and empty quotes form an empty argument. No variable
+/// expansion or globbing is performed.
+#[cfg_attr(all(target_family = "wasm", target_os = "unknown"), allow(dead_code))]
+pub(crate) fn split(line: &str) -> Result<Vec<String>, Error> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    // Whether an argument has been started, even if it is empty (such as with `""`).
+    let mut started = false;
+    let mut chars = line.char_indices();
+
+    while let Some((offset, c)) = chars.next() {
+        match c {
+            '\'' | '"' => {
+                started = true;
+                loop {
+                    match chars.next() {
+                        Some((_, quoted)) if quoted == c => break,
+                        Some((_, '\\')) if c == '"' => match chars.next() {
+                            Some((_, escaped @ ('"' | '\\'))) => current.push(escaped),
+                            Some((_, escaped)) => {
+                                current.push('\\');
+                                current.push(escaped);
+                            }
+                            None => return Err(Error::UnterminatedQuote { quote: c, offset }),
+                        },
+                        Some((_, quoted)) => current.push(quoted),
+                        None => return Err(Error::UnterminatedQuote { quote: c, offset }),
+                    }
+                }
+            }
+            '\\' => {
+                started = true;
+                // A trailing backslash has nothing to escape, and is kept as is.
+                current.push(chars.next().map_or('\\', |(_, escaped)| escaped));
+            }
+            c if c.is_whitespace() => {
+                if started {
+                    args.push(current.split_off(0));
+                    started = false;
+                }
+            }
+            c => {
+                started = true;
+                current.push(c);
+            }
+        }
+    }
+    if started {
+        args.push(current);
+    }
+
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        split,
+        Error,
+    };
+    use claims::{
+        assert_err_eq,
+        assert_ok_eq,
+    };
+
+    #[test]
+    fn split_empty() {
+        assert_ok_eq!(split(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn split_whitespace_only() {
+        assert_ok_eq!(split("  \t\n "), Vec::<String>::new());
+    }
+
+    #[test]
+    fn split_words() {
+        assert_ok_eq!(
+            split("commit  --force\tfoo\n"),
+            vec!["commit", "--force", "foo"]
+        );
+    }
+
+    #[test]
+    fn split_double_quoted_spaces() {
+        assert_ok_eq!(
+            split(r#"commit -m "fix things" --force"#),
+            vec!["commit", "-m", "fix things", "--force"]
+        );
+    }
+
+    #[test]
+    fn split_single_quoted_spaces() {
+        assert_ok_eq!(split("-m 'fix things'"), vec!["-m", "fix things"]);
+    }
+
+    #[test]
+    fn split_single_quoted_literal() {
+        assert_ok_eq!(split(r#"'a \" b $HOME'"#), vec![r#"a \" b $HOME"#]);
+    }
+
+    #[test]
+    fn split_double_quoted_escapes() {
+        assert_ok_eq!(split(r#""a \" \\ \n b""#), vec![r#"a " \ \n b"#]);
+    }
+
+    #[test]
+    fn split_double_quoted_single_quote() {
+        assert_ok_eq!(split(r#""it's""#), vec!["it's"]);
+    }
+
+    #[test]
+    fn split_unquoted_escapes() {
+        assert_ok_eq!(split(r#"a\ b \"c\" \\"#), vec!["a b", "\"c\"", "\\"]);
+    }
+
+    #[test]
+    fn split_trailing_backslash() {
+        assert_ok_eq!(split(r"foo\"), vec![r"foo\"]);
+    }
+
+    #[test]
+    fn split_adjacent_quotes() {
+        assert_ok_eq!(split(r#"--name="foo bar"'baz'"#), vec!["--name=foo barbaz"]);
+    }
+
+    #[test]
+    fn split_empty_arguments() {
+        assert_ok_eq!(split(r#"foo "" '' bar"#), vec!["foo", "", "", "bar"]);
+    }
+
+    #[test]
+    fn split_no_variable_expansion() {
+        assert_ok_eq!(split("$HOME ~ *"), vec!["$HOME", "~", "*"]);
+    }
+
+    #[test]
+    fn split_non_ascii() {
+        assert_ok_eq!(split("\u{1f600} 'ü ö'"), vec!["\u{1f600}", "ü ö"]);
+    }
+
+    #[test]
+    fn split_unterminated_double_quote() {
+        assert_err_eq!(
+            split(r#"commit -m "fix things"#),
+            Error::UnterminatedQuote {
+                quote: '"',
+                offset: 10,
+            }
+        );
+    }
+
+    #[test]
+    fn split_unterminated_single_quote() {
+        assert_err_eq!(
+            split("ü 'foo"),
+            Error::UnterminatedQuote {
+                quote: '\'',
+                offset: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn split_unterminated_double_quote_escaped() {
+        assert_err_eq!(
+            split(r#""foo\""#),
+            Error::UnterminatedQuote {
+                quote: '"',
+                offset: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn unterminated_double_quote_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnterminatedQuote {
+                    quote: '"',
+                    offset: 10,
+                }
+            ),
+            "unterminated double quote starting at byte 10"
+        );
+    }
+
+    #[test]
+    fn unterminated_single_quote_display() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnterminatedQuote {
+                    quote: '\'',
+                    offset: 3,
+                }
+            ),
+            "unterminated single quote starting at byte 3"
+        );
+    }
+}
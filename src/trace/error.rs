@@ -18,6 +18,45 @@ pub(crate) enum Error {
     NotSelfDescribing,
     UnsupportedIdentifierDeserialization,
     CannotMixDeserializeStructAndDeserializeEnum,
+    UnsupportedSequenceElement,
+    UnsupportedTupleElement,
+    /// A tuple element rejected the placeholder value it was given while later elements were
+    /// traced.
+    ///
+    /// This contains the position of the element, starting from one.
+    UntraceableTupleElement(usize),
+    UnsupportedTupleLength(usize),
+    CaseInsensitiveVariantConflict(&'static str, &'static str),
+    UnsupportedOptionalPositional(&'static str),
+    OptionalPositionalNotLast(&'static str),
+    UnsupportedValueDelimiter(&'static str),
+    UnsupportedTrailing(&'static str),
+    TrailingNotLast(&'static str),
+    UnsupportedRequired(&'static str),
+    UnsupportedConstraint(&'static str),
+    UnsupportedFlags(&'static str),
+    /// A flag of a field given as flags has the same name as another option of its struct.
+    ///
+    /// This contains the name of the field followed by the name of the flag.
+    ConflictingFlagName(&'static str, &'static str),
+    UnsupportedMultiple(&'static str),
+    UnsupportedRaw(&'static str),
+    /// Types were nested more deeply than the tracing limit, usually because a type contains
+    /// itself.
+    ///
+    /// This contains the names of the types containing the one that was nested too deeply,
+    /// starting from the outermost.
+    RecursiveType(Vec<&'static str>),
+    /// A `Deserialize` implementation recovered from a tracing status instead of returning it.
+    ///
+    /// This contains the name of the field or variant being deserialized, if any.
+    UnsupportedType(Option<&'static str>),
+    MissingExecutableName,
+    UnknownMetadataPath(String),
+    /// Metadata deprecates an alias that the field or variant at the path does not have.
+    ///
+    /// This contains the path followed by the alias.
+    UnknownMetadataAlias(String, String),
 
     // `serde` errors.
     Custom(String),
@@ -33,9 +72,44 @@ pub(crate) enum Error {
 impl Display for Error {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
-            Self::NotSelfDescribing => formatter.write_str("cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` or `Deserializer::deserialize_ignored_any()` is not allowed"),
+            Self::NotSelfDescribing => formatter.write_str("cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` is only allowed for enums accepting their variants either by name or by numeric code, and use of `Deserializer::deserialize_ignored_any()` is not allowed"),
             Self::UnsupportedIdentifierDeserialization => formatter.write_str("identifiers must be deserialized with `deserialize_identifier()`"),
             Self::CannotMixDeserializeStructAndDeserializeEnum => formatter.write_str("cannot deserialize using both `deserialize_struct()` and `deserialize_enum()` on same type on seperate calls"),
+            Self::UnsupportedSequenceElement => formatter.write_str("sequence elements must be primitive values, each consuming exactly one argument"),
+            Self::UnsupportedTupleElement => formatter.write_str("tuple and array elements must be primitive values, each consuming exactly one argument"),
+            Self::UntraceableTupleElement(position) => write!(formatter, "tuple element {} cannot be traced, since its `Deserialize` implementation rejected a placeholder value; only the last element may reject placeholder values", position),
+            Self::UnsupportedTupleLength(len) => write!(formatter, "tuples and arrays may have at most {} elements, found {}", super::ELEMENT_NAMES.len(), len),
+            Self::CaseInsensitiveVariantConflict(first, second) => write!(formatter, "variants `{}` and `{}` cannot be distinguished when matching commands case-insensitively", first, second),
+            Self::UnsupportedOptionalPositional(field) => write!(formatter, "positional field `{}` must be an optional primitive value", field),
+            Self::OptionalPositionalNotLast(field) => write!(formatter, "optional positional field `{}` must be the last positional argument", field),
+            Self::UnsupportedTrailing(field) => write!(formatter, "trailing field `{}` must be a required sequence", field),
+            Self::TrailingNotLast(field) => write!(formatter, "trailing field `{}` must be the last positional argument", field),
+            Self::UnsupportedRequired(field) => write!(formatter, "required field `{}` must be an optional field given as an option taking a value", field),
+            Self::UnsupportedConstraint(field) => write!(formatter, "field `{}` in a constraint must be an optional field or boolean flag given as an option", field),
+            Self::UnsupportedFlags(field) => write!(formatter, "field `{}` given as flags must be an optional enum whose variants are all units", field),
+            Self::ConflictingFlagName(field, flag) => write!(formatter, "flag `{}` of field `{}` given as flags has the same name as another option", flag, field),
+            Self::UnsupportedMultiple(field) => write!(formatter, "field `{}` taking multiple values must be an optional sequence of primitive values without a value delimiter", field),
+            Self::UnsupportedRaw(variant) => write!(formatter, "variant `{}` given as raw cannot contain options", variant),
+            Self::RecursiveType(names) => {
+                formatter.write_str("recursive or overly deep type detected while tracing")?;
+                // Only the types up to the first one that repeats are listed, which is where a
+                // recursive type begins containing itself.
+                let end = names
+                    .iter()
+                    .enumerate()
+                    .position(|(index, name)| names[..index].contains(name))
+                    .map_or(names.len(), |index| index + 1);
+                if end > 0 {
+                    write!(formatter, ": {}", names[..end].join(" -> "))?;
+                }
+                Ok(())
+            }
+            Self::UnsupportedType(Some(name)) => write!(formatter, "`{}` cannot be traced, since its `Deserialize` implementation recovered from an error returned by the deserializer instead of returning it", name),
+            Self::UnsupportedType(None) => formatter.write_str("type cannot be traced, since its `Deserialize` implementation recovered from an error returned by the deserializer instead of returning it"),
+            Self::MissingExecutableName => formatter.write_str("the name of the executable could not be obtained; set it using `Parser::name()`"),
+            Self::UnknownMetadataPath(path) => write!(formatter, "metadata refers to `{}`, which is not a field or variant", path),
+            Self::UnknownMetadataAlias(path, alias) => write!(formatter, "metadata deprecates alias `{}` of `{}`, which is not one of its aliases", alias, path),
+            Self::UnsupportedValueDelimiter(field) => write!(formatter, "field `{}` with a value delimiter must be an optional sequence of primitive values", field),
             Self::Custom(message) => write!(formatter, "serde error: custom: {}", message),
             Self::InvalidType(unexpected, expected) => write!(
                 formatter,
@@ -72,6 +146,23 @@ impl Display for Error {
     }
 }
 
+impl Error {
+    /// Returns whether this error was created through `serde::de::Error`, rather than by tracing.
+    pub(super) fn is_deserialize_error(&self) -> bool {
+        matches!(
+            self,
+            Self::Custom(_)
+                | Self::InvalidType(..)
+                | Self::InvalidValue(..)
+                | Self::InvalidLength(..)
+                | Self::UnknownVariant(..)
+                | Self::UnknownField(..)
+                | Self::MissingField(_)
+                | Self::DuplicateField(_)
+        )
+    }
+}
+
 impl de::Error for Error {
     fn custom<T>(message: T) -> Self
     where
@@ -123,7 +214,7 @@ mod tests {
     fn error_display_not_self_describing() {
         assert_eq!(
             format!("{}", Error::NotSelfDescribing),
-            "cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` or `Deserializer::deserialize_ignored_any()` is not allowed"
+            "cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` is only allowed for enums accepting their variants either by name or by numeric code, and use of `Deserializer::deserialize_ignored_any()` is not allowed"
         );
     }
 
@@ -143,6 +234,156 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_display_unsupported_sequence_element() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedSequenceElement),
+            "sequence elements must be primitive values, each consuming exactly one argument"
+        );
+    }
+
+    #[test]
+    fn error_display_unsupported_tuple_element() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedTupleElement),
+            "tuple and array elements must be primitive values, each consuming exactly one argument"
+        );
+    }
+
+    #[test]
+    fn error_display_untraceable_tuple_element() {
+        assert_eq!(
+            format!("{}", Error::UntraceableTupleElement(2)),
+            "tuple element 2 cannot be traced, since its `Deserialize` implementation rejected a placeholder value; only the last element may reject placeholder values"
+        );
+    }
+
+    #[test]
+    fn error_display_unsupported_tuple_length() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedTupleLength(33)),
+            "tuples and arrays may have at most 32 elements, found 33"
+        );
+    }
+
+    #[test]
+    fn error_display_case_insensitive_variant_conflict() {
+        assert_eq!(
+            format!("{}", Error::CaseInsensitiveVariantConflict("clone", "Clone")),
+            "variants `clone` and `Clone` cannot be distinguished when matching commands case-insensitively"
+        );
+    }
+
+    #[test]
+    fn error_display_unsupported_flags() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedFlags("format")),
+            "field `format` given as flags must be an optional enum whose variants are all units"
+        );
+    }
+
+    #[test]
+    fn error_display_conflicting_flag_name() {
+        assert_eq!(
+            format!("{}", Error::ConflictingFlagName("format", "json")),
+            "flag `json` of field `format` given as flags has the same name as another option"
+        );
+    }
+
+    #[test]
+    fn error_display_unsupported_multiple() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedMultiple("nums")),
+            "field `nums` taking multiple values must be an optional sequence of primitive values without a value delimiter"
+        );
+    }
+
+    #[test]
+    fn error_display_recursive_type() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::RecursiveType(vec!["Args", "Tree", "Option", "Tree", "Option", "Tree"])
+            ),
+            "recursive or overly deep type detected while tracing: Args -> Tree -> Option -> Tree"
+        );
+    }
+
+    #[test]
+    fn error_display_recursive_type_no_repeats() {
+        assert_eq!(
+            format!("{}", Error::RecursiveType(vec!["Outer", "Inner"])),
+            "recursive or overly deep type detected while tracing: Outer -> Inner"
+        );
+    }
+
+    #[test]
+    fn error_display_recursive_type_empty() {
+        assert_eq!(
+            format!("{}", Error::RecursiveType(Vec::new())),
+            "recursive or overly deep type detected while tracing"
+        );
+    }
+
+    #[test]
+    fn error_display_unsupported_raw() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedRaw("exec")),
+            "variant `exec` given as raw cannot contain options"
+        );
+    }
+
+    #[test]
+    fn error_display_unsupported_type() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedType(Some("id"))),
+            "`id` cannot be traced, since its `Deserialize` implementation recovered from an error returned by the deserializer instead of returning it"
+        );
+    }
+
+    #[test]
+    fn error_display_unsupported_type_unnamed() {
+        assert_eq!(
+            format!("{}", Error::UnsupportedType(None)),
+            "type cannot be traced, since its `Deserialize` implementation recovered from an error returned by the deserializer instead of returning it"
+        );
+    }
+
+    #[test]
+    fn error_is_deserialize_error() {
+        assert!(Error::Custom("foo".into()).is_deserialize_error());
+        assert!(Error::MissingField("foo").is_deserialize_error());
+        assert!(!Error::NotSelfDescribing.is_deserialize_error());
+        assert!(!Error::UnsupportedType(None).is_deserialize_error());
+    }
+
+    #[test]
+    fn error_display_missing_executable_name() {
+        assert_eq!(
+            format!("{}", Error::MissingExecutableName),
+            "the name of the executable could not be obtained; set it using `Parser::name()`"
+        );
+    }
+
+    #[test]
+    fn error_display_unknown_metadata_path() {
+        assert_eq!(
+            format!("{}", Error::UnknownMetadataPath("foo.bar".into())),
+            "metadata refers to `foo.bar`, which is not a field or variant"
+        );
+    }
+
+    #[test]
+    fn error_display_unknown_metadata_alias() {
+        assert_eq!(
+            format!(
+                "{}",
+                Error::UnknownMetadataAlias("foo.bar".into(), "baz".into())
+            ),
+            "metadata deprecates alias `baz` of `foo.bar`, which is not one of its aliases"
+        );
+    }
+
     #[test]
     fn error_display_custom() {
         assert_eq!(
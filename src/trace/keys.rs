@@ -1,4 +1,8 @@
 use super::{
+    description_from_visitor,
+    record::Setting,
+    settings_from_visitor,
+    version_from_visitor,
     Error,
     Field,
     Shape,
@@ -26,17 +30,21 @@ impl KeyInfo {
                     name: _,
                     description: self_description,
                     version: self_version,
+                    examples: self_examples,
                     required: self_required,
                     optional: self_optional,
                     booleans: self_booleans,
+                    constraints: self_constraints,
                 },
                 Shape::Struct {
                     name: _,
                     description: other_description,
                     version: other_version,
+                    examples: other_examples,
                     required: other_required,
                     optional: other_optional,
                     booleans: other_booleans,
+                    constraints: other_constraints,
                 },
             ) => {
                 // Compare without name field.
@@ -46,9 +54,11 @@ impl KeyInfo {
                         name: "",
                         description: self_description.clone(),
                         version: self_version.clone(),
+                        examples: self_examples.clone(),
                         required: self_required.clone(),
                         optional: self_optional.clone(),
                         booleans: self_booleans.clone(),
+                        constraints: self_constraints.clone(),
                     },
                 } == Self {
                     discriminant: other.discriminant,
@@ -56,9 +66,11 @@ impl KeyInfo {
                         name: "",
                         description: other_description.clone(),
                         version: other_version.clone(),
+                        examples: other_examples.clone(),
                         required: other_required.clone(),
                         optional: other_optional.clone(),
                         booleans: other_booleans.clone(),
+                        constraints: other_constraints.clone(),
                     },
                 }
             }
@@ -72,6 +84,7 @@ pub(super) struct Fields {
     pub(super) name: &'static str,
     pub(super) description: String,
     pub(super) version: Option<String>,
+    pub(super) examples: Option<String>,
     pub(super) iter: slice::Iter<'static, &'static str>,
     pub(super) revisit: Option<&'static str>,
     pub(super) required_fields: Vec<(KeyInfo, Vec<&'static str>, String, usize)>,
@@ -79,23 +92,86 @@ pub(super) struct Fields {
     pub(super) boolean_fields: Vec<(KeyInfo, Vec<&'static str>, String, usize)>,
 }
 
+impl Fields {
+    pub(super) fn new(
+        name: &'static str,
+        fields: &'static [&'static str],
+        visitor: &dyn Expected,
+    ) -> Self {
+        let description = description_from_visitor(visitor);
+        let record = settings_from_visitor(visitor);
+        let version = version_from_visitor(visitor, &record, None, [&description, &description]);
+        let examples = record.value(Setting::Examples, None).map(str::to_owned);
+        Self {
+            name,
+            description,
+            version,
+            examples,
+            iter: fields.iter(),
+            revisit: None,
+            required_fields: Vec::new(),
+            optional_fields: Vec::new(),
+            boolean_fields: Vec::new(),
+        }
+    }
+
+    /// Records the traced `shape` of `field`.
+    ///
+    /// Optional and boolean fields are recorded without their wrapping shape. A field sharing its
+    /// key with a field recorded earlier is recorded as one of its names, rather than as a separate
+    /// field.
+    pub(super) fn record(
+        &mut self,
+        field: &'static str,
+        discriminant: u64,
+        description: String,
+        shape: Shape,
+    ) {
+        let index =
+            self.required_fields.len() + self.optional_fields.len() + self.boolean_fields.len();
+        let (fields, shape) = match shape {
+            Shape::Optional(shape) => (&mut self.optional_fields, *shape),
+            Shape::Boolean {
+                description,
+                version,
+                ..
+            } => (
+                &mut self.boolean_fields,
+                Shape::Empty {
+                    description,
+                    version,
+                },
+            ),
+            shape => (&mut self.required_fields, shape),
+        };
+        let key_info = KeyInfo {
+            discriminant,
+            shape,
+        };
+        if let Some((_, names, _, _)) = fields.iter_mut().find(|(info, ..)| *info == key_info) {
+            names.push(field);
+        } else {
+            fields.push((key_info, vec![field], description, index));
+        }
+    }
+}
+
 impl From<Fields> for Shape {
     fn from(fields: Fields) -> Self {
         Shape::Struct {
             name: fields.name,
             description: fields.description,
             version: fields.version,
+            examples: fields.examples,
             required: fields
                 .required_fields
                 .into_iter()
                 .map(|(info, mut names, description, index)| {
                     let first = names.remove(0);
                     Field {
-                        name: first,
                         description,
                         aliases: names,
-                        shape: info.shape,
-                        index,
+                        ..Field::new(first, info.shape, index)
                     }
                 })
                 .collect(),
@@ -105,11 +181,9 @@ impl From<Fields> for Shape {
                 .map(|(info, mut names, description, index)| {
                     let first = names.remove(0);
                     Field {
-                        name: first,
                         description,
                         aliases: names,
-                        shape: info.shape,
-                        index,
+                        ..Field::new(first, info.shape, index)
                     }
                 })
                 .collect(),
@@ -119,14 +193,13 @@ impl From<Fields> for Shape {
                 .map(|(info, mut names, description, index)| {
                     let first = names.remove(0);
                     Field {
-                        name: first,
                         description,
                         aliases: names,
-                        shape: info.shape,
-                        index,
+                        ..Field::new(first, info.shape, index)
                     }
                 })
                 .collect(),
+            constraints: Vec::new(),
         }
     }
 }
@@ -144,14 +217,24 @@ impl PartialEq for Fields {
 
 impl Eq for Fields {}
 
+/// A traced variant's key info, names, description, version, and help group.
+pub(super) type VariantInfo = (
+    KeyInfo,
+    Vec<&'static str>,
+    String,
+    Option<String>,
+    Option<String>,
+);
+
 #[derive(Debug)]
 pub(super) struct Variants {
     pub(super) name: &'static str,
     pub(super) description: String,
     pub(super) version: Option<String>,
+    pub(super) examples: Option<String>,
     pub(super) iter: slice::Iter<'static, &'static str>,
     pub(super) revisit: Option<&'static str>,
-    pub(super) variants: Vec<(KeyInfo, Vec<&'static str>, String, Option<String>)>,
+    pub(super) variants: Vec<VariantInfo>,
 }
 
 impl Variants {
@@ -160,19 +243,15 @@ impl Variants {
         variants: &'static [&'static str],
         visitor: &dyn Expected,
     ) -> Self {
-        let description = format!("{}", visitor);
-        let version = {
-            let version = format!("{:v<}", visitor);
-            if version == description {
-                None
-            } else {
-                Some(version)
-            }
-        };
+        let description = description_from_visitor(visitor);
+        let record = settings_from_visitor(visitor);
+        let version = version_from_visitor(visitor, &record, None, [&description, &description]);
+        let examples = record.value(Setting::Examples, None).map(str::to_owned);
         Self {
             name,
             description,
             version,
+            examples,
             iter: variants.iter(),
             revisit: None,
             variants: Vec::new(),
@@ -186,16 +265,20 @@ impl From<Variants> for Shape {
             name: variants.name,
             description: variants.description,
             version: variants.version,
+            examples: variants.examples,
             variants: variants
                 .variants
                 .into_iter()
-                .map(|(info, mut names, description, version)| {
+                .map(|(info, mut names, description, version, group)| {
                     let first = names.remove(0);
                     Variant {
                         name: first,
                         description,
                         version,
+                        group,
+                        raw: false,
                         aliases: names,
+                        deprecated_aliases: Vec::new(),
                         shape: info.shape,
                     }
                 })
@@ -222,6 +305,9 @@ pub(super) enum Keys {
     Fields(Fields),
     Variants(Variants),
     Newtype(Shape),
+    /// The shapes of the elements of a tuple or array traced so far, in order, along with the
+    /// index of the placeholder value each is given while later elements are traced.
+    Elements(Vec<(Shape, usize)>),
 }
 
 impl Keys {
@@ -231,7 +317,7 @@ impl Keys {
         }
 
         match self {
-            Keys::None | Keys::Newtype(_) => unreachable!(),
+            Keys::None | Keys::Newtype(_) | Keys::Elements(_) => unreachable!(),
             Keys::Fields(ref mut fields) => Ok(fields),
             Keys::Variants(_) => Err(Error::CannotMixDeserializeStructAndDeserializeEnum),
         }
@@ -246,7 +332,7 @@ impl Keys {
         }
 
         match self {
-            Keys::None | Keys::Newtype(_) => unreachable!(),
+            Keys::None | Keys::Newtype(_) | Keys::Elements(_) => unreachable!(),
             Keys::Fields(_) => Err(Error::CannotMixDeserializeStructAndDeserializeEnum),
             Keys::Variants(ref mut variants) => Ok(variants),
         }
@@ -260,6 +346,7 @@ impl From<Keys> for Shape {
             Keys::Fields(fields) => fields.into(),
             Keys::Variants(variants) => variants.into(),
             Keys::Newtype(_) => unimplemented!("cannot deserialize shape from newtype directly"),
+            Keys::Elements(_) => unimplemented!("cannot deserialize shape from elements directly"),
         }
     }
 }
@@ -270,6 +357,7 @@ mod tests {
         super::{
             Error,
             Field,
+            PrimitiveKind,
             Shape,
             Variant,
         },
@@ -292,6 +380,7 @@ mod tests {
                 description: String::new(),
                 name: "baz".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Str,
             },
         }
         .variant_equality(&KeyInfo {
@@ -300,6 +389,7 @@ mod tests {
                 description: String::new(),
                 name: "baz".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Str,
             },
         }));
     }
@@ -312,6 +402,7 @@ mod tests {
                 description: String::new(),
                 name: "baz".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Str,
             },
         }
         .variant_equality(&KeyInfo {
@@ -320,6 +411,7 @@ mod tests {
                 description: String::new(),
                 name: "qux".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Str,
             },
         }));
     }
@@ -332,9 +424,11 @@ mod tests {
                 name: "foo",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             },
         }
         .variant_equality(&KeyInfo {
@@ -343,9 +437,11 @@ mod tests {
                 name: "bar",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             },
         }));
     }
@@ -358,9 +454,11 @@ mod tests {
                 name: "foo",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             },
         }
         .variant_equality(&KeyInfo {
@@ -369,9 +467,11 @@ mod tests {
                 name: "bar",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             },
         }));
     }
@@ -383,6 +483,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![],
@@ -393,9 +494,11 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
         );
     }
@@ -407,6 +510,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![(
@@ -416,6 +520,7 @@ mod tests {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     },
                     vec!["bar"],
@@ -429,19 +534,20 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
-                required: vec![Field {
-                    name: "bar",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                examples: None,
+                required: vec![Field::new(
+                    "bar",
+                    Shape::Primitive {
                         name: "foo".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 0,
-                },],
+                    0
+                ),],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
         );
     }
@@ -453,6 +559,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![
@@ -463,6 +570,7 @@ mod tests {
                                 name: "foo".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["bar"],
@@ -476,6 +584,7 @@ mod tests {
                                 description: String::new(),
                                 name: "baz".to_owned(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["qux"],
@@ -490,32 +599,32 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![
-                    Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                        0
+                    ),
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
         );
     }
@@ -527,6 +636,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![(
@@ -536,6 +646,7 @@ mod tests {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     },
                     vec!["bar", "baz", "qux"],
@@ -549,19 +660,23 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![Field {
-                    name: "bar",
-                    description: String::new(),
                     aliases: vec!["baz", "qux"],
-                    shape: Shape::Primitive {
-                        name: "foo".to_owned(),
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 0,
+                    ..Field::new(
+                        "bar",
+                        Shape::Primitive {
+                            name: "foo".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )
                 },],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
         );
     }
@@ -574,6 +689,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![],
             }
         );
@@ -586,6 +702,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 variants: vec![(
@@ -595,26 +712,33 @@ mod tests {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     },
                     vec!["bar"],
                     String::new(),
                     None,
+                    None,
                 ),],
             }),
             Shape::Enum {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![Variant {
                     name: "bar",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: vec![],
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Primitive {
                         name: "foo".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
                 }],
             }
@@ -628,6 +752,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 variants: vec![
@@ -638,11 +763,13 @@ mod tests {
                                 name: "foo".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["bar"],
                         String::new(),
                         None,
+                        None,
                     ),
                     (
                         KeyInfo {
@@ -651,11 +778,13 @@ mod tests {
                                 description: String::new(),
                                 name: "baz".to_owned(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["qux"],
                         String::new(),
                         None,
+                        None,
                     ),
                 ],
             }),
@@ -663,27 +792,36 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "bar",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Primitive {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     },
                     Variant {
                         name: "qux",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     }
                 ],
@@ -698,6 +836,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 variants: vec![(
@@ -707,26 +846,33 @@ mod tests {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     },
                     vec!["bar", "baz", "qux"],
                     String::new(),
                     None,
+                    None,
                 ),],
             }),
             Shape::Enum {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![Variant {
                     name: "bar",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: vec!["baz", "qux"],
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Primitive {
                         name: "foo".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
                 }],
             }
@@ -742,6 +888,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![],
@@ -752,6 +899,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![],
@@ -768,6 +916,7 @@ mod tests {
             name: "foo",
             description: "bar".into(),
             version: None,
+            examples: None,
             iter: [].iter(),
             revisit: None,
             required_fields: vec![],
@@ -780,6 +929,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![],
@@ -790,6 +940,7 @@ mod tests {
                 name: "foo",
                 description: "bar".into(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![],
@@ -809,6 +960,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![],
@@ -837,6 +989,7 @@ mod tests {
             name: "",
             description: String::new(),
             version: None,
+            examples: None,
             iter: [].iter(),
             revisit: None,
             required_fields: vec![],
@@ -875,6 +1028,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 required_fields: vec![
@@ -885,6 +1039,7 @@ mod tests {
                                 name: "foo".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["bar"],
@@ -898,6 +1053,7 @@ mod tests {
                                 description: String::new(),
                                 name: "baz".to_owned(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["qux"],
@@ -912,32 +1068,32 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![
-                    Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
-                    },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                        0
+                    ),
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
         );
     }
@@ -949,6 +1105,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 iter: [].iter(),
                 revisit: None,
                 variants: vec![
@@ -959,11 +1116,13 @@ mod tests {
                                 name: "foo".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["bar"],
                         String::new(),
                         None,
+                        None,
                     ),
                     (
                         KeyInfo {
@@ -972,11 +1131,13 @@ mod tests {
                                 description: String::new(),
                                 name: "baz".to_owned(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
                         },
                         vec!["qux"],
                         String::new(),
                         None,
+                        None,
                     ),
                 ],
             })),
@@ -984,27 +1145,36 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "bar",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Primitive {
                             name: "foo".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     },
                     Variant {
                         name: "qux",
                         description: String::new(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
                     }
                 ],
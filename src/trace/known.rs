@@ -0,0 +1,324 @@
+//! Display names for primitive types from well-known crates.
+//!
+//! These types do not describe themselves in a way that reads well in help messages, so their
+//! descriptions are mapped to short names instead. Types are recognized by the description their
+//! `Deserialize` implementation gives through `expecting()`, which means no dependency on the
+//! crates themselves is required.
+
+/// A primitive type from a well-known crate.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) struct Known {
+    /// The description given by the type's visitor.
+    description: &'static str,
+    /// The name displayed in help and error messages.
+    pub(crate) name: &'static str,
+    /// A prefix the type adds to its parse errors, which is redundant once the value is named.
+    error_prefix: &'static str,
+}
+
+const KNOWN: &[Known] = &[
+    #[cfg(feature = "url")]
+    Known {
+        description: "a string representing an URL",
+        name: "url",
+        error_prefix: "",
+    },
+    #[cfg(feature = "uuid")]
+    Known {
+        description: "a UUID string",
+        name: "uuid",
+        error_prefix: "UUID parsing failed: ",
+    },
+    #[cfg(feature = "chrono")]
+    Known {
+        description: "an RFC 3339 formatted date and time string",
+        name: "datetime",
+        error_prefix: "",
+    },
+];
+
+/// Finds the known type with the given visitor description, if any.
+pub(crate) fn find(description: &str) -> Option<&'static Known> {
+    KNOWN.iter().find(|known| known.description == description)
+}
+
+impl Known {
+    /// Removes the redundant prefix from a parse error message.
+    pub(crate) fn reason<'a>(&self, message: &'a str) -> &'a str {
+        message.strip_prefix(self.error_prefix).unwrap_or(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::find;
+    #[cfg(any(feature = "chrono", feature = "url", feature = "uuid"))]
+    use crate::{
+        de,
+        de::Deserializer,
+        parse::parse,
+        trace::{
+            trace,
+            Shape,
+        },
+    };
+    use claims::assert_none;
+    #[cfg(any(feature = "chrono", feature = "url", feature = "uuid"))]
+    use claims::{
+        assert_err_eq,
+        assert_ok,
+        assert_ok_eq,
+    };
+    #[cfg(any(feature = "chrono", feature = "url", feature = "uuid"))]
+    use serde::de::{
+        Deserialize,
+        DeserializeOwned,
+        Error,
+        Unexpected,
+        Visitor,
+    };
+    #[cfg(any(feature = "chrono", feature = "url", feature = "uuid"))]
+    use std::{
+        fmt,
+        fmt::Formatter,
+        marker::PhantomData,
+    };
+
+    #[cfg(any(feature = "chrono", feature = "url", feature = "uuid"))]
+    fn name<D>() -> String
+    where
+        D: DeserializeOwned,
+    {
+        match assert_ok!(trace(PhantomData::<D>)) {
+            Shape::Primitive { name, .. } => name,
+            shape => panic!("expected primitive shape, found {}", shape),
+        }
+    }
+
+    #[cfg(any(feature = "chrono", feature = "url", feature = "uuid"))]
+    fn deserialize<D>(arg: &str) -> Result<D, de::Error>
+    where
+        D: DeserializeOwned,
+    {
+        let mut shape = assert_ok!(trace(PhantomData::<D>));
+        D::deserialize(Deserializer::new(assert_ok!(parse([arg], &mut shape))))
+    }
+
+    /// Mirrors the `Deserialize` implementation of `url::Url`.
+    #[cfg(feature = "url")]
+    #[derive(Debug, Eq, PartialEq)]
+    struct Url(String);
+
+    #[cfg(feature = "url")]
+    impl<'de> Deserialize<'de> for Url {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct UrlVisitor;
+
+            impl Visitor<'_> for UrlVisitor {
+                type Value = Url;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("a string representing an URL")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    if value.contains("://") {
+                        Ok(Url(value.to_owned()))
+                    } else {
+                        Err(E::invalid_value(
+                            Unexpected::Str(value),
+                            &"relative URL without a base",
+                        ))
+                    }
+                }
+            }
+
+            deserializer.deserialize_str(UrlVisitor)
+        }
+    }
+
+    /// Mirrors the `Deserialize` implementation of `uuid::Uuid`, which expects bytes when the
+    /// deserializer is not human-readable.
+    #[cfg(feature = "uuid")]
+    #[derive(Debug, Eq, PartialEq)]
+    struct Uuid(String);
+
+    #[cfg(feature = "uuid")]
+    impl<'de> Deserialize<'de> for Uuid {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct UuidVisitor;
+
+            impl Visitor<'_> for UuidVisitor {
+                type Value = Uuid;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("a UUID string")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    if value.len() == 36 {
+                        Ok(Uuid(value.to_owned()))
+                    } else {
+                        Err(E::custom(format_args!(
+                            "UUID parsing failed: invalid length: expected 36, found {}",
+                            value.len()
+                        )))
+                    }
+                }
+            }
+
+            struct UuidBytesVisitor;
+
+            impl Visitor<'_> for UuidBytesVisitor {
+                type Value = Uuid;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("bytes")
+                }
+            }
+
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(UuidVisitor)
+            } else {
+                deserializer.deserialize_bytes(UuidBytesVisitor)
+            }
+        }
+    }
+
+    /// Mirrors the `Deserialize` implementation of `chrono::DateTime`.
+    #[cfg(feature = "chrono")]
+    #[derive(Debug, Eq, PartialEq)]
+    struct DateTime(String);
+
+    #[cfg(feature = "chrono")]
+    impl<'de> Deserialize<'de> for DateTime {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            struct DateTimeVisitor;
+
+            impl Visitor<'_> for DateTimeVisitor {
+                type Value = DateTime;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("an RFC 3339 formatted date and time string")
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: Error,
+                {
+                    if value.contains('T') {
+                        Ok(DateTime(value.to_owned()))
+                    } else {
+                        Err(E::custom("input contains invalid characters"))
+                    }
+                }
+            }
+
+            deserializer.deserialize_str(DateTimeVisitor)
+        }
+    }
+
+    #[test]
+    fn find_unknown() {
+        assert_none!(find("a string"));
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_name() {
+        assert_eq!(name::<Url>(), "url");
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_valid() {
+        assert_ok_eq!(
+            deserialize::<Url>("https://example.com"),
+            Url("https://example.com".to_owned())
+        );
+    }
+
+    #[cfg(feature = "url")]
+    #[test]
+    fn url_invalid() {
+        assert_err_eq!(
+            deserialize::<Url>("example"),
+            de::Error::InvalidKnownValue {
+                name: "url",
+                value: "example".to_owned(),
+                reason: "relative URL without a base".to_owned(),
+            }
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_name() {
+        assert_eq!(name::<Uuid>(), "uuid");
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_valid() {
+        assert_ok_eq!(
+            deserialize::<Uuid>("67e55044-10b1-426f-9247-bb680e5fe0c8"),
+            Uuid("67e55044-10b1-426f-9247-bb680e5fe0c8".to_owned())
+        );
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn uuid_invalid() {
+        assert_err_eq!(
+            deserialize::<Uuid>("67e55044"),
+            de::Error::InvalidKnownValue {
+                name: "uuid",
+                value: "67e55044".to_owned(),
+                reason: "invalid length: expected 36, found 8".to_owned(),
+            }
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_name() {
+        assert_eq!(name::<DateTime>(), "datetime");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_valid() {
+        assert_ok_eq!(
+            deserialize::<DateTime>("2024-01-01T00:00:00Z"),
+            DateTime("2024-01-01T00:00:00Z".to_owned())
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_invalid() {
+        assert_err_eq!(
+            deserialize::<DateTime>("yesterday"),
+            de::Error::InvalidKnownValue {
+                name: "datetime",
+                value: "yesterday".to_owned(),
+                reason: "input contains invalid characters".to_owned(),
+            }
+        );
+    }
+}
@@ -3,16 +3,26 @@
 mod error;
 mod hash;
 mod keys;
+pub(crate) mod known;
+mod record;
+mod settings;
 mod shape;
 
 pub(crate) use error::Error;
+pub(crate) use settings::Settings;
 pub(crate) use shape::{
+    Constraint,
+    ConstraintKind,
     Field,
+    PrimitiveKind,
     Shape,
     Variant,
 };
 
-use crate::key;
+use crate::{
+    helpers::path,
+    key,
+};
 use hash::IdentityHasher;
 use keys::{
     Fields,
@@ -20,6 +30,10 @@ use keys::{
     Keys,
     Variants,
 };
+use record::{
+    Record,
+    Setting,
+};
 use serde::{
     de,
     de::{
@@ -30,6 +44,7 @@ use serde::{
         Unexpected,
         Visitor,
     },
+    forward_to_deserialize_any,
 };
 use std::{
     fmt,
@@ -44,23 +59,114 @@ use std::{
     mem,
 };
 
+/// The default greatest depth of nested types that are traced.
+///
+/// This leaves room for tracing within the stack of a spawned thread, even in debug builds.
+pub(crate) const DEFAULT_MAX_DEPTH: usize = 128;
+
 pub(crate) fn trace<'de, D>(seed: D) -> Result<Shape, Error>
 where
     D: Copy + DeserializeSeed<'de>,
 {
-    let mut deserializer = Deserializer::new();
+    trace_with_settings(seed).map(|(shape, _)| shape)
+}
+
+/// Traces the shape of the type, along with any parser settings declared by the type itself.
+///
+/// Only settings declared on the top-level type are returned.
+pub(crate) fn trace_with_settings<'de, D>(seed: D) -> Result<(Shape, Settings), Error>
+where
+    D: Copy + DeserializeSeed<'de>,
+{
+    trace_with_max_depth(seed, DEFAULT_MAX_DEPTH)
+}
+
+/// Traces the shape of the type and its settings, nesting types at most `max_depth` levels deep.
+///
+/// A type containing itself, such as a tree whose nodes contain optional children, would
+/// otherwise be traced until the stack overflowed. Each field, variant, or newtype containing
+/// another type nests one level deeper, while options and boxes do not.
+pub(crate) fn trace_with_max_depth<'de, D>(
+    seed: D,
+    max_depth: usize,
+) -> Result<(Shape, Settings), Error>
+where
+    D: Copy + DeserializeSeed<'de>,
+{
+    let mut deserializer = Deserializer::with_max_depth(max_depth);
     loop {
-        let trace = match seed.deserialize(&mut deserializer) {
+        deserializer.probed = false;
+        let result = seed.deserialize(&mut deserializer);
+        let trace = match propagated(&deserializer, None, result) {
             Ok(_) => unreachable!("tracing unexpectedly succeeded in deserializing"),
             Err(trace) => trace,
         };
         match trace.0? {
-            Status::Success(shape) => return Ok(shape),
+            Status::Success(shape) => {
+                if let Some(field) = shape.optional_positional_conflict() {
+                    return Err(Error::OptionalPositionalNotLast(field));
+                }
+                return Ok((shape, deserializer.settings));
+            }
             Status::Continue => {}
         }
     }
 }
 
+/// Checks that the `Deserialize` implementation of `name` returned the tracing status it received
+/// from `deserializer`.
+///
+/// Implementations that recover from errors, such as by falling back to another type, would
+/// otherwise trace the wrong shape, since the status describes the type that was attempted.
+/// Tracing never successfully deserializes a value, so a successful result also means that a
+/// status was recovered from.
+// `Trace` carries traced shapes through `serde`'s error channel, so it is large by design.
+#[allow(clippy::result_large_err)]
+fn propagated<T>(
+    deserializer: &Deserializer,
+    name: Option<&'static str>,
+    result: Result<T, Trace>,
+) -> Result<T, Trace> {
+    match result {
+        Ok(_) => Err(Trace(Err(Error::UnsupportedType(name)))),
+        Err(Trace(Err(error))) if deserializer.probed && error.is_deserialize_error() => {
+            Err(Trace(Err(Error::UnsupportedType(name))))
+        }
+        result => result,
+    }
+}
+
+/// Returns the deserializer for a type nested within the type traced by a deserializer with
+/// `remaining_depth` levels remaining.
+///
+/// The deserializer is reused between passes, since it holds the progress of tracing the nested
+/// type.
+// As with `propagated()`, the error is a `Trace`.
+#[allow(clippy::result_large_err)]
+fn nested_deserializer(
+    recursive_deserializer: &mut Option<Box<Deserializer>>,
+    remaining_depth: usize,
+) -> Result<&mut Deserializer, Trace> {
+    let remaining_depth = remaining_depth
+        .checked_sub(1)
+        .ok_or(Trace(Err(Error::RecursiveType(Vec::new()))))?;
+    Ok(recursive_deserializer
+        .get_or_insert_with(|| Box::new(Deserializer::with_max_depth(remaining_depth)))
+        .as_mut())
+}
+
+/// Records `name` as containing the type that was nested too deeply, if that is what `trace`
+/// failed on.
+fn within_type(trace: Trace, name: &'static str) -> Trace {
+    match trace {
+        Trace(Err(Error::RecursiveType(mut names))) => {
+            names.insert(0, name);
+            Trace(Err(Error::RecursiveType(names)))
+        }
+        trace => trace,
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 enum Status {
     Success(Shape),
@@ -137,40 +243,105 @@ fn description_from_visitor(visitor: &dyn Expected) -> String {
     format!("{}", visitor)
 }
 
-fn version_from_visitor(visitor: &dyn Expected) -> String {
-    format!("{:v<}", visitor)
+/// Returns the settings record written by `expecting()` when `formatter.fill()` is set to `'s'`.
+///
+/// This is the only place the record is requested. A visitor that does not write a record writes
+/// its description instead, which is not a record, so it provides no settings.
+fn settings_from_visitor(visitor: &dyn Expected) -> Record {
+    Record::parse(&format!("{:s<}", visitor)).unwrap_or_default()
+}
+
+/// Returns the description of the key at `key`.
+///
+/// Descriptions are taken from the `record`. A visitor that did not write a record is instead asked
+/// for each description individually by providing the key's index through `formatter.width()`.
+fn key_description_from_visitor(visitor: &dyn Expected, record: &Record, key: usize) -> String {
+    if record.is_empty() {
+        format!("{:#key$}", visitor)
+    } else {
+        record
+            .value(Setting::Description, Some(key))
+            .unwrap_or_default()
+            .to_owned()
+    }
+}
+
+/// Returns the version of the key at `key`, or of the container itself if `key` is `None`.
+///
+/// Versions are taken from the `record`. A visitor that did not write a record is instead asked
+/// for the version by setting `formatter.fill()` to `'v'`, and writes one of the `descriptions` or
+/// nothing if there is no version.
+fn version_from_visitor(
+    visitor: &dyn Expected,
+    record: &Record,
+    key: Option<usize>,
+    descriptions: [&str; 2],
+) -> Option<String> {
+    let version = if record.is_empty() {
+        match key {
+            Some(key) => format!("{:v<key$}", visitor),
+            None => format!("{:v<}", visitor),
+        }
+    } else {
+        record.value(Setting::Version, key)?.to_owned()
+    };
+    (!version.is_empty() && !descriptions.contains(&version.as_str())).then_some(version)
 }
 
 #[derive(Debug, Eq, PartialEq)]
 struct Deserializer {
     keys: Keys,
     recursive_deserializer: Option<Box<Deserializer>>,
+    /// How many more levels of nested types can be traced beneath this deserializer.
+    remaining_depth: usize,
+    /// Settings declared by the container traced by this deserializer.
+    settings: Settings,
+    /// Whether this deserializer has returned a tracing status since this was last reset.
+    ///
+    /// Statuses are only ever created by this deserializer, so a `Deserialize` implementation
+    /// that returns anything else after receiving one must have recovered from it.
+    probed: bool,
+    /// The variant names reported when probing a value deserialized with `deserialize_any()`.
+    ///
+    /// This is empty when the probed value did not report any variant names.
+    any_variants: Option<&'static [&'static str]>,
 }
 
 impl Deserializer {
     fn new() -> Deserializer {
+        Self::with_max_depth(DEFAULT_MAX_DEPTH)
+    }
+
+    fn with_max_depth(max_depth: usize) -> Deserializer {
         Deserializer {
             keys: Keys::None,
             recursive_deserializer: None,
+            remaining_depth: max_depth,
+            settings: Settings::default(),
+            probed: false,
+            any_variants: None,
         }
     }
 
-    fn trace_required_primitive<'de, V>(&mut self, visitor: &V) -> Trace
+    fn trace_required_primitive<'de, V>(&mut self, visitor: &V, kind: PrimitiveKind) -> Trace
     where
         V: Visitor<'de>,
     {
-        Trace(Ok(Status::Success(Shape::primitive_from_visitor(visitor))))
+        self.probed = true;
+        Trace(Ok(Status::Success(Shape::primitive_from_visitor(
+            visitor, kind,
+        ))))
     }
 }
 
 macro_rules! deserialize_as_primitive {
-    ($($function:ident,)*) => {
+    ($($function:ident => $kind:ident,)*) => {
         $(
             fn $function<V>(self, visitor: V) -> Result<V::Value, Self::Error>
             where
                 V: Visitor<'de>,
             {
-                Err(self.trace_required_primitive(&visitor))
+                Err(self.trace_required_primitive(&visitor, PrimitiveKind::$kind))
             }
         )*
     }
@@ -179,15 +350,44 @@ macro_rules! deserialize_as_primitive {
 impl<'de> de::Deserializer<'de> for &mut Deserializer {
     type Error = Trace;
 
+    /// The shape must match the one seen during deserialization, which is always human-readable.
+    fn is_human_readable(&self) -> bool {
+        true
+    }
+
     // ---------------
     // Self-describing
     // ---------------
 
-    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    /// Only enums accepting their variants either by name or by numeric code are supported, and are
+    /// traced as opaque values whose validation is deferred to deserialization.
+    ///
+    /// The visitor is probed with a name on one pass and with a number on the next. An enum
+    /// rejects the name as an unknown variant, which reports the names of its variants.
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        Err(Trace(Err(Error::NotSelfDescribing)))
+        self.probed = true;
+        match self.any_variants.take() {
+            None => {
+                self.any_variants = Some(match visitor.visit_str(ANY_PROBE) {
+                    Err(Trace(Err(Error::UnknownVariant(_, variants)))) => variants,
+                    _ => &[],
+                });
+                Err(Trace(Ok(Status::Continue)))
+            }
+            Some([]) => Err(Trace(Err(Error::NotSelfDescribing))),
+            Some(variants) => {
+                let shape = Shape::primitive_from_visitor(&visitor, PrimitiveKind::Any(variants));
+                match visitor.visit_u64(u64::MAX) {
+                    Err(Trace(Err(Error::InvalidType(..)))) => {
+                        Err(Trace(Err(Error::NotSelfDescribing)))
+                    }
+                    _ => Err(Trace(Ok(Status::Success(shape)))),
+                }
+            }
+        }
     }
 
     fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
@@ -205,36 +405,38 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
     where
         V: Visitor<'de>,
     {
+        self.probed = true;
         Err(Trace(Ok(Status::Success(Shape::boolean_from_visitor(
             &visitor,
         )))))
     }
 
     deserialize_as_primitive! {
-        deserialize_i8,
-        deserialize_i16,
-        deserialize_i32,
-        deserialize_i64,
-        deserialize_i128,
-        deserialize_u8,
-        deserialize_u16,
-        deserialize_u32,
-        deserialize_u64,
-        deserialize_u128,
-        deserialize_f32,
-        deserialize_f64,
-        deserialize_char,
-        deserialize_str,
-        deserialize_string,
-        deserialize_bytes,
-        deserialize_byte_buf,
-        deserialize_identifier,
+        deserialize_i8 => I8,
+        deserialize_i16 => I16,
+        deserialize_i32 => I32,
+        deserialize_i64 => I64,
+        deserialize_i128 => I128,
+        deserialize_u8 => U8,
+        deserialize_u16 => U16,
+        deserialize_u32 => U32,
+        deserialize_u64 => U64,
+        deserialize_u128 => U128,
+        deserialize_f32 => F32,
+        deserialize_f64 => F64,
+        deserialize_char => Char,
+        deserialize_str => Str,
+        deserialize_string => Str,
+        deserialize_bytes => Bytes,
+        deserialize_byte_buf => Bytes,
+        deserialize_identifier => Bytes,
     }
 
     fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        self.probed = true;
         Err(Trace(Ok(Status::Success(Shape::empty_from_visitor(
             &visitor,
         )))))
@@ -248,6 +450,7 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
     where
         V: Visitor<'de>,
     {
+        self.probed = true;
         Err(Trace(Ok(Status::Success(Shape::empty_from_visitor(
             &visitor,
         )))))
@@ -262,10 +465,13 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
         V: Visitor<'de>,
     {
         visitor.visit_some(self).map_err(|trace| {
-            Trace(trace.0.map(|status| match status {
-                Status::Continue => Status::Continue,
-                Status::Success(shape) => Status::Success(Shape::Optional(Box::new(shape))),
-            }))
+            within_type(
+                Trace(trace.0.map(|status| match status {
+                    Status::Continue => Status::Continue,
+                    Status::Success(shape) => Status::Success(Shape::Optional(Box::new(shape))),
+                })),
+                "Option",
+            )
         })
     }
 
@@ -277,25 +483,29 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
     where
         V: Visitor<'de>,
     {
-        fn key_description_from_visitor(visitor: &dyn Expected, key: usize) -> String {
-            format!("{:#key$}", visitor)
-        }
-        fn key_version_from_visitor(visitor: &dyn Expected, key: usize) -> String {
-            format!("{:v<key$}", visitor)
-        }
         // Attempt to overwrite descriptions and versions using this visitor.
         match mem::replace(&mut self.keys, Keys::None) {
             Keys::None => {
-                match visitor.visit_newtype_struct(
-                    self.recursive_deserializer
-                        .get_or_insert(Box::new(Deserializer::new()))
-                        .as_mut(),
-                ) {
-                    passthrough @ Ok(_)
-                    | passthrough @ Err(Trace(Err(_)))
-                    | passthrough @ Err(Trace(Ok(Status::Continue))) => passthrough,
+                match visitor.visit_newtype_struct(nested_deserializer(
+                    &mut self.recursive_deserializer,
+                    self.remaining_depth,
+                )?) {
+                    passthrough @ Ok(_) => passthrough,
+                    // Newtypes generated to provide descriptions wrap a type of the same name, so
+                    // they are only named once.
+                    Err(Trace(Err(Error::RecursiveType(names))))
+                        if names.first() == Some(&struct_name) =>
+                    {
+                        Err(Trace(Err(Error::RecursiveType(names))))
+                    }
+                    Err(trace @ Trace(Err(_))) => Err(within_type(trace, struct_name)),
+                    Err(Trace(Ok(Status::Continue))) => {
+                        self.probed = true;
+                        Err(Trace(Ok(Status::Continue)))
+                    }
                     Err(Trace(Ok(Status::Success(shape)))) => {
                         self.keys = Keys::Newtype(shape);
+                        self.probed = true;
                         Err(Trace(Ok(Status::Continue)))
                     }
                 }
@@ -303,14 +513,15 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
             Keys::Newtype(mut shape) => {
                 // Extract descriptions.
                 let container_description = description_from_visitor(&visitor);
-                let container_version = {
-                    let version = version_from_visitor(&visitor);
-                    if version == container_description {
-                        None
-                    } else {
-                        Some(version)
-                    }
-                };
+                let record = settings_from_visitor(&visitor);
+                self.settings = record.settings();
+                let container_version = version_from_visitor(
+                    &visitor,
+                    &record,
+                    None,
+                    [&container_description, &container_description],
+                );
+                let container_examples = record.value(Setting::Examples, None).map(str::to_owned);
                 match &mut shape {
                     Shape::Empty {
                         description,
@@ -327,13 +538,17 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                         name,
                         description,
                         version,
+                        ..
                     }
                     | Shape::Boolean {
                         name,
                         description,
                         version,
                     } => {
-                        *name = struct_name.into();
+                        // Paths checked on the filesystem keep the name of the path itself.
+                        if path::Constraint::from_name(struct_name).is_none() {
+                            *name = struct_name.into();
+                        }
                         if !container_description.is_empty() {
                             *description = container_description;
                         }
@@ -341,14 +556,16 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                             *version = container_version;
                         }
                     }
-                    Shape::Optional(_) => {}
+                    Shape::Optional(_) | Shape::Sequence(_) => {}
                     Shape::Struct {
                         name,
                         description,
                         version,
+                        examples,
                         required,
                         optional,
                         booleans,
+                        constraints,
                     } => {
                         *name = struct_name;
                         if !container_description.is_empty() {
@@ -357,21 +574,215 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                         if container_version.is_some() {
                             *version = container_version;
                         }
+                        if container_examples.is_some() {
+                            *examples = container_examples;
+                        }
+                        let mut positional = Vec::new();
                         for field in required
                             .iter_mut()
                             .chain(optional.iter_mut())
                             .chain(booleans.iter_mut())
                         {
-                            let description = key_description_from_visitor(&visitor, field.index);
+                            let description =
+                                key_description_from_visitor(&visitor, &record, field.index);
+                            // `serde` lists a field's names in sorted order, so the first name
+                            // traced may be an alias rather than the field's actual name.
+                            if let Some(name) = record.value(Setting::Name, Some(field.index)) {
+                                if let Some(position) =
+                                    field.aliases.iter().position(|alias| *alias == name)
+                                {
+                                    let alias =
+                                        mem::replace(&mut field.name, field.aliases[position]);
+                                    field.aliases.remove(position);
+                                    let position = field
+                                        .aliases
+                                        .binary_search(&alias)
+                                        .unwrap_or_else(|position| position);
+                                    field.aliases.insert(position, alias);
+                                }
+                            }
+                            if let Some(value_name) =
+                                record.value(Setting::ValueName, Some(field.index))
+                            {
+                                field.value_name = Some(value_name.to_owned());
+                            }
+                            // Only single ASCII characters are used as delimiters, so that values
+                            // can be split without interpreting them as UTF-8.
+                            if let Some(&[delimiter]) = record
+                                .value(Setting::ValueDelimiter, Some(field.index))
+                                .map(str::as_bytes)
+                            {
+                                if delimiter.is_ascii() {
+                                    field.value_delimiter = Some(delimiter);
+                                }
+                            }
+                            let enabled = |setting| record.enabled(setting, field.index);
+                            field.allow_hyphen_values |= enabled(Setting::AllowHyphenValues);
+                            field.trailing |= enabled(Setting::Trailing);
+                            field.required |= enabled(Setting::Required);
+                            field.flags |= enabled(Setting::Flags);
+                            field.multiple |= enabled(Setting::Multiple);
+                            if enabled(Setting::Positional) {
+                                positional.push(field.index);
+                            }
                             if description != container_description && !description.is_empty() {
                                 field.description = description;
                             }
                         }
+                        // Optional fields parsed as positional arguments are moved into the
+                        // required fields, in declaration order.
+                        let mut index = 0;
+                        while index < optional.len() {
+                            if !positional.contains(&optional[index].index) {
+                                index += 1;
+                                continue;
+                            }
+                            let mut field = optional.remove(index);
+                            if !matches!(field.shape, Shape::Primitive { .. }) {
+                                return Err(Trace(Err(Error::UnsupportedOptionalPositional(
+                                    field.name,
+                                ))));
+                            }
+                            field.shape = Shape::Optional(Box::new(field.shape));
+                            let position = required
+                                .iter()
+                                .position(|required_field| required_field.index > field.index)
+                                .unwrap_or(required.len());
+                            required.insert(position, field);
+                        }
+                        // Value delimiters are only supported on options taking sequences.
+                        if let Some(field) = required
+                            .iter()
+                            .chain(booleans.iter())
+                            .find(|field| field.value_delimiter.is_some())
+                            .or_else(|| {
+                                optional.iter().find(|field| {
+                                    field.value_delimiter.is_some()
+                                        && !matches!(
+                                            &field.shape,
+                                            Shape::Sequence(shape)
+                                                if matches!(**shape, Shape::Primitive { .. })
+                                        )
+                                })
+                            })
+                        {
+                            return Err(Trace(Err(Error::UnsupportedValueDelimiter(field.name))));
+                        }
+                        // Fields capturing the remaining arguments must be the last required
+                        // sequence.
+                        if let Some(field) = optional
+                            .iter()
+                            .chain(booleans.iter())
+                            .find(|field| field.trailing)
+                        {
+                            return Err(Trace(Err(Error::UnsupportedTrailing(field.name))));
+                        }
+                        // Only options taking values can be required to be given.
+                        if let Some(field) = required
+                            .iter()
+                            .chain(booleans.iter())
+                            .find(|field| field.required)
+                        {
+                            return Err(Trace(Err(Error::UnsupportedRequired(field.name))));
+                        }
+                        // Only optional enums of unit variants can be given as flags.
+                        if let Some(field) = required
+                            .iter()
+                            .chain(booleans.iter())
+                            .filter(|field| field.flags)
+                            .chain(optional.iter().filter(|field| {
+                                field.flags
+                                    && !matches!(
+                                        &field.shape,
+                                        Shape::Enum { variants, .. }
+                                            if variants.iter().all(|variant| {
+                                                matches!(variant.shape, Shape::Empty { .. })
+                                            })
+                                    )
+                            }))
+                            .next()
+                        {
+                            return Err(Trace(Err(Error::UnsupportedFlags(field.name))));
+                        }
+                        // Flags are given alongside the struct's other options, so their names
+                        // must not be shared with any of them.
+                        for flags_field in optional.iter().filter(|field| field.flags) {
+                            let other_names: Vec<&str> = optional
+                                .iter()
+                                .chain(booleans.iter())
+                                .filter(|field| field.index != flags_field.index)
+                                .flat_map(Field::option_names)
+                                .collect();
+                            if let Some(flag) = flags_field
+                                .option_names()
+                                .into_iter()
+                                .find(|flag| other_names.contains(flag))
+                            {
+                                return Err(Trace(Err(Error::ConflictingFlagName(
+                                    flags_field.name,
+                                    flag,
+                                ))));
+                            }
+                        }
+                        // Only options taking sequences can take multiple values per occurrence.
+                        if let Some(field) = required
+                            .iter()
+                            .chain(booleans.iter())
+                            .filter(|field| field.multiple)
+                            .chain(optional.iter().filter(|field| {
+                                field.multiple
+                                    && (field.value_delimiter.is_some()
+                                        || !matches!(
+                                            &field.shape,
+                                            Shape::Sequence(shape)
+                                                if matches!(**shape, Shape::Primitive { .. })
+                                        ))
+                            }))
+                            .next()
+                        {
+                            return Err(Trace(Err(Error::UnsupportedMultiple(field.name))));
+                        }
+                        for (index, field) in required.iter().enumerate() {
+                            if !field.trailing {
+                                continue;
+                            }
+                            if !matches!(field.shape, Shape::Sequence(_)) {
+                                return Err(Trace(Err(Error::UnsupportedTrailing(field.name))));
+                            }
+                            if required[index + 1..]
+                                .iter()
+                                .any(|field| !matches!(field.shape, Shape::Empty { .. }))
+                            {
+                                return Err(Trace(Err(Error::TrailingNotLast(field.name))));
+                            }
+                        }
+                        // Constraints can only apply to fields given as options.
+                        for (kind, indices) in record.constraints() {
+                            let mut fields = Vec::with_capacity(indices.len());
+                            for index in indices {
+                                if let Some(field) =
+                                    required.iter().find(|field| field.index == index)
+                                {
+                                    return Err(Trace(Err(Error::UnsupportedConstraint(
+                                        field.name,
+                                    ))));
+                                }
+                                if let Some(field) = optional
+                                    .iter()
+                                    .chain(booleans.iter())
+                                    .find(|field| field.index == index)
+                                {
+                                    fields.push(field.name);
+                                }
+                            }
+                            constraints.push(Constraint { kind, fields });
+                        }
                     }
                     Shape::Enum {
                         name,
                         description,
                         version,
+                        examples,
                         variants,
                     } => {
                         *name = struct_name;
@@ -381,45 +792,116 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                         if container_version.is_some() {
                             *version = container_version;
                         }
+                        if container_examples.is_some() {
+                            *examples = container_examples;
+                        }
                         for (index, variant) in variants.iter_mut().enumerate() {
-                            let description = key_description_from_visitor(&visitor, index);
-                            let version = {
-                                let version = key_version_from_visitor(&visitor, index);
-                                if version == description || version == container_description {
-                                    None
-                                } else {
-                                    Some(version)
-                                }
-                            };
+                            let description =
+                                key_description_from_visitor(&visitor, &record, index);
+                            let version = version_from_visitor(
+                                &visitor,
+                                &record,
+                                Some(index),
+                                [&description, &container_description],
+                            );
+                            let group =
+                                record.value(Setting::Group, Some(index)).map(str::to_owned);
+                            let raw = record.enabled(Setting::Raw, index);
                             if description != container_description && !description.is_empty() {
                                 variant.description = description;
                             }
                             if version.is_some() {
                                 variant.version = version;
                             }
+                            if group.is_some() {
+                                variant.group = group;
+                            }
+                            if raw {
+                                // Options could never be given within a raw variant.
+                                if variant.shape.has_options() {
+                                    return Err(Trace(Err(Error::UnsupportedRaw(variant.name))));
+                                }
+                                variant.raw = true;
+                                // The values of a raw sequence are whatever arguments follow the
+                                // variant's name, regardless of their type.
+                                if let Shape::Sequence(element) = &mut variant.shape {
+                                    if let Shape::Primitive { name, .. } = element.as_mut() {
+                                        *name = "args".to_owned();
+                                    }
+                                }
+                            }
                         }
                     }
                     Shape::Variant { .. } => unreachable!(),
                 }
 
+                self.probed = true;
                 Err(Trace(Ok(Status::Success(shape))))
             }
-            Keys::Fields(_) | Keys::Variants(_) => unimplemented!(),
+            Keys::Fields(_) | Keys::Variants(_) | Keys::Elements(_) => unimplemented!(),
         }
     }
 
-    fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        visitor
+            .visit_seq(SeqAccess { deserializer: self })
+            .map_err(|trace| {
+                Trace(trace.0.and_then(|status| match status {
+                    Status::Continue => Ok(Status::Continue),
+                    // Each element is parsed from a single argument, so only primitive elements
+                    // can be collected.
+                    Status::Success(shape @ (Shape::Primitive { .. } | Shape::Boolean { .. })) => {
+                        Ok(Status::Success(Shape::Sequence(Box::new(shape))))
+                    }
+                    Status::Success(_) => Err(Error::UnsupportedSequenceElement),
+                }))
+            })
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        if len > ELEMENT_NAMES.len() {
+            return Err(Trace(Err(Error::UnsupportedTupleLength(len))));
+        }
+        let description = description_from_visitor(&visitor);
+        if let Keys::None = self.keys {
+            self.keys = Keys::Elements(Vec::new());
+        }
+        let elements = match &mut self.keys {
+            Keys::Elements(elements) => elements,
+            _ => return Err(Trace(Err(Error::UnsupportedTupleElement))),
+        };
+        // Each pass traces one more element. Since the elements are visited in order, the elements
+        // that were already traced are given placeholder values to reach it.
+        let result = visitor.visit_seq(ElementAccess {
+            elements,
+            position: 0,
+        });
+        self.probed = true;
+        match result {
+            Ok(_) if elements.len() == len => Err(Trace(Ok(Status::Success(Shape::Struct {
+                name: "",
+                description,
+                version: None,
+                examples: None,
+                required: mem::take(elements)
+                    .into_iter()
+                    .zip(ELEMENT_NAMES)
+                    .enumerate()
+                    .map(|(index, ((shape, _), name))| Field::new(name, shape, index))
+                    .collect(),
+                optional: Vec::new(),
+                booleans: Vec::new(),
+                constraints: Vec::new(),
+            })))),
+            Ok(_) => Err(Trace(Err(Error::UnsupportedType(None)))),
+            Err(trace) => Err(trace),
+        }
     }
 
     fn deserialize_tuple_struct<V>(
@@ -450,27 +932,9 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
     where
         V: Visitor<'de>,
     {
-        let description = description_from_visitor(&visitor);
-        let version = {
-            let version = version_from_visitor(&visitor);
-            if version == description {
-                None
-            } else {
-                Some(version)
-            }
-        };
         let fields = self
             .keys
-            .get_fields_or_insert(Fields {
-                name,
-                description,
-                version,
-                iter: fields.iter(),
-                revisit: None,
-                required_fields: Vec::new(),
-                optional_fields: Vec::new(),
-                boolean_fields: Vec::new(),
-            })
+            .get_fields_or_insert(Fields::new(name, fields, &visitor))
             .map_err(|error| Trace(Err(error)))?;
         if let Some(field) = fields
             .revisit
@@ -478,12 +942,10 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
             .or_else(|| fields.iter.next().copied())
         {
             // Obtain description for the possible next variant.
-            fn field_description_from_visitor(visitor: &dyn Expected, field: usize) -> String {
-                format!("{:#field$}", visitor)
-            }
             let description = {
-                let description = field_description_from_visitor(
+                let description = key_description_from_visitor(
                     &visitor,
+                    &settings_from_visitor(&visitor),
                     fields.required_fields.len() + fields.optional_fields.len(),
                 );
                 if description == fields.description {
@@ -497,6 +959,7 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                 field,
                 discriminant: &mut discriminant,
                 recursive_deserializer: &mut self.recursive_deserializer,
+                remaining_depth: self.remaining_depth,
             };
             match visitor.visit_map(&mut struct_access) {
                 Ok(value) => Ok(value),
@@ -504,105 +967,21 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                     Ok(status) => {
                         match status {
                             Status::Success(shape) => {
-                                match shape {
-                                    Shape::Optional(shape) => {
-                                        // Optional fields.
-                                        let key_info = KeyInfo {
-                                            discriminant,
-                                            shape: *shape,
-                                        };
-                                        let mut found = false;
-                                        for (info, names, _, _) in fields.optional_fields.iter_mut()
-                                        {
-                                            if *info == key_info {
-                                                found = true;
-                                                names.push(field);
-                                                break;
-                                            }
-                                        }
-                                        if !found {
-                                            fields.optional_fields.push((
-                                                key_info,
-                                                vec![field],
-                                                description,
-                                                fields.required_fields.len()
-                                                    + fields.optional_fields.len()
-                                                    + fields.boolean_fields.len(),
-                                            ));
-                                        }
-                                    }
-                                    Shape::Boolean {
-                                        description: bool_description,
-                                        version,
-                                        ..
-                                    } => {
-                                        // Boolean fields.
-                                        let key_info = KeyInfo {
-                                            discriminant,
-                                            shape: Shape::Empty {
-                                                description: bool_description,
-                                                version,
-                                            },
-                                        };
-                                        let mut found = false;
-                                        for (info, names, _, _) in fields.boolean_fields.iter_mut()
-                                        {
-                                            if *info == key_info {
-                                                found = true;
-                                                names.push(field);
-                                                break;
-                                            }
-                                        }
-                                        if !found {
-                                            fields.boolean_fields.push((
-                                                key_info,
-                                                vec![field],
-                                                description,
-                                                fields.required_fields.len()
-                                                    + fields.optional_fields.len()
-                                                    + fields.boolean_fields.len(),
-                                            ))
-                                        }
-                                    }
-                                    shape => {
-                                        // Required fields.
-                                        let key_info = KeyInfo {
-                                            discriminant,
-                                            shape,
-                                        };
-                                        let mut found = false;
-                                        for (info, names, _, _) in fields.required_fields.iter_mut()
-                                        {
-                                            if *info == key_info {
-                                                found = true;
-                                                names.push(field);
-                                                break;
-                                            }
-                                        }
-                                        if !found {
-                                            fields.required_fields.push((
-                                                key_info,
-                                                vec![field],
-                                                description,
-                                                fields.required_fields.len()
-                                                    + fields.optional_fields.len()
-                                                    + fields.boolean_fields.len(),
-                                            ));
-                                        }
-                                    }
-                                }
+                                fields.record(field, discriminant, description, shape);
                                 self.recursive_deserializer = None;
                             }
                             Status::Continue => {
                                 fields.revisit = Some(field);
                             }
                         }
+                        self.probed = true;
                         Err(Trace(Ok(Status::Continue)))
                     }
-                    Err(_) => Err(trace),
+                    Err(_) => Err(within_type(trace, name)),
                 },
             }
         } else {
+            self.probed = true;
             Err(Trace(Ok(Status::Success(
                 mem::replace(&mut self.keys, Keys::None).into(),
             ))))
@@ -628,35 +1007,32 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
             .or_else(|| variants.iter.next().copied())
         {
             // Obtain description and version for the possible next variant.
-            fn variant_description_from_visitor(visitor: &dyn Expected, variant: usize) -> String {
-                format!("{:#variant$}", visitor)
-            }
-            fn variant_version_from_visitor(visitor: &dyn Expected, variant: usize) -> String {
-                format!("{:v<variant$}", visitor)
-            }
+            let record = settings_from_visitor(&visitor);
             let description = {
                 let description =
-                    variant_description_from_visitor(&visitor, variants.variants.len());
+                    key_description_from_visitor(&visitor, &record, variants.variants.len());
                 if description == variants.description {
                     String::new()
                 } else {
                     description
                 }
             };
-            let version = {
-                let version = variant_version_from_visitor(&visitor, variants.variants.len());
-                if version == variants.description || version == description {
-                    None
-                } else {
-                    Some(version)
-                }
-            };
+            let version = version_from_visitor(
+                &visitor,
+                &record,
+                Some(variants.variants.len()),
+                [&description, &variants.description],
+            );
+            let group = record
+                .value(Setting::Group, Some(variants.variants.len()))
+                .map(str::to_owned);
             // Process the current variant.
             let mut discriminant = 0;
             let mut enum_access = EnumAccess {
                 variant,
                 discriminant: &mut discriminant,
                 recursive_deserializer: &mut self.recursive_deserializer,
+                remaining_depth: self.remaining_depth,
             };
             match visitor.visit_enum(&mut enum_access) {
                 Ok(value) => Ok(value),
@@ -669,7 +1045,7 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                                     shape,
                                 };
                                 let mut found = false;
-                                for (info, names, _description, _version) in
+                                for (info, names, _description, _version, _group) in
                                     variants.variants.iter_mut()
                                 {
                                     if info.variant_equality(&key_info) {
@@ -684,6 +1060,7 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                                         vec![variant],
                                         description,
                                         version,
+                                        group,
                                     ));
                                 }
                                 self.recursive_deserializer = None;
@@ -692,13 +1069,15 @@ impl<'de> de::Deserializer<'de> for &mut Deserializer {
                                 variants.revisit = Some(variant);
                             }
                         }
+                        self.probed = true;
                         Err(Trace(Ok(Status::Continue)))
                     }
-                    Err(_) => Err(trace),
+                    Err(_) => Err(within_type(trace, name)),
                 },
             }
         } else {
             // No more variants to process.
+            self.probed = true;
             Err(Trace(Ok(Status::Success(
                 mem::replace(&mut self.keys, Keys::None).into(),
             ))))
@@ -718,6 +1097,7 @@ struct StructAccess<'a> {
     field: &'static str,
     discriminant: &'a mut u64,
     recursive_deserializer: &'a mut Option<Box<Deserializer>>,
+    remaining_depth: usize,
 }
 
 impl<'de> MapAccess<'de> for StructAccess<'_> {
@@ -741,11 +1121,150 @@ impl<'de> MapAccess<'de> for StructAccess<'_> {
         // We can only hit one field at a time here, so we have to use the recursive deserializer.
         // This is because seed values are not guaranteed to implement `Copy` or `Clone`, and
         // therefore cannot be reused.
-        seed.deserialize(
-            self.recursive_deserializer
-                .get_or_insert(Box::new(Deserializer::new()))
-                .as_mut(),
-        )
+        let deserializer = nested_deserializer(self.recursive_deserializer, self.remaining_depth)?;
+        deserializer.probed = false;
+        let result = seed.deserialize(&mut *deserializer);
+        propagated(deserializer, Some(self.field), result)
+    }
+}
+
+struct SeqAccess<'a> {
+    deserializer: &'a mut Deserializer,
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_> {
+    type Error = Trace;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.deserializer).map(Some)
+    }
+}
+
+/// The names of the elements of tuples and arrays, displayed as their positional arguments.
+const ELEMENT_NAMES: [&str; 32] = [
+    "arg1", "arg2", "arg3", "arg4", "arg5", "arg6", "arg7", "arg8", "arg9", "arg10", "arg11",
+    "arg12", "arg13", "arg14", "arg15", "arg16", "arg17", "arg18", "arg19", "arg20", "arg21",
+    "arg22", "arg23", "arg24", "arg25", "arg26", "arg27", "arg28", "arg29", "arg30", "arg31",
+    "arg32",
+];
+
+/// Placeholder values given to string elements, tried in order until one is accepted.
+///
+/// These cover the formats of common types parsed from strings, such as IP addresses.
+/// The name given when probing a value deserialized with `deserialize_any()`.
+///
+/// This is not expected to be the name of any variant.
+const ANY_PROBE: &str = "\u{0}";
+
+const STRING_PLACEHOLDERS: [&str; 6] = ["", "0", "0.0.0.0", "::", "0.0.0.0:0", "[::]:0"];
+
+/// Traces the elements of a tuple or array, one element per pass.
+struct ElementAccess<'a> {
+    /// The shapes of the elements traced in previous passes, along with the index of the
+    /// placeholder value each is given.
+    elements: &'a mut Vec<(Shape, usize)>,
+    position: usize,
+}
+
+impl<'de> de::SeqAccess<'de> for ElementAccess<'_> {
+    type Error = Trace;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.position += 1;
+        if let Some((shape, placeholder)) = self.elements.get_mut(self.position - 1) {
+            return match seed.deserialize(Placeholder {
+                shape,
+                index: *placeholder,
+            }) {
+                Ok(value) => Ok(Some(value)),
+                // Try the next placeholder value on the next pass.
+                Err(_) if *placeholder + 1 < Placeholder::count(shape) => {
+                    *placeholder += 1;
+                    Err(Trace(Ok(Status::Continue)))
+                }
+                Err(_) => Err(Trace(Err(Error::UntraceableTupleElement(self.position)))),
+            };
+        }
+        let mut deserializer = Deserializer::new();
+        let result = seed.deserialize(&mut deserializer);
+        match propagated(&deserializer, None, result) {
+            Err(Trace(Ok(Status::Success(
+                shape @ (Shape::Primitive { .. } | Shape::Boolean { .. }),
+            )))) => {
+                self.elements.push((shape, 0));
+                Err(Trace(Ok(Status::Continue)))
+            }
+            Err(Trace(Ok(_))) => Err(Trace(Err(Error::UnsupportedTupleElement))),
+            Err(trace) => Err(trace),
+            Ok(_) => unreachable!(),
+        }
+    }
+}
+
+/// Provides a placeholder value for an element whose shape has already been traced.
+///
+/// Each element consumes exactly one argument, so this only ever needs to provide primitive
+/// values.
+struct Placeholder<'a> {
+    shape: &'a Shape,
+    /// Which of the placeholder values for the shape is given.
+    index: usize,
+}
+
+impl Placeholder<'_> {
+    /// Returns the number of placeholder values available for `shape`.
+    fn count(shape: &Shape) -> usize {
+        match shape {
+            Shape::Primitive {
+                kind: PrimitiveKind::Str | PrimitiveKind::Any(_),
+                ..
+            } => STRING_PLACEHOLDERS.len(),
+            _ => 1,
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for Placeholder<'_> {
+    type Error = Trace;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.shape {
+            Shape::Boolean { .. } => visitor.visit_bool(false),
+            Shape::Primitive { kind, .. } => match kind {
+                PrimitiveKind::I8
+                | PrimitiveKind::I16
+                | PrimitiveKind::I32
+                | PrimitiveKind::I64
+                | PrimitiveKind::I128
+                | PrimitiveKind::U8
+                | PrimitiveKind::U16
+                | PrimitiveKind::U32
+                | PrimitiveKind::U64
+                | PrimitiveKind::U128 => visitor.visit_u8(1),
+                PrimitiveKind::F32 | PrimitiveKind::F64 => visitor.visit_f64(0.0),
+                PrimitiveKind::Char => visitor.visit_char('a'),
+                PrimitiveKind::Str | PrimitiveKind::Any(_) => {
+                    visitor.visit_str(STRING_PLACEHOLDERS[self.index])
+                }
+                PrimitiveKind::Bytes => visitor.visit_bytes(&[]),
+            },
+            _ => unreachable!(),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
     }
 }
 
@@ -753,6 +1272,7 @@ struct EnumAccess<'a> {
     variant: &'static str,
     discriminant: &'a mut u64,
     recursive_deserializer: &'a mut Option<Box<Deserializer>>,
+    remaining_depth: usize,
 }
 
 impl<'a, 'de> de::EnumAccess<'de> for &'a mut EnumAccess<'_> {
@@ -772,6 +1292,7 @@ impl<'a, 'de> de::EnumAccess<'de> for &'a mut EnumAccess<'_> {
             VariantAccess {
                 name: self.variant,
                 recursive_deserializer: self.recursive_deserializer,
+                remaining_depth: self.remaining_depth,
             },
         ))
     }
@@ -781,6 +1302,7 @@ impl<'a, 'de> de::EnumAccess<'de> for &'a mut EnumAccess<'_> {
 struct VariantAccess<'a> {
     name: &'static str,
     recursive_deserializer: &'a mut Option<Box<Deserializer>>,
+    remaining_depth: usize,
 }
 
 impl<'de> de::VariantAccess<'de> for VariantAccess<'_> {
@@ -797,11 +1319,10 @@ impl<'de> de::VariantAccess<'de> for VariantAccess<'_> {
     where
         T: DeserializeSeed<'de>,
     {
-        seed.deserialize(
-            self.recursive_deserializer
-                .get_or_insert(Box::new(Deserializer::new()))
-                .as_mut(),
-        )
+        let deserializer = nested_deserializer(self.recursive_deserializer, self.remaining_depth)?;
+        deserializer.probed = false;
+        let result = seed.deserialize(&mut *deserializer);
+        propagated(deserializer, Some(self.name), result)
     }
 
     fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
@@ -819,8 +1340,7 @@ impl<'de> de::VariantAccess<'de> for VariantAccess<'_> {
     where
         V: Visitor<'de>,
     {
-        self.recursive_deserializer
-            .get_or_insert(Box::new(Deserializer::new()))
+        nested_deserializer(self.recursive_deserializer, self.remaining_depth)?
             .deserialize_struct(self.name, fields, visitor)
     }
 }
@@ -829,16 +1349,24 @@ impl<'de> de::VariantAccess<'de> for VariantAccess<'_> {
 mod tests {
     use super::{
         trace,
+        trace_with_max_depth,
+        trace_with_settings,
+        Constraint,
+        ConstraintKind,
         Deserializer,
         EnumAccess,
         Error,
         Field,
+        PrimitiveKind,
+        Settings,
         Shape,
         Status,
         StructAccess,
         Trace,
         Variant,
         VariantAccess,
+        DEFAULT_MAX_DEPTH,
+        STRING_PLACEHOLDERS,
     };
     use crate::key::DeserializerError;
     use claims::{
@@ -863,11 +1391,21 @@ mod tests {
     };
     use serde_derive::Deserialize;
     use std::{
+        collections::HashSet,
         fmt,
         fmt::Formatter,
         marker::PhantomData,
     };
 
+    /// Writes a settings record made of `entries`, as `expecting()` does when `formatter.fill()` is
+    /// `'s'`.
+    fn write_record(formatter: &mut Formatter, entries: &[&str]) -> fmt::Result {
+        for entry in entries {
+            write!(formatter, "{}:{}", entry.len(), entry)?;
+        }
+        Ok(())
+    }
+
     #[test]
     fn status_display_success() {
         assert_eq!(
@@ -903,7 +1441,7 @@ mod tests {
 
     #[test]
     fn trace_display_error() {
-        assert_eq!(format!("{}", Trace(Err(Error::NotSelfDescribing))), "error: cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` or `Deserializer::deserialize_ignored_any()` is not allowed");
+        assert_eq!(format!("{}", Trace(Err(Error::NotSelfDescribing))), "error: cannot deserialize as self-describing; use of `Deserializer::deserialize_any()` is only allowed for enums accepting their variants either by name or by numeric code, and use of `Deserializer::deserialize_ignored_any()` is not allowed");
     }
 
     #[test]
@@ -975,17 +1513,94 @@ mod tests {
         let mut deserializer = Deserializer::new();
 
         assert_ok_eq!(
-            deserializer.trace_required_primitive(&IgnoredAny).0,
+            deserializer
+                .trace_required_primitive(&IgnoredAny, PrimitiveKind::U64)
+                .0,
             Status::Success(Shape::Primitive {
                 name: "anything at all".to_owned(),
                 description: "anything at all".to_owned(),
                 version: None,
+                kind: PrimitiveKind::U64,
             })
         );
     }
 
+    #[test]
+    fn deserializer_is_human_readable() {
+        assert!(de::Deserializer::is_human_readable(
+            &&mut Deserializer::new()
+        ));
+    }
+
+    /// An enum accepting its variants either by name or by numeric code.
+    #[derive(Debug)]
+    enum Mode {
+        Fast,
+        Slow,
+    }
+
+    impl<'de> Deserialize<'de> for Mode {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            struct ModeVisitor;
+
+            impl Visitor<'_> for ModeVisitor {
+                type Value = Mode;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    formatter.write_str("a mode name or code")
+                }
+
+                fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        0 => Ok(Mode::Fast),
+                        1 => Ok(Mode::Slow),
+                        _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+                    }
+                }
+
+                fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                where
+                    E: de::Error,
+                {
+                    match value {
+                        "fast" => Ok(Mode::Fast),
+                        "slow" => Ok(Mode::Slow),
+                        _ => Err(E::unknown_variant(value, &["fast", "slow"])),
+                    }
+                }
+            }
+
+            deserializer.deserialize_any(ModeVisitor)
+        }
+    }
+
     #[test]
     fn deserializer_any() {
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(Mode::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        assert_ok_eq!(
+            assert_err!(Mode::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Primitive {
+                name: "a mode name or code".to_owned(),
+                description: "a mode name or code".to_owned(),
+                version: None,
+                kind: PrimitiveKind::Any(&["fast", "slow"]),
+            })
+        );
+    }
+
+    #[test]
+    fn deserializer_any_not_enum() {
         #[derive(Debug)]
         struct Any;
 
@@ -999,8 +1614,22 @@ mod tests {
                 impl Visitor<'_> for AnyVisitor {
                     type Value = Any;
 
-                    fn expecting(&self, _formatter: &mut Formatter) -> fmt::Result {
-                        unimplemented!()
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str("anything")
+                    }
+
+                    fn visit_str<E>(self, _value: &str) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        Ok(Any)
+                    }
+
+                    fn visit_u64<E>(self, _value: u64) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        Ok(Any)
                     }
                 }
 
@@ -1010,12 +1639,72 @@ mod tests {
 
         let mut deserializer = Deserializer::new();
 
+        assert_ok_eq!(
+            assert_err!(Any::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
         assert_err_eq!(
             assert_err!(Any::deserialize(&mut deserializer)).0,
             Error::NotSelfDescribing,
         );
     }
 
+    #[test]
+    fn deserializer_any_names_only() {
+        #[derive(Debug)]
+        struct Name;
+
+        impl<'de> Deserialize<'de> for Name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NameVisitor;
+
+                impl Visitor<'_> for NameVisitor {
+                    type Value = Name;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str("a name")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        Err(E::unknown_variant(value, &["foo"]))
+                    }
+                }
+
+                deserializer.deserialize_any(NameVisitor)
+            }
+        }
+
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(Name::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        assert_err_eq!(
+            assert_err!(Name::deserialize(&mut deserializer)).0,
+            Error::NotSelfDescribing,
+        );
+    }
+
+    #[test]
+    fn trace_any() {
+        assert_ok_eq!(
+            trace(PhantomData::<Mode>),
+            Shape::Primitive {
+                name: "a mode name or code".to_owned(),
+                description: "a mode name or code".to_owned(),
+                version: None,
+                kind: PrimitiveKind::Any(&["fast", "slow"]),
+            }
+        );
+    }
+
     #[test]
     fn deserializer_ignored_any() {
         let mut deserializer = Deserializer::new();
@@ -1050,6 +1739,7 @@ mod tests {
                 name: "i8".to_owned(),
                 description: "i8".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I8,
             })
         );
     }
@@ -1064,6 +1754,7 @@ mod tests {
                 name: "i16".to_owned(),
                 description: "i16".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I16,
             })
         );
     }
@@ -1078,6 +1769,7 @@ mod tests {
                 name: "i32".to_owned(),
                 description: "i32".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I32,
             })
         );
     }
@@ -1092,6 +1784,7 @@ mod tests {
                 name: "i64".to_owned(),
                 description: "i64".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I64,
             })
         );
     }
@@ -1106,6 +1799,7 @@ mod tests {
                 name: "i128".to_owned(),
                 description: "i128".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I128,
             })
         );
     }
@@ -1120,6 +1814,7 @@ mod tests {
                 name: "u8".to_owned(),
                 description: "u8".to_owned(),
                 version: None,
+                kind: PrimitiveKind::U8,
             })
         );
     }
@@ -1134,6 +1829,7 @@ mod tests {
                 name: "u16".to_owned(),
                 description: "u16".to_owned(),
                 version: None,
+                kind: PrimitiveKind::U16,
             })
         );
     }
@@ -1148,6 +1844,7 @@ mod tests {
                 name: "u32".to_owned(),
                 description: "u32".to_owned(),
                 version: None,
+                kind: PrimitiveKind::U32,
             })
         );
     }
@@ -1162,6 +1859,7 @@ mod tests {
                 name: "u64".to_owned(),
                 description: "u64".to_owned(),
                 version: None,
+                kind: PrimitiveKind::U64,
             })
         );
     }
@@ -1176,6 +1874,7 @@ mod tests {
                 name: "u128".to_owned(),
                 description: "u128".to_owned(),
                 version: None,
+                kind: PrimitiveKind::U128,
             })
         );
     }
@@ -1190,6 +1889,7 @@ mod tests {
                 name: "f32".to_owned(),
                 description: "f32".to_owned(),
                 version: None,
+                kind: PrimitiveKind::F32,
             })
         );
     }
@@ -1204,6 +1904,7 @@ mod tests {
                 name: "f64".to_owned(),
                 description: "f64".to_owned(),
                 version: None,
+                kind: PrimitiveKind::F64,
             })
         );
     }
@@ -1218,6 +1919,7 @@ mod tests {
                 name: "a character".to_owned(),
                 description: "a character".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Char,
             })
         );
     }
@@ -1232,6 +1934,7 @@ mod tests {
                 name: "a borrowed string".to_owned(),
                 description: "a borrowed string".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Str,
             })
         );
     }
@@ -1246,6 +1949,7 @@ mod tests {
                 name: "a string".to_owned(),
                 description: "a string".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Str,
             })
         );
     }
@@ -1282,6 +1986,7 @@ mod tests {
                 name: "bytes".to_owned(),
                 description: "bytes".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Bytes,
             })
         );
     }
@@ -1318,6 +2023,7 @@ mod tests {
                 name: "byte buf".to_owned(),
                 description: "byte buf".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Bytes,
             })
         );
     }
@@ -1354,6 +2060,7 @@ mod tests {
                 name: "identifier".to_owned(),
                 description: "identifier".to_owned(),
                 version: None,
+                kind: PrimitiveKind::Bytes,
             })
         );
     }
@@ -1397,12 +2104,252 @@ mod tests {
                 name: "i32".to_owned(),
                 description: "i32".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I32,
             })))
         );
     }
 
     #[test]
-    fn deserializer_newtype_struct() {
+    fn deserializer_seq() {
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(Vec::<i32>::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Sequence(Box::new(Shape::Primitive {
+                name: "i32".to_owned(),
+                description: "i32".to_owned(),
+                version: None,
+                kind: PrimitiveKind::I32,
+            })))
+        );
+    }
+
+    #[test]
+    fn deserializer_set() {
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(HashSet::<String>::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Sequence(Box::new(Shape::Primitive {
+                name: "a string".to_owned(),
+                description: "a string".to_owned(),
+                version: None,
+                kind: PrimitiveKind::Str,
+            })))
+        );
+    }
+
+    #[test]
+    fn deserializer_seq_unsupported_element() {
+        let mut deserializer = Deserializer::new();
+
+        assert_err_eq!(
+            assert_err!(Vec::<()>::deserialize(&mut deserializer)).0,
+            Error::UnsupportedSequenceElement
+        );
+    }
+
+    #[test]
+    fn deserializer_tuple() {
+        let mut deserializer = Deserializer::new();
+
+        // Each element is traced on its own pass.
+        assert_ok_eq!(
+            assert_err!(<(String, u32)>::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        assert_ok_eq!(
+            assert_err!(<(String, u32)>::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+
+        assert_ok_eq!(
+            assert_err!(<(String, u32)>::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Struct {
+                name: "",
+                description: "a tuple of size 2".into(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field::new(
+                        "arg1",
+                        Shape::Primitive {
+                            name: "a string".to_owned(),
+                            description: "a string".to_owned(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    ),
+                    Field::new(
+                        "arg2",
+                        Shape::Primitive {
+                            name: "u32".to_owned(),
+                            description: "u32".to_owned(),
+                            version: None,
+                            kind: PrimitiveKind::U32,
+                        },
+                        1
+                    ),
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn deserializer_array() {
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(<[bool; 2]>::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        assert_ok_eq!(
+            assert_err!(<[bool; 2]>::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+
+        assert_ok_eq!(
+            assert_err!(<[bool; 2]>::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Struct {
+                name: "",
+                description: "an array of length 2".into(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field::new(
+                        "arg1",
+                        Shape::Boolean {
+                            name: "a boolean".to_owned(),
+                            description: "a boolean".to_owned(),
+                            version: None,
+                        },
+                        0
+                    ),
+                    Field::new(
+                        "arg2",
+                        Shape::Boolean {
+                            name: "a boolean".to_owned(),
+                            description: "a boolean".to_owned(),
+                            version: None,
+                        },
+                        1
+                    ),
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn deserializer_tuple_unsupported_element() {
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(<(u8, ())>::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        assert_err_eq!(
+            assert_err!(<(u8, ())>::deserialize(&mut deserializer)).0,
+            Error::UnsupportedTupleElement
+        );
+    }
+
+    #[test]
+    fn deserializer_tuple_untraceable_element() {
+        #[derive(Debug)]
+        struct Exact;
+
+        impl<'de> Deserialize<'de> for Exact {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                if String::deserialize(deserializer)? == "exact" {
+                    Ok(Exact)
+                } else {
+                    Err(de::Error::custom("not exact"))
+                }
+            }
+        }
+
+        let mut deserializer = Deserializer::new();
+
+        // Every placeholder value is tried before giving up.
+        for _ in 0..STRING_PLACEHOLDERS.len() {
+            assert_ok_eq!(
+                assert_err!(<(Exact, u8)>::deserialize(&mut deserializer)).0,
+                Status::Continue
+            );
+        }
+        assert_err_eq!(
+            assert_err!(<(Exact, u8)>::deserialize(&mut deserializer)).0,
+            Error::UntraceableTupleElement(1)
+        );
+    }
+
+    #[test]
+    fn deserializer_enum_containing_seq() {
+        let mut deserializer = Deserializer::new();
+
+        // Obtain information about both variants.
+        assert_ok_eq!(
+            assert_err!(Result::<Vec<String>, ()>::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        assert_ok_eq!(
+            assert_err!(Result::<Vec<String>, ()>::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+
+        assert_ok_eq!(
+            assert_err!(Result::<Vec<String>, ()>::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Enum {
+                name: "Result",
+                description: "enum Result".into(),
+                version: None,
+                examples: None,
+                variants: vec![
+                    Variant {
+                        name: "Ok",
+                        description: "".into(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Sequence(Box::new(Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        })),
+                    },
+                    Variant {
+                        name: "Err",
+                        description: "".into(),
+                        version: None,
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Empty {
+                            description: "unit".into(),
+                            version: None,
+                        },
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn deserializer_newtype_struct() {
         #[derive(Debug, Deserialize)]
         #[allow(dead_code)] // Internal type is needed for its `Visitor`.
         struct Newtype(i32);
@@ -1421,6 +2368,7 @@ mod tests {
                 name: "Newtype".to_owned(),
                 description: "tuple struct Newtype".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I32,
             })
         );
     }
@@ -1452,32 +2400,32 @@ mod tests {
                 name: "Struct",
                 description: "struct Struct".into(),
                 version: None,
+                examples: None,
                 required: vec![
-                    Field {
-                        name: "foo",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "foo",
+                        Shape::Primitive {
                             name: "usize".to_owned(),
                             description: "usize".to_owned(),
                             version: None,
+                            kind: PrimitiveKind::U64,
                         },
-                        index: 0,
-                    },
-                    Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                        0
+                    ),
+                    Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "a string".to_owned(),
                             description: "a string".to_owned(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -1514,9 +2462,11 @@ mod tests {
                 name: "Struct",
                 description: "empty struct".into(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -1563,32 +2513,38 @@ mod tests {
                 name: "Struct",
                 description: "struct Struct".into(),
                 version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "f",
-                        description: String::new(),
                         aliases: vec!["foo"],
-                        shape: Shape::Primitive {
-                            name: "usize".to_owned(),
-                            description: "usize".to_owned(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "f",
+                            Shape::Primitive {
+                                name: "usize".to_owned(),
+                                description: "usize".to_owned(),
+                                version: None,
+                                kind: PrimitiveKind::U64,
+                            },
+                            0
+                        )
                     },
                     Field {
-                        name: "b",
-                        description: String::new(),
                         aliases: vec!["bar", "baz"],
-                        shape: Shape::Primitive {
-                            name: "a string".to_owned(),
-                            description: "a string".to_owned(),
-                            version: None,
-                        },
-                        index: 1,
+                        ..Field::new(
+                            "b",
+                            Shape::Primitive {
+                                name: "a string".to_owned(),
+                                description: "a string".to_owned(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
                     },
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -1620,29 +2576,29 @@ mod tests {
                 name: "Struct",
                 description: "struct Struct".into(),
                 version: None,
-                required: vec![Field {
-                    name: "bar",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                examples: None,
+                required: vec![Field::new(
+                    "bar",
+                    Shape::Primitive {
                         name: "a string".to_owned(),
                         description: "a string".to_owned(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 1,
-                },],
-                optional: vec![Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                    1
+                ),],
+                optional: vec![Field::new(
+                    "foo",
+                    Shape::Primitive {
                         name: "usize".to_owned(),
                         description: "usize".to_owned(),
                         version: None,
+                        kind: PrimitiveKind::U64,
                     },
-                    index: 0,
-                },],
+                    0
+                ),],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -1674,28 +2630,27 @@ mod tests {
                 name: "Struct",
                 description: "struct Struct".into(),
                 version: None,
-                required: vec![Field {
-                    name: "bar",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                examples: None,
+                required: vec![Field::new(
+                    "bar",
+                    Shape::Primitive {
                         name: "a string".to_owned(),
                         description: "a string".to_owned(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 1,
-                },],
+                    1
+                ),],
                 optional: vec![],
-                booleans: vec![Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Empty {
+                booleans: vec![Field::new(
+                    "foo",
+                    Shape::Empty {
                         description: "a boolean".to_owned(),
                         version: None,
                     },
-                    index: 0,
-                },],
+                    0
+                ),],
+                constraints: vec![],
             })
         );
     }
@@ -1737,15 +2692,15 @@ mod tests {
                 name: "Nested",
                 description: "struct Nested".into(),
                 version: None,
+                examples: None,
                 required: vec![
-                    Field {
-                        name: "struct",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Struct {
+                    Field::new(
+                        "struct",
+                        Shape::Struct {
                             name: "Struct",
                             description: "struct Struct".into(),
                             version: None,
+                            examples: None,
                             required: vec![Field {
                                 name: "foo",
                                 description: String::new(),
@@ -1754,28 +2709,38 @@ mod tests {
                                     name: "usize".to_owned(),
                                     description: "usize".to_owned(),
                                     version: None,
+                                    kind: PrimitiveKind::U64,
                                 },
                                 index: 0,
+                                allow_hyphen_values: false,
+                                value_name: None,
+                                value_delimiter: None,
+                                trailing: false,
+                                required: false,
+                                flags: false,
+                                multiple: false,
+                                deprecated_aliases: Vec::new(),
                             },],
                             optional: vec![],
                             booleans: vec![],
+                            constraints: vec![],
                         },
-                        index: 0,
-                    },
-                    Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                        0
+                    ),
+                    Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "isize".to_owned(),
                             description: "isize".to_owned(),
                             version: None,
+                            kind: PrimitiveKind::I64,
                         },
-                        index: 1,
-                    }
+                        1
+                    )
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -1816,32 +2781,32 @@ mod tests {
                 name: "Newtype",
                 description: "tuple struct Newtype".into(),
                 version: None,
+                examples: None,
                 required: vec![
-                    Field {
-                        name: "foo",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "foo",
+                        Shape::Primitive {
                             name: "usize".to_owned(),
                             description: "usize".to_owned(),
                             version: None,
+                            kind: PrimitiveKind::U64,
                         },
-                        index: 0,
-                    },
-                    Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                        0
+                    ),
+                    Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "a string".to_owned(),
                             description: "a string".to_owned(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -1866,12 +2831,16 @@ mod tests {
                 name: "Result",
                 description: "enum Result".into(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "Ok",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: "unit".into(),
                             version: None,
@@ -1881,7 +2850,10 @@ mod tests {
                         name: "Err",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: "unit".into(),
                             version: None,
@@ -1927,46 +2899,53 @@ mod tests {
                 name: "Result",
                 description: "enum Result".into(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "Ok",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Struct {
                             name: "Struct",
                             description: "struct Struct".into(),
                             version: None,
-                            required: vec![Field {
-                                name: "bar",
-                                description: String::new(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
+                            examples: None,
+                            required: vec![Field::new(
+                                "bar",
+                                Shape::Primitive {
                                     name: "a string".to_owned(),
                                     description: "a string".to_owned(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
-                                index: 1,
-                            },],
-                            optional: vec![Field {
-                                name: "foo",
-                                description: String::new(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
+                                1
+                            ),],
+                            optional: vec![Field::new(
+                                "foo",
+                                Shape::Primitive {
                                     name: "usize".to_owned(),
                                     description: "usize".to_owned(),
                                     version: None,
+                                    kind: PrimitiveKind::U64,
                                 },
-                                index: 0,
-                            },],
+                                0
+                            ),],
                             booleans: vec![],
+                            constraints: vec![],
                         },
                     },
                     Variant {
                         name: "Err",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: "unit".into(),
                             version: None,
@@ -2005,22 +2984,30 @@ mod tests {
                 name: "Result",
                 description: "enum Result".into(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "Ok",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Enum {
                             name: "Result",
                             description: "enum Result".into(),
                             version: None,
+                            examples: None,
                             variants: vec![
                                 Variant {
                                     name: "Ok",
                                     description: "".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: "unit".into(),
                                         version: None,
@@ -2030,7 +3017,10 @@ mod tests {
                                     name: "Err",
                                     description: "".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: "unit".into(),
                                         version: None,
@@ -2043,7 +3033,10 @@ mod tests {
                         name: "Err",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: "unit".into(),
                             version: None,
@@ -2111,32 +3104,44 @@ mod tests {
                 name: "Result",
                 description: "enum Result".into(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "Ok",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Enum {
                             name: "Result",
                             description: "enum Result".into(),
                             version: None,
+                            examples: None,
                             variants: vec![
                                 Variant {
                                     name: "Ok",
                                     description: "".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Enum {
                                         name: "Result",
                                         description: "enum Result".into(),
                                         version: None,
+                                        examples: None,
                                         variants: vec![
                                             Variant {
                                                 name: "Ok",
                                                 description: "".into(),
                                                 version: None,
+                                                group: None,
+                                                raw: false,
                                                 aliases: vec![],
+                                                deprecated_aliases: Vec::new(),
                                                 shape: Shape::Empty {
                                                     description: "unit".into(),
                                                     version: None,
@@ -2146,7 +3151,10 @@ mod tests {
                                                 name: "Err",
                                                 description: "".into(),
                                                 version: None,
+                                                group: None,
+                                                raw: false,
                                                 aliases: vec![],
+                                                deprecated_aliases: Vec::new(),
                                                 shape: Shape::Empty {
                                                     description: "unit".into(),
                                                     version: None,
@@ -2159,7 +3167,10 @@ mod tests {
                                     name: "Err",
                                     description: "".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: "unit".into(),
                                         version: None,
@@ -2172,7 +3183,10 @@ mod tests {
                         name: "Err",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: "unit".into(),
                             version: None,
@@ -2298,6 +3312,7 @@ mod tests {
                 name: "description".to_owned(),
                 description: "description".to_owned(),
                 version: Some("version".to_owned()),
+                kind: PrimitiveKind::U8,
             })
         );
     }
@@ -2338,9 +3353,56 @@ mod tests {
                 name: "Struct",
                 description: "description".to_owned(),
                 version: Some("version".to_owned()),
+                examples: None,
+                required: vec![],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_struct_examples() {
+        #[derive(Debug)]
+        struct Struct;
+
+        impl<'de> Deserialize<'de> for Struct {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct StructVisitor;
+
+                impl Visitor<'_> for StructVisitor {
+                    type Value = Struct;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        if formatter.fill() == 's' {
+                            write_record(formatter, &["examples\x1F\x1F$ foo"])
+                        } else {
+                            formatter.write_str("description")
+                        }
+                    }
+                }
+
+                deserializer.deserialize_struct("Struct", &[], StructVisitor)
+            }
+        }
+
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(Struct::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Struct {
+                name: "Struct",
+                description: "description".to_owned(),
+                version: None,
+                examples: Some("$ foo".to_owned()),
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -2381,13 +3443,14 @@ mod tests {
                 name: "Enum",
                 description: "description".to_owned(),
                 version: Some("version".to_owned()),
+                examples: None,
                 variants: vec![],
             })
         );
     }
 
     #[test]
-    fn deserialize_enum_variant_versions() {
+    fn deserialize_enum_examples() {
         #[derive(Debug)]
         struct Enum;
 
@@ -2396,11 +3459,53 @@ mod tests {
             where
                 D: de::Deserializer<'de>,
             {
-                #[derive(Deserialize)]
-                #[serde(variant_identifier)]
-                #[serde(rename_all = "kebab-case")]
-                enum Variant {
-                    Foo,
+                struct EnumVisitor;
+
+                impl Visitor<'_> for EnumVisitor {
+                    type Value = Enum;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        if formatter.fill() == 's' {
+                            write_record(formatter, &["examples\x1F\x1F$ foo"])
+                        } else {
+                            formatter.write_str("description")
+                        }
+                    }
+                }
+
+                deserializer.deserialize_enum("Enum", &[], EnumVisitor)
+            }
+        }
+
+        let mut deserializer = Deserializer::new();
+
+        assert_ok_eq!(
+            assert_err!(Enum::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Enum {
+                name: "Enum",
+                description: "description".to_owned(),
+                version: None,
+                examples: Some("$ foo".to_owned()),
+                variants: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_enum_variant_versions() {
+        #[derive(Debug)]
+        struct Enum;
+
+        impl<'de> Deserialize<'de> for Enum {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(variant_identifier)]
+                #[serde(rename_all = "kebab-case")]
+                enum Variant {
+                    Foo,
                     Bar,
                 }
 
@@ -2459,12 +3564,16 @@ mod tests {
                 name: "Enum",
                 description: "description".to_owned(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "foo",
                         description: "foo description".to_owned(),
                         version: Some("foo version".to_owned()),
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2474,7 +3583,10 @@ mod tests {
                         name: "bar",
                         description: "bar description".to_owned(),
                         version: Some("bar version".to_owned()),
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2651,6 +3763,7 @@ mod tests {
                 name: "Newtype".to_owned(),
                 description: "description".to_owned(),
                 version: Some("version".to_owned()),
+                kind: PrimitiveKind::U8,
             })
         );
     }
@@ -2730,34 +3843,36 @@ mod tests {
                 name: "Newtype",
                 description: "description".to_owned(),
                 version: Some("version".to_owned()),
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
 
     #[test]
-    fn deserialize_newtype_enum_version() {
+    fn deserialize_newtype_struct_examples() {
         #[derive(Debug)]
-        struct Enum;
+        struct Struct;
 
-        impl<'de> Deserialize<'de> for Enum {
+        impl<'de> Deserialize<'de> for Struct {
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
             where
                 D: de::Deserializer<'de>,
             {
-                struct EnumVisitor;
+                struct StructVisitor;
 
-                impl Visitor<'_> for EnumVisitor {
-                    type Value = Enum;
+                impl Visitor<'_> for StructVisitor {
+                    type Value = Struct;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
                         formatter.write_str("inner description")
                     }
                 }
 
-                deserializer.deserialize_enum("Enum", &[], EnumVisitor)
+                deserializer.deserialize_struct("Struct", &[], StructVisitor)
             }
         }
 
@@ -2775,8 +3890,8 @@ mod tests {
                     type Value = Newtype;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                        if formatter.fill() == 'v' {
-                            formatter.write_str("version")
+                        if formatter.fill() == 's' {
+                            write_record(formatter, &["examples\x1F\x1F$ foo"])
                         } else {
                             formatter.write_str("description")
                         }
@@ -2789,7 +3904,7 @@ mod tests {
                     where
                         D: de::Deserializer<'de>,
                     {
-                        Enum::deserialize(deserializer)?;
+                        Struct::deserialize(deserializer)?;
                         Ok(Newtype)
                     }
                 }
@@ -2808,22 +3923,26 @@ mod tests {
         // Finish.
         assert_ok_eq!(
             assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Success(Shape::Enum {
+            Status::Success(Shape::Struct {
                 name: "Newtype",
                 description: "description".to_owned(),
-                version: Some("version".to_owned()),
-                variants: vec![],
+                version: None,
+                examples: Some("$ foo".to_owned()),
+                required: vec![],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
             })
         );
     }
 
     #[test]
-    fn deserialize_newtype_enum_variant_versions() {
+    fn deserialize_newtype_struct_allow_hyphen_values() {
         #[derive(Debug, Deserialize)]
-        #[serde(rename_all = "kebab-case")]
-        enum Enum {
-            Foo,
-            Bar,
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: String,
         }
 
         #[derive(Debug)]
@@ -2840,21 +3959,8 @@ mod tests {
                     type Value = Newtype;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                        match formatter.width() {
-                            Some(0) => {
-                                if formatter.fill() == 'v' {
-                                    formatter.write_str("foo version")
-                                } else {
-                                    formatter.write_str("foo description")
-                                }
-                            }
-                            Some(1) => {
-                                if formatter.fill() == 'v' {
-                                    formatter.write_str("bar version")
-                                } else {
-                                    formatter.write_str("bar description")
-                                }
-                            }
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["allow_hyphen_values\x1F1\x1F"]),
                             _ => formatter.write_str("description"),
                         }
                     }
@@ -2866,7 +3972,7 @@ mod tests {
                     where
                         D: de::Deserializer<'de>,
                     {
-                        Enum::deserialize(deserializer)?;
+                        Struct::deserialize(deserializer)?;
                         Ok(Newtype)
                     }
                 }
@@ -2875,145 +3981,1809 @@ mod tests {
             }
         }
 
-        let mut deserializer = Deserializer::new();
-
-        // Trace the newtype.
-        assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Continue
-        );
-        // Trace the variants.
-        assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Continue
-        );
-        assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Continue
-        );
-        // Finish.
         assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Success(Shape::Enum {
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
                 name: "Newtype",
-                description: "description".to_owned(),
+                description: "description".into(),
                 version: None,
-                variants: vec![
-                    Variant {
-                        name: "foo",
-                        description: "foo description".to_owned(),
-                        version: Some("foo version".to_owned()),
-                        aliases: vec![],
-                        shape: Shape::Empty {
-                            description: String::new(),
-                            version: None,
-                        },
-                    },
-                    Variant {
-                        name: "bar",
-                        description: "bar description".to_owned(),
-                        version: Some("bar version".to_owned()),
-                        aliases: vec![],
-                        shape: Shape::Empty {
-                            description: String::new(),
+                examples: None,
+                required: vec![
+                    Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
+                        0
+                    ),
+                    Field {
+                        allow_hyphen_values: true,
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
                     },
                 ],
-            })
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
         );
     }
 
     #[test]
-    fn deserialize_newtype_empty_internal_version() {
+    fn deserialize_newtype_struct_value_name() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: String,
+        }
+
         #[derive(Debug)]
-        struct Empty;
+        struct Newtype;
 
-        impl<'de> Deserialize<'de> for Empty {
+        impl<'de> Deserialize<'de> for Newtype {
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
             where
                 D: de::Deserializer<'de>,
             {
-                struct EmptyVisitor;
+                struct NewtypeVisitor;
 
-                impl Visitor<'_> for EmptyVisitor {
-                    type Value = Empty;
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                        if formatter.fill() == 'v' {
-                            formatter.write_str("version")
-                        } else {
-                            formatter.write_str("description")
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["value_name\x1F0\x1FFILE"]),
+                            _ => formatter.write_str("description"),
                         }
                     }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
                 }
 
-                deserializer.deserialize_unit(EmptyVisitor)
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
             }
         }
 
-        #[derive(Debug, Deserialize)]
-        struct Newtype(Empty);
-
-        let mut deserializer = Deserializer::new();
-
-        // Trace the newtype.
         assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Continue
-        );
-        // Finish.
-        assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Success(Shape::Empty {
-                description: "tuple struct Newtype".to_owned(),
-                version: Some("version".to_owned()),
-            })
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
+                name: "Newtype",
+                description: "description".into(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field {
+                        value_name: Some("FILE".into()),
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    },
+                    Field::new(
+                        "bar",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        1
+                    ),
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
         );
     }
 
     #[test]
-    fn deserialize_newtype_bool_internal_version() {
+    fn deserialize_newtype_struct_renamed_with_aliases() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            #[serde(rename = "color", alias = "colour", alias = "c")]
+            foo: String,
+        }
+
         #[derive(Debug)]
-        struct Bool;
+        struct Newtype;
 
-        impl<'de> Deserialize<'de> for Bool {
+        impl<'de> Deserialize<'de> for Newtype {
             fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
             where
                 D: de::Deserializer<'de>,
             {
-                struct BoolVisitor;
+                struct NewtypeVisitor;
 
-                impl Visitor<'_> for BoolVisitor {
-                    type Value = Bool;
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                        if formatter.fill() == 'v' {
-                            formatter.write_str("version")
-                        } else {
-                            formatter.write_str("description")
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["name\x1F0\x1Fcolor"]),
+                            _ => formatter.write_str("description"),
                         }
                     }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
                 }
 
-                deserializer.deserialize_bool(BoolVisitor)
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
             }
         }
 
-        #[derive(Debug, Deserialize)]
-        struct Newtype(Bool);
-
-        let mut deserializer = Deserializer::new();
-
-        // Trace the newtype.
-        assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Continue
-        );
-        // Finish.
         assert_ok_eq!(
-            assert_err!(Newtype::deserialize(&mut deserializer)).0,
-            Status::Success(Shape::Boolean {
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
+                name: "Newtype",
+                description: "description".into(),
+                version: None,
+                examples: None,
+                required: vec![Field {
+                    aliases: vec!["c", "colour"],
+                    ..Field::new(
+                        "color",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )
+                }],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_positional() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["positional\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
+                name: "Newtype",
+                description: "description".into(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    ),
+                    Field::new(
+                        "bar",
+                        Shape::Optional(Box::new(Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        })),
+                        1
+                    ),
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_value_delimiter() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<Vec<String>>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["value_delimiter\x1F1\x1F,"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
+                name: "Newtype",
+                description: "description".into(),
+                version: None,
+                examples: None,
+                required: vec![Field::new(
+                    "foo",
+                    Shape::Primitive {
+                        name: "a string".into(),
+                        description: "a string".into(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    0
+                )],
+                optional: vec![Field {
+                    value_delimiter: Some(b','),
+                    ..Field::new(
+                        "bar",
+                        Shape::Sequence(Box::new(Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        })),
+                        1
+                    )
+                }],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_value_delimiter_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["value_delimiter\x1F1\x1F,"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::UnsupportedValueDelimiter("bar")
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_trailing() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Vec<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["trailing\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
+                name: "Newtype",
+                description: "description".into(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    ),
+                    Field {
+                        trailing: true,
+                        ..Field::new(
+                            "bar",
+                            Shape::Sequence(Box::new(Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            1
+                        )
+                    },
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_trailing_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: String,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["trailing\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::UnsupportedTrailing("bar")
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_positional_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<Vec<String>>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["positional\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::UnsupportedOptionalPositional("bar")
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_required() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["required\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
+                name: "Newtype",
+                description: "description".into(),
+                version: None,
+                examples: None,
+                required: vec![Field::new(
+                    "foo",
+                    Shape::Primitive {
+                        name: "a string".into(),
+                        description: "a string".into(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    0
+                )],
+                optional: vec![Field {
+                    required: true,
+                    ..Field::new(
+                        "bar",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        1
+                    )
+                }],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_required_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: bool,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["required\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::UnsupportedRequired("bar")
+        );
+    }
+
+    /// A visitor that ignores the fill character enables no settings, even if its description is
+    /// one of their messages.
+    #[test]
+    fn deserialize_newtype_struct_settings_container_description() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str("required")
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace_with_settings(PhantomData::<Newtype>),
+            (
+                Shape::Struct {
+                    name: "Newtype",
+                    description: "required".into(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )],
+                    optional: vec![Field::new(
+                        "bar",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        1
+                    )],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+                Settings::default(),
+            )
+        );
+    }
+
+    /// A visitor that ignores the fill character enables no settings, even if a field's description
+    /// is one of their messages.
+    #[test]
+    fn deserialize_newtype_struct_settings_key_description() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.width() {
+                            Some(0) => formatter.write_str("trailing"),
+                            Some(1) => formatter.write_str("required"),
+                            _ => formatter.write_str("no_auto_help"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace_with_settings(PhantomData::<Newtype>),
+            (
+                Shape::Struct {
+                    name: "Newtype",
+                    description: "no_auto_help".into(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field {
+                        description: "trailing".into(),
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    }],
+                    optional: vec![Field {
+                        description: "required".into(),
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                },
+                Settings::default(),
+            )
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_flags() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        enum Format {
+            Json,
+            Yaml,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<Format>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["flags\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        let shape = assert_ok!(trace(PhantomData::<Newtype>));
+        if let Shape::Struct { optional, .. } = shape {
+            assert_eq!(optional[0].name, "bar");
+            assert!(optional[0].flags);
+            assert_eq!(optional[0].option_names(), vec!["Json", "Yaml"]);
+        } else {
+            panic!("expected struct shape, found {:?}", shape);
+        }
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_flags_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["flags\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::UnsupportedFlags("bar")
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_flags_conflicting_name() {
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "lowercase")]
+        #[allow(dead_code)]
+        enum Format {
+            Json,
+            Yaml,
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            json: bool,
+            bar: Option<Format>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["flags\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::ConflictingFlagName("bar", "json")
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_multiple() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<Vec<u32>>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["multiple\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        let shape = assert_ok!(trace(PhantomData::<Newtype>));
+        if let Shape::Struct { optional, .. } = shape {
+            assert_eq!(optional[0].name, "bar");
+            assert!(optional[0].multiple);
+        } else {
+            panic!("expected struct shape, found {:?}", shape);
+        }
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_multiple_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["multiple\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::UnsupportedMultiple("bar")
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_enum_raw() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        enum Enum {
+            Foo(String),
+            Bar(Vec<String>),
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["raw\x1F1\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Enum::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        let shape = assert_ok!(trace(PhantomData::<Newtype>));
+        if let Shape::Enum { variants, .. } = shape {
+            assert_eq!(variants[0].name, "Foo");
+            assert!(!variants[0].raw);
+            assert_eq!(variants[1].name, "Bar");
+            assert!(variants[1].raw);
+            assert_eq!(
+                variants[1].shape,
+                Shape::Sequence(Box::new(Shape::Primitive {
+                    name: "args".to_owned(),
+                    description: "a string".to_owned(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }))
+            );
+        } else {
+            panic!("expected enum shape, found {:?}", shape);
+        }
+    }
+
+    #[test]
+    fn deserialize_newtype_enum_raw_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        enum Enum {
+            Foo { bar: String, baz: bool },
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["raw\x1F0\x1F"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Enum::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(trace(PhantomData::<Newtype>), Error::UnsupportedRaw("Foo"));
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_constraints() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: Option<String>,
+            bar: bool,
+            baz: Option<String>,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(
+                                formatter,
+                                &["one_of\x1F\x1F0,1", "all_or_none\x1F\x1F0,2"],
+                            ),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace(PhantomData::<Newtype>),
+            Shape::Struct {
+                name: "Newtype",
+                description: "description".into(),
+                version: None,
+                examples: None,
+                required: vec![],
+                optional: vec![
+                    Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    ),
+                    Field::new(
+                        "baz",
+                        Shape::Primitive {
+                            name: "a string".into(),
+                            description: "a string".into(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        2
+                    )
+                ],
+                booleans: vec![Field::new(
+                    "bar",
+                    Shape::Empty {
+                        description: "a boolean".into(),
+                        version: None,
+                    },
+                    1
+                )],
+                constraints: vec![
+                    Constraint {
+                        kind: ConstraintKind::OneOf,
+                        fields: vec!["foo", "bar"],
+                    },
+                    Constraint {
+                        kind: ConstraintKind::AllOrNone,
+                        fields: vec!["foo", "baz"],
+                    },
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_struct_constraint_unsupported() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: bool,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.fill() {
+                            's' => write_record(formatter, &["at_most_one\x1F\x1F0,1"]),
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Struct::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Newtype>),
+            Error::UnsupportedConstraint("foo")
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_enum_version() {
+        #[derive(Debug)]
+        struct Enum;
+
+        impl<'de> Deserialize<'de> for Enum {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct EnumVisitor;
+
+                impl Visitor<'_> for EnumVisitor {
+                    type Value = Enum;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str("inner description")
+                    }
+                }
+
+                deserializer.deserialize_enum("Enum", &[], EnumVisitor)
+            }
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        if formatter.fill() == 'v' {
+                            formatter.write_str("version")
+                        } else {
+                            formatter.write_str("description")
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Enum::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        let mut deserializer = Deserializer::new();
+
+        // Trace the newtype.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        // Finish.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Enum {
+                name: "Newtype",
+                description: "description".to_owned(),
+                version: Some("version".to_owned()),
+                examples: None,
+                variants: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_enum_examples() {
+        #[derive(Debug)]
+        struct Enum;
+
+        impl<'de> Deserialize<'de> for Enum {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct EnumVisitor;
+
+                impl Visitor<'_> for EnumVisitor {
+                    type Value = Enum;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str("inner description")
+                    }
+                }
+
+                deserializer.deserialize_enum("Enum", &[], EnumVisitor)
+            }
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        if formatter.fill() == 's' {
+                            write_record(formatter, &["examples\x1F\x1F$ foo"])
+                        } else {
+                            formatter.write_str("description")
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Enum::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        let mut deserializer = Deserializer::new();
+
+        // Trace the newtype.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        // Finish.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Enum {
+                name: "Newtype",
+                description: "description".to_owned(),
+                version: None,
+                examples: Some("$ foo".to_owned()),
+                variants: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_enum_variant_versions() {
+        #[derive(Debug, Deserialize)]
+        #[serde(rename_all = "kebab-case")]
+        enum Enum {
+            Foo,
+            Bar,
+        }
+
+        #[derive(Debug)]
+        struct Newtype;
+
+        impl<'de> Deserialize<'de> for Newtype {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct NewtypeVisitor;
+
+                impl<'de> Visitor<'de> for NewtypeVisitor {
+                    type Value = Newtype;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.width() {
+                            Some(0) => {
+                                if formatter.fill() == 'v' {
+                                    formatter.write_str("foo version")
+                                } else {
+                                    formatter.write_str("foo description")
+                                }
+                            }
+                            Some(1) => {
+                                if formatter.fill() == 'v' {
+                                    formatter.write_str("bar version")
+                                } else {
+                                    formatter.write_str("bar description")
+                                }
+                            }
+                            _ => formatter.write_str("description"),
+                        }
+                    }
+
+                    fn visit_newtype_struct<D>(
+                        self,
+                        deserializer: D,
+                    ) -> Result<Self::Value, D::Error>
+                    where
+                        D: de::Deserializer<'de>,
+                    {
+                        Enum::deserialize(deserializer)?;
+                        Ok(Newtype)
+                    }
+                }
+
+                deserializer.deserialize_newtype_struct("Newtype", NewtypeVisitor)
+            }
+        }
+
+        let mut deserializer = Deserializer::new();
+
+        // Trace the newtype.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        // Trace the variants.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        // Finish.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Enum {
+                name: "Newtype",
+                description: "description".to_owned(),
+                version: None,
+                examples: None,
+                variants: vec![
+                    Variant {
+                        name: "foo",
+                        description: "foo description".to_owned(),
+                        version: Some("foo version".to_owned()),
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        },
+                    },
+                    Variant {
+                        name: "bar",
+                        description: "bar description".to_owned(),
+                        version: Some("bar version".to_owned()),
+                        group: None,
+                        raw: false,
+                        aliases: vec![],
+                        deprecated_aliases: Vec::new(),
+                        shape: Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        },
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_empty_internal_version() {
+        #[derive(Debug)]
+        struct Empty;
+
+        impl<'de> Deserialize<'de> for Empty {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct EmptyVisitor;
+
+                impl Visitor<'_> for EmptyVisitor {
+                    type Value = Empty;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        if formatter.fill() == 'v' {
+                            formatter.write_str("version")
+                        } else {
+                            formatter.write_str("description")
+                        }
+                    }
+                }
+
+                deserializer.deserialize_unit(EmptyVisitor)
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Newtype(Empty);
+
+        let mut deserializer = Deserializer::new();
+
+        // Trace the newtype.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        // Finish.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Empty {
+                description: "tuple struct Newtype".to_owned(),
+                version: Some("version".to_owned()),
+            })
+        );
+    }
+
+    #[test]
+    fn deserialize_newtype_bool_internal_version() {
+        #[derive(Debug)]
+        struct Bool;
+
+        impl<'de> Deserialize<'de> for Bool {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct BoolVisitor;
+
+                impl Visitor<'_> for BoolVisitor {
+                    type Value = Bool;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        if formatter.fill() == 'v' {
+                            formatter.write_str("version")
+                        } else {
+                            formatter.write_str("description")
+                        }
+                    }
+                }
+
+                deserializer.deserialize_bool(BoolVisitor)
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        struct Newtype(Bool);
+
+        let mut deserializer = Deserializer::new();
+
+        // Trace the newtype.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Continue
+        );
+        // Finish.
+        assert_ok_eq!(
+            assert_err!(Newtype::deserialize(&mut deserializer)).0,
+            Status::Success(Shape::Boolean {
                 name: "Newtype".to_owned(),
                 description: "tuple struct Newtype".to_owned(),
                 version: Some("version".to_owned()),
@@ -3066,6 +5836,7 @@ mod tests {
                 name: "Newtype".to_owned(),
                 description: "tuple struct Newtype".to_owned(),
                 version: Some("version".to_owned()),
+                kind: PrimitiveKind::U8,
             })
         );
     }
@@ -3115,9 +5886,11 @@ mod tests {
                 name: "Newtype",
                 description: "tuple struct Newtype".to_owned(),
                 version: Some("version".to_owned()),
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             })
         );
     }
@@ -3167,6 +5940,7 @@ mod tests {
                 name: "Newtype",
                 description: "tuple struct Newtype".to_owned(),
                 version: Some("version".to_owned()),
+                examples: None,
                 variants: vec![],
             })
         );
@@ -3253,12 +6027,16 @@ mod tests {
                 name: "Newtype",
                 description: "tuple struct Newtype".to_owned(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "foo",
                         description: "foo description".to_owned(),
                         version: Some("foo version".to_owned()),
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3268,7 +6046,10 @@ mod tests {
                         name: "bar",
                         description: "bar description".to_owned(),
                         version: Some("bar version".to_owned()),
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3327,18 +6108,18 @@ mod tests {
                 name: "Struct",
                 description: "struct Struct".into(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
-                booleans: vec![Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Empty {
+                booleans: vec![Field::new(
+                    "foo",
+                    Shape::Empty {
                         description: "description".to_owned(),
                         version: Some("version".to_owned()),
                     },
-                    index: 0,
-                },],
+                    0
+                ),],
+                constraints: vec![],
             })
         );
     }
@@ -3366,6 +6147,7 @@ mod tests {
             field: "bar",
             discriminant: &mut discriminant,
             recursive_deserializer: &mut None,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
 
         assert_some_eq!(assert_ok!(struct_access.next_key::<Key>()), Key::Bar);
@@ -3387,6 +6169,7 @@ mod tests {
             field: "bar",
             discriminant: &mut discriminant,
             recursive_deserializer: &mut None,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
 
         assert_some_eq!(assert_ok!(struct_access.next_key::<Key>()), Key::Bar);
@@ -3396,6 +6179,7 @@ mod tests {
                 name: "i32".to_owned(),
                 description: "i32".to_owned(),
                 version: None,
+                kind: PrimitiveKind::I32,
             })
         );
     }
@@ -3415,6 +6199,7 @@ mod tests {
             variant: "bar",
             discriminant: &mut discriminant,
             recursive_deserializer: &mut None,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
 
         let (key, variant_access) = assert_ok!(enum_access.variant::<Key>());
@@ -3424,6 +6209,7 @@ mod tests {
             VariantAccess {
                 name: "bar",
                 recursive_deserializer: &mut None,
+                remaining_depth: DEFAULT_MAX_DEPTH,
             }
         );
         assert_eq!(discriminant, 1);
@@ -3434,6 +6220,7 @@ mod tests {
         let variant_access = VariantAccess {
             name: "foo",
             recursive_deserializer: &mut None,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
 
         assert_ok_eq!(
@@ -3450,6 +6237,7 @@ mod tests {
         let variant_access = VariantAccess {
             name: "foo",
             recursive_deserializer: &mut None,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
 
         assert_ok_eq!(
@@ -3458,6 +6246,7 @@ mod tests {
                 name: "u64".into(),
                 description: "u64".into(),
                 version: None,
+                kind: PrimitiveKind::U64,
             })
         );
     }
@@ -3525,6 +6314,7 @@ mod tests {
         let variant_access = VariantAccess {
             name: "foo",
             recursive_deserializer: &mut recursive_deserializer,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
         assert_ok_eq!(
             assert_err!(variant_access.struct_variant(&["bar", "baz"], StructVisitor)).0,
@@ -3535,6 +6325,7 @@ mod tests {
         let variant_access = VariantAccess {
             name: "foo",
             recursive_deserializer: &mut recursive_deserializer,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
         assert_ok_eq!(
             assert_err!(variant_access.struct_variant(&["bar", "baz"], StructVisitor)).0,
@@ -3545,6 +6336,7 @@ mod tests {
         let variant_access = VariantAccess {
             name: "foo",
             recursive_deserializer: &mut recursive_deserializer,
+            remaining_depth: DEFAULT_MAX_DEPTH,
         };
         assert_ok_eq!(
             assert_err!(variant_access.struct_variant(&["bar", "baz"], StructVisitor)).0,
@@ -3552,37 +6344,147 @@ mod tests {
                 name: "foo",
                 description: "struct variant".into(),
                 version: None,
+                examples: None,
                 required: vec![
-                    Field {
-                        name: "bar",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "bar",
+                        Shape::Primitive {
                             name: "u64".into(),
                             description: "u64".into(),
                             version: None,
+                            kind: PrimitiveKind::U64,
+                        },
+                        0
+                    ),
+                    Field::new(
+                        "baz",
+                        Shape::Empty {
+                            description: "unit".into(),
+                            version: None,
                         },
-                        index: 0,
+                        1
+                    )
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn trace_struct_with_field_descriptions() {
+        #[allow(dead_code)]
+        struct Struct {
+            foo: String,
+            bar: u32,
+        }
+
+        impl<'de> Deserialize<'de> for Struct {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(field_identifier)]
+                #[serde(rename_all = "lowercase")]
+                enum Key {
+                    Foo,
+                    Bar,
+                }
+
+                struct StructVisitor;
+
+                impl<'de> Visitor<'de> for StructVisitor {
+                    type Value = Struct;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        match formatter.width() {
+                            Some(0) => formatter.write_str("foo description"),
+                            Some(1) => formatter.write_str("bar description"),
+                            _ => formatter.write_str("Struct description"),
+                        }
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+                    where
+                        A: MapAccess<'de>,
+                    {
+                        let mut foo = None;
+                        let mut bar = None;
+
+                        while let Some(key) = map.next_key()? {
+                            match key {
+                                Key::Foo => {
+                                    if foo.is_some() {
+                                        return Err(de::Error::duplicate_field("foo"));
+                                    }
+                                    foo = Some(map.next_value()?);
+                                }
+                                Key::Bar => {
+                                    if bar.is_some() {
+                                        return Err(de::Error::duplicate_field("bar"));
+                                    }
+                                    bar = Some(map.next_value()?);
+                                }
+                            }
+                        }
+
+                        Ok(Struct {
+                            foo: foo.ok_or_else(|| de::Error::missing_field("foo"))?,
+                            bar: bar.ok_or_else(|| de::Error::missing_field("bar"))?,
+                        })
+                    }
+                }
+
+                deserializer.deserialize_struct("Struct", &["foo", "bar"], StructVisitor)
+            }
+        }
+
+        assert_ok_eq!(
+            trace(PhantomData::<Struct>),
+            Shape::Struct {
+                name: "Struct",
+                description: "Struct description".into(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field {
+                        description: "foo description".into(),
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
                     Field {
-                        name: "baz",
-                        description: String::new(),
-                        aliases: vec![],
-                        shape: Shape::Empty {
-                            description: "unit".into(),
-                            version: None,
-                        },
-                        index: 1,
-                    }
+                        description: "bar description".into(),
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "u32".into(),
+                                description: "u32".into(),
+                                version: None,
+                                kind: PrimitiveKind::U32,
+                            },
+                            1
+                        )
+                    },
                 ],
                 optional: vec![],
                 booleans: vec![],
-            })
+                constraints: vec![],
+            }
         );
     }
 
     #[test]
-    fn trace_struct_with_field_descriptions() {
+    fn trace_struct_with_field_descriptions_table() {
         #[allow(dead_code)]
         struct Struct {
             foo: String,
@@ -3608,10 +6510,19 @@ mod tests {
                     type Value = Struct;
 
                     fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                        match formatter.width() {
-                            Some(0) => formatter.write_str("foo description"),
-                            Some(1) => formatter.write_str("bar description"),
-                            _ => formatter.write_str("Struct description"),
+                        // Descriptions requested individually are ignored when a record is written.
+                        if formatter.fill() == 's' {
+                            write_record(
+                                formatter,
+                                &[
+                                    "description\x1F0\x1Ffoo description",
+                                    "description\x1F1\x1Fbar description",
+                                ],
+                            )
+                        } else if formatter.width().is_some() {
+                            formatter.write_str("wrong description")
+                        } else {
+                            formatter.write_str("Struct description")
                         }
                     }
 
@@ -3656,32 +6567,38 @@ mod tests {
                 name: "Struct",
                 description: "Struct description".into(),
                 version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "foo",
                         description: "foo description".into(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
-                            name: "a string".into(),
-                            description: "a string".into(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
                     Field {
-                        name: "bar",
                         description: "bar description".into(),
-                        aliases: vec![],
-                        shape: Shape::Primitive {
-                            name: "u32".into(),
-                            description: "u32".into(),
-                            version: None,
-                        },
-                        index: 1,
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "u32".into(),
+                                description: "u32".into(),
+                                version: None,
+                                kind: PrimitiveKind::U32,
+                            },
+                            1
+                        )
                     },
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
         );
     }
@@ -3763,32 +6680,40 @@ mod tests {
                 name: "Struct",
                 description: "Struct description".into(),
                 version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "foo",
                         description: "foo description".into(),
                         aliases: vec!["f"],
-                        shape: Shape::Primitive {
-                            name: "a string".into(),
-                            description: "a string".into(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "a string".into(),
+                                description: "a string".into(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
                     Field {
-                        name: "bar",
                         description: "bar description".into(),
                         aliases: vec!["b"],
-                        shape: Shape::Primitive {
-                            name: "u32".into(),
-                            description: "u32".into(),
-                            version: None,
-                        },
-                        index: 1,
+                        ..Field::new(
+                            "bar",
+                            Shape::Primitive {
+                                name: "u32".into(),
+                                description: "u32".into(),
+                                version: None,
+                                kind: PrimitiveKind::U32,
+                            },
+                            1
+                        )
                     },
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
         );
     }
@@ -3847,12 +6772,16 @@ mod tests {
                 name: "Enum",
                 description: "Enum description".into(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "foo",
                         description: "foo description".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3862,7 +6791,10 @@ mod tests {
                         name: "bar",
                         description: "bar description".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3929,12 +6861,16 @@ mod tests {
                 name: "Enum",
                 description: "Enum description".into(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "foo",
                         description: "foo description".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec!["f"],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3944,7 +6880,10 @@ mod tests {
                         name: "bar",
                         description: "bar description".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec!["b"],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3979,12 +6918,16 @@ mod tests {
                 name: "Enum",
                 description: "enum Enum".into(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "f",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec!["foo"],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -3994,56 +6937,67 @@ mod tests {
                         name: "bar",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Primitive {
                             name: "u8".to_owned(),
                             description: "u8".to_owned(),
                             version: None,
+                            kind: PrimitiveKind::U8,
                         },
                     },
                     Variant {
                         name: "b",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec!["baz"],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Optional(Box::new(Shape::Primitive {
                             name: "a string".to_owned(),
                             description: "a string".to_owned(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         })),
                     },
                     Variant {
                         name: "q",
                         description: "".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec!["qux"],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Struct {
                             name: "q",
                             description: "struct variant Enum::Qux".to_owned(),
                             version: None,
-                            required: vec![Field {
-                                name: "required",
-                                description: String::new(),
-                                aliases: vec![],
-                                shape: Shape::Primitive {
+                            examples: None,
+                            required: vec![Field::new(
+                                "required",
+                                Shape::Primitive {
                                     name: "a string".to_owned(),
                                     description: "a string".to_owned(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
-                                index: 0,
-                            }],
-                            optional: vec![Field {
-                                name: "optional",
-                                description: String::new(),
-                                aliases: vec![],
-                                shape: Shape::Primitive {
+                                0
+                            )],
+                            optional: vec![Field::new(
+                                "optional",
+                                Shape::Primitive {
                                     name: "a string".to_owned(),
                                     description: "a string".to_owned(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
-                                index: 1,
-                            }],
+                                1
+                            )],
                             booleans: vec![],
+                            constraints: vec![],
                         }
                     }
                 ],
@@ -4052,7 +7006,184 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "tracing unexpectedly succeeded in deserializing")]
+    fn trace_transparent_newtype_primitive() {
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct Port(#[allow(unused)] u16);
+
+        assert_ok_eq!(
+            trace(PhantomData::<Port>),
+            Shape::Primitive {
+                name: "u16".to_owned(),
+                description: "u16".to_owned(),
+                version: None,
+                kind: PrimitiveKind::U16,
+            }
+        );
+    }
+
+    #[test]
+    fn trace_transparent_newtype_option() {
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct Port(#[allow(unused)] Option<u16>);
+
+        assert_ok_eq!(
+            trace(PhantomData::<Port>),
+            Shape::Optional(Box::new(Shape::Primitive {
+                name: "u16".to_owned(),
+                description: "u16".to_owned(),
+                version: None,
+                kind: PrimitiveKind::U16,
+            }))
+        );
+    }
+
+    #[test]
+    fn trace_transparent_newtype_nested() {
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct Inner(#[allow(unused)] u16);
+
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct Port(#[allow(unused)] Inner);
+
+        assert_ok_eq!(
+            trace(PhantomData::<Port>),
+            Shape::Primitive {
+                name: "u16".to_owned(),
+                description: "u16".to_owned(),
+                version: None,
+                kind: PrimitiveKind::U16,
+            }
+        );
+    }
+
+    #[test]
+    fn trace_transparent_newtype_field() {
+        #[derive(Deserialize)]
+        #[serde(transparent)]
+        struct Port(#[allow(unused)] u16);
+
+        #[derive(Deserialize)]
+        struct Args {
+            #[allow(unused)]
+            port: Port,
+        }
+
+        // The field name is used as the positional argument's name, so the lost type name is not
+        // displayed.
+        assert_ok_eq!(
+            trace(PhantomData::<Args>),
+            Shape::Struct {
+                name: "Args",
+                description: "struct Args".to_owned(),
+                version: None,
+                examples: None,
+                required: vec![Field::new(
+                    "port",
+                    Shape::Primitive {
+                        name: "u16".to_owned(),
+                        description: "u16".to_owned(),
+                        version: None,
+                        kind: PrimitiveKind::U16,
+                    },
+                    0
+                )],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+        );
+    }
+
+    /// A newtype around `u16` declaring both settings in its `expecting()` output.
+    #[derive(Debug)]
+    struct Settled;
+
+    impl<'de> Deserialize<'de> for Settled {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: de::Deserializer<'de>,
+        {
+            struct SettledVisitor;
+
+            impl<'de> Visitor<'de> for SettledVisitor {
+                type Value = Settled;
+
+                fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                    match formatter.fill() {
+                        's' => write_record(
+                            formatter,
+                            &["no_auto_help\x1F\x1F", "no_empty_help\x1F\x1F"],
+                        ),
+                        _ => formatter.write_str("description"),
+                    }
+                }
+
+                fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+                where
+                    D: de::Deserializer<'de>,
+                {
+                    u16::deserialize(deserializer)?;
+                    Ok(Settled)
+                }
+            }
+
+            deserializer.deserialize_newtype_struct("Settled", SettledVisitor)
+        }
+    }
+
+    #[test]
+    fn trace_with_settings_default() {
+        assert_ok_eq!(
+            trace_with_settings(PhantomData::<u16>),
+            (
+                Shape::Primitive {
+                    name: "u16".to_owned(),
+                    description: "u16".to_owned(),
+                    version: None,
+                    kind: PrimitiveKind::U16,
+                },
+                Settings::default()
+            )
+        );
+    }
+
+    #[test]
+    fn trace_with_settings_declared() {
+        assert_ok_eq!(
+            trace_with_settings(PhantomData::<Settled>),
+            (
+                Shape::Primitive {
+                    name: "Settled".to_owned(),
+                    description: "description".to_owned(),
+                    version: None,
+                    kind: PrimitiveKind::U16,
+                },
+                Settings {
+                    no_auto_help: true,
+                    no_empty_help: true,
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn trace_with_settings_nested_ignored() {
+        #[derive(Deserialize)]
+        struct Args {
+            #[allow(unused)]
+            port: Settled,
+        }
+
+        // Only the top-level type can declare settings.
+        let (_, settings) = assert_ok!(trace_with_settings(PhantomData::<Args>));
+        assert_eq!(settings, Settings::default());
+    }
+
+    #[test]
     fn trace_type_that_does_not_call_deserializer() {
         struct Foo;
 
@@ -4066,6 +7197,194 @@ mod tests {
             }
         }
 
-        let _ = trace(PhantomData::<Foo>);
+        assert_err_eq!(trace(PhantomData::<Foo>), Error::UnsupportedType(None));
+    }
+
+    #[test]
+    fn trace_field_recovering_from_error() {
+        #[derive(Debug)]
+        #[allow(dead_code)]
+        enum Id {
+            Number(u64),
+            Name(String),
+        }
+
+        impl<'de> Deserialize<'de> for Id {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                // Falls back to a name if the value is not a number.
+                match u64::deserialize(deserializer) {
+                    Ok(number) => Ok(Id::Number(number)),
+                    Err(error) => Ok(Id::Name(error.to_string())),
+                }
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            name: String,
+            id: Id,
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Struct>),
+            Error::UnsupportedType(Some("id"))
+        );
+    }
+
+    #[test]
+    fn trace_field_converting_error() {
+        #[derive(Debug)]
+        struct Port;
+
+        impl<'de> Deserialize<'de> for Port {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                u64::deserialize(deserializer)
+                    .map(|_| Port)
+                    .map_err(|error| D::Error::custom(format!("invalid port: {}", error)))
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            port: Port,
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Struct>),
+            Error::UnsupportedType(Some("port"))
+        );
+    }
+
+    #[test]
+    fn trace_variant_recovering_from_error() {
+        #[derive(Debug)]
+        struct Count;
+
+        impl<'de> Deserialize<'de> for Count {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                Ok(u64::deserialize(deserializer).map_or(Count, |_| Count))
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        enum Enum {
+            Foo(Count),
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Enum>),
+            Error::UnsupportedType(Some("Foo"))
+        );
+    }
+
+    #[test]
+    fn trace_custom_error_without_status() {
+        #[derive(Debug)]
+        struct Foo;
+
+        impl<'de> Deserialize<'de> for Foo {
+            fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                Err(D::Error::custom("foo"))
+            }
+        }
+
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Struct {
+            foo: Foo,
+        }
+
+        assert_err_eq!(trace(PhantomData::<Struct>), Error::Custom("foo".into()));
+    }
+
+    #[test]
+    fn trace_recursive_struct() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Tree {
+            name: String,
+            child: Option<Box<Tree>>,
+        }
+
+        assert_err_eq!(
+            trace_with_max_depth(PhantomData::<Tree>, 2),
+            Error::RecursiveType(vec!["Tree", "Option", "Tree", "Option", "Tree"])
+        );
+    }
+
+    #[test]
+    fn trace_recursive_enum() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        enum Expr {
+            Not(Box<Expr>),
+            Literal(u32),
+        }
+
+        assert_err_eq!(
+            trace_with_max_depth(PhantomData::<Expr>, 2),
+            Error::RecursiveType(vec!["Expr", "Expr", "Expr"])
+        );
+    }
+
+    #[test]
+    fn trace_recursive_enum_default_depth() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        enum Expr {
+            Not(Box<Expr>),
+            Literal(u32),
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Expr>),
+            Error::RecursiveType(vec!["Expr"; 129])
+        );
+    }
+
+    #[test]
+    fn trace_recursive_struct_default_depth() {
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        struct Tree {
+            name: String,
+            children: Vec<Tree>,
+        }
+
+        assert_err_eq!(
+            trace(PhantomData::<Tree>),
+            Error::RecursiveType(vec!["Tree"; 129])
+        );
+    }
+
+    #[test]
+    fn trace_max_depth_zero() {
+        assert_ok_eq!(
+            trace_with_max_depth(PhantomData::<u32>, 0),
+            (
+                Shape::Primitive {
+                    name: "u32".to_owned(),
+                    description: "u32".to_owned(),
+                    version: None,
+                    kind: PrimitiveKind::U32,
+                },
+                Settings::default()
+            )
+        );
     }
 }
@@ -0,0 +1,243 @@
+//! The settings record written by `expecting()`.
+//!
+//! Everything a type declares beyond what `serde` provides, such as descriptions of its keys or
+//! the settings of its fields, is written by its visitor's `expecting()` as a single record when
+//! `formatter.fill()` is set to `'s'`.
+
+use super::{
+    ConstraintKind,
+    Settings,
+};
+
+/// A setting provided by an entry of the record.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum Setting {
+    /// The description of a key.
+    Description,
+    /// The name of a field that also has aliases.
+    Name,
+    /// The version of the container or of a variant.
+    Version,
+    /// The heading a variant is grouped under.
+    Group,
+    /// The examples of the container.
+    Examples,
+    /// The name displayed for a field's value.
+    ValueName,
+    /// The delimiter a field's values are split on.
+    ValueDelimiter,
+    AllowHyphenValues,
+    Positional,
+    Trailing,
+    Required,
+    Flags,
+    Multiple,
+    /// An enum variant whose arguments are all positional.
+    Raw,
+    NoAutoHelp,
+    NoEmptyHelp,
+    /// A constraint on the container's fields, identified by their indices.
+    Constraint(ConstraintKind),
+}
+
+impl Setting {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "description" => Self::Description,
+            "name" => Self::Name,
+            "version" => Self::Version,
+            "group" => Self::Group,
+            "examples" => Self::Examples,
+            "value_name" => Self::ValueName,
+            "value_delimiter" => Self::ValueDelimiter,
+            "allow_hyphen_values" => Self::AllowHyphenValues,
+            "positional" => Self::Positional,
+            "trailing" => Self::Trailing,
+            "required" => Self::Required,
+            "flags" => Self::Flags,
+            "multiple" => Self::Multiple,
+            "raw" => Self::Raw,
+            "no_auto_help" => Self::NoAutoHelp,
+            "no_empty_help" => Self::NoEmptyHelp,
+            "one_of" => Self::Constraint(ConstraintKind::OneOf),
+            "at_most_one" => Self::Constraint(ConstraintKind::AtMostOne),
+            "all_or_none" => Self::Constraint(ConstraintKind::AllOrNone),
+            _ => return None,
+        })
+    }
+}
+
+/// The entries of a record, in the order they were written.
+///
+/// Each entry is a setting, the index of the key it applies to, and its value, separated by
+/// `\x1F`. The index is empty for settings of the container itself, and the value is empty for
+/// settings that are simply enabled. Each entry is prefixed with its length in bytes followed by a
+/// `:`, so that values can contain any text.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub(super) struct Record(Vec<(Setting, Option<usize>, String)>);
+
+impl Record {
+    /// Parses the record written by `expecting()`.
+    ///
+    /// Anything that is not a record, such as a type's description, is not a record. Entries for
+    /// settings that are not known are skipped.
+    pub(super) fn parse(mut record: &str) -> Option<Self> {
+        if record.is_empty() {
+            return None;
+        }
+        let mut entries = Vec::new();
+        while !record.is_empty() {
+            let (length, rest) = record.split_once(':')?;
+            let length = length.parse().ok()?;
+            let (setting, entry) = rest.get(..length)?.split_once('\x1F')?;
+            let (index, value) = entry.split_once('\x1F')?;
+            let index = if index.is_empty() {
+                None
+            } else {
+                Some(index.parse().ok()?)
+            };
+            if let Some(setting) = Setting::from_name(setting) {
+                entries.push((setting, index, value.to_owned()));
+            }
+            record = &rest[length..];
+        }
+        Some(Self(entries))
+    }
+
+    /// Returns whether the record has no entries, such as when no record was written.
+    pub(super) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value of `setting` for the key at `key`, or for the container itself if `key`
+    /// is `None`.
+    pub(super) fn value(&self, setting: Setting, key: Option<usize>) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|entry| entry.0 == setting && entry.1 == key)
+            .map(|entry| entry.2.as_str())
+    }
+
+    /// Returns whether `setting` is enabled for the key at `key`.
+    pub(super) fn enabled(&self, setting: Setting, key: usize) -> bool {
+        self.value(setting, Some(key)).is_some()
+    }
+
+    /// Returns the parser settings declared on the container.
+    pub(super) fn settings(&self) -> Settings {
+        Settings {
+            no_auto_help: self.value(Setting::NoAutoHelp, None).is_some(),
+            no_empty_help: self.value(Setting::NoEmptyHelp, None).is_some(),
+        }
+    }
+
+    /// Returns the constraints declared on the container, in the order they were written.
+    ///
+    /// Each constraint's value is the indices of its fields separated by `,`. Constraints whose
+    /// value is not such a list are skipped.
+    pub(super) fn constraints(&self) -> Vec<(ConstraintKind, Vec<usize>)> {
+        self.0
+            .iter()
+            .filter_map(|(setting, key, value)| match (setting, key) {
+                (Setting::Constraint(kind), None) => Some((
+                    *kind,
+                    value
+                        .split(',')
+                        .map(|index| index.parse().ok())
+                        .collect::<Option<Vec<_>>>()?,
+                )),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        super::ConstraintKind,
+        Record,
+        Setting,
+    };
+    use claims::{
+        assert_none,
+        assert_some,
+        assert_some_eq,
+    };
+
+    #[test]
+    fn parse() {
+        assert_some_eq!(
+            Record::parse(
+                "17:description\x1F0\x1Ffoo19:description\x1F1\x1Fa:b\x1Fc14:no_auto_help\x1F\x1F"
+            ),
+            Record(vec![
+                (Setting::Description, Some(0), "foo".to_owned()),
+                (Setting::Description, Some(1), "a:b\x1Fc".to_owned()),
+                (Setting::NoAutoHelp, None, String::new()),
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_unknown_setting() {
+        assert_some_eq!(
+            Record::parse("9:unknown\x1F\x1F8:flags\x1F2\x1F"),
+            Record(vec![(Setting::Flags, Some(2), String::new())])
+        );
+    }
+
+    #[test]
+    fn parse_empty() {
+        assert_none!(Record::parse(""));
+    }
+
+    #[test]
+    fn parse_description() {
+        assert_none!(Record::parse("struct Foo"));
+    }
+
+    #[test]
+    fn parse_truncated() {
+        assert_none!(Record::parse("20:description\x1F0\x1Ffoo"));
+    }
+
+    #[test]
+    fn parse_invalid_index() {
+        assert_none!(Record::parse("8:flags\x1Fa\x1F"));
+    }
+
+    #[test]
+    fn value() {
+        let record = assert_some!(Record::parse("12:version\x1F\x1F1.013:version\x1F0\x1F2.0"));
+
+        assert_some_eq!(record.value(Setting::Version, None), "1.0");
+        assert_some_eq!(record.value(Setting::Version, Some(0)), "2.0");
+        assert_none!(record.value(Setting::Version, Some(1)));
+        assert_none!(record.value(Setting::Group, Some(0)));
+    }
+
+    #[test]
+    fn enabled() {
+        let record = assert_some!(Record::parse("8:flags\x1F2\x1F"));
+
+        assert!(record.enabled(Setting::Flags, 2));
+        assert!(!record.enabled(Setting::Flags, 1));
+        assert!(!record.enabled(Setting::Multiple, 2));
+    }
+
+    #[test]
+    fn constraints() {
+        let record = assert_some!(Record::parse(
+            "11:one_of\x1F\x1F0,116:all_or_none\x1F\x1F0,214:at_most_one\x1F\x1Fa"
+        ));
+
+        assert_eq!(
+            record.constraints(),
+            vec![
+                (ConstraintKind::OneOf, vec![0, 1]),
+                (ConstraintKind::AllOrNone, vec![0, 2]),
+            ]
+        );
+    }
+}
@@ -0,0 +1,11 @@
+/// Parser behavior declared by the top-level type itself.
+///
+/// These are declared using `#[serde_args::generate]` parameters, and can still be overridden by
+/// the caller through the [`Parser`](crate::Parser) builder.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct Settings {
+    /// Whether the automatic `--help` option is disabled.
+    pub(crate) no_auto_help: bool,
+    /// Whether help is no longer displayed when no arguments are provided.
+    pub(crate) no_empty_help: bool,
+}
@@ -1,3 +1,4 @@
+use super::known;
 use serde::de::Expected;
 use std::{
     fmt,
@@ -6,6 +7,7 @@ use std::{
         Formatter,
         Write,
     },
+    iter,
 };
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -15,17 +17,148 @@ pub(crate) struct Field {
     pub(crate) aliases: Vec<&'static str>,
     pub(crate) shape: Shape,
     pub(crate) index: usize,
+    /// Whether values starting with a hyphen may be consumed by this field.
+    ///
+    /// Such values are only consumed when they do not match any option in scope.
+    pub(crate) allow_hyphen_values: bool,
+    /// The name displayed for this field's value when it is a positional argument.
+    ///
+    /// This only affects help and error messages. The field is still matched using `name`.
+    pub(crate) value_name: Option<String>,
+    /// The byte separating multiple values given in a single occurrence of this option.
+    ///
+    /// This is only set for optional fields containing sequences. Such options take a single value
+    /// per occurrence, and may be repeated.
+    pub(crate) value_delimiter: Option<u8>,
+    /// Whether this field captures all remaining arguments verbatim.
+    ///
+    /// Once this field's first value is found, every remaining argument is captured as one of its
+    /// values, including options and `--`.
+    pub(crate) trailing: bool,
+    /// Whether this option must be given, despite its field being optional.
+    ///
+    /// This is only set for optional fields that are not booleans.
+    pub(crate) required: bool,
+    /// Whether each variant of this option's enum is given as a separate flag, such as `--json`.
+    ///
+    /// This is only set for optional fields containing enums whose variants are all units. At most
+    /// one of the flags may be given.
+    pub(crate) flags: bool,
+    /// Whether this option takes multiple values per occurrence, such as `--nums 1 2 3`.
+    ///
+    /// This is only set for optional fields containing sequences. Values are taken until the next
+    /// option or `--`, and the option may be repeated.
+    pub(crate) multiple: bool,
+    /// Aliases that are deprecated, along with a message describing what to use instead.
+    ///
+    /// Giving the option by one of these aliases is still accepted, but records a warning.
+    pub(crate) deprecated_aliases: Vec<(&'static str, String)>,
 }
 
 impl Field {
+    /// Creates a field without a description, aliases, or any settings.
+    pub(crate) fn new(name: &'static str, shape: Shape, index: usize) -> Self {
+        Self {
+            name,
+            description: String::new(),
+            aliases: Vec::new(),
+            shape,
+            index,
+            allow_hyphen_values: false,
+            value_name: None,
+            value_delimiter: None,
+            trailing: false,
+            required: false,
+            flags: false,
+            multiple: false,
+            deprecated_aliases: Vec::new(),
+        }
+    }
+
+    /// The names and aliases this field is given by as an option.
+    ///
+    /// An option given as flags is given by the names and aliases of its variants instead.
+    pub(crate) fn option_names(&self) -> Vec<&'static str> {
+        match &self.shape {
+            Shape::Enum { variants, .. } if self.flags => variants
+                .iter()
+                .flat_map(|variant| iter::once(variant.name).chain(variant.aliases.iter().copied()))
+                .collect(),
+            _ => iter::once(self.name)
+                .chain(self.aliases.iter().copied())
+                .collect(),
+        }
+    }
+
+    /// Returns the deprecation message for the option name `name`, if it is deprecated.
+    ///
+    /// An option given as flags checks the deprecated aliases of its variants instead.
+    pub(crate) fn deprecation(&self, name: &str) -> Option<&str> {
+        match &self.shape {
+            Shape::Enum { variants, .. } if self.flags => variants
+                .iter()
+                .find_map(|variant| variant.deprecation(name, false)),
+            _ => find_deprecation(&self.deprecated_aliases, name, false),
+        }
+    }
+
+    /// The name used to display this field as a positional argument.
+    pub(crate) fn placeholder(&self) -> &str {
+        self.value_name.as_deref().unwrap_or(self.name)
+    }
+
+    /// Displays the value taken by this field when it is given as an option.
+    ///
+    /// Sequences split on a value delimiter are displayed with their delimiter.
+    pub(crate) fn option_value(&self) -> String {
+        match (&self.shape, self.value_delimiter) {
+            (Shape::Sequence(shape), Some(delimiter)) => {
+                format!("{}{}...", shape, char::from(delimiter))
+            }
+            (shape, _) => format!("{}", shape),
+        }
+    }
+
+    /// The rows listing this option in help messages.
+    ///
+    /// Each row contains the names and aliases of an option, its value, and its description. An
+    /// option given as flags is listed with a row for each of its variants.
+    pub(crate) fn help_rows(&self) -> Vec<(Vec<&'static str>, String, &str)> {
+        match &self.shape {
+            Shape::Enum { variants, .. } if self.flags => variants
+                .iter()
+                .map(|variant| {
+                    (
+                        iter::once(variant.name)
+                            .chain(variant.aliases.iter().copied())
+                            .collect(),
+                        format!("{}", variant.shape),
+                        variant.description.as_str(),
+                    )
+                })
+                .collect(),
+            _ => vec![(
+                iter::once(self.name)
+                    .chain(self.aliases.iter().copied())
+                    .collect(),
+                self.option_value(),
+                self.description.as_str(),
+            )],
+        }
+    }
+
     fn required_arguments(&self) -> Vec<(&str, &str)> {
+        // Optional positional fields are listed alongside the required arguments.
+        if let Shape::Optional(_) = self.shape {
+            return vec![(self.placeholder(), self.description.as_str())];
+        }
         let mut result = self.shape.required_arguments();
         if matches!(
             self.shape,
-            Shape::Empty { .. } | Shape::Primitive { .. } | Shape::Enum { .. }
+            Shape::Empty { .. } | Shape::Primitive { .. } | Shape::Enum { .. } | Shape::Sequence(_)
         ) {
             result.iter_mut().for_each(|(name, description)| {
-                *name = self.name;
+                *name = self.placeholder();
                 *description = self.description.as_str();
             });
         }
@@ -38,11 +171,12 @@ impl Display for Field {
         match &self.shape {
             Shape::Empty { .. } => Ok(()),
             Shape::Primitive { .. } | Shape::Enum { .. } => {
-                write!(formatter, "<{}>", self.name)
+                write!(formatter, "<{}>", self.placeholder())
             }
             Shape::Boolean { .. } => {
                 write!(formatter, "[--{}]", self.name)
             }
+            Shape::Sequence(_) => write!(formatter, "<{}>...", self.placeholder()),
             Shape::Optional(shape) => {
                 if matches!(**shape, Shape::Empty { .. }) {
                     write!(formatter, "[--{}]", self.name)
@@ -55,15 +189,95 @@ impl Display for Field {
     }
 }
 
+/// The ways a group of options can be constrained.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ConstraintKind {
+    /// Exactly one of the options must be given.
+    OneOf,
+    /// At most one of the options may be given.
+    AtMostOne,
+    /// Either all of the options or none of them must be given.
+    AllOrNone,
+}
+
+/// A constraint on which options of a struct may be given together.
+///
+/// Constraints are declared on the struct using `#[serde_args::generate]`, and are checked once
+/// the arguments have been parsed.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct Constraint {
+    pub(crate) kind: ConstraintKind,
+    /// The names of the constrained fields, which are all optional fields or boolean flags.
+    pub(crate) fields: Vec<&'static str>,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) struct Variant {
     pub(crate) name: &'static str,
     pub(crate) description: String,
     pub(crate) version: Option<String>,
+    pub(crate) group: Option<String>,
+    /// Whether every argument following this variant is a positional argument.
+    ///
+    /// No options are recognized within such a variant, as if `--` were given immediately after
+    /// its name.
+    pub(crate) raw: bool,
     pub(crate) aliases: Vec<&'static str>,
+    /// Aliases that are deprecated, along with a message describing what to use instead.
+    pub(crate) deprecated_aliases: Vec<(&'static str, String)>,
     pub(crate) shape: Shape,
 }
 
+impl Variant {
+    /// Returns the declared name of this variant if `name` matches its name or any of its aliases.
+    ///
+    /// If `case_insensitive` is set, names are compared after Unicode case folding.
+    pub(crate) fn find_name(&self, name: &str, case_insensitive: bool) -> Option<&'static str> {
+        let mut names = iter::once(self.name).chain(self.aliases.iter().copied());
+        let matched = if case_insensitive {
+            let folded = fold_case(name);
+            names.any(|variant_name| fold_case(variant_name) == folded)
+        } else {
+            names.any(|variant_name| variant_name == name)
+        };
+        matched.then_some(self.name)
+    }
+
+    /// Returns the deprecation message for the name `name`, if it is a deprecated alias.
+    ///
+    /// If `case_insensitive` is set, names are compared after Unicode case folding.
+    pub(crate) fn deprecation(&self, name: &str, case_insensitive: bool) -> Option<&str> {
+        find_deprecation(&self.deprecated_aliases, name, case_insensitive)
+    }
+}
+
+/// Finds the message of the deprecated alias matching `name`.
+fn find_deprecation<'a>(
+    deprecated_aliases: &'a [(&'static str, String)],
+    name: &str,
+    case_insensitive: bool,
+) -> Option<&'a str> {
+    let folded = case_insensitive.then(|| fold_case(name));
+    deprecated_aliases
+        .iter()
+        .find(|(alias, _)| match &folded {
+            Some(folded) => fold_case(alias) == *folded,
+            None => *alias == name,
+        })
+        .map(|(_, message)| message.as_str())
+}
+
+/// Folds the case of `name`, such that names differing only by case fold to the same value.
+///
+/// Each character is mapped to uppercase before being mapped to lowercase, which also folds
+/// characters such as `ß` and `ς` that have no single-character lowercase counterpart.
+fn fold_case(name: &str) -> String {
+    name.chars()
+        .flat_map(char::to_uppercase)
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
 impl Display for Variant {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match &self.shape {
@@ -71,6 +285,7 @@ impl Display for Variant {
             Shape::Primitive { .. }
             | Shape::Boolean { .. }
             | Shape::Optional(_)
+            | Shape::Sequence(_)
             | Shape::Enum { .. }
             | Shape::Struct { .. }
             | Shape::Variant { .. } => {
@@ -80,6 +295,56 @@ impl Display for Variant {
     }
 }
 
+/// The type requested from the deserializer for a primitive value.
+///
+/// Each kind corresponds to the deserializer method that was called, and therefore determines
+/// which values can be parsed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PrimitiveKind {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    F32,
+    F64,
+    Char,
+    Str,
+    Bytes,
+    /// Deserialized using `deserialize_any()` by an enum accepting its variants either by name or
+    /// by numeric code.
+    ///
+    /// This contains the names of the variants, which are listed in help messages since the other
+    /// accepted forms are not known.
+    Any(&'static [&'static str]),
+}
+
+impl PrimitiveKind {
+    /// Whether the primitive is an integer or floating point number.
+    pub(crate) fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Self::I8
+                | Self::I16
+                | Self::I32
+                | Self::I64
+                | Self::I128
+                | Self::U8
+                | Self::U16
+                | Self::U32
+                | Self::U64
+                | Self::U128
+                | Self::F32
+                | Self::F64
+        )
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) enum Shape {
     Empty {
@@ -90,6 +355,7 @@ pub(crate) enum Shape {
         name: String,
         description: String,
         version: Option<String>,
+        kind: PrimitiveKind,
     },
     Boolean {
         name: String,
@@ -97,18 +363,26 @@ pub(crate) enum Shape {
         version: Option<String>,
     },
     Optional(Box<Shape>),
+    /// Any number of values of the contained shape, consuming all remaining positional arguments.
+    Sequence(Box<Shape>),
     Struct {
         name: &'static str,
         description: String,
         version: Option<String>,
+        /// Examples of invocations, displayed verbatim at the end of help messages.
+        examples: Option<String>,
         required: Vec<Field>,
         optional: Vec<Field>,
         booleans: Vec<Field>,
+        /// Constraints on which of the struct's options may be given together.
+        constraints: Vec<Constraint>,
     },
     Enum {
         name: &'static str,
         description: String,
         version: Option<String>,
+        /// Examples of invocations, displayed verbatim at the end of help messages.
+        examples: Option<String>,
         variants: Vec<Variant>,
     },
     Variant {
@@ -136,15 +410,19 @@ impl Shape {
         }
     }
 
-    pub(super) fn primitive_from_visitor(expected: &dyn Expected) -> Self {
+    pub(super) fn primitive_from_visitor(expected: &dyn Expected, kind: PrimitiveKind) -> Self {
         let name = format!("{}", expected);
         let description = format!("{:#}", expected);
         let version = format!("{:v<}", expected);
 
         Self::Primitive {
             version: if version == name { None } else { Some(version) },
-            name,
+            name: match known::find(&name) {
+                Some(known) => known.name.to_owned(),
+                None => name,
+            },
             description,
+            kind,
         }
     }
 
@@ -168,7 +446,7 @@ impl Shape {
             | Self::Struct { description, .. }
             | Self::Enum { description, .. }
             | Self::Variant { description, .. } => description,
-            Self::Optional(shape) => shape.description(),
+            Self::Optional(shape) | Self::Sequence(shape) => shape.description(),
         }
     }
 
@@ -180,7 +458,45 @@ impl Shape {
             | Self::Struct { version, .. }
             | Self::Enum { version, .. }
             | Self::Variant { version, .. } => version.as_deref(),
-            Self::Optional(shape) => shape.version(),
+            Self::Optional(shape) | Self::Sequence(shape) => shape.version(),
+        }
+    }
+
+    pub(crate) fn description_mut(&mut self) -> &mut String {
+        match self {
+            Self::Empty { description, .. }
+            | Self::Primitive { description, .. }
+            | Self::Boolean { description, .. }
+            | Self::Struct { description, .. }
+            | Self::Enum { description, .. }
+            | Self::Variant { description, .. } => description,
+            Self::Optional(shape) | Self::Sequence(shape) => shape.description_mut(),
+        }
+    }
+
+    pub(crate) fn version_mut(&mut self) -> &mut Option<String> {
+        match self {
+            Self::Empty { version, .. }
+            | Self::Primitive { version, .. }
+            | Self::Boolean { version, .. }
+            | Self::Struct { version, .. }
+            | Self::Enum { version, .. }
+            | Self::Variant { version, .. } => version,
+            Self::Optional(shape) | Self::Sequence(shape) => shape.version_mut(),
+        }
+    }
+
+    /// Returns the examples declared by the container, if any.
+    ///
+    /// A variant provides the examples of the container it contains, since the examples of the
+    /// enum it belongs to describe invoking the enum itself.
+    pub(crate) fn examples(&self) -> Option<&str> {
+        match self {
+            Self::Empty { .. } | Self::Primitive { .. } | Self::Boolean { .. } => None,
+            Self::Struct { examples, .. } | Self::Enum { examples, .. } => examples.as_deref(),
+            Self::Optional(shape) | Self::Sequence(shape) | Self::Variant { shape, .. } => {
+                shape.examples()
+            }
         }
     }
 
@@ -202,7 +518,7 @@ impl Shape {
             } => {
                 result.push((name, description));
             }
-            Self::Variant { shape, .. } => {
+            Self::Sequence(shape) | Self::Variant { shape, .. } => {
                 result.extend(shape.required_arguments());
             }
             Self::Struct { required, .. } => {
@@ -223,7 +539,7 @@ impl Shape {
             | Self::Primitive { .. }
             | Self::Boolean { .. }
             | Self::Enum { .. } => {}
-            Self::Optional(shape) => {
+            Self::Optional(shape) | Self::Sequence(shape) => {
                 result.extend(shape.optional_groups());
             }
             Self::Struct {
@@ -253,7 +569,7 @@ impl Shape {
 
         match self {
             Self::Empty { .. } | Self::Primitive { .. } | Self::Boolean { .. } => {}
-            Self::Optional(shape) => {
+            Self::Optional(shape) | Self::Sequence(shape) => {
                 result.extend(shape.variant_groups());
             }
             Self::Struct {
@@ -262,10 +578,12 @@ impl Shape {
                 booleans,
                 ..
             } => {
+                // The variants of options given as flags are listed as options instead.
                 for field in required
                     .iter()
                     .chain(optional.iter())
                     .chain(booleans.iter())
+                    .filter(|field| !field.flags)
                 {
                     result.extend(field.shape.variant_groups());
                 }
@@ -281,12 +599,28 @@ impl Shape {
         result
     }
 
+    /// Returns the field selecting a command, if this is a struct whose last required argument is
+    /// an enum.
+    ///
+    /// The commands of a variant are those of the struct it contains.
+    pub(crate) fn command_field(&self) -> Option<&Field> {
+        match self {
+            Self::Struct { required, .. } => required
+                .iter()
+                .rfind(|field| !matches!(field.shape, Shape::Empty { .. }))
+                .filter(|field| matches!(field.shape, Shape::Enum { .. })),
+            Self::Variant { shape, .. } => shape.command_field(),
+            _ => None,
+        }
+    }
+
     pub(crate) fn trailing_options(&self) -> Vec<&Field> {
         match self {
             Shape::Primitive { .. }
             | Shape::Boolean { .. }
             | Shape::Empty { .. }
             | Shape::Optional(_)
+            | Shape::Sequence(_)
             | Shape::Enum { .. } => vec![],
             Shape::Variant { shape, .. } => shape.trailing_options(),
             Shape::Struct {
@@ -306,12 +640,210 @@ impl Shape {
                 .collect(),
         }
     }
+
+    /// Returns the first pair of variant names or aliases within a single enum that only differ
+    /// by case, searching this shape and all shapes nested within it.
+    pub(crate) fn case_insensitive_conflict(&self) -> Option<(&'static str, &'static str)> {
+        match self {
+            Shape::Primitive { .. } | Shape::Boolean { .. } | Shape::Empty { .. } => None,
+            Shape::Optional(shape) | Shape::Sequence(shape) => shape.case_insensitive_conflict(),
+            Shape::Struct {
+                required,
+                optional,
+                booleans,
+                ..
+            } => required
+                .iter()
+                .chain(optional)
+                .chain(booleans)
+                .find_map(|field| field.shape.case_insensitive_conflict()),
+            Shape::Enum { variants, .. } => variants_case_insensitive_conflict(variants),
+            Shape::Variant {
+                shape, variants, ..
+            } => shape
+                .case_insensitive_conflict()
+                .or_else(|| variants_case_insensitive_conflict(variants)),
+        }
+    }
+
+    /// Returns the name of the first optional positional field that is followed by another
+    /// positional argument, searching this shape and all shapes nested within it.
+    pub(crate) fn optional_positional_conflict(&self) -> Option<&'static str> {
+        match self {
+            Shape::Primitive { .. } | Shape::Boolean { .. } | Shape::Empty { .. } => None,
+            Shape::Optional(shape) | Shape::Sequence(shape) => shape.optional_positional_conflict(),
+            Shape::Struct {
+                required,
+                optional,
+                booleans,
+                ..
+            } => required
+                .iter()
+                .enumerate()
+                .find_map(|(index, field)| {
+                    if required[index + 1..]
+                        .iter()
+                        .all(|field| matches!(field.shape, Shape::Empty { .. }))
+                    {
+                        None
+                    } else if matches!(field.shape, Shape::Optional(_)) {
+                        Some(field.name)
+                    } else {
+                        field.shape.trailing_optional_positional()
+                    }
+                })
+                .or_else(|| {
+                    required
+                        .iter()
+                        .chain(optional)
+                        .chain(booleans)
+                        .find_map(|field| field.shape.optional_positional_conflict())
+                }),
+            Shape::Enum { variants, .. } => variants
+                .iter()
+                .find_map(|variant| variant.shape.optional_positional_conflict()),
+            Shape::Variant {
+                shape, variants, ..
+            } => shape.optional_positional_conflict().or_else(|| {
+                variants
+                    .iter()
+                    .find_map(|variant| variant.shape.optional_positional_conflict())
+            }),
+        }
+    }
+
+    /// Returns the name of an optional positional field that may be the last positional argument
+    /// of this shape.
+    pub(crate) fn trailing_optional_positional(&self) -> Option<&'static str> {
+        match self {
+            Shape::Primitive { .. }
+            | Shape::Boolean { .. }
+            | Shape::Empty { .. }
+            | Shape::Optional(_)
+            | Shape::Sequence(_) => None,
+            Shape::Struct { required, .. } => required
+                .iter()
+                .rev()
+                .find(|field| !matches!(field.shape, Shape::Empty { .. }))
+                .and_then(|field| {
+                    if matches!(field.shape, Shape::Optional(_)) {
+                        Some(field.name)
+                    } else {
+                        field.shape.trailing_optional_positional()
+                    }
+                }),
+            Shape::Enum { variants, .. } => variants
+                .iter()
+                .find_map(|variant| variant.shape.trailing_optional_positional()),
+            Shape::Variant {
+                shape, variants, ..
+            } => shape.trailing_optional_positional().or_else(|| {
+                variants
+                    .iter()
+                    .find_map(|variant| variant.shape.trailing_optional_positional())
+            }),
+        }
+    }
+
+    /// Returns whether any struct within this shape declares optional or boolean fields.
+    pub(crate) fn has_options(&self) -> bool {
+        match self {
+            Shape::Primitive { .. } | Shape::Boolean { .. } | Shape::Empty { .. } => false,
+            Shape::Optional(shape) | Shape::Sequence(shape) => shape.has_options(),
+            Shape::Struct {
+                required,
+                optional,
+                booleans,
+                ..
+            } => {
+                !optional.is_empty()
+                    || !booleans.is_empty()
+                    || required.iter().any(|field| field.shape.has_options())
+            }
+            Shape::Enum { variants, .. } => {
+                variants.iter().any(|variant| variant.shape.has_options())
+            }
+            Shape::Variant {
+                shape, variants, ..
+            } => shape.has_options() || variants.iter().any(|variant| variant.shape.has_options()),
+        }
+    }
+
+    /// Returns the names of the selected variants along the trailing path of this shape, along
+    /// with the innermost shape reached by that path.
+    ///
+    /// The trailing path follows the same route as `trailing_options()`: through selected
+    /// variants and the last required field of each struct. If no variant is selected along the
+    /// path, the path is empty and the innermost shape is this shape.
+    pub(crate) fn trailing_command(&self) -> (Vec<&str>, &Shape) {
+        match self {
+            Shape::Primitive { .. }
+            | Shape::Boolean { .. }
+            | Shape::Empty { .. }
+            | Shape::Optional(_)
+            | Shape::Sequence(_)
+            | Shape::Enum { .. } => (vec![], self),
+            Shape::Variant { name, shape, .. } => {
+                let (mut path, innermost) = shape.trailing_command();
+                path.insert(0, name);
+                (path, innermost)
+            }
+            Shape::Struct { required, .. } => match required.last() {
+                Some(field) => {
+                    let (path, innermost) = field.shape.trailing_command();
+                    if path.is_empty() {
+                        (path, self)
+                    } else {
+                        (path, innermost)
+                    }
+                }
+                None => (vec![], self),
+            },
+        }
+    }
+
+    /// Returns the declared name of the command matching `name`, if this shape is an enum.
+    ///
+    /// If `case_insensitive` is set, names are compared after Unicode case folding.
+    pub(crate) fn find_command(&self, name: &str, case_insensitive: bool) -> Option<&'static str> {
+        match self {
+            Shape::Enum { variants, .. } => variants
+                .iter()
+                .find_map(|variant| variant.find_name(name, case_insensitive)),
+            _ => None,
+        }
+    }
+}
+
+fn variants_case_insensitive_conflict(
+    variants: &[Variant],
+) -> Option<(&'static str, &'static str)> {
+    let mut seen: Vec<(String, &'static str)> = Vec::new();
+    for variant in variants {
+        for name in iter::once(variant.name).chain(variant.aliases.iter().copied()) {
+            let folded = fold_case(name);
+            if let Some((_, existing)) = seen
+                .iter()
+                .find(|(seen_folded, seen_name)| *seen_folded == folded && *seen_name != name)
+            {
+                return Some((existing, name));
+            }
+            seen.push((folded, name));
+        }
+    }
+    variants
+        .iter()
+        .find_map(|variant| variant.shape.case_insensitive_conflict())
 }
 
 impl Display for Shape {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         match self {
             Self::Empty { .. } => Ok(()),
+            Self::Primitive {
+                kind: PrimitiveKind::Any(variants),
+                ..
+            } => write!(formatter, "<{}>", variants.join("|")),
             Self::Primitive { name, .. } | Self::Boolean { name, .. } => {
                 write!(formatter, "<{}>", name)
             }
@@ -322,6 +854,7 @@ impl Display for Shape {
                     write!(formatter, "[--{}]", shape)
                 }
             }
+            Self::Sequence(shape) => write!(formatter, "{}...", shape),
             Self::Struct {
                 name,
                 required,
@@ -337,17 +870,47 @@ impl Display for Shape {
                         formatter.write_str("[options]")?;
                     }
                 }
-                let mut required_iter = required.iter();
+                // Optional positional fields are displayed as bracketed positional arguments.
+                let display_required = |field: &Field, formatter: &mut Formatter| {
+                    if matches!(field.shape, Shape::Optional(_)) {
+                        write!(formatter, "[<{}>]", field.placeholder())
+                    } else {
+                        Display::fmt(field, formatter)
+                    }
+                };
+                let prefix = |name: &str| if name.chars().count() <= 1 { "-" } else { "--" };
+                // Options that must be given are displayed alongside the positional arguments.
+                for field in optional.iter().filter(|field| field.required) {
+                    // Exactly one flag of an option given as flags must be given.
+                    if let (true, Shape::Enum { variants, .. }) = (field.flags, &field.shape) {
+                        formatter.write_str(" (")?;
+                        for (index, variant) in variants.iter().enumerate() {
+                            if index > 0 {
+                                formatter.write_str(" | ")?;
+                            }
+                            write!(formatter, "{}{}", prefix(variant.name), variant.name)?;
+                        }
+                        formatter.write_char(')')?;
+                        continue;
+                    }
+                    write!(formatter, " {}{}", prefix(field.name), field.name)?;
+                    let value = field.option_value();
+                    if !value.is_empty() {
+                        write!(formatter, " {}", value)?;
+                    }
+                }
+                // Unit fields consume no arguments, so they are not displayed.
+                let mut required_iter = required
+                    .iter()
+                    .filter(|field| !matches!(field.shape, Shape::Empty { .. }));
                 if let Some(field) = required_iter.next() {
                     if has_optional {
                         formatter.write_char(' ')?;
                     }
-                    Display::fmt(field, formatter)?;
-                    for field in
-                        required_iter.filter(|field| !matches!(field.shape, Shape::Empty { .. }))
-                    {
+                    display_required(field, formatter)?;
+                    for field in required_iter {
                         formatter.write_char(' ')?;
-                        Display::fmt(field, formatter)?;
+                        display_required(field, formatter)?;
                     }
                 }
                 Ok(())
@@ -366,6 +929,7 @@ impl Display for Shape {
 mod tests {
     use super::{
         Field,
+        PrimitiveKind,
         Shape,
         Variant,
     };
@@ -380,16 +944,14 @@ mod tests {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Empty {
+                Field::new(
+                    "foo",
+                    Shape::Empty {
                         description: String::new(),
                         version: None,
                     },
-                    index: 0,
-                }
+                    0
+                )
             ),
             ""
         );
@@ -400,121 +962,194 @@ mod tests {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                Field::new(
+                    "foo",
+                    Shape::Primitive {
                         name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 0,
-                }
+                    0
+                )
             ),
             "<foo>"
         );
     }
 
     #[test]
-    fn field_display_boolean() {
+    fn field_display_primitive_value_name() {
         assert_eq!(
             format!(
                 "{}",
                 Field {
-                    name: "foo",
+                    value_name: Some("FILE".into()),
+                    ..Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )
+                }
+            ),
+            "<FILE>"
+        );
+    }
+
+    #[test]
+    fn field_option_value_sequence() {
+        assert_eq!(
+            Field::new(
+                "foo",
+                Shape::Sequence(Box::new(Shape::Primitive {
+                    name: "bar".to_owned(),
                     description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Boolean {
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                })),
+                0
+            )
+            .option_value(),
+            "<bar>..."
+        );
+    }
+
+    #[test]
+    fn field_option_value_sequence_value_delimiter() {
+        assert_eq!(
+            Field {
+                value_delimiter: Some(b','),
+                ..Field::new(
+                    "foo",
+                    Shape::Sequence(Box::new(Shape::Primitive {
                         name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
-                    },
-                    index: 0,
-                }
-            ),
-            "[--foo]"
+                        kind: PrimitiveKind::Str,
+                    })),
+                    0
+                )
+            }
+            .option_value(),
+            "<bar>,..."
         );
     }
 
     #[test]
-    fn field_display_optional_empty() {
+    fn field_display_boolean() {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Optional(Box::new(Shape::Empty {
+                Field::new(
+                    "foo",
+                    Shape::Boolean {
+                        name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
-                    })),
-                    index: 0,
-                }
+                    },
+                    0
+                )
             ),
             "[--foo]"
         );
     }
 
     #[test]
-    fn field_display_optional_primitive() {
+    fn field_display_sequence() {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Optional(Box::new(Shape::Primitive {
+                Field::new(
+                    "foo",
+                    Shape::Sequence(Box::new(Shape::Primitive {
                         name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     })),
-                    index: 0,
-                }
+                    0
+                )
             ),
-            "[--foo <bar>]"
+            "<foo>..."
         );
     }
 
     #[test]
-    fn field_display_optional_boolean() {
+    fn field_display_optional_empty() {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Optional(Box::new(Shape::Boolean {
-                        name: "bar".to_owned(),
+                Field::new(
+                    "foo",
+                    Shape::Optional(Box::new(Shape::Empty {
                         description: String::new(),
                         version: None,
                     })),
-                    index: 0,
-                }
+                    0
+                )
             ),
-            "[--foo <bar>]"
+            "[--foo]"
         );
     }
 
     #[test]
-    fn field_display_optional_optional() {
+    fn field_display_optional_primitive() {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Optional(Box::new(Shape::Optional(Box::new(Shape::Primitive {
+                Field::new(
+                    "foo",
+                    Shape::Optional(Box::new(Shape::Primitive {
                         name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
+                    })),
+                    0
+                )
+            ),
+            "[--foo <bar>]"
+        );
+    }
+
+    #[test]
+    fn field_display_optional_boolean() {
+        assert_eq!(
+            format!(
+                "{}",
+                Field::new(
+                    "foo",
+                    Shape::Optional(Box::new(Shape::Boolean {
+                        name: "bar".to_owned(),
+                        description: String::new(),
+                        version: None,
+                    })),
+                    0
+                )
+            ),
+            "[--foo <bar>]"
+        );
+    }
+
+    #[test]
+    fn field_display_optional_optional() {
+        assert_eq!(
+            format!(
+                "{}",
+                Field::new(
+                    "foo",
+                    Shape::Optional(Box::new(Shape::Optional(Box::new(Shape::Primitive {
+                        name: "bar".to_owned(),
+                        description: String::new(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
                     })))),
-                    index: 0,
-                }
+                    0
+                )
             ),
             "[--foo [--<bar>]]"
         );
@@ -525,14 +1160,13 @@ mod tests {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Optional(Box::new(Shape::Struct {
+                Field::new(
+                    "foo",
+                    Shape::Optional(Box::new(Shape::Struct {
                         name: "",
                         description: String::new(),
                         version: None,
+                        examples: None,
                         required: vec![
                             Field {
                                 name: "bar",
@@ -542,8 +1176,17 @@ mod tests {
                                     name: "foo".to_owned(),
                                     description: String::new(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
                                 index: 0,
+                                allow_hyphen_values: false,
+                                value_name: None,
+                                value_delimiter: None,
+                                trailing: false,
+                                required: false,
+                                flags: false,
+                                multiple: false,
+                                deprecated_aliases: Vec::new(),
                             },
                             Field {
                                 name: "baz",
@@ -553,15 +1196,25 @@ mod tests {
                                     name: "foo".to_owned(),
                                     description: String::new(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
                                 index: 1,
+                                allow_hyphen_values: false,
+                                value_name: None,
+                                value_delimiter: None,
+                                trailing: false,
+                                required: false,
+                                flags: false,
+                                multiple: false,
+                                deprecated_aliases: Vec::new(),
                             },
                         ],
                         optional: vec![],
                         booleans: vec![],
+                        constraints: vec![],
                     })),
-                    index: 0,
-                }
+                    0
+                )
             ),
             "[--foo <bar> <baz>]"
         );
@@ -572,18 +1225,17 @@ mod tests {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Optional(Box::new(Shape::Enum {
+                Field::new(
+                    "foo",
+                    Shape::Optional(Box::new(Shape::Enum {
                         name: "bar",
                         description: String::new(),
                         version: None,
+                        examples: None,
                         variants: vec![],
                     })),
-                    index: 0,
-                }
+                    0
+                )
             ),
             "[--foo <bar>]"
         );
@@ -594,11 +1246,9 @@ mod tests {
         assert_eq!(
             format!(
                 "{}",
-                Field {
-                    name: "foo",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Optional(Box::new(Shape::Variant {
+                Field::new(
+                    "foo",
+                    Shape::Optional(Box::new(Shape::Variant {
                         name: "bar",
                         description: String::new(),
                         version: None,
@@ -606,12 +1256,13 @@ mod tests {
                             name: "baz".into(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         }),
                         variants: vec![],
                         enum_name: "qux",
                     })),
-                    index: 0,
-                }
+                    0
+                )
             ),
             "[--foo bar <baz>]"
         );
@@ -626,7 +1277,10 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: Vec::new(),
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Empty {
                         description: String::new(),
                         version: None,
@@ -646,11 +1300,15 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: Vec::new(),
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Primitive {
                         name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
                 }
             ),
@@ -667,11 +1325,15 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: Vec::new(),
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Optional(Box::new(Shape::Primitive {
                         name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     })),
                 }
             ),
@@ -688,34 +1350,37 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: Vec::new(),
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Struct {
                         name: "",
                         description: String::new(),
                         version: None,
-                        required: vec![Field {
-                            name: "bar",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                        examples: None,
+                        required: vec![Field::new(
+                            "bar",
+                            Shape::Primitive {
                                 name: "baz".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },],
-                        optional: vec![Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: vec![],
-                            shape: Shape::Primitive {
+                            0
+                        ),],
+                        optional: vec![Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "quux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },],
+                            1
+                        ),],
                         booleans: vec![],
+                        constraints: vec![],
                     },
                 }
             ),
@@ -732,17 +1397,24 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: Vec::new(),
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Enum {
                         name: "bar",
                         description: String::new(),
                         version: None,
+                        examples: None,
                         variants: vec![
                             Variant {
                                 name: "baz",
                                 description: String::new(),
                                 version: None,
+                                group: None,
+                                raw: false,
                                 aliases: vec![],
+                                deprecated_aliases: Vec::new(),
                                 shape: Shape::Empty {
                                     description: String::new(),
                                     version: None,
@@ -752,7 +1424,10 @@ mod tests {
                                 name: "qux",
                                 description: String::new(),
                                 version: None,
+                                group: None,
+                                raw: false,
                                 aliases: vec![],
+                                deprecated_aliases: Vec::new(),
                                 shape: Shape::Empty {
                                     description: String::new(),
                                     version: None,
@@ -775,7 +1450,10 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: Vec::new(),
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Variant {
                         name: "bar",
                         description: String::new(),
@@ -784,6 +1462,7 @@ mod tests {
                             name: "baz".into(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         }),
                         variants: vec![],
                         enum_name: "qux",
@@ -808,11 +1487,12 @@ mod tests {
     #[test]
     fn shape_primitive_from_visitor() {
         assert_eq!(
-            Shape::primitive_from_visitor(&IgnoredAny),
+            Shape::primitive_from_visitor(&IgnoredAny, PrimitiveKind::U64),
             Shape::Primitive {
                 name: "anything at all".to_owned(),
                 description: "anything at all".to_owned(),
                 version: None,
+                kind: PrimitiveKind::U64,
             }
         );
     }
@@ -836,6 +1516,7 @@ mod tests {
                 name: "foo".into(),
                 description: "bar".into(),
                 version: None,
+                kind: PrimitiveKind::Str,
             }
             .description(),
             "bar"
@@ -849,6 +1530,7 @@ mod tests {
                 name: "foo".into(),
                 description: "bar".into(),
                 version: None,
+                kind: PrimitiveKind::Str,
             }))
             .description(),
             "bar"
@@ -862,19 +1544,20 @@ mod tests {
                 name: "",
                 description: "foo".into(),
                 version: None,
-                required: vec![Field {
-                    name: "bar",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                examples: None,
+                required: vec![Field::new(
+                    "bar",
+                    Shape::Primitive {
                         name: "baz".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 0,
-                },],
+                    0
+                ),],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
             .description(),
             "foo"
@@ -888,6 +1571,7 @@ mod tests {
                 name: "foo",
                 description: "bar".into(),
                 version: None,
+                examples: None,
                 variants: vec![],
             }
             .description(),
@@ -906,6 +1590,7 @@ mod tests {
                     name: "baz".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }),
                 enum_name: "qux",
                 variants: vec![],
@@ -943,6 +1628,7 @@ mod tests {
                 name: String::new(),
                 description: String::new(),
                 version: Some("foo".into()),
+                kind: PrimitiveKind::Str,
             }
             .version(),
             "foo"
@@ -955,6 +1641,7 @@ mod tests {
             name: String::new(),
             description: String::new(),
             version: None,
+            kind: PrimitiveKind::Str,
         }
         .version());
     }
@@ -1010,9 +1697,11 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: Some("foo".into()),
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
             .version(),
             "foo"
@@ -1025,9 +1714,11 @@ mod tests {
             name: "",
             description: String::new(),
             version: None,
+            examples: None,
             required: vec![],
             optional: vec![],
             booleans: vec![],
+            constraints: vec![],
         }
         .version());
     }
@@ -1039,6 +1730,7 @@ mod tests {
                 name: "",
                 description: String::new(),
                 version: Some("foo".into()),
+                examples: None,
                 variants: vec![],
             }
             .version(),
@@ -1052,6 +1744,7 @@ mod tests {
             name: "",
             description: String::new(),
             version: None,
+            examples: None,
             variants: vec![],
         }
         .version());
@@ -1093,289 +1786,588 @@ mod tests {
     }
 
     #[test]
-    fn shape_empty_required_arguments() {
-        assert_eq!(
-            Shape::Empty {
-                description: String::new(),
-                version: None,
-            }
-            .required_arguments(),
-            vec![]
-        );
-    }
-
-    #[test]
-    fn shape_primitive_required_arguments() {
-        assert_eq!(
-            Shape::Primitive {
-                name: "foo".into(),
-                description: "bar".into(),
-                version: None,
-            }
-            .required_arguments(),
-            vec![("foo", "bar")]
-        );
-    }
-
-    #[test]
-    fn shape_optional_required_arguments() {
-        assert_eq!(
-            Shape::Optional(Box::new(Shape::Primitive {
-                name: "foo".into(),
-                description: "bar".into(),
-                version: None,
-            }))
-            .required_arguments(),
-            vec![]
-        );
+    fn shape_primitive_no_examples() {
+        assert_none!(Shape::Primitive {
+            name: String::new(),
+            description: String::new(),
+            version: None,
+            kind: PrimitiveKind::Str,
+        }
+        .examples());
     }
 
     #[test]
-    fn shape_struct_required_arguments() {
-        assert_eq!(
+    fn shape_struct_examples() {
+        assert_some_eq!(
             Shape::Struct {
-                name: "Struct",
+                name: "",
                 description: String::new(),
                 version: None,
-                required: vec![Field {
-                    name: "foo",
-                    description: "bar".into(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "baz".to_owned(),
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 0,
-                },],
-                optional: vec![Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "quux".to_owned(),
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 1,
-                },],
+                examples: Some("foo".into()),
+                required: vec![],
+                optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
-            .required_arguments(),
-            vec![("foo", "bar")]
+            .examples(),
+            "foo"
         );
     }
 
     #[test]
-    fn shape_enum_required_arguments() {
-        assert_eq!(
+    fn shape_enum_examples() {
+        assert_some_eq!(
             Shape::Enum {
-                name: "foo",
-                description: "bar".into(),
+                name: "",
+                description: String::new(),
                 version: None,
-                variants: vec![Variant {
-                    name: "baz",
-                    description: "qux".into(),
-                    version: None,
-                    aliases: vec![],
-                    shape: Shape::Empty {
-                        description: String::new(),
-                        version: None,
-                    },
-                }],
+                examples: Some("foo".into()),
+                variants: vec![],
             }
-            .required_arguments(),
-            vec![("foo", "bar")]
+            .examples(),
+            "foo"
         );
     }
 
     #[test]
-    fn shape_variant_required_arguments() {
-        assert_eq!(
+    fn shape_variant_examples() {
+        assert_some_eq!(
             Shape::Variant {
-                name: "foo",
-                description: "bar".into(),
+                name: "",
+                description: String::new(),
                 version: None,
-                shape: Box::new(Shape::Primitive {
-                    name: "baz".into(),
-                    description: "qux".into(),
+                shape: Box::new(Shape::Struct {
+                    name: "",
+                    description: String::new(),
                     version: None,
+                    examples: Some("foo".into()),
+                    required: vec![],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
                 }),
+                enum_name: "",
                 variants: vec![],
-                enum_name: "quux",
             }
-            .required_arguments(),
-            vec![("baz", "qux")]
+            .examples(),
+            "foo"
         );
     }
 
     #[test]
-    fn shape_empty_optional_groups() {
+    fn shape_empty_required_arguments() {
         assert_eq!(
             Shape::Empty {
                 description: String::new(),
                 version: None,
             }
-            .optional_groups(),
+            .required_arguments(),
             vec![]
         );
     }
 
     #[test]
-    fn shape_primitive_optional_groups() {
+    fn shape_primitive_required_arguments() {
         assert_eq!(
             Shape::Primitive {
                 name: "foo".into(),
                 description: "bar".into(),
                 version: None,
+                kind: PrimitiveKind::Str,
             }
-            .optional_groups(),
-            vec![]
+            .required_arguments(),
+            vec![("foo", "bar")]
         );
     }
 
     #[test]
-    fn shape_optional_optional_groups() {
+    fn shape_optional_required_arguments() {
         assert_eq!(
             Shape::Optional(Box::new(Shape::Primitive {
                 name: "foo".into(),
                 description: "bar".into(),
                 version: None,
+                kind: PrimitiveKind::Str,
             }))
-            .optional_groups(),
+            .required_arguments(),
             vec![]
         );
     }
 
     #[test]
-    fn shape_optional_struct_optional_groups() {
+    fn shape_struct_required_arguments() {
         assert_eq!(
-            Shape::Optional(Box::new(Shape::Struct {
+            Shape::Struct {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![Field {
-                    name: "foo",
                     description: "bar".into(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "baz".to_owned(),
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 0,
+                    ..Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )
                 },],
-                optional: vec![Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                optional: vec![Field::new(
+                    "qux",
+                    Shape::Primitive {
                         name: "quux".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 1,
-                },],
+                    1
+                ),],
                 booleans: vec![],
-            }))
-            .optional_groups(),
-            vec![(
-                "Struct",
-                vec![&Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "quux".to_owned(),
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 1,
-                },]
-            )]
+                constraints: vec![],
+            }
+            .required_arguments(),
+            vec![("foo", "bar")]
         );
     }
 
     #[test]
-    fn shape_optional_struct_optional_groups_booleans() {
+    fn shape_struct_required_arguments_value_name() {
         assert_eq!(
-            Shape::Optional(Box::new(Shape::Struct {
+            Shape::Struct {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![Field {
-                    name: "foo",
                     description: "bar".into(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "baz".to_owned(),
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 0,
+                    value_name: Some("FILE".into()),
+                    ..Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )
                 },],
                 optional: vec![],
-                booleans: vec![Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Empty {
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 1,
-                },],
-            }))
-            .optional_groups(),
-            vec![(
-                "Struct",
-                vec![&Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Empty {
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 1,
-                },]
-            )]
+                booleans: vec![],
+                constraints: vec![],
+            }
+            .required_arguments(),
+            vec![("FILE", "bar")]
         );
     }
 
     #[test]
-    fn shape_struct_no_options_optional_groups() {
+    fn shape_struct_required_arguments_optional_positional() {
         assert_eq!(
             Shape::Struct {
-                name: "Struct",
+                name: "",
                 description: String::new(),
                 version: None,
+                examples: None,
+                required: vec![
+                    Field {
+                        description: "bar".into(),
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "bar".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    },
+                    Field {
+                        description: "qux".into(),
+                        ..Field::new(
+                            "baz",
+                            Shape::Optional(Box::new(Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            1
+                        )
+                    },
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+            .required_arguments(),
+            vec![("foo", "bar"), ("baz", "qux")]
+        );
+    }
+
+    #[test]
+    fn shape_struct_optional_positional_last() {
+        assert_eq!(
+            Shape::Struct {
+                name: "",
+                description: String::new(),
+                version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "bar".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
+                    },
+                    Field {
+                        description: "qux".into(),
+                        ..Field::new(
+                            "baz",
+                            Shape::Optional(Box::new(Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            1
+                        )
+                    },
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+            .optional_positional_conflict(),
+            None
+        );
+    }
+
+    #[test]
+    fn shape_struct_optional_positional_not_last() {
+        assert_eq!(
+            Shape::Struct {
+                name: "",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field {
+                        description: "bar".into(),
+                        ..Field::new(
+                            "foo",
+                            Shape::Optional(Box::new(Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            })),
+                            0
+                        )
+                    },
+                    Field {
+                        description: "qux".into(),
+                        ..Field::new(
+                            "baz",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        )
+                    },
+                ],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+            .optional_positional_conflict(),
+            Some("foo")
+        );
+    }
+
+    #[test]
+    fn shape_display_struct_optional_positional() {
+        assert_eq!(
+            format!(
+                "{}",
+                Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field {
+                            description: "bar".into(),
+                            ..Field::new(
+                                "foo",
+                                Shape::Primitive {
+                                    name: "bar".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                0
+                            )
+                        },
+                        Field {
+                            description: "qux".into(),
+                            ..Field::new(
+                                "baz",
+                                Shape::Optional(Box::new(Shape::Primitive {
+                                    name: "qux".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                })),
+                                1
+                            )
+                        },
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            "<foo> [<baz>]"
+        );
+    }
+
+    #[test]
+    fn shape_enum_required_arguments() {
+        assert_eq!(
+            Shape::Enum {
+                name: "foo",
+                description: "bar".into(),
+                version: None,
+                examples: None,
+                variants: vec![Variant {
+                    name: "baz",
+                    description: "qux".into(),
+                    version: None,
+                    group: None,
+                    raw: false,
+                    aliases: vec![],
+                    deprecated_aliases: Vec::new(),
+                    shape: Shape::Empty {
+                        description: String::new(),
+                        version: None,
+                    },
+                }],
+            }
+            .required_arguments(),
+            vec![("foo", "bar")]
+        );
+    }
+
+    #[test]
+    fn shape_variant_required_arguments() {
+        assert_eq!(
+            Shape::Variant {
+                name: "foo",
+                description: "bar".into(),
+                version: None,
+                shape: Box::new(Shape::Primitive {
+                    name: "baz".into(),
+                    description: "qux".into(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }),
+                variants: vec![],
+                enum_name: "quux",
+            }
+            .required_arguments(),
+            vec![("baz", "qux")]
+        );
+    }
+
+    #[test]
+    fn shape_empty_optional_groups() {
+        assert_eq!(
+            Shape::Empty {
+                description: String::new(),
+                version: None,
+            }
+            .optional_groups(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn shape_primitive_optional_groups() {
+        assert_eq!(
+            Shape::Primitive {
+                name: "foo".into(),
+                description: "bar".into(),
+                version: None,
+                kind: PrimitiveKind::Str,
+            }
+            .optional_groups(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn shape_optional_optional_groups() {
+        assert_eq!(
+            Shape::Optional(Box::new(Shape::Primitive {
+                name: "foo".into(),
+                description: "bar".into(),
+                version: None,
+                kind: PrimitiveKind::Str,
+            }))
+            .optional_groups(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn shape_optional_struct_optional_groups() {
+        assert_eq!(
+            Shape::Optional(Box::new(Shape::Struct {
+                name: "Struct",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![Field {
+                    description: "bar".into(),
+                    ..Field::new(
+                        "foo",
+                        Shape::Primitive {
                             name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 0,
+                        0
+                    )
+                },],
+                optional: vec![Field::new(
+                    "qux",
+                    Shape::Primitive {
+                        name: "quux".to_owned(),
+                        description: String::new(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    Field {
-                        name: "qux",
+                    1
+                ),],
+                booleans: vec![],
+                constraints: vec![],
+            }))
+            .optional_groups(),
+            vec![(
+                "Struct",
+                vec![&Field::new(
+                    "qux",
+                    Shape::Primitive {
+                        name: "quux".to_owned(),
                         description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "quux".to_owned(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    1
+                ),]
+            )]
+        );
+    }
+
+    #[test]
+    fn shape_optional_struct_optional_groups_booleans() {
+        assert_eq!(
+            Shape::Optional(Box::new(Shape::Struct {
+                name: "Struct",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![Field {
+                    description: "bar".into(),
+                    ..Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
+                        0
+                    )
+                },],
+                optional: vec![],
+                booleans: vec![Field::new(
+                    "qux",
+                    Shape::Empty {
+                        description: String::new(),
+                        version: None,
+                    },
+                    1
+                ),],
+                constraints: vec![],
+            }))
+            .optional_groups(),
+            vec![(
+                "Struct",
+                vec![&Field::new(
+                    "qux",
+                    Shape::Empty {
+                        description: String::new(),
+                        version: None,
+                    },
+                    1
+                ),]
+            )]
+        );
+    }
+
+    #[test]
+    fn shape_struct_no_options_optional_groups() {
+        assert_eq!(
+            Shape::Struct {
+                name: "Struct",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![
+                    Field {
+                        description: "bar".into(),
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
+                            name: "quux".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
             .optional_groups(),
             vec![],
@@ -1389,59 +2381,63 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "quux".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 booleans: vec![],
+                constraints: vec![],
             }
             .optional_groups(),
             vec![(
                 "Struct",
                 vec![
                     &Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
-                    &Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    &Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "quux".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ]
             )],
         );
@@ -1454,52 +2450,73 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Struct {
-                            name: "Nested",
-                            description: String::new(),
-                            version: None,
-                            required: vec![],
-                            optional: vec![
-                                Field {
-                                    name: "foo",
-                                    description: "bar".into(),
-                                    aliases: Vec::new(),
-                                    shape: Shape::Primitive {
-                                        name: "baz".to_owned(),
-                                        description: String::new(),
-                                        version: None,
+                        ..Field::new(
+                            "foo",
+                            Shape::Struct {
+                                name: "Nested",
+                                description: String::new(),
+                                version: None,
+                                examples: None,
+                                required: vec![],
+                                optional: vec![
+                                    Field {
+                                        name: "foo",
+                                        description: "bar".into(),
+                                        aliases: Vec::new(),
+                                        shape: Shape::Primitive {
+                                            name: "baz".to_owned(),
+                                            description: String::new(),
+                                            version: None,
+                                            kind: PrimitiveKind::Str,
+                                        },
+                                        index: 0,
+                                        allow_hyphen_values: false,
+                                        value_name: None,
+                                        value_delimiter: None,
+                                        trailing: false,
+                                        required: false,
+                                        flags: false,
+                                        multiple: false,
+                                        deprecated_aliases: Vec::new(),
                                     },
-                                    index: 0,
-                                },
-                                Field {
-                                    name: "qux",
-                                    description: String::new(),
-                                    aliases: Vec::new(),
-                                    shape: Shape::Primitive {
-                                        name: "quux".to_owned(),
+                                    Field {
+                                        name: "qux",
                                         description: String::new(),
-                                        version: None,
+                                        aliases: Vec::new(),
+                                        shape: Shape::Primitive {
+                                            name: "quux".to_owned(),
+                                            description: String::new(),
+                                            version: None,
+                                            kind: PrimitiveKind::Str,
+                                        },
+                                        index: 1,
+                                        allow_hyphen_values: false,
+                                        value_name: None,
+                                        value_delimiter: None,
+                                        trailing: false,
+                                        required: false,
+                                        flags: false,
+                                        multiple: false,
+                                        deprecated_aliases: Vec::new(),
                                     },
-                                    index: 1,
-                                },
-                            ],
-                            booleans: vec![],
-                        },
-                        index: 0,
+                                ],
+                                booleans: vec![],
+                                constraints: vec![],
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Struct {
+                    Field::new(
+                        "qux",
+                        Shape::Struct {
                             name: "NotIncluded",
                             description: String::new(),
                             version: None,
+                            examples: None,
                             required: vec![
                                 Field {
                                     name: "foo",
@@ -1509,8 +2526,17 @@ mod tests {
                                         name: "baz".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 0,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },
                                 Field {
                                     name: "qux",
@@ -1520,24 +2546,33 @@ mod tests {
                                         name: "quux".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 1,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },
                             ],
                             optional: vec![],
                             booleans: vec![],
+                            constraints: vec![],
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
-                optional: vec![Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Struct {
+                optional: vec![Field::new(
+                    "qux",
+                    Shape::Struct {
                         name: "NotIncluded",
                         description: String::new(),
                         version: None,
+                        examples: None,
                         required: vec![
                             Field {
                                 name: "foo",
@@ -1547,8 +2582,17 @@ mod tests {
                                     name: "baz".to_owned(),
                                     description: String::new(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
                                 index: 0,
+                                allow_hyphen_values: false,
+                                value_name: None,
+                                value_delimiter: None,
+                                trailing: false,
+                                required: false,
+                                flags: false,
+                                multiple: false,
+                                deprecated_aliases: Vec::new(),
                             },
                             Field {
                                 name: "qux",
@@ -1558,29 +2602,39 @@ mod tests {
                                     name: "quux".to_owned(),
                                     description: String::new(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
                                 index: 1,
+                                allow_hyphen_values: false,
+                                value_name: None,
+                                value_delimiter: None,
+                                trailing: false,
+                                required: false,
+                                flags: false,
+                                multiple: false,
+                                deprecated_aliases: Vec::new(),
                             },
                         ],
                         optional: vec![],
                         booleans: vec![],
+                        constraints: vec![],
                     },
-                    index: 0,
-                },],
+                    0
+                ),],
                 booleans: vec![],
+                constraints: vec![],
             }
             .optional_groups(),
             vec![
                 (
                     "Struct",
-                    vec![&Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Struct {
+                    vec![&Field::new(
+                        "qux",
+                        Shape::Struct {
                             name: "NotIncluded",
                             description: String::new(),
                             version: None,
+                            examples: None,
                             required: vec![
                                 Field {
                                     name: "foo",
@@ -1590,8 +2644,17 @@ mod tests {
                                         name: "baz".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 0,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },
                                 Field {
                                     name: "qux",
@@ -1601,41 +2664,52 @@ mod tests {
                                         name: "quux".to_owned(),
                                         description: String::new(),
                                         version: None,
+                                        kind: PrimitiveKind::Str,
                                     },
                                     index: 1,
+                                    allow_hyphen_values: false,
+                                    value_name: None,
+                                    value_delimiter: None,
+                                    trailing: false,
+                                    required: false,
+                                    flags: false,
+                                    multiple: false,
+                                    deprecated_aliases: Vec::new(),
                                 },
                             ],
                             optional: vec![],
                             booleans: vec![],
+                            constraints: vec![],
                         },
-                        index: 0,
-                    },]
+                        0
+                    ),]
                 ),
                 (
                     "Nested",
                     vec![
                         &Field {
-                            name: "foo",
                             description: "bar".into(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
-                                name: "baz".to_owned(),
-                                description: String::new(),
-                                version: None,
-                            },
-                            index: 0,
+                            ..Field::new(
+                                "foo",
+                                Shape::Primitive {
+                                    name: "baz".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                0
+                            )
                         },
-                        &Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        &Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "quux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ]
                 ),
             ],
@@ -1649,41 +2723,48 @@ mod tests {
                 name: "foo",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![Variant {
                     name: "baz",
                     description: "qux".into(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: vec![],
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Struct {
                         name: "Struct",
                         description: String::new(),
                         version: None,
+                        examples: None,
                         required: vec![],
                         optional: vec![
                             Field {
-                                name: "foo",
                                 description: "bar".into(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
-                                    name: "baz".to_owned(),
-                                    description: String::new(),
-                                    version: None,
-                                },
-                                index: 0,
+                                ..Field::new(
+                                    "foo",
+                                    Shape::Primitive {
+                                        name: "baz".to_owned(),
+                                        description: String::new(),
+                                        version: None,
+                                        kind: PrimitiveKind::Str,
+                                    },
+                                    0
+                                )
                             },
-                            Field {
-                                name: "qux",
-                                description: String::new(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
+                            Field::new(
+                                "qux",
+                                Shape::Primitive {
                                     name: "quux".to_owned(),
                                     description: String::new(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
-                                index: 1,
-                            },
+                                1
+                            ),
                         ],
                         booleans: vec![],
+                        constraints: vec![],
                     },
                 }],
             }
@@ -1703,68 +2784,77 @@ mod tests {
                     name: "Struct",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![
                         Field {
-                            name: "foo",
                             description: "bar".into(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
-                                name: "baz".to_owned(),
-                                description: String::new(),
-                                version: None,
-                            },
-                            index: 0,
+                            ..Field::new(
+                                "foo",
+                                Shape::Primitive {
+                                    name: "baz".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                0
+                            )
                         },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "quux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 },),
                 variants: vec![Variant {
                     name: "baz",
                     description: "qux".into(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: vec![],
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Struct {
                         name: "Struct",
                         description: String::new(),
                         version: None,
+                        examples: None,
                         required: vec![],
                         optional: vec![
                             Field {
-                                name: "foo",
                                 description: "bar".into(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
-                                    name: "baz".to_owned(),
-                                    description: String::new(),
-                                    version: None,
-                                },
-                                index: 0,
+                                ..Field::new(
+                                    "foo",
+                                    Shape::Primitive {
+                                        name: "baz".to_owned(),
+                                        description: String::new(),
+                                        version: None,
+                                        kind: PrimitiveKind::Str,
+                                    },
+                                    0
+                                )
                             },
-                            Field {
-                                name: "qux",
-                                description: String::new(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
+                            Field::new(
+                                "qux",
+                                Shape::Primitive {
                                     name: "quux".to_owned(),
                                     description: String::new(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
-                                index: 1,
-                            },
+                                1
+                            ),
                         ],
                         booleans: vec![],
+                        constraints: vec![],
                     },
                 }],
                 enum_name: "Enum",
@@ -1774,27 +2864,28 @@ mod tests {
                 "Struct",
                 vec![
                     &Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
-                    &Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    &Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "quux".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ]
             )]
         );
@@ -1819,6 +2910,7 @@ mod tests {
                 name: "foo".into(),
                 description: "bar".into(),
                 version: None,
+                kind: PrimitiveKind::Str,
             }
             .variant_groups(),
             vec![]
@@ -1844,12 +2936,16 @@ mod tests {
                 name: "Enum",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "foo",
                         description: "bar".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -1859,7 +2955,10 @@ mod tests {
                         name: "baz",
                         description: "qux".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -1875,7 +2974,10 @@ mod tests {
                         name: "foo",
                         description: "bar".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -1885,7 +2987,10 @@ mod tests {
                         name: "baz",
                         description: "qux".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -1903,32 +3008,35 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "quux".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
             .variant_groups(),
             vec![]
@@ -1942,54 +3050,65 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Enum {
-                            name: "Enum1",
-                            description: String::new(),
-                            version: None,
-                            variants: vec![
-                                Variant {
-                                    name: "a",
-                                    description: "b".into(),
-                                    version: None,
-                                    aliases: vec![],
-                                    shape: Shape::Empty {
-                                        description: String::new(),
+                        ..Field::new(
+                            "foo",
+                            Shape::Enum {
+                                name: "Enum1",
+                                description: String::new(),
+                                version: None,
+                                examples: None,
+                                variants: vec![
+                                    Variant {
+                                        name: "a",
+                                        description: "b".into(),
                                         version: None,
+                                        group: None,
+                                        raw: false,
+                                        aliases: vec![],
+                                        deprecated_aliases: Vec::new(),
+                                        shape: Shape::Empty {
+                                            description: String::new(),
+                                            version: None,
+                                        },
                                     },
-                                },
-                                Variant {
-                                    name: "c",
-                                    description: "d".into(),
-                                    version: None,
-                                    aliases: vec![],
-                                    shape: Shape::Empty {
-                                        description: String::new(),
+                                    Variant {
+                                        name: "c",
+                                        description: "d".into(),
                                         version: None,
+                                        group: None,
+                                        raw: false,
+                                        aliases: vec![],
+                                        deprecated_aliases: Vec::new(),
+                                        shape: Shape::Empty {
+                                            description: String::new(),
+                                            version: None,
+                                        },
                                     },
-                                },
-                            ],
-                        },
-                        index: 0,
+                                ],
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Enum {
+                    Field::new(
+                        "qux",
+                        Shape::Enum {
                             name: "Enum2",
                             description: String::new(),
                             version: None,
+                            examples: None,
                             variants: vec![
                                 Variant {
                                     name: "e",
                                     description: "f".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: String::new(),
                                         version: None,
@@ -1999,7 +3118,10 @@ mod tests {
                                     name: "g",
                                     description: "h".into(),
                                     version: None,
+                                    group: None,
+                                    raw: false,
                                     aliases: vec![],
+                                    deprecated_aliases: Vec::new(),
                                     shape: Shape::Empty {
                                         description: String::new(),
                                         version: None,
@@ -2007,11 +3129,12 @@ mod tests {
                                 },
                             ],
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
             .variant_groups(),
             vec![
@@ -2022,7 +3145,10 @@ mod tests {
                             name: "a",
                             description: "b".into(),
                             version: None,
+                            group: None,
+                            raw: false,
                             aliases: vec![],
+                            deprecated_aliases: Vec::new(),
                             shape: Shape::Empty {
                                 description: String::new(),
                                 version: None,
@@ -2032,7 +3158,10 @@ mod tests {
                             name: "c",
                             description: "d".into(),
                             version: None,
+                            group: None,
+                            raw: false,
                             aliases: vec![],
+                            deprecated_aliases: Vec::new(),
                             shape: Shape::Empty {
                                 description: String::new(),
                                 version: None,
@@ -2047,7 +3176,10 @@ mod tests {
                             name: "e",
                             description: "f".into(),
                             version: None,
+                            group: None,
+                            raw: false,
                             aliases: vec![],
+                            deprecated_aliases: Vec::new(),
                             shape: Shape::Empty {
                                 description: String::new(),
                                 version: None,
@@ -2057,7 +3189,10 @@ mod tests {
                             name: "g",
                             description: "h".into(),
                             version: None,
+                            group: None,
+                            raw: false,
                             aliases: vec![],
+                            deprecated_aliases: Vec::new(),
                             shape: Shape::Empty {
                                 description: String::new(),
                                 version: None,
@@ -2076,12 +3211,16 @@ mod tests {
                 name: "Enum",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![
                     Variant {
                         name: "foo",
                         description: "bar".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2091,7 +3230,10 @@ mod tests {
                         name: "baz",
                         description: "qux".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2107,7 +3249,10 @@ mod tests {
                         name: "foo",
                         description: "bar".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2117,7 +3262,10 @@ mod tests {
                         name: "baz",
                         description: "qux".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2139,6 +3287,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }),
                 enum_name: "baz",
                 variants: vec![
@@ -2146,7 +3295,10 @@ mod tests {
                         name: "foo",
                         description: "bar".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2156,7 +3308,10 @@ mod tests {
                         name: "baz",
                         description: "qux".into(),
                         version: None,
+                        group: None,
+                        raw: false,
                         aliases: vec![],
+                        deprecated_aliases: Vec::new(),
                         shape: Shape::Empty {
                             description: String::new(),
                             version: None,
@@ -2188,6 +3343,7 @@ mod tests {
                 name: String::new(),
                 description: String::new(),
                 version: None,
+                kind: PrimitiveKind::Str,
             }
             .trailing_options(),
             Vec::<&Field>::new()
@@ -2201,6 +3357,7 @@ mod tests {
                 name: String::new(),
                 description: String::new(),
                 version: None,
+                kind: PrimitiveKind::Str,
             }))
             .trailing_options(),
             Vec::<&Field>::new()
@@ -2214,32 +3371,35 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "quux".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 booleans: vec![],
+                constraints: vec![],
             }))
             .trailing_options(),
             Vec::<&Field>::new()
@@ -2253,57 +3413,61 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "quux".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 booleans: vec![],
+                constraints: vec![],
             }
             .trailing_options(),
             vec![
                 &Field {
-                    name: "foo",
                     description: "bar".into(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "baz".to_owned(),
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 0,
+                    ..Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )
                 },
-                &Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
+                &Field::new(
+                    "qux",
+                    Shape::Primitive {
                         name: "quux".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 1,
-                },
+                    1
+                ),
             ],
         );
     }
@@ -2315,53 +3479,53 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![],
                 optional: vec![],
                 booleans: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Empty {
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Empty {
+                                description: String::new(),
+                                version: None,
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Empty {
+                    Field::new(
+                        "qux",
+                        Shape::Empty {
                             description: String::new(),
                             version: None,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
+                constraints: vec![],
             }
             .trailing_options(),
             vec![
                 &Field {
-                    name: "foo",
                     description: "bar".into(),
-                    aliases: Vec::new(),
-                    shape: Shape::Empty {
-                        description: String::new(),
-                        version: None,
-                    },
-                    index: 0,
+                    ..Field::new(
+                        "foo",
+                        Shape::Empty {
+                            description: String::new(),
+                            version: None,
+                        },
+                        0
+                    )
                 },
-                &Field {
-                    name: "qux",
-                    description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Empty {
+                &Field::new(
+                    "qux",
+                    Shape::Empty {
                         description: String::new(),
                         version: None,
                     },
-                    index: 1,
-                },
+                    1
+                ),
             ],
         );
     }
@@ -2373,32 +3537,35 @@ mod tests {
                 name: "Struct",
                 description: String::new(),
                 version: None,
+                examples: None,
                 required: vec![
                     Field {
-                        name: "foo",
                         description: "bar".into(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
-                            name: "baz".to_owned(),
-                            description: String::new(),
-                            version: None,
-                        },
-                        index: 0,
+                        ..Field::new(
+                            "foo",
+                            Shape::Primitive {
+                                name: "baz".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            0
+                        )
                     },
-                    Field {
-                        name: "qux",
-                        description: String::new(),
-                        aliases: Vec::new(),
-                        shape: Shape::Primitive {
+                    Field::new(
+                        "qux",
+                        Shape::Primitive {
                             name: "quux".to_owned(),
                             description: String::new(),
                             version: None,
+                            kind: PrimitiveKind::Str,
                         },
-                        index: 1,
-                    },
+                        1
+                    ),
                 ],
                 optional: vec![],
                 booleans: vec![],
+                constraints: vec![],
             }
             .trailing_options(),
             Vec::<&Field>::new(),
@@ -2412,6 +3579,7 @@ mod tests {
                 name: "foo",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![],
             }
             .trailing_options(),
@@ -2426,41 +3594,48 @@ mod tests {
                 name: "foo",
                 description: String::new(),
                 version: None,
+                examples: None,
                 variants: vec![Variant {
                     name: "baz",
                     description: "qux".into(),
                     version: None,
+                    group: None,
+                    raw: false,
                     aliases: vec![],
+                    deprecated_aliases: Vec::new(),
                     shape: Shape::Struct {
                         name: "Struct",
                         description: String::new(),
                         version: None,
+                        examples: None,
                         required: vec![],
                         optional: vec![
                             Field {
-                                name: "foo",
                                 description: "bar".into(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
-                                    name: "baz".to_owned(),
-                                    description: String::new(),
-                                    version: None,
-                                },
-                                index: 0,
+                                ..Field::new(
+                                    "foo",
+                                    Shape::Primitive {
+                                        name: "baz".to_owned(),
+                                        description: String::new(),
+                                        version: None,
+                                        kind: PrimitiveKind::Str,
+                                    },
+                                    0
+                                )
                             },
-                            Field {
-                                name: "qux",
-                                description: String::new(),
-                                aliases: Vec::new(),
-                                shape: Shape::Primitive {
+                            Field::new(
+                                "qux",
+                                Shape::Primitive {
                                     name: "quux".to_owned(),
                                     description: String::new(),
                                     version: None,
+                                    kind: PrimitiveKind::Str,
                                 },
-                                index: 1,
-                            },
+                                1
+                            ),
                         ],
                         booleans: vec![],
+                        constraints: vec![],
                     },
                 }],
             }
@@ -2480,6 +3655,7 @@ mod tests {
                     name: "bar".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }),
                 enum_name: "baz",
                 variants: vec![],
@@ -2500,32 +3676,35 @@ mod tests {
                     name: "Struct",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![
                         Field {
-                            name: "foo",
                             description: "bar".into(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
-                                name: "baz".to_owned(),
-                                description: String::new(),
-                                version: None,
-                            },
-                            index: 0,
+                            ..Field::new(
+                                "foo",
+                                Shape::Primitive {
+                                    name: "baz".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                0
+                            )
                         },
-                        Field {
-                            name: "qux",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "qux",
+                            Shape::Primitive {
                                 name: "quux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }),
                 enum_name: "baz",
                 variants: vec![],
@@ -2533,29 +3712,377 @@ mod tests {
             .trailing_options(),
             vec![
                 &Field {
-                    name: "foo",
                     description: "bar".into(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "baz".to_owned(),
+                    ..Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "baz".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )
+                },
+                &Field::new(
+                    "qux",
+                    Shape::Primitive {
+                        name: "quux".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     },
-                    index: 0,
-                },
-                &Field {
-                    name: "qux",
+                    1
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn shape_struct_required_variant_trailing_options() {
+        let force = Field::new(
+            "force",
+            Shape::Empty {
+                description: String::new(),
+                version: None,
+            },
+            0,
+        );
+        let verbose = Field::new(
+            "verbose",
+            Shape::Empty {
+                description: String::new(),
+                version: None,
+            },
+            1,
+        );
+        let shape = Shape::Struct {
+            name: "Struct",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new(
+                "command",
+                Shape::Variant {
+                    name: "push",
                     description: String::new(),
-                    aliases: Vec::new(),
-                    shape: Shape::Primitive {
-                        name: "quux".to_owned(),
+                    version: None,
+                    shape: Box::new(Shape::Struct {
+                        name: "Push",
+                        description: String::new(),
+                        version: None,
+                        examples: None,
+                        required: vec![],
+                        optional: vec![],
+                        booleans: vec![force.clone()],
+                        constraints: vec![],
+                    }),
+                    enum_name: "Command",
+                    variants: vec![],
+                },
+                0,
+            )],
+            optional: vec![],
+            booleans: vec![verbose.clone()],
+            constraints: vec![],
+        };
+
+        assert_eq!(shape.trailing_options(), vec![&verbose, &force]);
+    }
+
+    #[test]
+    fn shape_struct_required_not_last_trailing_options() {
+        let shape = Shape::Struct {
+            name: "Struct",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![
+                Field::new(
+                    "inner",
+                    Shape::Struct {
+                        name: "Inner",
                         description: String::new(),
                         version: None,
+                        examples: None,
+                        required: vec![],
+                        optional: vec![],
+                        booleans: vec![Field {
+                            name: "force",
+                            description: String::new(),
+                            aliases: Vec::new(),
+                            shape: Shape::Empty {
+                                description: String::new(),
+                                version: None,
+                            },
+                            index: 0,
+                            allow_hyphen_values: false,
+                            value_name: None,
+                            value_delimiter: None,
+                            trailing: false,
+                            required: false,
+                            flags: false,
+                            multiple: false,
+                            deprecated_aliases: Vec::new(),
+                        }],
+                        constraints: vec![],
+                    },
+                    0,
+                ),
+                Field::new(
+                    "path",
+                    Shape::Primitive {
+                        name: "path".to_owned(),
+                        description: String::new(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    1,
+                ),
+            ],
+            optional: vec![],
+            booleans: vec![],
+            constraints: vec![],
+        };
+
+        assert_eq!(shape.trailing_options(), Vec::<&Field>::new());
+    }
+
+    #[test]
+    fn shape_struct_command_field() {
+        let shape = Shape::Struct {
+            name: "Struct",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![
+                Field::new(
+                    "name",
+                    Shape::Primitive {
+                        name: "name".to_owned(),
+                        description: String::new(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    0,
+                ),
+                Field::new(
+                    "command",
+                    Shape::Enum {
+                        name: "Command",
+                        description: String::new(),
+                        version: None,
+                        examples: None,
+                        variants: vec![],
+                    },
+                    1,
+                ),
+                Field::new(
+                    "unit",
+                    Shape::Empty {
+                        description: String::new(),
+                        version: None,
+                    },
+                    2,
+                ),
+            ],
+            optional: vec![],
+            booleans: vec![],
+            constraints: vec![],
+        };
+
+        assert_some_eq!(shape.command_field().map(|field| field.name), "command");
+    }
+
+    #[test]
+    fn shape_struct_command_field_not_last() {
+        let shape = Shape::Struct {
+            name: "Struct",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![
+                Field::new(
+                    "command",
+                    Shape::Enum {
+                        name: "Command",
+                        description: String::new(),
+                        version: None,
+                        examples: None,
+                        variants: vec![],
+                    },
+                    0,
+                ),
+                Field::new(
+                    "name",
+                    Shape::Primitive {
+                        name: "name".to_owned(),
+                        description: String::new(),
+                        version: None,
+                        kind: PrimitiveKind::Str,
+                    },
+                    1,
+                ),
+            ],
+            optional: vec![],
+            booleans: vec![],
+            constraints: vec![],
+        };
+
+        assert_none!(shape.command_field());
+    }
+
+    #[test]
+    fn shape_variant_command_field() {
+        let shape = Shape::Variant {
+            name: "remote",
+            description: String::new(),
+            version: None,
+            shape: Box::new(Shape::Struct {
+                name: "Struct",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![Field::new(
+                    "command",
+                    Shape::Enum {
+                        name: "Command",
+                        description: String::new(),
+                        version: None,
+                        examples: None,
+                        variants: vec![],
                     },
-                    index: 1,
+                    0,
+                )],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }),
+            enum_name: "Remote",
+            variants: vec![],
+        };
+
+        assert_some_eq!(shape.command_field().map(|field| field.name), "command");
+    }
+
+    #[test]
+    fn shape_enum_command_field() {
+        assert_none!(Shape::Enum {
+            name: "Command",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants: vec![],
+        }
+        .command_field());
+    }
+
+    #[test]
+    fn shape_primitive_trailing_command() {
+        let shape = Shape::Primitive {
+            name: "foo".to_owned(),
+            description: String::new(),
+            version: None,
+            kind: PrimitiveKind::Str,
+        };
+
+        assert_eq!(shape.trailing_command(), (vec![], &shape));
+    }
+
+    #[test]
+    fn shape_struct_trailing_command() {
+        let shape = Shape::Struct {
+            name: "Struct",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new(
+                "path",
+                Shape::Primitive {
+                    name: "path".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
                 },
-            ]
-        );
+                0,
+            )],
+            optional: vec![],
+            booleans: vec![],
+            constraints: vec![],
+        };
+
+        assert_eq!(shape.trailing_command(), (vec![], &shape));
+    }
+
+    #[test]
+    fn shape_variant_trailing_command() {
+        let inner = Shape::Empty {
+            description: String::new(),
+            version: None,
+        };
+        let shape = Shape::Variant {
+            name: "stop",
+            description: String::new(),
+            version: None,
+            shape: Box::new(inner.clone()),
+            enum_name: "Command",
+            variants: vec![],
+        };
+
+        assert_eq!(shape.trailing_command(), (vec!["stop"], &inner));
+    }
+
+    #[test]
+    fn shape_nested_variant_trailing_command() {
+        let inner = Shape::Primitive {
+            name: "remote".to_owned(),
+            description: String::new(),
+            version: None,
+            kind: PrimitiveKind::Str,
+        };
+        let shape = Shape::Struct {
+            name: "Struct",
+            description: String::new(),
+            version: None,
+            examples: None,
+            required: vec![Field::new(
+                "command",
+                Shape::Variant {
+                    name: "remote",
+                    description: String::new(),
+                    version: None,
+                    shape: Box::new(Shape::Variant {
+                        name: "add",
+                        description: String::new(),
+                        version: None,
+                        shape: Box::new(inner.clone()),
+                        enum_name: "Remote",
+                        variants: vec![],
+                    }),
+                    enum_name: "Command",
+                    variants: vec![],
+                },
+                0,
+            )],
+            optional: vec![],
+            booleans: vec![],
+            constraints: vec![],
+        };
+
+        assert_eq!(shape.trailing_command(), (vec!["remote", "add"], &inner));
+    }
+
+    #[test]
+    fn shape_enum_trailing_command() {
+        let shape = Shape::Enum {
+            name: "Command",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants: vec![],
+        };
+
+        assert_eq!(shape.trailing_command(), (vec![], &shape));
     }
 
     #[test]
@@ -2581,12 +4108,29 @@ mod tests {
                     name: "foo".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }
             ),
             "<foo>"
         );
     }
 
+    #[test]
+    fn shape_display_primitive_any() {
+        assert_eq!(
+            format!(
+                "{}",
+                Shape::Primitive {
+                    name: "a mode".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Any(&["fast", "slow"]),
+                }
+            ),
+            "<fast|slow>"
+        );
+    }
+
     #[test]
     fn shape_display_boolean() {
         assert_eq!(
@@ -2602,6 +4146,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn shape_display_sequence() {
+        assert_eq!(
+            format!(
+                "{}",
+                Shape::Sequence(Box::new(Shape::Primitive {
+                    name: "foo".to_owned(),
+                    description: String::new(),
+                    version: None,
+                    kind: PrimitiveKind::Str,
+                }))
+            ),
+            "<foo>..."
+        );
+    }
+
     #[test]
     fn shape_display_optional_empty() {
         assert_eq!(
@@ -2625,6 +4185,7 @@ mod tests {
                     name: "foo".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }))
             ),
             "[--<foo>]"
@@ -2640,6 +4201,7 @@ mod tests {
                     name: "foo".to_owned(),
                     description: String::new(),
                     version: None,
+                    kind: PrimitiveKind::Str,
                 }))))
             ),
             "[-- [--<foo>]]"
@@ -2655,32 +4217,32 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "bar".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "qux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }))
             ),
             "[--<foo> <baz>]"
@@ -2696,6 +4258,7 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![],
                 }))
             ),
@@ -2716,6 +4279,7 @@ mod tests {
                         name: "bar".into(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     }),
                     variants: vec![],
                     enum_name: "baz",
@@ -2734,38 +4298,77 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "bar".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "qux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             "<foo> <baz>"
         );
     }
 
+    #[test]
+    fn shape_display_struct_unit_field() {
+        assert_eq!(
+            format!(
+                "{}",
+                Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![
+                        Field::new(
+                            "foo",
+                            Shape::Empty {
+                                description: String::new(),
+                                version: None,
+                            },
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
+                                name: "qux".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            1
+                        ),
+                    ],
+                    optional: vec![],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            "<baz>"
+        );
+    }
+
     #[test]
     fn shape_display_struct_only_optional_fields() {
         assert_eq!(
@@ -2775,38 +4378,151 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "bar".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "qux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             "[options]"
         );
     }
 
+    #[test]
+    fn shape_display_struct_required_options() {
+        assert_eq!(
+            format!(
+                "{}",
+                Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![Field::new(
+                        "foo",
+                        Shape::Primitive {
+                            name: "bar".to_owned(),
+                            description: String::new(),
+                            version: None,
+                            kind: PrimitiveKind::Str,
+                        },
+                        0
+                    )],
+                    optional: vec![
+                        Field {
+                            required: true,
+                            ..Field::new(
+                                "baz",
+                                Shape::Primitive {
+                                    name: "qux".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                1
+                            )
+                        },
+                        Field {
+                            required: true,
+                            ..Field::new(
+                                "q",
+                                Shape::Primitive {
+                                    name: "quux".to_owned(),
+                                    description: String::new(),
+                                    version: None,
+                                    kind: PrimitiveKind::Str,
+                                },
+                                2
+                            )
+                        },
+                        Field::new(
+                            "corge",
+                            Shape::Primitive {
+                                name: "grault".to_owned(),
+                                description: String::new(),
+                                version: None,
+                                kind: PrimitiveKind::Str,
+                            },
+                            3
+                        ),
+                    ],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            "[options] --baz <qux> -q <quux> <foo>"
+        );
+    }
+
+    #[test]
+    fn shape_display_struct_required_flags() {
+        let variant = |name| Variant {
+            name,
+            description: String::new(),
+            version: None,
+            group: None,
+            raw: false,
+            aliases: vec![],
+            deprecated_aliases: Vec::new(),
+            shape: Shape::Empty {
+                description: String::new(),
+                version: None,
+            },
+        };
+        assert_eq!(
+            format!(
+                "{}",
+                Shape::Struct {
+                    name: "",
+                    description: String::new(),
+                    version: None,
+                    examples: None,
+                    required: vec![],
+                    optional: vec![Field {
+                        required: true,
+                        flags: true,
+                        ..Field::new(
+                            "foo",
+                            Shape::Enum {
+                                name: "Foo",
+                                description: String::new(),
+                                version: None,
+                                examples: None,
+                                variants: vec![variant("bar"), variant("b")],
+                            },
+                            0
+                        )
+                    }],
+                    booleans: vec![],
+                    constraints: vec![],
+                }
+            ),
+            "[options] (--bar | -b)"
+        );
+    }
+
     #[test]
     fn shape_display_struct_only_boolean_fields() {
         assert_eq!(
@@ -2816,30 +4532,28 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![],
                     booleans: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Empty {
+                        Field::new(
+                            "foo",
+                            Shape::Empty {
                                 description: String::new(),
                                 version: None,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Empty {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Empty {
                                 description: String::new(),
                                 version: None,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
+                    constraints: vec![],
                 }
             ),
             "[options]"
@@ -2855,55 +4569,53 @@ mod tests {
                     name: "",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "bar".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "qux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     optional: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "bar".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 2,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                            2
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "qux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 3,
-                        },
+                            3
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             "[options] <foo> <baz>"
@@ -2919,32 +4631,32 @@ mod tests {
                     name: "Struct",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     required: vec![],
                     optional: vec![
-                        Field {
-                            name: "foo",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                        Field::new(
+                            "foo",
+                            Shape::Primitive {
                                 name: "bar".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 0,
-                        },
-                        Field {
-                            name: "baz",
-                            description: String::new(),
-                            aliases: Vec::new(),
-                            shape: Shape::Primitive {
+                            0
+                        ),
+                        Field::new(
+                            "baz",
+                            Shape::Primitive {
                                 name: "qux".to_owned(),
                                 description: String::new(),
                                 version: None,
+                                kind: PrimitiveKind::Str,
                             },
-                            index: 1,
-                        },
+                            1
+                        ),
                     ],
                     booleans: vec![],
+                    constraints: vec![],
                 }
             ),
             "[Struct options]"
@@ -2960,6 +4672,7 @@ mod tests {
                     name: "foo",
                     description: String::new(),
                     version: None,
+                    examples: None,
                     variants: vec![],
                 }
             ),
@@ -2980,6 +4693,7 @@ mod tests {
                         name: "bar".to_owned(),
                         description: String::new(),
                         version: None,
+                        kind: PrimitiveKind::Str,
                     }),
                     enum_name: "baz",
                     variants: vec![],
@@ -2988,4 +4702,167 @@ mod tests {
             "foo <bar>",
         )
     }
+
+    fn unit_variant(name: &'static str, aliases: Vec<&'static str>) -> Variant {
+        Variant {
+            name,
+            description: String::new(),
+            version: None,
+            group: None,
+            raw: false,
+            aliases,
+            deprecated_aliases: Vec::new(),
+            shape: Shape::Empty {
+                description: String::new(),
+                version: None,
+            },
+        }
+    }
+
+    fn command_enum(variants: Vec<Variant>) -> Shape {
+        Shape::Enum {
+            name: "command",
+            description: String::new(),
+            version: None,
+            examples: None,
+            variants,
+        }
+    }
+
+    #[test]
+    fn variant_find_name_case_sensitive() {
+        let variant = unit_variant("clone", vec!["cl"]);
+
+        assert_some_eq!(variant.find_name("clone", false), "clone");
+        assert_some_eq!(variant.find_name("cl", false), "clone");
+        assert_none!(variant.find_name("Clone", false));
+    }
+
+    #[test]
+    fn variant_find_name_case_insensitive() {
+        let variant = unit_variant("clone", vec!["cl"]);
+
+        assert_some_eq!(variant.find_name("CLONE", true), "clone");
+        assert_some_eq!(variant.find_name("Cl", true), "clone");
+        assert_none!(variant.find_name("clones", true));
+    }
+
+    #[test]
+    fn variant_find_name_case_insensitive_unicode() {
+        let variant = unit_variant("straße", vec![]);
+
+        assert_some_eq!(variant.find_name("STRASSE", true), "straße");
+    }
+
+    #[test]
+    fn case_insensitive_conflict_none() {
+        assert_none!(command_enum(vec![
+            unit_variant("clone", vec!["cl"]),
+            unit_variant("commit", vec!["ci"]),
+        ])
+        .case_insensitive_conflict());
+    }
+
+    #[test]
+    fn case_insensitive_conflict_names() {
+        assert_some_eq!(
+            command_enum(vec![
+                unit_variant("clone", vec![]),
+                unit_variant("Clone", vec![])
+            ])
+            .case_insensitive_conflict(),
+            ("clone", "Clone")
+        );
+    }
+
+    #[test]
+    fn case_insensitive_conflict_alias() {
+        assert_some_eq!(
+            command_enum(vec![
+                unit_variant("clone", vec![]),
+                unit_variant("copy", vec!["CLONE"])
+            ])
+            .case_insensitive_conflict(),
+            ("clone", "CLONE")
+        );
+    }
+
+    #[test]
+    fn case_insensitive_conflict_nested() {
+        assert_some_eq!(
+            Shape::Struct {
+                name: "args",
+                description: String::new(),
+                version: None,
+                examples: None,
+                required: vec![Field::new(
+                    "command",
+                    command_enum(vec![
+                        unit_variant("remote", vec![]),
+                        Variant {
+                            shape: command_enum(vec![
+                                unit_variant("add", vec![]),
+                                unit_variant("ADD", vec![]),
+                            ]),
+                            ..unit_variant("config", vec![])
+                        },
+                    ]),
+                    0
+                )],
+                optional: vec![],
+                booleans: vec![],
+                constraints: vec![],
+            }
+            .case_insensitive_conflict(),
+            ("add", "ADD")
+        );
+    }
+
+    #[test]
+    fn find_command_name() {
+        assert_some_eq!(
+            command_enum(vec![
+                unit_variant("clone", vec!["cl"]),
+                unit_variant("commit", vec!["ci"]),
+            ])
+            .find_command("commit", false),
+            "commit"
+        );
+    }
+
+    #[test]
+    fn find_command_alias() {
+        assert_some_eq!(
+            command_enum(vec![
+                unit_variant("clone", vec!["cl"]),
+                unit_variant("commit", vec!["ci"]),
+            ])
+            .find_command("ci", false),
+            "commit"
+        );
+    }
+
+    #[test]
+    fn find_command_case_insensitive() {
+        assert_some_eq!(
+            command_enum(vec![unit_variant("clone", vec![])]).find_command("CLONE", true),
+            "clone"
+        );
+    }
+
+    #[test]
+    fn find_command_not_found() {
+        assert_none!(command_enum(vec![unit_variant("clone", vec![])]).find_command("CLONE", false));
+    }
+
+    #[test]
+    fn find_command_not_enum() {
+        assert_none!(Shape::Primitive {
+            name: "clone".to_owned(),
+            description: String::new(),
+            version: None,
+            kind: PrimitiveKind::Str,
+        }
+        .find_command("clone", false));
+    }
 }
@@ -0,0 +1,583 @@
+use crate::trace::{
+    trace,
+    Field,
+    Shape,
+    Variant,
+};
+use serde::de::{
+    Deserialize,
+    DeserializeSeed,
+};
+use std::{
+    error,
+    fmt,
+    fmt::{
+        Display,
+        Formatter,
+    },
+    iter,
+    marker::PhantomData,
+};
+
+/// A problem with a type that would prevent it from being deserialized from the command line.
+///
+/// Returned by [`validate()`] and [`validate_seed()`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ValidationError {
+    path: String,
+    message: String,
+}
+
+impl ValidationError {
+    fn new(path: &str, message: String) -> Self {
+        Self {
+            path: path.to_owned(),
+            message,
+        }
+    }
+
+    /// The field or variant the problem was found in.
+    ///
+    /// Paths are the names of fields and variants separated by `.`, in the same form accepted by
+    /// [`Metadata::field_help()`](crate::Metadata::field_help()). Problems with the type itself
+    /// have an empty path.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// A description of the problem.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            formatter.write_str(&self.message)
+        } else {
+            write!(formatter, "`{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+impl error::Error for ValidationError {}
+
+/// Checks that `D` can be deserialized from the command line, returning every problem found.
+///
+/// Some mistakes in a type's definition are only noticed when a user gives the arguments that
+/// reach them, such as two options sharing an alias or an enum without any variants. This traces
+/// the type the same way every entry point does and reports all such problems at once, making it
+/// suitable for use in a test suite. Problems found while tracing, such as unsupported `serde`
+/// attributes, stop tracing, so only the first of those is reported.
+///
+/// # Example
+///
+/// ``` rust
+/// # mod hidden {
+/// use serde::Deserialize;
+/// # }
+/// # use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// enum Command {
+///     #[serde(alias = "c")]
+///     Clone,
+///     #[serde(alias = "c")]
+///     Commit,
+/// }
+///
+/// let errors = serde_args::validate::<Command>().unwrap_err();
+///
+/// assert_eq!(
+///     errors[0].to_string(),
+///     "`Clone`: name `c` is used by more than one variant, but always selects this one"
+/// );
+/// ```
+pub fn validate<'de, D>() -> Result<(), Vec<ValidationError>>
+where
+    D: Deserialize<'de>,
+{
+    validate_seed(PhantomData::<D>)
+}
+
+/// Checks that `seed` can be deserialized from the command line, returning every problem found.
+///
+/// This function behaves the same as [`validate()`], except that the type is deserialized using
+/// the provided seed.
+pub fn validate_seed<'de, D>(seed: D) -> Result<(), Vec<ValidationError>>
+where
+    D: Copy + DeserializeSeed<'de>,
+{
+    let shape = match trace(seed) {
+        Ok(shape) => shape,
+        Err(error) => return Err(vec![ValidationError::new("", error.to_string())]),
+    };
+    let mut errors = Vec::new();
+    validate_shape(&shape, "", &mut errors);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Asserts that `D` can be deserialized from the command line.
+///
+/// # Panics
+///
+/// Panics if [`validate()`] finds any problems, listing all of them.
+///
+/// # Example
+///
+/// ``` rust
+/// # mod hidden {
+/// use serde::Deserialize;
+/// # }
+/// # use serde_derive::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Args {
+///     path: String,
+///     #[serde(alias = "f")]
+///     force: bool,
+/// }
+///
+/// serde_args::assert_valid::<Args>();
+/// ```
+#[track_caller]
+pub fn assert_valid<'de, D>()
+where
+    D: Deserialize<'de>,
+{
+    assert_valid_seed(PhantomData::<D>)
+}
+
+/// Asserts that `seed` can be deserialized from the command line.
+///
+/// # Panics
+///
+/// Panics if [`validate_seed()`] finds any problems, listing all of them.
+#[track_caller]
+pub fn assert_valid_seed<'de, D>(seed: D)
+where
+    D: Copy + DeserializeSeed<'de>,
+{
+    if let Err(errors) = validate_seed(seed) {
+        let mut message = format!(
+            "{} problem{} found:",
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" }
+        );
+        for error in errors {
+            message.push_str(&format!("\n  - {}", error));
+        }
+        panic!("{}", message);
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn validate_shape(shape: &Shape, path: &str, errors: &mut Vec<ValidationError>) {
+    match shape {
+        Shape::Empty { .. } | Shape::Primitive { .. } | Shape::Boolean { .. } => {}
+        Shape::Optional(shape) | Shape::Sequence(shape) => validate_shape(shape, path, errors),
+        Shape::Struct {
+            required,
+            optional,
+            booleans,
+            ..
+        } => {
+            validate_fields(required, optional, booleans, path, errors);
+            for field in required.iter().chain(optional).chain(booleans) {
+                validate_shape(&field.shape, &join(path, field.name), errors);
+            }
+        }
+        Shape::Enum { name, variants, .. } => {
+            if variants.is_empty() {
+                errors.push(ValidationError::new(
+                    path,
+                    format!(
+                        "enum `{}` has no variants, so no command can be given",
+                        name
+                    ),
+                ));
+            }
+            validate_variants(variants, path, errors);
+        }
+        Shape::Variant {
+            name,
+            shape,
+            variants,
+            ..
+        } => {
+            validate_shape(shape, &join(path, name), errors);
+            validate_variants(variants, path, errors);
+        }
+    }
+}
+
+fn validate_fields(
+    required: &[Field],
+    optional: &[Field],
+    booleans: &[Field],
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    for field in required.iter().chain(optional).chain(booleans) {
+        for name in duplicate_names(field.name, &field.aliases) {
+            errors.push(ValidationError::new(
+                &join(path, field.name),
+                format!(
+                    "name `{}` is used by more than one field, but always selects this one",
+                    name
+                ),
+            ));
+        }
+    }
+    for field in optional.iter().chain(booleans) {
+        if field.name.is_empty() {
+            errors.push(ValidationError::new(
+                path,
+                "an option has an empty name, so it cannot be given".to_owned(),
+            ));
+        }
+    }
+    if let Some((index, sequence)) = required
+        .iter()
+        .enumerate()
+        .find(|(_, field)| matches!(field.shape, Shape::Sequence(_)))
+    {
+        for field in &required[index + 1..] {
+            if !matches!(field.shape, Shape::Empty { .. }) {
+                errors.push(ValidationError::new(
+                    &join(path, field.name),
+                    format!(
+                        "positional field follows `{}`, which consumes all remaining arguments",
+                        sequence.name
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+/// Returns the names given to more than one field or variant.
+///
+/// Names are traced by the field or variant they select, so a name declared by several of them is
+/// recorded repeatedly for the one that `serde` selects, and not at all for the others.
+fn duplicate_names(name: &'static str, aliases: &[&'static str]) -> Vec<&'static str> {
+    let mut seen = Vec::new();
+    let mut duplicates = Vec::new();
+    for name in iter::once(name).chain(aliases.iter().copied()) {
+        if seen.contains(&name) {
+            if !duplicates.contains(&name) {
+                duplicates.push(name);
+            }
+        } else {
+            seen.push(name);
+        }
+    }
+    duplicates
+}
+
+fn validate_variants(variants: &[Variant], path: &str, errors: &mut Vec<ValidationError>) {
+    for variant in variants {
+        let variant_path = join(path, variant.name);
+        if variant.name.is_empty() {
+            errors.push(ValidationError::new(
+                path,
+                "a command has an empty name, so it cannot be given".to_owned(),
+            ));
+        }
+        for name in duplicate_names(variant.name, &variant.aliases) {
+            errors.push(ValidationError::new(
+                &variant_path,
+                format!(
+                    "name `{}` is used by more than one variant, but always selects this one",
+                    name
+                ),
+            ));
+        }
+        validate_shape(&variant.shape, &variant_path, errors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        assert_valid,
+        assert_valid_seed,
+        validate,
+        validate_seed,
+        ValidationError,
+    };
+    use claims::{
+        assert_err_eq,
+        assert_ok,
+    };
+    use duplicates::{
+        DuplicateAlias,
+        DuplicateVariant,
+    };
+    use serde::de::{
+        Deserialize,
+        DeserializeSeed,
+        Deserializer,
+    };
+    use serde_derive::Deserialize;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Valid {
+        path: String,
+        #[serde(alias = "f")]
+        force: bool,
+        level: Option<u8>,
+    }
+
+    // Duplicate names are rejected by the compiler within the derived implementations.
+    #[allow(unreachable_patterns)]
+    mod duplicates {
+        use serde_derive::Deserialize;
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        pub(super) struct DuplicateAlias {
+            #[serde(alias = "f")]
+            force: bool,
+            #[serde(alias = "f")]
+            file: Option<String>,
+        }
+
+        #[derive(Deserialize)]
+        #[allow(dead_code)]
+        pub(super) enum DuplicateVariant {
+            #[serde(alias = "c")]
+            Clone,
+            #[serde(alias = "c")]
+            Commit,
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum Empty {}
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct AfterSequence {
+        files: Vec<String>,
+        destination: String,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct EmptyName {
+        #[serde(rename = "")]
+        level: Option<u8>,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum EmptyCommand {
+        #[serde(rename = "")]
+        Run,
+        Stop,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Nested {
+        #[serde(alias = "v")]
+        verbose: bool,
+        command: Command,
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    enum Command {
+        Push(DuplicateAlias),
+        Pull(AfterSequence),
+        Stash(Empty),
+    }
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Unsupported {
+        values: Vec<Valid>,
+    }
+
+    #[test]
+    fn validate_valid() {
+        assert_ok!(validate::<Valid>());
+    }
+
+    #[test]
+    fn validate_duplicate_alias() {
+        assert_err_eq!(
+            validate::<DuplicateAlias>(),
+            vec![ValidationError {
+                path: "f".into(),
+                message: "name `f` is used by more than one field, but always selects this one"
+                    .into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_empty_enum() {
+        assert_err_eq!(
+            validate::<Empty>(),
+            vec![ValidationError {
+                path: String::new(),
+                message: "enum `Empty` has no variants, so no command can be given".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_duplicate_variant_alias() {
+        assert_err_eq!(
+            validate::<DuplicateVariant>(),
+            vec![ValidationError {
+                path: "Clone".into(),
+                message: "name `c` is used by more than one variant, but always selects this one"
+                    .into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_field_after_sequence() {
+        assert_err_eq!(
+            validate::<AfterSequence>(),
+            vec![ValidationError {
+                path: "destination".into(),
+                message: "positional field follows `files`, which consumes all remaining arguments"
+                    .into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_empty_option_name() {
+        assert_err_eq!(
+            validate::<EmptyName>(),
+            vec![ValidationError {
+                path: String::new(),
+                message: "an option has an empty name, so it cannot be given".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_empty_command_name() {
+        assert_err_eq!(
+            validate::<EmptyCommand>(),
+            vec![ValidationError {
+                path: String::new(),
+                message: "a command has an empty name, so it cannot be given".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_nested_aggregates_all() {
+        assert_err_eq!(
+            validate::<Nested>(),
+            vec![
+                ValidationError {
+                    path: "command.Push.f".into(),
+                    message: "name `f` is used by more than one field, but always selects this one"
+                        .into(),
+                },
+                ValidationError {
+                    path: "command.Pull.destination".into(),
+                    message:
+                        "positional field follows `files`, which consumes all remaining arguments"
+                            .into(),
+                },
+                ValidationError {
+                    path: "command.Stash".into(),
+                    message: "enum `Empty` has no variants, so no command can be given".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn validate_trace_error() {
+        assert_err_eq!(
+            validate::<Unsupported>(),
+            vec![ValidationError {
+                path: String::new(),
+                message: "sequence elements must be primitive values, each consuming exactly one argument".into(),
+            }]
+        );
+    }
+
+    #[test]
+    fn validate_seed_valid() {
+        #[derive(Clone, Copy)]
+        struct Seed;
+
+        impl<'de> DeserializeSeed<'de> for Seed {
+            type Value = Valid;
+
+            fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                Valid::deserialize(deserializer)
+            }
+        }
+
+        assert_ok!(validate_seed(Seed));
+        assert_valid_seed(Seed);
+    }
+
+    #[test]
+    fn assert_valid_valid() {
+        assert_valid::<Valid>();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "3 problems found:\n  - `command.Push.f`: name `f` is used by more than one field, but always selects this one\n  - `command.Pull.destination`: positional field follows `files`, which consumes all remaining arguments\n  - `command.Stash`: enum `Empty` has no variants, so no command can be given"
+    )]
+    fn assert_valid_invalid() {
+        assert_valid::<Nested>();
+    }
+
+    #[test]
+    #[should_panic(expected = "1 problem found:\n  - enum `Empty` has no variants")]
+    fn assert_valid_single() {
+        assert_valid::<Empty>();
+    }
+
+    #[test]
+    fn display_path() {
+        assert_eq!(
+            format!(
+                "{}",
+                ValidationError::new("foo.bar", "something is wrong".into())
+            ),
+            "`foo.bar`: something is wrong"
+        );
+    }
+
+    #[test]
+    fn display_no_path() {
+        assert_eq!(
+            format!("{}", ValidationError::new("", "something is wrong".into())),
+            "something is wrong"
+        );
+    }
+}
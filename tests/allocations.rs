@@ -0,0 +1,118 @@
+//! Checks that representative scenarios stay within their recorded allocation budgets.
+//!
+//! Allocations are counted by a global allocator, only on the thread running a scenario, so that
+//! tests running in parallel do not affect each other. Budgets are recorded in
+//! `tests/allocations/budgets.txt`.
+
+mod git;
+
+use git::Git;
+use std::{
+    alloc::{
+        GlobalAlloc,
+        Layout,
+        System,
+    },
+    cell::Cell,
+    hint::black_box,
+};
+
+const BUDGETS: &str = include_str!("allocations/budgets.txt");
+
+thread_local! {
+    /// The number of allocations made on this thread, if they are being counted.
+    static ALLOCATIONS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Counts allocations made on the current thread, forwarding them to the system allocator.
+struct Counting;
+
+impl Counting {
+    fn count() {
+        // The thread local may already be destroyed if this thread is exiting.
+        let _ = ALLOCATIONS.try_with(|allocations| {
+            if let Some(count) = allocations.get() {
+                allocations.set(Some(count + 1));
+            }
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::count();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+/// Returns the number of allocations made while running `scenario`.
+fn allocations<F>(scenario: F) -> usize
+where
+    F: FnOnce(),
+{
+    ALLOCATIONS.with(|allocations| allocations.set(Some(0)));
+    scenario();
+    ALLOCATIONS
+        .with(|allocations| allocations.take())
+        .expect("allocations were not being counted")
+}
+
+/// Returns the budget recorded for the scenario `name`.
+fn budget(name: &str) -> usize {
+    BUDGETS
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .find_map(|line| {
+            let (scenario, budget) = line.split_once(' ')?;
+            (scenario == name).then(|| budget.parse().expect("invalid budget"))
+        })
+        .unwrap_or_else(|| panic!("no budget recorded for scenario `{name}`"))
+}
+
+/// Asserts that `scenario` makes no more allocations than the budget recorded for `name`.
+fn assert_within_budget<F>(name: &str, scenario: F)
+where
+    F: FnOnce(),
+{
+    let count = allocations(scenario);
+    let budget = budget(name);
+    assert!(
+        count <= budget,
+        "scenario `{name}` made {count} allocations, exceeding its budget of {budget}; if this is intended, record the new count in tests/allocations/budgets.txt"
+    );
+}
+
+#[test]
+fn end_to_end() {
+    assert_within_budget("end_to_end", || {
+        black_box(serde_args::from_slice::<Git>(black_box(git::LONG)).unwrap());
+    });
+}
+
+#[test]
+fn help() {
+    assert_within_budget("help", || {
+        black_box(
+            serde_args::from_slice::<Git>(black_box(git::HELP))
+                .unwrap_err()
+                .to_string(),
+        );
+    });
+}
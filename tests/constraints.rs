@@ -0,0 +1,232 @@
+//! Tests for constraints on which options may be given together.
+//!
+//! Constraints are only checked once every argument has been parsed, so each constraint is checked
+//! with options given in any position, including within commands.
+#![cfg(feature = "macros")]
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_derive::Deserialize;
+
+#[serde_args::generate(
+    one_of(stdin, file),
+    at_most_one(quiet, verbose),
+    all_or_none(user, password)
+)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Args {
+    name: String,
+    stdin: bool,
+    file: Option<String>,
+    quiet: bool,
+    verbose: bool,
+    user: Option<String>,
+    password: Option<String>,
+}
+
+#[serde_args::generate(at_most_one(force, dry_run))]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+struct Push {
+    remote: String,
+    force: bool,
+    dry_run: bool,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum Command {
+    Push(Push),
+    Status,
+}
+
+#[test]
+fn one_of_satisfied() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "foo", "--file", "bar"]),
+        Args {
+            name: "foo".to_owned(),
+            stdin: false,
+            file: Some("bar".to_owned()),
+            quiet: false,
+            verbose: false,
+            user: None,
+            password: None,
+        }
+    );
+}
+
+#[test]
+fn one_of_satisfied_flag() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "--stdin", "foo"]),
+        Args {
+            name: "foo".to_owned(),
+            stdin: true,
+            file: None,
+            quiet: false,
+            verbose: false,
+            user: None,
+            password: None,
+        }
+    );
+}
+
+#[test]
+fn one_of_missing() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&["executable", "foo"]))
+        ),
+        "ERROR: missing one of the options: '--stdin' '--file'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn one_of_conflicting() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "--file",
+                "bar",
+                "foo",
+                "--stdin"
+            ]))
+        ),
+        "ERROR: options cannot be used together: '--stdin' '--file'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn at_most_one_satisfied() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "foo", "--stdin", "--verbose"]),
+        Args {
+            name: "foo".to_owned(),
+            stdin: true,
+            file: None,
+            quiet: false,
+            verbose: true,
+            user: None,
+            password: None,
+        }
+    );
+}
+
+#[test]
+fn at_most_one_conflicting() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "foo",
+                "--stdin",
+                "--quiet",
+                "--verbose"
+            ]))
+        ),
+        "ERROR: options cannot be used together: '--quiet' '--verbose'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn all_or_none_satisfied() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&[
+            "executable",
+            "--user",
+            "alice",
+            "foo",
+            "--stdin",
+            "--password",
+            "secret"
+        ]),
+        Args {
+            name: "foo".to_owned(),
+            stdin: true,
+            file: None,
+            quiet: false,
+            verbose: false,
+            user: Some("alice".to_owned()),
+            password: Some("secret".to_owned()),
+        }
+    );
+}
+
+#[test]
+fn all_or_none_incomplete() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "foo",
+                "--stdin",
+                "--user",
+                "alice"
+            ]))
+        ),
+        "ERROR: missing option '--password', which must be given together with '--user'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn multiple_violations() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "foo",
+                "--password",
+                "secret",
+                "--verbose",
+                "--quiet"
+            ]))
+        ),
+        "ERROR: 3 problems found:\n  - missing one of the options: '--stdin' '--file'\n  - options cannot be used together: '--quiet' '--verbose'\n  - missing option '--user', which must be given together with '--password'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn command_satisfied() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "push", "origin", "--force"]),
+        Command::Push(Push {
+            remote: "origin".to_owned(),
+            force: true,
+            dry_run: false,
+        })
+    );
+}
+
+#[test]
+fn command_conflicting() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Command>(&[
+                "executable",
+                "push",
+                "--dry-run",
+                "origin",
+                "--force"
+            ]))
+        ),
+        "ERROR: options cannot be used together: '--force' '--dry-run'\n\nUSAGE: executable push [Push options] <remote>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn command_not_given() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "status"]),
+        Command::Status
+    );
+}
@@ -0,0 +1,194 @@
+//! Tests for options whose variants are given as separate flags.
+#![cfg(feature = "macros")]
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum Format {
+    Json,
+    #[serde(alias = "yml")]
+    Yaml,
+    Toml,
+}
+
+#[serde_args::generate]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Args {
+    input: String,
+    #[serde_args(flags)]
+    format: Option<Format>,
+    verbose: bool,
+}
+
+#[serde_args::generate]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct RequiredArgs {
+    input: String,
+    #[serde_args(flags, required)]
+    format: Option<Format>,
+}
+
+#[serde_args::generate]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct ConflictingArgs {
+    json: bool,
+    #[serde_args(flags)]
+    format: Option<Format>,
+}
+
+#[test]
+fn no_flags() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "foo"]),
+        Args {
+            input: "foo".to_owned(),
+            format: None,
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn one_flag() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "--yaml", "foo", "--verbose"]),
+        Args {
+            input: "foo".to_owned(),
+            format: Some(Format::Yaml),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn alias_flag() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "foo", "--yml"]),
+        Args {
+            input: "foo".to_owned(),
+            format: Some(Format::Yaml),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn conflicting_flags() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "--json",
+                "foo",
+                "--yaml"
+            ]))
+        ),
+        "ERROR: '--json' cannot be used with '--yaml'\n\nUSAGE: executable [options] <input>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn conflicting_alias_flags() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "--yml",
+                "foo",
+                "--toml"
+            ]))
+        ),
+        "ERROR: '--yaml' cannot be used with '--toml'\n\nUSAGE: executable [options] <input>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn repeated_flag() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "--yaml",
+                "foo",
+                "--yaml"
+            ]))
+        ),
+        "ERROR: '--yaml' cannot be used multiple times\n\nUSAGE: executable [options] <input>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn repeated_alias_flag() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "--yml",
+                "foo",
+                "--yaml"
+            ]))
+        ),
+        "ERROR: '--yaml' cannot be used multiple times\n\nUSAGE: executable [options] <input>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn unrecognized_flag() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "foo",
+                "--format"
+            ]))
+        ),
+        "ERROR: unrecognized optional flag: '--format'\n\n  tip: a similar option exists: --toml\n\nUSAGE: executable [options] <input>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn required_flag_given() {
+    assert_ok_eq!(
+        serde_args::from_slice::<RequiredArgs>(&["executable", "foo", "--toml"]),
+        RequiredArgs {
+            input: "foo".to_owned(),
+            format: Some(Format::Toml),
+        }
+    );
+}
+
+#[test]
+fn required_flag_missing() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<RequiredArgs>(&[
+                "executable",
+                "foo"
+            ]))
+        ),
+        "ERROR: missing one of the options: '--json' '--yaml' '--toml'\n\nUSAGE: executable [options] (--json | --yaml | --toml) <input>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn flag_conflicting_with_boolean() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<ConflictingArgs>(&["executable"]))
+        ),
+        "flag `json` of field `format` given as flags has the same name as another option"
+    );
+}
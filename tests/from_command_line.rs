@@ -0,0 +1,93 @@
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Command {
+    Commit {
+        #[serde(alias = "m")]
+        message: Option<String>,
+        force: bool,
+    },
+    Add(Vec<String>),
+}
+
+#[test]
+fn quoted_spaces() {
+    assert_ok_eq!(
+        serde_args::from_command_line::<Command>(r#"commit -m "fix things" --force"#),
+        Command::Commit {
+            message: Some("fix things".into()),
+            force: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_command_line::<Command>("commit --message 'fix things'"),
+        Command::Commit {
+            message: Some("fix things".into()),
+            force: false,
+        }
+    );
+}
+
+#[test]
+fn escapes() {
+    assert_ok_eq!(
+        serde_args::from_command_line::<Command>(r#"add foo\ bar "\"baz\"" 'qux\'"#),
+        Command::Add(vec!["foo bar".into(), "\"baz\"".into(), "qux\\".into()])
+    );
+}
+
+#[test]
+fn empty_arguments() {
+    assert_ok_eq!(
+        serde_args::from_command_line::<Command>(r#"add "" foo ''"#),
+        Command::Add(vec!["".into(), "foo".into(), "".into()])
+    );
+    assert_ok_eq!(
+        serde_args::from_command_line::<Command>(r#"commit -m """#),
+        Command::Commit {
+            message: Some("".into()),
+            force: false,
+        }
+    );
+}
+
+#[test]
+fn no_variable_expansion() {
+    assert_ok_eq!(
+        serde_args::from_command_line::<Command>("add $HOME ~ *"),
+        Command::Add(vec!["$HOME".into(), "~".into(), "*".into()])
+    );
+}
+
+#[test]
+fn unterminated_quote() {
+    let error = format!(
+        "{}",
+        assert_err!(serde_args::from_command_line::<Command>(
+            r#"commit -m "fix things"#
+        ))
+    );
+
+    assert!(
+        error.starts_with("ERROR: unterminated double quote starting at byte 10\n\nUSAGE: "),
+        "{error}"
+    );
+}
+
+#[test]
+fn unterminated_single_quote() {
+    let error = format!(
+        "{}",
+        assert_err!(serde_args::from_command_line::<Command>("add 'foo bar"))
+    );
+
+    assert!(
+        error.starts_with("ERROR: unterminated single quote starting at byte 4\n\nUSAGE: "),
+        "{error}"
+    );
+}
@@ -7,9 +7,9 @@ fn empty() {
     assert_run_ok!(Command::new("tests/from_env/empty"));
     assert_run_ok!(Command::new("tests/from_env/empty").args(["--"]));
 
-    assert_run_err!(Command::new("tests/from_env/empty").args(["foo"]), "ERROR: unexpected positional argument: foo\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/empty").args(["--foo"]), "ERROR: unrecognized optional flag: --foo\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/empty").args(["--", "--"]), "ERROR: unexpected positional argument: --\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/empty").args(["foo"]), "ERROR: unexpected argument 'foo': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/empty").args(["--foo"]), "ERROR: unrecognized optional flag: '--foo'\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/empty").args(["--", "--"]), "ERROR: unexpected argument '--': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
     assert_run_err!(
         Command::new("tests/from_env/empty").args(["-h"]),
         "unit\n\nUSAGE: {name} \n\nOverride Options:\n  -h --help  Display this message.\n"
@@ -18,8 +18,8 @@ fn empty() {
         Command::new("tests/from_env/empty").args(["--help"]),
         "unit\n\nUSAGE: {name} \n\nOverride Options:\n  -h --help  Display this message.\n"
     );
-    assert_run_err!(Command::new("tests/from_env/empty").args(["--", "-h"]), "ERROR: unexpected positional argument: -h\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/empty").args(["--", "--help"]), "ERROR: unexpected positional argument: --help\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/empty").args(["--", "-h"]), "ERROR: unexpected argument '-h': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/empty").args(["--", "--help"]), "ERROR: unexpected argument '--help': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
 }
 
 #[test]
@@ -28,8 +28,8 @@ fn primitive() {
     assert_run_ok!(Command::new("tests/from_env/primitive").args(["--", "42"]));
     assert_run_ok!(Command::new("tests/from_env/primitive").args(["42", "--"]));
 
-    assert_run_err!(Command::new("tests/from_env/primitive").args(["foo"]), "ERROR: invalid type: expected u64, found foo\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/primitive").args(["-42"]), "ERROR: invalid type: expected u64, found -42\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/primitive").args(["foo"]), "ERROR: invalid value: expected u64, found 'foo'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/primitive").args(["-42"]), "ERROR: invalid value: expected u64 between 0 and 18446744073709551615, found '-42'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
     assert_run_err!(
         Command::new("tests/from_env/primitive").args(["-h"]),
         "u64\n\nUSAGE: {name} <u64>\n\nRequired Arguments:\n  <u64>  u64\n\nOverride Options:\n  -h --help  Display this message.\n"
@@ -44,19 +44,19 @@ fn primitive() {
     );
     assert_run_err!(
         Command::new("tests/from_env/primitive").args(["--", "-h"]),
-        "ERROR: invalid type: expected u64, found -h\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: invalid value: expected u64, found '-h'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/primitive").args(["--", "--help"]),
-        "ERROR: invalid type: expected u64, found --help\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: invalid value: expected u64, found '--help'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/primitive").args(["--", "42", "-h"]),
-        "ERROR: unexpected positional argument: -h\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument '-h': '{name}' takes exactly 1 argument (<u64>)\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/primitive").args(["--", "42", "--help"]),
-        "ERROR: unexpected positional argument: --help\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument '--help': '{name}' takes exactly 1 argument (<u64>)\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
 }
 
@@ -69,11 +69,12 @@ fn boolean() {
     assert_run_ok!(Command::new("tests/from_env/boolean").args(["true", "--"]));
     assert_run_ok!(Command::new("tests/from_env/boolean").args(["false", "--"]));
 
-    assert_run_err!(Command::new("tests/from_env/boolean").args(["foo"]), "ERROR: invalid type: expected a boolean, found foo\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/boolean").args(["0"]), "ERROR: invalid type: expected a boolean, found 0\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/boolean").args(["1"]), "ERROR: invalid type: expected a boolean, found 1\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/boolean").args(["TRUE"]), "ERROR: invalid type: expected a boolean, found TRUE\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env/boolean").args(["FALSE"]), "ERROR: invalid type: expected a boolean, found FALSE\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
+    for spelling in ["TRUE", "FALSE", "yes", "No", "on", "OFF", "1", "0"] {
+        assert_run_ok!(Command::new("tests/from_env/boolean").args([spelling]));
+    }
+
+    assert_run_err!(Command::new("tests/from_env/boolean").args(["foo"]), "ERROR: invalid value: expected a boolean (true/false, yes/no, on/off, or 1/0), found 'foo'\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/boolean").args(["2"]), "ERROR: invalid value: expected a boolean (true/false, yes/no, on/off, or 1/0), found '2'\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
     assert_run_err!(Command::new("tests/from_env/boolean"), "a boolean\n\nUSAGE: {name} <a boolean>\n\nRequired Arguments:\n  <a boolean>  a boolean\n\nOverride Options:\n  -h --help  Display this message.\n");
     assert_run_err!(Command::new("tests/from_env/boolean").args(["-h"]), "a boolean\n\nUSAGE: {name} <a boolean>\n\nRequired Arguments:\n  <a boolean>  a boolean\n\nOverride Options:\n  -h --help  Display this message.\n");
     assert_run_err!(Command::new("tests/from_env/boolean").args(["--help"]), "a boolean\n\nUSAGE: {name} <a boolean>\n\nRequired Arguments:\n  <a boolean>  a boolean\n\nOverride Options:\n  -h --help  Display this message.\n");
@@ -91,11 +92,11 @@ fn option() {
 
     assert_run_err!(
         Command::new("tests/from_env/option").args(["--", "--foo"]),
-        "ERROR: unrecognized optional flag: --foo\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '--foo'\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/option").args(["--", "-"]),
-        "ERROR: unrecognized optional flag: -\n\n  tip: a similar option exists: -h\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '-'\n\n  tip: a similar option exists: -h\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/option").args(["--", "-h"]),
@@ -123,7 +124,16 @@ fn required_fields() {
     assert_run_ok!(Command::new("tests/from_env/required_fields").args(["--", "--help", "42"]));
     assert_run_ok!(Command::new("tests/from_env/required_fields").args(["hello", "--", "-42"]));
     assert_run_ok!(Command::new("tests/from_env/required_fields").args(["hello", "--", "-3"]));
+    assert_run_ok!(Command::new("tests/from_env/required_fields").args(["hello", "-3"]));
     assert_run_ok!(Command::new("tests/from_env/required_fields").args(["hello", "42", "--"]));
+    assert_run_ok!(
+        Command::new("tests/from_env/required_fields").args(["hello", "9223372036854775807"])
+    );
+
+    assert_run_err!(
+        Command::new("tests/from_env/required_fields").args(["hello", "9223372036854775808"]),
+        "ERROR: invalid value for <baz>: expected i64 between -9223372036854775808 and 9223372036854775807, found '9223372036854775808'\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+    );
 
     assert_run_err!(
         Command::new("tests/from_env/required_fields").args(["--"]),
@@ -159,19 +169,23 @@ fn required_fields() {
     );
     assert_run_err!(
         Command::new("tests/from_env/required_fields").args(["hello", "42", "hello"]),
-        "ERROR: unexpected positional argument: hello\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument 'hello': '{name}' takes exactly 2 arguments (<foo> <baz>)\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/required_fields").args(["hello", "42", "--hello"]),
-        "ERROR: unrecognized optional flag: --hello\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '--hello'\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/required_fields").args(["hello", "--", "--help"]),
-        "ERROR: invalid type: expected i64, found --help\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+        "ERROR: invalid value for <baz>: expected i64, found '--help'\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
-        Command::new("tests/from_env/required_fields").args(["hello", "-3"]),
-        "ERROR: unrecognized optional flag: -3\n\n  tip: a similar option exists: -h\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+        Command::new("tests/from_env/required_fields").args(["--qux"]),
+        "ERROR: 2 problems found:\n  - unrecognized optional flag: '--qux'\n    tip: a similar option exists: --help\n  - missing required positional arguments: <foo> <baz>\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/required_fields").args(["hello", "42", "foo", "--qux"]),
+        "ERROR: 2 problems found:\n  - unexpected argument 'foo': '{name}' takes exactly 2 arguments (<foo> <baz>)\n  - unrecognized optional flag: '--qux'\n    tip: a similar option exists: --help\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
     );
 }
 
@@ -186,37 +200,46 @@ fn optional_fields() {
     assert_run_ok!(Command::new("tests/from_env/optional_fields")
         .args(["--foo", "hello", "--baz", "--", "-3",]));
 
+    assert_run_err!(
+        Command::new("tests/from_env/optional_fields").args(["--baz", "99999999999999999999"]),
+        "ERROR: invalid value for --baz: expected i64 between -9223372036854775808 and 9223372036854775807, found '99999999999999999999'\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+    );
+
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["--qux"]),
-        "ERROR: unrecognized optional flag: --qux\n\n  tip: a similar option exists: --foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '--qux'\n\n  tip: a similar option exists: --foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["--foo"]),
-        "ERROR: missing required positional argument: <a string>\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: missing argument <a string> for option '--foo'\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["--baz"]),
-        "ERROR: missing required positional argument: <i64>\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: missing argument <i64> for option '--baz'\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["--bar", "--", "--foo"]),
-        "ERROR: unexpected positional argument: --foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument '--foo': '{name}' takes no arguments\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["--foo", "hello", "--baz", "42", "--bar", "--help"]),
         "struct Args\n\nUSAGE: {name} [options]\n\nGlobal Options:\n  --foo <a string>  \n  --bar             \n  --baz <i64>       \n\nOverride Options:\n  -h --help  Display this message.\n"
     );
+    assert_run_err!(
+        Command::new("tests/from_env/optional_fields").args(["--foo", "--help"]),
+        "struct Args\n\nUSAGE: {name} [options]\n\nGlobal Options:\n  --foo <a string>  \n  --bar             \n  --baz <i64>       \n\nOverride Options:\n  -h --help  Display this message.\n"
+    );
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["--foo", "hello", "--baz", "-h", "42", "--bar"]),
         "struct Args\n\nUSAGE: {name} [options]\n\nGlobal Options:\n  --foo <a string>  \n  --bar             \n  --baz <i64>       \n\nOverride Options:\n  -h --help  Display this message.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["foo"]),
-        "ERROR: unexpected positional argument: foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument 'foo': '{name}' takes no arguments\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/optional_fields").args(["--", "--foo"]),
-        "ERROR: unexpected positional argument: --foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument '--foo': '{name}' takes no arguments\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
 }
 
@@ -232,7 +255,7 @@ fn boolean_fields() {
 
     assert_run_err!(
         Command::new("tests/from_env/boolean_fields").args(["--qux"]),
-        "ERROR: unrecognized optional flag: --qux\n\n  tip: a similar option exists: --foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '--qux'\n\n  tip: a similar option exists: --foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/boolean_fields").args(["--foo", "--foo"]),
@@ -240,11 +263,11 @@ fn boolean_fields() {
     );
     assert_run_err!(
         Command::new("tests/from_env/boolean_fields").args(["--foo", "true"]),
-        "ERROR: unexpected positional argument: true\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument 'true': '{name}' takes no arguments\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/boolean_fields").args(["--", "--foo"]),
-        "ERROR: unexpected positional argument: --foo\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument '--foo': '{name}' takes no arguments\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/boolean_fields").args(["--help"]),
@@ -279,21 +302,29 @@ fn r#enum() {
         "--help"
     ]));
 
+    assert_run_err!(
+        Command::new("tests/from_env/enum").args(["foo", "bar"]),
+        "ERROR: unexpected argument 'bar': '{name} foo' takes no arguments\n\nUSAGE: {name} foo \n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/enum").args(["bar", "42", "--", "baz", "qux"]),
+        "ERROR: unexpected argument 'baz' (and 1 more): '{name} bar' takes exactly 1 argument (<u8>)\n\nUSAGE: {name} bar <u8>\n\nFor more information, use --help.\n"
+    );
     assert_run_err!(
         Command::new("tests/from_env/enum").args(["--"]),
         "ERROR: missing required positional argument: <Command>\n\nUSAGE: {name} <Command>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env/enum"),
-        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
+        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
     );
     assert_run_err!(
         Command::new("tests/from_env/enum").args(["-h"]),
-        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
+        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
     );
     assert_run_err!(
         Command::new("tests/from_env/enum").args(["--help"]),
-        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
+        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
     );
     assert_run_err!(
         Command::new("tests/from_env/enum").args(["--help", "foo"]),
@@ -313,7 +344,11 @@ fn r#enum() {
     );
     assert_run_err!(
         Command::new("tests/from_env/enum").args(["quux"]),
-        "ERROR: unrecognized command: quux\n\n  tip: a similar command exists: qux\n\nUSAGE: {name} <Command>\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized command: 'quux'\n\n  tip: a similar command exists: qux\n\nUSAGE: {name} <Command>\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/enum").args(["quux", "--optional", "hi", "hello"]),
+        "ERROR: unrecognized command: 'quux'\n\n  tip: a similar command exists: qux\n\nUSAGE: {name} <Command>\n\nFor more information, use --help.\n"
     );
 }
 
@@ -329,7 +364,19 @@ fn struct_help() {
 fn enum_help() {
     assert_run_err!(
         Command::new("tests/from_env/enum_help").args(["--help"]),
-        "This is a description of my program.\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  This is a description of my program.\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  foo                       Don't provide any arguments to this command.\n  bar <u8>                  Provide one argument to this command.\n  baz [--<a string>]        You can do zero or one arguments for this command.\n  qux [options] <required>  This command takes a required argument and an optional flag.\n"
+        "This is a description of my program.\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  This is a description of my program.\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       Don't provide any arguments to this command.\n  bar <u8>                  Provide one argument to this command.\n  baz [--<a string>]        You can do zero or one arguments for this command.\n  qux [options] <required>  This command takes a required argument and an optional flag.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/enum_help").args(["-h"]),
+        "This is a description of my program.\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  This is a description of my program.\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       Don't provide any arguments to this command.\n  bar <u8>                  Provide one argument to this command.\n  baz [--<a string>]        You can do zero or one arguments for this command.\n  qux [options] <required>  This command takes a required argument and an optional flag.\n"
+    );
+}
+
+#[test]
+fn enum_help_groups() {
+    assert_run_err!(
+        Command::new("tests/from_env/enum_help_groups").args(["--help"]),
+        "This is a description of my program.\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  This is a description of my program.\n\nOverride Options:\n  -h --help  Display this message.\n\nRepository:\n  init              Create a new repository.\n  clone <a string>  Copy an existing repository.\n\nChanges:\n  add <a string>    Add a file to be committed.\n  commit [options]  Record the added changes.\n\nCommands:\n  status            Display the status of the working tree.\n"
     );
 }
 
@@ -345,31 +392,47 @@ fn struct_help_color() {
 fn enum_help_color() {
     assert_run_err!(
         Command::new("tests/from_env/enum_help_color").args(["--help"]),
-        "This is a description of my program.\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96m{name}\x1b[0m \x1b[36m<Command>\x1b[0m\n\n\x1b[97mRequired Arguments:\x1b[0m\n  \x1b[96m<Command>\x1b[0m  This is a description of my program.\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message.\n\n\x1b[97mCommand Variants:\x1b[0m\n  \x1b[96mfoo \x1b[0m\x1b[36m\x1b[0m                      Don't provide any arguments to this command.\n  \x1b[96mbar \x1b[0m\x1b[36m<u8>\x1b[0m                  Provide one argument to this command.\n  \x1b[96mbaz \x1b[0m\x1b[36m[--<a string>]\x1b[0m        You can do zero or one arguments for this command.\n  \x1b[96mqux \x1b[0m\x1b[36m[options] <required>\x1b[0m  This command takes a required argument and an optional flag.\n"
+        "This is a description of my program.\n\n\x1b[97mUSAGE\x1b[0m: \x1b[96m{name}\x1b[0m \x1b[36m<Command>\x1b[0m\n\n\x1b[97mRequired Arguments:\x1b[0m\n  \x1b[96m<Command>\x1b[0m  This is a description of my program.\n\n\x1b[97mOverride Options:\x1b[0m\n  \x1b[96m-h --help\x1b[0m  Display this message.\n\n\x1b[97mCommands:\x1b[0m\n  \x1b[96mfoo \x1b[0m\x1b[36m\x1b[0m                      Don't provide any arguments to this command.\n  \x1b[96mbar \x1b[0m\x1b[36m<u8>\x1b[0m                  Provide one argument to this command.\n  \x1b[96mbaz \x1b[0m\x1b[36m[--<a string>]\x1b[0m        You can do zero or one arguments for this command.\n  \x1b[96mqux \x1b[0m\x1b[36m[options] <required>\x1b[0m  This command takes a required argument and an optional flag.\n"
     );
 }
 
 #[test]
 fn struct_version() {
-    assert_run_err_literal!(
+    assert_run_err!(
         Command::new("tests/from_env/struct_version").args(["--version"]),
-        "0.0.0\n"
+        "{name} 0.0.0\n"
     );
 }
 
 #[test]
 fn enum_version() {
-    assert_run_err_literal!(
+    assert_run_err!(
         Command::new("tests/from_env/enum_version").args(["--version"]),
-        "0.0.0\n"
+        "{name} 0.0.0\n"
+    );
+}
+
+#[test]
+fn struct_version_color() {
+    assert_run_err!(
+        Command::new("tests/from_env/struct_version_color").args(["--version"]),
+        "\x1b[2m{name}\x1b[0m 0.0.0\n"
+    );
+}
+
+#[test]
+fn enum_version_color() {
+    assert_run_err!(
+        Command::new("tests/from_env/enum_version_color").args(["--version"]),
+        "\x1b[2m{name}\x1b[0m 0.0.0\n"
     );
 }
 
 #[test]
 fn struct_version_help() {
-    assert_run_err_literal!(
+    assert_run_err!(
         Command::new("tests/from_env/struct_version_help").args(["--version"]),
-        "0.0.0\n"
+        "{name} 0.0.0\n"
     );
 }
 
@@ -381,11 +444,99 @@ fn struct_deserialize_with() {
     );
 }
 
+#[test]
+fn struct_allow_hyphen_values() {
+    assert_run_ok!(Command::new("tests/from_env/struct_allow_hyphen_values").args(["-v"]));
+    assert_run_ok!(Command::new("tests/from_env/struct_allow_hyphen_values").args(["--foo"]));
+    assert_run_ok!(
+        Command::new("tests/from_env/struct_allow_hyphen_values").args(["--verbose", "-x"])
+    );
+    assert_run_ok!(Command::new("tests/from_env/struct_allow_hyphen_values").args(["--", "-v"]));
+}
+
+#[test]
+fn enum_sequence() {
+    assert_run_ok!(Command::new("tests/from_env/enum_sequence").args(["files", "a", "b", "c"]));
+    assert_run_ok!(Command::new("tests/from_env/enum_sequence").args(["files"]));
+    assert_run_ok!(Command::new("tests/from_env/enum_sequence").args(["count", "1", "2"]));
+
+    assert_run_err!(Command::new("tests/from_env/enum_sequence").args(["--help"]), "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  files <path string>...  \n  count <u64>...          \n");
+    assert_run_err!(Command::new("tests/from_env/enum_sequence").args(["files", "--help"]), "USAGE: {name} files <path string>...\n\nRequired Arguments:\n  <path string>  path string\n\nOverride Options:\n  -h --help  Display this message.\n");
+    assert_run_err!(Command::new("tests/from_env/enum_sequence").args(["count", "1", "a"]), "ERROR: invalid value: expected u64, found 'a'\n\nUSAGE: {name} count <u64>...\n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env/enum_sequence").args(["count", "1", "--foo"]), "ERROR: unrecognized optional flag: '--foo'\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} count <u64>...\n\nFor more information, use --help.\n");
+}
+
+#[test]
+fn struct_char() {
+    assert_run_ok!(Command::new("tests/from_env/struct_char").args(["a"]));
+    assert_run_ok!(Command::new("tests/from_env/struct_char").args(["🦀", "--shortcut", "é"]));
+
+    assert_run_err!(
+        Command::new("tests/from_env/struct_char").args(["ab"]),
+        "ERROR: expected a single character, got 'ab'\n\nUSAGE: {name} [options] <key>\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/struct_char").args(["a", "--shortcut", "🇺🇸"]),
+        "ERROR: expected a single character, got '🇺🇸', which is composed of 2 characters\n\nUSAGE: {name} [options] <key>\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/struct_char").args([""]),
+        "ERROR: expected a single character, got ''\n\nUSAGE: {name} [options] <key>\n\nFor more information, use --help.\n"
+    );
+}
+
+#[test]
+fn struct_value_name() {
+    assert_run_ok!(Command::new("tests/from_env/struct_value_name").args(["foo"]));
+    assert_run_ok!(
+        Command::new("tests/from_env/struct_value_name").args(["foo", "--output", "bar"])
+    );
+
+    assert_run_err!(
+        Command::new("tests/from_env/struct_value_name").args(["--output", "bar"]),
+        "ERROR: missing required positional argument: <FILE>\n\nUSAGE: {name} [options] <FILE>\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/struct_value_name").args(["foo", "--input"]),
+        "ERROR: unrecognized optional flag: '--input'\n\n  tip: a similar option exists: --output\n\nUSAGE: {name} [options] <FILE>\n\nFor more information, use --help.\n"
+    );
+}
+
+#[test]
+fn struct_global_options() {
+    assert_run_ok!(Command::new("tests/from_env/struct_global_options").args(["run", "foo"]));
+    assert_run_ok!(Command::new("tests/from_env/struct_global_options").args([
+        "--verbose",
+        "run",
+        "foo"
+    ]));
+    assert_run_ok!(Command::new("tests/from_env/struct_global_options").args([
+        "run",
+        "--verbose",
+        "foo"
+    ]));
+    assert_run_ok!(Command::new("tests/from_env/struct_global_options").args([
+        "run",
+        "foo",
+        "--verbose"
+    ]));
+    assert_run_ok!(Command::new("tests/from_env/struct_global_options").args(["--verbose", "stop"]));
+
+    assert_run_err!(
+        Command::new("tests/from_env/struct_global_options").args(["--verbose"]),
+        "ERROR: missing required command <command>, expected one of [\"run\", \"stop\"]\n\nUSAGE: {name} [options] <command>\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/struct_global_options").args(["--verbose", "--verbose", "stop"]),
+        "ERROR: the argument --verbose cannot be used multiple times\n\nUSAGE: {name} [options] stop \n\nFor more information, use --help.\n"
+    );
+}
+
 #[test]
 fn enum_version_help() {
-    assert_run_err_literal!(
+    assert_run_err!(
         Command::new("tests/from_env/enum_version_help").args(["--version"]),
-        "0.0.0\n"
+        "{name} 0.0.0\n"
     );
 }
 
@@ -393,6 +544,43 @@ fn enum_version_help() {
 fn enum_alias() {
     assert_run_err!(
         Command::new("tests/from_env/enum_alias"),
-        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  f foo                       \n  bar <u8>                    \n  b baz [--<a string>]        \n  q qux [options] <required>  \n"
+        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  f foo                       \n  bar <u8>                    \n  b baz [--<a string>]        \n  q qux [options] <required>  \n"
+    );
+}
+
+#[test]
+fn struct_any() {
+    assert_run_ok!(Command::new("tests/from_env/struct_any"));
+    assert_run_ok!(Command::new("tests/from_env/struct_any").args(["--mode", "slow"]));
+    assert_run_ok!(Command::new("tests/from_env/struct_any").args(["--mode", "1"]));
+
+    assert_run_err!(
+        Command::new("tests/from_env/struct_any").args(["--mode", "medium"]),
+        "ERROR: unknown command medium, expected one of [\"fast\", \"slow\"]\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/struct_any").args(["--mode", "3"]),
+        "ERROR: invalid value: expected fast, slow, or a numeric code, found integer `3`\n\nUSAGE: {name} [options]\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/struct_any").args(["-h"]),
+        "struct Args\n\nUSAGE: {name} [options]\n\nGlobal Options:\n  --mode <fast|slow>  \n\nOverride Options:\n  -h --help  Display this message.\n"
+    );
+}
+
+#[test]
+fn defaults() {
+    assert_run_ok!(Command::new("tests/from_env/defaults").args(["foo"]));
+    assert_run_ok!(Command::new("tests/from_env/defaults").args(["foo", "--output", "bar"]));
+    assert_run_ok!(Command::new("tests/from_env/defaults").args(["foo", "--level", "1"]));
+    assert_run_ok!(Command::new("tests/from_env/defaults").args(["foo", "--verbose"]));
+
+    assert_run_err!(
+        Command::new("tests/from_env/defaults").args(["--output", "bar"]),
+        "ERROR: missing required positional argument: <input>\n\nUSAGE: {name} [options] <input>\n\nFor more information, use --help.\n"
+    );
+    assert_run_err!(
+        Command::new("tests/from_env/defaults").args(["foo", "--level", "256"]),
+        "ERROR: invalid value for --level: expected u8 between 0 and 255, found '256'\n\nUSAGE: {name} [options] <input>\n\nFor more information, use --help.\n"
     );
 }
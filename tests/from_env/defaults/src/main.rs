@@ -0,0 +1,36 @@
+use std::{env, process::exit};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize)]
+struct Args {
+    input: String,
+    output: Option<String>,
+    level: Option<u8>,
+    verbose: bool,
+}
+
+fn main() {
+    let defaults = Args {
+        input: "default".into(),
+        output: Some("default".into()),
+        level: Some(3),
+        verbose: true,
+    };
+    match serde_args::from_env_with_defaults(defaults) {
+        Ok(args) => {
+            let given: Vec<String> = env::args().collect();
+            if !given.contains(&"--output".to_owned()) {
+                assert_eq!(args.output.as_deref(), Some("default"));
+            }
+            if !given.contains(&"--level".to_owned()) {
+                assert_eq!(args.level, Some(3));
+            }
+            assert!(args.verbose);
+            assert_ne!(args.input, "default");
+        }
+        Err(error) => {
+            println!("{}", error);
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use serde::Deserialize;
+use std::process::exit;
+
+/// This is a description of my program.
+#[serde_args::generate(doc_help)]
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Command {
+    /// [Repository]
+    /// Create a new repository.
+    Init,
+    /// [Changes]
+    /// Add a file to be committed.
+    Add(String),
+    /// Display the status of the working tree.
+    Status,
+    /// [Repository]
+    /// Copy an existing repository.
+    Clone(String),
+    /// [Changes]
+    /// Record the added changes.
+    Commit {
+        message: Option<String>,
+    },
+}
+
+fn main() {
+    if let Err(error) = serde_args::from_env::<Command>() {
+        println!("{}", error);
+        exit(1);
+    }
+}
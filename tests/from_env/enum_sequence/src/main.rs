@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use std::{
+    path::PathBuf,
+    process::exit,
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Command {
+    Files(Vec<PathBuf>),
+    Count(Vec<u64>),
+}
+
+fn main() {
+    if let Err(error) = serde_args::from_env::<Command>() {
+        println!("{}", error);
+        exit(1);
+    }
+}
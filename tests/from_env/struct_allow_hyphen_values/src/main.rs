@@ -0,0 +1,25 @@
+use serde::Deserialize;
+use std::process::exit;
+
+#[serde_args::generate]
+#[derive(Deserialize)]
+struct Args {
+    #[serde_args(allow_hyphen_values)]
+    pattern: String,
+    #[serde(default)]
+    verbose: bool,
+}
+
+fn main() {
+    match serde_args::from_env::<Args>() {
+        Ok(args) => {
+            if !args.pattern.starts_with('-') {
+                exit(2);
+            }
+        }
+        Err(error) => {
+            println!("{}", error);
+            exit(1);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use serde::{
+    de,
+    de::{
+        Deserializer,
+        Unexpected,
+        Visitor,
+    },
+    Deserialize,
+};
+use std::{
+    fmt,
+    fmt::Formatter,
+    process::exit,
+};
+
+/// A mode given either by name or by numeric code.
+enum Mode {
+    Fast,
+    Slow,
+}
+
+impl<'de> Deserialize<'de> for Mode {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ModeVisitor;
+
+        impl Visitor<'_> for ModeVisitor {
+            type Value = Mode;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("fast, slow, or a numeric code")
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    0 => Ok(Mode::Fast),
+                    1 => Ok(Mode::Slow),
+                    _ => Err(E::invalid_value(Unexpected::Unsigned(value), &self)),
+                }
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                match value {
+                    "fast" => Ok(Mode::Fast),
+                    "slow" => Ok(Mode::Slow),
+                    _ => Err(E::unknown_variant(value, &["fast", "slow"])),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(ModeVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+struct Args {
+    #[allow(dead_code)]
+    mode: Option<Mode>,
+}
+
+fn main() {
+    if let Err(error) = serde_args::from_env::<Args>() {
+        println!("{}", error);
+        exit(1);
+    }
+}
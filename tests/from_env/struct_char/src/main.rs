@@ -0,0 +1,15 @@
+use serde::Deserialize;
+use std::process::exit;
+
+#[derive(Deserialize)]
+struct Args {
+    key: char,
+    shortcut: Option<char>,
+}
+
+fn main() {
+    if let Err(error) = serde_args::from_env::<Args>() {
+        println!("{}", error);
+        exit(1);
+    }
+}
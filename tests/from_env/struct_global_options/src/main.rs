@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use std::process::exit;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Command {
+    Run {
+        #[allow(dead_code)]
+        path: String,
+    },
+    Stop,
+}
+
+#[derive(Deserialize)]
+struct Args {
+    #[allow(dead_code)]
+    verbose: bool,
+    #[allow(dead_code)]
+    command: Command,
+}
+
+fn main() {
+    if let Err(error) = serde_args::from_env::<Args>() {
+        println!("{}", error);
+        exit(1);
+    }
+}
@@ -0,0 +1,17 @@
+use serde::Deserialize;
+use std::process::exit;
+
+#[serde_args::generate]
+#[derive(Deserialize)]
+struct Args {
+    #[serde_args(value_name = "FILE")]
+    input: String,
+    output: Option<String>,
+}
+
+fn main() {
+    if let Err(error) = serde_args::from_env::<Args>() {
+        println!("{}", error);
+        exit(1);
+    }
+}
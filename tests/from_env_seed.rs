@@ -9,9 +9,9 @@ fn empty() {
     assert_run_ok!(Command::new("tests/from_env_seed/empty"));
     assert_run_ok!(Command::new("tests/from_env_seed/empty").args(["--"]));
 
-    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["foo"]), "ERROR: unexpected positional argument: foo\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--foo"]), "ERROR: unrecognized optional flag: --foo\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--", "--"]), "ERROR: unexpected positional argument: --\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["foo"]), "ERROR: unexpected argument 'foo': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--foo"]), "ERROR: unrecognized optional flag: '--foo'\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--", "--"]), "ERROR: unexpected argument '--': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
     assert_run_err!(
         Command::new("tests/from_env_seed/empty").args(["-h"]),
         "unit\n\nUSAGE: {name} \n\nOverride Options:\n  -h --help  Display this message.\n"
@@ -20,8 +20,8 @@ fn empty() {
         Command::new("tests/from_env_seed/empty").args(["--help"]),
         "unit\n\nUSAGE: {name} \n\nOverride Options:\n  -h --help  Display this message.\n"
     );
-    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--", "-h"]), "ERROR: unexpected positional argument: -h\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--", "--help"]), "ERROR: unexpected positional argument: --help\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--", "-h"]), "ERROR: unexpected argument '-h': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/empty").args(["--", "--help"]), "ERROR: unexpected argument '--help': '{name}' takes no arguments\n\nUSAGE: {name} \n\nFor more information, use --help.\n");
 }
 
 #[test]
@@ -30,8 +30,8 @@ fn primitive() {
     assert_run_ok!(Command::new("tests/from_env_seed/primitive").args(["--", "42"]));
     assert_run_ok!(Command::new("tests/from_env_seed/primitive").args(["42", "--"]));
 
-    assert_run_err!(Command::new("tests/from_env_seed/primitive").args(["foo"]), "ERROR: invalid type: expected u64, found foo\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/primitive").args(["-42"]), "ERROR: invalid type: expected u64, found -42\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/primitive").args(["foo"]), "ERROR: invalid value: expected u64, found 'foo'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/primitive").args(["-42"]), "ERROR: invalid value: expected u64 between 0 and 18446744073709551615, found '-42'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n");
     assert_run_err!(
         Command::new("tests/from_env_seed/primitive").args(["-h"]),
         "u64\n\nUSAGE: {name} <u64>\n\nRequired Arguments:\n  <u64>  u64\n\nOverride Options:\n  -h --help  Display this message.\n"
@@ -46,19 +46,19 @@ fn primitive() {
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/primitive").args(["--", "-h"]),
-        "ERROR: invalid type: expected u64, found -h\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: invalid value: expected u64, found '-h'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/primitive").args(["--", "--help"]),
-        "ERROR: invalid type: expected u64, found --help\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: invalid value: expected u64, found '--help'\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/primitive").args(["--", "42", "-h"]),
-        "ERROR: unexpected positional argument: -h\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument '-h': '{name}' takes exactly 1 argument (<u64>)\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/primitive").args(["--", "42", "--help"]),
-        "ERROR: unexpected positional argument: --help\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument '--help': '{name}' takes exactly 1 argument (<u64>)\n\nUSAGE: {name} <u64>\n\nFor more information, use --help.\n"
     );
 }
 
@@ -71,11 +71,12 @@ fn boolean() {
     assert_run_ok!(Command::new("tests/from_env_seed/boolean").args(["true", "--"]));
     assert_run_ok!(Command::new("tests/from_env_seed/boolean").args(["false", "--"]));
 
-    assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["foo"]), "ERROR: invalid type: expected a boolean, found foo\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["0"]), "ERROR: invalid type: expected a boolean, found 0\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["1"]), "ERROR: invalid type: expected a boolean, found 1\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["TRUE"]), "ERROR: invalid type: expected a boolean, found TRUE\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
-    assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["FALSE"]), "ERROR: invalid type: expected a boolean, found FALSE\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
+    for spelling in ["TRUE", "FALSE", "yes", "No", "on", "OFF", "1", "0"] {
+        assert_run_ok!(Command::new("tests/from_env_seed/boolean").args([spelling]));
+    }
+
+    assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["foo"]), "ERROR: invalid value: expected a boolean (true/false, yes/no, on/off, or 1/0), found 'foo'\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
+    assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["2"]), "ERROR: invalid value: expected a boolean (true/false, yes/no, on/off, or 1/0), found '2'\n\nUSAGE: {name} <a boolean>\n\nFor more information, use --help.\n");
     assert_run_err!(Command::new("tests/from_env_seed/boolean"), "a boolean\n\nUSAGE: {name} <a boolean>\n\nRequired Arguments:\n  <a boolean>  a boolean\n\nOverride Options:\n  -h --help  Display this message.\n");
     assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["-h"]), "a boolean\n\nUSAGE: {name} <a boolean>\n\nRequired Arguments:\n  <a boolean>  a boolean\n\nOverride Options:\n  -h --help  Display this message.\n");
     assert_run_err!(Command::new("tests/from_env_seed/boolean").args(["--help"]), "a boolean\n\nUSAGE: {name} <a boolean>\n\nRequired Arguments:\n  <a boolean>  a boolean\n\nOverride Options:\n  -h --help  Display this message.\n");
@@ -93,11 +94,11 @@ fn option() {
 
     assert_run_err!(
         Command::new("tests/from_env_seed/option").args(["--", "--foo"]),
-        "ERROR: unrecognized optional flag: --foo\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '--foo'\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/option").args(["--", "-"]),
-        "ERROR: unrecognized optional flag: -\n\n  tip: a similar option exists: -h\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '-'\n\n  tip: a similar option exists: -h\n\nUSAGE: {name} [--<a string>]\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/option").args(["--", "-h"]),
@@ -125,6 +126,7 @@ fn r#struct() {
     assert_run_ok!(Command::new("tests/from_env_seed/struct").args(["--", "--help", "42"]));
     assert_run_ok!(Command::new("tests/from_env_seed/struct").args(["hello", "--", "-42"]));
     assert_run_ok!(Command::new("tests/from_env_seed/struct").args(["hello", "--", "-3"]));
+    assert_run_ok!(Command::new("tests/from_env_seed/struct").args(["hello", "-3"]));
     assert_run_ok!(Command::new("tests/from_env_seed/struct").args(["hello", "42", "--"]));
 
     assert_run_err!(
@@ -161,19 +163,15 @@ fn r#struct() {
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/struct").args(["hello", "42", "hello"]),
-        "ERROR: unexpected positional argument: hello\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+        "ERROR: unexpected argument 'hello': '{name}' takes exactly 2 arguments (<foo> <baz>)\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/struct").args(["hello", "42", "--hello"]),
-        "ERROR: unrecognized optional flag: --hello\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized optional flag: '--hello'\n\n  tip: a similar option exists: --help\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/struct").args(["hello", "--", "--help"]),
-        "ERROR: invalid type: expected i64, found --help\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
-    );
-    assert_run_err!(
-        Command::new("tests/from_env_seed/struct").args(["hello", "-3"]),
-        "ERROR: unrecognized optional flag: -3\n\n  tip: a similar option exists: -h\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
+        "ERROR: invalid value for <baz>: expected i64, found '--help'\n\nUSAGE: {name} <foo> <baz>\n\nFor more information, use --help.\n"
     );
 }
 
@@ -212,15 +210,15 @@ fn r#enum() {
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/enum"),
-        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
+        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/enum").args(["-h"]),
-        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
+        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/enum").args(["--help"]),
-        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommand Variants:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
+        "enum Command\n\nUSAGE: {name} <Command>\n\nRequired Arguments:\n  <Command>  enum Command\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  foo                       \n  bar <u8>                  \n  baz [--<a string>]        \n  qux [options] <required>  \n"
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/enum").args(["--help", "foo"]),
@@ -240,6 +238,6 @@ fn r#enum() {
     );
     assert_run_err!(
         Command::new("tests/from_env_seed/enum").args(["quux"]),
-        "ERROR: unrecognized command: quux\n\n  tip: a similar command exists: qux\n\nUSAGE: {name} <Command>\n\nFor more information, use --help.\n"
+        "ERROR: unrecognized command: 'quux'\n\n  tip: a similar command exists: qux\n\nUSAGE: {name} <Command>\n\nFor more information, use --help.\n"
     );
 }
@@ -0,0 +1,2172 @@
+use claims::{
+    assert_err,
+    assert_ok,
+    assert_ok_eq,
+    assert_some,
+};
+use serde::de;
+use serde_derive::Deserialize;
+use std::{
+    collections::{
+        BTreeSet,
+        HashSet,
+    },
+    ffi::OsString,
+    fmt,
+    fmt::Formatter,
+    num::{
+        NonZeroU8,
+        NonZeroUsize,
+    },
+    path::PathBuf,
+};
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Required {
+    foo: String,
+    bar: (),
+    baz: i64,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Optional {
+    foo: Option<String>,
+    bar: Option<()>,
+    baz: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Command {
+    Foo,
+    Bar(u8),
+    Baz(Option<String>),
+}
+
+#[test]
+fn empty() {
+    assert_ok!(serde_args::from_slice::<()>(&["executable"]));
+    assert_ok!(serde_args::from_slice::<()>(&["executable", "--"]));
+
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<()>(&["executable", "foo"]))
+        ),
+        "ERROR: unexpected argument 'foo': 'executable' takes no arguments\n\nUSAGE: executable \n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn executable_path() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<()>(&["/usr/bin/executable", "foo"]))
+        ),
+        "ERROR: unexpected argument 'foo': 'executable' takes no arguments\n\nUSAGE: executable \n\nFor more information, use --help."
+    );
+}
+
+#[test]
+#[should_panic(expected = "could not obtain binary name")]
+fn no_executable() {
+    let _ = serde_args::from_slice::<()>(&[]);
+}
+
+#[test]
+fn required_fields() {
+    const CASES: &[&[&str]] = &[
+        &["executable", "foo", "42"],
+        &["executable", "foo", "--", "42"],
+        &["executable", "--", "foo", "42"],
+    ];
+
+    for case in CASES {
+        assert_ok_eq!(
+            serde_args::from_slice::<Required>(case),
+            Required {
+                foo: "foo".into(),
+                bar: (),
+                baz: 42,
+            }
+        );
+    }
+
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Required>(&[
+                "executable",
+                "hello",
+                "42",
+                "hello"
+            ]))
+        ),
+        "ERROR: unexpected argument 'hello': 'executable' takes exactly 2 arguments (<foo> <baz>)\n\nUSAGE: executable <foo> <baz>\n\nFor more information, use --help."
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Required>(&[
+                "executable",
+                "hello",
+                "--",
+                "--help"
+            ]))
+        ),
+        "ERROR: invalid value for <baz>: expected i64, found '--help'\n\nUSAGE: executable <foo> <baz>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn optional_fields() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Optional>(&["executable"]),
+        Optional {
+            foo: None,
+            bar: None,
+            baz: None,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Optional>(&[
+            "executable",
+            "--baz",
+            "42",
+            "--bar",
+            "--foo",
+            "hello"
+        ]),
+        Optional {
+            foo: Some("hello".into()),
+            bar: Some(()),
+            baz: Some(42),
+        }
+    );
+}
+
+#[test]
+fn r#enum() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "foo"]),
+        Command::Foo
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "bar", "42"]),
+        Command::Bar(42)
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "baz"]),
+        Command::Baz(None)
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "baz", "--foo"]),
+        Command::Baz(Some("foo".into()))
+    );
+
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Command>(&["executable", "foo", "bar"]))
+        ),
+        "ERROR: unexpected argument 'bar': 'executable foo' takes no arguments\n\nUSAGE: executable foo \n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Force {
+    force: Option<bool>,
+}
+
+#[test]
+fn boolean_spellings() {
+    for (spelling, expected) in [
+        ("true", true),
+        ("false", false),
+        ("Yes", true),
+        ("NO", false),
+        ("on", true),
+        ("Off", false),
+        ("1", true),
+        ("0", false),
+    ] {
+        assert_ok_eq!(
+            serde_args::from_slice::<Force>(&["executable", "--force", spelling]),
+            Force {
+                force: Some(expected),
+            }
+        );
+    }
+
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Force>(&[
+                "executable",
+                "--force",
+                "maybe"
+            ]))
+        ),
+        "ERROR: invalid value for --force: expected a boolean (true/false, yes/no, on/off, or 1/0), found 'maybe'\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+    );
+}
+
+/// Converts *configuration* files between formats.
+///
+/// Reads from `stdin` unless an input
+/// file is given.
+///
+/// ```
+/// convert config.json
+/// ```
+#[cfg(feature = "macros")]
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Markdown {
+    /// The file to read, in one of the
+    /// supported formats:
+    /// - `json`
+    /// - `toml`
+    input: String,
+    /// Overwrite the output file if it **already** exists.
+    force: bool,
+    /// The format to write:
+    /// 1. `json`
+    /// 2. `toml`
+    format: Option<String>,
+}
+
+/// Manages *remote* repositories.
+#[cfg(feature = "macros")]
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)]
+enum MarkdownCommand {
+    /// Adds a remote named `name`
+    /// at `url`.
+    ///
+    /// Fails if the remote already exists.
+    Add { name: String, url: String },
+    /// Removes a remote.
+    Remove(String),
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn doc_help_markdown() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Markdown>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "Converts configuration files between formats.\n\nReads from stdin unless an input file is given.\n\nUSAGE: executable [options] <input>\n\nRequired Arguments:\n  <input>  The file to read, in one of the supported formats:\n             - json\n             - toml\n\nGlobal Options:\n  --format <a string>  The format to write:\n                         1. json\n                         2. toml\n  --force              Overwrite the output file if it already exists.\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn doc_help_markdown_commands() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<MarkdownCommand>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "Manages remote repositories.\n\nUSAGE: executable <MarkdownCommand>\n\nRequired Arguments:\n  <MarkdownCommand>  Manages remote repositories.\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  add <name> <url>   Adds a remote named name at url.\n\n                     Fails if the remote already exists.\n  remove <a string>  Removes a remote."
+    );
+}
+
+/// Copies a file.
+///
+/// # Examples
+///
+/// ```text
+/// $ copy source.txt destination.txt
+///   Copies source.txt to destination.txt.
+///
+/// $ copy --force source.txt destination.txt
+/// ```
+#[cfg(feature = "macros")]
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Examples {
+    /// The file to copy.
+    source: String,
+    /// Where to copy the file to.
+    destination: String,
+    /// Overwrite the destination if it exists.
+    force: bool,
+}
+
+/// Manages remote repositories.
+///
+/// # Examples
+/// $ remote add origin https://example.com
+#[cfg(feature = "macros")]
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)]
+enum ExamplesCommand {
+    /// Adds a remote.
+    Add { name: String, url: String },
+    /// Removes a remote.
+    Remove(String),
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn doc_help_examples() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Examples>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "Copies a file.\n\nUSAGE: executable [options] <source> <destination>\n\nRequired Arguments:\n  <source>       The file to copy.\n  <destination>  Where to copy the file to.\n\nGlobal Options:\n  --force   Overwrite the destination if it exists.\n\nOverride Options:\n  -h --help  Display this message.\n\nEXAMPLES:\n  $ copy source.txt destination.txt\n    Copies source.txt to destination.txt.\n\n  $ copy --force source.txt destination.txt"
+    );
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn doc_help_examples_commands() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<ExamplesCommand>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "Manages remote repositories.\n\nUSAGE: executable <ExamplesCommand>\n\nRequired Arguments:\n  <ExamplesCommand>  Manages remote repositories.\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  add <name> <url>   Adds a remote.\n  remove <a string>  Removes a remote.\n\nEXAMPLES:\n  $ remote add origin https://example.com"
+    );
+}
+
+#[serde_args::generate(no_auto_help, no_empty_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Embedded {
+    foo: String,
+}
+
+#[test]
+fn no_auto_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Embedded>(&[
+                "executable",
+                "bar",
+                "--help"
+            ]))
+        ),
+        "ERROR: unrecognized optional flag: '--help'\n\nUSAGE: executable <foo>"
+    );
+}
+
+#[test]
+fn no_empty_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Embedded>(&["executable"]))
+        ),
+        "ERROR: missing required positional argument: <foo>\n\nUSAGE: executable <foo>"
+    );
+}
+
+#[test]
+fn no_auto_help_compiled() {
+    let compiled = assert_ok!(serde_args::compile::<Embedded>());
+
+    assert_ok_eq!(
+        compiled.parse(["executable", "--", "--help"]),
+        Embedded {
+            foo: "--help".into()
+        }
+    );
+}
+
+/// Clone a repository.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Clone {
+    /// The repository to clone from.
+    repository: String,
+    /// Be more verbose.
+    verbose: bool,
+    /// The directory to clone into.
+    #[serde_args(positional)]
+    directory: Option<String>,
+}
+
+#[test]
+fn optional_positional_present() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Clone>(&["executable", "foo", "bar"]),
+        Clone {
+            repository: "foo".into(),
+            verbose: false,
+            directory: Some("bar".into()),
+        }
+    );
+}
+
+#[test]
+fn optional_positional_absent() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Clone>(&["executable", "foo"]),
+        Clone {
+            repository: "foo".into(),
+            verbose: false,
+            directory: None,
+        }
+    );
+}
+
+#[test]
+fn optional_positional_after_options() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Clone>(&["executable", "foo", "--verbose", "bar"]),
+        Clone {
+            repository: "foo".into(),
+            verbose: true,
+            directory: Some("bar".into()),
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Clone>(&["executable", "foo", "bar", "--verbose"]),
+        Clone {
+            repository: "foo".into(),
+            verbose: true,
+            directory: Some("bar".into()),
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Clone>(&["executable", "foo", "--", "--verbose"]),
+        Clone {
+            repository: "foo".into(),
+            verbose: false,
+            directory: Some("--verbose".into()),
+        }
+    );
+}
+
+#[test]
+fn optional_positional_too_many() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Clone>(&[
+                "executable",
+                "foo",
+                "bar",
+                "baz"
+            ]))
+        ),
+        "ERROR: unexpected argument 'baz': 'executable' takes at most 2 arguments (<repository> [<directory>])\n\nUSAGE: executable [options] <repository> [<directory>]\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn optional_positional_missing_required() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Clone>(&["executable", "--verbose"]))
+        ),
+        "ERROR: missing required positional argument: <repository>\n\nUSAGE: executable [options] <repository> [<directory>]\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn optional_positional_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Clone>(&["executable", "--help"]))
+        ),
+        "Clone a repository.\n\nUSAGE: executable [options] <repository> [<directory>]\n\nRequired Arguments:\n  <repository>  The repository to clone from.\n  <directory>   The directory to clone into.\n\nGlobal Options:\n  --verbose   Be more verbose.\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+/// Build a target.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Build {
+    /// The target to build.
+    target: String,
+    /// The file to write the output to.
+    #[serde_args(required)]
+    output: Option<String>,
+    /// Be more verbose.
+    verbose: bool,
+}
+
+#[test]
+fn required_option_present() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Build>(&["executable", "foo", "--output", "bar"]),
+        Build {
+            target: "foo".into(),
+            output: Some("bar".into()),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn required_option_absent() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Build>(&[
+                "executable",
+                "foo",
+                "--verbose"
+            ]))
+        ),
+        "ERROR: missing required option: '--output'\n\nUSAGE: executable [options] --output <a string> <target>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn required_option_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Build>(&["executable", "--help"]))
+        ),
+        "Build a target.\n\nUSAGE: executable [options] --output <a string> <target>\n\nRequired Arguments:\n  <target>  The target to build.\n\nGlobal Options:\n  --output <a string>  The file to write the output to.\n  --verbose            Be more verbose.\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[serde_args::generate]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct NotLast {
+    #[serde_args(positional)]
+    directory: Option<String>,
+    repository: String,
+}
+
+#[test]
+fn optional_positional_not_last() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<NotLast>(&["executable", "foo"]))
+        ),
+        "optional positional field `directory` must be the last positional argument"
+    );
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct NestedNotLast {
+    clone: Clone,
+    branch: String,
+}
+
+#[test]
+fn optional_positional_nested_not_last() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<NestedNotLast>(&[
+                "executable",
+                "foo",
+                "bar"
+            ]))
+        ),
+        "optional positional field `directory` must be the last positional argument"
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Tag {
+    package: String,
+    tags: Option<Vec<String>>,
+}
+
+#[test]
+fn sequence_option_repeated() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Tag>(&["executable", "--tags", "a", "foo", "--tags", "b"]),
+        Tag {
+            package: "foo".into(),
+            tags: Some(vec!["a".into(), "b".into()]),
+        }
+    );
+}
+
+#[test]
+fn sequence_option_single_value_per_occurrence() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Tag>(&["executable", "--tags", "a", "foo"]),
+        Tag {
+            package: "foo".into(),
+            tags: Some(vec!["a".into()]),
+        }
+    );
+}
+
+#[test]
+fn sequence_option_does_not_take_positional() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Tag>(&[
+                "executable",
+                "--tags",
+                "a",
+                "b",
+                "foo"
+            ]))
+        ),
+        "ERROR: unexpected argument 'foo': 'executable' takes exactly 1 argument (<package>)\n\nUSAGE: executable [options] <package>\n\nFor more information, use --help."
+    );
+}
+
+/// Publish a package.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Publish {
+    /// The package to publish.
+    package: String,
+    /// Tags to publish the package with.
+    #[serde(alias = "t")]
+    #[serde_args(value_delimiter = ',')]
+    tags: Option<Vec<String>>,
+    /// Be more verbose.
+    verbose: bool,
+}
+
+#[test]
+fn value_delimiter() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Publish>(&["executable", "foo", "--tags", "rust,cli,serde"]),
+        Publish {
+            package: "foo".into(),
+            tags: Some(vec!["rust".into(), "cli".into(), "serde".into()]),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn value_delimiter_before_positional() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Publish>(&["executable", "--tags", "rust,cli", "foo"]),
+        Publish {
+            package: "foo".into(),
+            tags: Some(vec!["rust".into(), "cli".into()]),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn value_delimiter_mixed_with_repeated() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Publish>(&[
+            "executable",
+            "--tags",
+            "rust,cli",
+            "foo",
+            "--verbose",
+            "-t",
+            "serde",
+            "--tags",
+            "args,parser",
+        ]),
+        Publish {
+            package: "foo".into(),
+            tags: Some(vec![
+                "rust".into(),
+                "cli".into(),
+                "serde".into(),
+                "args".into(),
+                "parser".into()
+            ]),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn value_delimiter_absent() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Publish>(&["executable", "foo"]),
+        Publish {
+            package: "foo".into(),
+            tags: None,
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn value_delimiter_empty_element() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Publish>(&[
+                "executable",
+                "foo",
+                "--tags",
+                "rust,,serde"
+            ]))
+        ),
+        "ERROR: empty value in list given to --tags\n\nUSAGE: executable [options] <package>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn value_delimiter_trailing_delimiter() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Publish>(&[
+                "executable",
+                "foo",
+                "-t",
+                "rust,cli,"
+            ]))
+        ),
+        "ERROR: trailing ',' in list given to -t\n\nUSAGE: executable [options] <package>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn value_delimiter_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Publish>(&["executable", "--help"]))
+        ),
+        "Publish a package.\n\nUSAGE: executable [options] <package>\n\nRequired Arguments:\n  <package>  The package to publish.\n\nGlobal Options:\n  -t --tags <a string>,...  Tags to publish the package with.\n     --verbose              Be more verbose.\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+/// Sum some numbers.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Sum {
+    /// Where to write the sum.
+    output: String,
+    /// The numbers to sum.
+    #[serde(alias = "n")]
+    #[serde_args(multiple)]
+    nums: Option<Vec<u32>>,
+    /// Be more verbose.
+    verbose: bool,
+}
+
+#[test]
+fn multiple() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Sum>(&["executable", "out", "--nums", "1", "2", "3"]),
+        Sum {
+            output: "out".into(),
+            nums: Some(vec![1, 2, 3]),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn multiple_end_of_options() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Sum>(&["executable", "--nums", "1", "2", "--", "out"]),
+        Sum {
+            output: "out".into(),
+            nums: Some(vec![1, 2]),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn multiple_until_option() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Sum>(&["executable", "--nums", "1", "2", "--verbose", "out"]),
+        Sum {
+            output: "out".into(),
+            nums: Some(vec![1, 2]),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn multiple_repeated() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Sum>(&[
+            "executable",
+            "out",
+            "--nums",
+            "1",
+            "2",
+            "--verbose",
+            "-n",
+            "3"
+        ]),
+        Sum {
+            output: "out".into(),
+            nums: Some(vec![1, 2, 3]),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn multiple_absent() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Sum>(&["executable", "out"]),
+        Sum {
+            output: "out".into(),
+            nums: None,
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn multiple_consumes_positional() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Sum>(&[
+                "executable",
+                "--nums",
+                "1",
+                "out"
+            ]))
+        ),
+        "ERROR: missing required positional argument: <output>\n\nUSAGE: executable [options] <output>\n\nFor more information, use --help."
+    );
+}
+
+#[serde_args::generate]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct UnsupportedMultiple {
+    #[serde_args(multiple)]
+    name: Option<String>,
+}
+
+#[test]
+fn multiple_unsupported() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<UnsupportedMultiple>(&[
+                "executable"
+            ]))
+        ),
+        "field `name` taking multiple values must be an optional sequence of primitive values without a value delimiter"
+    );
+}
+
+/// A version control system.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Vcs {
+    /// Be more verbose.
+    verbose: bool,
+    /// The command to run.
+    command: VcsCommand,
+}
+
+/// A command of the version control system.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum VcsCommand {
+    /// Add files to the index.
+    Add,
+    /// Record changes.
+    Commit,
+    /// Update the remote.
+    Push,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct UndocumentedVcs {
+    verbose: bool,
+    command: UndocumentedVcsCommand,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum UndocumentedVcsCommand {
+    Add,
+    Commit,
+    Push,
+}
+
+#[test]
+fn command_field() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Vcs>(&["executable", "--verbose", "commit"]),
+        Vcs {
+            verbose: true,
+            command: VcsCommand::Commit,
+        }
+    );
+}
+
+#[test]
+fn command_field_missing() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Vcs>(&["executable", "--verbose"]))
+        ),
+        "ERROR: missing required command <command>, expected one of [\"add\", \"commit\", \"push\"]\n\nUSAGE: executable [options] <command>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn command_field_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Vcs>(&["executable", "--help"]))
+        ),
+        "A version control system.\n\nUSAGE: executable [options] <command>\n\nRequired Arguments:\n  <command>  The command to run.\n\nGlobal Options:\n  --verbose   Be more verbose.\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  add      Add files to the index.\n  commit   Record changes.\n  push     Update the remote."
+    );
+}
+
+#[test]
+fn command_field_missing_undocumented() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<UndocumentedVcs>(&[
+                "executable",
+                "--verbose"
+            ]))
+        ),
+        "ERROR: missing required command <command>, expected one of [\"add\", \"commit\", \"push\"]\n\nUSAGE: executable [options] <command>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn command_field_help_undocumented() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<UndocumentedVcs>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "struct UndocumentedVcs\n\nUSAGE: executable [options] <command>\n\nRequired Arguments:\n  <command>  \n\nGlobal Options:\n  --verbose   \n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  add      \n  commit   \n  push     "
+    );
+}
+
+/// A tree of names.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Tree {
+    /// The name of this node.
+    name: String,
+    /// The child of this node.
+    child: Option<Box<Tree>>,
+}
+
+#[test]
+fn recursive_type() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Tree>(&["executable", "foo"]))
+        ),
+        "recursive or overly deep type detected while tracing: Tree -> Option -> Tree"
+    );
+}
+
+/// Run a program.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Wrapper {
+    /// Be more verbose.
+    verbose: bool,
+    /// The program to run.
+    program: String,
+    /// Arguments passed to the program.
+    #[serde_args(trailing)]
+    args: Vec<OsString>,
+}
+
+#[test]
+fn trailing() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Wrapper>(&[
+            "executable",
+            "cargo",
+            "build",
+            "--release",
+            "-p",
+            "foo"
+        ]),
+        Wrapper {
+            verbose: false,
+            program: "cargo".into(),
+            args: vec![
+                "build".into(),
+                "--release".into(),
+                "-p".into(),
+                "foo".into()
+            ],
+        }
+    );
+}
+
+#[test]
+fn trailing_empty() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Wrapper>(&["executable", "cargo"]),
+        Wrapper {
+            verbose: false,
+            program: "cargo".into(),
+            args: vec![],
+        }
+    );
+}
+
+#[test]
+fn trailing_recognized_options_before() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Wrapper>(&[
+            "executable",
+            "cargo",
+            "--verbose",
+            "build",
+            "--verbose"
+        ]),
+        Wrapper {
+            verbose: true,
+            program: "cargo".into(),
+            args: vec!["build".into(), "--verbose".into()],
+        }
+    );
+}
+
+#[test]
+fn trailing_unrecognized_option() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Wrapper>(&["executable", "cargo", "--frozen", "build", "-h"]),
+        Wrapper {
+            verbose: false,
+            program: "cargo".into(),
+            args: vec!["--frozen".into(), "build".into(), "-h".into()],
+        }
+    );
+}
+
+#[test]
+fn trailing_end_of_options() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Wrapper>(&["executable", "cargo", "--", "--verbose", "--", "x"]),
+        Wrapper {
+            verbose: false,
+            program: "cargo".into(),
+            args: vec!["--verbose".into(), "--".into(), "x".into()],
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Wrapper>(&["executable", "--", "cargo", "--verbose"]),
+        Wrapper {
+            verbose: false,
+            program: "cargo".into(),
+            args: vec!["--verbose".into()],
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Wrapper>(&["executable", "cargo", "build", "--", "--verbose"]),
+        Wrapper {
+            verbose: false,
+            program: "cargo".into(),
+            args: vec!["build".into(), "--".into(), "--verbose".into()],
+        }
+    );
+}
+
+#[test]
+fn trailing_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Wrapper>(&[
+                "executable",
+                "cargo",
+                "--help"
+            ]))
+        ),
+        "Run a program.\n\nUSAGE: executable [options] <program> <args>...\n\nRequired Arguments:\n  <program>  The program to run.\n  <args>     Arguments passed to the program.\n\nGlobal Options:\n  --verbose   Be more verbose.\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[serde_args::generate]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct TrailingNotLast {
+    #[serde_args(trailing)]
+    args: Vec<OsString>,
+    program: String,
+}
+
+#[test]
+fn trailing_not_last() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<TrailingNotLast>(&["executable"]))
+        ),
+        "trailing field `args` must be the last positional argument"
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Rect {
+    width: u32,
+    height: u32,
+}
+
+/// Draw a shape.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Draw {
+    /// The name of the shape.
+    name: String,
+    /// The size of the shape.
+    rect: Option<Rect>,
+    /// Be more verbose.
+    verbose: bool,
+}
+
+#[test]
+fn struct_option() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Draw>(&["executable", "foo", "--rect", "10", "20", "--verbose"]),
+        Draw {
+            name: "foo".into(),
+            rect: Some(Rect {
+                width: 10,
+                height: 20,
+            }),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn struct_option_end_of_options() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Draw>(&["executable", "--rect", "--", "10", "20", "foo"]),
+        Draw {
+            name: "foo".into(),
+            rect: Some(Rect {
+                width: 10,
+                height: 20,
+            }),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn struct_option_missing_value() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Draw>(&[
+                "executable",
+                "foo",
+                "--rect",
+                "10",
+                "--verbose"
+            ]))
+        ),
+        "ERROR: missing argument <height> for option '--rect'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn struct_option_missing_values() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Draw>(&[
+                "executable",
+                "foo",
+                "--rect"
+            ]))
+        ),
+        "ERROR: missing arguments <width> <height> for option '--rect'\n\nUSAGE: executable [options] <name>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn struct_option_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Draw>(&["executable", "--help"]))
+        ),
+        "Draw a shape.\n\nUSAGE: executable [options] <name>\n\nRequired Arguments:\n  <name>  The name of the shape.\n\nGlobal Options:\n  --rect <width> <height>  The size of the shape.\n  --verbose                Be more verbose.\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Source {
+    repository: String,
+    branch: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Copy {
+    source: Source,
+    destination: String,
+}
+
+#[test]
+fn nested_struct_option_interleaved() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Copy>(&["executable", "foo", "--branch", "main", "bar"]),
+        Copy {
+            source: Source {
+                repository: "foo".into(),
+                branch: Some("main".into()),
+            },
+            destination: "bar".into(),
+        }
+    );
+}
+
+#[test]
+fn nested_struct_option_late() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Copy>(&["executable", "foo", "bar", "--branch", "main"]),
+        Copy {
+            source: Source {
+                repository: "foo".into(),
+                branch: Some("main".into()),
+            },
+            destination: "bar".into(),
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Labels {
+    names: HashSet<String>,
+    ids: Option<BTreeSet<u32>>,
+}
+
+#[test]
+fn set_duplicates() {
+    let labels = assert_ok!(serde_args::from_slice::<Labels>(&[
+        "executable",
+        "foo",
+        "bar",
+        "foo",
+        "--ids",
+        "3",
+        "--ids",
+        "1",
+        "--ids",
+        "3",
+        "--ids",
+        "2",
+    ]));
+
+    assert_eq!(labels.names.len(), 2);
+    assert_eq!(
+        labels,
+        Labels {
+            names: HashSet::from(["foo".into(), "bar".into()]),
+            ids: Some(BTreeSet::from([1, 2, 3])),
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Counts {
+    count: NonZeroU8,
+    jobs: Option<NonZeroUsize>,
+}
+
+#[test]
+fn nonzero() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Counts>(&["executable", "1", "--jobs", "8"]),
+        Counts {
+            count: assert_some!(NonZeroU8::new(1)),
+            jobs: NonZeroUsize::new(8),
+        }
+    );
+}
+
+#[test]
+fn nonzero_u8_zero() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Counts>(&["executable", "0"]))
+        ),
+        "ERROR: value must be nonzero for <count>\n\nUSAGE: executable [options] <count>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn nonzero_usize_zero() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Counts>(&[
+                "executable",
+                "1",
+                "--jobs",
+                "0"
+            ]))
+        ),
+        "ERROR: value must be nonzero for --jobs\n\nUSAGE: executable [options] <count>\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Limits {
+    level: u8,
+    offset: Option<i32>,
+    size: Option<u64>,
+    ratio: Option<f32>,
+    threshold: Option<f64>,
+}
+
+/// Formats the error produced by parsing `Limits` from `args`.
+fn limits_error(args: &[&str]) -> String {
+    let mut full_args = vec!["executable", "1"];
+    full_args.extend(args);
+    format!(
+        "{}",
+        assert_err!(serde_args::from_slice::<Limits>(&full_args))
+    )
+}
+
+#[test]
+fn numeric_limits() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Limits>(&[
+            "executable",
+            "255",
+            "--offset",
+            "-2147483648",
+            "--size",
+            "18446744073709551615",
+            "--ratio",
+            "3.4028235e38",
+            "--threshold",
+            "-1.7976931348623157e308",
+        ]),
+        Limits {
+            level: 255,
+            offset: Some(i32::MIN),
+            size: Some(u64::MAX),
+            ratio: Some(f32::MAX),
+            threshold: Some(f64::MIN),
+        }
+    );
+}
+
+#[test]
+fn numeric_limits_u8_too_large() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Limits>(&["executable", "256"]))
+        ),
+        "ERROR: invalid value for <level>: expected u8 between 0 and 255, found '256'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_i32_too_small() {
+    assert_eq!(
+        limits_error(&["--offset", "-2147483649"]),
+        "ERROR: invalid value for --offset: expected i32 between -2147483648 and 2147483647, found '-2147483649'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_u64_too_large() {
+    assert_eq!(
+        limits_error(&["--size", "99999999999999999999"]),
+        "ERROR: invalid value for --size: expected u64 between 0 and 18446744073709551615, found '99999999999999999999'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_f32_too_large() {
+    assert_eq!(
+        limits_error(&["--ratio", "1e39"]),
+        "ERROR: invalid value for --ratio: expected f32 between -3.4028235e38 and 3.4028235e38, found '1e39'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_f64_too_large() {
+    assert_eq!(
+        limits_error(&["--threshold", "1e999"]),
+        "ERROR: invalid value for --threshold: expected f64 between -1.7976931348623157e308 and 1.7976931348623157e308, found '1e999'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_float_special_values() {
+    let limits = assert_ok!(serde_args::from_slice::<Limits>(&[
+        "executable",
+        "0",
+        "--ratio",
+        "-INF",
+        "--threshold",
+        "NaN",
+    ]));
+
+    assert_eq!(limits.ratio, Some(f32::NEG_INFINITY));
+    assert!(assert_some!(limits.threshold).is_nan());
+}
+
+#[test]
+fn numeric_limits_float_decimal() {
+    let limits = assert_ok!(serde_args::from_slice::<Limits>(&[
+        "executable",
+        "0",
+        "--ratio",
+        "inf",
+        "--threshold",
+        "0.25",
+    ]));
+
+    assert_eq!(limits.ratio, Some(f32::INFINITY));
+    assert_eq!(limits.threshold, Some(0.25));
+}
+
+#[test]
+fn numeric_limits_float_decimal_comma() {
+    assert_eq!(
+        limits_error(&["--threshold", "3,14"]),
+        "ERROR: invalid value for --threshold: expected f64, found '3,14'; use '.' as the decimal separator\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_negative_values() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Limits>(&[
+            "executable",
+            "0",
+            "--offset",
+            "-1",
+            "--ratio",
+            "-1",
+            "--threshold",
+            "-0",
+        ]),
+        Limits {
+            level: 0,
+            offset: Some(-1),
+            size: None,
+            ratio: Some(-1.0),
+            threshold: Some(-0.0),
+        }
+    );
+}
+
+#[test]
+fn numeric_limits_u8_negative() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Limits>(&["executable", "-1"]))
+        ),
+        "ERROR: invalid value for <level>: expected u8 between 0 and 255, found '-1'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_u8_surrounding_whitespace() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Limits>(&["executable", " 1"]))
+        ),
+        "ERROR: invalid value for <level>: expected u8, found ' 1'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_u64_negative() {
+    assert_eq!(
+        limits_error(&["--size", "-1"]),
+        "ERROR: invalid value for --size: expected u64 between 0 and 18446744073709551615, found '-1'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_u8_invalid() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Limits>(&["executable", "abc"]))
+        ),
+        "ERROR: invalid value for <level>: expected u8, found 'abc'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_i32_invalid() {
+    assert_eq!(
+        limits_error(&["--offset", "-1.5"]),
+        "ERROR: invalid value for --offset: expected i32, found '-1.5'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_u64_invalid() {
+    assert_eq!(
+        limits_error(&["--size", "abc"]),
+        "ERROR: invalid value for --size: expected u64, found 'abc'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_f32_invalid() {
+    assert_eq!(
+        limits_error(&["--ratio", "abc"]),
+        "ERROR: invalid value for --ratio: expected f32, found 'abc'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_f64_invalid() {
+    assert_eq!(
+        limits_error(&["--threshold", "1.2.3"]),
+        "ERROR: invalid value for --threshold: expected f64, found '1.2.3'\n\nUSAGE: executable [options] <level>\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Wide {
+    id: u128,
+    delta: Option<i128>,
+}
+
+#[test]
+fn numeric_limits_128_bit() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Wide>(&[
+            "executable",
+            "340282366920938463463374607431768211455",
+            "--delta",
+            "-170141183460469231731687303715884105728",
+        ]),
+        Wide {
+            id: u128::MAX,
+            delta: Some(i128::MIN),
+        }
+    );
+}
+
+#[test]
+fn numeric_limits_u128_too_large() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Wide>(&[
+                "executable",
+                "340282366920938463463374607431768211456"
+            ]))
+        ),
+        "ERROR: invalid value for <id>: expected u128 between 0 and 340282366920938463463374607431768211455, found '340282366920938463463374607431768211456'\n\nUSAGE: executable [options] <id>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn numeric_limits_i128_too_small() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Wide>(&[
+                "executable",
+                "0",
+                "--delta",
+                "-170141183460469231731687303715884105729"
+            ]))
+        ),
+        "ERROR: invalid value for --delta: expected i128 between -170141183460469231731687303715884105728 and 170141183460469231731687303715884105727, found '-170141183460469231731687303715884105729'\n\nUSAGE: executable [options] <id>\n\nFor more information, use --help."
+    );
+}
+
+/// Arguments deserialized by hand, accepting only the names of fields and not their aliases.
+#[derive(Debug, Eq, PartialEq)]
+struct Strict {
+    output: Option<String>,
+}
+
+impl<'de> de::Deserialize<'de> for Strict {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        /// A field identifier that remembers the name it was given.
+        enum Key {
+            Output(String),
+        }
+
+        impl<'de> de::Deserialize<'de> for Key {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: de::Deserializer<'de>,
+            {
+                struct KeyVisitor;
+
+                impl<'de> de::Visitor<'de> for KeyVisitor {
+                    type Value = Key;
+
+                    fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                        formatter.write_str("field identifier")
+                    }
+
+                    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+                    where
+                        E: de::Error,
+                    {
+                        match value {
+                            "output" | "o" => Ok(Key::Output(value.to_owned())),
+                            _ => Err(E::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_identifier(KeyVisitor)
+            }
+        }
+
+        struct StrictVisitor;
+
+        impl<'de> de::Visitor<'de> for StrictVisitor {
+            type Value = Strict;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                formatter.write_str("struct Strict")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: de::MapAccess<'de>,
+            {
+                let mut output = None;
+                while let Some(Key::Output(name)) = map.next_key()? {
+                    output = map.next_value()?;
+                    if name != "output" {
+                        return Err(de::Error::unknown_field(&name, &["output"]));
+                    }
+                }
+                Ok(Strict { output })
+            }
+        }
+
+        const FIELDS: &[&str] = &["output", "o"];
+        deserializer.deserialize_struct("Strict", FIELDS, StrictVisitor)
+    }
+}
+
+#[test]
+fn alias_deserialized_as_field_name() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Strict>(&["executable", "-o", "foo"]),
+        Strict {
+            output: Some("foo".into()),
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Canvas {
+    #[serde(alias = "r")]
+    rect: Option<Rect>,
+}
+
+#[test]
+fn alias_error_uses_given_spelling() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Canvas>(&["executable", "--rect", "10"]))
+        ),
+        "ERROR: missing argument <height> for option '--rect'\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Canvas>(&["executable", "-r", "10"]))
+        ),
+        "ERROR: missing argument <height> for option '-r'\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn os_slice() {
+    let args: Vec<OsString> = vec!["executable".into(), "cargo".into(), "build".into()];
+
+    assert_ok_eq!(
+        serde_args::from_os_slice::<Wrapper>(&args),
+        Wrapper {
+            verbose: false,
+            program: "cargo".into(),
+            args: vec!["build".into()],
+        }
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn os_slice_not_utf8() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let args = vec![
+        "executable".into(),
+        "cat".into(),
+        OsString::from_vec(vec![b'f', 0xff]),
+    ];
+
+    assert_ok_eq!(
+        serde_args::from_os_slice::<Wrapper>(&args),
+        Wrapper {
+            verbose: false,
+            program: "cat".into(),
+            args: vec![OsString::from_vec(vec![b'f', 0xff])],
+        }
+    );
+}
+
+#[derive(Debug, Eq, PartialEq)]
+enum Id {
+    Number(u64),
+    Name(String),
+}
+
+impl<'de> de::Deserialize<'de> for Id {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        // Recover from the deserializer's error rather than returning it.
+        match u64::deserialize(deserializer) {
+            Ok(number) => Ok(Id::Number(number)),
+            Err(error) => Ok(Id::Name(error.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[allow(dead_code)]
+struct Lookup {
+    name: String,
+    id: Id,
+}
+
+#[test]
+fn type_recovering_from_error() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Lookup>(&["executable", "foo", "5"]))
+        ),
+        "`id` cannot be traced, since its `Deserialize` implementation recovered from an error returned by the deserializer instead of returning it"
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Format {
+    Json,
+    Yaml,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Search {
+    pattern: String,
+    name: Option<String>,
+    count: Option<i64>,
+    format: Option<Format>,
+}
+
+#[test]
+fn empty_value_positional_string() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Search>(&["executable", ""]),
+        Search {
+            pattern: "".into(),
+            name: None,
+            count: None,
+            format: None,
+        }
+    );
+}
+
+#[test]
+fn empty_value_option_string() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Search>(&["executable", "foo", "--name", ""]),
+        Search {
+            pattern: "foo".into(),
+            name: Some("".into()),
+            count: None,
+            format: None,
+        }
+    );
+}
+
+#[test]
+fn empty_value_option_integer() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Search>(&[
+                "executable",
+                "foo",
+                "--count",
+                ""
+            ]))
+        ),
+        "ERROR: invalid value for --count: expected i64, found an empty value\n\nUSAGE: executable [options] <pattern>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn empty_value_positional_integer() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<i64>(&["executable", ""]))
+        ),
+        "ERROR: invalid value: expected i64, found an empty value\n\nUSAGE: executable <i64>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn empty_value_option_enum() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Search>(&[
+                "executable",
+                "foo",
+                "--format",
+                ""
+            ]))
+        ),
+        "ERROR: unrecognized command: ''\n\nUSAGE: executable [options] <pattern>\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Joined {
+    opt: Option<String>,
+    present: Option<()>,
+}
+
+#[test]
+fn joined_value_empty() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Joined>(&["executable", "--opt="]),
+        Joined {
+            opt: Some("".into()),
+            present: None,
+        }
+    );
+}
+
+#[test]
+fn joined_value() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Joined>(&["executable", "--opt=x"]),
+        Joined {
+            opt: Some("x".into()),
+            present: None,
+        }
+    );
+}
+
+#[test]
+fn joined_value_hyphen() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Joined>(&["executable", "--opt=--present"]),
+        Joined {
+            opt: Some("--present".into()),
+            present: None,
+        }
+    );
+}
+
+#[test]
+fn joined_value_containing_equals() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Joined>(&["executable", "--opt=a=b"]),
+        Joined {
+            opt: Some("a=b".into()),
+            present: None,
+        }
+    );
+}
+
+#[test]
+fn joined_value_not_taken() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Joined>(&[
+                "executable",
+                "--present="
+            ]))
+        ),
+        "ERROR: unrecognized optional flag: '--present='\n\n  tip: a similar option exists: --present\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn trailing_option_missing_value() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Joined>(&["executable", "--opt"]))
+        ),
+        "ERROR: missing argument <a string> for option '--opt'\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn trailing_option_empty_value() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Joined>(&["executable", "--present"]),
+        Joined {
+            opt: None,
+            present: Some(()),
+        }
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Stdin {
+    input: PathBuf,
+    output: Option<PathBuf>,
+    verbose: bool,
+}
+
+#[test]
+fn dash_positional_path() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Stdin>(&["executable", "-"]),
+        Stdin {
+            input: PathBuf::from("-"),
+            output: None,
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn dash_option_value() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Stdin>(&["executable", "--output", "-", "input.txt"]),
+        Stdin {
+            input: PathBuf::from("input.txt"),
+            output: Some(PathBuf::from("-")),
+            verbose: false,
+        }
+    );
+}
+
+#[test]
+fn dash_option_value_and_positional() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Stdin>(&["executable", "-", "--output", "-", "--verbose"]),
+        Stdin {
+            input: PathBuf::from("-"),
+            output: Some(PathBuf::from("-")),
+            verbose: true,
+        }
+    );
+}
+
+#[test]
+fn dash_sequence_element() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Vec<String>>(&["executable", "a", "-", "b"]),
+        vec!["a".to_owned(), "-".to_owned(), "b".to_owned()]
+    );
+}
+
+#[test]
+fn dash_positional_optional() {
+    // A lone `-` still begins the isolated context of a positional optional.
+    assert_ok_eq!(
+        serde_args::from_slice::<Option<()>>(&["executable", "-"]),
+        Some(())
+    );
+}
+
+#[test]
+fn dash_without_value() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Joined>(&["executable", "-"]))
+        ),
+        "ERROR: unrecognized optional flag: '-'\n\n  tip: a similar option exists: -h\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn escape_sequence_in_value() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Command>(&[
+                "executable",
+                "\x1b[2J\x1b[Hfoo"
+            ]))
+        ),
+        "ERROR: unrecognized command: '\\x1b[2J\\x1b[Hfoo'\n\nUSAGE: executable <Command>\n\nFor more information, use --help."
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn invalid_utf8_in_value() {
+    use std::os::unix::ffi::OsStringExt;
+
+    let args = vec![
+        "executable".into(),
+        "foo".into(),
+        OsString::from_vec(vec![b'b', 0xff, b'r']),
+    ];
+
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_os_slice::<String>(&args))
+        ),
+        "ERROR: unexpected argument 'b\\xffr': 'executable' takes exactly 1 argument (<a string>)\n\nUSAGE: executable <a string>\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+enum Level {
+    #[serde(rename = "0")]
+    Low,
+    #[serde(rename = "1")]
+    Medium,
+    #[serde(rename = "2")]
+    High,
+}
+
+#[test]
+fn numeric_variant_name() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Level>(&["executable", "2"]),
+        Level::High
+    );
+}
+
+#[test]
+fn numeric_variant_name_unrecognized() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Level>(&["executable", "3"]))
+        ),
+        "ERROR: unrecognized command: '3'\n\n  tip: a similar command exists: 0\n\nUSAGE: executable <Level>\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize)]
+enum Never {}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct NeverField {
+    never: Never,
+}
+
+#[test]
+fn empty_enum() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Never>(&["executable", "foo"]))
+        ),
+        "ERROR: no commands are available for <Never>, so it can never be given\n\nUSAGE: executable <Never>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn empty_enum_field() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<NeverField>(&["executable", "foo"]))
+        ),
+        "ERROR: no commands are available for <Never>, so it can never be given\n\nUSAGE: executable <never>\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct UnitStruct;
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct UnitField {
+    unit: UnitStruct,
+    name: String,
+}
+
+#[test]
+fn unit_struct_field() {
+    assert_ok_eq!(
+        serde_args::from_slice::<UnitField>(&["executable", "foo"]),
+        UnitField {
+            unit: UnitStruct,
+            name: "foo".into(),
+        }
+    );
+}
+
+#[test]
+fn unit_struct_field_usage() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<UnitField>(&[
+                "executable",
+                "foo",
+                "bar"
+            ]))
+        ),
+        "ERROR: unexpected argument 'bar': 'executable' takes exactly 1 argument (<name>)\n\nUSAGE: executable <name>\n\nFor more information, use --help."
+    );
+}
@@ -0,0 +1,105 @@
+//! A `git`-like interface, shared by the benchmarks and the allocation budgets.
+//!
+//! This is intended to be representative of a real program: global options, a top-level command
+//! with aliases, and commands taking required arguments, options, and flags.
+
+#![allow(dead_code)]
+
+use serde_derive::Deserialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Deserialize)]
+pub struct Git {
+    #[serde(rename = "C")]
+    pub directory: Option<PathBuf>,
+    pub verbose: bool,
+    #[serde(alias = "q")]
+    pub quiet: bool,
+    pub command: Command,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Command {
+    Clone {
+        repository: String,
+        directory: Option<PathBuf>,
+        depth: Option<u32>,
+        branch: Option<String>,
+        bare: bool,
+    },
+    Init {
+        bare: bool,
+    },
+    Add {
+        paths: Vec<PathBuf>,
+    },
+    #[serde(alias = "ci")]
+    Commit {
+        #[serde(alias = "m")]
+        message: Option<String>,
+        #[serde(alias = "a")]
+        all: bool,
+        amend: bool,
+    },
+    Push {
+        remote: String,
+        refspec: String,
+        force: bool,
+        tags: bool,
+    },
+    Pull {
+        remote: Option<String>,
+        rebase: bool,
+    },
+    Log {
+        #[serde(alias = "n")]
+        max_count: Option<usize>,
+        oneline: bool,
+        graph: bool,
+    },
+    #[serde(alias = "st")]
+    Status {
+        #[serde(alias = "s")]
+        short: bool,
+    },
+    #[serde(alias = "co")]
+    Checkout {
+        branch: String,
+        #[serde(alias = "b")]
+        create: bool,
+    },
+    Remote(Remote),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Remote {
+    Add { name: String, url: String },
+    Remove { name: String },
+    Rename { old: String, new: String },
+}
+
+/// A short invocation, selecting a command with a single flag.
+pub const SHORT: &[&str] = &["git", "status", "-s"];
+
+/// A long invocation, using global options and every kind of argument of a command.
+pub const LONG: &[&str] = &[
+    "git",
+    "-C",
+    "repositories",
+    "--verbose",
+    "clone",
+    "--depth",
+    "1",
+    "--branch",
+    "main",
+    "--bare",
+    "https://github.com/Anders429/serde_args",
+];
+
+/// An invocation containing a misspelled command.
+pub const INVALID: &[&str] = &["git", "comit", "-m", "message"];
+
+/// An invocation requesting help for the top-level command.
+pub const HELP: &[&str] = &["git", "--help"];
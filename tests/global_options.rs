@@ -0,0 +1,185 @@
+//! Tests for options declared once on a struct containing a command, applying to every command.
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Args {
+    verbose: bool,
+    config: Option<String>,
+    command: Command,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum Command {
+    Push {
+        remote: String,
+        refspec: String,
+        force: bool,
+    },
+    Remote(Remote),
+    Status {
+        verbose: bool,
+        path: String,
+    },
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum Remote {
+    Add { name: String, url: String },
+}
+
+fn push(verbose: bool) -> Args {
+    Args {
+        verbose,
+        config: None,
+        command: Command::Push {
+            remote: "origin".into(),
+            refspec: "main".into(),
+            force: false,
+        },
+    }
+}
+
+#[test]
+fn global_option_before_command() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "--verbose", "push", "origin", "main"]),
+        push(true)
+    );
+}
+
+#[test]
+fn global_option_before_command_arguments() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "push", "--verbose", "origin", "main"]),
+        push(true)
+    );
+}
+
+#[test]
+fn global_option_between_command_arguments() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "push", "origin", "--verbose", "main"]),
+        push(true)
+    );
+}
+
+#[test]
+fn global_option_after_command_arguments() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "push", "origin", "main", "--verbose"]),
+        push(true)
+    );
+}
+
+#[test]
+fn global_option_absent() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "push", "origin", "main"]),
+        push(false)
+    );
+}
+
+#[test]
+fn global_option_with_value_mixed_with_command_options() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&[
+            "executable",
+            "push",
+            "origin",
+            "--config",
+            "git.toml",
+            "main",
+            "--force"
+        ]),
+        Args {
+            verbose: false,
+            config: Some("git.toml".into()),
+            command: Command::Push {
+                remote: "origin".into(),
+                refspec: "main".into(),
+                force: true,
+            },
+        }
+    );
+}
+
+#[test]
+fn global_option_nested_command() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&[
+            "executable",
+            "remote",
+            "add",
+            "origin",
+            "--verbose",
+            "https://example.com"
+        ]),
+        Args {
+            verbose: true,
+            config: None,
+            command: Command::Remote(Remote::Add {
+                name: "origin".into(),
+                url: "https://example.com".into(),
+            }),
+        }
+    );
+}
+
+#[test]
+fn global_option_shadowed_by_command_option() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "status", "--verbose", "src"]),
+        Args {
+            verbose: false,
+            config: None,
+            command: Command::Status {
+                verbose: true,
+                path: "src".into(),
+            },
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "status", "src", "--verbose"]),
+        Args {
+            verbose: false,
+            config: None,
+            command: Command::Status {
+                verbose: true,
+                path: "src".into(),
+            },
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Args>(&["executable", "--verbose", "status", "src"]),
+        Args {
+            verbose: true,
+            config: None,
+            command: Command::Status {
+                verbose: false,
+                path: "src".into(),
+            },
+        }
+    );
+}
+
+#[test]
+fn global_option_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Args>(&[
+                "executable",
+                "push",
+                "--help"
+            ]))
+        ),
+        "struct Args\n\nUSAGE: executable [options] push [push options] <remote> <refspec>\n\nRequired Arguments:\n  <remote>   \n  <refspec>  \n\nGlobal Options:\n  --config <a string>  \n  --verbose            \n\npush Options:\n  --force   \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
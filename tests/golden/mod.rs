@@ -0,0 +1,51 @@
+//! Comparison of rendered output against committed golden files.
+//!
+//! Golden files live in the directory named after the test file using them, such as `tests/help`
+//! for `tests/help.rs`, and are named after the case they record. Running the tests with the
+//! `UPDATE_GOLDEN` environment variable set rewrites every golden file with the current output
+//! instead of comparing against it, after which the changes can be reviewed with `git diff`.
+//!
+//! Help messages are not wrapped to the width of the terminal, so the output does not depend on
+//! the environment the tests are run in.
+
+use std::{
+    env,
+    fs,
+    path::PathBuf,
+};
+
+/// The environment variable requesting that golden files be rewritten.
+const UPDATE_VAR: &str = "UPDATE_GOLDEN";
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join(env!("CARGO_CRATE_NAME"))
+        .join(format!("{name}.txt"))
+}
+
+/// Asserts that `actual` matches the golden file `name`, or rewrites the golden file if requested.
+///
+/// Golden files end with a newline that is not part of the output, and are compared regardless of
+/// line endings, so that they are unaffected by checking them out on Windows.
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = path(name);
+    if env::var_os(UPDATE_VAR).is_some() {
+        fs::write(&path, format!("{actual}\n"))
+            .unwrap_or_else(|error| panic!("could not write {}: {error}", path.display()));
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|error| {
+        panic!(
+            "could not read {}: {error}; run with {UPDATE_VAR}=1 to create it",
+            path.display()
+        )
+    });
+    let expected = expected.replace("\r\n", "\n");
+    let expected = expected.strip_suffix('\n').unwrap_or(&expected);
+    assert!(
+        expected == actual,
+        "output does not match {}; run with {UPDATE_VAR}=1 to update it\n\nexpected:\n{expected}\n\nactual:\n{actual}",
+        path.display(),
+    );
+}
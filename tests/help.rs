@@ -0,0 +1,311 @@
+//! Golden-file tests for help messages.
+//!
+//! Each test renders the help message of a representative type and compares it against
+//! `tests/help/<test name>.txt`. See the `golden` module for how to update the golden files.
+
+#![cfg(feature = "macros")]
+
+mod golden;
+
+use claims::assert_err;
+use golden::assert_golden;
+use serde::de::DeserializeOwned;
+use serde_args::{
+    Parser,
+    SortOrder,
+};
+use serde_derive::Deserialize;
+use std::fmt::Debug;
+
+/// Renders the help message of `D`, as requested by `args`.
+fn help<D>(args: &[&str]) -> String
+where
+    D: DeserializeOwned + Debug,
+{
+    format!(
+        "{}",
+        assert_err!(Parser::from_args_without_exe(args)
+            .name("prog")
+            .parse_borrowed::<D>())
+    )
+}
+
+/// Renders the help message of `D`, as requested by `args`, summarizing it when requested using
+/// `-h`.
+fn short_help<D>(args: &[&str]) -> String
+where
+    D: DeserializeOwned + Debug,
+{
+    format!(
+        "{}",
+        assert_err!(Parser::from_args_without_exe(args)
+            .name("prog")
+            .with_short_help()
+            .parse_borrowed::<D>())
+    )
+}
+
+/// Copy files between locations.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Copy {
+    /// The file to copy.
+    #[serde_args(value_name = "SOURCE")]
+    source: String,
+    /// Overwrite the destination if it exists.
+    #[serde(alias = "f", alias = "overwrite")]
+    force: bool,
+    /// The number of times to retry.
+    #[serde(alias = "r")]
+    retries: Option<u32>,
+    /// Labels to attach to the copy.
+    #[serde_args(value_delimiter = ',')]
+    labels: Option<Vec<String>>,
+    /// Where to record the result.
+    #[serde_args(required)]
+    log: Option<String>,
+    /// Where to copy the file to.
+    ///
+    /// Defaults to the current directory.
+    #[serde_args(positional)]
+    destination: Option<String>,
+}
+
+#[test]
+fn struct_options() {
+    assert_golden("struct_options", &help::<Copy>(&["--help"]));
+}
+
+#[test]
+fn struct_options_short() {
+    assert_golden("struct_options_short", &short_help::<Copy>(&["-h"]));
+}
+
+#[test]
+fn struct_options_short_not_requested() {
+    assert_golden("struct_options", &help::<Copy>(&["-h"]));
+}
+
+#[test]
+fn struct_options_long_with_short_help() {
+    assert_golden("struct_options", &short_help::<Copy>(&["--help"]));
+}
+
+#[test]
+fn struct_options_declaration() {
+    assert_golden(
+        "struct_options_declaration",
+        &format!(
+            "{}",
+            assert_err!(Parser::from_args_without_exe(["--help"])
+                .name("prog")
+                .sort_options(SortOrder::Declaration)
+                .parse_borrowed::<Copy>())
+        ),
+    );
+}
+
+#[test]
+fn struct_options_color() {
+    assert_golden(
+        "struct_options_color",
+        &format!(
+            "{:#}",
+            assert_err!(Parser::from_args_without_exe(["--help"])
+                .name("prog")
+                .parse_borrowed::<Copy>())
+        ),
+    );
+}
+
+/// Connect to a server.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Connect {
+    /// The server to connect to.
+    server: String,
+    /// The identity file used to authenticate with the server, read before any configuration.
+    a_very_long_option_name_for_identity: Option<String>,
+    /// The port to connect to.
+    #[serde(alias = "p")]
+    port: Option<u16>,
+}
+
+#[test]
+fn struct_long_option() {
+    assert_golden("struct_long_option", &help::<Connect>(&["--help"]));
+}
+
+/// The format to write the report in.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(dead_code)]
+enum Format {
+    /// Write the report as JSON.
+    Json,
+    /// Write the report as YAML.
+    #[serde(alias = "y")]
+    Yaml,
+    /// Write the report as TOML.
+    Toml,
+}
+
+/// Report on the state of a file.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Report {
+    /// The file to report on.
+    file: String,
+    /// The format to write the report in.
+    #[serde_args(flags)]
+    format: Option<Format>,
+    /// Be more verbose.
+    verbose: bool,
+}
+
+#[test]
+fn struct_flags() {
+    assert_golden("struct_flags", &help::<Report>(&["--help"]));
+}
+
+/// The repository to mirror.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Source {
+    /// The location of the repository.
+    repository: String,
+    /// The branch to mirror.
+    branch: Option<String>,
+}
+
+/// Mirror a repository.
+#[serde_args::generate(doc_help, version)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Mirror {
+    /// Where to mirror from.
+    source: Source,
+    /// Where to mirror to.
+    destination: String,
+    /// Be more verbose.
+    verbose: bool,
+}
+
+#[test]
+fn struct_nested_version() {
+    assert_golden("struct_nested_version", &help::<Mirror>(&["--help"]));
+}
+
+#[test]
+fn struct_nested_version_short() {
+    assert_golden(
+        "struct_nested_version_short",
+        &short_help::<Mirror>(&["-h"]),
+    );
+}
+
+/// Manage remote repositories.
+///
+/// # Examples
+///
+/// ```text
+/// $ prog remote add origin https://example.com
+/// $ prog push --force origin main
+/// ```
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(dead_code)]
+enum Git {
+    /// [Repository]
+    /// Create an empty repository.
+    Init,
+    /// [Repository]
+    /// Clone a repository into a new directory.
+    #[serde(alias = "cl")]
+    Clone(String),
+    /// [Changes]
+    /// Update remote refs along with associated objects.
+    Push(Push),
+    /// Manage the set of tracked repositories.
+    Remote(Remote),
+}
+
+/// Update remote refs along with associated objects.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Push {
+    /// The remote to push to.
+    remote: String,
+    /// The branch to push.
+    refspec: String,
+    /// Overwrite the remote branch.
+    #[serde(alias = "f")]
+    force: bool,
+}
+
+/// Manage the set of tracked repositories.
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[allow(dead_code)]
+enum Remote {
+    /// Add a remote named `name` at `url`.
+    Add { name: String, url: String },
+    /// Remove a remote.
+    #[serde(alias = "rm")]
+    Remove(String),
+}
+
+/// A standalone enum whose description is given through `serde` rather than `#[generate]`.
+#[derive(Debug, Deserialize)]
+#[serde(expecting = "Control the service.", rename_all = "kebab-case")]
+#[allow(dead_code)]
+enum Service {
+    Start,
+    Stop,
+}
+
+#[test]
+fn enum_commands() {
+    assert_golden("enum_commands", &help::<Git>(&["--help"]));
+}
+
+#[test]
+fn enum_commands_short() {
+    assert_golden("enum_commands_short", &short_help::<Git>(&["-h"]));
+}
+
+#[test]
+fn enum_expecting() {
+    assert_golden("enum_expecting", &help::<Service>(&["--help"]));
+}
+
+#[test]
+fn enum_variant() {
+    assert_golden("enum_variant", &help::<Git>(&["push", "--help"]));
+}
+
+#[test]
+fn enum_variant_short() {
+    assert_golden("enum_variant_short", &short_help::<Git>(&["push", "-h"]));
+}
+
+#[test]
+fn enum_nested_variant() {
+    assert_golden("enum_nested_variant", &help::<Git>(&["remote", "--help"]));
+}
+
+#[test]
+fn enum_nested_variant_alias() {
+    assert_golden(
+        "enum_nested_variant_alias",
+        &help::<Git>(&["remote", "rm", "--help"]),
+    );
+}
@@ -0,0 +1,164 @@
+//! Tests for help messages of types with many documented fields.
+#![cfg(feature = "macros")]
+
+use claims::assert_err;
+use serde::de::{
+    Deserialize,
+    Deserializer,
+    Visitor,
+};
+use serde_args::Parser;
+use serde_derive::Deserialize;
+use std::{
+    fmt,
+    fmt::{
+        Display,
+        Formatter,
+    },
+};
+
+/// The name of a level, padded to whatever width it is formatted with.
+struct LevelName;
+
+impl Display for LevelName {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter.pad("level")
+    }
+}
+
+#[derive(Debug)]
+#[allow(dead_code)]
+struct Level(u8);
+
+impl<'de> Deserialize<'de> for Level {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct LevelVisitor;
+
+        impl Visitor<'_> for LevelVisitor {
+            type Value = Level;
+
+            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+                // The formatter is forwarded as is, including any width it was given.
+                Display::fmt(&LevelName, formatter)
+            }
+
+            fn visit_u8<E>(self, value: u8) -> Result<Self::Value, E> {
+                Ok(Level(value))
+            }
+        }
+
+        deserializer.deserialize_u8(LevelVisitor)
+    }
+}
+
+macro_rules! many {
+    ($($field:ident $doc:tt)*) => {
+        /// Configure many settings.
+        #[serde_args::generate(doc_help)]
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Many {
+            $(
+                #[doc = $doc]
+                $field: Option<u8>,
+            )*
+            /// The level to log at.
+            level: Option<Level>,
+        }
+    };
+}
+
+many! {
+    f0 "Set f0." f1 "Set f1." f2 "Set f2." f3 "Set f3." f4 "Set f4."
+    f5 "Set f5." f6 "Set f6." f7 "Set f7." f8 "Set f8." f9 "Set f9."
+    f10 "Set f10." f11 "Set f11." f12 "Set f12." f13 "Set f13." f14 "Set f14."
+    f15 "Set f15." f16 "Set f16." f17 "Set f17." f18 "Set f18." f19 "Set f19."
+    f20 "Set f20." f21 "Set f21." f22 "Set f22." f23 "Set f23." f24 "Set f24."
+    f25 "Set f25." f26 "Set f26." f27 "Set f27." f28 "Set f28." f29 "Set f29."
+    f30 "Set f30." f31 "Set f31." f32 "Set f32." f33 "Set f33." f34 "Set f34."
+    f35 "Set f35." f36 "Set f36." f37 "Set f37." f38 "Set f38." f39 "Set f39."
+    f40 "Set f40." f41 "Set f41." f42 "Set f42." f43 "Set f43." f44 "Set f44."
+    f45 "Set f45." f46 "Set f46." f47 "Set f47." f48 "Set f48." f49 "Set f49."
+    f50 "Set f50." f51 "Set f51." f52 "Set f52." f53 "Set f53." f54 "Set f54."
+    f55 "Set f55." f56 "Set f56." f57 "Set f57." f58 "Set f58." f59 "Set f59."
+    f60 "Set f60." f61 "Set f61." f62 "Set f62." f63 "Set f63." f64 "Set f64."
+    f65 "Set f65." f66 "Set f66." f67 "Set f67." f68 "Set f68." f69 "Set f69."
+    f70 "Set f70." f71 "Set f71." f72 "Set f72." f73 "Set f73." f74 "Set f74."
+    f75 "Set f75." f76 "Set f76." f77 "Set f77." f78 "Set f78." f79 "Set f79."
+    f80 "Set f80." f81 "Set f81." f82 "Set f82." f83 "Set f83." f84 "Set f84."
+    f85 "Set f85." f86 "Set f86." f87 "Set f87." f88 "Set f88." f89 "Set f89."
+    f90 "Set f90." f91 "Set f91." f92 "Set f92." f93 "Set f93." f94 "Set f94."
+    f95 "Set f95." f96 "Set f96." f97 "Set f97." f98 "Set f98." f99 "Set f99."
+    f100 "Set f100." f101 "Set f101." f102 "Set f102." f103 "Set f103." f104 "Set f104."
+    f105 "Set f105." f106 "Set f106." f107 "Set f107." f108 "Set f108." f109 "Set f109."
+    f110 "Set f110." f111 "Set f111." f112 "Set f112." f113 "Set f113." f114 "Set f114."
+    f115 "Set f115." f116 "Set f116." f117 "Set f117." f118 "Set f118." f119 "Set f119."
+    f120 "Set f120." f121 "Set f121." f122 "Set f122." f123 "Set f123." f124 "Set f124."
+    f125 "Set f125." f126 "Set f126." f127 "Set f127." f128 "Set f128." f129 "Set f129."
+    f130 "Set f130." f131 "Set f131." f132 "Set f132." f133 "Set f133." f134 "Set f134."
+    f135 "Set f135." f136 "Set f136." f137 "Set f137." f138 "Set f138." f139 "Set f139."
+    f140 "Set f140." f141 "Set f141." f142 "Set f142." f143 "Set f143." f144 "Set f144."
+    f145 "Set f145." f146 "Set f146." f147 "Set f147." f148 "Set f148." f149 "Set f149."
+    f150 "Set f150." f151 "Set f151." f152 "Set f152." f153 "Set f153." f154 "Set f154."
+    f155 "Set f155." f156 "Set f156." f157 "Set f157." f158 "Set f158." f159 "Set f159."
+    f160 "Set f160." f161 "Set f161." f162 "Set f162." f163 "Set f163." f164 "Set f164."
+    f165 "Set f165." f166 "Set f166." f167 "Set f167." f168 "Set f168." f169 "Set f169."
+    f170 "Set f170." f171 "Set f171." f172 "Set f172." f173 "Set f173." f174 "Set f174."
+    f175 "Set f175." f176 "Set f176." f177 "Set f177." f178 "Set f178." f179 "Set f179."
+    f180 "Set f180." f181 "Set f181." f182 "Set f182." f183 "Set f183." f184 "Set f184."
+    f185 "Set f185." f186 "Set f186." f187 "Set f187." f188 "Set f188." f189 "Set f189."
+    f190 "Set f190." f191 "Set f191." f192 "Set f192." f193 "Set f193." f194 "Set f194."
+    f195 "Set f195." f196 "Set f196." f197 "Set f197." f198 "Set f198." f199 "Set f199."
+    f200 "Set f200." f201 "Set f201." f202 "Set f202." f203 "Set f203." f204 "Set f204."
+    f205 "Set f205." f206 "Set f206." f207 "Set f207." f208 "Set f208." f209 "Set f209."
+    f210 "Set f210." f211 "Set f211." f212 "Set f212." f213 "Set f213." f214 "Set f214."
+    f215 "Set f215." f216 "Set f216." f217 "Set f217." f218 "Set f218." f219 "Set f219."
+    f220 "Set f220." f221 "Set f221." f222 "Set f222." f223 "Set f223." f224 "Set f224."
+    f225 "Set f225." f226 "Set f226." f227 "Set f227." f228 "Set f228." f229 "Set f229."
+    f230 "Set f230." f231 "Set f231." f232 "Set f232." f233 "Set f233." f234 "Set f234."
+    f235 "Set f235." f236 "Set f236." f237 "Set f237." f238 "Set f238." f239 "Set f239."
+    f240 "Set f240." f241 "Set f241." f242 "Set f242." f243 "Set f243." f244 "Set f244."
+    f245 "Set f245." f246 "Set f246." f247 "Set f247." f248 "Set f248." f249 "Set f249."
+    f250 "Set f250." f251 "Set f251." f252 "Set f252." f253 "Set f253." f254 "Set f254."
+    f255 "Set f255." f256 "Set f256." f257 "Set f257." f258 "Set f258." f259 "Set f259."
+    f260 "Set f260." f261 "Set f261." f262 "Set f262." f263 "Set f263." f264 "Set f264."
+    f265 "Set f265." f266 "Set f266." f267 "Set f267." f268 "Set f268." f269 "Set f269."
+    f270 "Set f270." f271 "Set f271." f272 "Set f272." f273 "Set f273." f274 "Set f274."
+    f275 "Set f275." f276 "Set f276." f277 "Set f277." f278 "Set f278." f279 "Set f279."
+    f280 "Set f280." f281 "Set f281." f282 "Set f282." f283 "Set f283." f284 "Set f284."
+    f285 "Set f285." f286 "Set f286." f287 "Set f287." f288 "Set f288." f289 "Set f289."
+    f290 "Set f290." f291 "Set f291." f292 "Set f292." f293 "Set f293." f294 "Set f294."
+    f295 "Set f295." f296 "Set f296." f297 "Set f297." f298 "Set f298." f299 "Set f299."
+}
+
+#[test]
+fn many_fields_help() {
+    let help = format!(
+        "{}",
+        assert_err!(Parser::from_args_without_exe(["--help"])
+            .name("prog")
+            .parse_borrowed::<Many>())
+    );
+
+    for index in 0..300 {
+        let option = format!("--f{} <u8>", index);
+        let line = help
+            .lines()
+            .find(|line| line.trim_start().starts_with(&option))
+            .unwrap_or_else(|| panic!("missing {} in help:\n{}", option, help));
+        assert!(
+            line.ends_with(&format!("  Set f{}.", index)),
+            "wrong description for {}: {}",
+            option,
+            line
+        );
+    }
+    let line = help
+        .lines()
+        .find(|line| line.trim_start().starts_with("--level <level>"))
+        .unwrap_or_else(|| panic!("missing --level in help:\n{}", help));
+    assert!(line.ends_with("  The level to log at."), "{}", line);
+}
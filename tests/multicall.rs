@@ -0,0 +1,171 @@
+//! Tests for selecting a command using the name of the executable.
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_args::Parser;
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Tool {
+    Cat(String),
+    #[serde(alias = "dir")]
+    Ls {
+        path: Option<String>,
+        all: bool,
+    },
+    Copy {
+        source: String,
+        destination: String,
+        force: bool,
+    },
+}
+
+/// Holds `args` as though the executable were invoked as `name`.
+fn parser(name: &str, args: &[&str]) -> Parser {
+    Parser::from_args_without_exe(args)
+        .name(name)
+        .multicall(true)
+}
+
+#[test]
+fn match_name() {
+    assert_ok_eq!(
+        parser("copy", &["foo", "bar", "--force"]).parse_borrowed::<Tool>(),
+        Tool::Copy {
+            source: "foo".into(),
+            destination: "bar".into(),
+            force: true,
+        }
+    );
+}
+
+#[test]
+fn match_name_newtype() {
+    assert_ok_eq!(
+        parser("cat", &["foo"]).parse_borrowed::<Tool>(),
+        Tool::Cat("foo".into())
+    );
+}
+
+#[test]
+fn match_path() {
+    assert_ok_eq!(
+        parser("/usr/bin/cat", &["foo"]).parse_borrowed::<Tool>(),
+        Tool::Cat("foo".into())
+    );
+}
+
+#[test]
+fn match_alias() {
+    assert_ok_eq!(
+        parser("dir", &["--all"]).parse_borrowed::<Tool>(),
+        Tool::Ls {
+            path: None,
+            all: true,
+        }
+    );
+}
+
+#[test]
+fn match_case_insensitive() {
+    assert_ok_eq!(
+        Parser::from_args_without_exe(["foo"])
+            .name("CAT")
+            .multicall(true)
+            .case_insensitive_commands(true)
+            .parse_borrowed::<Tool>(),
+        Tool::Cat("foo".into())
+    );
+}
+
+#[test]
+fn match_command_name_as_argument() {
+    // Only the executable's name selects the command, so later arguments are values as usual.
+    assert_ok_eq!(
+        parser("cat", &["copy"]).parse_borrowed::<Tool>(),
+        Tool::Cat("copy".into())
+    );
+}
+
+#[test]
+fn no_match() {
+    assert_ok_eq!(
+        parser("box", &["cat", "foo"]).parse_borrowed::<Tool>(),
+        Tool::Cat("foo".into())
+    );
+}
+
+#[test]
+fn no_match_help() {
+    assert_eq!(
+        format!("{}", assert_err!(parser("box", &["--help"]).parse_borrowed::<Tool>())),
+        "enum Tool\n\nUSAGE: box <Tool>\n\nRequired Arguments:\n  <Tool>  enum Tool\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  cat <a string>                         \n  dir ls [options]                       \n  copy [options] <source> <destination>  "
+    );
+}
+
+#[test]
+fn not_enabled() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(Parser::from_args_without_exe(["foo"])
+                .name("cat")
+                .parse_borrowed::<Tool>())
+        ),
+        "ERROR: unrecognized command: 'foo'\n\n  tip: a similar command exists: cat\n\nUSAGE: cat <Tool>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn not_enum() {
+    assert_ok_eq!(
+        Parser::from_args_without_exe(["foo"])
+            .name("cat")
+            .multicall(true)
+            .parse_borrowed::<String>(),
+        "foo"
+    );
+}
+
+#[test]
+fn help() {
+    assert_eq!(
+        format!("{}", assert_err!(parser("copy", &["--help"]).parse_borrowed::<Tool>())),
+        "USAGE: copy [copy options] <source> <destination>\n\nRequired Arguments:\n  <source>       \n  <destination>  \n\ncopy Options:\n  --force   \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[test]
+fn help_no_arguments() {
+    assert_eq!(
+        format!("{}", assert_err!(parser("copy", &[]).parse_borrowed::<Tool>())),
+        "USAGE: copy [copy options] <source> <destination>\n\nRequired Arguments:\n  <source>       \n  <destination>  \n\ncopy Options:\n  --force   \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[test]
+fn help_alias() {
+    assert_eq!(
+        format!("{}", assert_err!(parser("dir", &["--help"]).parse_borrowed::<Tool>())),
+        "USAGE: dir [dir options]\n\ndir Options:\n  --path <a string>  \n  --all              \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[test]
+fn missing_arguments() {
+    assert_eq!(
+        format!("{}", assert_err!(parser("copy", &["foo"]).parse_borrowed::<Tool>())),
+        "ERROR: missing required positional argument: <destination>\n\nUSAGE: copy [copy options] <source> <destination>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn too_many_arguments() {
+    assert_eq!(
+        format!("{}", assert_err!(parser("cat", &["foo", "bar"]).parse_borrowed::<Tool>())),
+        "ERROR: unexpected argument 'bar': 'cat' takes exactly 1 argument (<a string>)\n\nUSAGE: cat <a string>\n\nFor more information, use --help."
+    );
+}
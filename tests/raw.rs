@@ -0,0 +1,134 @@
+//! Tests for variants whose arguments are all parsed as positional arguments.
+#![cfg(feature = "macros")]
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_derive::Deserialize;
+
+#[serde_args::generate]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+enum Command {
+    #[serde_args(raw)]
+    Run(Vec<String>),
+    #[serde_args(raw)]
+    Exec {
+        program: String,
+        args: Vec<String>,
+    },
+    Build {
+        release: bool,
+    },
+}
+
+#[test]
+fn raw_option() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "run", "--foo"]),
+        Command::Run(vec!["--foo".to_owned()])
+    );
+}
+
+#[test]
+fn raw_help() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "run", "--help", "-h"]),
+        Command::Run(vec!["--help".to_owned(), "-h".to_owned()])
+    );
+}
+
+#[test]
+fn raw_end_of_options() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "run", "--", "foo"]),
+        Command::Run(vec!["--".to_owned(), "foo".to_owned()])
+    );
+}
+
+#[test]
+fn raw_struct() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "exec", "--verbose", "ls", "-la"]),
+        Command::Exec {
+            program: "--verbose".to_owned(),
+            args: vec!["ls".to_owned(), "-la".to_owned()],
+        }
+    );
+}
+
+#[test]
+fn raw_struct_missing_argument() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Command>(&["executable", "exec"]))
+        ),
+        "ERROR: missing required positional argument: <program>\n\nUSAGE: executable exec <program> <args>...\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn not_raw() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "build", "--release"]),
+        Command::Build { release: true }
+    );
+}
+
+#[test]
+fn raw_single_hyphen() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "run", "-x", "cargo", "build", "-"]),
+        Command::Run(vec![
+            "-x".to_owned(),
+            "cargo".to_owned(),
+            "build".to_owned(),
+            "-".to_owned()
+        ])
+    );
+}
+
+#[test]
+fn raw_long_options() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "run", "cargo", "build", "--release"]),
+        Command::Run(vec![
+            "cargo".to_owned(),
+            "build".to_owned(),
+            "--release".to_owned()
+        ])
+    );
+}
+
+#[test]
+fn raw_empty() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Command>(&["executable", "run"]),
+        Command::Run(vec![])
+    );
+}
+
+#[test]
+fn raw_help_before_command() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Command>(&[
+                "executable",
+                "--help",
+                "run",
+                "--foo"
+            ]))
+        ),
+        "USAGE: executable run <args>...\n\nRequired Arguments:\n  <args>  a string\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[test]
+fn raw_help_usage() {
+    let error = assert_err!(serde_args::from_slice::<Command>(&["executable", "--help"]));
+
+    assert!(format!("{}", error).contains("\n  run <args>...  "));
+}
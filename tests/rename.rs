@@ -0,0 +1,616 @@
+//! Tests for each of serde's `rename_all` conventions.
+//!
+//! The names produced by serde are the names users type on the command line, so each convention
+//! is checked both while parsing and when displaying help.
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_derive::Deserialize;
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+struct Screaming {
+    input_path: String,
+    output_file: Option<String>,
+    dry_run: bool,
+}
+
+#[test]
+fn struct_screaming_snake_case() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Screaming>(&[
+            "executable",
+            "foo",
+            "--OUTPUT_FILE",
+            "bar",
+            "--DRY_RUN"
+        ]),
+        Screaming {
+            input_path: "foo".to_owned(),
+            output_file: Some("bar".to_owned()),
+            dry_run: true,
+        }
+    );
+}
+
+#[test]
+fn struct_screaming_snake_case_wrong_case() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Screaming>(&[
+                "executable",
+                "foo",
+                "--output_file",
+                "bar"
+            ]))
+        ),
+        "ERROR: 2 problems found:\n  - unrecognized optional flag: '--output_file'\n    tip: a similar option exists: --OUTPUT_FILE\n  - unexpected argument 'bar': 'executable' takes exactly 1 argument (<INPUT_PATH>)\n\nUSAGE: executable [options] <INPUT_PATH>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn struct_screaming_snake_case_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Screaming>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "struct Screaming\n\nUSAGE: executable [options] <INPUT_PATH>\n\nRequired Arguments:\n  <INPUT_PATH>  \n\nGlobal Options:\n  --OUTPUT_FILE <a string>  \n  --DRY_RUN                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "UPPERCASE")]
+struct Short {
+    v: bool,
+    q: bool,
+}
+
+#[test]
+fn struct_uppercase_short() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Short>(&["executable", "-V", "-Q"]),
+        Short { v: true, q: true }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Short>(&["executable", "-VQ"]),
+        Short { v: true, q: true }
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Short>(&["executable", "-v"]))
+        ),
+        "ERROR: unrecognized optional flag: '-v'\n\n  tip: a similar option exists: -V\n\nUSAGE: executable [options]\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase", rename_all_fields = "lowercase")]
+enum Lowercase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn lowercase() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Lowercase>(&[
+            "executable",
+            "showlog",
+            "--output_file",
+            "foo",
+            "--dry_run"
+        ]),
+        Lowercase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Lowercase>(&["executable", "remoteadd", "foo"]),
+        Lowercase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn lowercase_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Lowercase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum Lowercase\n\nUSAGE: executable <Lowercase>\n\nRequired Arguments:\n  <Lowercase>  enum Lowercase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  showlog [options]     \n  remoteadd <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Lowercase>(&[
+                "executable",
+                "showlog",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable showlog [showlog options]\n\nshowlog Options:\n  --output_file <a string>  \n  --dry_run                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "UPPERCASE", rename_all_fields = "UPPERCASE")]
+enum Uppercase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn uppercase() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Uppercase>(&[
+            "executable",
+            "SHOWLOG",
+            "--OUTPUT_FILE",
+            "foo",
+            "--DRY_RUN"
+        ]),
+        Uppercase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Uppercase>(&["executable", "REMOTEADD", "foo"]),
+        Uppercase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn uppercase_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Uppercase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum Uppercase\n\nUSAGE: executable <Uppercase>\n\nRequired Arguments:\n  <Uppercase>  enum Uppercase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  SHOWLOG [options]     \n  REMOTEADD <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Uppercase>(&[
+                "executable",
+                "SHOWLOG",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable SHOWLOG [SHOWLOG options]\n\nSHOWLOG Options:\n  --OUTPUT_FILE <a string>  \n  --DRY_RUN                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "PascalCase", rename_all_fields = "PascalCase")]
+enum PascalCase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn pascal_case() {
+    assert_ok_eq!(
+        serde_args::from_slice::<PascalCase>(&[
+            "executable",
+            "ShowLog",
+            "--OutputFile",
+            "foo",
+            "--DryRun"
+        ]),
+        PascalCase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<PascalCase>(&["executable", "RemoteAdd", "foo"]),
+        PascalCase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn pascal_case_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<PascalCase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum PascalCase\n\nUSAGE: executable <PascalCase>\n\nRequired Arguments:\n  <PascalCase>  enum PascalCase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  ShowLog [options]     \n  RemoteAdd <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<PascalCase>(&[
+                "executable",
+                "ShowLog",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable ShowLog [ShowLog options]\n\nShowLog Options:\n  --OutputFile <a string>  \n  --DryRun                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase")]
+enum CamelCase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn camel_case() {
+    assert_ok_eq!(
+        serde_args::from_slice::<CamelCase>(&[
+            "executable",
+            "showLog",
+            "--outputFile",
+            "foo",
+            "--dryRun"
+        ]),
+        CamelCase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<CamelCase>(&["executable", "remoteAdd", "foo"]),
+        CamelCase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn camel_case_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<CamelCase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum CamelCase\n\nUSAGE: executable <CamelCase>\n\nRequired Arguments:\n  <CamelCase>  enum CamelCase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  showLog [options]     \n  remoteAdd <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<CamelCase>(&[
+                "executable",
+                "showLog",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable showLog [showLog options]\n\nshowLog Options:\n  --outputFile <a string>  \n  --dryRun                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "snake_case", rename_all_fields = "snake_case")]
+enum SnakeCase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn snake_case() {
+    assert_ok_eq!(
+        serde_args::from_slice::<SnakeCase>(&[
+            "executable",
+            "show_log",
+            "--output_file",
+            "foo",
+            "--dry_run"
+        ]),
+        SnakeCase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<SnakeCase>(&["executable", "remote_add", "foo"]),
+        SnakeCase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn snake_case_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<SnakeCase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum SnakeCase\n\nUSAGE: executable <SnakeCase>\n\nRequired Arguments:\n  <SnakeCase>  enum SnakeCase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  show_log [options]     \n  remote_add <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<SnakeCase>(&[
+                "executable",
+                "show_log",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable show_log [show_log options]\n\nshow_log Options:\n  --output_file <a string>  \n  --dry_run                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(
+    rename_all = "SCREAMING_SNAKE_CASE",
+    rename_all_fields = "SCREAMING_SNAKE_CASE"
+)]
+enum ScreamingSnakeCase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn screaming_snake_case() {
+    assert_ok_eq!(
+        serde_args::from_slice::<ScreamingSnakeCase>(&[
+            "executable",
+            "SHOW_LOG",
+            "--OUTPUT_FILE",
+            "foo",
+            "--DRY_RUN"
+        ]),
+        ScreamingSnakeCase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<ScreamingSnakeCase>(&["executable", "REMOTE_ADD", "foo"]),
+        ScreamingSnakeCase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn screaming_snake_case_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<ScreamingSnakeCase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum ScreamingSnakeCase\n\nUSAGE: executable <ScreamingSnakeCase>\n\nRequired Arguments:\n  <ScreamingSnakeCase>  enum ScreamingSnakeCase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  SHOW_LOG [options]     \n  REMOTE_ADD <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<ScreamingSnakeCase>(&[
+                "executable",
+                "SHOW_LOG",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable SHOW_LOG [SHOW_LOG options]\n\nSHOW_LOG Options:\n  --OUTPUT_FILE <a string>  \n  --DRY_RUN                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case", rename_all_fields = "kebab-case")]
+enum KebabCase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn kebab_case() {
+    assert_ok_eq!(
+        serde_args::from_slice::<KebabCase>(&[
+            "executable",
+            "show-log",
+            "--output-file",
+            "foo",
+            "--dry-run"
+        ]),
+        KebabCase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<KebabCase>(&["executable", "remote-add", "foo"]),
+        KebabCase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn kebab_case_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<KebabCase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum KebabCase\n\nUSAGE: executable <KebabCase>\n\nRequired Arguments:\n  <KebabCase>  enum KebabCase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  show-log [options]     \n  remote-add <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<KebabCase>(&[
+                "executable",
+                "show-log",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable show-log [show-log options]\n\nshow-log Options:\n  --output-file <a string>  \n  --dry-run                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(
+    rename_all = "SCREAMING-KEBAB-CASE",
+    rename_all_fields = "SCREAMING-KEBAB-CASE"
+)]
+enum ScreamingKebabCase {
+    ShowLog {
+        output_file: Option<String>,
+        dry_run: bool,
+    },
+    RemoteAdd(String),
+}
+
+#[test]
+fn screaming_kebab_case() {
+    assert_ok_eq!(
+        serde_args::from_slice::<ScreamingKebabCase>(&[
+            "executable",
+            "SHOW-LOG",
+            "--OUTPUT-FILE",
+            "foo",
+            "--DRY-RUN"
+        ]),
+        ScreamingKebabCase::ShowLog {
+            output_file: Some("foo".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<ScreamingKebabCase>(&["executable", "REMOTE-ADD", "foo"]),
+        ScreamingKebabCase::RemoteAdd("foo".to_owned())
+    );
+}
+
+#[test]
+fn screaming_kebab_case_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<ScreamingKebabCase>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "enum ScreamingKebabCase\n\nUSAGE: executable <ScreamingKebabCase>\n\nRequired Arguments:\n  <ScreamingKebabCase>  enum ScreamingKebabCase\n\nOverride Options:\n  -h --help  Display this message.\n\nCommands:\n  SHOW-LOG [options]     \n  REMOTE-ADD <a string>  "
+    );
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<ScreamingKebabCase>(&[
+                "executable",
+                "SHOW-LOG",
+                "--help"
+            ]))
+        ),
+        "USAGE: executable SHOW-LOG [SHOW-LOG options]\n\nSHOW-LOG Options:\n  --OUTPUT-FILE <a string>  \n  --DRY-RUN                 \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
+
+/// A command with renamed fields that also have aliases.
+#[cfg(feature = "macros")]
+#[serde_args::generate(doc_help)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+struct Aliased {
+    /// The file to paint.
+    #[serde(rename = "path", alias = "file", alias = "f")]
+    input: String,
+    /// The color to use.
+    #[serde(rename = "color", alias = "colour", alias = "c")]
+    paint: Option<String>,
+    /// Only display what would be painted.
+    #[serde(alias = "d")]
+    dry_run: bool,
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn renamed_with_aliases() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Aliased>(&["executable", "foo", "--color", "red", "--dry-run"]),
+        Aliased {
+            input: "foo".to_owned(),
+            paint: Some("red".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Aliased>(&["executable", "foo", "--colour", "red", "-d"]),
+        Aliased {
+            input: "foo".to_owned(),
+            paint: Some("red".to_owned()),
+            dry_run: true,
+        }
+    );
+    assert_ok_eq!(
+        serde_args::from_slice::<Aliased>(&["executable", "foo", "-c", "red"]),
+        Aliased {
+            input: "foo".to_owned(),
+            paint: Some("red".to_owned()),
+            dry_run: false,
+        }
+    );
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn renamed_with_aliases_missing_argument() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Aliased>(&["executable", "-d"]))
+        ),
+        "ERROR: missing required positional argument: <path>\n\nUSAGE: executable [options] <path>\n\nFor more information, use --help."
+    );
+}
+
+#[cfg(feature = "macros")]
+#[test]
+fn renamed_with_aliases_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Aliased>(&["executable", "--help"]))
+        ),
+        "A command with renamed fields that also have aliases.\n\nUSAGE: executable [options] <path>\n\nRequired Arguments:\n  <path>  The file to paint.\n\nGlobal Options:\n  -c --color --colour <a string>  The color to use.\n  -d --dry-run                    Only display what would be painted.\n\nOverride Options:\n  -h --help  Display this message."
+    );
+}
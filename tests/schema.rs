@@ -0,0 +1,86 @@
+//! Golden-file tests for the schema format of generated specifications.
+//!
+//! Each test generates the schema of a representative type and compares it against
+//! `tests/schema/<test name>.txt`. See the `golden` module for how to update the golden files.
+
+mod git;
+mod golden;
+
+use claims::assert_ok;
+use golden::assert_golden;
+use serde_args::SpecFormat;
+use serde_derive::Deserialize;
+use std::{
+    fs,
+    path::PathBuf,
+};
+
+/// The example from the README.
+#[derive(Debug, Deserialize)]
+#[serde(expecting = "An example program")]
+#[allow(dead_code)]
+struct Args {
+    path: PathBuf,
+    #[serde(alias = "f")]
+    force: bool,
+}
+
+#[test]
+fn readme_args() {
+    assert_golden(
+        "readme_args",
+        &assert_ok!(serde_args::generate_spec::<Args>(SpecFormat::Schema)),
+    );
+}
+
+#[test]
+fn git_command() {
+    assert_golden(
+        "git_command",
+        &assert_ok!(serde_args::generate_spec::<git::Command>(
+            SpecFormat::Schema
+        )),
+    );
+}
+
+#[test]
+fn git() {
+    assert_golden(
+        "git",
+        &assert_ok!(serde_args::generate_spec::<git::Git>(SpecFormat::Schema)),
+    );
+}
+
+#[test]
+fn stable_across_runs() {
+    assert_eq!(
+        assert_ok!(serde_args::generate_spec::<git::Git>(SpecFormat::Schema)),
+        assert_ok!(serde_args::generate_spec::<git::Git>(SpecFormat::Schema)),
+    );
+}
+
+/// Changing the schema version must be deliberate: the golden files are rewritten along with the
+/// version asserted here.
+#[test]
+fn schema_version() {
+    let fixture = fs::read_to_string(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests")
+            .join("schema")
+            .join("git_command.txt"),
+    )
+    .expect("could not read fixture");
+    let version = "\n  \"schema_version\": 1,\n";
+
+    assert!(
+        fixture.contains(version),
+        "schema version of the fixture has changed"
+    );
+    assert!(
+        assert_ok!(serde_args::generate_spec::<git::Command>(
+            SpecFormat::Schema
+        ))
+        .contains(version),
+        "schema version of the output has changed"
+    );
+}
@@ -0,0 +1,136 @@
+//! Tests for smart pointers, which are parsed exactly as their contained values.
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+use serde_derive::Deserialize;
+use std::{
+    path::PathBuf,
+    rc::Rc,
+    sync::Arc,
+};
+
+#[test]
+fn boxed() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Box<u32>>(&["executable", "42"]),
+        Box::new(42)
+    );
+}
+
+#[test]
+fn arc() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Arc<String>>(&["executable", "foo"]),
+        Arc::new("foo".to_owned())
+    );
+}
+
+#[test]
+fn rc() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Rc<PathBuf>>(&["executable", "foo"]),
+        Rc::new(PathBuf::from("foo"))
+    );
+}
+
+#[test]
+fn boxed_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Box<u32>>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<u32>(&["executable", "--help"]))
+        )
+    );
+}
+
+#[test]
+fn boxed_invalid() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Box<u32>>(&["executable", "foo"]))
+        ),
+        "ERROR: invalid value: expected u32, found 'foo'\n\nUSAGE: executable <u32>\n\nFor more information, use --help."
+    );
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Pointers {
+    count: Box<u32>,
+    name: Arc<String>,
+    path: Rc<PathBuf>,
+    limit: Option<Box<u32>>,
+    force: Box<bool>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Plain {
+    count: u32,
+    name: String,
+    path: PathBuf,
+    limit: Option<u32>,
+    force: bool,
+}
+
+#[test]
+fn fields() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Pointers>(&[
+            "executable",
+            "1",
+            "foo",
+            "bar",
+            "--limit",
+            "2",
+            "--force"
+        ]),
+        Pointers {
+            count: Box::new(1),
+            name: Arc::new("foo".to_owned()),
+            path: Rc::new(PathBuf::from("bar")),
+            limit: Some(Box::new(2)),
+            force: Box::new(true),
+        }
+    );
+}
+
+#[test]
+fn fields_absent() {
+    assert_ok_eq!(
+        serde_args::from_slice::<Pointers>(&["executable", "1", "foo", "bar"]),
+        Pointers {
+            count: Box::new(1),
+            name: Arc::new("foo".to_owned()),
+            path: Rc::new(PathBuf::from("bar")),
+            limit: None,
+            force: Box::new(false),
+        }
+    );
+}
+
+#[test]
+fn fields_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Pointers>(&[
+                "executable",
+                "--help"
+            ]))
+        )
+        .replace("struct Pointers", "struct Plain"),
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<Plain>(&["executable", "--help"]))
+        )
+    );
+}
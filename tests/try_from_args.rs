@@ -0,0 +1,53 @@
+use claims::assert_matches;
+use serde_args::Outcome;
+use serde_derive::Deserialize;
+
+/// Mirror a repository.
+#[serde_args::generate(doc_help, version)]
+#[derive(Debug, Deserialize, Eq, PartialEq)]
+struct Mirror {
+    /// Where to mirror from.
+    source: String,
+    /// Be more verbose.
+    verbose: bool,
+}
+
+#[test]
+fn parsed() {
+    assert_matches!(
+        serde_args::try_from_args::<Mirror, _, _>(["executable", "foo", "--verbose"]),
+        Outcome::Parsed(Mirror { source, verbose: true }) if source == "foo"
+    );
+}
+
+#[test]
+fn help() {
+    assert_matches!(
+        serde_args::try_from_args::<Mirror, _, _>(["executable", "--help"]),
+        Outcome::Help(message) if message == "Mirror a repository.\n\nUSAGE: executable [options] <source>\n\nRequired Arguments:\n  <source>  Where to mirror from.\n\nGlobal Options:\n  --verbose   Be more verbose.\n\nOverride Options:\n  -h --help     Display this message.\n     --version  Display version information."
+    );
+}
+
+#[test]
+fn help_no_arguments() {
+    assert_matches!(
+        serde_args::try_from_args::<Mirror, _, _>(["executable"]),
+        Outcome::Help(message) if message.starts_with("Mirror a repository.\n\nUSAGE: executable [options] <source>")
+    );
+}
+
+#[test]
+fn version() {
+    assert_matches!(
+        serde_args::try_from_args::<Mirror, _, _>(["executable", "--version"]),
+        Outcome::Version(message) if message == format!("executable {}", env!("CARGO_PKG_VERSION"))
+    );
+}
+
+#[test]
+fn error() {
+    assert_matches!(
+        serde_args::try_from_args::<Mirror, _, _>(["executable", "foo", "--force"]),
+        Outcome::Error(error) if error.to_string() == "ERROR: unrecognized optional flag: '--force'\n\nUSAGE: executable [options] <source>\n\nFor more information, use --help."
+    );
+}
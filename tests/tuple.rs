@@ -0,0 +1,102 @@
+//! Tests for tuples and arrays, whose elements are parsed as positional arguments in order.
+
+use claims::{
+    assert_err,
+    assert_ok_eq,
+};
+
+#[test]
+fn tuple() {
+    assert_ok_eq!(
+        serde_args::from_slice::<(String, u32)>(&["executable", "foo", "42"]),
+        ("foo".to_owned(), 42)
+    );
+}
+
+#[test]
+fn array() {
+    assert_ok_eq!(
+        serde_args::from_slice::<[f64; 3]>(&["executable", "1", "2.5", "3"]),
+        [1.0, 2.5, 3.0]
+    );
+}
+
+#[test]
+fn tuple_too_few() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<(String, u32)>(&[
+                "executable",
+                "foo"
+            ]))
+        ),
+        "ERROR: missing required positional argument: <arg2>\n\nUSAGE: executable <arg1> <arg2>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn array_too_few() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<[f64; 3]>(&["executable", "1"]))
+        ),
+        "ERROR: missing required positional arguments: <arg2> <arg3>\n\nUSAGE: executable <arg1> <arg2> <arg3>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn tuple_too_many() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<(String, u32)>(&[
+                "executable",
+                "foo",
+                "42",
+                "bar"
+            ]))
+        ),
+        "ERROR: unexpected argument 'bar': 'executable' takes exactly 2 arguments (<arg1> <arg2>)\n\nUSAGE: executable <arg1> <arg2>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn array_too_many() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<[f64; 3]>(&[
+                "executable",
+                "1",
+                "2",
+                "3",
+                "4"
+            ]))
+        ),
+        "ERROR: unexpected argument '4': 'executable' takes exactly 3 arguments (<arg1> <arg2> <arg3>)\n\nUSAGE: executable <arg1> <arg2> <arg3>\n\nFor more information, use --help."
+    );
+}
+
+#[test]
+fn tuple_string_element_before_last() {
+    assert_ok_eq!(
+        serde_args::from_slice::<(std::net::IpAddr, u16)>(&["executable", "127.0.0.1", "8080"]),
+        ([127, 0, 0, 1].into(), 8080)
+    );
+}
+
+#[test]
+fn tuple_help() {
+    assert_eq!(
+        format!(
+            "{}",
+            assert_err!(serde_args::from_slice::<(String, u32)>(&[
+                "executable",
+                "--help"
+            ]))
+        ),
+        "a tuple of size 2\n\nUSAGE: executable <arg1> <arg2>\n\nRequired Arguments:\n  <arg1>  \n  <arg2>  \n\nOverride Options:\n  -h --help  Display this message."
+    );
+}